@@ -0,0 +1,93 @@
+//! Time [ZarrConventions] construction and lookups,
+//! against a baseline of the `BTreeSet`-backed layout it replaced.
+//!
+//! Run with `cargo bench -p zarrs_conventions`.
+use std::{collections::BTreeSet, hint::black_box, time::Instant};
+
+use serde::Deserialize;
+use serde_json::json;
+use zarrs_conventions::{
+    Attributes, Convention, ConventionId, ZarrConventions, iref::UriBuf, uuid::Uuid,
+};
+
+const ITERATIONS: u32 = 100_000;
+
+fn attributes(n: u128) -> Attributes {
+    let conventions: Vec<_> = (0..n)
+        .map(|i| {
+            json!({
+                "uuid": Uuid::from_u128(i),
+                "schema_url": format!("https://example.com/schemas/{i}.json"),
+                "spec_url": format!("https://example.com/specs/{i}"),
+            })
+        })
+        .collect();
+    let mut attrs = Attributes::default();
+    attrs.insert("zarr_conventions".to_string(), conventions.into());
+    attrs
+}
+
+/// The layout [ZarrConventions] had before being backed by small vec-based sets.
+#[derive(Default)]
+struct BTreeConventions {
+    uuids: BTreeSet<Uuid>,
+    // only built, to time construction
+    #[allow(dead_code)]
+    schema_urls: BTreeSet<UriBuf>,
+    #[allow(dead_code)]
+    spec_urls: BTreeSet<UriBuf>,
+}
+
+impl BTreeConventions {
+    fn from_attributes(attributes: &Attributes) -> Self {
+        let lst = Vec::<Convention>::deserialize(&attributes["zarr_conventions"]).unwrap();
+        lst.into_iter()
+            .fold(BTreeConventions::default(), |mut c, item| {
+                if let Some(uuid) = item.uuid() {
+                    c.uuids.insert(uuid);
+                }
+                if let Some(schema_url) = item.schema_url() {
+                    c.schema_urls.insert(schema_url.to_owned());
+                }
+                if let Some(spec_url) = item.spec_url() {
+                    c.spec_urls.insert(spec_url.to_owned());
+                }
+                c
+            })
+    }
+}
+
+fn time(label: &str, mut f: impl FnMut()) {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    let per_iter = start.elapsed() / ITERATIONS;
+    println!("{label:<40} {per_iter:?}");
+}
+
+fn main() {
+    for n in [1, 3, 5, 20] {
+        let attrs = attributes(n);
+        let present = ConventionId::Uuid(Uuid::from_u128(n - 1));
+        let absent = ConventionId::Uuid(Uuid::from_u128(n));
+
+        time(&format!("construct ZarrConventions (n={n})"), || {
+            black_box(ZarrConventions::from_attributes(black_box(&attrs)).unwrap());
+        });
+        time(&format!("construct BTreeSet (n={n})"), || {
+            black_box(BTreeConventions::from_attributes(black_box(&attrs)));
+        });
+
+        let conventions = ZarrConventions::from_attributes(&attrs).unwrap();
+        let baseline = BTreeConventions::from_attributes(&attrs);
+        time(&format!("lookup ZarrConventions (n={n})"), || {
+            black_box(conventions.contains(black_box(&present)));
+            black_box(conventions.contains(black_box(&absent)));
+        });
+        time(&format!("lookup BTreeSet (n={n})"), || {
+            black_box(baseline.uuids.contains(black_box(&Uuid::from_u128(n - 1))));
+            black_box(baseline.uuids.contains(black_box(&Uuid::from_u128(n))));
+        });
+    }
+}
@@ -0,0 +1,93 @@
+//! Count allocations per document when scanning many metadata documents with [MetadataScanner],
+//! against a baseline of deserializing each document's attributes into a fresh [AttributesParser].
+//!
+//! For documents of the same shape, the scanner reuses the previous document's keys and values,
+//! so only the attribute map's tree nodes are allocated per document.
+//!
+//! Run with `cargo bench -p zarrs_conventions --bench scanner`.
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    hint::black_box,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Instant,
+};
+
+use serde::Deserialize;
+use serde_json::json;
+use zarrs_conventions::{AttributesParser, MetadataScanner, uuid::Uuid};
+
+const DOCUMENTS: usize = 10_000;
+
+/// Counts every allocation, including reallocations.
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// How the scanner parsed documents before filling its parser in place.
+#[derive(Deserialize)]
+struct Baseline {
+    #[serde(default)]
+    attributes: AttributesParser,
+}
+
+fn document(n_fields: usize) -> Vec<u8> {
+    let mut attributes = serde_json::Map::default();
+    attributes.insert(
+        "zarr_conventions".into(),
+        json!([{"uuid": Uuid::from_u128(1), "name": "example"}]),
+    );
+    for i in 0..n_fields {
+        attributes.insert(format!("field_{i}"), json!(i));
+    }
+    serde_json::to_vec(&json!({
+        "zarr_format": 3,
+        "node_type": "group",
+        "attributes": attributes,
+    }))
+    .unwrap()
+}
+
+fn measure(label: &str, mut f: impl FnMut()) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    for _ in 0..DOCUMENTS {
+        f();
+    }
+    let per_doc = start.elapsed() / DOCUMENTS as u32;
+    let allocations = (ALLOCATIONS.load(Ordering::Relaxed) - allocations) as f64 / DOCUMENTS as f64;
+    println!("{label:<40} {allocations:>8.1} allocations {per_doc:>10?}");
+}
+
+fn main() {
+    for n_fields in [1, 10, 50] {
+        let doc = document(n_fields);
+
+        let mut scanner = MetadataScanner::default();
+        measure(&format!("MetadataScanner (fields={n_fields})"), || {
+            black_box(scanner.scan_slice(black_box(&doc)).unwrap());
+        });
+        measure(&format!("fresh parser (fields={n_fields})"), || {
+            let metadata: Baseline = serde_json::from_slice(black_box(&doc)).unwrap();
+            black_box(metadata.attributes);
+        });
+    }
+}
@@ -0,0 +1,309 @@
+//! Arena-backed attributes for bulk processing.
+//!
+//! Batch tools which parse and immediately discard very many metadata documents
+//! can allocate each document's attribute map in a [Bump] arena,
+//! and reset the arena between batches rather than freeing every map, key, and value.
+//!
+//! ```
+//! use zarrs_conventions::arena::{ArenaAttributes, Bump};
+//!
+//! let mut bump = Bump::new();
+//! for doc in [
+//!     r#"{"zarr_format": 3, "node_type": "group", "attributes": {"a": 1}}"#,
+//!     r#"{"zarr_format": 3, "node_type": "group", "attributes": {"a": 2}}"#,
+//! ] {
+//!     let attrs = ArenaAttributes::from_metadata_str(&bump, doc).unwrap();
+//!     let _a: Option<u8> = attrs.get("a").unwrap();
+//! }
+//! bump.reset();
+//! ```
+use std::{cmp::Ordering, fmt};
+
+pub use bumpalo::Bump;
+use bumpalo::collections::Vec as BumpVec;
+use serde::{
+    Deserialize,
+    de::{DeserializeSeed, IgnoredAny, MapAccess, Visitor},
+};
+use serde_json::value::RawValue;
+
+use crate::{LazyView, NestedRepr, PrefixedRepr, ZarrConventionImpl, ZarrConventions};
+
+/// Attributes map allocated in an arena,
+/// whose values are kept as unparsed JSON.
+///
+/// See also [LazyAttributes](crate::LazyAttributes), which uses the global allocator.
+#[derive(Debug)]
+pub struct ArenaAttributes<'bump> {
+    zarr_conventions: ZarrConventions,
+    /// Sorted by key; duplicate keys stay in document order.
+    fields: BumpVec<'bump, (&'bump str, &'bump RawValue)>,
+}
+
+impl<'bump> ArenaAttributes<'bump> {
+    /// Parse the attributes from a full Zarr metadata document.
+    ///
+    /// The document is copied into the arena.
+    pub fn from_metadata_str(bump: &'bump Bump, s: &str) -> serde_json::Result<Self> {
+        let s = bump.alloc_str(s);
+        let mut de = serde_json::Deserializer::from_str(s);
+        let fields = serde::Deserializer::deserialize_map(&mut de, MetadataSeed(bump))?;
+        de.end()?;
+        Self::from_fields(fields.unwrap_or_else(|| BumpVec::new_in(bump)))
+    }
+
+    /// Parse an attributes object.
+    ///
+    /// The document is copied into the arena.
+    pub fn from_attributes_str(bump: &'bump Bump, s: &str) -> serde_json::Result<Self> {
+        let s = bump.alloc_str(s);
+        let mut de = serde_json::Deserializer::from_str(s);
+        let fields = AttributesSeed(bump).deserialize(&mut de)?;
+        de.end()?;
+        Self::from_fields(fields)
+    }
+
+    fn from_fields(
+        mut fields: BumpVec<'bump, (&'bump str, &'bump RawValue)>,
+    ) -> serde_json::Result<Self> {
+        // stable, so that the last of any duplicate keys wins, as in serde_json's maps
+        fields.sort_by_key(|(k, _)| *k);
+        let mut out = Self {
+            zarr_conventions: ZarrConventions::default(),
+            fields,
+        };
+        if let Some(raw) = out.get_raw(ZarrConventions::KEY) {
+            out.zarr_conventions = serde_json::from_str(raw.get())?;
+        }
+        Ok(out)
+    }
+
+    /// Check whether a particular convention is in use.
+    pub fn in_use<T: ZarrConventionImpl>(&self) -> bool {
+        T::in_use(&self.zarr_conventions)
+    }
+
+    /// Get the unparsed value of an attribute.
+    ///
+    /// If the key is duplicated, the last value in the document is used, as in serde_json.
+    pub fn get_raw(&self, key: &str) -> Option<&'bump RawValue> {
+        self.find_raw(|k| k.cmp(key))
+    }
+
+    /// Find the last value whose key is equal to the one sought,
+    /// given how each key is ordered relative to it.
+    fn find_raw(&self, cmp: impl Fn(&str) -> Ordering) -> Option<&'bump RawValue> {
+        let end = self.fields.partition_point(|(k, _)| cmp(k).is_le());
+        match end.checked_sub(1).map(|idx| self.fields[idx]) {
+            Some((k, raw)) if cmp(k).is_eq() => Some(raw),
+            _ => None,
+        }
+    }
+
+    /// Get and deserialize an unstructured attribute.
+    ///
+    /// None if not present.
+    pub fn get<T: Deserialize<'bump>>(&self, key: &str) -> serde_json::Result<Option<T>> {
+        self.get_raw(key)
+            .map(|raw| serde_json::from_str(raw.get()))
+            .transpose()
+    }
+
+    /// Lazily view conventional metadata in nested form.
+    ///
+    /// None if the convention is not listed in "zarr_conventions" or the key is absent.
+    pub fn view_nested<T: NestedRepr>(&self) -> Option<LazyView<'bump, T>> {
+        if !T::in_use(&self.zarr_conventions) {
+            return None;
+        }
+        self.get_raw(T::KEY).map(LazyView::new)
+    }
+
+    /// Deserialize a single field of conventional metadata in prefixed form.
+    ///
+    /// None if the convention is not listed in "zarr_conventions" or the field is absent.
+    pub fn prefixed_field<T: PrefixedRepr, F: Deserialize<'bump>>(
+        &self,
+        name: &str,
+    ) -> serde_json::Result<Option<F>> {
+        if !T::in_use(&self.zarr_conventions) {
+            return Ok(None);
+        }
+        // compared piecewise, so that lookups do not allocate the key in the arena
+        self.find_raw(|k| k.bytes().cmp(T::PREFIX.bytes().chain(name.bytes())))
+            .map(|raw| serde_json::from_str(raw.get()))
+            .transpose()
+    }
+}
+
+/// Finds the attributes of a metadata document.
+struct MetadataSeed<'bump>(&'bump Bump);
+
+impl<'bump> Visitor<'bump> for MetadataSeed<'bump> {
+    type Value = Option<BumpVec<'bump, (&'bump str, &'bump RawValue)>>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a Zarr metadata object")
+    }
+
+    fn visit_map<A: MapAccess<'bump>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut out = None;
+        while let Some(key) = map.next_key_seed(KeySeed(self.0))? {
+            // the last of any duplicate keys wins, as in serde_json's maps
+            if key == "attributes" {
+                out = Some(map.next_value_seed(AttributesSeed(self.0))?);
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+        Ok(out)
+    }
+}
+
+/// Collects an attributes object into the arena.
+struct AttributesSeed<'bump>(&'bump Bump);
+
+impl<'bump> DeserializeSeed<'bump> for AttributesSeed<'bump> {
+    type Value = BumpVec<'bump, (&'bump str, &'bump RawValue)>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'bump>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'bump> Visitor<'bump> for AttributesSeed<'bump> {
+    type Value = BumpVec<'bump, (&'bump str, &'bump RawValue)>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an attributes object")
+    }
+
+    fn visit_map<A: MapAccess<'bump>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut out = BumpVec::with_capacity_in(map.size_hint().unwrap_or(0), self.0);
+        while let Some(key) = map.next_key_seed(KeySeed(self.0))? {
+            out.push((key, map.next_value()?));
+        }
+        Ok(out)
+    }
+}
+
+/// Borrows keys from the document where possible,
+/// and otherwise (e.g. if they contain escapes) copies them into the arena.
+struct KeySeed<'bump>(&'bump Bump);
+
+impl<'bump> DeserializeSeed<'bump> for KeySeed<'bump> {
+    type Value = &'bump str;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'bump>,
+    {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl<'bump> Visitor<'bump> for KeySeed<'bump> {
+    type Value = &'bump str;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a string key")
+    }
+
+    fn visit_borrowed_str<E: serde::de::Error>(self, v: &'bump str) -> Result<Self::Value, E> {
+        Ok(v)
+    }
+
+    fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(self.0.alloc_str(v))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ArenaAttributes, Bump};
+    use crate::mock::{MustBeNested, MustBePrefixed};
+
+    const DOC: &str = r#"{
+        "zarr_format": 3,
+        "node_type": "group",
+        "attributes": {
+            "zarr_conventions": [
+                {"uuid": "11111111-1111-1111-1111-111111111111"},
+                {"uuid": "22222222-2222-2222-2222-222222222222"}
+            ],
+            "must_be_nested": {"a": 1, "b": 2},
+            "must_be_prefixed:x": 3,
+            "must_be_prefixed:y": 4,
+            "esc\u0061ped": true
+        }
+    }"#;
+
+    #[test]
+    fn arena_roundtrip() {
+        let mut bump = Bump::new();
+        for _ in 0..3 {
+            let attrs = ArenaAttributes::from_metadata_str(&bump, DOC).unwrap();
+            let nested = attrs.view_nested::<MustBeNested>().unwrap();
+            assert_eq!(nested.get().unwrap(), MustBeNested { a: 1, b: 2 });
+            let x: u8 = attrs
+                .prefixed_field::<MustBePrefixed, _>("x")
+                .unwrap()
+                .unwrap();
+            assert_eq!(x, 3);
+            let escaped: bool = attrs.get("escaped").unwrap().unwrap();
+            assert!(escaped);
+        }
+        bump.reset();
+    }
+
+    #[test]
+    fn arena_prefixed_lookups_do_not_allocate() {
+        let bump = Bump::new();
+        let attrs = ArenaAttributes::from_metadata_str(&bump, DOC).unwrap();
+        let allocated = (bump.allocated_bytes(), bump.chunk_capacity());
+        for _ in 0..1000 {
+            let y: Option<u8> = attrs.prefixed_field::<MustBePrefixed, _>("y").unwrap();
+            assert_eq!(y, Some(4));
+            let missing: Option<u8> = attrs.prefixed_field::<MustBePrefixed, _>("z").unwrap();
+            assert_eq!(missing, None);
+        }
+        assert_eq!((bump.allocated_bytes(), bump.chunk_capacity()), allocated);
+        let x: Option<u8> = attrs.prefixed_field::<MustBePrefixed, _>("").unwrap();
+        assert_eq!(x, None);
+    }
+
+    #[test]
+    fn arena_no_attributes() {
+        let bump = Bump::new();
+        let attrs = ArenaAttributes::from_metadata_str(
+            &bump,
+            r#"{"zarr_format": 3, "node_type": "array"}"#,
+        )
+        .unwrap();
+        assert!(!attrs.in_use::<MustBeNested>());
+        assert!(attrs.get_raw("anything").is_none());
+    }
+
+    #[test]
+    fn arena_duplicate_keys() {
+        let bump = Bump::new();
+        let doc = r#"{"b": 0, "a": 1, "c": 0, "a": 2, "a": 3, "d": 0}"#;
+        let attrs = ArenaAttributes::from_attributes_str(&bump, doc).unwrap();
+        let expected: serde_json::Map<_, _> = serde_json::from_str(doc).unwrap();
+        assert_eq!(attrs.get::<u8>("a").unwrap(), Some(3));
+        assert_eq!(expected["a"], 3);
+        assert!(attrs.get_raw("aa").is_none());
+    }
+
+    #[test]
+    fn arena_duplicate_attributes() {
+        let bump = Bump::new();
+        let doc = r#"{"attributes": {"a": 1, "b": 1}, "zarr_format": 3, "attributes": {"a": 2}}"#;
+        let attrs = ArenaAttributes::from_metadata_str(&bump, doc).unwrap();
+        assert_eq!(attrs.get::<u8>("a").unwrap(), Some(2));
+        assert!(attrs.get_raw("b").is_none());
+    }
+}
@@ -1,11 +1,15 @@
 use std::collections::BTreeSet;
 
-use serde::Deserialize;
+use serde::{
+    Deserialize, Deserializer,
+    de::{DeserializeSeed, MapAccess, Visitor},
+};
 
 use crate::{
     Attributes, NestedOrPrefixedRepr, NestedRepr, PrefixedRepr, ZarrConventionImpl,
     ZarrConventions,
     convention::{ConventionBuilder, ConventionDefinition},
+    scanner::{KeyInto, ValueInPlace},
 };
 
 /// Type for building zarr attributes,
@@ -36,6 +40,17 @@ impl Default for AttributesBuilder {
 }
 
 impl AttributesBuilder {
+    /// Start from an existing attributes map, e.g. to annotate an existing node.
+    ///
+    /// Conventions already declared in the map are kept,
+    /// and added conventions are appended to them unless already declared.
+    pub fn from_attributes(attributes: Attributes) -> Self {
+        Self {
+            attributes,
+            ..Default::default()
+        }
+    }
+
     /// Whether to include the conventions' UUID.
     pub fn uuid(&mut self, enable: bool) -> &mut Self {
         self.uuid = enable;
@@ -113,31 +128,42 @@ impl AttributesBuilder {
         }
 
         if !self.convention_definitions.is_empty() {
-            let res: serde_json::Result<Vec<serde_json::Value>> = self
-                .convention_definitions
-                .into_iter()
-                .map(|d| {
-                    let mut cb = ConventionBuilder::default();
-                    if self.uuid {
-                        cb = cb.uuid(d.uuid);
-                    }
-                    if self.schema_url {
-                        cb = cb.schema_url(d.schema_url.to_owned());
-                    }
-                    if self.spec_url {
-                        cb = cb.spec_url(d.spec_url.to_owned());
-                    }
-                    if self.name {
-                        cb = cb.name(d.name);
-                    }
-                    if self.description {
-                        cb = cb.description(d.description);
-                    }
-                    let c = cb.build().expect("convention definition should build");
-                    serde_json::to_value(c)
-                })
-                .collect();
-            let conventions = res?;
+            let existing = self.attributes.remove(ZarrConventions::KEY);
+            let declared = existing
+                .as_ref()
+                .map(ZarrConventions::deserialize)
+                .transpose()?
+                .unwrap_or_default();
+            let mut conventions = match existing {
+                Some(serde_json::Value::Array(v)) => v,
+                _ => Vec::default(),
+            };
+            for d in self.convention_definitions {
+                if declared.contains(d.uuid)
+                    || declared.contains(d.id_schema_ref())
+                    || declared.contains(d.id_spec_ref())
+                {
+                    continue;
+                }
+                let mut cb = ConventionBuilder::default();
+                if self.uuid {
+                    cb = cb.uuid(d.uuid);
+                }
+                if self.schema_url {
+                    cb = cb.schema_url(d.schema_url.to_owned());
+                }
+                if self.spec_url {
+                    cb = cb.spec_url(d.spec_url.to_owned());
+                }
+                if self.name {
+                    cb = cb.name(d.name);
+                }
+                if self.description {
+                    cb = cb.description(d.description);
+                }
+                let c = cb.build().expect("convention definition should build");
+                conventions.push(serde_json::to_value(c)?);
+            }
 
             self.attributes.insert(
                 ZarrConventions::KEY.to_string(),
@@ -150,7 +176,7 @@ impl AttributesBuilder {
 }
 
 /// Retrieve conventional and unstructured metadata from an attributes map.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize)]
 pub struct AttributesParser {
     #[serde(default)]
     zarr_conventions: ZarrConventions,
@@ -158,7 +184,68 @@ pub struct AttributesParser {
     fields: Attributes,
 }
 
+/// Deserializes an attributes object into an existing [AttributesParser],
+/// rather than buffering the flattened fields.
+///
+/// Fields whose keys were also in the parser's previous attributes are deserialized into
+/// the previous keys and values in place; see [ValueInPlace].
+pub(crate) struct InPlace<'a> {
+    pub(crate) parser: &'a mut AttributesParser,
+    /// Scratch space for keys, to look up previous fields without allocating.
+    pub(crate) key: &'a mut String,
+}
+
+impl<'de> DeserializeSeed<'de> for InPlace<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for InPlace<'_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("an attributes object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        let Self { parser, key } = self;
+        parser.zarr_conventions.refill(Vec::default());
+        let mut previous = std::mem::take(&mut parser.fields);
+        while map.next_key_seed(KeyInto(&mut *key))?.is_some() {
+            if key == ZarrConventions::KEY {
+                parser.zarr_conventions.refill(map.next_value()?);
+                continue;
+            }
+            match previous.remove_entry(key.as_str()) {
+                Some((k, mut value)) => {
+                    map.next_value_seed(ValueInPlace {
+                        value: &mut value,
+                        key: &mut *key,
+                    })?;
+                    parser.fields.insert(k, value);
+                }
+                None => {
+                    let k = key.clone();
+                    parser.fields.insert(k, map.next_value()?);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
 impl AttributesParser {
+    /// Empty the parser.
+    ///
+    /// The sets of convention ids keep their capacity, but the fields are freed.
+    pub(crate) fn clear(&mut self) {
+        self.zarr_conventions.refill(Vec::default());
+        self.fields.clear();
+    }
+
     /// Check whether a particular convention is in use.
     pub fn in_use<T: ZarrConventionImpl>(&self) -> bool {
         T::in_use(&self.zarr_conventions)
@@ -199,10 +286,10 @@ impl AttributesParser {
     ///
     /// None if not present.
     pub fn get<T: serde::de::DeserializeOwned>(&self, key: &str) -> serde_json::Result<Option<T>> {
-        let Some(v) = self.fields.get(key).cloned() else {
+        let Some(v) = self.fields.get(key) else {
             return Ok(None);
         };
-        serde_json::from_value(v).map(Some)
+        T::deserialize(v).map(Some)
     }
 }
 
@@ -210,7 +297,7 @@ impl AttributesParser {
 mod tests {
     use crate::{
         AttributesBuilder,
-        tests::{CanBeEither, MustBeNested, MustBePrefixed},
+        mock::{CanBeEither, MustBeNested, MustBePrefixed},
     };
 
     fn example() -> serde_json::Value {
@@ -300,4 +387,34 @@ mod tests {
         let other: String = parser.get("other_key").unwrap().unwrap();
         assert_eq!(other, "other_value");
     }
+
+    #[test]
+    fn test_builder_from_attributes_merges() {
+        let existing = serde_json::json!({
+            "zarr_conventions": [
+                {"uuid": "f0000000-0000-0000-0000-000000000000"},
+                {"spec_url": "https://example.com/specs/must_be_nested"}
+            ],
+            "other_key": "other_value"
+        });
+        let serde_json::Value::Object(existing) = existing else {
+            unreachable!()
+        };
+        let mut builder = AttributesBuilder::from_attributes(existing);
+        builder
+            .add_nested(&MustBeNested { a: 1, b: 2 })
+            .unwrap()
+            .add_prefixed(&MustBePrefixed { x: 3, y: 4 })
+            .unwrap();
+        let attrs = builder.build().unwrap();
+        let conventions = attrs["zarr_conventions"].as_array().unwrap();
+        assert_eq!(conventions.len(), 3);
+        assert_eq!(
+            conventions[0]["uuid"],
+            "f0000000-0000-0000-0000-000000000000"
+        );
+        assert_eq!(conventions[2]["name"], "must_be_prefixed");
+        assert_eq!(attrs["other_key"], "other_value");
+        assert_eq!(attrs["must_be_nested"]["a"], 1);
+    }
 }
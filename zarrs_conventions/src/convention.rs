@@ -2,7 +2,7 @@ use iref::{Uri, UriBuf};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::ConventionId;
+use crate::{ConventionId, ConventionIdRef};
 
 /// Statically-defined definition of a zarr convention.
 #[derive(Debug, Clone, Copy, Serialize, PartialEq, PartialOrd, Eq, Ord)]
@@ -24,6 +24,14 @@ impl ConventionDefinition {
     pub fn id_spec(&self) -> ConventionId {
         ConventionId::SpecUrl(self.spec_url.to_owned())
     }
+    /// Borrowed schema URL identifier; does not allocate.
+    pub fn id_schema_ref(&self) -> ConventionIdRef<'static> {
+        ConventionIdRef::SchemaUrl(self.schema_url)
+    }
+    /// Borrowed specification URL identifier; does not allocate.
+    pub fn id_spec_ref(&self) -> ConventionIdRef<'static> {
+        ConventionIdRef::SpecUrl(self.spec_url)
+    }
 }
 
 impl From<ConventionDefinition> for Convention {
@@ -77,6 +85,44 @@ impl Convention {
             unreachable!("Convention must have at least one identifier");
         }
     }
+
+    /// The convention's UUID, if given.
+    pub fn uuid(&self) -> Option<Uuid> {
+        self.uuid
+    }
+
+    /// The convention's schema URL, if given.
+    pub fn schema_url(&self) -> Option<&Uri> {
+        self.schema_url.as_deref()
+    }
+
+    /// The convention's specification URL, if given.
+    pub fn spec_url(&self) -> Option<&Uri> {
+        self.spec_url.as_deref()
+    }
+
+    /// The convention's name, if given.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// The convention's description, if given.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Borrowed form of [Convention::id]; does not allocate.
+    pub fn id_ref(&self) -> ConventionIdRef<'_> {
+        if let Some(uuid) = self.uuid {
+            ConventionIdRef::Uuid(uuid)
+        } else if let Some(ref url) = self.schema_url {
+            ConventionIdRef::SchemaUrl(url.as_ref())
+        } else if let Some(ref url) = self.spec_url {
+            ConventionIdRef::SpecUrl(url.as_ref())
+        } else {
+            unreachable!("Convention must have at least one identifier");
+        }
+    }
 }
 
 /// Builder for convention data;
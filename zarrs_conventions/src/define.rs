@@ -0,0 +1,273 @@
+/// Define a convention type, its trait implementations, and its registration in one go.
+///
+/// Takes a braced or tuple struct definition followed by the convention's identifiers,
+/// and optionally the key for the nested representation and/or the prefix for the prefixed representation.
+/// URLs and UUIDs are checked at compile time.
+/// If `uuid` is omitted, it is derived from `spec_url` with [convention_uuid!].
+///
+/// In place of `prefix`, `prefix_from_name` uses the convention's name followed by `:`.
+/// A trailing `rename_all = "..."` is applied to the struct as `#[serde(rename_all = "...")]`,
+/// so the serialized field names (and therefore prefixed keys) follow a single policy.
+///
+/// The convention is added to the [default registry](crate::DEFAULT_ZARR_CONVENTION_REGISTRY)
+/// when the program starts, unless the last argument is `register = false`,
+/// e.g. for conventions whose identifiers are not yet published;
+/// users can then opt in with [ConventionRegistry::register](crate::registry::ConventionRegistry::register).
+///
+/// The struct must implement [serde::Serialize] and [serde::Deserialize]
+/// (e.g. by deriving them, as below).
+/// The calling crate must depend on `iref` for the URL literals.
+///
+/// ```
+/// use zarrs_conventions::{NestedRepr, PrefixedRepr, ZarrConventionImpl, define_convention};
+///
+/// define_convention! {
+///     /// Example convention.
+///     #[derive(Debug, serde::Serialize, serde::Deserialize)]
+///     pub struct Example {
+///         pub code: String,
+///     }
+///     uuid = "dddddddd-dddd-dddd-dddd-dddddddddddd",
+///     schema_url = "https://example.com/schemas/example.json",
+///     spec_url = "https://example.com/specs/example",
+///     name = "example",
+///     description = "An example convention.",
+///     nested = "example",
+///     prefix = "example:",
+/// }
+///
+/// assert_eq!(Example::DEFINITION.name, "example");
+/// assert_eq!(Example::KEY, "example");
+/// assert_eq!(Example::PREFIX, "example:");
+/// ```
+#[macro_export]
+macro_rules! define_convention {
+    (
+        @impl ($uuid:expr) [$($meta:tt)*] [$($item:tt)*] $name:ident
+        schema_url = $schema_url:literal,
+        spec_url = $spec_url:literal,
+        name = $conv_name:literal,
+        description = $description:literal
+        $(, nested = $key:literal)?
+        , prefix_from_name
+        $(, rename_all = $rename_all:literal)?
+        $(, register = $register:tt)?
+        $(,)?
+    ) => {
+        $crate::define_convention! {
+            @impl ($uuid) [$($meta)*] [$($item)*] $name
+            schema_url = $schema_url,
+            spec_url = $spec_url,
+            name = $conv_name,
+            description = $description
+            $(, nested = $key)?
+            , prefix = ::core::concat!($conv_name, ":")
+            $(, rename_all = $rename_all)?
+            $(, register = $register)?
+        }
+    };
+    (
+        @impl ($uuid:expr) [$($meta:tt)*] [$($item:tt)*] $name:ident
+        schema_url = $schema_url:literal,
+        spec_url = $spec_url:literal,
+        name = $conv_name:literal,
+        description = $description:literal
+        $(, nested = $key:literal)?
+        $(, prefix = $prefix:expr)?
+        $(, rename_all = $rename_all:literal)?
+        $(, register = $register:tt)?
+        $(,)?
+    ) => {
+        $($meta)*
+        $(#[serde(rename_all = $rename_all)])?
+        $($item)*
+
+        impl $crate::ZarrConventionImpl for $name {
+            const DEFINITION: $crate::ConventionDefinition = $crate::ConventionDefinition {
+                uuid: $uuid,
+                schema_url: $crate::iref::uri!($schema_url),
+                spec_url: $crate::iref::uri!($spec_url),
+                name: $conv_name,
+                description: $description,
+            };
+        }
+
+        $(
+            impl $crate::NestedRepr for $name {
+                const KEY: &'static str = $key;
+            }
+        )?
+
+        $(
+            impl $crate::PrefixedRepr for $name {
+                const PREFIX: &'static str = $prefix;
+            }
+        )?
+
+        $crate::define_convention!(@register $name $(, $register)?);
+    };
+    (@register $name:ident $(, true)?) => {
+        $crate::register_zarr_conventions!($name);
+    };
+    (@register $name:ident, false) => {};
+    (
+        @struct [$($meta:tt)*] [$($item:tt)*] $name:ident
+        uuid = $uuid:literal,
+        schema_url = $schema_url:literal,
+        $($rest:tt)*
+    ) => {
+        $crate::define_convention! {
+            @impl ($crate::uuid::uuid!($uuid)) [$($meta)*] [$($item)*] $name
+            schema_url = $schema_url,
+            $($rest)*
+        }
+    };
+    (
+        @struct [$($meta:tt)*] [$($item:tt)*] $name:ident
+        schema_url = $schema_url:literal,
+        spec_url = $spec_url:literal,
+        $($rest:tt)*
+    ) => {
+        $crate::define_convention! {
+            @impl ($crate::convention_uuid!($spec_url)) [$($meta)*] [$($item)*] $name
+            schema_url = $schema_url,
+            spec_url = $spec_url,
+            $($rest)*
+        }
+    };
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident { $($body:tt)* }
+        $($rest:tt)*
+    ) => {
+        $crate::define_convention! {
+            @struct [$(#[$meta])*] [$vis struct $name { $($body)* }] $name
+            $($rest)*
+        }
+    };
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident ( $($body:tt)* );
+        $($rest:tt)*
+    ) => {
+        $crate::define_convention! {
+            @struct [$(#[$meta])*] [$vis struct $name ( $($body)* );] $name
+            $($rest)*
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        AttributesBuilder, AttributesParser, DEFAULT_ZARR_CONVENTION_REGISTRY, NestedRepr,
+        PrefixedRepr, ZarrConventionImpl,
+    };
+
+    define_convention! {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Defined {
+            value: u8,
+        }
+        uuid = "44444444-4444-4444-4444-444444444444",
+        schema_url = "https://example.com/schemas/defined.json",
+        spec_url = "https://example.com/specs/defined",
+        name = "defined",
+        description = "A convention defined by macro.",
+        nested = "defined",
+    }
+
+    define_convention! {
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct AlsoDefined {}
+        uuid = "55555555-5555-5555-5555-555555555555",
+        schema_url = "https://example.com/schemas/also_defined.json",
+        spec_url = "https://example.com/specs/also_defined",
+        name = "also_defined",
+        description = "Another convention defined by macro in the same module.",
+    }
+
+    define_convention! {
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct DerivedUuid {}
+        schema_url = "https://example.com/schemas/derived_uuid.json",
+        spec_url = "https://example.com/specs/derived_uuid",
+        name = "derived_uuid",
+        description = "A convention whose UUID is derived from its specification URL.",
+    }
+
+    define_convention! {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct NamedPrefix {
+            some_value: u8,
+        }
+        schema_url = "https://example.com/schemas/named_prefix.json",
+        spec_url = "https://example.com/specs/named_prefix",
+        name = "named_prefix",
+        description = "A convention whose prefix is derived from its name.",
+        prefix_from_name,
+        rename_all = "camelCase",
+    }
+
+    define_convention! {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        #[serde(transparent)]
+        struct Unregistered(Vec<u8>);
+        schema_url = "https://example.com/schemas/unregistered.json",
+        spec_url = "https://example.com/specs/unregistered",
+        name = "unregistered",
+        description = "A tuple-struct convention which is not registered by default.",
+        nested = "unregistered",
+        register = false,
+    }
+
+    #[test]
+    fn defined_unregistered_tuple() {
+        assert!(!DEFAULT_ZARR_CONVENTION_REGISTRY.contains(Unregistered::DEFINITION.uuid));
+        let mut builder = AttributesBuilder::default();
+        builder.add_nested(&Unregistered(vec![1, 2])).unwrap();
+        let attrs = builder.build().unwrap();
+        assert_eq!(attrs["unregistered"], serde_json::json!([1, 2]));
+    }
+
+    #[test]
+    fn defined_prefix_from_name() {
+        assert_eq!(NamedPrefix::PREFIX, "named_prefix:");
+        let mut builder = AttributesBuilder::default();
+        builder
+            .add_prefixed(&NamedPrefix { some_value: 1 })
+            .unwrap();
+        let attrs = builder.build().unwrap();
+        assert_eq!(attrs["named_prefix:someValue"], 1);
+        let parser: AttributesParser = serde_json::from_value(attrs).unwrap();
+        assert_eq!(
+            parser.parse_prefixed::<NamedPrefix>().unwrap(),
+            Some(NamedPrefix { some_value: 1 })
+        );
+    }
+
+    #[test]
+    fn defined_registered() {
+        assert!(DEFAULT_ZARR_CONVENTION_REGISTRY.contains(Defined::DEFINITION.uuid));
+        assert!(DEFAULT_ZARR_CONVENTION_REGISTRY.contains(AlsoDefined::DEFINITION.uuid));
+    }
+
+    #[test]
+    fn defined_derived_uuid() {
+        assert_eq!(
+            DerivedUuid::DEFINITION.uuid,
+            crate::uuid5::convention_uuid("https://example.com/specs/derived_uuid")
+        );
+        assert!(DEFAULT_ZARR_CONVENTION_REGISTRY.contains(DerivedUuid::DEFINITION.uuid));
+    }
+
+    #[test]
+    fn defined_roundtrip() {
+        assert_eq!(Defined::KEY, "defined");
+        let mut builder = AttributesBuilder::default();
+        builder.add_nested(&Defined { value: 3 }).unwrap();
+        let parser: AttributesParser = serde_json::from_value(builder.build().unwrap()).unwrap();
+        let parsed: Defined = parser.parse_nested().unwrap().unwrap();
+        assert_eq!(parsed, Defined { value: 3 });
+    }
+}
@@ -0,0 +1,206 @@
+//! Convention-aware comparison of attributes.
+//!
+//! Declarations in `zarr_conventions` are compared by identity rather than position,
+//! and all other attributes are compared field by field.
+//!
+//! ```
+//! use zarrs_conventions::diff::{Change, diff_attributes};
+//!
+//! let a = serde_json::json!({"license": {"spdx": "MIT"}});
+//! let b = serde_json::json!({"license": {"spdx": "Apache-2.0"}});
+//! let diff = diff_attributes(a.as_object().unwrap(), b.as_object().unwrap()).unwrap();
+//! assert_eq!(
+//!     diff.changes,
+//!     [Change::Changed {
+//!         path: "/license/spdx".into(),
+//!         old: "MIT".into(),
+//!         new: "Apache-2.0".into(),
+//!     }]
+//! );
+//! ```
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{Attributes, Convention, ZarrConventions};
+
+/// Change to a single value, addressed by JSON pointer into the attributes.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum Change {
+    Added {
+        path: String,
+        new: Value,
+    },
+    Removed {
+        path: String,
+        old: Value,
+    },
+    Changed {
+        path: String,
+        old: Value,
+        new: Value,
+    },
+}
+
+impl Change {
+    /// JSON pointer to the changed value.
+    pub fn path(&self) -> &str {
+        match self {
+            Self::Added { path, .. } | Self::Removed { path, .. } | Self::Changed { path, .. } => {
+                path
+            }
+        }
+    }
+}
+
+/// Differences between two attributes maps.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct AttributesDiff {
+    /// Conventions declared only in the new attributes.
+    pub declared: Vec<Convention>,
+    /// Conventions declared only in the old attributes.
+    pub undeclared: Vec<Convention>,
+    /// Changes to all other attributes.
+    pub changes: Vec<Change>,
+}
+
+impl AttributesDiff {
+    /// Whether the attributes are equivalent.
+    pub fn is_empty(&self) -> bool {
+        self.declared.is_empty() && self.undeclared.is_empty() && self.changes.is_empty()
+    }
+}
+
+/// Whether two declarations share any identifier.
+fn same_convention(a: &Convention, b: &Convention) -> bool {
+    (a.uuid.is_some() && a.uuid == b.uuid)
+        || (a.schema_url.is_some() && a.schema_url == b.schema_url)
+        || (a.spec_url.is_some() && a.spec_url == b.spec_url)
+}
+
+fn declarations(attributes: &Attributes) -> serde_json::Result<Vec<Convention>> {
+    attributes
+        .get(ZarrConventions::KEY)
+        .map(Vec::<Convention>::deserialize)
+        .transpose()
+        .map(Option::unwrap_or_default)
+}
+
+/// Compare two attributes maps.
+///
+/// Fails if either has an invalid `zarr_conventions` list.
+pub fn diff_attributes(old: &Attributes, new: &Attributes) -> serde_json::Result<AttributesDiff> {
+    let old_decl = declarations(old)?;
+    let new_decl = declarations(new)?;
+    let mut out = AttributesDiff {
+        declared: new_decl
+            .iter()
+            .filter(|n| !old_decl.iter().any(|o| same_convention(o, n)))
+            .cloned()
+            .collect(),
+        undeclared: old_decl
+            .iter()
+            .filter(|o| !new_decl.iter().any(|n| same_convention(o, n)))
+            .cloned()
+            .collect(),
+        changes: Vec::default(),
+    };
+    diff_objects("", old, new, &mut out.changes);
+    out.changes
+        .retain(|c| c.path() != format!("/{}", ZarrConventions::KEY));
+    Ok(out)
+}
+
+/// Escape a key for use in a JSON pointer.
+fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+fn diff_objects(prefix: &str, old: &Attributes, new: &Attributes, out: &mut Vec<Change>) {
+    for (k, o) in old {
+        let path = format!("{prefix}/{}", escape(k));
+        match new.get(k) {
+            Some(n) => diff_values(path, o, n, out),
+            None => out.push(Change::Removed {
+                path,
+                old: o.clone(),
+            }),
+        }
+    }
+    for (k, n) in new {
+        if !old.contains_key(k) {
+            out.push(Change::Added {
+                path: format!("{prefix}/{}", escape(k)),
+                new: n.clone(),
+            });
+        }
+    }
+}
+
+fn diff_values(path: String, old: &Value, new: &Value, out: &mut Vec<Change>) {
+    match (old, new) {
+        (Value::Object(o), Value::Object(n)) => diff_objects(&path, o, n, out),
+        (o, n) if o != n => out.push(Change::Changed {
+            path,
+            old: o.clone(),
+            new: n.clone(),
+        }),
+        _ => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{Change, diff_attributes};
+
+    #[test]
+    fn diff_declarations_by_identity() {
+        let old = json!({
+            "zarr_conventions": [
+                {"uuid": "11111111-1111-1111-1111-111111111111"},
+                {"uuid": "22222222-2222-2222-2222-222222222222"}
+            ],
+            "a/b": {"x": 1, "y": [1]},
+            "gone": true
+        });
+        let new = json!({
+            "zarr_conventions": [
+                {"uuid": "33333333-3333-3333-3333-333333333333"},
+                {
+                    "uuid": "11111111-1111-1111-1111-111111111111",
+                    "name": "must_be_nested"
+                }
+            ],
+            "a/b": {"x": 1, "y": [2], "z": null}
+        });
+        let diff = diff_attributes(old.as_object().unwrap(), new.as_object().unwrap()).unwrap();
+        assert_eq!(diff.declared.len(), 1);
+        assert_eq!(diff.undeclared.len(), 1);
+        assert_eq!(
+            diff.changes,
+            [
+                Change::Changed {
+                    path: "/a~1b/y".into(),
+                    old: json!([1]),
+                    new: json!([2])
+                },
+                Change::Added {
+                    path: "/a~1b/z".into(),
+                    new: json!(null)
+                },
+                Change::Removed {
+                    path: "/gone".into(),
+                    old: json!(true)
+                },
+            ]
+        );
+        assert!(!diff.is_empty());
+        assert!(
+            diff_attributes(old.as_object().unwrap(), old.as_object().unwrap())
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
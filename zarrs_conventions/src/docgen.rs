@@ -0,0 +1,505 @@
+//! Generate a convention's JSON schema and a Markdown specification stub from its Rust type.
+//!
+//! The payload schema comes from [schemars::JsonSchema],
+//! so doc comments on the type and its fields become descriptions in both outputs.
+//!
+//! Convention crates can keep their `spec/` directory in lockstep with the implementation
+//! by writing and checking in the output, e.g. from a test:
+//!
+//! ```ignore
+//! ConventionDocs::nested::<MyConvention>().write_to("spec")?;
+//! ```
+use std::{fmt::Write as _, path::Path};
+
+use schemars::JsonSchema;
+use serde_json::{Map, Value, json};
+
+use crate::{ConventionDefinition, NestedRepr, PrefixedRepr, ZarrConventionImpl};
+
+/// JSON schema dialect of the generated schemas.
+pub const SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Schema and documentation for a convention, derived from its Rust type.
+#[derive(Debug, Clone)]
+pub struct ConventionDocs {
+    definition: ConventionDefinition,
+    key: Option<&'static str>,
+    prefix: Option<&'static str>,
+    payload: Map<String, Value>,
+    defs: Option<Value>,
+}
+
+impl ConventionDocs {
+    fn new<T: ZarrConventionImpl + JsonSchema>(
+        key: Option<&'static str>,
+        prefix: Option<&'static str>,
+    ) -> Self {
+        let mut root = schemars::schema_for!(T).to_value();
+        strip_examples(&mut root);
+        // fields are listed alphabetically even if serde_json's `preserve_order` is enabled
+        root.sort_all_objects();
+        let Value::Object(mut payload) = root else {
+            unreachable!("generated root schema is an object");
+        };
+        payload.remove("$schema");
+        let defs = payload.remove("$defs");
+        Self {
+            definition: T::DEFINITION,
+            key,
+            prefix,
+            payload,
+            defs,
+        }
+    }
+
+    /// Document a convention with a nested representation.
+    pub fn nested<T: NestedRepr + JsonSchema>() -> Self {
+        Self::new::<T>(Some(T::KEY), None)
+    }
+
+    /// Document a convention with a prefixed representation.
+    pub fn prefixed<T: PrefixedRepr + JsonSchema>() -> Self {
+        Self::new::<T>(None, Some(T::PREFIX))
+    }
+
+    /// Document a convention with both nested and prefixed representations.
+    pub fn either<T: NestedRepr + PrefixedRepr + JsonSchema>() -> Self {
+        Self::new::<T>(Some(T::KEY), Some(T::PREFIX))
+    }
+
+    /// Definition of the documented convention.
+    pub fn definition(&self) -> &ConventionDefinition {
+        &self.definition
+    }
+
+    fn properties(&self) -> Vec<(&str, &Value)> {
+        properties(&self.payload)
+    }
+
+    fn required(&self) -> Vec<&str> {
+        required(&self.payload)
+    }
+
+    /// Schema of the items of an array payload, resolving a reference to the definitions.
+    fn items(&self) -> Option<&Map<String, Value>> {
+        if self.payload.get("type").and_then(Value::as_str) != Some("array") {
+            return None;
+        }
+        let items = self.payload.get("items")?;
+        let items = match items.get("$ref").and_then(Value::as_str) {
+            Some(r) => self.defs.as_ref()?.get(r.strip_prefix("#/$defs/")?)?,
+            None => items,
+        };
+        items.as_object()
+    }
+
+    /// JSON schema for an attributes object in which the convention is used.
+    pub fn schema(&self) -> Value {
+        let def = &self.definition;
+        let mut properties = Map::default();
+        properties.insert(
+            "zarr_conventions".into(),
+            json!({
+                "type": "array",
+                "contains": {
+                    "type": "object",
+                    "anyOf": [
+                        {"properties": {"uuid": {"const": def.uuid}}, "required": ["uuid"]},
+                        {
+                            "properties": {"schema_url": {"const": def.schema_url.as_str()}},
+                            "required": ["schema_url"],
+                        },
+                        {
+                            "properties": {"spec_url": {"const": def.spec_url.as_str()}},
+                            "required": ["spec_url"],
+                        },
+                    ],
+                },
+            }),
+        );
+        let mut required = vec![Value::from("zarr_conventions")];
+        let mut all_of = Vec::default();
+        if let Some(key) = self.key {
+            let mut payload = self.payload.clone();
+            if self.prefix.is_some() {
+                // required fields may be given by prefixed keys instead; see below
+                payload.remove("required");
+            } else {
+                required.push(key.into());
+            }
+            properties.insert(key.into(), Value::Object(payload));
+        }
+        if let Some(prefix) = self.prefix {
+            for (name, schema) in self.properties() {
+                properties.insert(format!("{prefix}{name}"), schema.clone());
+            }
+            for name in self.required() {
+                let prefixed = format!("{prefix}{name}");
+                match self.key {
+                    // as in NestedOrPrefixedRepr::from_attributes,
+                    // each field may be nested or prefixed independently
+                    Some(key) => all_of.push(json!({"anyOf": [
+                        {"required": [prefixed]},
+                        {"required": [key], "properties": {key: {"required": [name]}}},
+                    ]})),
+                    None => required.push(prefixed.into()),
+                }
+            }
+        }
+
+        let mut out = json!({
+            "$schema": SCHEMA_DIALECT,
+            "$id": def.schema_url.as_str(),
+            "title": def.name,
+            "description": def.description,
+            "type": "object",
+            "properties": properties,
+            "required": required,
+        });
+        if !all_of.is_empty() {
+            out["allOf"] = Value::Array(all_of);
+        }
+        if let Some(defs) = &self.defs {
+            out["$defs"] = defs.clone();
+        }
+        out
+    }
+
+    /// Markdown specification stub, with a table of the payload's fields.
+    pub fn markdown(&self) -> String {
+        let def = &self.definition;
+        let mut out = String::default();
+        writeln!(out, "# {}\n", def.name).unwrap();
+        writeln!(out, "{}\n", def.description).unwrap();
+        writeln!(out, "- UUID: `{}`", def.uuid).unwrap();
+        writeln!(out, "- Schema: <{}>", def.schema_url.as_str()).unwrap();
+        writeln!(out, "- Specification: <{}>\n", def.spec_url.as_str()).unwrap();
+
+        writeln!(out, "## Representation\n").unwrap();
+        let items = self.items();
+        if let Some(key) = self.key {
+            match items {
+                Some(_) => writeln!(
+                    out,
+                    "- Nested: an array under the `{key}` key, of objects with the fields below"
+                ),
+                None => writeln!(out, "- Nested: an object under the `{key}` key"),
+            }
+            .unwrap();
+        }
+        if let Some(prefix) = self.prefix {
+            writeln!(
+                out,
+                "- Prefixed: each field as a key prefixed with `{prefix}`"
+            )
+            .unwrap();
+        }
+        if let Some(Value::String(description)) = self.payload.get("description") {
+            writeln!(out, "\n{description}").unwrap();
+        }
+
+        writeln!(out, "\n## Fields\n").unwrap();
+        writeln!(out, "| Field | Type | Required | Description |").unwrap();
+        writeln!(out, "| --- | --- | --- | --- |").unwrap();
+        let fields = items.unwrap_or(&self.payload);
+        let required = required(fields);
+        for (name, schema) in properties(fields) {
+            let description = schema
+                .get("description")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .replace('\n', " ");
+            writeln!(
+                out,
+                "| `{name}` | {} | {} | {description} |",
+                type_name(schema),
+                if required.contains(&name) {
+                    "yes"
+                } else {
+                    "no"
+                },
+            )
+            .unwrap();
+        }
+        out
+    }
+
+    /// Contents of `schema.json` and `README.md`, by file name.
+    pub fn files(&self) -> [(&'static str, String); 2] {
+        let mut schema = self.schema();
+        schema.sort_all_objects();
+        let mut schema = serde_json::to_string_pretty(&schema).expect("schema should serialize");
+        schema.push('\n');
+        [("schema.json", schema), ("README.md", self.markdown())]
+    }
+
+    /// Write `schema.json` and `README.md` into the given directory.
+    pub fn write_to(&self, dir: impl AsRef<Path>) -> std::io::Result<()> {
+        let dir = dir.as_ref();
+        for (name, contents) in self.files() {
+            std::fs::write(dir.join(name), contents)?;
+        }
+        Ok(())
+    }
+}
+
+fn properties(schema: &Map<String, Value>) -> Vec<(&str, &Value)> {
+    schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .map(|props| props.iter().map(|(k, v)| (k.as_str(), v)).collect())
+        .unwrap_or_default()
+}
+
+fn required(schema: &Map<String, Value>) -> Vec<&str> {
+    schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|req| req.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default()
+}
+
+/// Remove fenced code blocks, i.e. Rust examples, from a doc comment.
+fn without_code_blocks(description: &str) -> String {
+    let mut out: Vec<&str> = Vec::default();
+    let mut in_block = false;
+    for line in description.lines() {
+        if line.trim_start().starts_with("```") {
+            in_block = !in_block;
+            continue;
+        }
+        // also collapse the blank lines around a removed block
+        let repeated_blank = line.is_empty() && out.last().is_some_and(|l| l.is_empty());
+        if !in_block && !repeated_blank {
+            out.push(line);
+        }
+    }
+    out.join("\n").trim().to_string()
+}
+
+/// Remove Rust examples from every description in a schema.
+fn strip_examples(schema: &mut Value) {
+    match schema {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                match value {
+                    Value::String(description) if key == "description" => {
+                        *description = without_code_blocks(description);
+                    }
+                    _ => strip_examples(value),
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(strip_examples),
+        _ => (),
+    }
+}
+
+/// Short human-readable type of a schema.
+fn type_name(schema: &Value) -> String {
+    if let Some(r) = schema.get("$ref").and_then(Value::as_str) {
+        return r.rsplit('/').next().unwrap_or(r).to_string();
+    }
+    match schema.get("type") {
+        Some(Value::String(t)) if t == "array" => match schema.get("items") {
+            Some(items) => format!("array of {}", type_name(items)),
+            None => "array".into(),
+        },
+        Some(Value::String(t)) => t.clone(),
+        Some(Value::Array(ts)) => ts
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join(" \\| "),
+        _ => ["anyOf", "oneOf"]
+            .into_iter()
+            .find_map(|k| schema.get(k).and_then(Value::as_array))
+            .map(|alts| alts.iter().map(type_name).collect::<Vec<_>>().join(" \\| "))
+            .unwrap_or_else(|| "any".into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iref::uri;
+    use schemars::JsonSchema;
+
+    use super::ConventionDocs;
+    use crate::{ConventionDefinition, NestedRepr, PrefixedRepr, ZarrConventionImpl};
+
+    /// Documented payload.
+    #[allow(unused)]
+    #[derive(serde::Serialize, serde::Deserialize, JsonSchema)]
+    struct Documented {
+        /// Width in pixels.
+        width: u32,
+        /// Optional label.
+        label: Option<String>,
+    }
+
+    impl ZarrConventionImpl for Documented {
+        const DEFINITION: ConventionDefinition = ConventionDefinition {
+            uuid: uuid::uuid!("66666666-6666-6666-6666-666666666666"),
+            schema_url: uri!("https://example.com/schemas/documented.json"),
+            spec_url: uri!("https://example.com/specs/documented"),
+            name: "documented",
+            description: "A documented convention.",
+        };
+    }
+
+    impl NestedRepr for Documented {
+        const KEY: &'static str = "documented";
+    }
+
+    impl PrefixedRepr for Documented {
+        const PREFIX: &'static str = "documented:";
+    }
+
+    #[test]
+    fn docgen_schema() {
+        let schema = ConventionDocs::either::<Documented>().schema();
+        assert_eq!(schema["$id"], "https://example.com/schemas/documented.json");
+        assert_eq!(
+            schema["properties"]["documented"]["properties"]["width"]["description"],
+            "Width in pixels."
+        );
+        assert_eq!(
+            schema["properties"]["documented:width"]["description"],
+            "Width in pixels."
+        );
+        assert_eq!(
+            schema["allOf"][0]["anyOf"][0]["required"][0],
+            "documented:width"
+        );
+        assert!(schema["properties"]["documented"].get("required").is_none());
+
+        let declaration = &schema["properties"]["zarr_conventions"]["contains"]["anyOf"];
+        assert_eq!(
+            declaration[1]["properties"]["schema_url"]["const"],
+            "https://example.com/schemas/documented.json"
+        );
+        assert_eq!(
+            declaration[2]["properties"]["spec_url"]["const"],
+            "https://example.com/specs/documented"
+        );
+
+        let nested = ConventionDocs::nested::<Documented>().schema();
+        assert_eq!(
+            nested["required"],
+            serde_json::json!(["zarr_conventions", "documented"])
+        );
+    }
+
+    #[cfg(feature = "test-util")]
+    #[test]
+    fn docgen_schema_accepts_any_identifier() {
+        let schema = ConventionDocs::nested::<Documented>().schema();
+        let validator = jsonschema::validator_for(&schema).unwrap();
+        for declaration in [
+            serde_json::json!({"uuid": "66666666-6666-6666-6666-666666666666"}),
+            serde_json::json!({"schema_url": "https://example.com/schemas/documented.json"}),
+            serde_json::json!({"spec_url": "https://example.com/specs/documented"}),
+        ] {
+            let attrs = serde_json::json!({
+                "zarr_conventions": [declaration],
+                "documented": {"width": 1},
+            });
+            assert!(validator.is_valid(&attrs), "{attrs}");
+        }
+        let attrs = serde_json::json!({
+            "zarr_conventions": [{"name": "documented"}],
+            "documented": {"width": 1},
+        });
+        assert!(!validator.is_valid(&attrs));
+    }
+
+    #[cfg(feature = "test-util")]
+    #[test]
+    fn docgen_schema_agrees_with_parser() {
+        use crate::NestedOrPrefixedRepr;
+
+        let validator =
+            jsonschema::validator_for(&ConventionDocs::either::<Documented>().schema()).unwrap();
+        let declaration = serde_json::json!([{"uuid": "66666666-6666-6666-6666-666666666666"}]);
+        for (payload, valid) in [
+            (serde_json::json!({"documented": {"width": 1}}), true),
+            (serde_json::json!({"documented:width": 1}), true),
+            (
+                serde_json::json!({"documented": {"label": "a"}, "documented:width": 1}),
+                true,
+            ),
+            (
+                serde_json::json!({"documented": {"width": 1}, "documented:label": "a"}),
+                true,
+            ),
+            (serde_json::json!({"documented": {"label": "a"}}), false),
+            (serde_json::json!({"documented:label": "a"}), false),
+        ] {
+            let mut attrs = payload.as_object().unwrap().clone();
+            attrs.insert("zarr_conventions".into(), declaration.clone());
+            assert_eq!(
+                Documented::from_attributes(&attrs).is_ok(),
+                valid,
+                "parser on {payload}"
+            );
+            assert_eq!(
+                validator.is_valid(&serde_json::Value::Object(attrs)),
+                valid,
+                "schema on {payload}"
+            );
+        }
+    }
+
+    /// Listed item.
+    #[allow(unused)]
+    #[derive(serde::Serialize, serde::Deserialize, JsonSchema)]
+    struct Item {
+        /// Name of the item.
+        name: String,
+    }
+
+    /// Items in a list.
+    ///
+    /// ```
+    /// let example = "not part of the spec";
+    /// ```
+    #[derive(serde::Serialize, serde::Deserialize, JsonSchema)]
+    #[serde(transparent)]
+    struct Listed(Vec<Item>);
+
+    impl ZarrConventionImpl for Listed {
+        const DEFINITION: ConventionDefinition = ConventionDefinition {
+            uuid: uuid::uuid!("77777777-7777-7777-7777-777777777777"),
+            schema_url: uri!("https://example.com/schemas/listed.json"),
+            spec_url: uri!("https://example.com/specs/listed"),
+            name: "listed",
+            description: "A convention whose payload is an array.",
+        };
+    }
+
+    impl NestedRepr for Listed {
+        const KEY: &'static str = "listed";
+    }
+
+    #[test]
+    fn docgen_array_payload() {
+        let docs = ConventionDocs::nested::<Listed>();
+        assert_eq!(
+            docs.schema()["properties"]["listed"]["description"],
+            "Items in a list."
+        );
+        let md = docs.markdown();
+        assert!(md.contains("an array under the `listed` key"));
+        assert!(md.contains("| `name` | string | yes | Name of the item. |"));
+        assert!(!md.contains("not part of the spec"));
+    }
+
+    #[test]
+    fn docgen_markdown() {
+        let md = ConventionDocs::prefixed::<Documented>().markdown();
+        assert!(md.starts_with("# documented\n"));
+        assert!(md.contains("| `width` | integer | yes | Width in pixels. |"));
+        assert!(md.contains("| `label` | string \\| null | no | Optional label. |"));
+        assert!(md.contains("prefixed with `documented:`"));
+    }
+}
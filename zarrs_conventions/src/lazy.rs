@@ -0,0 +1,284 @@
+use std::{collections::BTreeMap, fmt, marker::PhantomData};
+
+use serde::{
+    Deserialize,
+    de::{DeserializeOwned, DeserializeSeed, IgnoredAny, MapAccess, Visitor},
+};
+use serde_json::value::RawValue;
+
+use crate::{NestedRepr, PrefixedRepr, ZarrConventionImpl, ZarrConventions};
+
+/// Attributes map whose values are kept as unparsed JSON,
+/// borrowed from the source document.
+///
+/// Useful for scanning very large convention payloads where only a few fields are needed.
+///
+/// ```
+/// use zarrs_conventions::LazyAttributes;
+///
+/// let doc = r#"{"zarr_format": 3, "node_type": "group", "attributes": {"big": {"a": 1, "b": [1, 2, 3]}}}"#;
+/// let attrs = LazyAttributes::from_metadata_str(doc).unwrap();
+/// let raw = attrs.get_raw("big").unwrap();
+/// assert_eq!(raw.get(), r#"{"a": 1, "b": [1, 2, 3]}"#);
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct LazyAttributes<'a> {
+    zarr_conventions: ZarrConventions,
+    fields: BTreeMap<String, &'a RawValue>,
+}
+
+/// Zarr metadata document, retaining the raw attribute values.
+#[derive(Deserialize)]
+struct LazyMetadata<'a> {
+    #[serde(borrow, default)]
+    attributes: Option<BTreeMap<String, &'a RawValue>>,
+}
+
+impl<'a> LazyAttributes<'a> {
+    /// Parse the attributes from a full Zarr metadata document.
+    pub fn from_metadata_str(s: &'a str) -> serde_json::Result<Self> {
+        let metadata: LazyMetadata<'a> = serde_json::from_str(s)?;
+        Self::from_fields(metadata.attributes.unwrap_or_default())
+    }
+
+    /// Parse an attributes object.
+    pub fn from_attributes_str(s: &'a str) -> serde_json::Result<Self> {
+        Self::from_fields(serde_json::from_str(s)?)
+    }
+
+    fn from_fields(fields: BTreeMap<String, &'a RawValue>) -> serde_json::Result<Self> {
+        let zarr_conventions = match fields.get(ZarrConventions::KEY) {
+            Some(raw) => serde_json::from_str(raw.get())?,
+            None => ZarrConventions::default(),
+        };
+        Ok(Self {
+            zarr_conventions,
+            fields,
+        })
+    }
+
+    /// Check whether a particular convention is in use.
+    pub fn in_use<T: ZarrConventionImpl>(&self) -> bool {
+        T::in_use(&self.zarr_conventions)
+    }
+
+    /// Get the unparsed value of an attribute.
+    pub fn get_raw(&self, key: &str) -> Option<&'a RawValue> {
+        self.fields.get(key).copied()
+    }
+
+    /// Get and deserialize an unstructured attribute.
+    ///
+    /// None if not present.
+    pub fn get<T: Deserialize<'a>>(&self, key: &str) -> serde_json::Result<Option<T>> {
+        self.get_raw(key)
+            .map(|raw| serde_json::from_str(raw.get()))
+            .transpose()
+    }
+
+    /// Lazily view conventional metadata in nested form.
+    ///
+    /// None if the convention is not listed in "zarr_conventions" or the key is absent.
+    pub fn view_nested<T: NestedRepr>(&self) -> Option<LazyView<'a, T>> {
+        if !T::in_use(&self.zarr_conventions) {
+            return None;
+        }
+        self.get_raw(T::KEY).map(LazyView::new)
+    }
+
+    /// Deserialize a single field of conventional metadata in prefixed form.
+    ///
+    /// None if the convention is not listed in "zarr_conventions" or the field is absent.
+    pub fn prefixed_field<T: PrefixedRepr, F: Deserialize<'a>>(
+        &self,
+        name: &str,
+    ) -> serde_json::Result<Option<F>> {
+        if !T::in_use(&self.zarr_conventions) {
+            return Ok(None);
+        }
+        self.get(&format!("{}{}", T::PREFIX, name))
+    }
+}
+
+/// Unparsed payload of a convention in nested form,
+/// whose fields can be deserialized individually.
+pub struct LazyView<'a, T> {
+    raw: &'a RawValue,
+    _convention: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for LazyView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for LazyView<'_, T> {}
+
+impl<T> fmt::Debug for LazyView<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("LazyView").field(&self.raw).finish()
+    }
+}
+
+impl<'a, T> LazyView<'a, T> {
+    /// Wrap an unparsed payload.
+    pub fn new(raw: &'a RawValue) -> Self {
+        Self {
+            raw,
+            _convention: PhantomData,
+        }
+    }
+
+    /// The unparsed payload.
+    pub fn raw(&self) -> &'a RawValue {
+        self.raw
+    }
+
+    /// Deserialize a single top-level field of the payload,
+    /// skipping over the others without materializing them.
+    ///
+    /// None if the payload is an object without this field.
+    /// If the field is duplicated, the last value is used, as in a full parse.
+    /// Fails if the payload is not an object.
+    pub fn field<F: Deserialize<'a>>(&self, name: &str) -> serde_json::Result<Option<F>> {
+        let mut de = serde_json::Deserializer::from_str(self.raw.get());
+        let raw = serde::Deserializer::deserialize_map(&mut de, FieldVisitor(name))?;
+        de.end()?;
+        raw.map(|raw| serde_json::from_str(raw.get())).transpose()
+    }
+}
+
+impl<T: DeserializeOwned> LazyView<'_, T> {
+    /// Deserialize the full payload.
+    pub fn get(&self) -> serde_json::Result<T> {
+        serde_json::from_str(self.raw.get())
+    }
+}
+
+/// Matches a map key against the given name without allocating.
+struct KeyIs<'n>(&'n str);
+
+impl<'de> DeserializeSeed<'de> for KeyIs<'_> {
+    type Value = bool;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl Visitor<'_> for KeyIs<'_> {
+    type Value = bool;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a string key")
+    }
+
+    fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(v == self.0)
+    }
+}
+
+/// Finds the unparsed value of the last field with the given name,
+/// so that only that one is deserialized.
+struct FieldVisitor<'n>(&'n str);
+
+impl<'de> Visitor<'de> for FieldVisitor<'_> {
+    type Value = Option<&'de RawValue>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a JSON object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut out = None;
+        while let Some(matched) = map.next_key_seed(KeyIs(self.0))? {
+            // the last of any duplicate keys wins, as in serde_json's maps
+            if matched {
+                out = Some(map.next_value()?);
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LazyAttributes;
+    use crate::mock::{MustBeNested, MustBePrefixed};
+
+    const DOC: &str = r#"{
+        "zarr_format": 3,
+        "node_type": "group",
+        "attributes": {
+            "zarr_conventions": [
+                {"uuid": "11111111-1111-1111-1111-111111111111"},
+                {"uuid": "22222222-2222-2222-2222-222222222222"}
+            ],
+            "must_be_nested": {"a": 1, "ignored": [{"deep": [1, 2, 3]}], "b": 2},
+            "must_be_prefixed:x": 3,
+            "must_be_prefixed:y": 4,
+            "other_key": "other_value"
+        }
+    }"#;
+
+    #[test]
+    fn lazy_nested_field() {
+        let attrs = LazyAttributes::from_metadata_str(DOC).unwrap();
+        let view = attrs.view_nested::<MustBeNested>().unwrap();
+        assert_eq!(view.field::<u8>("b").unwrap(), Some(2));
+        assert_eq!(view.field::<u8>("missing").unwrap(), None);
+        assert_eq!(view.get().unwrap(), MustBeNested { a: 1, b: 2 });
+    }
+
+    #[test]
+    fn lazy_duplicate_field() {
+        let attrs = LazyAttributes::from_attributes_str(
+            r#"{
+                "zarr_conventions": [{"uuid": "11111111-1111-1111-1111-111111111111"}],
+                "must_be_nested": {"a": 1, "b": 2, "a": 3}
+            }"#,
+        )
+        .unwrap();
+        let view = attrs.view_nested::<MustBeNested>().unwrap();
+        let full: serde_json::Value = serde_json::from_str(view.raw().get()).unwrap();
+        assert_eq!(view.field::<u8>("a").unwrap(), Some(3));
+        assert_eq!(full["a"], 3);
+
+        // only the value which is used is deserialized
+        let attrs = LazyAttributes::from_attributes_str(
+            r#"{
+                "zarr_conventions": [{"uuid": "11111111-1111-1111-1111-111111111111"}],
+                "must_be_nested": {"a": "one", "b": 2, "a": 3}
+            }"#,
+        )
+        .unwrap();
+        let view = attrs.view_nested::<MustBeNested>().unwrap();
+        let full: serde_json::Value = serde_json::from_str(view.raw().get()).unwrap();
+        assert_eq!(view.field::<u8>("a").unwrap(), Some(3));
+        assert_eq!(full["a"], 3);
+    }
+
+    #[test]
+    fn lazy_prefixed_field() {
+        let attrs = LazyAttributes::from_metadata_str(DOC).unwrap();
+        let y: u8 = attrs
+            .prefixed_field::<MustBePrefixed, _>("y")
+            .unwrap()
+            .unwrap();
+        assert_eq!(y, 4);
+        let other: &str = attrs.get("other_key").unwrap().unwrap();
+        assert_eq!(other, "other_value");
+    }
+
+    #[test]
+    fn lazy_not_in_use() {
+        let attrs = LazyAttributes::from_attributes_str(r#"{"must_be_nested": {"a": 1}}"#).unwrap();
+        assert!(attrs.view_nested::<MustBeNested>().is_none());
+    }
+}
@@ -1,27 +1,53 @@
 #![doc = include_str!("../README.md")]
-use std::collections::BTreeSet;
+use std::borrow::Borrow;
 
 /// Used for representing URLs.
 pub use iref;
-use iref::UriBuf;
+use iref::{Uri, UriBuf};
 use serde::{Deserialize, Serialize};
 /// Used for uniquely identifying conventions.
 pub use uuid;
 use uuid::Uuid;
 
+#[cfg(feature = "arena")]
+pub mod arena;
 mod attributes;
+mod define;
+pub mod diff;
+#[cfg(feature = "schema")]
+pub mod docgen;
 pub use attributes::{AttributesBuilder, AttributesParser};
+mod lazy;
+pub use lazy::{LazyAttributes, LazyView};
+mod prefixed;
+mod scanner;
+pub use scanner::MetadataScanner;
 mod traits;
 pub use traits::{NestedOrPrefixedRepr, NestedRepr, PrefixedRepr, ZarrConventionImpl};
 
 mod convention;
 pub use convention::{Convention, ConventionDefinition};
 
+#[cfg(any(test, feature = "test-util"))]
+pub mod mock;
+mod node_path;
+pub use node_path::{NodePath, NodePathError};
 pub mod registry;
+pub mod repr;
+#[cfg(feature = "test-util")]
+pub mod test_util;
+pub mod uuid5;
+pub mod validate;
 pub use registry::DEFAULT_ZARR_CONVENTION_REGISTRY;
 
-#[cfg(test)]
-mod tests;
+/// Used by exported macros; not public API.
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(not(target_family = "wasm"))]
+    pub use ctor;
+    #[cfg(feature = "test-util")]
+    pub use zarrs_conventions_macros::include_examples;
+}
 
 /// Unstructured user attributes map from a Zarr node.
 pub type Attributes = serde_json::Map<String, serde_json::Value>;
@@ -43,6 +69,47 @@ pub enum ConventionId {
     SpecUrl(UriBuf),
 }
 
+impl ConventionId {
+    /// Borrow this identifier.
+    pub fn as_id_ref(&self) -> ConventionIdRef<'_> {
+        match self {
+            Self::Uuid(uuid) => ConventionIdRef::Uuid(*uuid),
+            Self::SchemaUrl(url) => ConventionIdRef::SchemaUrl(url.as_ref()),
+            Self::SpecUrl(url) => ConventionIdRef::SpecUrl(url.as_ref()),
+        }
+    }
+}
+
+/// Borrowed form of [ConventionId], for lookups which should not allocate.
+#[derive(Debug, Clone, Copy, Serialize, Hash, PartialEq, Eq)]
+pub enum ConventionIdRef<'a> {
+    Uuid(Uuid),
+    SchemaUrl(&'a Uri),
+    SpecUrl(&'a Uri),
+}
+
+impl<'a> From<&'a ConventionId> for ConventionIdRef<'a> {
+    fn from(value: &'a ConventionId) -> Self {
+        value.as_id_ref()
+    }
+}
+
+impl From<Uuid> for ConventionIdRef<'_> {
+    fn from(value: Uuid) -> Self {
+        Self::Uuid(value)
+    }
+}
+
+impl From<ConventionIdRef<'_>> for ConventionId {
+    fn from(value: ConventionIdRef<'_>) -> Self {
+        match value {
+            ConventionIdRef::Uuid(uuid) => Self::Uuid(uuid),
+            ConventionIdRef::SchemaUrl(url) => Self::SchemaUrl(url.to_owned()),
+            ConventionIdRef::SpecUrl(url) => Self::SpecUrl(url.to_owned()),
+        }
+    }
+}
+
 impl From<ConventionDefinition> for ConventionId {
     fn from(value: ConventionDefinition) -> Self {
         value.id_uuid()
@@ -77,9 +144,47 @@ impl From<Convention> for ConventionId {
 /// ```
 #[derive(Debug, Default, Clone)]
 pub struct ZarrConventions {
-    uuids: BTreeSet<Uuid>,
-    schema_urls: BTreeSet<UriBuf>,
-    spec_urls: BTreeSet<UriBuf>,
+    uuids: SmallSet<Uuid>,
+    schema_urls: SmallSet<UriBuf>,
+    spec_urls: SmallSet<UriBuf>,
+}
+
+/// Insertion-ordered set backed by a vec.
+///
+/// Nodes typically declare a handful of conventions,
+/// for which a linear scan beats hashing or tree traversal for both construction and lookup.
+#[derive(Debug, Clone)]
+struct SmallSet<T>(Vec<T>);
+
+impl<T> Default for SmallSet<T> {
+    fn default() -> Self {
+        Self(Vec::new())
+    }
+}
+
+impl<T: PartialEq> SmallSet<T> {
+    /// Empty the set and make room for `additional` items, keeping its allocation.
+    fn reset(&mut self, additional: usize) {
+        self.0.clear();
+        self.0.reserve(additional);
+    }
+
+    /// Returns whether the item was newly inserted.
+    fn insert(&mut self, item: T) -> bool {
+        if self.0.contains(&item) {
+            return false;
+        }
+        self.0.push(item);
+        true
+    }
+
+    fn contains<Q>(&self, item: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: PartialEq + ?Sized,
+    {
+        self.0.iter().any(|t| t.borrow() == item)
+    }
 }
 
 impl ZarrConventions {
@@ -90,14 +195,33 @@ impl ZarrConventions {
         let Some(zc) = attributes.get(Self::KEY) else {
             return Ok(ZarrConventions::default());
         };
-        serde_json::from_value(zc.clone())
+        Self::deserialize(zc)
+    }
+
+    /// Replace the set of in-use conventions, keeping the capacity of the id sets.
+    pub(crate) fn refill(&mut self, conventions: Vec<Convention>) {
+        self.uuids.reset(conventions.len());
+        self.schema_urls.reset(conventions.len());
+        self.spec_urls.reset(conventions.len());
+        for item in conventions {
+            if let Some(uuid) = item.uuid {
+                self.uuids.insert(uuid);
+            }
+            if let Some(schema_url) = item.schema_url {
+                self.schema_urls.insert(schema_url);
+            }
+            if let Some(spec_url) = item.spec_url {
+                self.spec_urls.insert(spec_url);
+            }
+        }
     }
 
-    pub fn contains(&self, id: &ConventionId) -> bool {
-        match id {
-            ConventionId::Uuid(uuid) => self.uuids.contains(uuid),
-            ConventionId::SchemaUrl(uri_buf) => self.schema_urls.contains(uri_buf),
-            ConventionId::SpecUrl(uri_buf) => self.spec_urls.contains(uri_buf),
+    /// Check whether the given convention is in use.
+    pub fn contains<'a>(&self, id: impl Into<ConventionIdRef<'a>>) -> bool {
+        match id.into() {
+            ConventionIdRef::Uuid(uuid) => self.uuids.contains(&uuid),
+            ConventionIdRef::SchemaUrl(url) => self.schema_urls.contains(url),
+            ConventionIdRef::SpecUrl(url) => self.spec_urls.contains(url),
         }
     }
 }
@@ -108,20 +232,9 @@ impl<'de> Deserialize<'de> for ZarrConventions {
         D: serde::Deserializer<'de>,
     {
         let lst: Vec<Convention> = Deserialize::deserialize(deserializer)?;
-        Ok(lst
-            .into_iter()
-            .fold(ZarrConventions::default(), |mut c, item| {
-                if let Some(uuid) = item.uuid {
-                    c.uuids.insert(uuid);
-                }
-                if let Some(schema_url) = item.schema_url {
-                    c.schema_urls.insert(schema_url);
-                }
-                if let Some(spec_url) = item.spec_url {
-                    c.spec_urls.insert(spec_url);
-                }
-                c
-            }))
+        let mut out = ZarrConventions::default();
+        out.refill(lst);
+        Ok(out)
     }
 }
 
@@ -1,9 +1,22 @@
+//! Mock conventions for testing registries, builders, and parsers.
+//!
+//! These have stable, obviously-fake UUIDs and example.com URLs,
+//! and are not registered in the default registry.
+//!
+//! ```
+//! use zarrs_conventions::{AttributesBuilder, AttributesParser, mock::MustBeNested};
+//!
+//! let mut builder = AttributesBuilder::default();
+//! builder.add_nested(&MustBeNested { a: 1, b: 2 }).unwrap();
+//! let parser: AttributesParser = serde_json::from_value(builder.build().unwrap()).unwrap();
+//! assert!(parser.in_use::<MustBeNested>());
+//! ```
 use iref::uri;
 
 use crate::{NestedRepr, PrefixedRepr, ZarrConventionImpl, convention::ConventionDefinition};
 
-#[allow(unused)]
 #[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq)]
+/// Mock convention with only a nested representation, under `"must_be_nested"`.
 pub struct MustBeNested {
     pub a: u8,
     pub b: u8,
@@ -23,8 +36,8 @@ impl NestedRepr for MustBeNested {
     const KEY: &'static str = "must_be_nested";
 }
 
-#[allow(unused)]
 #[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq)]
+/// Mock convention with only a prefixed representation, under `"must_be_prefixed:"`.
 pub struct MustBePrefixed {
     pub x: u8,
     pub y: u8,
@@ -44,8 +57,8 @@ impl PrefixedRepr for MustBePrefixed {
     const PREFIX: &'static str = "must_be_prefixed:";
 }
 
-#[allow(unused)]
 #[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq)]
+/// Mock convention with both nested (`"can_be_either"`) and prefixed (`"can_be_either:"`) representations.
 pub struct CanBeEither {
     pub foo: u8,
     pub bar: u8,
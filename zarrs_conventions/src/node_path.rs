@@ -0,0 +1,225 @@
+//! Absolute paths of nodes within a Zarr hierarchy, and paths relative to them.
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+/// Why a path could not be formed.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum NodePathError {
+    #[error("'{0}' is not an absolute path")]
+    NotAbsolute(String),
+    #[error("'{0}' is not a relative path")]
+    NotRelative(String),
+    #[error("'{0}' escapes the root of the hierarchy")]
+    EscapesRoot(String),
+}
+
+/// Absolute path of a node within a hierarchy, e.g. `/group/array`; the root is `/`.
+///
+/// Paths are normalised: empty and `.` components are dropped and `..` components resolved.
+///
+/// ```
+/// use zarrs_conventions::NodePath;
+///
+/// let node: NodePath = "/a/b/".parse().unwrap();
+/// assert_eq!(node.as_str(), "/a/b");
+/// assert_eq!(node.join("../c").unwrap().as_str(), "/a/c");
+/// assert_eq!(NodePath::root().join("a/c").unwrap().relative_to(&node), "../c");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct NodePath(String);
+
+impl NodePath {
+    pub fn root() -> Self {
+        Self("/".to_string())
+    }
+
+    pub fn new(path: &str) -> Result<Self, NodePathError> {
+        if !path.starts_with('/') {
+            return Err(NodePathError::NotAbsolute(path.to_string()));
+        }
+        Self::root().push(path, path)
+    }
+
+    /// Append the components of `relative`, resolving `..`;
+    /// `original` is reported if the root is escaped.
+    fn push(&self, relative: &str, original: &str) -> Result<Self, NodePathError> {
+        let mut parts: Vec<&str> = self.components().collect();
+        for part in relative.split('/') {
+            match part {
+                "" | "." => (),
+                ".." => {
+                    parts
+                        .pop()
+                        .ok_or_else(|| NodePathError::EscapesRoot(original.to_string()))?;
+                }
+                part => parts.push(part),
+            }
+        }
+        Ok(Self(format!("/{}", parts.join("/"))))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn is_root(&self) -> bool {
+        self.0 == "/"
+    }
+
+    /// Names of the nodes from the root's child down to this node.
+    pub fn components(&self) -> impl Iterator<Item = &str> {
+        self.0.split('/').filter(|p| !p.is_empty())
+    }
+
+    /// Name of the node, or `None` for the root.
+    pub fn name(&self) -> Option<&str> {
+        self.components().last()
+    }
+
+    /// Path of the node's parent, or `None` for the root.
+    pub fn parent(&self) -> Option<Self> {
+        let (parent, _) = self.0.rsplit_once('/').filter(|_| !self.is_root())?;
+        Some(if parent.is_empty() {
+            Self::root()
+        } else {
+            Self(parent.to_string())
+        })
+    }
+
+    /// This node and each of its ancestors, ending with the root.
+    pub fn ancestors(&self) -> impl Iterator<Item = Self> {
+        std::iter::successors(Some(self.clone()), Self::parent)
+    }
+
+    /// Whether this node is the given node or one of its descendants.
+    pub fn starts_with(&self, ancestor: &NodePath) -> bool {
+        ancestor.is_root()
+            || self.0 == ancestor.0
+            || self
+                .0
+                .strip_prefix(&ancestor.0)
+                .is_some_and(|rest| rest.starts_with('/'))
+    }
+
+    /// Path of the node at `relative` from this one, e.g. `../LICENSE` from `/a/b` is `/a/LICENSE`.
+    ///
+    /// Fails if `relative` is absolute or escapes the root.
+    pub fn join(&self, relative: &str) -> Result<Self, NodePathError> {
+        if relative.starts_with('/') {
+            return Err(NodePathError::NotRelative(relative.to_string()));
+        }
+        self.push(relative, relative)
+    }
+
+    /// Path of the node referenced from this one, which may be absolute or relative.
+    pub fn resolve(&self, reference: &str) -> Result<Self, NodePathError> {
+        if reference.starts_with('/') {
+            Self::new(reference)
+        } else {
+            self.join(reference)
+        }
+    }
+
+    /// Path to this node relative to `base`, e.g. `/c/d` relative to `/a/b` is `../../c/d`;
+    /// empty if they are the same node.
+    pub fn relative_to(&self, base: &NodePath) -> String {
+        let node: Vec<_> = base.components().collect();
+        let target: Vec<_> = self.components().collect();
+        let common = node.iter().zip(&target).take_while(|(a, b)| a == b).count();
+        let mut parts = vec![".."; node.len() - common];
+        parts.extend(&target[common..]);
+        parts.join("/")
+    }
+}
+
+impl fmt::Display for NodePath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for NodePath {
+    type Err = NodePathError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl TryFrom<String> for NodePath {
+    type Error = NodePathError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(&value)
+    }
+}
+
+impl From<NodePath> for String {
+    fn from(value: NodePath) -> Self {
+        value.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NodePath, NodePathError};
+
+    fn path(s: &str) -> NodePath {
+        NodePath::new(s).unwrap()
+    }
+
+    #[test]
+    fn normalise() {
+        assert_eq!(path("/").as_str(), "/");
+        assert_eq!(path("//a/./b/../c/").as_str(), "/a/c");
+        assert_eq!(
+            NodePath::new("a/b"),
+            Err(NodePathError::NotAbsolute("a/b".into()))
+        );
+        assert_eq!(
+            NodePath::new("/.."),
+            Err(NodePathError::EscapesRoot("/..".into()))
+        );
+        assert_eq!(
+            serde_json::from_str::<NodePath>("\"/a/\"").unwrap(),
+            path("/a")
+        );
+        assert!(serde_json::from_str::<NodePath>("\"a\"").is_err());
+    }
+
+    #[test]
+    fn navigate() {
+        let node = path("/a/b");
+        assert_eq!(node.name(), Some("b"));
+        assert_eq!(NodePath::root().name(), None);
+        assert_eq!(node.parent(), Some(path("/a")));
+        assert_eq!(path("/a").parent(), Some(NodePath::root()));
+        assert_eq!(NodePath::root().parent(), None);
+        let ancestors: Vec<_> = node.ancestors().map(String::from).collect();
+        assert_eq!(ancestors, ["/a/b", "/a", "/"]);
+
+        assert!(node.starts_with(&path("/a")));
+        assert!(node.starts_with(&node));
+        assert!(node.starts_with(&NodePath::root()));
+        assert!(!path("/ab").starts_with(&path("/a")));
+
+        assert_eq!(node.join("../../c").unwrap(), path("/c"));
+        assert_eq!(
+            node.join("/c"),
+            Err(NodePathError::NotRelative("/c".into()))
+        );
+        assert_eq!(
+            node.join("../../.."),
+            Err(NodePathError::EscapesRoot("../../..".into()))
+        );
+        assert_eq!(node.resolve("/c").unwrap(), path("/c"));
+        assert_eq!(node.resolve("c").unwrap(), path("/a/b/c"));
+
+        assert_eq!(path("/c/d").relative_to(&node), "../../c/d");
+        assert_eq!(node.relative_to(&node), "");
+        assert_eq!(path("/a/b/c").relative_to(&node), "c");
+    }
+}
@@ -0,0 +1,331 @@
+//! Serialize conventional metadata straight into prefixed attributes.
+use serde::{
+    Serialize, Serializer,
+    ser::{
+        Error as _, Impossible, SerializeMap, SerializeStruct, SerializeStructVariant,
+        SerializeTupleVariant,
+    },
+};
+use serde_json::{Error, Value, value::Serializer as JsonSerializer};
+
+fn not_object() -> Error {
+    Error::custom("Prefixed representation must serialize to a JSON object")
+}
+
+fn prefixed(prefix: &str, key: &str) -> String {
+    let mut out = String::with_capacity(prefix.len() + key.len());
+    out.push_str(prefix);
+    out.push_str(key);
+    out
+}
+
+/// Serializes an object's fields as prefixed entries,
+/// without first building the object as a [Value].
+///
+/// Only the fields' values are serialized to [Value]s.
+/// As with [serde_json::to_value], enum variants with data become a single field named after the variant.
+pub(crate) struct PrefixedSerializer<'a> {
+    pub(crate) prefix: &'a str,
+    pub(crate) entries: &'a mut Vec<(String, Value)>,
+}
+
+/// Collects the fields of a map or struct.
+pub(crate) struct Fields<'a> {
+    prefix: &'a str,
+    entries: &'a mut Vec<(String, Value)>,
+    key: Option<String>,
+}
+
+/// Collects a tuple or struct variant with [serde_json]'s serializer,
+/// then adds it as a single field.
+pub(crate) struct Variant<'a, S> {
+    prefix: &'a str,
+    entries: &'a mut Vec<(String, Value)>,
+    inner: S,
+}
+
+impl<S> Variant<'_, S> {
+    /// Add the single field of the variant's serialized object.
+    fn finish(self, end: impl FnOnce(S) -> Result<Value, Error>) -> Result<(), Error> {
+        let Value::Object(map) = end(self.inner)? else {
+            return Err(not_object());
+        };
+        for (k, v) in map {
+            self.entries.push((prefixed(self.prefix, &k), v));
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Serializer for PrefixedSerializer<'a> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Impossible<(), Error>;
+    type SerializeTuple = Impossible<(), Error>;
+    type SerializeTupleStruct = Impossible<(), Error>;
+    type SerializeTupleVariant = Variant<'a, <JsonSerializer as Serializer>::SerializeTupleVariant>;
+    type SerializeMap = Fields<'a>;
+    type SerializeStruct = Fields<'a>;
+    type SerializeStructVariant =
+        Variant<'a, <JsonSerializer as Serializer>::SerializeStructVariant>;
+
+    fn serialize_bool(self, _v: bool) -> Result<(), Error> {
+        Err(not_object())
+    }
+
+    fn serialize_i8(self, _v: i8) -> Result<(), Error> {
+        Err(not_object())
+    }
+
+    fn serialize_i16(self, _v: i16) -> Result<(), Error> {
+        Err(not_object())
+    }
+
+    fn serialize_i32(self, _v: i32) -> Result<(), Error> {
+        Err(not_object())
+    }
+
+    fn serialize_i64(self, _v: i64) -> Result<(), Error> {
+        Err(not_object())
+    }
+
+    fn serialize_u8(self, _v: u8) -> Result<(), Error> {
+        Err(not_object())
+    }
+
+    fn serialize_u16(self, _v: u16) -> Result<(), Error> {
+        Err(not_object())
+    }
+
+    fn serialize_u32(self, _v: u32) -> Result<(), Error> {
+        Err(not_object())
+    }
+
+    fn serialize_u64(self, _v: u64) -> Result<(), Error> {
+        Err(not_object())
+    }
+
+    fn serialize_f32(self, _v: f32) -> Result<(), Error> {
+        Err(not_object())
+    }
+
+    fn serialize_f64(self, _v: f64) -> Result<(), Error> {
+        Err(not_object())
+    }
+
+    fn serialize_char(self, _v: char) -> Result<(), Error> {
+        Err(not_object())
+    }
+
+    fn serialize_str(self, _v: &str) -> Result<(), Error> {
+        Err(not_object())
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<(), Error> {
+        Err(not_object())
+    }
+
+    fn serialize_none(self) -> Result<(), Error> {
+        Err(not_object())
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), Error> {
+        Err(not_object())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Error> {
+        Err(not_object())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<(), Error> {
+        Err(not_object())
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        let value = serde_json::to_value(value)?;
+        self.entries.push((prefixed(self.prefix, variant), value));
+        Ok(())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+        Err(not_object())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Error> {
+        Err(not_object())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Error> {
+        Err(not_object())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        let inner = JsonSerializer.serialize_tuple_variant(name, variant_index, variant, len)?;
+        Ok(self.variant(inner))
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        self.entries.reserve(len.unwrap_or_default());
+        Ok(self.fields())
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStruct, Error> {
+        self.entries.reserve(len);
+        Ok(self.fields())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        let inner = JsonSerializer.serialize_struct_variant(name, variant_index, variant, len)?;
+        Ok(self.variant(inner))
+    }
+}
+
+impl<'a> PrefixedSerializer<'a> {
+    fn fields(self) -> Fields<'a> {
+        Fields {
+            prefix: self.prefix,
+            entries: self.entries,
+            key: None,
+        }
+    }
+
+    fn variant<S>(self, inner: S) -> Variant<'a, S> {
+        Variant {
+            prefix: self.prefix,
+            entries: self.entries,
+            inner,
+        }
+    }
+}
+
+impl SerializeMap for Fields<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), Error> {
+        // the same keys as serde_json accepts for map keys
+        let key = match serde_json::to_value(key)? {
+            Value::String(s) => s,
+            Value::Number(n) => n.to_string(),
+            Value::Bool(b) => b.to_string(),
+            _ => return Err(Error::custom("key must be a string")),
+        };
+        self.key = Some(prefixed(self.prefix, &key));
+        Ok(())
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        let key = self
+            .key
+            .take()
+            .expect("serialize_value should be called after serialize_key");
+        self.entries.push((key, serde_json::to_value(value)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+impl SerializeStruct for Fields<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.entries
+            .push((prefixed(self.prefix, key), serde_json::to_value(value)?));
+        Ok(())
+    }
+
+    fn skip_field(&mut self, _key: &'static str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+impl<S: SerializeTupleVariant<Ok = Value, Error = Error>> SerializeTupleVariant for Variant<'_, S> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.inner.serialize_field(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.finish(S::end)
+    }
+}
+
+impl<S: SerializeStructVariant<Ok = Value, Error = Error>> SerializeStructVariant
+    for Variant<'_, S>
+{
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.inner.serialize_field(key, value)
+    }
+
+    fn skip_field(&mut self, key: &'static str) -> Result<(), Error> {
+        self.inner.skip_field(key)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.finish(S::end)
+    }
+}
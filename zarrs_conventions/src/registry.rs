@@ -7,7 +7,7 @@ use std::{
 use iref::Uri;
 use uuid::Uuid;
 
-use crate::{ConventionId, ZarrConventionImpl, convention::ConventionDefinition};
+use crate::{ConventionIdRef, ZarrConventionImpl, convention::ConventionDefinition};
 
 /// Global registry of accessible zarr conventions,
 /// queryable at runtime.
@@ -61,37 +61,35 @@ impl ConventionRegistry {
     /// registry.register::<MyConvention>().unwrap();
     /// ```
     pub fn register<T: ZarrConventionImpl>(&self) -> Result<&Self, String> {
+        self.register_definition(T::DEFINITION)
+    }
+
+    /// Register a convention by its definition,
+    /// e.g. one taken from a [DynConvention](crate::validate::DynConvention).
+    pub fn register_definition(&self, definition: ConventionDefinition) -> Result<&Self, String> {
         let mut inner = self.inner.write().expect("RwLock poisoned");
-        if inner
-            .uuid_reg
-            .insert(T::DEFINITION.uuid, T::DEFINITION)
-            .is_some()
-        {
+        // Check all identifiers before inserting any, so that a failed registration has no effect.
+        if inner.uuid_reg.contains_key(&definition.uuid) {
             return Err(format!(
                 "Convention with UUID {} is already registered",
-                T::DEFINITION.uuid
+                definition.uuid
             ));
         }
-        if inner
-            .schema_reg
-            .insert(T::DEFINITION.schema_url, T::DEFINITION)
-            .is_some()
-        {
+        if inner.schema_reg.contains_key(definition.schema_url) {
             return Err(format!(
                 "Convention with schema URL {} is already registered",
-                T::DEFINITION.schema_url
+                definition.schema_url
             ));
         }
-        if inner
-            .spec_reg
-            .insert(T::DEFINITION.spec_url, T::DEFINITION)
-            .is_some()
-        {
+        if inner.spec_reg.contains_key(definition.spec_url) {
             return Err(format!(
                 "Convention with spec URL {} is already registered",
-                T::DEFINITION.spec_url
+                definition.spec_url
             ));
         }
+        inner.uuid_reg.insert(definition.uuid, definition);
+        inner.schema_reg.insert(definition.schema_url, definition);
+        inner.spec_reg.insert(definition.spec_url, definition);
         Ok(self)
     }
 
@@ -102,31 +100,40 @@ impl ConventionRegistry {
     }
 
     /// Check whether a given convention is registered.
-    pub fn contains(&self, id: &ConventionId) -> bool {
+    ///
+    /// Accepts a [ConventionId](crate::ConventionId) reference or a [ConventionIdRef].
+    pub fn contains<'a>(&self, id: impl Into<ConventionIdRef<'a>>) -> bool {
         let inner = self.inner.read().expect("RwLock poisoned");
-        match id {
-            ConventionId::Uuid(uuid) => inner.uuid_reg.contains_key(uuid),
-            ConventionId::SchemaUrl(url) => inner.schema_reg.contains_key(&url.as_ref()),
-            ConventionId::SpecUrl(url) => inner.spec_reg.contains_key(&url.as_ref()),
+        match id.into() {
+            ConventionIdRef::Uuid(uuid) => inner.uuid_reg.contains_key(&uuid),
+            ConventionIdRef::SchemaUrl(url) => inner.schema_reg.contains_key(&url),
+            ConventionIdRef::SpecUrl(url) => inner.spec_reg.contains_key(&url),
         }
     }
 
     /// Get the definition for a given convention.
-    pub fn get(&self, id: &ConventionId) -> Option<ConventionDefinition> {
+    ///
+    /// Accepts a [ConventionId](crate::ConventionId) reference or a [ConventionIdRef].
+    pub fn get<'a>(&self, id: impl Into<ConventionIdRef<'a>>) -> Option<ConventionDefinition> {
         let inner = self.inner.read().expect("RwLock poisoned");
-        match id {
-            ConventionId::Uuid(uuid) => inner.uuid_reg.get(uuid).copied(),
-            ConventionId::SchemaUrl(url) => inner.schema_reg.get(&url.as_ref()).copied(),
-            ConventionId::SpecUrl(url) => inner.spec_reg.get(&url.as_ref()).copied(),
+        match id.into() {
+            ConventionIdRef::Uuid(uuid) => inner.uuid_reg.get(&uuid).copied(),
+            ConventionIdRef::SchemaUrl(url) => inner.schema_reg.get(&url).copied(),
+            ConventionIdRef::SpecUrl(url) => inner.spec_reg.get(&url).copied(),
         }
     }
 }
 
-/// Register conventions in the default registry.
+/// Register conventions in the default registry when the program starts.
 ///
-/// This macro can only be called once per module; multiple conventions can be registered in one invocation.
+/// Multiple conventions can be registered in one invocation,
+/// and the macro can be invoked any number of times in the same module or crate.
 ///
-/// Panics if registration fails (for example, due to duplicate identifiers).
+/// Panics (aborting the process) if registration fails, e.g. due to duplicate identifiers,
+/// naming the offending convention type.
+///
+/// Life-before-main is not supported on wasm targets, where this macro expands to nothing;
+/// register conventions explicitly there with [ConventionRegistry::register].
 ///
 /// ```
 /// use zarrs_conventions::{uuid, iref};
@@ -165,15 +172,23 @@ impl ConventionRegistry {
 /// ```
 #[macro_export]
 macro_rules! register_zarr_conventions {
-    ($($convention:ty),+) => {
-        #[ctor::ctor]
-        fn register_conventions() {
-            $(
-                $crate::DEFAULT_ZARR_CONVENTION_REGISTRY.register::<$convention>().map_err(|e|
-                    panic!("Failed to register convention {}: {}", stringify!($convention), e)
-                );
-            )+
-        }
+    ($($convention:ty),+ $(,)?) => {
+        // Scoped so that each invocation's constructor has its own namespace.
+        #[cfg(not(target_family = "wasm"))]
+        const _: () = {
+            #[$crate::__private::ctor::ctor(crate_path = $crate::__private::ctor)]
+            fn register_conventions() {
+                $(
+                    if let Err(e) = $crate::DEFAULT_ZARR_CONVENTION_REGISTRY.register::<$convention>() {
+                        panic!(
+                            "Failed to register convention {}: {}",
+                            stringify!($convention),
+                            e
+                        );
+                    }
+                )+
+            }
+        };
     };
 }
 
@@ -214,9 +229,63 @@ mod tests {
 
     register_zarr_conventions!(TestConvention);
 
+    #[derive(serde::Serialize, serde::Deserialize)]
+    struct OtherTestConvention;
+
+    impl ZarrConventionImpl for OtherTestConvention {
+        const DEFINITION: ConventionDefinition = ConventionDefinition {
+            uuid: uuid::uuid!("87654321-4321-8765-4321-876543218765"),
+            schema_url: uri!("https://example.com/schemas/other_test_convention.json"),
+            spec_url: uri!("https://example.com/specs/other_test_convention"),
+            name: "other_test_convention",
+            description: "Another test convention.",
+        };
+    }
+
+    // A second invocation in the same module.
+    register_zarr_conventions!(OtherTestConvention);
+
     #[test]
     fn test_registered_by_macro() {
         let id = crate::ConventionId::Uuid(uuid::uuid!("12345678-1234-5678-1234-567812345678"));
         assert!(crate::DEFAULT_ZARR_CONVENTION_REGISTRY.contains(&id));
+        assert!(
+            crate::DEFAULT_ZARR_CONVENTION_REGISTRY.contains(OtherTestConvention::DEFINITION.uuid)
+        );
+    }
+
+    #[test]
+    fn test_failed_registration_has_no_effect() {
+        struct ClashingSchema;
+
+        impl ZarrConventionImpl for ClashingSchema {
+            const DEFINITION: ConventionDefinition = ConventionDefinition {
+                uuid: uuid::uuid!("00000000-1234-5678-1234-567812345678"),
+                schema_url: TestConvention::DEFINITION.schema_url,
+                spec_url: uri!("https://example.com/specs/clashing_schema"),
+                name: "clashing_schema",
+                description: "Shares a schema URL with another convention.",
+            };
+        }
+
+        let registry = ConventionRegistry::default();
+        registry.register::<TestConvention>().unwrap();
+        assert!(registry.register::<ClashingSchema>().is_err());
+        assert!(!registry.contains(ClashingSchema::DEFINITION.uuid));
+        assert_eq!(
+            registry.get(TestConvention::DEFINITION.id_schema_ref()),
+            Some(TestConvention::DEFINITION)
+        );
+    }
+
+    #[test]
+    fn test_get_by_ref() {
+        let registry = ConventionRegistry::default();
+        registry.register::<TestConvention>().unwrap();
+
+        let def = TestConvention::DEFINITION;
+        assert!(registry.contains(def.id_schema_ref()));
+        assert!(registry.contains(def.id_spec_ref()));
+        assert_eq!(registry.get(def.uuid), Some(def));
     }
 }
@@ -0,0 +1,132 @@
+//! Convert conventional metadata between nested and prefixed representations in place.
+//!
+//! ```
+//! use zarrs_conventions::repr::{Repr, convert};
+//!
+//! let mut attrs = serde_json::json!({"proj:code": "EPSG:4326", "other": 1});
+//! let attrs = attrs.as_object_mut().unwrap();
+//! assert!(convert(attrs, "proj", "proj:", Repr::Nested).unwrap());
+//! assert_eq!(
+//!     serde_json::Value::Object(attrs.clone()),
+//!     serde_json::json!({"proj": {"code": "EPSG:4326"}, "other": 1})
+//! );
+//! ```
+use serde_json::Value;
+
+use crate::{Attributes, NestedRepr, PrefixedRepr};
+
+/// Layout of a convention's payload within the attributes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Repr {
+    /// An object under a single key.
+    Nested,
+    /// Each field under its own prefixed key.
+    Prefixed,
+}
+
+/// Convert a payload to the given representation, returning whether anything changed.
+///
+/// Where a field is given in both forms, the prefixed value takes precedence
+/// (as in [NestedOrPrefixedRepr::from_attributes](crate::NestedOrPrefixedRepr::from_attributes)).
+/// Fails if the nested payload is not an object.
+pub fn convert(
+    attributes: &mut Attributes,
+    key: &str,
+    prefix: &str,
+    to: Repr,
+) -> Result<bool, String> {
+    match to {
+        Repr::Nested => {
+            let prefixed: Vec<String> = attributes
+                .keys()
+                .filter(|k| k.starts_with(prefix))
+                .cloned()
+                .collect();
+            if prefixed.is_empty() {
+                return Ok(false);
+            }
+            let mut nested = match attributes.get(key) {
+                Some(Value::Object(_)) => match attributes.remove(key) {
+                    Some(Value::Object(m)) => m,
+                    _ => unreachable!("checked above"),
+                },
+                None => Attributes::default(),
+                Some(_) => return Err(format!("nested payload '{key}' is not an object")),
+            };
+            for k in prefixed {
+                let v = attributes.remove(&k).expect("key was listed");
+                nested.insert(k[prefix.len()..].to_string(), v);
+            }
+            attributes.insert(key.to_string(), Value::Object(nested));
+            Ok(true)
+        }
+        Repr::Prefixed => {
+            let nested = match attributes.get(key) {
+                Some(Value::Object(_)) => attributes.remove(key),
+                None => return Ok(false),
+                Some(_) => return Err(format!("nested payload '{key}' is not an object")),
+            };
+            let Some(Value::Object(nested)) = nested else {
+                unreachable!("checked above");
+            };
+            for (k, v) in nested {
+                attributes.entry(format!("{prefix}{k}")).or_insert(v);
+            }
+            Ok(true)
+        }
+    }
+}
+
+/// As [convert], using the convention's own key and prefix.
+pub fn convert_convention<T: NestedRepr + PrefixedRepr>(
+    attributes: &mut Attributes,
+    to: Repr,
+) -> Result<bool, String> {
+    convert(attributes, T::KEY, T::PREFIX, to)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{Repr, convert_convention};
+    use crate::{Attributes, NestedOrPrefixedRepr, mock::CanBeEither};
+
+    fn attrs(v: serde_json::Value) -> Attributes {
+        v.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn convert_roundtrip_preserves_meaning() {
+        let original = attrs(json!({
+            "can_be_either": {"foo": 1, "bar": 0},
+            "can_be_either:bar": 2,
+            "other": true
+        }));
+        let expected = CanBeEither::from_attributes(&original).unwrap();
+
+        let mut a = original.clone();
+        assert!(convert_convention::<CanBeEither>(&mut a, Repr::Prefixed).unwrap());
+        assert_eq!(
+            serde_json::Value::Object(a.clone()),
+            json!({"can_be_either:foo": 1, "can_be_either:bar": 2, "other": true})
+        );
+        assert_eq!(CanBeEither::from_attributes(&a).unwrap(), expected);
+        assert!(!convert_convention::<CanBeEither>(&mut a, Repr::Prefixed).unwrap());
+
+        assert!(convert_convention::<CanBeEither>(&mut a, Repr::Nested).unwrap());
+        assert_eq!(a["can_be_either"], json!({"foo": 1, "bar": 2}));
+        assert_eq!(CanBeEither::from_attributes(&a).unwrap(), expected);
+    }
+
+    #[test]
+    fn convert_rejects_non_object() {
+        let mut a = attrs(json!({"can_be_either": 1}));
+        assert!(convert_convention::<CanBeEither>(&mut a, Repr::Prefixed).is_err());
+
+        let original = attrs(json!({"can_be_either": 1, "can_be_either:foo": 2}));
+        let mut a = original.clone();
+        assert!(convert_convention::<CanBeEither>(&mut a, Repr::Nested).is_err());
+        assert_eq!(a, original);
+    }
+}
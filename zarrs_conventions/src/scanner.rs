@@ -0,0 +1,350 @@
+use std::io::Read;
+
+use serde::{
+    Deserialize, Deserializer,
+    de::{DeserializeSeed, IgnoredAny, MapAccess, SeqAccess, Visitor},
+};
+use serde_json::Value;
+
+use crate::{AttributesParser, attributes::InPlace};
+
+/// Parser for many Zarr metadata documents in a row,
+/// e.g. when walking every `zarr.json` in a large hierarchy.
+///
+/// The read buffer and the attributes parser are kept between documents,
+/// with each document's attributes filled into the same parser in place
+/// rather than buffered for a flattened field;
+/// the most recently scanned attributes are borrowed from the scanner.
+///
+/// Where a document has the same keys as the one before it,
+/// as is typical when walking a hierarchy,
+/// the previous document's keys, strings, arrays and objects are reused:
+/// only new keys, growing strings and arrays, the objects' tree nodes,
+/// and the list of conventions allocate.
+///
+/// ```
+/// use zarrs_conventions::MetadataScanner;
+///
+/// let documents: [&[u8]; 2] = [
+///     br#"{"zarr_format": 3, "node_type": "group", "attributes": {"a": 1}}"#,
+///     br#"{"zarr_format": 3, "node_type": "group"}"#,
+/// ];
+///
+/// let mut scanner = MetadataScanner::default();
+/// for doc in documents {
+///     let parser = scanner.scan(doc).unwrap();
+///     let _a: Option<u8> = parser.get("a").unwrap();
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct MetadataScanner {
+    buffer: Vec<u8>,
+    parser: AttributesParser,
+    key: String,
+}
+
+/// Top-level keys of a Zarr metadata document.
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "snake_case")]
+enum Field {
+    Attributes,
+    #[serde(other)]
+    Other,
+}
+
+/// Deserializes a Zarr metadata document, only retaining the attributes.
+struct ScannedMetadata<'a> {
+    parser: &'a mut AttributesParser,
+    key: &'a mut String,
+}
+
+impl<'de> DeserializeSeed<'de> for ScannedMetadata<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for ScannedMetadata<'_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("a Zarr metadata document")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        let mut has_attributes = false;
+        while let Some(field) = map.next_key()? {
+            match field {
+                Field::Attributes => {
+                    has_attributes = true;
+                    map.next_value_seed(InPlace {
+                        parser: &mut *self.parser,
+                        key: &mut *self.key,
+                    })?
+                }
+                Field::Other => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        if !has_attributes {
+            self.parser.clear();
+        }
+        Ok(())
+    }
+}
+
+/// Copies a key into a reused string.
+pub(crate) struct KeyInto<'a>(pub(crate) &'a mut String);
+
+impl<'de> DeserializeSeed<'de> for KeyInto<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl<'de> Visitor<'de> for KeyInto<'_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("a string key")
+    }
+
+    fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<(), E> {
+        self.0.clear();
+        self.0.push_str(v);
+        Ok(())
+    }
+}
+
+/// Deserializes a JSON value into an existing one,
+/// keeping the allocations of strings, arrays, and the keys and values of objects
+/// where the new value has the same shape.
+pub(crate) struct ValueInPlace<'a> {
+    pub(crate) value: &'a mut Value,
+    /// Scratch space for keys, to look up previous fields without allocating.
+    pub(crate) key: &'a mut String,
+}
+
+impl<'de> DeserializeSeed<'de> for ValueInPlace<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for ValueInPlace<'_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("any JSON value")
+    }
+
+    fn visit_unit<E: serde::de::Error>(self) -> Result<(), E> {
+        *self.value = Value::Null;
+        Ok(())
+    }
+
+    fn visit_bool<E: serde::de::Error>(self, v: bool) -> Result<(), E> {
+        *self.value = Value::Bool(v);
+        Ok(())
+    }
+
+    fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<(), E> {
+        *self.value = Value::from(v);
+        Ok(())
+    }
+
+    fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<(), E> {
+        *self.value = Value::from(v);
+        Ok(())
+    }
+
+    fn visit_f64<E: serde::de::Error>(self, v: f64) -> Result<(), E> {
+        *self.value = Value::from(v);
+        Ok(())
+    }
+
+    fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<(), E> {
+        match self.value {
+            Value::String(s) => {
+                s.clear();
+                s.push_str(v);
+            }
+            other => *other = Value::String(v.to_owned()),
+        }
+        Ok(())
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        if !self.value.is_array() {
+            *self.value = Value::Array(Vec::default());
+        }
+        let Value::Array(items) = self.value else {
+            unreachable!("value is an array");
+        };
+        let mut len = 0;
+        loop {
+            let more = match items.get_mut(len) {
+                Some(item) => seq
+                    .next_element_seed(ValueInPlace {
+                        value: item,
+                        key: &mut *self.key,
+                    })?
+                    .is_some(),
+                None => match seq.next_element()? {
+                    Some(item) => {
+                        items.push(item);
+                        true
+                    }
+                    None => false,
+                },
+            };
+            if !more {
+                break;
+            }
+            len += 1;
+        }
+        items.truncate(len);
+        Ok(())
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        if !self.value.is_object() {
+            *self.value = Value::Object(Default::default());
+        }
+        let Value::Object(fields) = self.value else {
+            unreachable!("value is an object");
+        };
+        let mut previous = std::mem::take(fields);
+        while map.next_key_seed(KeyInto(&mut *self.key))?.is_some() {
+            match previous.remove_entry(self.key.as_str()) {
+                Some((k, mut value)) => {
+                    map.next_value_seed(ValueInPlace {
+                        value: &mut value,
+                        key: &mut *self.key,
+                    })?;
+                    fields.insert(k, value);
+                }
+                None => {
+                    let k = self.key.clone();
+                    fields.insert(k, map.next_value()?);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parse a metadata document into the given parser.
+fn scan_into(
+    parser: &mut AttributesParser,
+    key: &mut String,
+    bytes: &[u8],
+) -> serde_json::Result<()> {
+    let mut deserializer = serde_json::Deserializer::from_slice(bytes);
+    ScannedMetadata { parser, key }.deserialize(&mut deserializer)?;
+    deserializer.end()
+}
+
+impl MetadataScanner {
+    /// Create a scanner with the given initial buffer capacity in bytes.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            buffer: Vec::with_capacity(capacity),
+            parser: AttributesParser::default(),
+            key: String::default(),
+        }
+    }
+
+    /// Read a full Zarr metadata document and parse its attributes.
+    ///
+    /// The returned parser is valid until the next call.
+    pub fn scan<R: Read>(&mut self, mut reader: R) -> serde_json::Result<&AttributesParser> {
+        self.buffer.clear();
+        reader
+            .read_to_end(&mut self.buffer)
+            .map_err(serde_json::Error::io)?;
+        scan_into(&mut self.parser, &mut self.key, &self.buffer)?;
+        Ok(&self.parser)
+    }
+
+    /// Parse the attributes of a Zarr metadata document which is already in memory.
+    ///
+    /// The bytes are parsed in place, without going through the read buffer.
+    ///
+    /// The returned parser is valid until the next call.
+    pub fn scan_slice(&mut self, bytes: &[u8]) -> serde_json::Result<&AttributesParser> {
+        scan_into(&mut self.parser, &mut self.key, bytes)?;
+        Ok(&self.parser)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::MetadataScanner;
+    use crate::mock::MustBeNested;
+
+    #[test]
+    fn scan_many() {
+        let with = br#"{
+            "zarr_format": 3,
+            "node_type": "group",
+            "attributes": {
+                "zarr_conventions": [{"uuid": "11111111-1111-1111-1111-111111111111"}],
+                "must_be_nested": {"a": 1, "b": 2}
+            }
+        }"#;
+        let without = br#"{"zarr_format": 3, "node_type": "array"}"#;
+
+        let mut scanner = MetadataScanner::with_capacity(64);
+        let parser = scanner.scan(&with[..]).unwrap();
+        let nested: MustBeNested = parser.parse_nested().unwrap().unwrap();
+        assert_eq!(nested, MustBeNested { a: 1, b: 2 });
+
+        let parser = scanner.scan(&without[..]).unwrap();
+        assert!(!parser.in_use::<MustBeNested>());
+
+        let parser = scanner.scan_slice(with).unwrap();
+        assert!(parser.in_use::<MustBeNested>());
+        assert!(scanner.scan_slice(b"not json").is_err());
+    }
+
+    #[test]
+    fn scan_reuses_previous_values() {
+        let first = br#"{"zarr_format": 3, "node_type": "group", "attributes": {
+            "a": "a long string which is replaced by a short one",
+            "b": [1, {"x": "y"}, 3],
+            "c": {"keep": [1], "drop": true},
+            "stale": "gone"
+        }}"#;
+        let second = br#"{"zarr_format": 3, "node_type": "group", "attributes": {
+            "b": [{"x": "z", "w": null}],
+            "a": "short",
+            "c": {"keep": [2, 3], "new": 1.5},
+            "d": "fresh",
+            "a": ["last", "wins"]
+        }}"#;
+        let expected = json!({
+            "a": ["last", "wins"],
+            "b": [{"x": "z", "w": null}],
+            "c": {"keep": [2, 3], "new": 1.5},
+            "d": "fresh"
+        });
+
+        let mut scanner = MetadataScanner::default();
+        scanner.scan_slice(first).unwrap();
+        let parser = scanner.scan_slice(second).unwrap();
+        for (key, value) in expected.as_object().unwrap() {
+            assert_eq!(&parser.get::<Value>(key).unwrap().unwrap(), value, "{key}");
+        }
+        assert!(parser.get::<Value>("stale").unwrap().is_none());
+    }
+}
@@ -0,0 +1,783 @@
+//! Utilities for testing convention implementations.
+//!
+//! Most convention crates should only need [convention_test_suite!](crate::convention_test_suite).
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+
+use crate::{
+    AttributesBuilder, AttributesParser, DEFAULT_ZARR_CONVENTION_REGISTRY, NestedOrPrefixedRepr,
+    NestedRepr, PrefixedRepr, ZarrConventionImpl, ZarrMetadata,
+};
+
+/// Assert that the convention is registered in the default registry under all of its identifiers.
+pub fn assert_registered<T: ZarrConventionImpl>() {
+    let def = T::DEFINITION;
+    for id in [
+        def.id_uuid().as_id_ref(),
+        def.id_schema_ref(),
+        def.id_spec_ref(),
+    ] {
+        assert_eq!(
+            DEFAULT_ZARR_CONVENTION_REGISTRY.get(id),
+            Some(def),
+            "convention '{}' not registered under {id:?}",
+            def.name
+        );
+    }
+}
+
+/// Assert that the convention is not in the default registry under any of its identifiers,
+/// e.g. because its identifiers are not yet published.
+pub fn assert_not_registered<T: ZarrConventionImpl>() {
+    let def = T::DEFINITION;
+    for id in [
+        def.id_uuid().as_id_ref(),
+        def.id_schema_ref(),
+        def.id_spec_ref(),
+    ] {
+        assert!(
+            !DEFAULT_ZARR_CONVENTION_REGISTRY.contains(id),
+            "convention '{}' unexpectedly registered under {id:?}",
+            def.name
+        );
+    }
+}
+
+/// Assert that the `schema.json` and `README.md` generated by the docs are up to date in the given directory.
+///
+/// If the `UPDATE_SPEC` environment variable is set, the files are written instead.
+#[cfg(feature = "schema")]
+pub fn assert_docs_up_to_date(docs: &crate::docgen::ConventionDocs, dir: impl AsRef<Path>) {
+    let dir = dir.as_ref();
+    if std::env::var_os("UPDATE_SPEC").is_some() {
+        std::fs::create_dir_all(dir).expect("should create spec directory");
+        docs.write_to(dir).expect("should write spec");
+        return;
+    }
+    for (name, expected) in docs.files() {
+        let path = dir.join(name);
+        let actual = std::fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("could not read {}: {e}", path.display()));
+        assert!(
+            actual == expected,
+            "{} is out of date; rerun the test with UPDATE_SPEC=1 to regenerate it",
+            path.display()
+        );
+    }
+}
+
+fn assert_same_json<T: Serialize>(expected: &T, actual: &T) {
+    assert_eq!(
+        serde_json::to_value(expected).expect("should serialize"),
+        serde_json::to_value(actual).expect("should serialize"),
+    );
+}
+
+fn build_parser(builder: AttributesBuilder) -> (serde_json::Value, AttributesParser) {
+    let attrs = builder.build().expect("attributes should build");
+    let parser = serde_json::from_value(attrs.clone()).expect("attributes should parse");
+    (attrs, parser)
+}
+
+/// Write the value in nested form with [AttributesBuilder] and read it back with [AttributesParser],
+/// asserting that it is unchanged.
+///
+/// Returns the written attributes.
+pub fn assert_roundtrip_nested<T: NestedRepr>(value: &T) -> serde_json::Value {
+    let mut builder = AttributesBuilder::default();
+    builder.add_nested(value).expect("should add nested");
+    let (attrs, parser) = build_parser(builder);
+    assert!(parser.in_use::<T>());
+    let parsed: T = parser
+        .parse_nested()
+        .expect("should parse nested")
+        .expect("should be in use");
+    assert_same_json(value, &parsed);
+    attrs
+}
+
+/// Write the value in prefixed form with [AttributesBuilder] and read it back with [AttributesParser],
+/// asserting that it is unchanged.
+///
+/// Returns the written attributes.
+pub fn assert_roundtrip_prefixed<T: PrefixedRepr>(value: &T) -> serde_json::Value {
+    let mut builder = AttributesBuilder::default();
+    builder.add_prefixed(value).expect("should add prefixed");
+    let (attrs, parser) = build_parser(builder);
+    assert!(parser.in_use::<T>());
+    let parsed: T = parser
+        .parse_prefixed()
+        .expect("should parse prefixed")
+        .expect("should be in use");
+    assert_same_json(value, &parsed);
+    attrs
+}
+
+/// Paths of all JSON files in a directory, sorted.
+///
+/// Panics if the directory cannot be read:
+/// for convention crates, this usually means the `spec` git submodule is not checked out.
+pub fn json_files(dir: impl AsRef<Path>) -> Vec<PathBuf> {
+    let dir = dir.as_ref();
+    let mut paths: Vec<_> = std::fs::read_dir(dir)
+        .unwrap_or_else(|e| {
+            panic!(
+                "could not read {}: {e} (is the spec submodule checked out?)",
+                dir.display()
+            )
+        })
+        .map(|entry| entry.expect("should read directory entry").path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    paths.sort();
+    paths
+}
+
+/// Parse every Zarr metadata document in a directory,
+/// asserting that each declares the convention and that its payload can be read by `parse`.
+///
+/// Returns the attributes of each document.
+pub fn assert_examples<T, F>(dir: impl AsRef<Path>, parse: F) -> Vec<serde_json::Value>
+where
+    T: ZarrConventionImpl,
+    F: Fn(&AttributesParser) -> serde_json::Result<Option<T>>,
+{
+    let paths = json_files(dir);
+    assert!(!paths.is_empty(), "no examples found");
+    paths
+        .into_iter()
+        .map(|path| {
+            let bytes = std::fs::read(&path).expect("should read example");
+            check_example(&path.display().to_string(), &bytes, &parse)
+        })
+        .collect()
+}
+
+/// As [assert_examples], for examples embedded with [include_examples!](crate::include_examples).
+pub fn assert_embedded_examples<T, F>(examples: &[Example], parse: F) -> Vec<serde_json::Value>
+where
+    T: ZarrConventionImpl,
+    F: Fn(&AttributesParser) -> serde_json::Result<Option<T>>,
+{
+    assert!(!examples.is_empty(), "no examples found");
+    examples
+        .iter()
+        .map(|ex| check_example(ex.name, ex.json.as_bytes(), &parse))
+        .collect()
+}
+
+/// Read the convention from an example Zarr metadata document,
+/// e.g. to use as the `example` of [convention_test_suite!](crate::convention_test_suite).
+///
+/// ```ignore
+/// let example = parse_example(include_str!("examples/mit.json"), parse_nested::<License>);
+/// ```
+pub fn parse_example<T, F>(json: &str, parse: F) -> T
+where
+    T: ZarrConventionImpl,
+    F: Fn(&AttributesParser) -> serde_json::Result<Option<T>>,
+{
+    parse_modified_example(json, |_| (), parse)
+        .expect("example should have a valid payload")
+        .expect("example should have a payload")
+}
+
+/// Read the convention from an example Zarr metadata document
+/// after modifying its attributes, e.g. to check that invalid metadata is rejected.
+///
+/// ```ignore
+/// let parsed = parse_modified_example(
+///     include_str!("examples/mit.json"),
+///     |attrs| attrs["license"] = "MIT".into(),
+///     parse_nested::<License>,
+/// );
+/// assert!(parsed.is_err());
+/// ```
+pub fn parse_modified_example<T, F>(
+    json: &str,
+    modify: impl FnOnce(&mut serde_json::Value),
+    parse: F,
+) -> serde_json::Result<Option<T>>
+where
+    T: ZarrConventionImpl,
+    F: Fn(&AttributesParser) -> serde_json::Result<Option<T>>,
+{
+    let attrs = serde_json::from_str::<ZarrMetadata>(json)
+        .expect("example should be valid metadata")
+        .attributes;
+    let mut attrs = serde_json::Value::Object(attrs);
+    modify(&mut attrs);
+    parse(&serde_json::from_value(attrs)?)
+}
+
+fn check_example<T, F>(label: &str, bytes: &[u8], parse: &F) -> serde_json::Value
+where
+    T: ZarrConventionImpl,
+    F: Fn(&AttributesParser) -> serde_json::Result<Option<T>>,
+{
+    let attrs = serde_json::from_slice::<ZarrMetadata>(bytes)
+        .unwrap_or_else(|e| panic!("{label} is not valid metadata: {e}"))
+        .attributes;
+    let value = serde_json::Value::Object(attrs);
+    let parser: AttributesParser = serde_json::from_value(value.clone())
+        .unwrap_or_else(|e| panic!("{label} has invalid attributes: {e}"));
+    assert!(
+        parser.in_use::<T>(),
+        "{label} does not declare convention '{}'",
+        T::DEFINITION.name
+    );
+    parse(&parser)
+        .unwrap_or_else(|e| panic!("{label} has an invalid payload: {e}"))
+        .unwrap_or_else(|| panic!("{label} has no payload"));
+    value
+}
+
+/// A JSON example document embedded by [include_examples!](crate::include_examples).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Example {
+    /// File name of the example.
+    pub name: &'static str,
+    /// Contents of the example, known to be valid JSON.
+    pub json: &'static str,
+}
+
+impl Example {
+    /// Parse the example.
+    pub fn value(&self) -> serde_json::Value {
+        serde_json::from_str(self.json).expect("embedded example should be valid JSON")
+    }
+}
+
+/// Embed all `*.json` files in a directory (relative to the calling crate's manifest)
+/// as a `&'static [Example]`, sorted by file name.
+///
+/// Fails to compile if the directory cannot be read or any file is not valid JSON.
+/// Once compiled, tests using the examples need no access to the files,
+/// e.g. when running the tests of a packaged crate.
+///
+/// Edits to embedded files trigger a rebuild, but Cargo does not track the directory listing:
+/// after adding or removing an example, run `cargo clean -p <crate>`
+/// (or touch the file invoking the macro) so that the list is read again.
+///
+/// ```ignore
+/// let examples: &[Example] = zarrs_conventions::include_examples!("spec/examples");
+/// zarrs_conventions::test_util::assert_embedded_examples(examples, parse_nested::<License>);
+/// ```
+#[macro_export]
+macro_rules! include_examples {
+    ($dir:literal) => {
+        $crate::__private::include_examples!($crate, $dir)
+    };
+}
+
+/// Assert that an instance is valid against the JSON Schema at the given path.
+pub fn assert_valid_against_schema(schema_path: impl AsRef<Path>, instance: &serde_json::Value) {
+    let schema_path = schema_path.as_ref();
+    let schema: serde_json::Value = serde_json::from_slice(
+        &std::fs::read(schema_path)
+            .unwrap_or_else(|e| panic!("could not read {}: {e}", schema_path.display())),
+    )
+    .expect("schema should be JSON");
+    let validator = jsonschema::validator_for(&schema).expect("schema should be valid");
+    let errors: Vec<String> = validator
+        .iter_errors(instance)
+        .map(|e| format!("{} at {}", e, e.instance_path()))
+        .collect();
+    assert!(
+        errors.is_empty(),
+        "instance does not match {}:\n{}",
+        schema_path.display(),
+        errors.join("\n")
+    );
+}
+
+/// Parser to use in [assert_examples] for conventions which may be represented in either form.
+pub fn parse_either<T: NestedOrPrefixedRepr>(
+    parser: &AttributesParser,
+) -> serde_json::Result<Option<T>> {
+    parser.parse()
+}
+
+/// Parser to use in [assert_examples] for conventions represented in nested form.
+pub fn parse_nested<T: NestedRepr>(parser: &AttributesParser) -> serde_json::Result<Option<T>> {
+    parser.parse_nested()
+}
+
+/// Parser to use in [assert_examples] for conventions represented in prefixed form.
+pub fn parse_prefixed<T: PrefixedRepr>(parser: &AttributesParser) -> serde_json::Result<Option<T>> {
+    parser.parse_prefixed()
+}
+
+/// In-memory Zarr hierarchy, mapping node paths to metadata documents.
+///
+/// Lets doctests demonstrate annotating and parsing nodes without a Zarr implementation.
+/// Paths are `/`-separated and normalised to have no leading or trailing slash,
+/// so the root group is `""`.
+/// Adding a node also adds any missing ancestors as groups without attributes.
+///
+/// ```
+/// use zarrs_conventions::{AttributesBuilder, test_util::MemoryStore};
+///
+/// let mut builder = AttributesBuilder::default();
+/// builder.add_attribute("answer", 42).unwrap();
+/// let store = MemoryStore::default().array("/a/b", builder.build().unwrap());
+///
+/// assert_eq!(store.paths().collect::<Vec<_>>(), ["", "a", "a/b"]);
+/// let parser = store.parser("a/b").unwrap().unwrap();
+/// assert_eq!(parser.get::<u8>("answer").unwrap(), Some(42));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStore {
+    nodes: BTreeMap<String, serde_json::Value>,
+}
+
+impl MemoryStore {
+    fn normalise(path: &str) -> String {
+        path.split('/')
+            .filter(|p| !p.is_empty())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// Add a group with the given attributes, replacing any existing node.
+    pub fn group(mut self, path: &str, attributes: serde_json::Value) -> Self {
+        self.insert(path, Self::metadata("group", attributes));
+        self
+    }
+
+    /// Add a minimal array with the given attributes, replacing any existing node.
+    pub fn array(mut self, path: &str, attributes: serde_json::Value) -> Self {
+        self.insert(path, Self::metadata("array", attributes));
+        self
+    }
+
+    fn metadata(node_type: &str, attributes: serde_json::Value) -> serde_json::Value {
+        let mut doc = serde_json::json!({"zarr_format": 3, "node_type": node_type});
+        if node_type == "array" {
+            doc["shape"] = serde_json::json!([1]);
+            doc["data_type"] = "uint8".into();
+            doc["chunk_grid"] =
+                serde_json::json!({"name": "regular", "configuration": {"chunk_shape": [1]}});
+            doc["chunk_key_encoding"] = serde_json::json!({"name": "default"});
+            doc["fill_value"] = 0.into();
+            doc["codecs"] = serde_json::json!([{"name": "bytes"}]);
+        }
+        doc["attributes"] = attributes;
+        doc
+    }
+
+    /// Add a full metadata document, replacing any existing node.
+    pub fn insert(&mut self, path: &str, metadata: serde_json::Value) -> &mut Self {
+        let path = Self::normalise(path);
+        let mut ancestor = path.as_str();
+        while let Some((parent, _)) = ancestor.rsplit_once('/') {
+            self.ensure_group(parent);
+            ancestor = parent;
+        }
+        if !path.is_empty() {
+            self.ensure_group("");
+        }
+        self.nodes.insert(path, metadata);
+        self
+    }
+
+    fn ensure_group(&mut self, path: &str) {
+        if !self.nodes.contains_key(path) {
+            self.nodes.insert(
+                path.to_string(),
+                Self::metadata("group", serde_json::json!({})),
+            );
+        }
+    }
+
+    /// All node paths, in sorted order.
+    pub fn paths(&self) -> impl Iterator<Item = &str> {
+        self.nodes.keys().map(String::as_str)
+    }
+
+    /// The metadata document at this path.
+    pub fn get(&self, path: &str) -> Option<&serde_json::Value> {
+        self.nodes.get(&Self::normalise(path))
+    }
+
+    /// The metadata document at this path, serialised as `zarr.json` would be.
+    pub fn zarr_json(&self, path: &str) -> Option<String> {
+        self.get(path)
+            .map(|v| serde_json::to_string_pretty(v).expect("JSON should serialize"))
+    }
+
+    /// Parse the attributes of the node at this path.
+    ///
+    /// None if there is no such node.
+    pub fn parser(&self, path: &str) -> Option<serde_json::Result<AttributesParser>> {
+        self.get(path).map(|doc| {
+            let metadata: ZarrMetadata = serde_json::from_value(doc.clone())?;
+            serde_json::from_value(serde_json::Value::Object(metadata.attributes))
+        })
+    }
+}
+
+/// Placeholder for redacted values in snapshots.
+pub const REDACTED: &str = "[redacted]";
+
+/// Canonical serialization of attributes for snapshot testing (e.g. with `insta::assert_snapshot!`).
+///
+/// Object keys are sorted, the `zarr_conventions` list is sorted,
+/// and any configured volatile fields are replaced by [REDACTED].
+///
+/// ```
+/// use zarrs_conventions::test_util::Snapshot;
+///
+/// let attrs = serde_json::json!({"b": 1, "a": {"created": "2025-01-01T00:00:00Z"}});
+/// let s = Snapshot::default().redact_key("created").to_string(&attrs);
+/// assert_eq!(s, "{\n  \"a\": {\n    \"created\": \"[redacted]\"\n  },\n  \"b\": 1\n}");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    redact_keys: Vec<String>,
+    redact_pointers: Vec<String>,
+    sort_conventions: bool,
+}
+
+impl Default for Snapshot {
+    fn default() -> Self {
+        Self {
+            redact_keys: Vec::default(),
+            redact_pointers: Vec::default(),
+            sort_conventions: true,
+        }
+    }
+}
+
+impl Snapshot {
+    /// Redact the values of all object members with this key, at any depth.
+    pub fn redact_key(mut self, key: impl Into<String>) -> Self {
+        self.redact_keys.push(key.into());
+        self
+    }
+
+    /// Redact the value at this JSON pointer (e.g. `"/thumbnails/0/url"`), if present.
+    pub fn redact_pointer(mut self, pointer: impl Into<String>) -> Self {
+        self.redact_pointers.push(pointer.into());
+        self
+    }
+
+    /// Whether to sort the top-level `zarr_conventions` list (default true).
+    pub fn sort_conventions(mut self, enable: bool) -> Self {
+        self.sort_conventions = enable;
+        self
+    }
+
+    /// Produce the canonical form of the value.
+    pub fn canonicalize(&self, value: &serde_json::Value) -> serde_json::Value {
+        let mut out = self.canonicalize_inner(value);
+        for pointer in &self.redact_pointers {
+            if let Some(v) = out.pointer_mut(pointer) {
+                *v = REDACTED.into();
+            }
+        }
+        if self.sort_conventions
+            && let Some(serde_json::Value::Array(conventions)) = out.get_mut("zarr_conventions")
+        {
+            conventions.sort_by_cached_key(|c| c.to_string());
+        }
+        out
+    }
+
+    fn canonicalize_inner(&self, value: &serde_json::Value) -> serde_json::Value {
+        match value {
+            serde_json::Value::Object(map) => {
+                let mut entries: Vec<_> = map.iter().collect();
+                entries.sort_by_key(|(k, _)| *k);
+                serde_json::Value::Object(
+                    entries
+                        .into_iter()
+                        .map(|(k, v)| {
+                            let v = if self.redact_keys.contains(k) {
+                                REDACTED.into()
+                            } else {
+                                self.canonicalize_inner(v)
+                            };
+                            (k.clone(), v)
+                        })
+                        .collect(),
+                )
+            }
+            serde_json::Value::Array(items) => {
+                serde_json::Value::Array(items.iter().map(|v| self.canonicalize_inner(v)).collect())
+            }
+            other => other.clone(),
+        }
+    }
+
+    /// Canonical form of the value as pretty-printed JSON.
+    pub fn to_string(&self, value: &serde_json::Value) -> String {
+        serde_json::to_string_pretty(&self.canonicalize(value)).expect("JSON should serialize")
+    }
+}
+
+/// Generate the standard battery of tests for a convention, in a module named `convention_test_suite`.
+///
+/// - the convention is registered in the default registry,
+///   or with `registered = false`, that it is not
+/// - an example value round-trips through [AttributesBuilder] and [AttributesParser]
+///   in the given representation (`nested`, `prefixed`, or `both`)
+/// - every example document in the given directory (default `spec/examples`, relative to the crate root)
+///   declares the convention and parses;
+///   the examples are embedded with [include_examples!](crate::include_examples) when the test is compiled,
+///   so running it needs no access to the files,
+///   but adding or removing an example needs a rebuild (see [include_examples!](crate::include_examples));
+///   the test fails if the directory is missing at compile time (e.g. the `spec` submodule is not checked out)
+/// - if `schema` is given (relative to the crate root),
+///   the round-tripped attributes and all examples validate against it;
+///   the schema is read when the tests run, and they fail if it is missing
+///
+/// With `optional_spec = true`, a missing examples directory or schema is skipped with a message instead,
+/// for crates whose `spec` is a git submodule which may not be checked out.
+///
+/// Typically invoked in an integration test, with `zarrs_conventions`'s `test-util` feature
+/// enabled in the crate's dev-dependencies.
+///
+/// ```ignore
+/// zarrs_conventions::convention_test_suite! {
+///     License,
+///     repr = nested,
+///     example = License::new_spdx("MIT"),
+///     schema = "spec/schema.json",
+/// }
+/// ```
+#[macro_export]
+macro_rules! convention_test_suite {
+    (@roundtrip nested, $value:expr) => {
+        vec![$crate::test_util::assert_roundtrip_nested($value)]
+    };
+    (@roundtrip prefixed, $value:expr) => {
+        vec![$crate::test_util::assert_roundtrip_prefixed($value)]
+    };
+    (@roundtrip both, $value:expr) => {
+        vec![
+            $crate::test_util::assert_roundtrip_nested($value),
+            $crate::test_util::assert_roundtrip_prefixed($value),
+        ]
+    };
+    (@parser nested, $convention:ty) => {
+        $crate::test_util::parse_nested::<$convention>
+    };
+    (@parser prefixed, $convention:ty) => {
+        $crate::test_util::parse_prefixed::<$convention>
+    };
+    (@parser both, $convention:ty) => {
+        $crate::test_util::parse_either::<$convention>
+    };
+    (@examples) => {
+        $crate::__private::include_examples!($crate, "spec/examples", optional)
+    };
+    (@examples $dir:literal) => {
+        $crate::__private::include_examples!($crate, $dir, optional)
+    };
+    (@examples_dir) => {
+        "spec/examples"
+    };
+    (@examples_dir $dir:literal) => {
+        $dir
+    };
+    (
+        $convention:ty,
+        repr = $repr:ident,
+        example = $example:expr
+        $(, examples = $examples:literal)?
+        $(, schema = $schema:literal)?
+        $(, optional_spec = $optional:literal)?
+        $(, registered = $registered:literal)?
+        $(,)?
+    ) => {
+        #[cfg(test)]
+        mod convention_test_suite {
+            #[allow(unused_imports)]
+            use super::*;
+
+            fn optional() -> bool {
+                let optional = false;
+                $( let optional = $optional; )?
+                optional
+            }
+
+            #[allow(unused)]
+            fn schema_path() -> Option<::std::path::PathBuf> {
+                let path: Option<&str> = None;
+                $( let path = Some($schema); )?
+                let path = ::std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(path?);
+                if optional() && !path.is_file() {
+                    eprintln!(
+                        "skipping schema validation: {} is missing \
+                         (is the spec submodule checked out?)",
+                        path.display()
+                    );
+                    return None;
+                }
+                Some(path)
+            }
+
+            #[test]
+            fn registered() {
+                let registered = true;
+                $( let registered = $registered; )?
+                if registered {
+                    $crate::test_util::assert_registered::<$convention>();
+                } else {
+                    $crate::test_util::assert_not_registered::<$convention>();
+                }
+            }
+
+            #[test]
+            fn roundtrip() {
+                let example: $convention = $example;
+                let attrs = $crate::convention_test_suite!(@roundtrip $repr, &example);
+                if let Some(schema) = schema_path() {
+                    for a in attrs {
+                        $crate::test_util::assert_valid_against_schema(&schema, &a);
+                    }
+                }
+            }
+
+            #[test]
+            fn examples() {
+                let Some(examples) = $crate::convention_test_suite!(@examples $($examples)?) else {
+                    let dir = $crate::convention_test_suite!(@examples_dir $($examples)?);
+                    if optional() {
+                        eprintln!(
+                            "skipping examples: {dir} could not be read when the test was compiled \
+                             (is the spec submodule checked out?)",
+                        );
+                        return;
+                    }
+                    panic!(
+                        "{dir} could not be read when the test was compiled \
+                         (is the spec submodule checked out?)"
+                    );
+                };
+                let attrs = $crate::test_util::assert_embedded_examples(
+                    examples,
+                    $crate::convention_test_suite!(@parser $repr, $convention),
+                );
+                if let Some(schema) = schema_path() {
+                    for a in attrs {
+                        $crate::test_util::assert_valid_against_schema(&schema, &a);
+                    }
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{
+        Example, MemoryStore, REDACTED, Snapshot, assert_embedded_examples,
+        assert_roundtrip_nested, assert_roundtrip_prefixed, assert_valid_against_schema,
+    };
+    use crate::mock::{CanBeEither, MustBeNested};
+
+    #[test]
+    fn embedded_examples() {
+        let examples: &[Example] = include_examples!("tests/data");
+        assert_eq!(examples[0].name, "must_be_nested.json");
+        let attrs = assert_embedded_examples(examples, super::parse_nested::<MustBeNested>);
+        assert_eq!(attrs[0]["must_be_nested"]["b"], 2);
+        assert_eq!(examples[0].value()["attributes"], attrs[0]);
+
+        let optional = crate::__private::include_examples!(crate, "tests/data", optional);
+        assert_eq!(optional, Some(examples));
+        let missing: Option<&[Example]> =
+            crate::__private::include_examples!(crate, "tests/missing", optional);
+        assert!(missing.is_none());
+    }
+
+    #[test]
+    fn parse_example_payload() {
+        let example: &[Example] = include_examples!("tests/data");
+        let value: MustBeNested = super::parse_example(example[0].json, super::parse_nested);
+        assert_eq!(value, MustBeNested { a: 1, b: 2 });
+        let modified = super::parse_modified_example(
+            example[0].json,
+            |attrs| attrs["must_be_nested"]["b"] = "two".into(),
+            super::parse_nested::<MustBeNested>,
+        );
+        assert!(modified.is_err());
+    }
+
+    #[test]
+    fn memory_store_nested() {
+        let mut builder = crate::AttributesBuilder::default();
+        builder.add_nested(&MustBeNested { a: 1, b: 2 }).unwrap();
+        let mut store = MemoryStore::default().group("/", json!({"root": true}));
+        store.insert("x/y/", json!({"zarr_format": 3, "node_type": "group"}));
+        let store = store.array("x/y/z", builder.build().unwrap());
+
+        assert_eq!(store.paths().collect::<Vec<_>>(), ["", "x", "x/y", "x/y/z"]);
+        assert_eq!(
+            store.get("").unwrap().get("attributes"),
+            Some(&json!({"root": true}))
+        );
+        let parser = store.parser("/x/y/z").unwrap().unwrap();
+        assert_eq!(
+            parser.parse_nested::<MustBeNested>().unwrap(),
+            Some(MustBeNested { a: 1, b: 2 })
+        );
+        assert!(
+            store
+                .parser("x/y")
+                .unwrap()
+                .unwrap()
+                .get::<u8>("a")
+                .unwrap()
+                .is_none()
+        );
+        assert!(store.parser("missing").is_none());
+    }
+
+    #[test]
+    fn snapshot_canonical() {
+        let a = json!({
+            "zarr_conventions": [{"uuid": "b"}, {"uuid": "a"}],
+            "z": {"y": 1, "x": [{"secret": 1}]},
+            "volatile": 5
+        });
+        let b = json!({
+            "volatile": 6,
+            "z": {"x": [{"secret": 2}], "y": 1},
+            "zarr_conventions": [{"uuid": "a"}, {"uuid": "b"}]
+        });
+        let snap = Snapshot::default()
+            .redact_key("secret")
+            .redact_pointer("/volatile");
+        assert_eq!(snap.to_string(&a), snap.to_string(&b));
+        assert_eq!(snap.canonicalize(&a)["z"]["x"][0]["secret"], REDACTED);
+    }
+
+    #[test]
+    fn roundtrip_and_schema() {
+        let attrs = assert_roundtrip_nested(&MustBeNested { a: 1, b: 2 });
+        assert_roundtrip_prefixed(&CanBeEither { foo: 3, bar: 4 });
+
+        let schema = json!({
+            "type": "object",
+            "required": ["zarr_conventions", "must_be_nested"],
+            "properties": {"must_be_nested": {"required": ["a", "b"]}}
+        });
+        // unique per process, so that concurrent test runs do not share the file
+        let path = std::env::temp_dir().join(format!(
+            "zarrs_conventions_test_util_roundtrip_and_schema_{}.json",
+            std::process::id()
+        ));
+        std::fs::write(&path, schema.to_string()).unwrap();
+        assert_valid_against_schema(&path, &attrs);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
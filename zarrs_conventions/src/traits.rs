@@ -1,12 +1,14 @@
+use std::collections::BTreeMap;
+
 use serde::{
     Serialize,
-    de::{DeserializeOwned, Error},
+    de::{DeserializeOwned, Error, value::MapDeserializer},
 };
 
 use crate::{
     Attributes, ZarrConventions,
     convention::{Convention, ConventionDefinition},
-    nest_prefixed,
+    prefixed::PrefixedSerializer,
 };
 
 /// Types should also implement at least one of [NestedRepr] and [PrefixedRepr].
@@ -44,25 +46,27 @@ pub trait PrefixedRepr: ZarrConventionImpl + DeserializeOwned + Serialize {
     const PREFIX: &'static str;
 
     /// Read the convention metadata in prefixed form from an attribute map.
+    ///
+    /// Values are deserialized in place, without building an intermediate nested object.
     fn from_attributes_prefixed(attributes: &Attributes) -> serde_json::Result<Self> {
-        let nested = nest_prefixed(Self::PREFIX, attributes, Default::default());
-        serde_json::from_value(nested)
+        let entries = attributes
+            .iter()
+            .filter_map(|(k, v)| Some((k.strip_prefix(Self::PREFIX)?, v)));
+        Self::deserialize(MapDeserializer::<_, serde_json::Error>::new(entries))
     }
 
     /// Write the convention metadata into an attribute map in prefixed form.
+    ///
+    /// Fields are serialized directly into prefixed entries, without building an intermediate nested object.
+    /// The map is unchanged if serialization fails.
     fn to_attributes_prefixed(&self, output: &mut Attributes) -> serde_json::Result<()> {
-        let value = serde_json::to_value(self)?;
-        match value {
-            serde_json::Value::Object(map) => {
-                for (k, v) in map {
-                    output.insert(format!("{}{}", Self::PREFIX, k), v);
-                }
-                Ok(())
-            }
-            _ => Err(serde_json::Error::custom(
-                "Prefixed representation must serialize to a JSON object",
-            )),
-        }
+        let mut entries = Vec::default();
+        self.serialize(PrefixedSerializer {
+            prefix: Self::PREFIX,
+            entries: &mut entries,
+        })?;
+        output.extend(entries);
+        Ok(())
     }
 }
 
@@ -84,16 +88,15 @@ pub trait NestedRepr: ZarrConventionImpl + DeserializeOwned + Serialize {
 
     /// Read the convention metadata in nested form from an attributes map.
     fn from_attributes_nested(attributes: &Attributes) -> serde_json::Result<Self> {
-        let cloned = attributes
-            .get(Self::KEY)
-            .ok_or_else(|| {
-                serde_json::Error::custom(format!("Zarr convention key not found: '{}'", Self::KEY))
-            })?
-            .clone();
-        serde_json::from_value(cloned)
+        let value = attributes.get(Self::KEY).ok_or_else(|| {
+            serde_json::Error::custom(format!("Zarr convention key not found: '{}'", Self::KEY))
+        })?;
+        Self::deserialize(value)
     }
 
     /// Write the convention metadata to an attributes map in nested form.
+    ///
+    /// The serialized value is inserted as it is, so no intermediate object is built.
     fn to_attributes_nested(&self, output: &mut Attributes) -> serde_json::Result<()> {
         let value = serde_json::to_value(self)?;
         output.insert(Self::KEY.to_string(), value);
@@ -110,14 +113,22 @@ pub trait NestedOrPrefixedRepr: NestedRepr + PrefixedRepr {
     /// Read convention metadata from an attributes map,
     /// in either prefixed or nested form, or a combination.
     fn from_attributes(attributes: &Attributes) -> serde_json::Result<Self> {
-        if let Some(cloned) = attributes.get(Self::KEY).cloned() {
-            if let serde_json::Value::Object(m) = cloned {
-                serde_json::from_value(nest_prefixed(Self::PREFIX, attributes, m))
-            } else {
-                serde_json::from_value(cloned)
+        match attributes.get(Self::KEY) {
+            Some(serde_json::Value::Object(m)) => {
+                // Borrow rather than clone; prefixed keys override nested ones.
+                let mut entries: BTreeMap<&str, &serde_json::Value> =
+                    m.iter().map(|(k, v)| (k.as_str(), v)).collect();
+                entries.extend(
+                    attributes
+                        .iter()
+                        .filter_map(|(k, v)| Some((k.strip_prefix(Self::PREFIX)?, v))),
+                );
+                Self::deserialize(MapDeserializer::<_, serde_json::Error>::new(
+                    entries.into_iter(),
+                ))
             }
-        } else {
-            Self::from_attributes_prefixed(attributes)
+            Some(value) => Self::deserialize(value),
+            None => Self::from_attributes_prefixed(attributes),
         }
     }
 }
@@ -231,6 +242,79 @@ mod tests {
         assert_eq!(proj_from_nested, proj_from_flat);
     }
 
+    #[test]
+    fn from_attributes_prefixed_overrides_nested() {
+        let attrs: Attributes = into_object(json!({
+            "zarr_conventions": make_zarr_conventions(),
+            "proj": {"code": "EPSG:1234"},
+            "proj:code": "EPSG:4326"
+        }));
+        let proj = Proj::from_attributes(&attrs).unwrap();
+        assert_eq!(proj, make_expected());
+    }
+
+    #[test]
+    fn to_attributes_prefixed_matches_value() {
+        #[derive(Serialize)]
+        struct Flattened {
+            code: Option<String>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            skipped: Option<u8>,
+            #[serde(flatten)]
+            extras: serde_json::Map<String, serde_json::Value>,
+        }
+
+        #[derive(Serialize)]
+        enum Variants {
+            Newtype(u8),
+            Tuple(u8, u8),
+            Struct { a: u8 },
+            Unit,
+        }
+
+        fn prefixed(value: &impl Serialize) -> serde_json::Result<Attributes> {
+            let mut out = into_object(json!({"other": 1}));
+            let mut entries = Vec::default();
+            value.serialize(crate::prefixed::PrefixedSerializer {
+                prefix: "p:",
+                entries: &mut entries,
+            })?;
+            out.extend(entries);
+            Ok(out)
+        }
+
+        let flattened = Flattened {
+            code: None,
+            skipped: None,
+            extras: into_object(json!({"x": [1, {"y": 2}]})),
+        };
+        assert_eq!(
+            prefixed(&flattened).unwrap(),
+            into_object(json!({"other": 1, "p:code": null, "p:x": [1, {"y": 2}]}))
+        );
+        assert_eq!(
+            prefixed(&Some(Variants::Newtype(1))).unwrap()["p:Newtype"],
+            1
+        );
+        assert_eq!(
+            prefixed(&Variants::Tuple(1, 2)).unwrap()["p:Tuple"],
+            json!([1, 2])
+        );
+        assert_eq!(
+            prefixed(&Variants::Struct { a: 1 }).unwrap()["p:Struct"],
+            json!({"a": 1})
+        );
+        assert!(prefixed(&Variants::Unit).is_err());
+        assert!(prefixed(&[1]).is_err());
+
+        let mut attrs = into_object(json!({"other": 1}));
+        make_expected().to_attributes_prefixed(&mut attrs).unwrap();
+        assert_eq!(
+            attrs,
+            into_object(json!({"other": 1, "proj:code": "EPSG:4326"}))
+        );
+    }
+
     #[ctor]
     fn register_proj() {
         crate::DEFAULT_ZARR_CONVENTION_REGISTRY.register::<Proj>();
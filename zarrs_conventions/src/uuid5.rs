@@ -0,0 +1,178 @@
+//! Deterministic convention UUIDs derived from specification URLs.
+use uuid::Uuid;
+
+/// Namespace in which convention UUIDs are derived from their specification URLs;
+/// itself the v5 UUID of `https://github.com/zarr-conventions` in the URL namespace.
+pub const CONVENTION_UUID_NAMESPACE: Uuid =
+    uuid_v5(Uuid::NAMESPACE_URL, "https://github.com/zarr-conventions");
+
+/// Derive a convention's UUID from its specification URL,
+/// as a v5 UUID within [CONVENTION_UUID_NAMESPACE].
+///
+/// Usually called via the [convention_uuid!](crate::convention_uuid) macro,
+/// which guarantees compile-time evaluation.
+pub const fn convention_uuid(spec_url: &str) -> Uuid {
+    uuid_v5(CONVENTION_UUID_NAMESPACE, spec_url)
+}
+
+/// Name-based (SHA-1) UUID as defined by RFC 9562, usable in const contexts.
+///
+/// ```
+/// use zarrs_conventions::{uuid::{Uuid, uuid}, uuid5::uuid_v5};
+///
+/// const ID: Uuid = uuid_v5(Uuid::NAMESPACE_DNS, "python.org");
+/// assert_eq!(ID, uuid!("886313e1-3b8a-5372-9b90-0c9aee199e5d"));
+/// ```
+pub const fn uuid_v5(namespace: Uuid, name: &str) -> Uuid {
+    let hash = sha1(namespace.as_bytes(), name.as_bytes());
+    let mut bytes = [0u8; 16];
+    let mut i = 0;
+    while i < 16 {
+        bytes[i] = hash[i];
+        i += 1;
+    }
+    bytes[6] = (bytes[6] & 0x0f) | 0x50;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    Uuid::from_bytes(bytes)
+}
+
+/// Byte `i` of the padded SHA-1 message formed by concatenating `a` and `b`.
+const fn padded_byte(a: &[u8], b: &[u8], padded_len: usize, i: usize) -> u8 {
+    let len = a.len() + b.len();
+    if i < a.len() {
+        a[i]
+    } else if i < len {
+        b[i - a.len()]
+    } else if i == len {
+        0x80
+    } else if i >= padded_len - 8 {
+        ((len as u64 * 8) >> ((padded_len - 1 - i) * 8)) as u8
+    } else {
+        0
+    }
+}
+
+/// SHA-1 digest of the concatenation of `a` and `b`.
+const fn sha1(a: &[u8], b: &[u8]) -> [u8; 20] {
+    let len = a.len() + b.len();
+    let padded_len = (len + 9).div_ceil(64) * 64;
+
+    let mut h: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+    let mut block = 0;
+    while block < padded_len {
+        let mut w = [0u32; 80];
+        let mut t = 0;
+        while t < 16 {
+            let i = block + t * 4;
+            w[t] = u32::from_be_bytes([
+                padded_byte(a, b, padded_len, i),
+                padded_byte(a, b, padded_len, i + 1),
+                padded_byte(a, b, padded_len, i + 2),
+                padded_byte(a, b, padded_len, i + 3),
+            ]);
+            t += 1;
+        }
+        while t < 80 {
+            w[t] = (w[t - 3] ^ w[t - 8] ^ w[t - 14] ^ w[t - 16]).rotate_left(1);
+            t += 1;
+        }
+
+        let [mut va, mut vb, mut vc, mut vd, mut ve] = h;
+        t = 0;
+        while t < 80 {
+            let (f, k) = if t < 20 {
+                ((vb & vc) | (!vb & vd), 0x5A827999)
+            } else if t < 40 {
+                (vb ^ vc ^ vd, 0x6ED9EBA1)
+            } else if t < 60 {
+                ((vb & vc) | (vb & vd) | (vc & vd), 0x8F1BBCDC)
+            } else {
+                (vb ^ vc ^ vd, 0xCA62C1D6)
+            };
+            let temp = va
+                .rotate_left(5)
+                .wrapping_add(f)
+                .wrapping_add(ve)
+                .wrapping_add(k)
+                .wrapping_add(w[t]);
+            ve = vd;
+            vd = vc;
+            vc = vb.rotate_left(30);
+            vb = va;
+            va = temp;
+            t += 1;
+        }
+        h[0] = h[0].wrapping_add(va);
+        h[1] = h[1].wrapping_add(vb);
+        h[2] = h[2].wrapping_add(vc);
+        h[3] = h[3].wrapping_add(vd);
+        h[4] = h[4].wrapping_add(ve);
+        block += 64;
+    }
+
+    let mut out = [0u8; 20];
+    let mut i = 0;
+    while i < 5 {
+        let word = h[i].to_be_bytes();
+        out[i * 4] = word[0];
+        out[i * 4 + 1] = word[1];
+        out[i * 4 + 2] = word[2];
+        out[i * 4 + 3] = word[3];
+        i += 1;
+    }
+    out
+}
+
+/// Derive a convention's UUID from its specification URL at compile time.
+///
+/// See [convention_uuid](crate::uuid5::convention_uuid).
+///
+/// ```
+/// use zarrs_conventions::{convention_uuid, uuid::Uuid};
+///
+/// const ID: Uuid = convention_uuid!("https://example.com/specs/my_convention");
+/// assert_eq!(ID.get_version_num(), 5);
+/// ```
+#[macro_export]
+macro_rules! convention_uuid {
+    ($spec_url:literal) => {
+        const { $crate::uuid5::convention_uuid($spec_url) }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::{Uuid, uuid};
+
+    use super::{sha1, uuid_v5};
+
+    #[test]
+    fn sha1_vectors() {
+        assert_eq!(
+            sha1(b"", b""),
+            [
+                0xda, 0x39, 0xa3, 0xee, 0x5e, 0x6b, 0x4b, 0x0d, 0x32, 0x55, 0xbf, 0xef, 0x95, 0x60,
+                0x18, 0x90, 0xaf, 0xd8, 0x07, 0x09
+            ]
+        );
+        // Spans multiple blocks, split across both inputs.
+        assert_eq!(
+            sha1(
+                b"abcdbcdecdefdefgefghfghighij",
+                b"hijkijkljklmklmnlmnomnopnopq"
+            ),
+            [
+                0x84, 0x98, 0x3e, 0x44, 0x1c, 0x3b, 0xd2, 0x6e, 0xba, 0xae, 0x4a, 0xa1, 0xf9, 0x51,
+                0x29, 0xe5, 0xe5, 0x46, 0x70, 0xf1
+            ]
+        );
+    }
+
+    #[test]
+    fn v5_vectors() {
+        assert_eq!(
+            uuid_v5(Uuid::NAMESPACE_DNS, "python.org"),
+            uuid!("886313e1-3b8a-5372-9b90-0c9aee199e5d")
+        );
+    }
+}
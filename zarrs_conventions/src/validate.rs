@@ -0,0 +1,394 @@
+//! Check attributes for problems with their conventional metadata.
+//!
+//! Conventions whose payloads should be checked are given as [DynConvention]s,
+//! type-erased handles on their implementations.
+//!
+//! ```
+//! use zarrs_conventions::validate::{Severity, check_attributes};
+//!
+//! let attrs = serde_json::json!({"zarr_conventions": [{"spec_url": "https://example.com/unknown"}]});
+//! let issues = check_attributes(attrs.as_object().unwrap(), &[]);
+//! assert_eq!(issues[0].code, "declaration-without-uuid");
+//! assert_eq!(issues[1].code, "unknown-convention");
+//! assert!(issues.iter().all(|i| i.severity == Severity::Warning));
+//! ```
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    Attributes, AttributesBuilder, AttributesParser, Convention, ConventionDefinition,
+    ConventionIdRef, DEFAULT_ZARR_CONVENTION_REGISTRY, NestedOrPrefixedRepr, NestedRepr,
+    PrefixedRepr, ZarrConventions,
+};
+
+/// Version of the serialized report structures,
+/// incremented on incompatible changes.
+pub const REPORT_VERSION: u32 = 1;
+
+/// Type-erased handle on a convention implementation.
+#[derive(Debug, Clone, Copy)]
+pub struct DynConvention {
+    pub definition: ConventionDefinition,
+    /// Key of the nested representation, if supported.
+    pub key: Option<&'static str>,
+    /// Prefix of the prefixed representation, if supported.
+    pub prefix: Option<&'static str>,
+    parse: fn(&AttributesParser) -> serde_json::Result<Option<Value>>,
+    add: fn(&mut AttributesBuilder, &Value) -> serde_json::Result<()>,
+    lint: fn(&Value) -> Vec<Issue>,
+}
+
+/// Convention-specific checks on a payload which parsed successfully,
+/// for problems which do not make it invalid.
+pub trait Lint {
+    fn lint(&self) -> Vec<Issue>;
+}
+
+fn reserialize<T: serde::Serialize>(value: Option<T>) -> serde_json::Result<Option<Value>> {
+    value.map(|v| serde_json::to_value(&v)).transpose()
+}
+
+impl DynConvention {
+    /// Handle on a convention with a nested representation.
+    pub fn nested<T: NestedRepr>() -> Self {
+        Self {
+            definition: T::DEFINITION,
+            key: Some(T::KEY),
+            prefix: None,
+            parse: |p| reserialize(p.parse_nested::<T>()?),
+            add: |b, v| b.add_nested(&T::deserialize(v)?).map(|_| ()),
+            lint: |_| Vec::default(),
+        }
+    }
+
+    /// Handle on a convention with a prefixed representation.
+    pub fn prefixed<T: PrefixedRepr>() -> Self {
+        Self {
+            definition: T::DEFINITION,
+            key: None,
+            prefix: Some(T::PREFIX),
+            parse: |p| reserialize(p.parse_prefixed::<T>()?),
+            add: |b, v| b.add_prefixed(&T::deserialize(v)?).map(|_| ()),
+            lint: |_| Vec::default(),
+        }
+    }
+
+    /// Handle on a convention with both representations;
+    /// payloads are added in nested form.
+    pub fn either<T: NestedOrPrefixedRepr>() -> Self {
+        Self {
+            definition: T::DEFINITION,
+            key: Some(T::KEY),
+            prefix: Some(T::PREFIX),
+            parse: |p| reserialize(p.parse::<T>()?),
+            add: |b, v| b.add_nested(&T::deserialize(v)?).map(|_| ()),
+            lint: |_| Vec::default(),
+        }
+    }
+
+    /// Also run the [Lint]s of `T`, which should be the convention's type,
+    /// on parsed payloads.
+    pub fn with_lint<T: Lint + serde::de::DeserializeOwned>(mut self) -> Self {
+        self.lint = |v| T::deserialize(v).map(|t| t.lint()).unwrap_or_default();
+        self
+    }
+
+    /// Parse and re-serialize the convention's payload, validating it in the process.
+    ///
+    /// None if the convention is not declared or its payload is absent.
+    pub fn parse(&self, parser: &AttributesParser) -> serde_json::Result<Option<Value>> {
+        (self.parse)(parser)
+    }
+
+    /// Validate a payload and add it, and the convention's declaration, to the builder.
+    pub fn add(&self, builder: &mut AttributesBuilder, payload: &Value) -> serde_json::Result<()> {
+        (self.add)(builder, payload)
+    }
+
+    /// Lint a parsed payload, as returned by [Self::parse].
+    pub fn lint(&self, payload: &Value) -> Vec<Issue> {
+        (self.lint)(payload)
+    }
+
+    /// Whether this convention matches the identifier.
+    pub fn is<'a>(&self, id: impl Into<ConventionIdRef<'a>>) -> bool {
+        let def = &self.definition;
+        match id.into() {
+            ConventionIdRef::Uuid(uuid) => uuid == def.uuid,
+            ConventionIdRef::SchemaUrl(url) => url == def.schema_url,
+            ConventionIdRef::SpecUrl(url) => url == def.spec_url,
+        }
+    }
+
+    /// Whether the attributes contain any of this convention's payload.
+    pub fn has_payload(&self, attributes: &Attributes) -> bool {
+        self.key.is_some_and(|k| attributes.contains_key(k))
+            || self
+                .prefix
+                .is_some_and(|p| attributes.keys().any(|k| k.starts_with(p)))
+    }
+
+    /// Whether the convention is declared.
+    pub fn is_declared(&self, conventions: &ZarrConventions) -> bool {
+        let def = &self.definition;
+        conventions.contains(def.uuid)
+            || conventions.contains(def.id_schema_ref())
+            || conventions.contains(def.id_spec_ref())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Worth knowing, but not necessarily a problem.
+    Info,
+    Warning,
+    Error,
+}
+
+/// A single problem found in a node.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Issue {
+    pub severity: Severity,
+    /// Stable identifier of the check which failed.
+    pub code: &'static str,
+    pub message: String,
+}
+
+impl Issue {
+    pub fn error(code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Error,
+            code,
+            message: message.into(),
+        }
+    }
+
+    pub fn info(code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Info,
+            code,
+            message: message.into(),
+        }
+    }
+
+    pub fn warning(code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Warning,
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+/// Issues found in a single node.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NodeReport {
+    pub path: String,
+    pub issues: Vec<Issue>,
+}
+
+/// Issues found in a hierarchy.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ValidationReport {
+    pub version: u32,
+    pub nodes: Vec<NodeReport>,
+}
+
+impl Default for ValidationReport {
+    fn default() -> Self {
+        Self {
+            version: REPORT_VERSION,
+            nodes: Vec::default(),
+        }
+    }
+}
+
+impl ValidationReport {
+    /// Number of issues with this severity.
+    pub fn count(&self, severity: Severity) -> usize {
+        self.nodes
+            .iter()
+            .flat_map(|n| &n.issues)
+            .filter(|i| i.severity == severity)
+            .count()
+    }
+}
+
+/// Human-readable label for a declared convention,
+/// using the name from the default registry if it is registered.
+pub fn label(conv: &Convention) -> String {
+    let id = match (conv.uuid(), conv.schema_url(), conv.spec_url()) {
+        (Some(uuid), _, _) => uuid.to_string(),
+        (None, Some(url), _) | (None, None, Some(url)) => url.to_string(),
+        (None, None, None) => unreachable!("Convention must have at least one identifier"),
+    };
+    let name = DEFAULT_ZARR_CONVENTION_REGISTRY
+        .get(conv.id_ref())
+        .map(|def| def.name)
+        .or(conv.name());
+    match name {
+        Some(name) => format!("{name} [{id}]"),
+        None => format!("[{id}]"),
+    }
+}
+
+/// The conventions declared in the attributes.
+pub fn declarations(attributes: &Attributes) -> serde_json::Result<Vec<Convention>> {
+    attributes
+        .get(ZarrConventions::KEY)
+        .map(Vec::<Convention>::deserialize)
+        .transpose()
+        .map(Option::unwrap_or_default)
+}
+
+/// Check the attributes' declarations against the default registry,
+/// and their payloads against the given convention implementations,
+/// including any [Lint]s.
+pub fn check_attributes(attributes: &Attributes, conventions: &[DynConvention]) -> Vec<Issue> {
+    let mut issues = Vec::default();
+    let declared = match declarations(attributes) {
+        Ok(d) => d,
+        Err(e) => {
+            issues.push(Issue::error("invalid-declarations", e.to_string()));
+            return issues;
+        }
+    };
+    let in_use = ZarrConventions::from_attributes(attributes).unwrap_or_default();
+    let parser: AttributesParser =
+        serde_json::from_value(Value::Object(attributes.clone())).unwrap_or_default();
+
+    // lints on the declarations themselves
+    let mut seen = HashSet::new();
+    for conv in &declared {
+        if !seen.insert(conv.id()) {
+            issues.push(Issue::warning(
+                "duplicate-declaration",
+                format!("{} is declared more than once", label(conv)),
+            ));
+        }
+        if conv.uuid().is_none() {
+            issues.push(Issue::warning(
+                "declaration-without-uuid",
+                format!("{} should be declared with its UUID", label(conv)),
+            ));
+        }
+        match DEFAULT_ZARR_CONVENTION_REGISTRY.get(conv.id_ref()) {
+            None => issues.push(Issue::warning(
+                "unknown-convention",
+                format!("{} is not a known convention", label(conv)),
+            )),
+            Some(def) => {
+                if let Some(name) = conv.name()
+                    && name != def.name
+                {
+                    issues.push(Issue::warning(
+                        "name-mismatch",
+                        format!("declared name '{name}' does not match '{}'", def.name),
+                    ));
+                }
+            }
+        }
+    }
+
+    // declarations against payloads, and payload semantics
+    for conv in conventions {
+        let name = conv.definition.name;
+        match (conv.is_declared(&in_use), conv.has_payload(attributes)) {
+            (false, true) => issues.push(Issue::warning(
+                "undeclared-payload",
+                format!("{name} payload is present but the convention is not declared"),
+            )),
+            (true, false) => issues.push(Issue::warning(
+                "missing-payload",
+                format!("the {name} convention is declared but its payload is absent"),
+            )),
+            (true, true) => match conv.parse(&parser) {
+                Ok(Some(payload)) => issues.extend(conv.lint(&payload)),
+                Ok(None) => (),
+                Err(e) => issues.push(Issue::error(
+                    "invalid-payload",
+                    format!("invalid {name} payload: {e}"),
+                )),
+            },
+            (false, false) => (),
+        }
+    }
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{DynConvention, Issue, Lint, Severity, check_attributes};
+    use crate::{
+        AttributesBuilder,
+        mock::{CanBeEither, MustBeNested, MustBePrefixed},
+    };
+
+    impl Lint for MustBeNested {
+        fn lint(&self) -> Vec<Issue> {
+            if self.a > self.b {
+                vec![Issue::warning("a-exceeds-b", "a should not exceed b")]
+            } else {
+                Vec::default()
+            }
+        }
+    }
+
+    #[test]
+    fn dyn_lint() {
+        let conv = DynConvention::nested::<MustBeNested>().with_lint::<MustBeNested>();
+        let attrs = json!({
+            "zarr_conventions": [{"uuid": "11111111-1111-1111-1111-111111111111"}],
+            "must_be_nested": {"a": 2, "b": 1},
+        });
+        let issues = check_attributes(attrs.as_object().unwrap(), &[conv]);
+        assert_eq!(issues.last().unwrap().code, "a-exceeds-b");
+    }
+
+    #[test]
+    fn check_payloads() {
+        let conventions = [
+            DynConvention::prefixed::<MustBePrefixed>(),
+            DynConvention::either::<CanBeEither>(),
+        ];
+        let attrs = json!({
+            "zarr_conventions": [
+                {"uuid": "22222222-2222-2222-2222-222222222222"},
+                {"uuid": "33333333-3333-3333-3333-333333333333"}
+            ],
+            "must_be_prefixed:x": "not a number",
+            "must_be_prefixed:y": 1,
+        });
+        let issues = check_attributes(attrs.as_object().unwrap(), &conventions);
+        let codes: Vec<_> = issues.iter().map(|i| (i.severity, i.code)).collect();
+        assert_eq!(
+            codes,
+            [
+                (Severity::Warning, "unknown-convention"),
+                (Severity::Warning, "unknown-convention"),
+                (Severity::Error, "invalid-payload"),
+                (Severity::Warning, "missing-payload"),
+            ]
+        );
+    }
+
+    #[test]
+    fn dyn_add() {
+        let mut builder = AttributesBuilder::default();
+        DynConvention::either::<CanBeEither>()
+            .add(&mut builder, &json!({"foo": 1, "bar": 2}))
+            .unwrap();
+        let attrs = builder.build().unwrap();
+        assert_eq!(attrs["can_be_either"], json!({"foo": 1, "bar": 2}));
+        assert!(
+            DynConvention::either::<CanBeEither>()
+                .add(&mut AttributesBuilder::default(), &json!({"foo": 1}))
+                .is_err()
+        );
+    }
+}
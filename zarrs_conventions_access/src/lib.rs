@@ -0,0 +1,355 @@
+#![doc = include_str!("../README.md")]
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+pub use zarrs_conventions;
+use zarrs_conventions::{
+    define_convention,
+    iref::{Uri, UriBuf},
+    validate::{Issue, Lint},
+};
+pub use zarrs_conventions_timestamps::{Timestamp, TimestampError};
+
+pub mod resolve;
+
+/// Who may access the data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum Level {
+    /// Anyone.
+    Public,
+    /// Only those granted access, e.g. through the request URL.
+    Restricted,
+    /// Nobody until the embargo ends, then anyone.
+    Embargoed,
+}
+
+/// When an embargo ends: an RFC 3339 timestamp, e.g. `2025-06-30T12:00:00Z`,
+/// or a date, e.g. `2025-06-30`, meaning the start of that day in UTC.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(transparent)]
+pub struct EmbargoEnd(String);
+
+impl EmbargoEnd {
+    pub fn new(end: impl Into<String>) -> Result<Self, TimestampError> {
+        let end = Self(end.into());
+        end.instant()?;
+        Ok(end)
+    }
+
+    /// An end which is checked when building [Access], or by [EmbargoEnd::instant].
+    pub fn new_unchecked(end: impl Into<String>) -> Self {
+        Self(end.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Whether the end is given as a date rather than a timestamp.
+    pub fn is_date(&self) -> bool {
+        self.0.len() == 10 && !self.0.contains('T')
+    }
+
+    /// Seconds and nanoseconds since the Unix epoch at which the embargo ends.
+    ///
+    /// ```
+    /// use zarrs_conventions_access::EmbargoEnd;
+    ///
+    /// let date = EmbargoEnd::new("2025-06-30").unwrap();
+    /// let timestamp = EmbargoEnd::new("2025-06-30T00:00:00Z").unwrap();
+    /// assert_eq!(date.instant(), timestamp.instant());
+    /// assert!(EmbargoEnd::new("2025-02-30").is_err());
+    /// ```
+    pub fn instant(&self) -> Result<(i64, u32), TimestampError> {
+        if self.is_date() {
+            Timestamp::new_unchecked(format!("{}T00:00:00Z", self.0))
+                .instant()
+                .map_err(|e| match e {
+                    TimestampError::OutOfRange(_) => TimestampError::OutOfRange(self.0.clone()),
+                    _ => TimestampError::Syntax(self.0.clone()),
+                })
+        } else {
+            Timestamp::new_unchecked(self.0.as_str()).instant()
+        }
+    }
+}
+
+impl fmt::Display for EmbargoEnd {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::str::FromStr for EmbargoEnd {
+    type Err = TimestampError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+define_convention! {
+    /// Who may access a node and its children, and how to ask for access.
+    #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+    pub struct Access {
+        /// Who may access the data.
+        level: Level,
+        /// When the embargo ends; required if and only if the data is embargoed.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        embargo_end: Option<EmbargoEnd>,
+        /// Where to ask for access to restricted or embargoed data.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "schema", schemars(with = "Option<String>", extend("format" = "uri")))]
+        request_url: Option<UriBuf>,
+    }
+    schema_url = "https://raw.githubusercontent.com/clbarnes/zarrs_conventions/refs/tags/access-v1/zarrs_conventions_access/spec/schema.json",
+    spec_url = "https://github.com/clbarnes/zarrs_conventions/blob/access-v1/zarrs_conventions_access/spec/README.md",
+    name = "access",
+    description = "Who may access a node and its children, and until when it is embargoed",
+    nested = "access",
+    register = false,
+}
+
+impl Access {
+    pub fn builder(level: Level) -> Builder {
+        Builder {
+            inner: Self {
+                level,
+                embargo_end: None,
+                request_url: None,
+            },
+        }
+    }
+
+    /// Access for anyone.
+    pub fn public() -> Self {
+        Self {
+            level: Level::Public,
+            embargo_end: None,
+            request_url: None,
+        }
+    }
+
+    pub fn level(&self) -> Level {
+        self.level
+    }
+
+    pub fn embargo_end(&self) -> Option<&EmbargoEnd> {
+        self.embargo_end.as_ref()
+    }
+
+    /// Where to ask for access to restricted or embargoed data.
+    pub fn request_url(&self) -> Option<&Uri> {
+        self.request_url.as_deref()
+    }
+
+    /// The level in effect at the given time: embargoed data is public once the embargo has ended.
+    ///
+    /// An embargo with a missing or invalid end never ends.
+    pub fn level_at(&self, now: &Timestamp) -> Level {
+        match (self.level, &self.embargo_end) {
+            (Level::Embargoed, Some(end)) => match (end.instant(), now.instant()) {
+                (Ok(end), Ok(now)) if now >= end => Level::Public,
+                _ => Level::Embargoed,
+            },
+            (level, _) => level,
+        }
+    }
+
+    /// Whether anyone may access the data at the given time.
+    ///
+    /// ```
+    /// use zarrs_conventions_access::{Access, Level, Timestamp};
+    ///
+    /// let access = Access::builder(Level::Embargoed)
+    ///     .embargo_end("2025-06-30")
+    ///     .build()
+    ///     .unwrap();
+    /// assert!(!access.is_accessible(&"2025-06-29T23:59:59Z".parse().unwrap()));
+    /// assert!(access.is_accessible(&"2025-06-30T00:00:00Z".parse().unwrap()));
+    /// ```
+    pub fn is_accessible(&self, now: &Timestamp) -> bool {
+        self.level_at(now) == Level::Public
+    }
+
+    /// Whether anyone may access the data now.
+    pub fn is_accessible_now(&self) -> bool {
+        self.is_accessible(&Timestamp::now())
+    }
+
+    fn check(&self) -> Result<(), BuildError> {
+        match (self.level, &self.embargo_end) {
+            (Level::Embargoed, None) => Err(BuildError::NoEmbargoEnd),
+            (Level::Embargoed, Some(end)) => end.instant().map(|_| ()).map_err(BuildError::from),
+            (_, Some(_)) => Err(BuildError::NotEmbargoed),
+            (_, None) => Ok(()),
+        }
+    }
+}
+
+impl Lint for Access {
+    fn lint(&self) -> Vec<Issue> {
+        let mut issues = Vec::default();
+        match self.check() {
+            Ok(()) => {}
+            Err(e @ BuildError::NoEmbargoEnd) => {
+                issues.push(Issue::error("no-embargo-end", e.to_string()))
+            }
+            Err(e @ BuildError::InvalidEmbargoEnd(_)) => {
+                issues.push(Issue::error("invalid-embargo-end", e.to_string()))
+            }
+            Err(e @ BuildError::NotEmbargoed) => {
+                issues.push(Issue::warning("not-embargoed", e.to_string()))
+            }
+        }
+        if self.level != Level::Public && self.request_url.is_none() {
+            issues.push(Issue::warning(
+                "no-request-url",
+                "data which is not public should say where to request access",
+            ));
+        }
+        issues
+    }
+}
+
+/// Why access could not be built.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum BuildError {
+    #[error("embargoed data must give when the embargo ends")]
+    NoEmbargoEnd,
+    #[error("invalid embargo end: {0}")]
+    InvalidEmbargoEnd(#[from] TimestampError),
+    #[error("an embargo end is given, but the data is not embargoed")]
+    NotEmbargoed,
+}
+
+/// Builder for [Access], created by [Access::builder].
+#[derive(Debug, Clone)]
+pub struct Builder {
+    inner: Access,
+}
+
+impl Builder {
+    /// Set when the embargo ends, as a timestamp or date; checked when building.
+    pub fn embargo_end(mut self, end: impl Into<String>) -> Self {
+        self.inner.embargo_end = Some(EmbargoEnd::new_unchecked(end));
+        self
+    }
+
+    pub fn request_url(mut self, url: UriBuf) -> Self {
+        self.inner.request_url = Some(url);
+        self
+    }
+
+    /// Build the access, checking that embargoed data, and only embargoed data, has a valid embargo end.
+    pub fn build(self) -> Result<Access, BuildError> {
+        self.inner.check()?;
+        Ok(self.inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use zarrs_conventions::validate::Lint;
+
+    use crate::{Access, BuildError, EmbargoEnd, Level, Timestamp, TimestampError};
+
+    fn at(timestamp: &str) -> Timestamp {
+        timestamp.parse().unwrap()
+    }
+
+    #[test]
+    fn build() {
+        let access = Access::builder(Level::Embargoed)
+            .embargo_end("2025-06-30T12:00:00+02:00")
+            .request_url("https://example.org/request".parse().unwrap())
+            .build()
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(&access).unwrap(),
+            json!({
+                "level": "embargoed",
+                "embargo_end": "2025-06-30T12:00:00+02:00",
+                "request_url": "https://example.org/request",
+            })
+        );
+        assert!(access.lint().is_empty());
+
+        assert_eq!(
+            Access::builder(Level::Embargoed).build(),
+            Err(BuildError::NoEmbargoEnd)
+        );
+        assert_eq!(
+            Access::builder(Level::Restricted)
+                .embargo_end("2025-06-30")
+                .build(),
+            Err(BuildError::NotEmbargoed)
+        );
+        assert_eq!(
+            Access::builder(Level::Embargoed)
+                .embargo_end("30/06/2025")
+                .build(),
+            Err(BuildError::InvalidEmbargoEnd(TimestampError::Syntax(
+                "30/06/2025".into()
+            )))
+        );
+        assert!(EmbargoEnd::new("2025-06-30T12:00:00").is_err());
+    }
+
+    #[test]
+    fn accessible() {
+        let access = Access::builder(Level::Embargoed)
+            .embargo_end("2025-06-30T12:00:00+02:00")
+            .build()
+            .unwrap();
+        assert!(!access.is_accessible(&at("2025-06-30T09:59:59Z")));
+        assert!(access.is_accessible(&at("2025-06-30T10:00:00Z")));
+        assert_eq!(
+            access.level_at(&at("2025-06-30T09:59:59Z")),
+            Level::Embargoed
+        );
+
+        let now = at("2000-01-01T00:00:00Z");
+        assert!(Access::public().is_accessible(&now));
+        let restricted = Access::builder(Level::Restricted).build().unwrap();
+        assert!(!restricted.is_accessible(&now));
+
+        let broken: Access =
+            serde_json::from_value(json!({"level": "embargoed", "embargo_end": "soon"})).unwrap();
+        assert!(!broken.is_accessible(&at("9999-12-31T23:59:59Z")));
+    }
+
+    #[test]
+    fn lint() {
+        let codes = |value| {
+            let access: Access = serde_json::from_value(value).unwrap();
+            access
+                .lint()
+                .into_iter()
+                .map(|i| i.code)
+                .collect::<Vec<_>>()
+        };
+        assert!(codes(json!({"level": "public"})).is_empty());
+        assert_eq!(
+            codes(json!({"level": "embargoed"})),
+            ["no-embargo-end", "no-request-url"]
+        );
+        assert_eq!(
+            codes(
+                json!({"level": "embargoed", "embargo_end": "2025-13-01", "request_url": "mailto:data@example.org"})
+            ),
+            ["invalid-embargo-end"]
+        );
+        assert_eq!(
+            codes(json!({"level": "public", "embargo_end": "2025-06-30"})),
+            ["not-embargoed"]
+        );
+    }
+}
@@ -0,0 +1,111 @@
+//! Access through a hierarchy, for archives mixing public and restricted data:
+//! a node can only be accessed if it and each of its ancestors which declares access allows it.
+use zarrs_conventions::{Attributes, NestedRepr, NodePath};
+
+use crate::{Access, Timestamp};
+
+/// Access which prevents a node being accessed, and where it was declared.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Blocked {
+    /// The node declaring the access; may be the node itself.
+    pub declared_by: NodePath,
+    pub access: Access,
+}
+
+/// Whether the node at `node` can be accessed by anyone at the given time,
+/// considering the access declared by the node and all of its ancestors;
+/// nodes declaring no access do not restrict it.
+///
+/// `lookup` returns the attributes of the node at an absolute path.
+/// Returns the nearest declaration preventing access, if any.
+///
+/// ```
+/// use serde_json::json;
+/// use zarrs_conventions::NodePath;
+/// use zarrs_conventions_access::resolve::blocked_by;
+///
+/// let lookup = |node: &NodePath| match node.as_str() {
+///     "/" => json!({"access": {"level": "public"}}).as_object().cloned(),
+///     "/private" => json!({"access": {"level": "restricted"}}).as_object().cloned(),
+///     _ => Some(Default::default()),
+/// };
+/// let now = "2025-01-01T00:00:00Z".parse().unwrap();
+/// assert_eq!(blocked_by(&"/public/array".parse().unwrap(), &now, lookup), Ok(None));
+/// let blocked = blocked_by(&"/private/array".parse().unwrap(), &now, lookup)
+///     .unwrap()
+///     .unwrap();
+/// assert_eq!(blocked.declared_by.as_str(), "/private");
+/// ```
+pub fn blocked_by(
+    node: &NodePath,
+    now: &Timestamp,
+    lookup: impl Fn(&NodePath) -> Option<Attributes>,
+) -> Result<Option<Blocked>, String> {
+    for path in node.ancestors() {
+        let Some(attributes) = lookup(&path) else {
+            continue;
+        };
+        if !attributes.contains_key(Access::KEY) {
+            continue;
+        }
+        let access = Access::from_attributes_nested(&attributes)
+            .map_err(|e| format!("invalid access at {path}: {e}"))?;
+        if !access.is_accessible(now) {
+            return Ok(Some(Blocked {
+                declared_by: path,
+                access,
+            }));
+        }
+    }
+    Ok(None)
+}
+
+/// Whether the node at `node` can be accessed by anyone at the given time; see [blocked_by].
+///
+/// Invalid access declarations are treated as preventing access.
+pub fn is_accessible(
+    node: &NodePath,
+    now: &Timestamp,
+    lookup: impl Fn(&NodePath) -> Option<Attributes>,
+) -> bool {
+    matches!(blocked_by(node, now, lookup), Ok(None))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use zarrs_conventions::{Attributes, NodePath};
+
+    use super::{blocked_by, is_accessible};
+    use crate::Timestamp;
+
+    #[test]
+    fn hierarchy() {
+        let lookup = |node: &NodePath| -> Option<Attributes> {
+            let attributes = match node.as_str() {
+                "/" => json!({"access": {"level": "public"}}),
+                "/embargoed" => {
+                    json!({"access": {"level": "embargoed", "embargo_end": "2025-06-30"}})
+                }
+                "/embargoed/restricted" => json!({"access": {"level": "restricted"}}),
+                "/bad" => json!({"access": "public"}),
+                _ => json!({}),
+            };
+            attributes.as_object().cloned()
+        };
+        let path = |s: &str| s.parse::<NodePath>().unwrap();
+        let before: Timestamp = "2025-01-01T00:00:00Z".parse().unwrap();
+        let after: Timestamp = "2026-01-01T00:00:00Z".parse().unwrap();
+
+        assert!(is_accessible(&path("/a/b"), &before, lookup));
+        assert!(!is_accessible(&path("/embargoed/a"), &before, lookup));
+        assert!(is_accessible(&path("/embargoed/a"), &after, lookup));
+        let blocked = blocked_by(&path("/embargoed/restricted/a"), &after, lookup)
+            .unwrap()
+            .unwrap();
+        assert_eq!(blocked.declared_by, path("/embargoed/restricted"));
+        assert!(blocked_by(&path("/bad/a"), &after, lookup).is_err());
+        assert!(!is_accessible(&path("/bad/a"), &after, lookup));
+        assert!(is_accessible(&path("/a"), &before, |_| None));
+    }
+}
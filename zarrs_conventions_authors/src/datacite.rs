@@ -0,0 +1,98 @@
+//! Export to the `creators` and `contributors` of [DataCite metadata](https://schema.datacite.org/).
+use serde_json::{Value, json};
+
+use crate::{Affiliation, Authors, Person};
+
+fn affiliation(affiliation: &Affiliation) -> Value {
+    let mut value = json!({"name": affiliation.name()});
+    if let Some(ror) = affiliation.ror() {
+        value["affiliationIdentifier"] = ror.url().into();
+        value["affiliationIdentifierScheme"] = "ROR".into();
+        value["schemeUri"] = "https://ror.org".into();
+    }
+    value
+}
+
+fn person(person: &Person) -> Value {
+    let mut value = json!({"name": person.name()});
+    if let Some(orcid) = person.orcid() {
+        // only people have ORCIDs; otherwise, the name may be an organisation's
+        value["nameType"] = "Personal".into();
+        value["nameIdentifiers"] = json!([{
+            "nameIdentifier": orcid.url(),
+            "nameIdentifierScheme": "ORCID",
+            "schemeUri": "https://orcid.org",
+        }]);
+    }
+    if !person.affiliations().is_empty() {
+        value["affiliation"] = person.affiliations().iter().map(affiliation).collect();
+    }
+    value
+}
+
+impl Authors {
+    /// The authors as the `creators` and `contributors` properties of DataCite metadata (JSON, v4).
+    pub fn to_datacite(&self) -> Value {
+        let contributors: Vec<_> = self
+            .contributors()
+            .iter()
+            .map(|c| {
+                let mut value = person(c.person());
+                value["contributorType"] =
+                    serde_json::to_value(c.role()).expect("roles should serialize");
+                value
+            })
+            .collect();
+        json!({
+            "creators": self.creators().iter().map(person).collect::<Vec<_>>(),
+            "contributors": contributors,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{Affiliation, Authors, ContributorRole, Person};
+
+    #[test]
+    fn datacite() {
+        let authors = Authors::builder()
+            .creator(
+                Person::new("Josiah Carberry")
+                    .with_orcid("0000-0002-1825-0097")
+                    .with_affiliation(Affiliation::new("Brown University").with_ror("05gq02987")),
+            )
+            .contributor(
+                Person::new("Brown University"),
+                ContributorRole::HostingInstitution,
+            )
+            .build()
+            .unwrap();
+        assert_eq!(
+            authors.to_datacite(),
+            json!({
+                "creators": [{
+                    "name": "Josiah Carberry",
+                    "nameType": "Personal",
+                    "nameIdentifiers": [{
+                        "nameIdentifier": "https://orcid.org/0000-0002-1825-0097",
+                        "nameIdentifierScheme": "ORCID",
+                        "schemeUri": "https://orcid.org",
+                    }],
+                    "affiliation": [{
+                        "name": "Brown University",
+                        "affiliationIdentifier": "https://ror.org/05gq02987",
+                        "affiliationIdentifierScheme": "ROR",
+                        "schemeUri": "https://ror.org",
+                    }],
+                }],
+                "contributors": [{
+                    "name": "Brown University",
+                    "contributorType": "HostingInstitution",
+                }],
+            })
+        );
+    }
+}
@@ -0,0 +1,207 @@
+//! Persistent identifiers for people and organisations, with checksum validation.
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+const ORCID_URL: &str = "https://orcid.org/";
+const ROR_URL: &str = "https://ror.org/";
+/// Crockford's base 32 alphabet, used by ROR IDs.
+const CROCKFORD: &str = "0123456789abcdefghjkmnpqrstvwxyz";
+
+/// Why an identifier is invalid.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum IdError {
+    #[error("'{0}' is not of the form 0000-0000-0000-000X")]
+    OrcidFormat(String),
+    #[error("ORCID '{0}' has an incorrect check digit")]
+    OrcidChecksum(String),
+    #[error("'{0}' is not a 9-character ROR ID beginning with 0")]
+    RorFormat(String),
+    #[error("ROR ID '{0}' has incorrect check digits")]
+    RorChecksum(String),
+}
+
+/// An [ORCID](https://orcid.org) identifying a researcher, e.g. `0000-0002-1825-0097`.
+///
+/// Deserialization does not check the identifier; see [Orcid::validate].
+///
+/// ```
+/// use zarrs_conventions_authors::Orcid;
+///
+/// let orcid = Orcid::new("https://orcid.org/0000-0002-1825-0097").unwrap();
+/// assert_eq!(orcid.as_str(), "0000-0002-1825-0097");
+/// assert!(Orcid::new("0000-0002-1825-0098").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(transparent)]
+pub struct Orcid(String);
+
+impl Orcid {
+    /// Parse an ORCID, with or without the `https://orcid.org/` prefix,
+    /// checking its check digit.
+    pub fn new(orcid: impl AsRef<str>) -> Result<Self, IdError> {
+        let orcid = Self::new_unchecked(orcid);
+        orcid.validate()?;
+        Ok(orcid)
+    }
+
+    /// An ORCID, with or without the `https://orcid.org/` prefix, which is not checked.
+    pub fn new_unchecked(orcid: impl AsRef<str>) -> Self {
+        let orcid = orcid.as_ref().trim();
+        Self(orcid.strip_prefix(ORCID_URL).unwrap_or(orcid).to_string())
+    }
+
+    /// Check the format and ISO 7064 11,2 check digit.
+    pub fn validate(&self) -> Result<(), IdError> {
+        let format_error = || IdError::OrcidFormat(self.0.clone());
+        if !self.0.is_ascii() {
+            return Err(format_error());
+        }
+        let groups: Vec<_> = self.0.split('-').collect();
+        if groups.len() != 4 || groups.iter().any(|g| g.len() != 4) {
+            return Err(format_error());
+        }
+        let chars: Vec<char> = groups.concat().chars().collect();
+        let (digits, check) = chars.split_at(15);
+        let mut total = 0;
+        for c in digits {
+            total = (total + c.to_digit(10).ok_or_else(format_error)?) * 2;
+        }
+        let expected = match (12 - total % 11) % 11 {
+            10 => 'X',
+            n => char::from_digit(n, 10).expect("single digit"),
+        };
+        if check[0] != expected {
+            return Err(IdError::OrcidChecksum(self.0.clone()));
+        }
+        Ok(())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The ORCID as a URL, e.g. `https://orcid.org/0000-0002-1825-0097`.
+    pub fn url(&self) -> String {
+        format!("{ORCID_URL}{}", self.0)
+    }
+}
+
+impl fmt::Display for Orcid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// A [ROR](https://ror.org) ID identifying a research organisation, e.g. `05gq02987`.
+///
+/// Deserialization does not check the identifier; see [Ror::validate].
+///
+/// ```
+/// use zarrs_conventions_authors::Ror;
+///
+/// let ror = Ror::new("https://ror.org/05gq02987").unwrap();
+/// assert_eq!(ror.url(), "https://ror.org/05gq02987");
+/// assert!(Ror::new("05gq02988").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(transparent)]
+pub struct Ror(String);
+
+impl Ror {
+    /// Parse a ROR ID, with or without the `https://ror.org/` prefix,
+    /// checking its check digits.
+    pub fn new(ror: impl AsRef<str>) -> Result<Self, IdError> {
+        let ror = Self::new_unchecked(ror);
+        ror.validate()?;
+        Ok(ror)
+    }
+
+    /// A ROR ID, with or without the `https://ror.org/` prefix, which is not checked.
+    pub fn new_unchecked(ror: impl AsRef<str>) -> Self {
+        let ror = ror.as_ref().trim();
+        Self(ror.strip_prefix(ROR_URL).unwrap_or(ror).to_string())
+    }
+
+    /// Check the format and ISO 7064 97,10 check digits.
+    pub fn validate(&self) -> Result<(), IdError> {
+        let format_error = || IdError::RorFormat(self.0.clone());
+        if self.0.len() != 9 || !self.0.starts_with('0') || !self.0.is_ascii() {
+            return Err(format_error());
+        }
+        let (body, check) = self.0.split_at(7);
+        let mut value: u64 = 0;
+        for c in body.chars() {
+            let digit = CROCKFORD.find(c).ok_or_else(format_error)?;
+            value = value * 32 + digit as u64;
+        }
+        let check: u64 = check.parse().map_err(|_| format_error())?;
+        if 98 - (value * 100) % 97 != check {
+            return Err(IdError::RorChecksum(self.0.clone()));
+        }
+        Ok(())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The ROR ID as a URL, e.g. `https://ror.org/05gq02987`.
+    pub fn url(&self) -> String {
+        format!("{ROR_URL}{}", self.0)
+    }
+}
+
+impl fmt::Display for Ror {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IdError, Orcid, Ror};
+
+    #[test]
+    fn orcids() {
+        assert!(Orcid::new("0000-0002-1694-233X").is_ok());
+        assert!(Orcid::new("0000-0001-5109-3700").is_ok());
+        assert_eq!(
+            Orcid::new("0000-0001-5109-370"),
+            Err(IdError::OrcidFormat("0000-0001-5109-370".into()))
+        );
+        assert_eq!(
+            Orcid::new("0000-000A-5109-3700"),
+            Err(IdError::OrcidFormat("0000-000A-5109-3700".into()))
+        );
+        assert_eq!(
+            Orcid::new("0000-0001-5109-3701"),
+            Err(IdError::OrcidChecksum("0000-0001-5109-3701".into()))
+        );
+        assert_eq!(
+            Orcid::new("éé-éé-éé-éé"),
+            Err(IdError::OrcidFormat("éé-éé-éé-éé".into()))
+        );
+    }
+
+    #[test]
+    fn rors() {
+        assert!(Ror::new("05dxps055").is_ok());
+        assert!(Ror::new("03yrm5c26").is_ok());
+        assert_eq!(
+            Ror::new("15dxps055"),
+            Err(IdError::RorFormat("15dxps055".into()))
+        );
+        assert_eq!(
+            Ror::new("05dxpsi55"),
+            Err(IdError::RorFormat("05dxpsi55".into()))
+        );
+        assert_eq!(
+            Ror::new("05dxps056"),
+            Err(IdError::RorChecksum("05dxps056".into()))
+        );
+    }
+}
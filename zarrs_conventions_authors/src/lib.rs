@@ -0,0 +1,329 @@
+#![doc = include_str!("../README.md")]
+use serde::{Deserialize, Serialize};
+pub use zarrs_conventions;
+use zarrs_conventions::{
+    define_convention,
+    validate::{Issue, Lint},
+};
+
+mod datacite;
+mod ids;
+mod schema_org;
+pub use ids::{IdError, Orcid, Ror};
+
+/// An organisation to which a person belongs.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Affiliation {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ror: Option<Ror>,
+}
+
+impl Affiliation {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ror: None,
+        }
+    }
+
+    /// Set the organisation's ROR ID, which is checked when building [Authors].
+    pub fn with_ror(mut self, ror: impl AsRef<str>) -> Self {
+        self.ror = Some(Ror::new_unchecked(ror));
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn ror(&self) -> Option<&Ror> {
+        self.ror.as_ref()
+    }
+}
+
+/// A person or organisation credited for a dataset.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Person {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    orcid: Option<Orcid>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    affiliations: Vec<Affiliation>,
+}
+
+impl Person {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            orcid: None,
+            affiliations: Vec::default(),
+        }
+    }
+
+    /// Set the person's ORCID, which is checked when building [Authors].
+    pub fn with_orcid(mut self, orcid: impl AsRef<str>) -> Self {
+        self.orcid = Some(Orcid::new_unchecked(orcid));
+        self
+    }
+
+    /// Add an organisation to which the person belongs.
+    pub fn with_affiliation(mut self, affiliation: Affiliation) -> Self {
+        self.affiliations.push(affiliation);
+        self
+    }
+
+    /// Full name, e.g. `Josiah Carberry`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn orcid(&self) -> Option<&Orcid> {
+        self.orcid.as_ref()
+    }
+
+    pub fn affiliations(&self) -> &[Affiliation] {
+        &self.affiliations
+    }
+
+    /// Every identifier given for the person and their affiliations.
+    fn validate_ids(&self) -> Result<(), IdError> {
+        if let Some(orcid) = &self.orcid {
+            orcid.validate()?;
+        }
+        for ror in self.affiliations.iter().filter_map(Affiliation::ror) {
+            ror.validate()?;
+        }
+        Ok(())
+    }
+}
+
+/// How a contributor contributed to a dataset, as in DataCite's `contributorType`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub enum ContributorRole {
+    ContactPerson,
+    DataCollector,
+    DataCurator,
+    DataManager,
+    Distributor,
+    Editor,
+    HostingInstitution,
+    Producer,
+    ProjectLeader,
+    ProjectManager,
+    ProjectMember,
+    RegistrationAgency,
+    RegistrationAuthority,
+    RelatedPerson,
+    Researcher,
+    ResearchGroup,
+    RightsHolder,
+    Sponsor,
+    Supervisor,
+    WorkPackageLeader,
+    Other,
+}
+
+/// Someone who contributed to a dataset without being one of its creators.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Contributor {
+    #[serde(flatten)]
+    person: Person,
+    role: ContributorRole,
+}
+
+impl Contributor {
+    pub fn person(&self) -> &Person {
+        &self.person
+    }
+
+    pub fn role(&self) -> ContributorRole {
+        self.role
+    }
+}
+
+define_convention! {
+    /// Creators of and contributors to a dataset.
+    #[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+    #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+    pub struct Authors {
+        /// People and organisations who created the dataset, in order of precedence.
+        creators: Vec<Person>,
+        /// Others who contributed to the dataset, each with a DataCite contributor role.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        contributors: Vec<Contributor>,
+    }
+    schema_url = "https://raw.githubusercontent.com/clbarnes/zarrs_conventions/refs/tags/authors-v1/zarrs_conventions_authors/spec/schema.json",
+    spec_url = "https://github.com/clbarnes/zarrs_conventions/blob/authors-v1/zarrs_conventions_authors/spec/README.md",
+    name = "authors",
+    description = "Creators of and contributors to a dataset",
+    nested = "authors",
+    register = false,
+}
+
+impl Authors {
+    pub fn builder() -> Builder {
+        Default::default()
+    }
+
+    /// The main people or organisations responsible for the dataset, in order of priority.
+    pub fn creators(&self) -> &[Person] {
+        &self.creators
+    }
+
+    pub fn contributors(&self) -> &[Contributor] {
+        &self.contributors
+    }
+
+    /// Everyone credited, creators first.
+    pub fn people(&self) -> impl Iterator<Item = &Person> {
+        self.creators
+            .iter()
+            .chain(self.contributors.iter().map(Contributor::person))
+    }
+
+    /// Find a credited person by ORCID.
+    pub fn find_orcid(&self, orcid: &Orcid) -> Option<&Person> {
+        self.people().find(|p| p.orcid() == Some(orcid))
+    }
+}
+
+impl Lint for Authors {
+    fn lint(&self) -> Vec<Issue> {
+        let mut issues = Vec::default();
+        if self.creators.is_empty() {
+            issues.push(Issue::error(
+                "no-creators",
+                BuildError::NoCreators.to_string(),
+            ));
+        }
+        for person in self.people() {
+            if person.name.trim().is_empty() {
+                issues.push(Issue::error("empty-name", "a person has an empty name"));
+            }
+            if let Some(Err(e)) = person.orcid().map(Orcid::validate) {
+                issues.push(Issue::error("invalid-orcid", e.to_string()));
+            }
+            for affiliation in &person.affiliations {
+                if let Some(Err(e)) = affiliation.ror().map(Ror::validate) {
+                    issues.push(Issue::error("invalid-ror", e.to_string()));
+                }
+            }
+        }
+        issues
+    }
+}
+
+/// Why authors could not be built.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum BuildError {
+    #[error("at least one creator must be given")]
+    NoCreators,
+    #[error(transparent)]
+    Id(#[from] IdError),
+}
+
+/// Builder for [Authors], created by [Authors::builder].
+#[derive(Debug, Clone, Default)]
+pub struct Builder {
+    inner: Authors,
+}
+
+impl Builder {
+    /// Add the next creator, in order of priority.
+    pub fn creator(mut self, person: Person) -> Self {
+        self.inner.creators.push(person);
+        self
+    }
+
+    /// Add a contributor with the given role.
+    pub fn contributor(mut self, person: Person, role: ContributorRole) -> Self {
+        self.inner.contributors.push(Contributor { person, role });
+        self
+    }
+
+    /// Build the authors, checking that there is a creator
+    /// and that every ORCID and ROR ID is valid.
+    pub fn build(self) -> Result<Authors, BuildError> {
+        if self.inner.creators.is_empty() {
+            return Err(BuildError::NoCreators);
+        }
+        for person in self.inner.people() {
+            person.validate_ids()?;
+        }
+        Ok(self.inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use zarrs_conventions::validate::Lint;
+
+    use crate::{Affiliation, Authors, BuildError, ContributorRole, IdError, Orcid, Person};
+
+    #[test]
+    fn build() {
+        let authors = Authors::builder()
+            .creator(
+                Person::new("Josiah Carberry")
+                    .with_orcid("https://orcid.org/0000-0002-1825-0097")
+                    .with_affiliation(Affiliation::new("Brown University").with_ror("05gq02987")),
+            )
+            .contributor(Person::new("Jane Doe"), ContributorRole::DataCurator)
+            .build()
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(&authors).unwrap(),
+            json!({
+                "creators": [{
+                    "name": "Josiah Carberry",
+                    "orcid": "0000-0002-1825-0097",
+                    "affiliations": [{"name": "Brown University", "ror": "05gq02987"}],
+                }],
+                "contributors": [{"name": "Jane Doe", "role": "DataCurator"}],
+            })
+        );
+        let orcid = Orcid::new("0000-0002-1825-0097").unwrap();
+        assert_eq!(
+            authors.find_orcid(&orcid).unwrap().name(),
+            "Josiah Carberry"
+        );
+        assert!(authors.lint().is_empty());
+
+        assert_eq!(Authors::builder().build(), Err(BuildError::NoCreators));
+        assert_eq!(
+            Authors::builder()
+                .creator(Person::new("A").with_orcid("0000-0002-1825-0098"))
+                .build(),
+            Err(BuildError::Id(IdError::OrcidChecksum(
+                "0000-0002-1825-0098".into()
+            )))
+        );
+    }
+
+    #[test]
+    fn lint() {
+        let authors: Authors = serde_json::from_value(json!({
+            "creators": [],
+            "contributors": [{
+                "name": " ",
+                "orcid": "0000-0002-1825-0098",
+                "affiliations": [{"name": "Nowhere", "ror": "nowhere"}],
+                "role": "Other",
+            }],
+        }))
+        .unwrap();
+        let codes: Vec<_> = authors.lint().into_iter().map(|i| i.code).collect();
+        assert_eq!(
+            codes,
+            ["no-creators", "empty-name", "invalid-orcid", "invalid-ror"]
+        );
+    }
+}
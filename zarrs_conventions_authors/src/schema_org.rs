@@ -0,0 +1,83 @@
+//! Export to the `creator` and `contributor` properties of a [schema.org](https://schema.org/Dataset) dataset.
+use serde_json::{Value, json};
+
+use crate::{Affiliation, Authors, Person};
+
+fn affiliation(affiliation: &Affiliation) -> Value {
+    let mut value = json!({"@type": "Organization", "name": affiliation.name()});
+    if let Some(ror) = affiliation.ror() {
+        value["@id"] = ror.url().into();
+    }
+    value
+}
+
+fn person(person: &Person) -> Value {
+    let mut value = json!({"name": person.name()});
+    if let Some(orcid) = person.orcid() {
+        // only people have ORCIDs; otherwise, the name may be an organisation's
+        value["@type"] = "Person".into();
+        value["@id"] = orcid.url().into();
+    }
+    if !person.affiliations().is_empty() {
+        value["affiliation"] = person.affiliations().iter().map(affiliation).collect();
+    }
+    value
+}
+
+impl Authors {
+    /// The authors as the `creator` and `contributor` properties of a schema.org dataset,
+    /// omitting those with no people.
+    pub fn to_schema_org(&self) -> Value {
+        let mut value = json!({});
+        if !self.creators().is_empty() {
+            value["creator"] = self.creators().iter().map(person).collect();
+        }
+        if !self.contributors().is_empty() {
+            value["contributor"] = self
+                .contributors()
+                .iter()
+                .map(|c| person(c.person()))
+                .collect();
+        }
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{Affiliation, Authors, ContributorRole, Person};
+
+    #[test]
+    fn schema_org() {
+        let authors = Authors::builder()
+            .creator(
+                Person::new("Josiah Carberry")
+                    .with_orcid("0000-0002-1825-0097")
+                    .with_affiliation(Affiliation::new("Brown University").with_ror("05gq02987")),
+            )
+            .contributor(
+                Person::new("Brown University"),
+                ContributorRole::HostingInstitution,
+            )
+            .build()
+            .unwrap();
+        assert_eq!(
+            authors.to_schema_org(),
+            json!({
+                "creator": [{
+                    "@type": "Person",
+                    "@id": "https://orcid.org/0000-0002-1825-0097",
+                    "name": "Josiah Carberry",
+                    "affiliation": [{
+                        "@type": "Organization",
+                        "@id": "https://ror.org/05gq02987",
+                        "name": "Brown University",
+                    }],
+                }],
+                "contributor": [{"name": "Brown University"}],
+            })
+        );
+    }
+}
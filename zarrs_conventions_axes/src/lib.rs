@@ -0,0 +1,303 @@
+#![doc = include_str!("../README.md")]
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+pub use zarrs_conventions;
+use zarrs_conventions::{
+    define_convention,
+    validate::{Issue, Lint},
+};
+
+pub mod xarray;
+
+/// Why axes do not fit an array.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("{found} axes are given for an array with {expected} dimensions")]
+pub struct RankError {
+    pub expected: usize,
+    pub found: usize,
+}
+
+/// What an axis measures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum AxisType {
+    Space,
+    Time,
+    Channel,
+}
+
+/// Name of a single dimension of an array, and what it measures.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Axis {
+    /// Name of the dimension, unique within the array.
+    name: String,
+    /// What the axis measures.
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    axis_type: Option<AxisType>,
+    /// **Case-sensitive** UCUM unit of coordinates along the axis; not for channel axes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    unit: Option<String>,
+}
+
+impl Axis {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            axis_type: None,
+            unit: None,
+        }
+    }
+
+    pub fn with_type(mut self, axis_type: AxisType) -> Self {
+        self.axis_type = Some(axis_type);
+        self
+    }
+
+    /// Set the **case-sensitive** UCUM unit of coordinates along the axis.
+    pub fn with_unit(mut self, unit: impl Into<String>) -> Self {
+        self.unit = Some(unit.into());
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn axis_type(&self) -> Option<AxisType> {
+        self.axis_type
+    }
+
+    /// **Case-sensitive** UCUM unit of coordinates along the axis, if given.
+    pub fn unit(&self) -> Option<&str> {
+        self.unit.as_deref()
+    }
+
+    /// The axis unit as a uom payload, if given.
+    #[cfg(feature = "uom")]
+    pub fn uom(&self) -> Option<zarrs_conventions_uom::UnitOfMeasurement> {
+        let unit = self.unit()?;
+        Some(
+            zarrs_conventions_uom::UnitOfMeasurement::builder()
+                .unit(unit)
+                .build(),
+        )
+    }
+}
+
+define_convention! {
+    /// Names of an array's dimensions, in order.
+    ///
+    /// ```
+    /// use zarrs_conventions_axes::{Axes, Axis};
+    ///
+    /// let axes: Axes = vec![Axis::new("y"), Axis::new("x")].into();
+    /// assert_eq!(axes.names().collect::<Vec<_>>(), ["y", "x"]);
+    /// assert!(axes.validate_rank(3).is_err());
+    /// ```
+    #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+    #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+    #[serde(transparent)]
+    pub struct Axes(Vec<Axis>);
+    schema_url = "https://raw.githubusercontent.com/clbarnes/zarrs_conventions/refs/tags/axes-v1/zarrs_conventions_axes/spec/schema.json",
+    spec_url = "https://github.com/clbarnes/zarrs_conventions/blob/axes-v1/zarrs_conventions_axes/spec/README.md",
+    name = "axes",
+    description = "Names and types of array dimensions",
+    nested = "axes",
+    register = false,
+}
+
+impl Axes {
+    pub fn builder() -> AxesBuilder {
+        Default::default()
+    }
+
+    /// Number of axes described.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Axis of the given dimension.
+    pub fn get(&self, index: usize) -> Option<&Axis> {
+        self.0.get(index)
+    }
+
+    /// Axis with the given name.
+    pub fn get_by_name(&self, name: &str) -> Option<&Axis> {
+        self.0.iter().find(|a| a.name == name)
+    }
+
+    /// Dimension with the given name.
+    pub fn index_of(&self, name: &str) -> Option<usize> {
+        self.0.iter().position(|a| a.name == name)
+    }
+
+    /// Each axis, in order.
+    pub fn iter(&self) -> impl Iterator<Item = &Axis> {
+        self.0.iter()
+    }
+
+    /// Name of each axis, in order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(Axis::name)
+    }
+
+    /// Dimensions whose axes have the given type.
+    pub fn indices_of_type(&self, axis_type: AxisType) -> impl Iterator<Item = usize> {
+        self.0
+            .iter()
+            .enumerate()
+            .filter(move |(_, a)| a.axis_type == Some(axis_type))
+            .map(|(i, _)| i)
+    }
+
+    /// Check that there is one axis for each dimension of an array with the given rank.
+    pub fn validate_rank(&self, rank: usize) -> Result<(), RankError> {
+        if self.0.len() == rank {
+            Ok(())
+        } else {
+            Err(RankError {
+                expected: rank,
+                found: self.0.len(),
+            })
+        }
+    }
+}
+
+impl From<Vec<Axis>> for Axes {
+    fn from(value: Vec<Axis>) -> Self {
+        Self(value)
+    }
+}
+
+impl Lint for Axes {
+    fn lint(&self) -> Vec<Issue> {
+        let mut issues = Vec::default();
+        let mut names = HashSet::new();
+        for (index, axis) in self.0.iter().enumerate() {
+            if axis.name.is_empty() {
+                issues.push(Issue::error(
+                    "empty-axis-name",
+                    format!("axis {index} has an empty name"),
+                ));
+            } else if !names.insert(axis.name.as_str()) {
+                issues.push(Issue::error(
+                    "duplicate-axis-name",
+                    format!("axis name '{}' is used more than once", axis.name),
+                ));
+            }
+            if axis.axis_type == Some(AxisType::Channel) && axis.unit.is_some() {
+                issues.push(Issue::warning(
+                    "channel-axis-unit",
+                    format!("channel axis '{}' has a unit", axis.name),
+                ));
+            }
+            #[cfg(feature = "uom")]
+            if let Some(Err(e)) = axis.unit().map(zarrs_conventions_uom::Ucum::parse) {
+                issues.push(Issue::error(
+                    "invalid-axis-unit",
+                    format!("axis '{}': {e}", axis.name),
+                ));
+            }
+        }
+        issues
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct AxesBuilder {
+    axes: Vec<Axis>,
+}
+
+impl AxesBuilder {
+    /// Add the next axis.
+    pub fn axis(mut self, axis: Axis) -> Self {
+        self.axes.push(axis);
+        self
+    }
+
+    /// Build the axes, checking that there is one for each dimension of an array with the given rank.
+    pub fn build_for_rank(self, rank: usize) -> Result<Axes, RankError> {
+        let axes = self.build();
+        axes.validate_rank(rank)?;
+        Ok(axes)
+    }
+
+    pub fn build(self) -> Axes {
+        Axes(self.axes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use zarrs_conventions::validate::Lint;
+
+    use crate::{Axes, Axis, AxisType, RankError};
+
+    #[test]
+    fn axes() {
+        let axes = Axes::builder()
+            .axis(Axis::new("c").with_type(AxisType::Channel))
+            .axis(Axis::new("y").with_type(AxisType::Space).with_unit("um"))
+            .axis(Axis::new("x").with_type(AxisType::Space).with_unit("um"))
+            .build();
+        assert_eq!(
+            serde_json::to_value(&axes).unwrap(),
+            json!([
+                {"name": "c", "type": "channel"},
+                {"name": "y", "type": "space", "unit": "um"},
+                {"name": "x", "type": "space", "unit": "um"},
+            ])
+        );
+        assert_eq!(
+            axes.indices_of_type(AxisType::Space).collect::<Vec<_>>(),
+            [1, 2]
+        );
+        assert_eq!(axes.get_by_name("x").unwrap().unit(), Some("um"));
+        assert_eq!(
+            axes.validate_rank(2),
+            Err(RankError {
+                expected: 2,
+                found: 3
+            })
+        );
+        assert!(axes.lint().is_empty());
+    }
+
+    #[test]
+    fn lint() {
+        let axes: Axes = serde_json::from_value(json!([
+            {"name": ""},
+            {"name": "c", "type": "channel", "unit": "nm"},
+            {"name": "c"},
+        ]))
+        .unwrap();
+        let codes: Vec<_> = axes.lint().into_iter().map(|i| i.code).collect();
+        assert_eq!(
+            codes,
+            [
+                "empty-axis-name",
+                "channel-axis-unit",
+                "duplicate-axis-name"
+            ]
+        );
+    }
+
+    #[cfg(feature = "uom")]
+    #[test]
+    fn lint_units() {
+        let axes: Axes = vec![Axis::new("x").with_unit("UM")].into();
+        assert_eq!(axes.lint()[0].code, "invalid-axis-unit");
+        assert_eq!(
+            axes.get(0).unwrap().uom().unwrap().ucum().unit(),
+            Some("UM")
+        );
+    }
+}
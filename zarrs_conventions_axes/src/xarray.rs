@@ -0,0 +1,116 @@
+//! xarray's `_ARRAY_DIMENSIONS` attribute, which names an array's dimensions
+//! for tools which do not know the convention.
+use zarrs_conventions::{Attributes, NestedRepr, validate::Issue};
+
+use crate::{Axes, Axis};
+
+/// Key of xarray's dimension names attribute.
+pub const ARRAY_DIMENSIONS_KEY: &str = "_ARRAY_DIMENSIONS";
+
+/// Why an `_ARRAY_DIMENSIONS` attribute could not be read.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("'{ARRAY_DIMENSIONS_KEY}' attribute is not an array of strings")]
+pub struct ArrayDimensionsError;
+
+/// Dimension names from an `_ARRAY_DIMENSIONS` attribute, if present.
+pub fn read_array_dimensions(
+    attributes: &Attributes,
+) -> Result<Option<Vec<String>>, ArrayDimensionsError> {
+    let Some(value) = attributes.get(ARRAY_DIMENSIONS_KEY) else {
+        return Ok(None);
+    };
+    value
+        .as_array()
+        .ok_or(ArrayDimensionsError)?
+        .iter()
+        .map(|v| v.as_str().map(String::from).ok_or(ArrayDimensionsError))
+        .collect::<Result<_, _>>()
+        .map(Some)
+}
+
+/// Check that an `_ARRAY_DIMENSIONS` attribute and an axes payload, if both are present, agree.
+///
+/// ```
+/// use zarrs_conventions_axes::xarray::lint_array_dimensions;
+///
+/// let attributes = serde_json::json!({
+///     "_ARRAY_DIMENSIONS": ["x", "y"],
+///     "axes": [{"name": "y"}, {"name": "x"}],
+/// });
+/// let issues = lint_array_dimensions(attributes.as_object().unwrap());
+/// assert_eq!(issues[0].code, "inconsistent-array-dimensions");
+/// ```
+pub fn lint_array_dimensions(attributes: &Attributes) -> Vec<Issue> {
+    if !attributes.contains_key(Axes::KEY) {
+        return Vec::default();
+    }
+    let names = match read_array_dimensions(attributes) {
+        Ok(Some(names)) => names,
+        Ok(None) => return Vec::default(),
+        Err(e) => return vec![Issue::warning("invalid-array-dimensions", e.to_string())],
+    };
+    let Ok(axes) = Axes::from_attributes_nested(attributes) else {
+        // reported when validating the payload itself
+        return Vec::default();
+    };
+    if axes.names().eq(names.iter().map(String::as_str)) {
+        return Vec::default();
+    }
+    vec![Issue::warning(
+        "inconsistent-array-dimensions",
+        format!(
+            "'{ARRAY_DIMENSIONS_KEY}' attribute is {names:?}, but the axes are named {:?}",
+            axes.names().collect::<Vec<_>>()
+        ),
+    )]
+}
+
+impl Axes {
+    /// Untyped axes named by an `_ARRAY_DIMENSIONS` attribute, if present.
+    pub fn from_array_dimensions(
+        attributes: &Attributes,
+    ) -> Result<Option<Self>, ArrayDimensionsError> {
+        Ok(read_array_dimensions(attributes)?
+            .map(|names| names.into_iter().map(Axis::new).collect::<Vec<_>>().into()))
+    }
+
+    /// Write the axis names as an `_ARRAY_DIMENSIONS` attribute, as read by xarray.
+    pub fn write_array_dimensions(&self, attributes: &mut Attributes) {
+        attributes.insert(
+            ARRAY_DIMENSIONS_KEY.to_string(),
+            self.names().collect::<Vec<_>>().into(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{ArrayDimensionsError, lint_array_dimensions};
+    use crate::{Axes, Axis};
+
+    #[test]
+    fn array_dimensions() {
+        let axes: Axes = vec![Axis::new("y"), Axis::new("x")].into();
+        let mut attributes = serde_json::Map::default();
+        axes.write_array_dimensions(&mut attributes);
+        assert_eq!(
+            Axes::from_array_dimensions(&attributes),
+            Ok(Some(axes.clone()))
+        );
+        attributes.insert("axes".into(), serde_json::to_value(&axes).unwrap());
+        assert!(lint_array_dimensions(&attributes).is_empty());
+
+        let attributes = json!({"_ARRAY_DIMENSIONS": [1], "axes": []});
+        let attributes = attributes.as_object().unwrap();
+        assert_eq!(
+            Axes::from_array_dimensions(attributes),
+            Err(ArrayDimensionsError)
+        );
+        assert_eq!(
+            lint_array_dimensions(attributes)[0].code,
+            "invalid-array-dimensions"
+        );
+    }
+}
@@ -0,0 +1,492 @@
+#![doc = include_str!("../README.md")]
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+pub use zarrs_conventions;
+use zarrs_conventions::{
+    define_convention,
+    validate::{Issue, Lint},
+};
+
+pub mod ome;
+
+/// UCUM units of length accepted for wavelengths, and their size in nanometres.
+static UNITS: &[(&str, f64)] = &[
+    ("pm", 1e-3),
+    ("Ao", 0.1),
+    ("nm", 1.0),
+    ("um", 1e3),
+    ("mm", 1e6),
+    ("cm", 1e7),
+    ("m", 1e9),
+];
+
+fn nm() -> String {
+    "nm".to_string()
+}
+
+/// A wavelength of light, in a UCUM unit of length, `nm` by default.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Wavelength {
+    /// The wavelength, in `unit`.
+    value: f64,
+    /// **Case-sensitive** UCUM unit of length, e.g. `um`.
+    #[serde(default = "nm")]
+    unit: String,
+}
+
+impl Wavelength {
+    /// A wavelength in the given **case-sensitive** UCUM unit, e.g. `um`.
+    pub fn new(value: f64, unit: impl Into<String>) -> Self {
+        Self {
+            value,
+            unit: unit.into(),
+        }
+    }
+
+    /// A wavelength in nanometres.
+    pub fn nm(value: f64) -> Self {
+        Self::new(value, nm())
+    }
+
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    pub fn unit(&self) -> &str {
+        &self.unit
+    }
+
+    /// The wavelength in nanometres, if the unit is one of `pm`, `Ao`, `nm`, `um`, `mm`, `cm`, or `m`.
+    ///
+    /// ```
+    /// use zarrs_conventions_channels::Wavelength;
+    ///
+    /// assert_eq!(Wavelength::new(0.488, "um").nanometers(), Some(488.0));
+    /// assert_eq!(Wavelength::new(488.0, "furlong").nanometers(), None);
+    /// ```
+    pub fn nanometers(&self) -> Option<f64> {
+        UNITS
+            .iter()
+            .find(|(u, _)| *u == self.unit)
+            .map(|(_, nm)| self.value * nm)
+    }
+
+    fn validate(&self) -> Result<(), ChannelError> {
+        if !(self.value.is_finite() && self.value > 0.0) {
+            return Err(ChannelError::InvalidWavelength(self.value));
+        }
+        if self.nanometers().is_none() {
+            return Err(ChannelError::UnknownUnit(self.unit.clone()));
+        }
+        Ok(())
+    }
+}
+
+/// How a single channel was acquired.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Channel {
+    /// Name of the channel, e.g. `DAPI`.
+    name: String,
+    /// Wavelength of the light which excited the sample.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    excitation: Option<Wavelength>,
+    /// Wavelength of the light which was detected, e.g. the center of an emission filter.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    emission: Option<Wavelength>,
+    /// Fluorophore imaged in the channel, e.g. `EGFP`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    fluorophore: Option<String>,
+    /// Identifier of a detector's spectral band, e.g. `B04` of Sentinel-2.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    band: Option<String>,
+}
+
+impl Channel {
+    /// A channel with the given name, e.g. `DAPI`.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            excitation: None,
+            emission: None,
+            fluorophore: None,
+            band: None,
+        }
+    }
+
+    /// Set the wavelength of the light which excited the sample.
+    pub fn with_excitation(mut self, wavelength: Wavelength) -> Self {
+        self.excitation = Some(wavelength);
+        self
+    }
+
+    /// Set the wavelength of the light which was detected, e.g. the center of an emission filter.
+    pub fn with_emission(mut self, wavelength: Wavelength) -> Self {
+        self.emission = Some(wavelength);
+        self
+    }
+
+    /// Set the fluorophore imaged in the channel, e.g. `EGFP`.
+    pub fn with_fluorophore(mut self, fluorophore: impl Into<String>) -> Self {
+        self.fluorophore = Some(fluorophore.into());
+        self
+    }
+
+    /// Set the identifier of a detector's spectral band, e.g. `B04` of Sentinel-2.
+    pub fn with_band(mut self, band: impl Into<String>) -> Self {
+        self.band = Some(band.into());
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn excitation(&self) -> Option<&Wavelength> {
+        self.excitation.as_ref()
+    }
+
+    pub fn emission(&self) -> Option<&Wavelength> {
+        self.emission.as_ref()
+    }
+
+    pub fn fluorophore(&self) -> Option<&str> {
+        self.fluorophore.as_deref()
+    }
+
+    pub fn band(&self) -> Option<&str> {
+        self.band.as_deref()
+    }
+
+    /// Problems with the channel's details.
+    fn validate(&self) -> Vec<ChannelError> {
+        let mut errors = Vec::default();
+        if self.name.trim().is_empty() {
+            errors.push(ChannelError::EmptyName);
+        }
+        errors.extend(
+            [&self.excitation, &self.emission]
+                .into_iter()
+                .flatten()
+                .filter_map(|w| w.validate().err()),
+        );
+        errors
+    }
+}
+
+/// Why a channel's details are invalid.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[non_exhaustive]
+pub enum ChannelError {
+    #[error("channel name is empty")]
+    EmptyName,
+    #[error("wavelength {0} is not a finite, positive number")]
+    InvalidWavelength(f64),
+    #[error("'{0}' is not a supported unit of wavelength")]
+    UnknownUnit(String),
+}
+
+impl ChannelError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::EmptyName => "empty-name",
+            Self::InvalidWavelength(_) => "invalid-wavelength",
+            Self::UnknownUnit(_) => "unknown-wavelength-unit",
+        }
+    }
+}
+
+define_convention! {
+    /// Acquisition details of each channel of an array.
+    #[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+    #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+    pub struct Channels {
+        /// Index of the array dimension over which channels vary, if there is more than one.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        channel_axis: Option<usize>,
+        /// Details of each channel, in order.
+        channels: Vec<Channel>,
+    }
+    schema_url = "https://raw.githubusercontent.com/clbarnes/zarrs_conventions/refs/tags/channels-v1/zarrs_conventions_channels/spec/schema.json",
+    spec_url = "https://github.com/clbarnes/zarrs_conventions/blob/channels-v1/zarrs_conventions_channels/spec/README.md",
+    name = "channels",
+    description = "Acquisition details of each channel of an array, such as wavelengths and fluorophores",
+    nested = "channels",
+    register = false,
+}
+
+impl Channels {
+    pub fn builder() -> Builder {
+        Default::default()
+    }
+
+    pub fn channel_axis(&self) -> Option<usize> {
+        self.channel_axis
+    }
+
+    /// Details of each channel, in order.
+    pub fn channels(&self) -> &[Channel] {
+        &self.channels
+    }
+
+    /// Details of the first channel with the given name.
+    pub fn get_by_name(&self, name: &str) -> Option<&Channel> {
+        self.channels.iter().find(|c| c.name == name)
+    }
+
+    /// Index along the channel axis of the first channel with the given name.
+    pub fn index_of(&self, name: &str) -> Option<usize> {
+        self.channels.iter().position(|c| c.name == name)
+    }
+
+    /// Check that there is one channel for each index along the channel axis of an array with the given shape.
+    ///
+    /// ```
+    /// use zarrs_conventions_channels::{Channel, Channels};
+    ///
+    /// let channels = Channels::builder()
+    ///     .channel_axis(0)
+    ///     .channel(Channel::new("DAPI"))
+    ///     .channel(Channel::new("GFP"))
+    ///     .build()
+    ///     .unwrap();
+    /// assert!(channels.validate_shape(&[2, 512, 512]).is_ok());
+    /// assert!(channels.validate_shape(&[3, 512, 512]).is_err());
+    /// ```
+    pub fn validate_shape(&self, shape: &[u64]) -> Result<(), BuildError> {
+        let found = self.channels.len();
+        match self.channel_axis {
+            Some(axis) if axis >= shape.len() => Err(BuildError::ChannelAxisOutOfRange {
+                axis,
+                rank: shape.len(),
+            }),
+            Some(axis) if shape[axis] != found as u64 => Err(BuildError::ChannelCount {
+                expected: shape[axis],
+                found,
+            }),
+            None if found != 1 => Err(BuildError::ChannelCount { expected: 1, found }),
+            _ => Ok(()),
+        }
+    }
+
+    fn validate(&self) -> Result<(), BuildError> {
+        match self.channels.len() {
+            0 => return Err(BuildError::NoChannels),
+            1 => (),
+            _ if self.channel_axis.is_none() => return Err(BuildError::NoChannelAxis),
+            _ => (),
+        }
+        for (index, channel) in self.channels.iter().enumerate() {
+            if let Some(source) = channel.validate().into_iter().next() {
+                return Err(BuildError::Channel { index, source });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Lint for Channels {
+    fn lint(&self) -> Vec<Issue> {
+        let mut issues = Vec::default();
+        if let Err(e @ (BuildError::NoChannels | BuildError::NoChannelAxis)) = self.validate() {
+            let code = match e {
+                BuildError::NoChannels => "no-channels",
+                _ => "no-channel-axis",
+            };
+            issues.push(Issue::error(code, e.to_string()));
+        }
+        let mut names = HashSet::new();
+        for (index, channel) in self.channels.iter().enumerate() {
+            for e in channel.validate() {
+                issues.push(Issue::error(e.code(), format!("channel {index}: {e}")));
+            }
+            if !names.insert(channel.name.as_str()) {
+                issues.push(Issue::warning(
+                    "duplicate-channel-name",
+                    format!(
+                        "channel {index}: name '{}' is used more than once",
+                        channel.name
+                    ),
+                ));
+            }
+            let nanometers = |w: &Option<Wavelength>| w.as_ref().and_then(Wavelength::nanometers);
+            if let (Some(excitation), Some(emission)) = (
+                nanometers(&channel.excitation),
+                nanometers(&channel.emission),
+            ) && emission < excitation
+            {
+                issues.push(Issue::warning(
+                    "emission-below-excitation",
+                    format!(
+                        "channel {index}: emission wavelength is shorter than excitation wavelength"
+                    ),
+                ));
+            }
+        }
+        issues
+    }
+}
+
+/// Why channel metadata could not be built.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[non_exhaustive]
+pub enum BuildError {
+    #[error("at least one channel must be given")]
+    NoChannels,
+    #[error("a channel axis must be given for more than one channel")]
+    NoChannelAxis,
+    #[error("channel axis {axis} is out of range for an array with {rank} dimensions")]
+    ChannelAxisOutOfRange { axis: usize, rank: usize },
+    #[error("{found} channels are given for an array with {expected} channels")]
+    ChannelCount { expected: u64, found: usize },
+    #[error("channel {index}: {source}")]
+    Channel { index: usize, source: ChannelError },
+}
+
+/// Builder for [Channels], created by [Channels::builder].
+#[derive(Debug, Clone, Default)]
+pub struct Builder {
+    inner: Channels,
+}
+
+impl Builder {
+    /// Add the details of the next channel.
+    pub fn channel(mut self, channel: Channel) -> Self {
+        self.inner.channels.push(channel);
+        self
+    }
+
+    /// Set the index of the array dimension over which channels vary.
+    pub fn channel_axis(mut self, axis: usize) -> Self {
+        self.inner.channel_axis = Some(axis);
+        self
+    }
+
+    /// Build the metadata, checking the details of each channel.
+    pub fn build(self) -> Result<Channels, BuildError> {
+        self.inner.validate()?;
+        Ok(self.inner)
+    }
+
+    /// Build the metadata, checking that there is one channel for each index along the channel axis
+    /// of an array with the given shape.
+    pub fn build_for_shape(self, shape: &[u64]) -> Result<Channels, BuildError> {
+        self.inner.validate_shape(shape)?;
+        self.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use zarrs_conventions::validate::Lint;
+
+    use crate::{BuildError, Channel, ChannelError, Channels, Wavelength};
+
+    #[test]
+    fn build() {
+        let channels = Channels::builder()
+            .channel_axis(1)
+            .channel(
+                Channel::new("DAPI")
+                    .with_excitation(Wavelength::nm(405.0))
+                    .with_emission(Wavelength::new(0.45, "um"))
+                    .with_fluorophore("DAPI"),
+            )
+            .channel(Channel::new("red").with_band("B04"))
+            .build_for_shape(&[10, 2, 512, 512])
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(&channels).unwrap(),
+            json!({
+                "channel_axis": 1,
+                "channels": [
+                    {
+                        "name": "DAPI",
+                        "excitation": {"value": 405.0, "unit": "nm"},
+                        "emission": {"value": 0.45, "unit": "um"},
+                        "fluorophore": "DAPI",
+                    },
+                    {"name": "red", "band": "B04"},
+                ],
+            })
+        );
+        assert!(channels.lint().is_empty());
+        assert_eq!(channels.index_of("red"), Some(1));
+        assert_eq!(
+            channels
+                .get_by_name("DAPI")
+                .unwrap()
+                .emission()
+                .unwrap()
+                .nanometers(),
+            Some(450.0)
+        );
+        assert_eq!(
+            channels.validate_shape(&[10]),
+            Err(BuildError::ChannelAxisOutOfRange { axis: 1, rank: 1 })
+        );
+
+        assert_eq!(
+            Channels::builder()
+                .channel(Channel::new("DAPI"))
+                .build_for_shape(&[512, 512])
+                .unwrap()
+                .channel_axis(),
+            None
+        );
+        assert_eq!(
+            Channels::builder()
+                .channel_axis(2)
+                .channel(Channel::new("DAPI"))
+                .build_for_shape(&[512, 512, 3]),
+            Err(BuildError::ChannelCount {
+                expected: 3,
+                found: 1
+            })
+        );
+        assert_eq!(
+            Channels::builder()
+                .channel(Channel::new("DAPI"))
+                .channel(Channel::new("GFP"))
+                .build(),
+            Err(BuildError::NoChannelAxis)
+        );
+        assert_eq!(
+            Channels::builder()
+                .channel(Channel::new("GFP").with_excitation(Wavelength::new(488.0, "nanometre")))
+                .build(),
+            Err(BuildError::Channel {
+                index: 0,
+                source: ChannelError::UnknownUnit("nanometre".into())
+            })
+        );
+    }
+
+    #[test]
+    fn lint() {
+        let channels: Channels = serde_json::from_value(json!({
+            "channels": [
+                {"name": "", "excitation": {"value": -1.0}},
+                {"name": "GFP", "excitation": {"value": 488}, "emission": {"value": 0.4, "unit": "um"}},
+                {"name": "GFP", "emission": {"value": 510, "unit": "nmm"}},
+            ],
+        }))
+        .unwrap();
+        assert_eq!(channels.channels()[1].excitation().unwrap().unit(), "nm");
+        let codes: Vec<_> = channels.lint().into_iter().map(|i| i.code).collect();
+        assert_eq!(
+            codes,
+            [
+                "no-channel-axis",
+                "empty-name",
+                "invalid-wavelength",
+                "emission-below-excitation",
+                "unknown-wavelength-unit",
+                "duplicate-channel-name",
+            ]
+        );
+    }
+}
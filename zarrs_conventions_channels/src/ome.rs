@@ -0,0 +1,340 @@
+//! Conversion to and from the channel metadata of OME:
+//! the `Channel` elements of the [OME data model](https://www.openmicroscopy.org/Schemas/Documentation/Generated/OME-2016-06/ome_xsd.html#Channel),
+//! and the channel labels of [OME-NGFF](https://ngff.openmicroscopy.org/latest/#omero-md)'s `omero` metadata.
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use zarrs_conventions::Attributes;
+
+use crate::{BuildError, Channel, Channels, Wavelength};
+
+/// UCUM units of wavelength, and their OME symbols.
+static UNITS: &[(&str, &str)] = &[
+    ("pm", "pm"),
+    ("Ao", "Å"),
+    ("nm", "nm"),
+    ("um", "µm"),
+    ("mm", "mm"),
+    ("cm", "cm"),
+    ("m", "m"),
+];
+
+/// Why channel metadata could not be converted to or from OME.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[non_exhaustive]
+pub enum OmeError {
+    #[error("'{0}' has no equivalent unit of wavelength")]
+    UnknownUnit(String),
+    #[error("OME-NGFF metadata is malformed: {0}")]
+    Malformed(&'static str),
+    #[error(transparent)]
+    Build(#[from] BuildError),
+}
+
+/// The attributes of an OME `Channel` element which this convention describes.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct OmeChannel {
+    #[serde(rename = "ID")]
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fluor: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub excitation_wavelength: Option<f64>,
+    /// OME symbol of the excitation wavelength's unit, `nm` if not given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub excitation_wavelength_unit: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub emission_wavelength: Option<f64>,
+    /// OME symbol of the emission wavelength's unit, `nm` if not given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub emission_wavelength_unit: Option<String>,
+}
+
+fn to_ome(wavelength: &Option<Wavelength>) -> Result<(Option<f64>, Option<String>), OmeError> {
+    let Some(wavelength) = wavelength else {
+        return Ok((None, None));
+    };
+    let (_, symbol) = UNITS
+        .iter()
+        .find(|(ucum, _)| *ucum == wavelength.unit)
+        .ok_or_else(|| OmeError::UnknownUnit(wavelength.unit.clone()))?;
+    Ok((Some(wavelength.value), Some(symbol.to_string())))
+}
+
+fn from_ome(value: Option<f64>, unit: &Option<String>) -> Result<Option<Wavelength>, OmeError> {
+    let Some(value) = value else {
+        return Ok(None);
+    };
+    let ucum = match unit {
+        Some(unit) => UNITS
+            .iter()
+            .find(|(_, symbol)| symbol == unit)
+            .map(|(ucum, _)| *ucum)
+            .ok_or_else(|| OmeError::UnknownUnit(unit.clone()))?,
+        None => "nm",
+    };
+    Ok(Some(Wavelength::new(value, ucum)))
+}
+
+/// The OME-NGFF metadata of a group, which is under `ome` from version 0.5.
+fn ome_metadata(attributes: &Attributes) -> &Attributes {
+    match attributes.get("ome") {
+        Some(Value::Object(ome)) => ome,
+        _ => attributes,
+    }
+}
+
+impl Channels {
+    /// The OME `Channel` elements of an image, with IDs of the form `Channel:0:<index>`.
+    ///
+    /// Spectral bands have no equivalent in OME, and are dropped.
+    ///
+    /// ```
+    /// use zarrs_conventions_channels::{Channel, Channels, Wavelength};
+    ///
+    /// let channels = Channels::builder()
+    ///     .channel(Channel::new("GFP").with_excitation(Wavelength::new(0.488, "um")))
+    ///     .build()
+    ///     .unwrap();
+    /// let ome = channels.to_ome().unwrap();
+    /// assert_eq!(ome[0].excitation_wavelength_unit.as_deref(), Some("µm"));
+    /// assert_eq!(Channels::from_ome(&ome, None).unwrap(), channels);
+    /// ```
+    pub fn to_ome(&self) -> Result<Vec<OmeChannel>, OmeError> {
+        self.channels
+            .iter()
+            .enumerate()
+            .map(|(index, channel)| {
+                let (excitation_wavelength, excitation_wavelength_unit) =
+                    to_ome(&channel.excitation)?;
+                let (emission_wavelength, emission_wavelength_unit) = to_ome(&channel.emission)?;
+                Ok(OmeChannel {
+                    id: format!("Channel:0:{index}"),
+                    name: Some(channel.name.clone()),
+                    fluor: channel.fluorophore.clone(),
+                    excitation_wavelength,
+                    excitation_wavelength_unit,
+                    emission_wavelength,
+                    emission_wavelength_unit,
+                })
+            })
+            .collect()
+    }
+
+    /// Channel metadata from OME `Channel` elements, named by their ID if they have no name.
+    pub fn from_ome(
+        channels: &[OmeChannel],
+        channel_axis: Option<usize>,
+    ) -> Result<Self, OmeError> {
+        let mut builder = Self::builder();
+        if let Some(axis) = channel_axis {
+            builder = builder.channel_axis(axis);
+        }
+        for ome in channels {
+            builder = builder.channel(Channel {
+                name: ome.name.clone().unwrap_or_else(|| ome.id.clone()),
+                excitation: from_ome(ome.excitation_wavelength, &ome.excitation_wavelength_unit)?,
+                emission: from_ome(ome.emission_wavelength, &ome.emission_wavelength_unit)?,
+                fluorophore: ome.fluor.clone(),
+                band: None,
+            });
+        }
+        Ok(builder.build()?)
+    }
+
+    /// Channel names from the labels in OME-NGFF `omero` metadata,
+    /// or `None` if there is no `omero` metadata.
+    ///
+    /// The channel axis is that of type `channel` in the first multiscale image.
+    ///
+    /// ```
+    /// use zarrs_conventions_channels::Channels;
+    ///
+    /// let attributes = serde_json::json!({
+    ///     "ome": {
+    ///         "version": "0.5",
+    ///         "multiscales": [{"axes": [{"name": "c", "type": "channel"}, {"name": "y"}, {"name": "x"}]}],
+    ///         "omero": {"channels": [{"label": "DAPI", "color": "0000FF"}, {"label": "GFP"}]},
+    ///     },
+    /// });
+    /// let channels = Channels::from_omero(attributes.as_object().unwrap()).unwrap().unwrap();
+    /// assert_eq!(channels.channel_axis(), Some(0));
+    /// assert_eq!(channels.index_of("GFP"), Some(1));
+    /// ```
+    pub fn from_omero(attributes: &Attributes) -> Result<Option<Self>, OmeError> {
+        let ome = ome_metadata(attributes);
+        let Some(omero) = ome.get("omero") else {
+            return Ok(None);
+        };
+        let labels = omero
+            .get("channels")
+            .and_then(Value::as_array)
+            .ok_or(OmeError::Malformed("'omero' has no 'channels' array"))?
+            .iter()
+            .map(|channel| {
+                channel
+                    .get("label")
+                    .and_then(Value::as_str)
+                    .ok_or(OmeError::Malformed("an 'omero' channel has no 'label'"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let channel_axis = ome
+            .get("multiscales")
+            .and_then(|m| m.get(0)?.get("axes")?.as_array())
+            .and_then(|axes| {
+                axes.iter()
+                    .position(|a| a.get("type").and_then(Value::as_str) == Some("channel"))
+            });
+        let mut builder = Self::builder();
+        if let Some(axis) = channel_axis {
+            builder = builder.channel_axis(axis);
+        }
+        for label in labels {
+            builder = builder.channel(Channel::new(label));
+        }
+        Ok(Some(builder.build()?))
+    }
+
+    /// Write the channel names as labels in OME-NGFF `omero` metadata,
+    /// keeping any other details of the `omero` channels, e.g. their colors.
+    ///
+    /// Labels are written under `ome` if it is present, as from OME-NGFF version 0.5.
+    pub fn write_omero_labels(&self, attributes: &mut Attributes) -> Result<(), OmeError> {
+        let ome = match attributes.get_mut("ome") {
+            Some(Value::Object(_)) => attributes
+                .get_mut("ome")
+                .and_then(Value::as_object_mut)
+                .expect("checked to be an object"),
+            _ => attributes,
+        };
+        let omero = ome
+            .entry("omero")
+            .or_insert_with(|| Value::Object(Default::default()))
+            .as_object_mut()
+            .ok_or(OmeError::Malformed("'omero' is not an object"))?;
+        let entries = omero
+            .entry("channels")
+            .or_insert_with(|| Value::Array(Default::default()))
+            .as_array_mut()
+            .ok_or(OmeError::Malformed("'omero' channels are not an array"))?;
+        for (index, channel) in self.channels.iter().enumerate() {
+            if entries.len() <= index {
+                entries.push(Value::Object(Default::default()));
+            }
+            entries[index]
+                .as_object_mut()
+                .ok_or(OmeError::Malformed("an 'omero' channel is not an object"))?
+                .insert("label".into(), channel.name.clone().into());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{OmeChannel, OmeError};
+    use crate::{BuildError, Channel, Channels, Wavelength};
+
+    #[test]
+    fn ome() {
+        let channels = Channels::builder()
+            .channel_axis(0)
+            .channel(
+                Channel::new("GFP")
+                    .with_excitation(Wavelength::nm(488.0))
+                    .with_emission(Wavelength::new(5100.0, "Ao"))
+                    .with_fluorophore("EGFP")
+                    .with_band("green"),
+            )
+            .channel(Channel::new("brightfield"))
+            .build()
+            .unwrap();
+        let ome = channels.to_ome().unwrap();
+        assert_eq!(
+            serde_json::to_value(&ome).unwrap(),
+            json!([
+                {
+                    "ID": "Channel:0:0",
+                    "Name": "GFP",
+                    "Fluor": "EGFP",
+                    "ExcitationWavelength": 488.0,
+                    "ExcitationWavelengthUnit": "nm",
+                    "EmissionWavelength": 5100.0,
+                    "EmissionWavelengthUnit": "Å",
+                },
+                {"ID": "Channel:0:1", "Name": "brightfield"},
+            ])
+        );
+        let round_trip = Channels::from_ome(&ome, Some(0)).unwrap();
+        assert_eq!(round_trip.channels()[0].band(), None);
+        assert_eq!(
+            round_trip.channels()[0].emission(),
+            channels.channels()[0].emission()
+        );
+
+        let unnamed: Vec<OmeChannel> = serde_json::from_value(json!([
+            {"ID": "Channel:0:0", "EmissionWavelength": 520},
+        ]))
+        .unwrap();
+        let unnamed = Channels::from_ome(&unnamed, None).unwrap();
+        assert_eq!(unnamed.channels()[0].name(), "Channel:0:0");
+        assert_eq!(
+            unnamed.channels()[0].emission(),
+            Some(&Wavelength::nm(520.0))
+        );
+
+        let furlongs: Channels = serde_json::from_value(json!({
+            "channels": [{"name": "GFP", "emission": {"value": 1, "unit": "[fur_us]"}}],
+        }))
+        .unwrap();
+        assert_eq!(
+            furlongs.to_ome(),
+            Err(OmeError::UnknownUnit("[fur_us]".into()))
+        );
+    }
+
+    #[test]
+    fn omero() {
+        let mut attributes = json!({
+            "multiscales": [{"axes": [{"name": "y"}, {"name": "x"}]}],
+            "omero": {"channels": [{"label": "DAPI", "color": "0000FF"}]},
+        });
+        let attributes = attributes.as_object_mut().unwrap();
+        let channels = Channels::from_omero(attributes).unwrap().unwrap();
+        assert_eq!(channels.channel_axis(), None);
+        assert_eq!(channels.channels(), [Channel::new("DAPI")]);
+
+        let renamed = Channels::builder()
+            .channel(Channel::new("nuclei"))
+            .build()
+            .unwrap();
+        renamed.write_omero_labels(attributes).unwrap();
+        assert_eq!(
+            attributes["omero"],
+            json!({"channels": [{"label": "nuclei", "color": "0000FF"}]})
+        );
+
+        let mut v05 = json!({"ome": {"version": "0.5"}});
+        let v05 = v05.as_object_mut().unwrap();
+        renamed.write_omero_labels(v05).unwrap();
+        assert_eq!(
+            v05["ome"]["omero"],
+            json!({"channels": [{"label": "nuclei"}]})
+        );
+        assert_eq!(Channels::from_omero(v05).unwrap(), Some(renamed));
+
+        assert_eq!(Channels::from_omero(&Default::default()), Ok(None));
+        assert_eq!(
+            Channels::from_omero(json!({"omero": {"channels": []}}).as_object().unwrap()),
+            Err(OmeError::Build(BuildError::NoChannels))
+        );
+        assert!(matches!(
+            Channels::from_omero(json!({"omero": {}}).as_object().unwrap()),
+            Err(OmeError::Malformed(_))
+        ));
+    }
+}
@@ -0,0 +1,153 @@
+//! Digest algorithms.
+use std::fmt::Write;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Algorithm used to compute a digest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum Algorithm {
+    /// SHA-256, for fixity checks which must detect deliberate tampering.
+    Sha256,
+    /// CRC-32 (IEEE), for fast detection of accidental corruption.
+    Crc32,
+}
+
+impl Algorithm {
+    /// Name of the algorithm, as serialized.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Sha256 => "sha256",
+            Self::Crc32 => "crc32",
+        }
+    }
+
+    /// Length of the digest in bytes.
+    pub fn digest_len(self) -> usize {
+        match self {
+            Self::Sha256 => 32,
+            Self::Crc32 => 4,
+        }
+    }
+
+    /// Digest of some bytes, as lowercase hexadecimal.
+    ///
+    /// ```
+    /// use zarrs_conventions_checksum::Algorithm;
+    ///
+    /// assert_eq!(Algorithm::Crc32.digest(b"123456789"), "cbf43926");
+    /// ```
+    pub fn digest(self, bytes: &[u8]) -> String {
+        let mut hasher = self.hasher();
+        hasher.update(bytes);
+        hasher.finish()
+    }
+
+    /// Incremental digest computation.
+    pub fn hasher(self) -> Hasher {
+        match self {
+            Self::Sha256 => Hasher::Sha256(Sha256::default()),
+            Self::Crc32 => Hasher::Crc32(crc32fast::Hasher::new()),
+        }
+    }
+
+    /// Whether a string is a digest of the right length for this algorithm, in hexadecimal.
+    pub fn is_digest(self, value: &str) -> bool {
+        value.len() == 2 * self.digest_len() && value.bytes().all(|b| b.is_ascii_hexdigit())
+    }
+}
+
+/// A digest being computed from bytes fed in turn.
+#[derive(Debug, Clone)]
+pub enum Hasher {
+    Sha256(Sha256),
+    Crc32(crc32fast::Hasher),
+}
+
+impl Hasher {
+    pub fn update(&mut self, bytes: &[u8]) {
+        match self {
+            Self::Sha256(sha) => sha.update(bytes),
+            Self::Crc32(crc) => crc.update(bytes),
+        }
+    }
+
+    /// The digest, as lowercase hexadecimal.
+    pub fn finish(self) -> String {
+        let bytes = match self {
+            Self::Sha256(sha) => sha.finalize().to_vec(),
+            Self::Crc32(crc) => crc.finalize().to_be_bytes().to_vec(),
+        };
+        bytes.iter().fold(String::default(), |mut out, b| {
+            write!(out, "{b:02x}").expect("writing to a string cannot fail");
+            out
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Algorithm;
+
+    #[test]
+    fn sha256() {
+        let cases = [
+            (
+                b"".to_vec(),
+                "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+            ),
+            (
+                b"abc".to_vec(),
+                "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+            ),
+            (
+                vec![b'a'; 1000],
+                "41edece42d63e8d9bf515a9ba6932e1c20cbc9f5a5d134645adb5db1b9737ea3",
+            ),
+            // lengths either side of the padding boundaries
+            (
+                vec![b'a'; 55],
+                "9f4390f8d30c2dd92ec9f095b65e2b9ae9b0a925a5258e241c9f1e910f734318",
+            ),
+            (
+                vec![b'a'; 56],
+                "b35439a4ac6f0948b6d6f9e3c6af0f5f590ce20f1bde7090ef7970686ec6738a",
+            ),
+            (
+                vec![b'a'; 63],
+                "7d3e74a05d7db15bce4ad9ec0658ea98e3f06eeecf16b4c6fff2da457ddc2f34",
+            ),
+            (
+                vec![b'a'; 64],
+                "ffe054fe7ae0cb6dc65c3af9b61d5209f439851db43d0ba5997337df154668eb",
+            ),
+            (
+                vec![b'a'; 119],
+                "31eba51c313a5c08226adf18d4a359cfdfd8d2e816b13f4af952f7ea6584dcfb",
+            ),
+            (
+                vec![b'a'; 120],
+                "2f3d335432c70b580af0e8e1b3674a7c020d683aa5f73aaaedfdc55af904c21c",
+            ),
+        ];
+        for (bytes, digest) in cases {
+            assert_eq!(Algorithm::Sha256.digest(&bytes), digest);
+            let mut hasher = Algorithm::Sha256.hasher();
+            for chunk in bytes.chunks(7) {
+                hasher.update(chunk);
+            }
+            assert_eq!(hasher.finish(), digest);
+        }
+    }
+
+    #[test]
+    fn crc32() {
+        assert_eq!(Algorithm::Crc32.digest(b"123456789"), "cbf43926");
+        assert_eq!(Algorithm::Crc32.digest(&[1, 2, 3, 4]), "b63cfbcd");
+        assert!(Algorithm::Crc32.is_digest("B63CFBCD"));
+        assert!(!Algorithm::Crc32.is_digest("b63cfbc"));
+    }
+}
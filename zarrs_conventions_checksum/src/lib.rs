@@ -0,0 +1,260 @@
+#![doc = include_str!("../README.md")]
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+pub use zarrs_conventions;
+use zarrs_conventions::{
+    define_convention,
+    validate::{Issue, Lint},
+};
+
+mod digest;
+pub use digest::{Algorithm, Hasher};
+mod verify;
+pub use verify::{Report, VerifyError};
+
+/// What a digest was computed over.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(tag = "scope", rename_all = "lowercase")]
+pub enum Scope {
+    /// A single digest of the stored bytes of every chunk of the array which is present,
+    /// concatenated in lexicographic order of their keys.
+    Array { value: String },
+    /// A digest of the stored bytes of each chunk, by key relative to the array, e.g. `c/0/0`.
+    ///
+    /// Digests can be given inline, or for large arrays in a manifest:
+    /// a JSON object of the same form, stored at the given path relative to the array.
+    Chunks {
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        digests: BTreeMap<String, String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        manifest: Option<String>,
+    },
+}
+
+define_convention! {
+    /// Digests of an array's stored bytes, for fixity checks.
+    #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+    pub struct Checksum {
+        /// Algorithm with which every digest was computed.
+        algorithm: Algorithm,
+        #[serde(flatten)]
+        scope: Scope,
+    }
+    schema_url = "https://raw.githubusercontent.com/clbarnes/zarrs_conventions/refs/tags/checksum-v1/zarrs_conventions_checksum/spec/schema.json",
+    spec_url = "https://github.com/clbarnes/zarrs_conventions/blob/checksum-v1/zarrs_conventions_checksum/spec/README.md",
+    name = "checksum",
+    description = "Digests of an array's stored bytes for fixity checks",
+    nested = "checksum",
+    register = false,
+}
+
+impl Checksum {
+    /// Build a checksum of chunks, or of the whole array with [Builder::array].
+    pub fn builder(algorithm: Algorithm) -> Builder {
+        Builder {
+            inner: Self {
+                algorithm,
+                scope: Scope::Chunks {
+                    digests: BTreeMap::default(),
+                    manifest: None,
+                },
+            },
+        }
+    }
+
+    pub fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    pub fn scope(&self) -> &Scope {
+        &self.scope
+    }
+
+    /// Every digest given in the attributes, by chunk key, or `None` for the whole array.
+    fn digests(&self) -> Box<dyn Iterator<Item = (Option<&str>, &str)> + '_> {
+        match &self.scope {
+            Scope::Array { value } => Box::new(std::iter::once((None, value.as_str()))),
+            Scope::Chunks { digests, .. } => {
+                Box::new(digests.iter().map(|(k, v)| (Some(k.as_str()), v.as_str())))
+            }
+        }
+    }
+
+    fn validate(&self) -> Result<(), BuildError> {
+        if let Scope::Chunks {
+            digests,
+            manifest: None,
+        } = &self.scope
+            && digests.is_empty()
+        {
+            return Err(BuildError::NoDigests);
+        }
+        match self
+            .digests()
+            .find(|(_, value)| !self.algorithm.is_digest(value))
+        {
+            Some((key, value)) => Err(BuildError::InvalidDigest {
+                key: key.map(str::to_string),
+                value: value.to_string(),
+                algorithm: self.algorithm,
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Lint for Checksum {
+    fn lint(&self) -> Vec<Issue> {
+        let mut issues: Vec<_> = self
+            .digests()
+            .filter(|(_, value)| !self.algorithm.is_digest(value))
+            .map(|(key, value)| {
+                let e = BuildError::InvalidDigest {
+                    key: key.map(str::to_string),
+                    value: value.to_string(),
+                    algorithm: self.algorithm,
+                };
+                Issue::error("invalid-digest", e.to_string())
+            })
+            .collect();
+        if let Err(e @ BuildError::NoDigests) = self.validate() {
+            issues.push(Issue::error("no-digests", e.to_string()));
+        }
+        issues
+    }
+}
+
+/// Why a checksum could not be built.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum BuildError {
+    #[error("chunk digests must be given inline or in a manifest")]
+    NoDigests,
+    #[error("{} digest '{value}' for {} is not {} hexadecimal digits", algorithm.name(), key.as_deref().map_or("the array".into(), |k| format!("chunk '{k}'")), 2 * algorithm.digest_len())]
+    InvalidDigest {
+        key: Option<String>,
+        value: String,
+        algorithm: Algorithm,
+    },
+}
+
+/// Builder for [Checksum], created by [Checksum::builder].
+#[derive(Debug, Clone)]
+pub struct Builder {
+    inner: Checksum,
+}
+
+impl Builder {
+    /// Give a single digest for the whole array, replacing any chunk digests.
+    pub fn array(mut self, value: impl Into<String>) -> Self {
+        self.inner.scope = Scope::Array {
+            value: value.into(),
+        };
+        self
+    }
+
+    /// Give the digest of a chunk inline, replacing a digest for the whole array.
+    pub fn chunk(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        let (key, value) = (key.into(), value.into());
+        match &mut self.inner.scope {
+            Scope::Chunks { digests, .. } => {
+                digests.insert(key, value);
+            }
+            scope @ Scope::Array { .. } => {
+                *scope = Scope::Chunks {
+                    digests: BTreeMap::from([(key, value)]),
+                    manifest: None,
+                }
+            }
+        }
+        self
+    }
+
+    /// Refer to a manifest of chunk digests, replacing a digest for the whole array.
+    pub fn manifest(mut self, path: impl Into<String>) -> Self {
+        let path = Some(path.into());
+        match &mut self.inner.scope {
+            Scope::Chunks { manifest, .. } => *manifest = path,
+            scope @ Scope::Array { .. } => {
+                *scope = Scope::Chunks {
+                    digests: BTreeMap::default(),
+                    manifest: path,
+                }
+            }
+        }
+        self
+    }
+
+    /// Build the checksum, checking that every digest is valid for the algorithm.
+    pub fn build(self) -> Result<Checksum, BuildError> {
+        self.inner.validate()?;
+        Ok(self.inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use zarrs_conventions::validate::Lint;
+
+    use crate::{Algorithm, BuildError, Checksum};
+
+    #[test]
+    fn build() {
+        let checksum = Checksum::builder(Algorithm::Crc32)
+            .chunk("c/0", "b63cfbcd")
+            .manifest("checksums.json")
+            .build()
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(&checksum).unwrap(),
+            json!({
+                "algorithm": "crc32",
+                "scope": "chunks",
+                "digests": {"c/0": "b63cfbcd"},
+                "manifest": "checksums.json",
+            })
+        );
+        let checksum = Checksum::builder(Algorithm::Crc32)
+            .array("b63cfbcd")
+            .build()
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(&checksum).unwrap(),
+            json!({"algorithm": "crc32", "scope": "array", "value": "b63cfbcd"})
+        );
+
+        assert_eq!(
+            Checksum::builder(Algorithm::Sha256).build(),
+            Err(BuildError::NoDigests)
+        );
+        let err = Checksum::builder(Algorithm::Sha256)
+            .chunk("c/0", "b63cfbcd")
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "sha256 digest 'b63cfbcd' for chunk 'c/0' is not 64 hexadecimal digits"
+        );
+    }
+
+    #[test]
+    fn lint() {
+        let checksum: Checksum = serde_json::from_value(json!({
+            "algorithm": "crc32",
+            "scope": "chunks",
+            "digests": {"c/0": "b63cfbcd", "c/1": "nothex!!", "c/2": "b63cfb"},
+        }))
+        .unwrap();
+        let codes: Vec<_> = checksum.lint().into_iter().map(|i| i.code).collect();
+        assert_eq!(codes, ["invalid-digest", "invalid-digest"]);
+
+        let checksum: Checksum =
+            serde_json::from_value(json!({"algorithm": "crc32", "scope": "chunks"})).unwrap();
+        let codes: Vec<_> = checksum.lint().into_iter().map(|i| i.code).collect();
+        assert_eq!(codes, ["no-digests"]);
+    }
+}
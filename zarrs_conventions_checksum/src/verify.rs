@@ -0,0 +1,276 @@
+//! Computing digests of stored chunks, and verifying them against a checksum.
+//!
+//! Chunks are read through a callback taking a key relative to the array, e.g. `c/0/0`,
+//! and returning the stored bytes, or `None` if the chunk is not present.
+use std::{collections::BTreeMap, io};
+
+use crate::{Algorithm, Checksum, Scope};
+
+/// Why a checksum could not be verified.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum VerifyError {
+    #[error("could not read '{key}': {source}")]
+    Read { key: String, source: io::Error },
+    #[error("manifest '{0}' is not present")]
+    MissingManifest(String),
+    #[error("manifest '{path}' is not a JSON object of digests: {source}")]
+    InvalidManifest {
+        path: String,
+        source: serde_json::Error,
+    },
+}
+
+fn read_key(
+    read: &mut impl FnMut(&str) -> io::Result<Option<Vec<u8>>>,
+    key: &str,
+) -> Result<Option<Vec<u8>>, VerifyError> {
+    read(key).map_err(|source| VerifyError::Read {
+        key: key.to_string(),
+        source,
+    })
+}
+
+fn sorted<S: AsRef<str>>(keys: impl IntoIterator<Item = S>) -> Vec<String> {
+    let mut keys: Vec<_> = keys.into_iter().map(|k| k.as_ref().to_string()).collect();
+    keys.sort();
+    keys.dedup();
+    keys
+}
+
+/// The outcome of verifying stored chunks against a checksum.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Report {
+    /// Keys of chunks whose digest differs from the one recorded,
+    /// or the empty string if the digest of the whole array differs.
+    pub mismatched: Vec<String>,
+    /// Keys of chunks with a recorded digest which are not present.
+    pub missing: Vec<String>,
+    /// Keys of present chunks with no recorded digest.
+    pub unrecorded: Vec<String>,
+}
+
+impl Report {
+    /// Whether every chunk matches its recorded digest.
+    pub fn is_ok(&self) -> bool {
+        self.mismatched.is_empty() && self.missing.is_empty() && self.unrecorded.is_empty()
+    }
+}
+
+impl Checksum {
+    /// Compute a single digest of the whole array from the chunks with the given keys.
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use zarrs_conventions_checksum::{Algorithm, Checksum};
+    ///
+    /// let store = HashMap::from([("c/0", vec![1u8, 2]), ("c/1", vec![3, 4])]);
+    /// let checksum = Checksum::compute_array(Algorithm::Crc32, ["c/1", "c/0", "c/2"], |key| {
+    ///     Ok(store.get(key).cloned())
+    /// })
+    /// .unwrap();
+    /// assert!(checksum.verify(store.keys(), |key| Ok(store.get(key).cloned())).unwrap().is_ok());
+    /// ```
+    pub fn compute_array<S: AsRef<str>>(
+        algorithm: Algorithm,
+        keys: impl IntoIterator<Item = S>,
+        mut read: impl FnMut(&str) -> io::Result<Option<Vec<u8>>>,
+    ) -> Result<Self, VerifyError> {
+        let mut hasher = algorithm.hasher();
+        for key in sorted(keys) {
+            if let Some(bytes) = read_key(&mut read, &key)? {
+                hasher.update(&bytes);
+            }
+        }
+        Ok(Self {
+            algorithm,
+            scope: Scope::Array {
+                value: hasher.finish(),
+            },
+        })
+    }
+
+    /// Compute the digest of each of the chunks with the given keys which is present,
+    /// to be given inline.
+    ///
+    /// To write the digests to a manifest instead, serialize [Checksum::chunk_digests]
+    /// and refer to it with [Checksum::with_manifest].
+    pub fn compute_chunks<S: AsRef<str>>(
+        algorithm: Algorithm,
+        keys: impl IntoIterator<Item = S>,
+        mut read: impl FnMut(&str) -> io::Result<Option<Vec<u8>>>,
+    ) -> Result<Self, VerifyError> {
+        let mut digests = BTreeMap::default();
+        for key in sorted(keys) {
+            if let Some(bytes) = read_key(&mut read, &key)? {
+                digests.insert(key, algorithm.digest(&bytes));
+            }
+        }
+        Ok(Self {
+            algorithm,
+            scope: Scope::Chunks {
+                digests,
+                manifest: None,
+            },
+        })
+    }
+
+    /// Chunk digests given inline.
+    pub fn chunk_digests(&self) -> Option<&BTreeMap<String, String>> {
+        match &self.scope {
+            Scope::Chunks { digests, .. } => Some(digests),
+            Scope::Array { .. } => None,
+        }
+    }
+
+    /// Move chunk digests given inline to a manifest at the given path, returning them to be written there.
+    ///
+    /// A checksum of the whole array is returned unchanged, with no digests.
+    pub fn with_manifest(mut self, path: impl Into<String>) -> (Self, BTreeMap<String, String>) {
+        let mut moved = BTreeMap::default();
+        if let Scope::Chunks { digests, manifest } = &mut self.scope {
+            moved = std::mem::take(digests);
+            *manifest = Some(path.into());
+        }
+        (self, moved)
+    }
+
+    /// Recorded chunk digests, inline and from any manifest, which is read with the same callback.
+    fn recorded_chunks(
+        &self,
+        read: &mut impl FnMut(&str) -> io::Result<Option<Vec<u8>>>,
+    ) -> Result<Option<BTreeMap<String, String>>, VerifyError> {
+        let Scope::Chunks { digests, manifest } = &self.scope else {
+            return Ok(None);
+        };
+        let mut recorded = BTreeMap::default();
+        if let Some(path) = manifest {
+            let bytes =
+                read_key(read, path)?.ok_or_else(|| VerifyError::MissingManifest(path.clone()))?;
+            recorded =
+                serde_json::from_slice(&bytes).map_err(|source| VerifyError::InvalidManifest {
+                    path: path.clone(),
+                    source,
+                })?;
+        }
+        recorded.extend(digests.iter().map(|(k, v)| (k.clone(), v.clone())));
+        Ok(Some(recorded))
+    }
+
+    /// Verify the stored chunks with the given keys against the recorded digests.
+    ///
+    /// Recorded chunks are read whether or not their keys are given;
+    /// the keys are needed to find chunks with no recorded digest,
+    /// and to compute the digest of the whole array.
+    pub fn verify<S: AsRef<str>>(
+        &self,
+        keys: impl IntoIterator<Item = S>,
+        mut read: impl FnMut(&str) -> io::Result<Option<Vec<u8>>>,
+    ) -> Result<Report, VerifyError> {
+        let keys = sorted(keys);
+        let mut report = Report::default();
+        let Some(recorded) = self.recorded_chunks(&mut read)? else {
+            let computed = Self::compute_array(self.algorithm, &keys, read)?;
+            if !computed.scope.eq_ignore_case(&self.scope) {
+                report.mismatched.push(String::default());
+            }
+            return Ok(report);
+        };
+        for (key, expected) in &recorded {
+            match read_key(&mut read, key)? {
+                None => report.missing.push(key.clone()),
+                Some(bytes) => {
+                    if !self.algorithm.digest(&bytes).eq_ignore_ascii_case(expected) {
+                        report.mismatched.push(key.clone());
+                    }
+                }
+            }
+        }
+        for key in keys.into_iter().filter(|k| !recorded.contains_key(k)) {
+            if read_key(&mut read, &key)?.is_some() {
+                report.unrecorded.push(key);
+            }
+        }
+        Ok(report)
+    }
+}
+
+impl Scope {
+    fn eq_ignore_case(&self, other: &Scope) -> bool {
+        match (self, other) {
+            (Scope::Array { value: a }, Scope::Array { value: b }) => a.eq_ignore_ascii_case(b),
+            _ => self == other,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, io};
+
+    use crate::{Algorithm, Checksum, Scope, VerifyError};
+
+    fn store() -> HashMap<String, Vec<u8>> {
+        HashMap::from([("c/0".into(), vec![1, 2]), ("c/1".into(), vec![3, 4])])
+    }
+
+    fn reader(store: &HashMap<String, Vec<u8>>) -> impl FnMut(&str) -> io::Result<Option<Vec<u8>>> {
+        |key| Ok(store.get(key).cloned())
+    }
+
+    #[test]
+    fn array() {
+        let mut store = store();
+        let keys = ["c/1", "c/0", "c/2"];
+        let checksum = Checksum::compute_array(Algorithm::Sha256, keys, reader(&store)).unwrap();
+        assert_eq!(
+            checksum.scope(),
+            &Scope::Array {
+                value: "9f64a747e1b97f131fabb6b447296c9b6f0201e79fb3c5356e6c77e89b6a806a".into()
+            }
+        );
+        assert!(checksum.verify(keys, reader(&store)).unwrap().is_ok());
+
+        store.insert("c/1".into(), vec![3, 5]);
+        let report = checksum.verify(keys, reader(&store)).unwrap();
+        assert_eq!(report.mismatched, [""]);
+    }
+
+    #[test]
+    fn chunks() {
+        let mut store = store();
+        let keys = ["c/0", "c/1", "c/2"];
+        let checksum = Checksum::compute_chunks(Algorithm::Sha256, keys, reader(&store)).unwrap();
+        let digests = checksum.chunk_digests().unwrap();
+        assert_eq!(
+            digests["c/0"],
+            "a12871fee210fb8619291eaea194581cbd2531e4b23759d225f6806923f63222"
+        );
+        assert_eq!(
+            digests["c/1"],
+            "0ce3940bebf2b22a5d2108ecf0c368a0541c7e3c45703f8540921b4eafc82947"
+        );
+        assert!(!digests.contains_key("c/2"));
+
+        let (checksum, manifest) = checksum.with_manifest("checksums.json");
+        assert!(checksum.chunk_digests().unwrap().is_empty());
+        assert!(matches!(
+            checksum.verify(keys, reader(&store)),
+            Err(VerifyError::MissingManifest(_))
+        ));
+        store.insert(
+            "checksums.json".into(),
+            serde_json::to_vec(&manifest).unwrap(),
+        );
+        assert!(checksum.verify(keys, reader(&store)).unwrap().is_ok());
+
+        store.remove("c/0");
+        store.insert("c/1".into(), vec![3, 5]);
+        store.insert("c/2".into(), vec![6]);
+        let report = checksum.verify(keys, reader(&store)).unwrap();
+        assert_eq!(report.missing, ["c/0"]);
+        assert_eq!(report.mismatched, ["c/1"]);
+        assert_eq!(report.unrecorded, ["c/2"]);
+        assert!(!report.is_ok());
+    }
+}
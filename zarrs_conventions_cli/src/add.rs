@@ -0,0 +1,252 @@
+//! Annotate existing nodes with conventional metadata.
+use std::{io::Write, path::PathBuf, process::ExitCode};
+
+use zarrs_conventions::{AttributesBuilder, NestedRepr};
+
+use crate::{
+    error::{Error, Result},
+    output::{Format, Modified, ModifiedNode, write_json},
+    store::Node,
+};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    #[command(subcommand)]
+    pub convention: Convention,
+}
+
+impl Args {
+    /// Options common to all conventions.
+    pub fn target_mut(&mut self) -> &mut Target {
+        match self.convention {
+            #[cfg(feature = "license")]
+            Convention::License { ref mut target, .. } => target,
+            #[cfg(feature = "uom")]
+            Convention::Uom { ref mut target, .. } => target,
+        }
+    }
+}
+
+/// Options common to all conventions.
+#[derive(Debug, clap::Args)]
+pub struct Target {
+    /// Directory of the Zarr node to annotate (containing `zarr.json`).
+    pub path: PathBuf,
+    /// Replace the convention's payload if the node already has one.
+    #[arg(long)]
+    pub force: bool,
+    /// Print the resulting attributes rather than writing them.
+    #[arg(long)]
+    pub dry_run: bool,
+    #[arg(long, value_enum, default_value_t)]
+    pub format: Format,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum Convention {
+    /// Add the license convention.
+    #[cfg(feature = "license")]
+    License {
+        #[command(flatten)]
+        target: Target,
+        /// SPDX license identifier, e.g. `MIT`.
+        #[arg(long)]
+        spdx: Option<String>,
+        /// URL of the license text.
+        #[arg(long)]
+        url: Option<zarrs_conventions::iref::UriBuf>,
+        /// Full text of the license.
+        #[arg(long)]
+        text: Option<String>,
+        /// Name of a license file in the same directory as the node's metadata.
+        #[arg(long)]
+        file: Option<String>,
+        /// Path to a license file in the hierarchy.
+        #[arg(long = "license-path")]
+        license_path: Option<String>,
+    },
+    /// Add the uom convention.
+    #[cfg(feature = "uom")]
+    Uom {
+        #[command(flatten)]
+        target: Target,
+        /// Case-sensitive UCUM unit string, e.g. `um`.
+        #[arg(long)]
+        unit: Option<String>,
+        /// Version of the UCUM specification.
+        #[arg(long)]
+        ucum_version: Option<String>,
+        /// Description of the quantity.
+        #[arg(long)]
+        description: Option<String>,
+    },
+}
+
+/// Merge a convention's payload and declaration into the node's attributes.
+// unused if no conventions which can be added are compiled in
+#[allow(dead_code)]
+fn add<T: NestedRepr>(target: Target, value: &T, out: &mut impl Write) -> Result<ExitCode> {
+    let mut node = Node::read(&target.path, "/".to_string())?;
+    let attributes = node.attributes();
+    if attributes.contains_key(T::KEY) && !target.force {
+        return Err(Error::Other(format!(
+            "{} already has '{}'; use --force to replace it",
+            target.path.display(),
+            T::KEY
+        )));
+    }
+    let json_err = |source| Error::Json {
+        path: node.file.clone(),
+        source,
+    };
+    let mut builder = AttributesBuilder::from_attributes(attributes);
+    builder.add_nested(value).map_err(json_err)?;
+    let serde_json::Value::Object(attributes) = builder.build().map_err(json_err)? else {
+        unreachable!("attributes are an object");
+    };
+    node.set_attributes(attributes.clone());
+
+    if !target.dry_run {
+        node.save()?;
+    }
+    match target.format {
+        Format::Json => {
+            let mut modified = Modified::new(target.dry_run);
+            modified.nodes.push(ModifiedNode {
+                path: node.path,
+                removed: Vec::default(),
+                attributes,
+            });
+            write_json(out, &modified)?;
+        }
+        Format::Text if target.dry_run => write_json(out, &attributes)?,
+        Format::Text => (),
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+#[allow(unused_variables)]
+pub fn run(args: Args, out: &mut impl Write) -> Result<ExitCode> {
+    match args.convention {
+        #[cfg(feature = "license")]
+        Convention::License {
+            target,
+            spdx,
+            url,
+            text,
+            file,
+            license_path,
+        } => {
+            let mut builder = zarrs_conventions_license::License::builder();
+            if let Some(spdx) = spdx {
+                builder = builder.spdx(spdx);
+            }
+            if let Some(url) = url {
+                builder = builder.url(url);
+            }
+            if let Some(text) = text {
+                builder = builder.text(text);
+            }
+            if let Some(file) = file {
+                builder = builder.file(file);
+            }
+            if let Some(path) = license_path {
+                builder = builder.path(path);
+            }
+            let license = builder.build().map_err(Error::Other)?;
+            add(target, &license, out)
+        }
+        #[cfg(feature = "uom")]
+        Convention::Uom {
+            target,
+            unit,
+            ucum_version,
+            description,
+        } => {
+            let mut builder = zarrs_conventions_uom::UnitOfMeasurement::builder();
+            if let Some(unit) = unit {
+                builder = builder.unit(unit);
+            }
+            if let Some(version) = ucum_version {
+                builder = builder.version(version);
+            }
+            if let Some(description) = description {
+                builder = builder.description(description);
+            }
+            let unit = builder
+                .build_validated()
+                .map_err(|e| Error::Other(e.to_string()))?;
+            add(target, &unit, out)
+        }
+    }
+}
+
+#[cfg(all(test, any(feature = "license", feature = "uom")))]
+mod tests {
+    use serde_json::json;
+
+    use super::Target;
+    use crate::store::{Node, tests::write_node};
+
+    #[cfg(feature = "license")]
+    #[test]
+    fn add_license_merges() {
+        use super::add;
+
+        let tmp = tempfile::tempdir().unwrap();
+        write_node(
+            tmp.path(),
+            "",
+            "group",
+            json!({
+                "zarr_conventions": [{"uuid": "f0000000-0000-0000-0000-000000000000"}],
+                "keep": true
+            }),
+        );
+        let target = || Target {
+            path: tmp.path().to_path_buf(),
+            force: false,
+            dry_run: false,
+            format: Default::default(),
+        };
+        let license = zarrs_conventions_license::License::new_spdx("MIT");
+        add(target(), &license, &mut Vec::default()).unwrap();
+
+        let attrs = Node::read(tmp.path(), "/".into()).unwrap().attributes();
+        assert_eq!(attrs["keep"], true);
+        assert_eq!(attrs["license"], json!({"spdx": "MIT"}));
+        assert_eq!(attrs["zarr_conventions"].as_array().unwrap().len(), 2);
+
+        assert!(add(target(), &license, &mut Vec::default()).is_err());
+    }
+
+    #[cfg(feature = "uom")]
+    #[test]
+    fn add_invalid_uom_rejected() {
+        use super::{Args, Convention, run};
+
+        let tmp = tempfile::tempdir().unwrap();
+        write_node(tmp.path(), "", "array", json!({"keep": true}));
+        let before = std::fs::read(tmp.path().join("zarr.json")).unwrap();
+
+        let args = Args {
+            convention: Convention::Uom {
+                target: Target {
+                    path: tmp.path().to_path_buf(),
+                    force: false,
+                    dry_run: false,
+                    format: Default::default(),
+                },
+                unit: Some("UM".into()),
+                ucum_version: None,
+                description: None,
+            },
+        };
+        assert!(run(args, &mut Vec::default()).is_err());
+
+        let after = std::fs::read(tmp.path().join("zarr.json")).unwrap();
+        assert_eq!(before, after);
+        let attrs = Node::read(tmp.path(), "/".into()).unwrap().attributes();
+        assert!(!attrs.contains_key("uom"));
+    }
+}
@@ -0,0 +1,186 @@
+//! Convert a convention's payloads between nested and prefixed representations.
+use std::{io::Write, path::PathBuf, process::ExitCode};
+
+use zarrs_conventions::repr::{Repr, convert};
+
+use crate::{
+    error::{Error, Result},
+    known,
+    output::{Format, Modified, ModifiedNode, write_json},
+    selector::Selector,
+    store,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Target {
+    Nested,
+    Prefixed,
+}
+
+impl From<Target> for Repr {
+    fn from(value: Target) -> Self {
+        match value {
+            Target::Nested => Repr::Nested,
+            Target::Prefixed => Repr::Prefixed,
+        }
+    }
+}
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Directory of a Zarr node (containing `zarr.json`).
+    pub path: PathBuf,
+    /// Representation to convert to.
+    #[arg(long, value_enum)]
+    pub to: Target,
+    /// Name, UUID, schema URL, or specification URL of the convention to convert.
+    #[arg(long)]
+    pub convention: Selector,
+    /// Key of the convention's nested payload, if not compiled into this tool.
+    #[arg(long)]
+    pub key: Option<String>,
+    /// Prefix of the convention's prefixed payload, if not compiled into this tool.
+    #[arg(long)]
+    pub prefix: Option<String>,
+    /// Also convert all descendants of the node.
+    #[arg(short, long)]
+    pub recursive: bool,
+    /// Report what would be converted without writing anything.
+    #[arg(long)]
+    pub dry_run: bool,
+    #[arg(long, value_enum, default_value_t)]
+    pub format: Format,
+}
+
+pub fn run(args: Args, out: &mut impl Write) -> Result<ExitCode> {
+    let known = args
+        .convention
+        .definition
+        .and_then(|def| known::find(def.uuid));
+    let key = args.key.as_deref().or(known.and_then(|k| k.key));
+    let prefix = args.prefix.as_deref().or(known.and_then(|k| k.prefix));
+    let (Some(key), Some(prefix)) = (key, prefix) else {
+        return Err(Error::Other(
+            "the convention's key and prefix are not both known; pass --key and --prefix"
+                .to_string(),
+        ));
+    };
+    let verb = if args.dry_run {
+        "would convert"
+    } else {
+        "converted"
+    };
+
+    let mut modified = Modified::new(args.dry_run);
+    for mut node in store::open(&args.path, args.recursive)? {
+        let declared = node.declared()?;
+        if !declared.iter().any(|c| args.convention.matches(c)) {
+            continue;
+        }
+        let mut attributes = node.attributes();
+        let changed = convert(&mut attributes, key, prefix, args.to.into())
+            .map_err(|e| Error::Other(format!("{}: {e}", node.path)))?;
+        if !changed {
+            continue;
+        }
+        if args.format == Format::Text {
+            writeln!(out, "{}: {verb}", node.path)?;
+        }
+        if !args.dry_run {
+            node.set_attributes(attributes.clone());
+            node.save()?;
+        }
+        modified.nodes.push(ModifiedNode {
+            path: node.path,
+            removed: Vec::default(),
+            attributes,
+        });
+    }
+    if args.format == Format::Json {
+        write_json(out, &modified)?;
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{Args, Target, run};
+    use crate::store::{Node, tests::write_node};
+
+    #[test]
+    fn convert_repr_declared_only() {
+        let tmp = tempfile::tempdir().unwrap();
+        write_node(
+            tmp.path(),
+            "",
+            "group",
+            json!({
+                "zarr_conventions": [{"spec_url": "https://example.com/specs/proj"}],
+                "proj:code": "EPSG:4326"
+            }),
+        );
+        write_node(
+            tmp.path(),
+            "undeclared",
+            "group",
+            json!({"proj:code": "EPSG:4326"}),
+        );
+        let args = Args {
+            path: tmp.path().to_path_buf(),
+            to: Target::Nested,
+            convention: "https://example.com/specs/proj".parse().unwrap(),
+            key: Some("proj".into()),
+            prefix: Some("proj:".into()),
+            recursive: true,
+            dry_run: false,
+            format: Default::default(),
+        };
+        let mut out = Vec::default();
+        run(args, &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "/: converted\n");
+        let root = Node::read(tmp.path(), "/".into()).unwrap().attributes();
+        assert_eq!(root["proj"], json!({"code": "EPSG:4326"}));
+        let other = Node::read(&tmp.path().join("undeclared"), "/undeclared".into())
+            .unwrap()
+            .attributes();
+        assert_eq!(other["proj:code"], "EPSG:4326");
+    }
+
+    #[cfg(feature = "proj")]
+    #[test]
+    fn convert_repr_proj_by_name() {
+        use clap::Parser;
+
+        use crate::{Cli, Command};
+
+        let tmp = tempfile::tempdir().unwrap();
+        write_node(
+            tmp.path(),
+            "",
+            "group",
+            json!({
+                "zarr_conventions": [{"uuid": "f17cb550-5864-4468-aeb7-f3180cfb622f"}],
+                "proj": {"code": "EPSG:4326"}
+            }),
+        );
+        let path = tmp.path().to_str().unwrap();
+        let argv = [
+            "zarrs-conventions",
+            "convert-repr",
+            "--to",
+            "prefixed",
+            "--convention",
+            "proj",
+            path,
+        ];
+        let Command::ConvertRepr(args) = Cli::try_parse_from(argv).unwrap().command else {
+            panic!("expected convert-repr subcommand");
+        };
+        run(args, &mut Vec::default()).unwrap();
+        let root = Node::read(tmp.path(), "/".into()).unwrap().attributes();
+        assert_eq!(root["proj:code"], "EPSG:4326");
+        assert!(!root.contains_key("proj"));
+    }
+}
@@ -0,0 +1,158 @@
+//! Compare the conventional metadata of two nodes or hierarchies.
+use std::{collections::BTreeMap, io::Write, path::PathBuf, process::ExitCode};
+
+use serde::Serialize;
+use zarrs_conventions::{
+    diff::{AttributesDiff, Change, diff_attributes},
+    validate::label,
+};
+
+use crate::{
+    error::{Error, Result},
+    output::{Format, REPORT_VERSION, write_json},
+    store,
+};
+
+/// Exit status is 0 if there are no differences, 1 if there are,
+/// and 2 if the comparison could not be completed.
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Directory of the old Zarr node (containing `zarr.json`).
+    pub old: PathBuf,
+    /// Directory of the new Zarr node (containing `zarr.json`).
+    pub new: PathBuf,
+    /// Also compare all descendants, matched by their path relative to the given nodes.
+    #[arg(short, long)]
+    pub recursive: bool,
+    #[arg(long, value_enum, default_value_t)]
+    pub format: Format,
+}
+
+/// Differences between two hierarchies.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct HierarchyDiff {
+    pub version: u32,
+    /// Paths of nodes only in the old hierarchy.
+    pub removed: Vec<String>,
+    /// Paths of nodes only in the new hierarchy.
+    pub added: Vec<String>,
+    /// Differences in nodes present in both.
+    pub changed: BTreeMap<String, AttributesDiff>,
+}
+
+impl HierarchyDiff {
+    pub fn is_empty(&self) -> bool {
+        self.removed.is_empty() && self.added.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Compare the nodes at (and, if `recursive`, below) two directories.
+pub fn diff(args: &Args) -> Result<HierarchyDiff> {
+    let by_path = |nodes: Vec<store::Node>| -> BTreeMap<String, store::Node> {
+        nodes.into_iter().map(|n| (n.path.clone(), n)).collect()
+    };
+    let old = by_path(store::open(&args.old, args.recursive)?);
+    let new = by_path(store::open(&args.new, args.recursive)?);
+
+    let mut out = HierarchyDiff {
+        version: REPORT_VERSION,
+        ..Default::default()
+    };
+    for (path, o) in &old {
+        let Some(n) = new.get(path) else {
+            out.removed.push(path.clone());
+            continue;
+        };
+        let d =
+            diff_attributes(&o.attributes(), &n.attributes()).map_err(|source| Error::Json {
+                path: o.file.clone(),
+                source,
+            })?;
+        if !d.is_empty() {
+            out.changed.insert(path.clone(), d);
+        }
+    }
+    out.added = new
+        .keys()
+        .filter(|p| !old.contains_key(*p))
+        .cloned()
+        .collect();
+    Ok(out)
+}
+
+fn write_text(out: &mut impl Write, diff: &HierarchyDiff) -> Result<()> {
+    for path in &diff.removed {
+        writeln!(out, "- {path} (node removed)")?;
+    }
+    for path in &diff.added {
+        writeln!(out, "+ {path} (node added)")?;
+    }
+    for (path, d) in &diff.changed {
+        writeln!(out, "~ {path}")?;
+        for conv in &d.undeclared {
+            writeln!(out, "    - declaration {}", label(conv))?;
+        }
+        for conv in &d.declared {
+            writeln!(out, "    + declaration {}", label(conv))?;
+        }
+        for change in &d.changes {
+            match change {
+                Change::Added { path, new } => writeln!(out, "    + {path}: {new}")?,
+                Change::Removed { path, old } => writeln!(out, "    - {path}: {old}")?,
+                Change::Changed { path, old, new } => {
+                    writeln!(out, "    ~ {path}: {old} -> {new}")?
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn run(args: Args, out: &mut impl Write) -> Result<ExitCode> {
+    let diff = diff(&args)?;
+    match args.format {
+        Format::Json => write_json(out, &diff)?,
+        Format::Text => write_text(out, &diff)?,
+    }
+    Ok(if diff.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{Args, diff};
+    use crate::{output::Format, store::tests::write_node};
+
+    #[test]
+    fn diff_hierarchies() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (old, new) = (tmp.path().join("old"), tmp.path().join("new"));
+        write_node(&old, "", "group", json!({"license": {"spdx": "MIT"}}));
+        write_node(&old, "gone", "array", json!({}));
+        write_node(
+            &new,
+            "",
+            "group",
+            json!({"license": {"spdx": "BSD-3-Clause"}}),
+        );
+        write_node(&new, "same", "array", json!({}));
+        write_node(&old, "same", "array", json!({}));
+
+        let args = Args {
+            old,
+            new,
+            recursive: true,
+            format: Format::Json,
+        };
+        let d = diff(&args).unwrap();
+        assert_eq!(d.removed, ["/gone"]);
+        assert!(d.added.is_empty());
+        assert_eq!(d.changed.len(), 1);
+        assert_eq!(d.changed["/"].changes[0].path(), "/license/spdx");
+    }
+}
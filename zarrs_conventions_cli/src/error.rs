@@ -0,0 +1,31 @@
+use std::path::PathBuf;
+
+/// Errors encountered by CLI subcommands.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("could not access {}: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("invalid JSON in {}: {source}", path.display())]
+    Json {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+    #[cfg(not(feature = "object_store"))]
+    #[error("{0} is a URL, but hierarchies in object stores need the `object_store` feature")]
+    Url(String),
+    #[cfg(feature = "object_store")]
+    #[error("could not access {url}: {source}")]
+    ObjectStore {
+        url: String,
+        source: object_store::Error,
+    },
+    #[error("could not write output: {0}")]
+    Output(#[from] std::io::Error),
+    #[error("{0}")]
+    Other(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
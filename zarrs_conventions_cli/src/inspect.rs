@@ -0,0 +1,179 @@
+//! Print the conventions declared by nodes and their parsed payloads.
+use std::{io::Write, path::PathBuf, process::ExitCode};
+
+use serde::Serialize;
+use serde_json::Value;
+use zarrs_conventions::{
+    AttributesParser, Convention, DEFAULT_ZARR_CONVENTION_REGISTRY, validate::label,
+};
+
+use crate::{
+    error::{Error, Result},
+    known,
+    output::{Format, REPORT_VERSION, write_json},
+    store,
+};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Directory of a Zarr node (containing `zarr.json`).
+    pub path: PathBuf,
+    /// Also inspect all descendants of the node.
+    #[arg(short, long)]
+    pub recursive: bool,
+    #[arg(long, value_enum, default_value_t)]
+    pub format: Format,
+}
+
+/// Conventions declared by nodes, and their parsed payloads.
+#[derive(Debug, Clone, Serialize)]
+pub struct Inspection {
+    pub version: u32,
+    pub nodes: Vec<InspectedNode>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct InspectedNode {
+    pub path: String,
+    pub node_type: String,
+    pub conventions: Vec<Declared>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Declared {
+    pub declaration: Convention,
+    /// Whether the convention is registered.
+    pub registered: bool,
+    /// Whether this tool can parse the convention's payload.
+    pub parseable: bool,
+    /// Validated payload, if present and parseable.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payload: Option<Value>,
+    /// Why the payload is invalid, if it is.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+pub fn inspect(args: &Args) -> Result<Inspection> {
+    let mut nodes = Vec::default();
+    for node in store::open(&args.path, args.recursive)? {
+        let parser: AttributesParser = serde_json::from_value(Value::Object(node.attributes()))
+            .map_err(|source| Error::Json {
+                path: node.file.clone(),
+                source,
+            })?;
+        let conventions = node
+            .declared()?
+            .into_iter()
+            .map(|declaration| {
+                let known = known::find(declaration.id_ref());
+                let (payload, error) = match known.map(|k| k.parse(&parser)) {
+                    Some(Ok(payload)) => (payload, None),
+                    Some(Err(e)) => (None, Some(e.to_string())),
+                    None => (None, None),
+                };
+                Declared {
+                    registered: DEFAULT_ZARR_CONVENTION_REGISTRY.contains(declaration.id_ref()),
+                    parseable: known.is_some(),
+                    declaration,
+                    payload,
+                    error,
+                }
+            })
+            .collect();
+        nodes.push(InspectedNode {
+            node_type: node.node_type().to_string(),
+            path: node.path,
+            conventions,
+        });
+    }
+    Ok(Inspection {
+        version: REPORT_VERSION,
+        nodes,
+    })
+}
+
+pub fn run(args: Args, out: &mut impl Write) -> Result<ExitCode> {
+    let inspection = inspect(&args)?;
+    if args.format == Format::Json {
+        write_json(out, &inspection)?;
+        return Ok(ExitCode::SUCCESS);
+    }
+    for node in &inspection.nodes {
+        writeln!(out, "{} ({})", node.path, node.node_type)?;
+        if node.conventions.is_empty() {
+            writeln!(out, "  no conventions declared")?;
+            continue;
+        }
+        for conv in &node.conventions {
+            let status = if conv.registered { "" } else { " (unknown)" };
+            writeln!(out, "  {}{status}", label(&conv.declaration))?;
+            if !conv.parseable {
+                continue;
+            }
+            match (&conv.payload, &conv.error) {
+                (_, Some(e)) => writeln!(out, "    invalid payload: {e}")?,
+                (Some(payload), None) => {
+                    let pretty =
+                        serde_json::to_string_pretty(payload).expect("JSON should serialize");
+                    for line in pretty.lines() {
+                        writeln!(out, "    {line}")?;
+                    }
+                }
+                (None, None) => writeln!(out, "    no payload")?,
+            }
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(all(test, feature = "license"))]
+mod tests {
+    use serde_json::json;
+
+    use super::{Args, inspect, run};
+    use crate::{output::Format, store::tests::write_node};
+
+    #[test]
+    fn inspect_license() {
+        let tmp = tempfile::tempdir().unwrap();
+        write_node(
+            tmp.path(),
+            "",
+            "group",
+            json!({
+                "zarr_conventions": [
+                    {"uuid": "b77365e5-2b0c-4141-b917-c03b7c68e935"},
+                    {"uuid": "00000000-0000-0000-0000-000000000001", "name": "mystery"}
+                ],
+                "license": {"spdx": "MIT"}
+            }),
+        );
+        write_node(tmp.path(), "arr", "array", json!({}));
+        let mut out = Vec::default();
+        run(
+            Args {
+                path: tmp.path().to_path_buf(),
+                recursive: true,
+                format: Default::default(),
+            },
+            &mut out,
+        )
+        .unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("license [b77365e5-2b0c-4141-b917-c03b7c68e935]\n"));
+        assert!(out.contains("\"spdx\": \"MIT\""));
+        assert!(out.contains("mystery [00000000-0000-0000-0000-000000000001] (unknown)"));
+        assert!(out.contains("/arr (array)\n  no conventions declared"));
+
+        let inspection = inspect(&Args {
+            path: tmp.path().to_path_buf(),
+            recursive: false,
+            format: Format::Json,
+        })
+        .unwrap();
+        let conventions = &inspection.nodes[0].conventions;
+        assert_eq!(conventions[0].payload, Some(json!({"spdx": "MIT"})));
+        assert!(!conventions[1].registered && !conventions[1].parseable);
+    }
+}
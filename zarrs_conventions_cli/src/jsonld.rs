@@ -0,0 +1,213 @@
+//! Export conventional metadata as schema.org/DCAT JSON-LD,
+//! for indexing by search engines and data catalogs.
+use std::{io::Write, path::PathBuf, process::ExitCode};
+
+use serde_json::{Map, Value, json};
+use zarrs_conventions::AttributesParser;
+
+#[cfg(any(
+    feature = "authors",
+    feature = "license",
+    feature = "links",
+    feature = "uom"
+))]
+use crate::known;
+use crate::{
+    error::Result,
+    output::write_json,
+    store::{self, Node},
+};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Directory of a Zarr node (containing `zarr.json`).
+    pub path: PathBuf,
+    /// Also describe all descendants, as parts of the node's dataset.
+    #[arg(short, long)]
+    pub recursive: bool,
+    /// IRI at which the hierarchy is published; nodes' paths are appended to form their `@id`s.
+    #[arg(long)]
+    pub base: Option<String>,
+}
+
+fn context() -> Value {
+    json!({
+        "@vocab": "https://schema.org/",
+        "dcat": "http://www.w3.org/ns/dcat#",
+        "dct": "http://purl.org/dc/terms/",
+    })
+}
+
+/// Add schema.org and DCAT license properties.
+#[allow(unused_variables)]
+fn annotate_license(parser: &AttributesParser, out: &mut Map<String, Value>) {
+    #[cfg(feature = "license")]
+    if let Some(l) = known::parse::<zarrs_conventions_license::License>(parser) {
+        let license = l.to_schema_org();
+        if let Some(url) = license.as_str() {
+            out.insert("dct:license".into(), json!({"@id": url}));
+        }
+        out.insert("license".into(), license);
+    }
+}
+
+/// Add schema.org creators and contributors.
+#[allow(unused_variables)]
+fn annotate_authors(parser: &AttributesParser, out: &mut Map<String, Value>) {
+    #[cfg(feature = "authors")]
+    if let Some(a) = known::parse::<zarrs_conventions_authors::Authors>(parser)
+        && let Value::Object(people) = a.to_schema_org()
+    {
+        out.extend(people);
+    }
+}
+
+/// Add the preferred citations of the node, from its `cite-as` links.
+#[allow(unused_variables)]
+fn annotate_citation(parser: &AttributesParser, out: &mut Map<String, Value>) {
+    #[cfg(feature = "links")]
+    if let Some(links) = known::parse::<zarrs_conventions_links::Links>(parser) {
+        let citations: Vec<Value> = links
+            .with_rel("cite-as")
+            .filter_map(|link| match link.target() {
+                zarrs_conventions_links::Target::Href(href) => Some(href.as_str().into()),
+                zarrs_conventions_links::Target::Path(_) => None,
+            })
+            .collect();
+        if !citations.is_empty() {
+            out.insert("citation".into(), citations.into());
+        }
+    }
+}
+
+/// Describe the node's unit of measurement as a measured variable.
+#[allow(unused_variables)]
+fn annotate_unit(node: &Node, parser: &AttributesParser, out: &mut Map<String, Value>) {
+    #[cfg(feature = "uom")]
+    if let Some(u) = known::parse::<zarrs_conventions_uom::UnitOfMeasurement>(parser) {
+        let mut variable = json!({
+            "@type": "PropertyValue",
+            "name": node.path,
+            "unitText": u.ucum().unit().unwrap_or("1"),
+        });
+        if !u.description().is_empty() {
+            variable["description"] = u.description().into();
+        }
+        out.insert("variableMeasured".into(), variable);
+    }
+}
+
+/// Dataset description of a single node.
+pub fn describe(node: &Node, base: Option<&str>) -> Map<String, Value> {
+    let mut out = Map::default();
+    if let Some(base) = base {
+        let id = format!("{}{}", base.trim_end_matches('/'), node.path);
+        out.insert("@id".into(), id.into());
+    }
+    out.insert("@type".into(), json!(["Dataset", "dcat:Dataset"]));
+    out.insert("identifier".into(), node.path.clone().into());
+    out.insert("additionalType".into(), node.node_type().into());
+    let parser: AttributesParser =
+        serde_json::from_value(Value::Object(node.attributes())).unwrap_or_default();
+    annotate_license(&parser, &mut out);
+    annotate_authors(&parser, &mut out);
+    annotate_citation(&parser, &mut out);
+    annotate_unit(node, &parser, &mut out);
+    out
+}
+
+/// JSON-LD document describing the node, with its descendants as parts if recursive.
+pub fn export(args: &Args) -> Result<Value> {
+    let nodes = store::open(&args.path, args.recursive)?;
+    let mut nodes = nodes.iter();
+    let root = nodes.next().expect("opened hierarchy includes its root");
+    let mut doc = Map::default();
+    doc.insert("@context".into(), context());
+    doc.extend(describe(root, args.base.as_deref()));
+    let parts: Vec<_> = nodes
+        .map(|n| Value::Object(describe(n, args.base.as_deref())))
+        .collect();
+    if !parts.is_empty() {
+        doc.insert("hasPart".into(), parts.into());
+    }
+    Ok(Value::Object(doc))
+}
+
+pub fn run(args: Args, out: &mut impl Write) -> Result<ExitCode> {
+    write_json(out, &export(&args)?)?;
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(all(
+    test,
+    feature = "authors",
+    feature = "license",
+    feature = "links",
+    feature = "uom"
+))]
+mod tests {
+    use serde_json::json;
+    use zarrs_conventions::ZarrConventionImpl;
+    use zarrs_conventions_authors::Authors;
+    use zarrs_conventions_links::Links;
+
+    use super::{Args, export};
+    use crate::store::tests::write_node;
+
+    #[test]
+    fn jsonld_export() {
+        let tmp = tempfile::tempdir().unwrap();
+        write_node(
+            tmp.path(),
+            "",
+            "group",
+            json!({
+                "zarr_conventions": [
+                    {"uuid": "b77365e5-2b0c-4141-b917-c03b7c68e935"},
+                    {"uuid": Authors::DEFINITION.uuid},
+                    {"uuid": Links::DEFINITION.uuid},
+                ],
+                "license": {"spdx": "CC-BY-4.0"},
+                "authors": {"creators": [{"name": "Jane Doe", "orcid": "0000-0002-1825-0097"}]},
+                "links": [
+                    {"rel": "cite-as", "href": "https://doi.org/10.5281/zenodo.1234"},
+                    {"rel": "cite-as", "path": "../paper"},
+                ],
+            }),
+        );
+        write_node(
+            tmp.path(),
+            "arr",
+            "array",
+            json!({
+                "zarr_conventions": [{"uuid": "3bbe438d-df37-49fe-8e2b-739296d46dfb"}],
+                "uom": {"ucum": {"unit": "um"}}
+            }),
+        );
+        let doc = export(&Args {
+            path: tmp.path().to_path_buf(),
+            recursive: true,
+            base: Some("https://example.com/data.zarr/".into()),
+        })
+        .unwrap();
+        assert_eq!(doc["@id"], "https://example.com/data.zarr/");
+        assert_eq!(doc["license"], "https://spdx.org/licenses/CC-BY-4.0");
+        assert_eq!(
+            doc["dct:license"]["@id"],
+            "https://spdx.org/licenses/CC-BY-4.0"
+        );
+        assert_eq!(doc["creator"][0]["name"], "Jane Doe");
+        assert_eq!(
+            doc["creator"][0]["@id"],
+            "https://orcid.org/0000-0002-1825-0097"
+        );
+        assert_eq!(
+            doc["citation"],
+            json!(["https://doi.org/10.5281/zenodo.1234"])
+        );
+        let part = &doc["hasPart"][0];
+        assert_eq!(part["@id"], "https://example.com/data.zarr/arr");
+        assert_eq!(part["variableMeasured"]["unitText"], "um");
+        assert!(part.get("license").is_none());
+    }
+}
@@ -0,0 +1,181 @@
+//! Conventions whose payloads this tool can parse.
+use std::sync::Once;
+
+use zarrs_conventions::{
+    AttributesParser, ConventionIdRef, DEFAULT_ZARR_CONVENTION_REGISTRY, ZarrConventionImpl,
+    docgen::ConventionDocs, validate::DynConvention,
+};
+
+/// All conventions compiled into this tool.
+pub fn known() -> Vec<DynConvention> {
+    vec![
+        #[cfg(feature = "license")]
+        DynConvention::nested::<zarrs_conventions_license::License>()
+            .with_lint::<zarrs_conventions_license::License>(),
+        #[cfg(feature = "proj")]
+        DynConvention::either::<zarrs_conventions_proj::Proj>()
+            .with_lint::<zarrs_conventions_proj::Proj>(),
+        #[cfg(feature = "thumbnails")]
+        DynConvention::nested::<zarrs_conventions_thumbnails::Thumbnails>(),
+        #[cfg(feature = "uom")]
+        DynConvention::nested::<zarrs_conventions_uom::UnitOfMeasurement>()
+            .with_lint::<zarrs_conventions_uom::UnitOfMeasurement>(),
+        #[cfg(feature = "transform")]
+        DynConvention::nested::<zarrs_conventions_transform::CoordinateTransform>()
+            .with_lint::<zarrs_conventions_transform::CoordinateTransform>(),
+        #[cfg(feature = "axes")]
+        DynConvention::nested::<zarrs_conventions_axes::Axes>()
+            .with_lint::<zarrs_conventions_axes::Axes>(),
+        #[cfg(feature = "authors")]
+        DynConvention::nested::<zarrs_conventions_authors::Authors>()
+            .with_lint::<zarrs_conventions_authors::Authors>(),
+        #[cfg(feature = "checksum")]
+        DynConvention::nested::<zarrs_conventions_checksum::Checksum>()
+            .with_lint::<zarrs_conventions_checksum::Checksum>(),
+        #[cfg(feature = "timestamps")]
+        DynConvention::nested::<zarrs_conventions_timestamps::Timestamps>()
+            .with_lint::<zarrs_conventions_timestamps::Timestamps>(),
+        #[cfg(feature = "display")]
+        DynConvention::nested::<zarrs_conventions_display::DisplaySettings>()
+            .with_lint::<zarrs_conventions_display::DisplaySettings>(),
+        #[cfg(feature = "names")]
+        DynConvention::nested::<zarrs_conventions_names::Names>()
+            .with_lint::<zarrs_conventions_names::Names>(),
+        #[cfg(feature = "contact")]
+        DynConvention::nested::<zarrs_conventions_contact::Contacts>()
+            .with_lint::<zarrs_conventions_contact::Contacts>(),
+        #[cfg(feature = "links")]
+        DynConvention::nested::<zarrs_conventions_links::Links>()
+            .with_lint::<zarrs_conventions_links::Links>(),
+        #[cfg(feature = "version")]
+        DynConvention::nested::<zarrs_conventions_version::DatasetVersion>()
+            .with_lint::<zarrs_conventions_version::DatasetVersion>(),
+        #[cfg(feature = "access")]
+        DynConvention::nested::<zarrs_conventions_access::Access>()
+            .with_lint::<zarrs_conventions_access::Access>(),
+        #[cfg(feature = "qc")]
+        DynConvention::nested::<zarrs_conventions_qc::QcFlags>()
+            .with_lint::<zarrs_conventions_qc::QcFlags>(),
+        #[cfg(feature = "stats")]
+        DynConvention::nested::<zarrs_conventions_stats::Stats>()
+            .with_lint::<zarrs_conventions_stats::Stats>(),
+        #[cfg(feature = "extent")]
+        DynConvention::nested::<zarrs_conventions_extent::Extent>()
+            .with_lint::<zarrs_conventions_extent::Extent>(),
+        #[cfg(feature = "timeaxis")]
+        DynConvention::nested::<zarrs_conventions_timeaxis::TimeAxis>()
+            .with_lint::<zarrs_conventions_timeaxis::TimeAxis>(),
+        #[cfg(feature = "channels")]
+        DynConvention::nested::<zarrs_conventions_channels::Channels>()
+            .with_lint::<zarrs_conventions_channels::Channels>(),
+        #[cfg(feature = "labels")]
+        DynConvention::nested::<zarrs_conventions_labels::LabelMap>()
+            .with_lint::<zarrs_conventions_labels::LabelMap>(),
+        #[cfg(feature = "sources")]
+        DynConvention::nested::<zarrs_conventions_sources::Sources>()
+            .with_lint::<zarrs_conventions_sources::Sources>(),
+    ]
+}
+
+/// Schemas of the compiled-in conventions whose crates bundle one,
+/// generated from the same types as the crate's `spec/schema.json`.
+///
+/// The license, proj, thumbnails, and uom conventions do not bundle schemas.
+pub fn schemas() -> Vec<ConventionDocs> {
+    vec![
+        #[cfg(feature = "transform")]
+        ConventionDocs::nested::<zarrs_conventions_transform::CoordinateTransform>(),
+        #[cfg(feature = "axes")]
+        ConventionDocs::nested::<zarrs_conventions_axes::Axes>(),
+        #[cfg(feature = "authors")]
+        ConventionDocs::nested::<zarrs_conventions_authors::Authors>(),
+        #[cfg(feature = "checksum")]
+        ConventionDocs::nested::<zarrs_conventions_checksum::Checksum>(),
+        #[cfg(feature = "timestamps")]
+        ConventionDocs::nested::<zarrs_conventions_timestamps::Timestamps>(),
+        #[cfg(feature = "display")]
+        ConventionDocs::nested::<zarrs_conventions_display::DisplaySettings>(),
+        #[cfg(feature = "names")]
+        ConventionDocs::nested::<zarrs_conventions_names::Names>(),
+        #[cfg(feature = "contact")]
+        ConventionDocs::nested::<zarrs_conventions_contact::Contacts>(),
+        #[cfg(feature = "links")]
+        ConventionDocs::nested::<zarrs_conventions_links::Links>(),
+        #[cfg(feature = "version")]
+        ConventionDocs::nested::<zarrs_conventions_version::DatasetVersion>(),
+        #[cfg(feature = "access")]
+        ConventionDocs::nested::<zarrs_conventions_access::Access>(),
+        #[cfg(feature = "qc")]
+        ConventionDocs::nested::<zarrs_conventions_qc::QcFlags>(),
+        #[cfg(feature = "stats")]
+        ConventionDocs::nested::<zarrs_conventions_stats::Stats>(),
+        #[cfg(feature = "extent")]
+        ConventionDocs::nested::<zarrs_conventions_extent::Extent>(),
+        #[cfg(feature = "timeaxis")]
+        ConventionDocs::nested::<zarrs_conventions_timeaxis::TimeAxis>(),
+        #[cfg(feature = "channels")]
+        ConventionDocs::nested::<zarrs_conventions_channels::Channels>(),
+        #[cfg(feature = "labels")]
+        ConventionDocs::nested::<zarrs_conventions_labels::LabelMap>(),
+        #[cfg(feature = "sources")]
+        ConventionDocs::nested::<zarrs_conventions_sources::Sources>(),
+    ]
+}
+
+/// Register the compiled-in conventions in the default registry,
+/// including those which do not register themselves because their identifiers are provisional.
+pub fn ensure_registered() {
+    static REGISTER: Once = Once::new();
+    REGISTER.call_once(|| {
+        for conv in known() {
+            if !DEFAULT_ZARR_CONVENTION_REGISTRY.contains(conv.definition.uuid) {
+                DEFAULT_ZARR_CONVENTION_REGISTRY
+                    .register_definition(conv.definition)
+                    .expect("compiled-in conventions should not conflict");
+            }
+        }
+    });
+}
+
+/// Find a compiled-in convention by identifier.
+pub fn find<'a>(id: impl Into<ConventionIdRef<'a>>) -> Option<DynConvention> {
+    let id = id.into();
+    known().into_iter().find(|k| k.is(id))
+}
+
+/// Parse a compiled-in convention's payload from whichever representations this tool understands,
+/// as when validating.
+///
+/// None if the convention is not declared, or its payload is absent or invalid.
+// only used with some conventions compiled in
+#[allow(dead_code)]
+pub fn parse<T: ZarrConventionImpl + serde::de::DeserializeOwned>(
+    parser: &AttributesParser,
+) -> Option<T> {
+    let payload = find(T::DEFINITION.uuid)?.parse(parser).ok()??;
+    serde_json::from_value(payload).ok()
+}
+
+#[cfg(all(test, feature = "proj"))]
+mod tests {
+    use serde_json::json;
+    use zarrs_conventions::{AttributesParser, ZarrConventionImpl};
+    use zarrs_conventions_proj::Proj;
+
+    #[test]
+    fn parse_either_repr() {
+        for attributes in [
+            json!({"proj": {"code": "EPSG:4326"}}),
+            json!({"proj:code": "EPSG:4326"}),
+        ] {
+            let mut attributes = attributes.as_object().cloned().unwrap();
+            attributes.insert(
+                "zarr_conventions".into(),
+                json!([{"uuid": Proj::DEFINITION.uuid}]),
+            );
+            let parser: AttributesParser = serde_json::from_value(attributes.into()).unwrap();
+            let proj = super::parse::<Proj>(&parser).unwrap();
+            assert_eq!(proj.epsg(), Some(4326));
+        }
+    }
+}
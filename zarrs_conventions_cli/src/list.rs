@@ -0,0 +1,94 @@
+//! List the conventions this tool understands.
+//!
+//! Only the fields of a convention's definition are shown.
+//! Versions, tags, and deprecation are not part of a definition, so are not listed;
+//! a convention's version is only implied by its schema and specification URLs.
+use std::{io::Write, process::ExitCode};
+
+use serde::Serialize;
+use zarrs_conventions::{ConventionDefinition, DEFAULT_ZARR_CONVENTION_REGISTRY};
+
+use crate::{
+    error::Result,
+    known,
+    output::{Format, REPORT_VERSION, write_json},
+};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    #[arg(long, value_enum, default_value_t)]
+    pub format: Format,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Entry {
+    #[serde(flatten)]
+    pub definition: ConventionDefinition,
+    /// Whether this tool can parse and validate the convention's payload.
+    pub parseable: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Listing {
+    pub version: u32,
+    pub conventions: Vec<Entry>,
+}
+
+/// All registered conventions, sorted by name.
+pub fn list() -> Listing {
+    let mut conventions: Vec<_> = DEFAULT_ZARR_CONVENTION_REGISTRY
+        .conventions()
+        .into_iter()
+        .map(|definition| Entry {
+            parseable: known::find(definition.uuid).is_some(),
+            definition,
+        })
+        .collect();
+    conventions.sort_by_key(|e| (e.definition.name, e.definition.uuid));
+    Listing {
+        version: REPORT_VERSION,
+        conventions,
+    }
+}
+
+pub fn run(args: Args, out: &mut impl Write) -> Result<ExitCode> {
+    let listing = list();
+    match args.format {
+        Format::Json => write_json(out, &listing)?,
+        Format::Text => {
+            for entry in &listing.conventions {
+                let def = &entry.definition;
+                let parseable = if entry.parseable {
+                    ""
+                } else {
+                    " (not parseable)"
+                };
+                writeln!(out, "{}{parseable}", def.name)?;
+                writeln!(out, "  {}", def.description)?;
+                writeln!(out, "  uuid:       {}", def.uuid)?;
+                writeln!(out, "  schema_url: {}", def.schema_url)?;
+                writeln!(out, "  spec_url:   {}", def.spec_url)?;
+            }
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(all(test, feature = "license"))]
+mod tests {
+    use super::list;
+
+    #[test]
+    fn list_includes_compiled_in() {
+        let listing = list();
+        let license = listing
+            .conventions
+            .iter()
+            .find(|e| e.definition.name == "license")
+            .unwrap();
+        assert!(license.parseable);
+        let json = serde_json::to_value(license).unwrap();
+        assert_eq!(json["uuid"], "b77365e5-2b0c-4141-b917-c03b7c68e935");
+        assert_eq!(json["parseable"], true);
+    }
+}
@@ -0,0 +1,169 @@
+//! Command-line tool for working with zarr-conventions metadata in Zarr v3 hierarchies,
+//! on the local filesystem or, with the `object_store` feature, in object stores.
+use std::process::ExitCode;
+
+use clap::{CommandFactory, Parser, Subcommand};
+
+mod add;
+mod convert_repr;
+mod diff;
+mod error;
+mod inspect;
+mod jsonld;
+mod known;
+mod list;
+mod output;
+#[cfg(feature = "object_store")]
+mod remote;
+mod report;
+mod selector;
+#[cfg(feature = "serve")]
+mod serve;
+mod store;
+mod strip;
+mod validate;
+
+#[derive(Debug, Parser)]
+#[command(name = "zarrs-conventions", version, about)]
+struct Cli {
+    /// Write versioned JSON rather than human-readable output; short for `--format json`.
+    /// Not accepted by subcommands without such output.
+    #[arg(long, global = true)]
+    json: bool,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Annotate an existing node with conventional metadata.
+    Add(add::Args),
+    /// Print a shell completion script.
+    Completions {
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
+    },
+    /// Convert a convention's payloads between nested and prefixed representations.
+    ConvertRepr(convert_repr::Args),
+    /// Compare the conventional metadata of two nodes or hierarchies.
+    Diff(diff::Args),
+    /// Print each node's declared conventions and their parsed payloads.
+    Inspect(inspect::Args),
+    /// Export conventional metadata as schema.org/DCAT JSON-LD.
+    Jsonld(jsonld::Args),
+    /// List the conventions this tool understands, with their identifiers.
+    List(list::Args),
+    /// Summarise the conventional metadata of a hierarchy.
+    Report(report::Args),
+    /// Validate documents submitted over HTTP, responding with JSON reports.
+    #[cfg(feature = "serve")]
+    Serve(serve::Args),
+    /// Remove a convention's payload and declaration from nodes.
+    Strip(strip::Args),
+    /// Check nodes' conventional metadata, for use in CI pipelines.
+    Validate(validate::Args),
+}
+
+impl Command {
+    /// Select JSON output, for subcommands which are not always JSON.
+    ///
+    /// Errs with an explanation for subcommands which have no such output.
+    fn set_json(&mut self) -> Result<(), &'static str> {
+        match self {
+            Command::Add(args) => args.target_mut().format = output::Format::Json,
+            Command::ConvertRepr(args) => args.format = output::Format::Json,
+            Command::Diff(args) => args.format = output::Format::Json,
+            Command::Inspect(args) => args.format = output::Format::Json,
+            Command::List(args) => args.format = output::Format::Json,
+            Command::Report(args) => args.format = report::ReportFormat::Json,
+            Command::Strip(args) => args.format = output::Format::Json,
+            Command::Validate(args) => args.format = output::Format::Json,
+            Command::Completions { .. } => {
+                return Err(
+                    "--json cannot be used with `completions`, which prints a shell script",
+                );
+            }
+            Command::Jsonld(_) => {
+                return Err("--json cannot be used with `jsonld`, which always writes JSON-LD");
+            }
+            #[cfg(feature = "serve")]
+            Command::Serve(_) => {
+                return Err("--json cannot be used with `serve`, whose responses are always JSON");
+            }
+        }
+        Ok(())
+    }
+}
+
+fn main() -> ExitCode {
+    // before parsing, as selectors are resolved against the registry
+    known::ensure_registered();
+    let mut cli = Cli::parse();
+    if cli.json
+        && let Err(msg) = cli.command.set_json()
+    {
+        Cli::command()
+            .error(clap::error::ErrorKind::ArgumentConflict, msg)
+            .exit();
+    }
+    let mut stdout = std::io::stdout().lock();
+    let result = match cli.command {
+        Command::Add(args) => add::run(args, &mut stdout),
+        Command::Completions { shell } => {
+            let mut cmd = Cli::command();
+            let name = cmd.get_name().to_string();
+            clap_complete::generate(shell, &mut cmd, name, &mut stdout);
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::ConvertRepr(args) => convert_repr::run(args, &mut stdout),
+        Command::Diff(args) => diff::run(args, &mut stdout),
+        Command::Inspect(args) => inspect::run(args, &mut stdout),
+        Command::Jsonld(args) => jsonld::run(args, &mut stdout),
+        Command::List(args) => list::run(args, &mut stdout),
+        Command::Report(args) => report::run(args, &mut stdout),
+        #[cfg(feature = "serve")]
+        Command::Serve(args) => serve::run(args, &mut stdout),
+        Command::Strip(args) => strip::run(args, &mut stdout),
+        Command::Validate(args) => validate::run(args, &mut stdout),
+    };
+    result.unwrap_or_else(|e| {
+        eprintln!("error: {e}");
+        ExitCode::from(2)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::{CommandFactory, Parser};
+
+    use super::{Cli, Command, output::Format};
+
+    #[test]
+    fn cli_json_flag() {
+        Cli::command().debug_assert();
+        let mut cli =
+            Cli::try_parse_from(["zarrs-conventions", "validate", "x", "--json"]).unwrap();
+        assert!(cli.json);
+        cli.command.set_json().unwrap();
+        let Command::Validate(args) = cli.command else {
+            panic!("expected validate subcommand");
+        };
+        assert_eq!(args.format, Format::Json);
+        for args in [
+            &["zarrs-conventions", "--json", "completions", "bash"][..],
+            &["zarrs-conventions", "jsonld", "x", "--json"],
+        ] {
+            let mut cli = Cli::try_parse_from(args).unwrap();
+            assert!(cli.command.set_json().is_err());
+        }
+
+        let mut script = Vec::default();
+        clap_complete::generate(
+            clap_complete::Shell::Bash,
+            &mut Cli::command(),
+            "zarrs-conventions",
+            &mut script,
+        );
+        assert!(String::from_utf8(script).unwrap().contains("convert-repr"));
+    }
+}
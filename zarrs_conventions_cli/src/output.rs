@@ -0,0 +1,54 @@
+//! Output formats shared by subcommands.
+use std::io::Write;
+
+use serde::Serialize;
+use zarrs_conventions::Attributes;
+
+use crate::error::{Error, Result};
+
+pub use zarrs_conventions::validate::REPORT_VERSION;
+
+/// Output format.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    /// Human-readable text.
+    #[default]
+    Text,
+    /// Pretty-printed JSON.
+    Json,
+}
+
+/// Nodes modified by a subcommand, or which would be in a dry run.
+#[derive(Debug, Clone, Serialize)]
+pub struct Modified {
+    pub version: u32,
+    pub dry_run: bool,
+    pub nodes: Vec<ModifiedNode>,
+}
+
+impl Modified {
+    pub fn new(dry_run: bool) -> Self {
+        Self {
+            version: REPORT_VERSION,
+            dry_run,
+            nodes: Vec::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ModifiedNode {
+    pub path: String,
+    /// Keys removed from the attributes.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub removed: Vec<String>,
+    /// The resulting attributes.
+    pub attributes: Attributes,
+}
+
+/// Write a value as pretty-printed JSON followed by a newline.
+pub fn write_json(out: &mut impl Write, value: &impl Serialize) -> Result<()> {
+    serde_json::to_writer_pretty(&mut *out, value).map_err(|e| Error::Output(e.into()))?;
+    writeln!(out)?;
+    Ok(())
+}
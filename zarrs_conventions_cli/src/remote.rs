@@ -0,0 +1,201 @@
+//! Zarr v3 hierarchies in object stores, addressed by URL.
+//!
+//! The store is picked from the URL's scheme (`s3://`, `gs://`, `az://`, `https://`, `file://`, ...)
+//! and configured from the environment, e.g. `AWS_REGION` or `GOOGLE_SERVICE_ACCOUNT`.
+use std::{path::PathBuf, sync::Arc, sync::OnceLock};
+
+use object_store::{ObjectStore, ObjectStoreExt, PutPayload, path::Path as ObjectPath};
+use url::Url;
+
+use crate::{
+    error::{Error, Result},
+    store::{METADATA_FILE, Node},
+};
+
+/// Where a node's metadata document lives in its object store.
+#[derive(Debug, Clone)]
+pub struct Location {
+    store: Arc<dyn ObjectStore>,
+    path: ObjectPath,
+}
+
+impl Location {
+    /// Write the serialized metadata document to the store.
+    ///
+    /// Object store writes are atomic, so the document is never left half-written.
+    pub fn save(&self, contents: String, file: &std::path::Path) -> Result<()> {
+        block_on(self.store.put(&self.path, PutPayload::from(contents)))
+            .map(|_| ())
+            .map_err(|source| object_store_err(file, source))
+    }
+}
+
+/// A hierarchy rooted at some prefix of an object store.
+#[derive(Debug)]
+pub struct Hierarchy {
+    store: Arc<dyn ObjectStore>,
+    root: ObjectPath,
+    url: String,
+}
+
+impl Hierarchy {
+    /// Pick and configure the object store for the given URL.
+    pub fn parse(url: &str) -> Result<Self> {
+        let parsed =
+            Url::parse(url).map_err(|e| Error::Other(format!("invalid URL {url}: {e}")))?;
+        let (store, root) =
+            object_store::parse_url_opts(&parsed, std::env::vars()).map_err(|source| {
+                Error::ObjectStore {
+                    url: url.to_string(),
+                    source,
+                }
+            })?;
+        Ok(Self {
+            store: store.into(),
+            root,
+            url: url.trim_end_matches('/').to_string(),
+        })
+    }
+
+    /// Read the node at the given path within the hierarchy.
+    pub fn read(&self, path: String) -> Result<Node> {
+        let location = self.dir(&path).join(METADATA_FILE);
+        let file = self.file(&path);
+        let bytes = block_on(async { self.store.get(&location).await?.bytes().await })
+            .map_err(|source| object_store_err(&file, source))?;
+        let mut node = Node::parse(path, file, &bytes)?;
+        node.remote = Some(Location {
+            store: Arc::clone(&self.store),
+            path: location,
+        });
+        Ok(node)
+    }
+
+    /// Read the root node and, if `recursive`, all of its descendants.
+    ///
+    /// Nodes are returned in depth-first order, with children sorted by name.
+    pub fn open(&self, recursive: bool) -> Result<Vec<Node>> {
+        let mut out = Vec::default();
+        self.walk("/".to_string(), recursive, &mut out)?;
+        Ok(out)
+    }
+
+    fn walk(&self, path: String, recursive: bool, out: &mut Vec<Node>) -> Result<()> {
+        let node = self.read(path)?;
+        let is_group = node.node_type() == "group";
+        let path = node.path.clone();
+        out.push(node);
+        if !(recursive && is_group) {
+            return Ok(());
+        }
+
+        for name in self.children(&path)? {
+            let child_path = if path == "/" {
+                format!("/{name}")
+            } else {
+                format!("{path}/{name}")
+            };
+            self.walk(child_path, recursive, out)?;
+        }
+        Ok(())
+    }
+
+    /// Names of the prefixes directly under the node which hold a metadata document.
+    fn children(&self, path: &str) -> Result<Vec<String>> {
+        let dir = self.dir(path);
+        let store_err = |source| object_store_err(&self.file(path), source);
+        let listing = block_on(self.store.list_with_delimiter(Some(&dir))).map_err(store_err)?;
+        let mut children = Vec::default();
+        for prefix in listing.common_prefixes {
+            let Some(name) = prefix.filename() else {
+                continue;
+            };
+            match block_on(self.store.head(&prefix.clone().join(METADATA_FILE))) {
+                Ok(_) => children.push(name.to_string()),
+                Err(object_store::Error::NotFound { .. }) => {}
+                Err(source) => return Err(store_err(source)),
+            }
+        }
+        children.sort();
+        Ok(children)
+    }
+
+    /// The prefix of the node at the given path.
+    fn dir(&self, path: &str) -> ObjectPath {
+        path.split('/')
+            .filter(|part| !part.is_empty())
+            .fold(self.root.clone(), |dir, part| dir.join(part))
+    }
+
+    /// URL of the node's metadata document, for reporting.
+    fn file(&self, path: &str) -> PathBuf {
+        let path = path.trim_end_matches('/');
+        PathBuf::from(format!("{}{path}/{METADATA_FILE}", self.url))
+    }
+}
+
+fn object_store_err(file: &std::path::Path, source: object_store::Error) -> Error {
+    Error::ObjectStore {
+        url: file.display().to_string(),
+        source,
+    }
+}
+
+/// Drive an object store request to completion on a shared single-threaded runtime.
+fn block_on<F: Future>(future: F) -> F::Output {
+    static RUNTIME: OnceLock<tokio::runtime::Runtime> = OnceLock::new();
+    RUNTIME
+        .get_or_init(|| {
+            tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .expect("tokio runtime should build")
+        })
+        .block_on(future)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::Hierarchy;
+    use crate::store::tests::write_node;
+
+    #[test]
+    fn open_and_save_file_url() {
+        let tmp = tempfile::tempdir().unwrap();
+        write_node(tmp.path(), "", "group", json!({}));
+        write_node(tmp.path(), "b", "array", json!({}));
+        write_node(tmp.path(), "a", "group", json!({"x": 1}));
+        write_node(tmp.path(), "a/c", "array", json!({}));
+        std::fs::create_dir_all(tmp.path().join("b/c/0")).unwrap();
+
+        let url = format!("file://{}/", tmp.path().display());
+        let hierarchy = Hierarchy::parse(&url).unwrap();
+        let nodes = hierarchy.open(true).unwrap();
+        let paths: Vec<_> = nodes.iter().map(|n| n.path.as_str()).collect();
+        assert_eq!(paths, ["/", "/a", "/a/c", "/b"]);
+        assert_eq!(
+            nodes[1].file.to_str().unwrap(),
+            format!("file://{}/a/zarr.json", tmp.path().display())
+        );
+
+        let mut node = nodes[1].clone();
+        node.set_attributes(json!({"x": 2}).as_object().unwrap().clone());
+        node.save().unwrap();
+        let node = Hierarchy::parse(&url).unwrap().read("/a".into()).unwrap();
+        assert_eq!(node.attributes()["x"], 2);
+    }
+
+    #[test]
+    fn missing_node() {
+        let tmp = tempfile::tempdir().unwrap();
+        let url = format!("file://{}", tmp.path().display());
+        let err = Hierarchy::parse(&url)
+            .unwrap()
+            .open(false)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("zarr.json"), "{err}");
+    }
+}
@@ -0,0 +1,370 @@
+//! Summarise the conventional metadata of a hierarchy.
+use std::{collections::BTreeMap, io::Write, path::PathBuf, process::ExitCode};
+
+use serde::Serialize;
+use zarrs_conventions::{
+    AttributesParser, DEFAULT_ZARR_CONVENTION_REGISTRY,
+    validate::{NodeReport, Severity, label},
+};
+
+#[cfg(any(feature = "license", feature = "uom"))]
+use crate::known;
+use crate::{
+    error::Result,
+    output::{REPORT_VERSION, write_json},
+    store,
+    validate::check_node,
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ReportFormat {
+    /// Markdown.
+    #[default]
+    Md,
+    /// A standalone HTML fragment.
+    Html,
+    /// Pretty-printed JSON.
+    Json,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Directory of the root Zarr node (containing `zarr.json`).
+    pub path: PathBuf,
+    #[arg(long, value_enum, default_value_t)]
+    pub format: ReportFormat,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct HierarchyReport {
+    pub version: u32,
+    pub groups: usize,
+    pub arrays: usize,
+    /// Number of nodes declaring each registered convention, by name.
+    pub coverage: BTreeMap<String, usize>,
+    /// Paths of nodes declaring each unregistered convention, by label.
+    pub unknown: BTreeMap<String, Vec<String>>,
+    /// Nodes with validation issues.
+    pub issues: Vec<NodeReport>,
+    /// Paths of nodes with each license.
+    pub licenses: BTreeMap<String, Vec<String>>,
+    /// Paths of nodes with each unit.
+    pub units: BTreeMap<String, Vec<String>>,
+}
+
+/// Short description of the node's license, if it has one.
+#[allow(unused_variables)]
+fn license_summary(parser: &AttributesParser) -> Option<String> {
+    #[cfg(feature = "license")]
+    if let Some(l) = known::parse::<zarrs_conventions_license::License>(parser) {
+        return Some(match (l.spdx(), l.url()) {
+            (Some(spdx), _) => spdx.to_string(),
+            (None, Some(url)) => url.to_string(),
+            (None, None) => "custom".to_string(),
+        });
+    }
+    None
+}
+
+/// The node's unit, if it has one.
+#[allow(unused_variables)]
+fn unit_summary(parser: &AttributesParser) -> Option<String> {
+    #[cfg(feature = "uom")]
+    if let Some(u) = known::parse::<zarrs_conventions_uom::UnitOfMeasurement>(parser) {
+        return Some(u.ucum().unit().unwrap_or("1").to_string());
+    }
+    None
+}
+
+pub fn report(args: &Args) -> Result<HierarchyReport> {
+    let mut out = HierarchyReport {
+        version: REPORT_VERSION,
+        ..Default::default()
+    };
+    for node in store::open(&args.path, true)? {
+        match node.node_type() {
+            "group" => out.groups += 1,
+            "array" => out.arrays += 1,
+            _ => (),
+        }
+        let issues = check_node(&node, None);
+        if !issues.is_empty() {
+            out.issues.push(NodeReport {
+                path: node.path.clone(),
+                issues,
+            });
+        }
+        for conv in node.declared().unwrap_or_default() {
+            match DEFAULT_ZARR_CONVENTION_REGISTRY.get(conv.id_ref()) {
+                Some(def) => *out.coverage.entry(def.name.to_string()).or_default() += 1,
+                None => out
+                    .unknown
+                    .entry(label(&conv))
+                    .or_default()
+                    .push(node.path.clone()),
+            }
+        }
+        let parser: AttributesParser =
+            serde_json::from_value(serde_json::Value::Object(node.attributes()))
+                .unwrap_or_default();
+        if let Some(license) = license_summary(&parser) {
+            out.licenses
+                .entry(license)
+                .or_default()
+                .push(node.path.clone());
+        }
+        if let Some(unit) = unit_summary(&parser) {
+            out.units.entry(unit).or_default().push(node.path.clone());
+        }
+    }
+    Ok(out)
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Rows of (label, paths) tables, shared by the renderers.
+fn paths_rows(map: &BTreeMap<String, Vec<String>>) -> Vec<(String, String)> {
+    map.iter()
+        .map(|(k, paths)| (k.clone(), paths.join(", ")))
+        .collect()
+}
+
+fn issue_rows(report: &HierarchyReport) -> Vec<[String; 4]> {
+    report
+        .issues
+        .iter()
+        .flat_map(|n| {
+            n.issues.iter().map(|i| {
+                let severity = match i.severity {
+                    Severity::Info => "info",
+                    Severity::Warning => "warning",
+                    Severity::Error => "error",
+                };
+                [
+                    n.path.clone(),
+                    severity.to_string(),
+                    i.code.to_string(),
+                    i.message.clone(),
+                ]
+            })
+        })
+        .collect()
+}
+
+fn write_markdown(out: &mut impl Write, r: &HierarchyReport) -> Result<()> {
+    fn table(out: &mut impl Write, header: &[&str], rows: Vec<Vec<String>>) -> Result<()> {
+        if rows.is_empty() {
+            writeln!(out, "None.")?;
+            return Ok(());
+        }
+        writeln!(out, "| {} |", header.join(" | "))?;
+        writeln!(out, "|{}", " --- |".repeat(header.len()))?;
+        for row in rows {
+            let row: Vec<_> = row.iter().map(|c| c.replace('|', "\\|")).collect();
+            writeln!(out, "| {} |", row.join(" | "))?;
+        }
+        Ok(())
+    }
+
+    writeln!(out, "# Conventions report\n")?;
+    writeln!(out, "{} groups, {} arrays\n", r.groups, r.arrays)?;
+    writeln!(out, "## Coverage\n")?;
+    table(
+        out,
+        &["Convention", "Nodes"],
+        r.coverage
+            .iter()
+            .map(|(k, v)| vec![k.clone(), v.to_string()])
+            .collect(),
+    )?;
+    for (title, header, map) in [
+        ("Unknown conventions", "Convention", &r.unknown),
+        ("Licenses", "License", &r.licenses),
+        ("Units", "Unit", &r.units),
+    ] {
+        writeln!(out, "\n## {title}\n")?;
+        table(
+            out,
+            &[header, "Nodes"],
+            paths_rows(map)
+                .into_iter()
+                .map(|(k, v)| vec![k, v])
+                .collect(),
+        )?;
+    }
+    writeln!(out, "\n## Issues\n")?;
+    table(
+        out,
+        &["Node", "Severity", "Check", "Message"],
+        issue_rows(r).into_iter().map(Vec::from).collect(),
+    )
+}
+
+fn write_html(out: &mut impl Write, r: &HierarchyReport) -> Result<()> {
+    fn table(out: &mut impl Write, header: &[&str], rows: Vec<Vec<String>>) -> Result<()> {
+        if rows.is_empty() {
+            writeln!(out, "<p>None.</p>")?;
+            return Ok(());
+        }
+        writeln!(out, "<table>")?;
+        let th: Vec<_> = header.iter().map(|h| format!("<th>{h}</th>")).collect();
+        writeln!(out, "<tr>{}</tr>", th.concat())?;
+        for row in rows {
+            let td: Vec<_> = row
+                .iter()
+                .map(|c| format!("<td>{}</td>", escape_html(c)))
+                .collect();
+            writeln!(out, "<tr>{}</tr>", td.concat())?;
+        }
+        writeln!(out, "</table>")?;
+        Ok(())
+    }
+
+    writeln!(out, "<section class=\"zarr-conventions-report\">")?;
+    writeln!(out, "<h1>Conventions report</h1>")?;
+    writeln!(out, "<p>{} groups, {} arrays</p>", r.groups, r.arrays)?;
+    writeln!(out, "<h2>Coverage</h2>")?;
+    table(
+        out,
+        &["Convention", "Nodes"],
+        r.coverage
+            .iter()
+            .map(|(k, v)| vec![k.clone(), v.to_string()])
+            .collect(),
+    )?;
+    for (title, header, map) in [
+        ("Unknown conventions", "Convention", &r.unknown),
+        ("Licenses", "License", &r.licenses),
+        ("Units", "Unit", &r.units),
+    ] {
+        writeln!(out, "<h2>{title}</h2>")?;
+        table(
+            out,
+            &[header, "Nodes"],
+            paths_rows(map)
+                .into_iter()
+                .map(|(k, v)| vec![k, v])
+                .collect(),
+        )?;
+    }
+    writeln!(out, "<h2>Issues</h2>")?;
+    table(
+        out,
+        &["Node", "Severity", "Check", "Message"],
+        issue_rows(r).into_iter().map(Vec::from).collect(),
+    )?;
+    writeln!(out, "</section>")?;
+    Ok(())
+}
+
+pub fn run(args: Args, out: &mut impl Write) -> Result<ExitCode> {
+    let r = report(&args)?;
+    match args.format {
+        ReportFormat::Md => write_markdown(out, &r)?,
+        ReportFormat::Html => write_html(out, &r)?,
+        ReportFormat::Json => write_json(out, &r)?,
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(all(test, feature = "license", feature = "uom"))]
+mod tests {
+    use serde_json::json;
+
+    use super::{Args, ReportFormat, report, write_html, write_markdown};
+    use crate::store::tests::write_node;
+
+    #[test]
+    fn report_summaries() {
+        let tmp = tempfile::tempdir().unwrap();
+        write_node(
+            tmp.path(),
+            "",
+            "group",
+            json!({
+                "zarr_conventions": [
+                    {"uuid": "b77365e5-2b0c-4141-b917-c03b7c68e935"},
+                    {"uuid": "00000000-0000-0000-0000-000000000001", "name": "a<b"}
+                ],
+                "license": {"spdx": "MIT"}
+            }),
+        );
+        write_node(
+            tmp.path(),
+            "arr",
+            "array",
+            json!({
+                "zarr_conventions": [{"uuid": "3bbe438d-df37-49fe-8e2b-739296d46dfb"}],
+                "uom": {"ucum": {"unit": "um"}}
+            }),
+        );
+        let r = report(&Args {
+            path: tmp.path().to_path_buf(),
+            format: ReportFormat::Json,
+        })
+        .unwrap();
+        assert_eq!((r.groups, r.arrays), (1, 1));
+        assert_eq!(r.coverage["license"], 1);
+        assert_eq!(r.licenses["MIT"], ["/"]);
+        assert_eq!(r.units["um"], ["/arr"]);
+        assert_eq!(r.issues[0].issues[0].code, "unknown-convention");
+
+        let mut md = Vec::default();
+        write_markdown(&mut md, &r).unwrap();
+        assert!(String::from_utf8(md).unwrap().contains("| MIT | / |"));
+        let mut html = Vec::default();
+        write_html(&mut html, &r).unwrap();
+        assert!(String::from_utf8(html).unwrap().contains("a&lt;b"));
+    }
+
+    #[test]
+    fn report_without_findings() {
+        let tmp = tempfile::tempdir().unwrap();
+        write_node(tmp.path(), "", "group", json!({}));
+        let r = report(&Args {
+            path: tmp.path().to_path_buf(),
+            format: ReportFormat::Md,
+        })
+        .unwrap();
+
+        let mut md = Vec::default();
+        write_markdown(&mut md, &r).unwrap();
+        assert_eq!(
+            String::from_utf8(md).unwrap(),
+            "# Conventions report
+
+1 groups, 0 arrays
+
+## Coverage
+
+None.
+
+## Unknown conventions
+
+None.
+
+## Licenses
+
+None.
+
+## Units
+
+None.
+
+## Issues
+
+None.
+"
+        );
+        let mut html = Vec::default();
+        write_html(&mut html, &r).unwrap();
+        let html = String::from_utf8(html).unwrap();
+        assert!(!html.contains("<table>"));
+        assert_eq!(html.matches("<p>None.</p>").count(), 5);
+    }
+}
@@ -0,0 +1,85 @@
+//! Select a convention by name, UUID, or URL on the command line.
+use std::str::FromStr;
+
+use zarrs_conventions::{
+    Convention, ConventionDefinition, DEFAULT_ZARR_CONVENTION_REGISTRY, iref::UriBuf, uuid::Uuid,
+};
+
+/// Identifiers by which to match declared conventions.
+///
+/// Resolved against the registry where possible so that declarations using any of
+/// the convention's identifiers are matched.
+/// Names are compared without any trailing `:`, so that e.g. `proj` selects the `proj:` convention.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Selector {
+    pub uuid: Option<Uuid>,
+    pub schema_url: Option<UriBuf>,
+    pub spec_url: Option<UriBuf>,
+    pub name: Option<String>,
+    /// The registered definition, if found.
+    pub definition: Option<ConventionDefinition>,
+}
+
+impl From<ConventionDefinition> for Selector {
+    fn from(def: ConventionDefinition) -> Self {
+        Self {
+            uuid: Some(def.uuid),
+            schema_url: Some(def.schema_url.to_owned()),
+            spec_url: Some(def.spec_url.to_owned()),
+            name: Some(def.name.to_string()),
+            definition: Some(def),
+        }
+    }
+}
+
+impl FromStr for Selector {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // selectors are parsed with the command line, before `main` could register anything
+        crate::known::ensure_registered();
+        let registry = &*DEFAULT_ZARR_CONVENTION_REGISTRY;
+        if let Ok(uuid) = Uuid::parse_str(s) {
+            return Ok(registry.get(uuid).map(Self::from).unwrap_or(Self {
+                uuid: Some(uuid),
+                ..Default::default()
+            }));
+        }
+        if s.contains("://") {
+            let url = UriBuf::from_str(s).map_err(|e| format!("invalid URL '{s}': {e}"))?;
+            if let Some(def) = registry
+                .get(zarrs_conventions::ConventionIdRef::SchemaUrl(&url))
+                .or_else(|| registry.get(zarrs_conventions::ConventionIdRef::SpecUrl(&url)))
+            {
+                return Ok(def.into());
+            }
+            return Ok(Self {
+                schema_url: Some(url.clone()),
+                spec_url: Some(url),
+                ..Default::default()
+            });
+        }
+        Ok(registry
+            .conventions()
+            .into_iter()
+            .find(|d| d.name.trim_end_matches(':') == s.trim_end_matches(':'))
+            .map(Self::from)
+            .unwrap_or(Self {
+                name: Some(s.to_string()),
+                ..Default::default()
+            }))
+    }
+}
+
+impl Selector {
+    /// Whether a declaration refers to the selected convention.
+    pub fn matches(&self, conv: &Convention) -> bool {
+        fn eq<T: PartialEq + ?Sized>(a: Option<&T>, b: Option<&T>) -> bool {
+            matches!((a, b), (Some(a), Some(b)) if a == b)
+        }
+        eq(self.uuid.as_ref(), conv.uuid().as_ref())
+            || eq(self.schema_url.as_deref(), conv.schema_url())
+            || eq(self.spec_url.as_deref(), conv.spec_url())
+            || (self.definition.is_none() && eq(self.name.as_deref(), conv.name()))
+    }
+}
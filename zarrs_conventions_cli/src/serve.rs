@@ -0,0 +1,149 @@
+//! Validate submitted documents over HTTP, e.g. as a pre-ingest check for data portals.
+//!
+//! - `POST /validate/attributes` takes an attributes object
+//! - `POST /validate/node` takes a node's full metadata document (`zarr.json`)
+//!
+//! Both respond with a [ValidationReport] for the single node `/`.
+//! Malformed requests get a 4xx status and a JSON object with an `error` message.
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use serde_json::{Value, json};
+use tiny_http::{Header, Method, Response, Server};
+use zarrs_conventions::{
+    Attributes,
+    validate::{NodeReport, ValidationReport},
+};
+
+use crate::{
+    error::{Error, Result},
+    validate::check,
+};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Address to listen on.
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    pub addr: String,
+    /// Directory of JSON schemas named `<convention name>.json`,
+    /// against which the attributes of documents using that convention are validated
+    /// in place of any schema bundled with the convention.
+    ///
+    /// Without a schema here, the license, proj, thumbnails, and uom conventions,
+    /// which do not bundle schemas, are only checked by parsing their payloads.
+    #[arg(long)]
+    pub schema_dir: Option<PathBuf>,
+}
+
+fn error(status: u16, message: impl Into<String>) -> (u16, Value) {
+    (status, json!({"error": message.into()}))
+}
+
+fn report(attrs: &Attributes, schema_dir: Option<&Path>) -> (u16, Value) {
+    let report = ValidationReport {
+        nodes: vec![NodeReport {
+            path: "/".to_string(),
+            issues: check(attrs, schema_dir),
+        }],
+        ..Default::default()
+    };
+    (
+        200,
+        serde_json::to_value(report).expect("report should serialize"),
+    )
+}
+
+/// Status and JSON body of the response to a request.
+pub fn handle(method: &Method, url: &str, body: &[u8], schema_dir: Option<&Path>) -> (u16, Value) {
+    let path = url.split('?').next().unwrap_or_default();
+    let node = match path {
+        "/validate/attributes" => false,
+        "/validate/node" => true,
+        _ => return error(404, format!("no such endpoint '{path}'")),
+    };
+    if *method != Method::Post {
+        return error(405, "use POST");
+    }
+    let document: Value = match serde_json::from_slice(body) {
+        Ok(v) => v,
+        Err(e) => return error(400, format!("invalid JSON: {e}")),
+    };
+    let attrs = if node {
+        document.get("attributes").cloned().unwrap_or(json!({}))
+    } else {
+        document
+    };
+    match attrs {
+        Value::Object(attrs) => report(&attrs, schema_dir),
+        _ => error(422, "attributes must be a JSON object"),
+    }
+}
+
+pub fn run(args: Args, out: &mut impl Write) -> Result<ExitCode> {
+    let server = Server::http(&args.addr).map_err(|e| Error::Other(e.to_string()))?;
+    writeln!(out, "listening on http://{}", args.addr)?;
+    out.flush()?;
+    let content_type =
+        Header::from_bytes("Content-Type", "application/json").expect("header should be valid");
+    for mut request in server.incoming_requests() {
+        let mut body = Vec::default();
+        let (status, value) = match request.as_reader().read_to_end(&mut body) {
+            Ok(_) => handle(
+                request.method(),
+                request.url(),
+                &body,
+                args.schema_dir.as_deref(),
+            ),
+            Err(e) => error(400, format!("could not read body: {e}")),
+        };
+        let response = Response::from_string(value.to_string())
+            .with_status_code(status)
+            .with_header(content_type.clone());
+        if let Err(e) = request.respond(response) {
+            eprintln!("error: could not respond: {e}");
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(all(test, feature = "license"))]
+mod tests {
+    use serde_json::json;
+    use tiny_http::Method;
+
+    use super::handle;
+
+    #[test]
+    fn serve_handle() {
+        let attrs = json!({"license": {"spdx": "MIT"}});
+        let (status, body) = handle(
+            &Method::Post,
+            "/validate/attributes",
+            attrs.to_string().as_bytes(),
+            None,
+        );
+        assert_eq!(status, 200);
+        assert_eq!(body["nodes"][0]["issues"][0]["code"], "undeclared-payload");
+
+        let node = json!({"zarr_format": 3, "node_type": "group", "attributes": attrs});
+        let (status, body) = handle(
+            &Method::Post,
+            "/validate/node?x=1",
+            node.to_string().as_bytes(),
+            None,
+        );
+        assert_eq!(status, 200);
+        assert_eq!(body["version"], 1);
+
+        assert_eq!(handle(&Method::Get, "/validate/node", b"", None).0, 405);
+        assert_eq!(handle(&Method::Post, "/nothing", b"", None).0, 404);
+        assert_eq!(handle(&Method::Post, "/validate/node", b"{", None).0, 400);
+        assert_eq!(
+            handle(&Method::Post, "/validate/attributes", b"[]", None).0,
+            422
+        );
+    }
+}
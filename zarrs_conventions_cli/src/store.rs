@@ -0,0 +1,419 @@
+//! Zarr v3 hierarchies on the local filesystem or, with the `object_store` feature, in object stores.
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Deserializer, de};
+use serde_json::{Value, value::RawValue};
+use zarrs_conventions::{Attributes, Convention};
+
+use crate::error::{Error, Result};
+
+/// Name of the metadata document in each node's directory.
+pub const METADATA_FILE: &str = "zarr.json";
+
+/// A node's metadata document.
+#[derive(Debug, Clone)]
+pub struct Node {
+    /// Path of the node within the opened hierarchy, starting with `/`.
+    pub path: String,
+    /// Location of the metadata document.
+    pub file: PathBuf,
+    /// Full metadata document.
+    pub metadata: Value,
+    /// Top-level members of the document as read, to be written back unchanged.
+    members: Option<Members>,
+    /// Where the document lives, if the hierarchy was opened from an object store URL.
+    #[cfg(feature = "object_store")]
+    pub remote: Option<crate::remote::Location>,
+}
+
+impl Node {
+    /// Read the node whose metadata document is in the given directory.
+    ///
+    /// The directory may be given as a URL of an object store prefix,
+    /// if the `object_store` feature is enabled.
+    pub fn read(dir: &Path, path: String) -> Result<Self> {
+        if is_url(dir) {
+            #[cfg(feature = "object_store")]
+            return crate::remote::Hierarchy::parse(&dir.to_string_lossy())?.read(path);
+            #[cfg(not(feature = "object_store"))]
+            return Err(Error::Url(dir.display().to_string()));
+        }
+        let file = dir.join(METADATA_FILE);
+        let bytes = std::fs::read(&file).map_err(|source| Error::Io {
+            path: file.clone(),
+            source,
+        })?;
+        Self::parse(path, file, &bytes)
+    }
+
+    /// Parse the node's metadata document.
+    pub fn parse(path: String, file: PathBuf, bytes: &[u8]) -> Result<Self> {
+        let metadata = serde_json::from_slice(bytes).map_err(|source| Error::Json {
+            path: file.clone(),
+            source,
+        })?;
+        Ok(Self {
+            path,
+            file,
+            metadata,
+            members: serde_json::from_slice(bytes).ok(),
+            #[cfg(feature = "object_store")]
+            remote: None,
+        })
+    }
+
+    /// `"group"`, `"array"`, or whatever else the document claims.
+    pub fn node_type(&self) -> &str {
+        self.metadata
+            .get("node_type")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown")
+    }
+
+    /// The node's attributes; empty if absent.
+    pub fn attributes(&self) -> Attributes {
+        match self.metadata.get("attributes") {
+            Some(Value::Object(attrs)) => attrs.clone(),
+            _ => Attributes::default(),
+        }
+    }
+
+    /// Replace the node's attributes (in memory; see [Node::save]).
+    pub fn set_attributes(&mut self, attributes: Attributes) {
+        self.metadata["attributes"] = Value::Object(attributes);
+    }
+
+    /// The conventions declared in the node's attributes.
+    pub fn declared(&self) -> Result<Vec<Convention>> {
+        match self
+            .metadata
+            .get("attributes")
+            .and_then(|a| a.get("zarr_conventions"))
+        {
+            Some(zc) => Vec::<Convention>::deserialize(zc).map_err(|source| Error::Json {
+                path: self.file.clone(),
+                source,
+            }),
+            None => Ok(Vec::default()),
+        }
+    }
+
+    /// The metadata document to write back.
+    ///
+    /// Other top-level members keep their order and formatting,
+    /// as do the attributes which are unchanged; new attributes are appended.
+    fn render(&self) -> String {
+        let (Some(members), Value::Object(metadata)) = (&self.members, &self.metadata) else {
+            let mut out = pretty(&self.metadata);
+            out.push('\n');
+            return out;
+        };
+
+        // of duplicate members, serde_json reads the last: only that one is replaced
+        let last = members.0.iter().rposition(|(key, _)| key == "attributes");
+        let attributes = metadata.get("attributes").map(|attrs| {
+            let original = last.and_then(|idx| serde_json::from_str(members.0[idx].1.get()).ok());
+            render_attributes(attrs, original.as_ref())
+        });
+
+        let mut entries = Vec::default();
+        for (idx, (key, raw)) in members.0.iter().enumerate() {
+            match (Some(idx) == last, &attributes) {
+                (true, Some(attrs)) => entries.push(entry(1, key, attrs)),
+                (true, None) => (),
+                (false, _) => entries.push(entry(1, key, raw.get())),
+            }
+        }
+        if let (Some(attrs), None) = (&attributes, last) {
+            entries.push(entry(1, "attributes", attrs));
+        }
+        format!("{{\n{}\n}}\n", entries.join(",\n"))
+    }
+
+    /// Write the metadata document back to where it was read from.
+    ///
+    /// Writes to a temporary file which is then renamed over the original,
+    /// so that the document is never left half-written.
+    pub fn save(&self) -> Result<()> {
+        let contents = self.render();
+        #[cfg(feature = "object_store")]
+        if let Some(remote) = &self.remote {
+            return remote.save(contents, &self.file);
+        }
+        let tmp = self.file.with_extension("json.tmp");
+        let io_err = |source| Error::Io {
+            path: self.file.clone(),
+            source,
+        };
+        std::fs::write(&tmp, contents).map_err(io_err)?;
+        std::fs::rename(&tmp, &self.file).map_err(io_err)
+    }
+}
+
+fn pretty(value: &Value) -> String {
+    serde_json::to_string_pretty(value).expect("JSON should serialize")
+}
+
+/// An object member, at the given depth of indentation.
+fn entry(depth: usize, key: &str, value: &str) -> String {
+    let key = serde_json::to_string(key).expect("JSON should serialize");
+    format!("{:1$}{key}: {value}", "", depth * 2)
+}
+
+/// The attributes object, keeping the order and formatting of the `original` members which are unchanged.
+fn render_attributes(attributes: &Value, original: Option<&Members>) -> String {
+    let (Value::Object(attributes), Some(original)) = (attributes, original) else {
+        return pretty(attributes).replace('\n', "\n  ");
+    };
+    let value = |v: &Value| pretty(v).replace('\n', "\n    ");
+
+    // of duplicate keys, the last was read
+    let positions: BTreeMap<&str, usize> = original
+        .0
+        .iter()
+        .enumerate()
+        .map(|(idx, (key, _))| (key.as_str(), idx))
+        .collect();
+    let mut entries = Vec::default();
+    for (idx, (key, raw)) in original.0.iter().enumerate() {
+        let Some(new) = attributes
+            .get(key)
+            .filter(|_| positions[key.as_str()] == idx)
+        else {
+            continue;
+        };
+        if serde_json::from_str::<Value>(raw.get()).is_ok_and(|old| &old == new) {
+            entries.push(entry(2, key, raw.get()));
+        } else {
+            entries.push(entry(2, key, &value(new)));
+        }
+    }
+    for (key, new) in attributes {
+        if !positions.contains_key(key.as_str()) {
+            entries.push(entry(2, key, &value(new)));
+        }
+    }
+    if entries.is_empty() {
+        return "{}".into();
+    }
+    format!("{{\n{}\n  }}", entries.join(",\n"))
+}
+
+/// Top-level members of a metadata document, in their original order and formatting.
+#[derive(Debug, Clone)]
+struct Members(Vec<(String, Box<RawValue>)>);
+
+impl<'de> Deserialize<'de> for Members {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = Members;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("a JSON object")
+            }
+
+            fn visit_map<A: de::MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> std::result::Result<Members, A::Error> {
+                let mut members = Vec::default();
+                while let Some(member) = map.next_entry()? {
+                    members.push(member);
+                }
+                Ok(Members(members))
+            }
+        }
+
+        deserializer.deserialize_map(Visitor)
+    }
+}
+
+/// Whether the path looks like a URL (`scheme://...`) rather than a local path.
+fn is_url(path: &Path) -> bool {
+    path.to_str()
+        .and_then(|p| p.split_once("://"))
+        .is_some_and(|(scheme, _)| {
+            scheme.len() > 1
+                && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+        })
+}
+
+/// Read the node at the given directory and, if `recursive`, all of its descendants.
+///
+/// Nodes are returned in depth-first order, with children sorted by name.
+pub fn open(root: &Path, recursive: bool) -> Result<Vec<Node>> {
+    if is_url(root) {
+        #[cfg(feature = "object_store")]
+        return crate::remote::Hierarchy::parse(&root.to_string_lossy())?.open(recursive);
+        #[cfg(not(feature = "object_store"))]
+        return Err(Error::Url(root.display().to_string()));
+    }
+    let mut out = Vec::default();
+    walk(root, "/".to_string(), recursive, &mut out)?;
+    Ok(out)
+}
+
+fn walk(dir: &Path, path: String, recursive: bool, out: &mut Vec<Node>) -> Result<()> {
+    let node = Node::read(dir, path)?;
+    let is_group = node.node_type() == "group";
+    let path = node.path.clone();
+    out.push(node);
+    if !(recursive && is_group) {
+        return Ok(());
+    }
+
+    let io_err = |source| Error::Io {
+        path: dir.to_path_buf(),
+        source,
+    };
+    let mut children = Vec::default();
+    for entry in std::fs::read_dir(dir).map_err(io_err)? {
+        let entry = entry.map_err(io_err)?;
+        if entry.path().join(METADATA_FILE).is_file() {
+            children.push(entry.file_name().to_string_lossy().into_owned());
+        }
+    }
+    children.sort();
+    for name in children {
+        let child_path = if path == "/" {
+            format!("/{name}")
+        } else {
+            format!("{path}/{name}")
+        };
+        walk(&dir.join(&name), child_path, recursive, out)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::path::Path;
+
+    use serde_json::{Value, json};
+
+    use super::{Node, is_url, open};
+
+    /// Write a metadata document for a node.
+    pub(crate) fn write_node(root: &Path, path: &str, node_type: &str, attributes: Value) {
+        let dir = root.join(path.trim_start_matches('/'));
+        std::fs::create_dir_all(&dir).unwrap();
+        let doc = json!({"zarr_format": 3, "node_type": node_type, "attributes": attributes});
+        std::fs::write(dir.join("zarr.json"), doc.to_string()).unwrap();
+    }
+
+    #[test]
+    fn open_recursive() {
+        let tmp = tempfile::tempdir().unwrap();
+        write_node(tmp.path(), "", "group", json!({}));
+        write_node(tmp.path(), "b", "array", json!({}));
+        write_node(tmp.path(), "a", "group", json!({"x": 1}));
+        write_node(tmp.path(), "a/c", "array", json!({}));
+        std::fs::create_dir_all(tmp.path().join("b/c/0")).unwrap();
+
+        let paths: Vec<_> = open(tmp.path(), true)
+            .unwrap()
+            .into_iter()
+            .map(|n| n.path)
+            .collect();
+        assert_eq!(paths, ["/", "/a", "/a/c", "/b"]);
+
+        let nodes = open(&tmp.path().join("a"), false).unwrap();
+        assert_eq!(nodes.len(), 1);
+        assert_eq!(nodes[0].attributes()["x"], 1);
+    }
+
+    #[test]
+    fn save_keeps_other_members() {
+        let tmp = tempfile::tempdir().unwrap();
+        let doc = r#"{"zarr_format": 3, "node_type": "array", "shape": [2,  3],
+            "attributes": {"b": 1, "a": [2,  2], "d": 4}, "fill_value": 0}"#;
+        std::fs::write(tmp.path().join("zarr.json"), doc).unwrap();
+
+        let mut node = Node::read(tmp.path(), "/".into()).unwrap();
+        let mut attrs = node.attributes();
+        attrs.insert("c".into(), json!([3]));
+        attrs.insert("d".into(), json!(5));
+        node.set_attributes(attrs);
+        node.save().unwrap();
+        let saved = std::fs::read_to_string(tmp.path().join("zarr.json")).unwrap();
+        // existing attributes keep their order and formatting; new ones are appended
+        assert_eq!(
+            saved,
+            r#"{
+  "zarr_format": 3,
+  "node_type": "array",
+  "shape": [2,  3],
+  "attributes": {
+    "b": 1,
+    "a": [2,  2],
+    "d": 5,
+    "c": [
+      3
+    ]
+  },
+  "fill_value": 0
+}
+"#
+        );
+
+        std::fs::write(tmp.path().join("zarr.json"), r#"{"node_type":"group"}"#).unwrap();
+        let mut node = Node::read(tmp.path(), "/".into()).unwrap();
+        node.set_attributes(json!({"x": 1}).as_object().unwrap().clone());
+        node.save().unwrap();
+        let saved = std::fs::read_to_string(tmp.path().join("zarr.json")).unwrap();
+        assert_eq!(
+            saved,
+            "{\n  \"node_type\": \"group\",\n  \"attributes\": {\n    \"x\": 1\n  }\n}\n"
+        );
+    }
+
+    #[test]
+    fn save_replaces_last_duplicate_attributes() {
+        let tmp = tempfile::tempdir().unwrap();
+        let doc = r#"{"attributes": {"a": 1}, "node_type": "group", "attributes": {"b": 2}}"#;
+        std::fs::write(tmp.path().join("zarr.json"), doc).unwrap();
+
+        let mut node = Node::read(tmp.path(), "/".into()).unwrap();
+        assert_eq!(node.attributes(), *json!({"b": 2}).as_object().unwrap());
+        node.set_attributes(json!({"c": 3}).as_object().unwrap().clone());
+        node.save().unwrap();
+        let saved = std::fs::read_to_string(tmp.path().join("zarr.json")).unwrap();
+        assert_eq!(
+            saved,
+            r#"{
+  "attributes": {"a": 1},
+  "node_type": "group",
+  "attributes": {
+    "c": 3
+  }
+}
+"#
+        );
+        let node = Node::read(tmp.path(), "/".into()).unwrap();
+        assert_eq!(node.attributes(), *json!({"c": 3}).as_object().unwrap());
+    }
+
+    #[cfg(not(feature = "object_store"))]
+    #[test]
+    fn reject_urls() {
+        use crate::error::Error;
+
+        let err = Node::read(Path::new("s3://bucket/data.zarr"), "/".into()).unwrap_err();
+        assert!(matches!(err, Error::Url(url) if url == "s3://bucket/data.zarr"));
+    }
+
+    #[test]
+    fn detect_urls() {
+        assert!(is_url(Path::new("s3://bucket/data.zarr")));
+        assert!(!is_url(Path::new("data/with://in/name")));
+        assert!(!is_url(Path::new("C://data.zarr")));
+    }
+}
@@ -0,0 +1,264 @@
+//! Remove a convention's payload and declaration from nodes.
+use std::{io::Write, path::PathBuf, process::ExitCode};
+
+use serde::Deserialize;
+use serde_json::Value;
+use zarrs_conventions::{Attributes, Convention};
+
+use crate::{
+    error::{Error, Result},
+    known,
+    output::{Format, Modified, ModifiedNode, write_json},
+    selector::Selector,
+    store,
+};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Directory of a Zarr node (containing `zarr.json`).
+    pub path: PathBuf,
+    /// Name, UUID, schema URL, or specification URL of the convention to remove.
+    #[arg(long)]
+    pub convention: Selector,
+    /// Key of the convention's nested payload, if not compiled into this tool.
+    #[arg(long)]
+    pub key: Option<String>,
+    /// Prefix of the convention's prefixed payload, if not compiled into this tool.
+    #[arg(long)]
+    pub prefix: Option<String>,
+    /// Remove only the declaration when the payload's location is not known,
+    /// leaving any payload behind.
+    #[arg(long)]
+    pub declaration_only: bool,
+    /// Also strip all descendants of the node.
+    #[arg(short, long)]
+    pub recursive: bool,
+    /// Report what would be removed without writing anything.
+    #[arg(long)]
+    pub dry_run: bool,
+    #[arg(long, value_enum, default_value_t)]
+    pub format: Format,
+}
+
+/// Remove the declaration and payload from the attributes,
+/// returning the removed keys (`zarr_conventions` if a declaration was removed).
+pub fn strip(
+    attributes: &mut Attributes,
+    selector: &Selector,
+    key: Option<&str>,
+    prefix: Option<&str>,
+) -> Vec<String> {
+    let mut removed = Vec::default();
+    if let Some(Value::Array(declared)) = attributes.get_mut("zarr_conventions") {
+        let before = declared.len();
+        declared.retain(|d| !Convention::deserialize(d).is_ok_and(|c| selector.matches(&c)));
+        if declared.len() != before {
+            removed.push("zarr_conventions".to_string());
+        }
+        if declared.is_empty() {
+            attributes.remove("zarr_conventions");
+        }
+    }
+    if let Some(key) = key
+        && attributes.remove(key).is_some()
+    {
+        removed.push(key.to_string());
+    }
+    if let Some(prefix) = prefix {
+        let keys: Vec<_> = attributes
+            .keys()
+            .filter(|k| k.starts_with(prefix))
+            .cloned()
+            .collect();
+        for k in keys {
+            attributes.remove(&k);
+            removed.push(k);
+        }
+    }
+    removed
+}
+
+pub fn run(args: Args, out: &mut impl Write) -> Result<ExitCode> {
+    let known = args
+        .convention
+        .definition
+        .and_then(|def| known::find(def.uuid));
+    let key = args.key.as_deref().or(known.and_then(|k| k.key));
+    if key.is_none() && args.prefix.is_none() && !args.declaration_only {
+        return Err(Error::Other(
+            "the convention's payload location is not known; pass --key or --prefix, \
+             or --declaration-only to leave any payload behind"
+                .to_string(),
+        ));
+    }
+    let verb = if args.dry_run {
+        "would remove"
+    } else {
+        "removed"
+    };
+
+    let mut modified = Modified::new(args.dry_run);
+    for mut node in store::open(&args.path, args.recursive)? {
+        let mut attributes = node.attributes();
+        let removed = strip(
+            &mut attributes,
+            &args.convention,
+            key,
+            args.prefix.as_deref(),
+        );
+        if removed.is_empty() {
+            continue;
+        }
+        if args.format == Format::Text {
+            writeln!(out, "{}: {verb} {}", node.path, removed.join(", "))?;
+        }
+        if !args.dry_run {
+            node.set_attributes(attributes.clone());
+            node.save()?;
+        }
+        modified.nodes.push(ModifiedNode {
+            path: node.path,
+            removed,
+            attributes,
+        });
+    }
+    if args.format == Format::Json {
+        write_json(out, &modified)?;
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+    use serde_json::json;
+
+    use super::run;
+    use crate::{
+        Cli, Command,
+        store::{Node, tests::write_node},
+    };
+
+    #[cfg(feature = "license")]
+    #[test]
+    fn strip_license() {
+        let tmp = tempfile::tempdir().unwrap();
+        let attrs = json!({
+            "zarr_conventions": [
+                {"spec_url": "https://github.com/clbarnes/zarr-convention-license/blob/v1/README.md"},
+                {"uuid": "f0000000-0000-0000-0000-000000000000"}
+            ],
+            "license": {"spdx": "MIT"},
+            "keep": 1
+        });
+        write_node(tmp.path(), "", "group", attrs.clone());
+        write_node(tmp.path(), "a", "array", attrs.clone());
+        let args = |dry_run| super::Args {
+            path: tmp.path().to_path_buf(),
+            convention: "license".parse().unwrap(),
+            key: None,
+            prefix: None,
+            declaration_only: false,
+            recursive: true,
+            dry_run,
+            format: Default::default(),
+        };
+
+        let mut out = Vec::default();
+        run(args(true), &mut out).unwrap();
+        assert!(
+            String::from_utf8(out)
+                .unwrap()
+                .contains("/a: would remove zarr_conventions, license")
+        );
+        assert_eq!(
+            Node::read(tmp.path(), "/".into()).unwrap().attributes()["license"]["spdx"],
+            "MIT"
+        );
+
+        run(args(false), &mut Vec::default()).unwrap();
+        let stripped = Node::read(&tmp.path().join("a"), "/a".into())
+            .unwrap()
+            .attributes();
+        assert_eq!(
+            serde_json::Value::Object(stripped),
+            json!({
+                "zarr_conventions": [{"uuid": "f0000000-0000-0000-0000-000000000000"}],
+                "keep": 1
+            })
+        );
+    }
+
+    /// Provisional conventions are not self-registered, so must resolve when parsing the command line.
+    #[cfg(feature = "authors")]
+    #[test]
+    fn strip_provisional() {
+        use zarrs_conventions::ZarrConventionImpl;
+        use zarrs_conventions_authors::Authors;
+
+        let uuid = Authors::DEFINITION.uuid.to_string();
+        for selector in ["authors", uuid.as_str()] {
+            let tmp = tempfile::tempdir().unwrap();
+            write_node(
+                tmp.path(),
+                "",
+                "group",
+                json!({
+                    "zarr_conventions": [{"uuid": uuid, "name": "authors"}],
+                    "authors": [{"name": "A. Person"}],
+                    "keep": 1
+                }),
+            );
+            let path = tmp.path().to_str().unwrap();
+            let cli =
+                Cli::try_parse_from(["zarrs-conventions", "strip", path, "--convention", selector])
+                    .unwrap();
+            let Command::Strip(args) = cli.command else {
+                panic!("expected strip subcommand");
+            };
+            let mut out = Vec::default();
+            run(args, &mut out).unwrap();
+            assert_eq!(
+                String::from_utf8(out).unwrap(),
+                "/: removed zarr_conventions, authors\n",
+                "{selector}"
+            );
+            let attrs = Node::read(tmp.path(), "/".into()).unwrap().attributes();
+            assert_eq!(serde_json::Value::Object(attrs), json!({"keep": 1}));
+        }
+    }
+
+    #[test]
+    fn strip_unknown_payload() {
+        let tmp = tempfile::tempdir().unwrap();
+        let attrs = json!({
+            "zarr_conventions": [{"uuid": "f0000000-0000-0000-0000-000000000000"}],
+            "payload": 1
+        });
+        write_node(tmp.path(), "", "group", attrs.clone());
+        let path = tmp.path().to_str().unwrap();
+        let strip = |extra: &[&str]| {
+            let mut argv = vec![
+                "zarrs-conventions",
+                "strip",
+                path,
+                "--convention",
+                "f0000000-0000-0000-0000-000000000000",
+            ];
+            argv.extend(extra);
+            let Command::Strip(args) = Cli::try_parse_from(argv).unwrap().command else {
+                panic!("expected strip subcommand");
+            };
+            run(args, &mut Vec::default())
+        };
+
+        let err = strip(&[]).unwrap_err().to_string();
+        assert!(err.contains("--declaration-only"), "{err}");
+        let unchanged = Node::read(tmp.path(), "/".into()).unwrap().attributes();
+        assert_eq!(serde_json::Value::Object(unchanged), attrs);
+
+        strip(&["--declaration-only"]).unwrap();
+        let stripped = Node::read(tmp.path(), "/".into()).unwrap().attributes();
+        assert_eq!(serde_json::Value::Object(stripped), json!({"payload": 1}));
+    }
+}
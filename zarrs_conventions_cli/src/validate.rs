@@ -0,0 +1,301 @@
+//! Check nodes' conventional metadata for problems.
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    sync::OnceLock,
+};
+
+use jsonschema::Validator;
+use serde_json::Value;
+use zarrs_conventions::{
+    Attributes, DEFAULT_ZARR_CONVENTION_REGISTRY,
+    uuid::Uuid,
+    validate::{Issue, NodeReport, Severity, ValidationReport, check_attributes, declarations},
+};
+
+use crate::{
+    error::Result,
+    known,
+    output::{Format, write_json},
+    store::{self, Node},
+};
+
+/// Exit status is 0 if no errors were found, 1 if any were,
+/// and 2 if validation could not be completed.
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Directory of a Zarr node (containing `zarr.json`); all descendants are also checked.
+    pub path: PathBuf,
+    /// Directory of JSON schemas named `<convention name>.json`,
+    /// against which the attributes of nodes using that convention are validated
+    /// in place of any schema bundled with the convention.
+    ///
+    /// Without a schema here, the license, proj, thumbnails, and uom conventions,
+    /// which do not bundle schemas, are only checked by parsing their payloads.
+    #[arg(long)]
+    pub schema_dir: Option<PathBuf>,
+    /// Also exit with status 1 if any warnings were found.
+    #[arg(long)]
+    pub deny_warnings: bool,
+    #[arg(long, value_enum, default_value_t)]
+    pub format: Format,
+}
+
+/// Run all checks on a node's attributes,
+/// including against each declared convention's schema in the given directory if any,
+/// or else the schema bundled with the convention.
+pub fn check(attrs: &Attributes, schema_dir: Option<&Path>) -> Vec<Issue> {
+    let mut issues = check_attributes(attrs, &known::known());
+    for conv in declarations(attrs).unwrap_or_default() {
+        let Some(def) = DEFAULT_ZARR_CONVENTION_REGISTRY.get(conv.id_ref()) else {
+            continue;
+        };
+        let path = schema_dir
+            .map(|dir| dir.join(format!("{}.json", def.name)))
+            .filter(|path| path.is_file());
+        if let Some(path) = path {
+            check_schema(&path, attrs, &mut issues);
+        } else if let Some(schema) = bundled_schema(def.uuid) {
+            let source = format!("bundled {} schema", def.name);
+            check_against(schema, &source, attrs, &mut issues);
+        }
+    }
+    issues
+}
+
+/// Validator for the schema bundled with a compiled-in convention, if it has one.
+fn bundled_schema(uuid: Uuid) -> Option<&'static Validator> {
+    static SCHEMAS: OnceLock<Vec<(Uuid, Validator)>> = OnceLock::new();
+    SCHEMAS
+        .get_or_init(|| {
+            known::schemas()
+                .into_iter()
+                .map(|docs| {
+                    let validator = jsonschema::validator_for(&docs.schema())
+                        .expect("bundled schemas should be valid");
+                    (docs.definition().uuid, validator)
+                })
+                .collect()
+        })
+        .iter()
+        .find(|(u, _)| *u == uuid)
+        .map(|(_, validator)| validator)
+}
+
+/// Run all checks on a single node.
+pub fn check_node(node: &Node, schema_dir: Option<&Path>) -> Vec<Issue> {
+    check(&node.attributes(), schema_dir)
+}
+
+fn check_schema(path: &Path, attrs: &Attributes, issues: &mut Vec<Issue>) {
+    let schema = match std::fs::read(path)
+        .map_err(|e| e.to_string())
+        .and_then(|b| serde_json::from_slice::<Value>(&b).map_err(|e| e.to_string()))
+        .and_then(|s| jsonschema::validator_for(&s).map_err(|e| e.to_string()))
+    {
+        Ok(v) => v,
+        Err(e) => {
+            issues.push(Issue::error(
+                "invalid-schema",
+                format!("could not load {}: {e}", path.display()),
+            ));
+            return;
+        }
+    };
+    check_against(&schema, &path.display().to_string(), attrs, issues);
+}
+
+/// Report the ways the attributes fail the schema, naming the schema's source.
+fn check_against(schema: &Validator, source: &str, attrs: &Attributes, issues: &mut Vec<Issue>) {
+    let instance = Value::Object(attrs.clone());
+    for e in schema.iter_errors(&instance) {
+        issues.push(Issue::error(
+            "schema",
+            format!("{e} at '{}' ({source})", e.instance_path()),
+        ));
+    }
+}
+
+/// Check all nodes at and below the given path.
+pub fn validate(path: &Path, schema_dir: Option<&Path>) -> Result<ValidationReport> {
+    let nodes = store::open(path, true)?
+        .iter()
+        .map(|node| NodeReport {
+            path: node.path.clone(),
+            issues: check_node(node, schema_dir),
+        })
+        .collect();
+    Ok(ValidationReport {
+        nodes,
+        ..Default::default()
+    })
+}
+
+pub fn run(args: Args, out: &mut impl Write) -> Result<ExitCode> {
+    let report = validate(&args.path, args.schema_dir.as_deref())?;
+    match args.format {
+        Format::Json => {
+            write_json(out, &report)?;
+        }
+        Format::Text => {
+            for node in &report.nodes {
+                for issue in &node.issues {
+                    let severity = match issue.severity {
+                        Severity::Info => "info",
+                        Severity::Warning => "warning",
+                        Severity::Error => "error",
+                    };
+                    writeln!(
+                        out,
+                        "{}: {severity}[{}]: {}",
+                        node.path, issue.code, issue.message
+                    )?;
+                }
+            }
+            writeln!(
+                out,
+                "{} nodes checked: {} errors, {} warnings",
+                report.nodes.len(),
+                report.count(Severity::Error),
+                report.count(Severity::Warning)
+            )?;
+        }
+    }
+    let failed = report.count(Severity::Error) > 0
+        || (args.deny_warnings && report.count(Severity::Warning) > 0);
+    Ok(if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
+
+#[cfg(all(test, any(feature = "license", feature = "authors")))]
+mod tests {
+    use serde_json::json;
+
+    use zarrs_conventions::validate::ValidationReport;
+
+    use super::validate;
+    use crate::store::tests::write_node;
+
+    #[cfg(feature = "license")]
+    const LICENSE_UUID: &str = "b77365e5-2b0c-4141-b917-c03b7c68e935";
+
+    fn codes(report: &ValidationReport, path: &str) -> Vec<&'static str> {
+        report
+            .nodes
+            .iter()
+            .find(|n| n.path == path)
+            .unwrap()
+            .issues
+            .iter()
+            .map(|i| i.code)
+            .collect()
+    }
+
+    #[cfg(feature = "license")]
+    #[test]
+    fn validate_checks() {
+        use zarrs_conventions::validate::Severity;
+
+        let tmp = tempfile::tempdir().unwrap();
+        write_node(
+            tmp.path(),
+            "",
+            "group",
+            json!({"zarr_conventions": [{"uuid": LICENSE_UUID}], "license": {"spdx": "MIT"}}),
+        );
+        write_node(
+            tmp.path(),
+            "undeclared",
+            "group",
+            json!({"license": {"spdx": "MIT"}}),
+        );
+        write_node(
+            tmp.path(),
+            "invalid",
+            "group",
+            json!({"zarr_conventions": [{"uuid": LICENSE_UUID}], "license": {}}),
+        );
+        write_node(
+            tmp.path(),
+            "lints",
+            "group",
+            json!({"zarr_conventions": [
+                {"spec_url": "https://example.com/nothing"},
+                {"spec_url": "https://example.com/nothing"},
+            ]}),
+        );
+
+        let report = validate(tmp.path(), None).unwrap();
+        assert!(codes(&report, "/").is_empty());
+        assert_eq!(codes(&report, "/undeclared"), ["undeclared-payload"]);
+        assert_eq!(codes(&report, "/invalid"), ["invalid-payload"]);
+        assert_eq!(
+            codes(&report, "/lints"),
+            [
+                "declaration-without-uuid",
+                "unknown-convention",
+                "duplicate-declaration",
+                "declaration-without-uuid",
+                "unknown-convention"
+            ]
+        );
+        assert_eq!(report.count(Severity::Error), 1);
+    }
+
+    #[cfg(feature = "license")]
+    #[test]
+    fn validate_schema() {
+        let tmp = tempfile::tempdir().unwrap();
+        let schemas = tmp.path().join("schemas");
+        std::fs::create_dir_all(&schemas).unwrap();
+        std::fs::write(
+            schemas.join("license.json"),
+            json!({"required": ["license"], "properties": {"license": {"required": ["url"]}}})
+                .to_string(),
+        )
+        .unwrap();
+        let store = tmp.path().join("store");
+        write_node(
+            &store,
+            "",
+            "group",
+            json!({"zarr_conventions": [{"uuid": LICENSE_UUID}], "license": {"spdx": "MIT"}}),
+        );
+        let report = validate(&store, Some(&schemas)).unwrap();
+        assert_eq!(codes(&report, "/"), ["schema"]);
+    }
+
+    #[cfg(feature = "authors")]
+    #[test]
+    fn validate_bundled_schema() {
+        use zarrs_conventions::ZarrConventionImpl;
+        use zarrs_conventions_authors::Authors;
+
+        crate::known::ensure_registered();
+        let tmp = tempfile::tempdir().unwrap();
+        let store = tmp.path().join("store");
+        write_node(
+            &store,
+            "",
+            "group",
+            json!({
+                "zarr_conventions": [{"uuid": Authors::DEFINITION.uuid}],
+                "authors": {"creators": "someone"},
+            }),
+        );
+        let report = validate(&store, None).unwrap();
+        assert_eq!(codes(&report, "/"), ["invalid-payload", "schema"]);
+        let message = &report.nodes[0].issues[1].message;
+        assert!(message.contains("bundled authors schema"), "{message}");
+
+        let schemas = tmp.path().join("schemas");
+        std::fs::create_dir_all(&schemas).unwrap();
+        std::fs::write(schemas.join("authors.json"), "{}").unwrap();
+        let report = validate(&store, Some(&schemas)).unwrap();
+        assert_eq!(codes(&report, "/"), ["invalid-payload"]);
+    }
+}
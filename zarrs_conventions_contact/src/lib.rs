@@ -0,0 +1,349 @@
+#![doc = include_str!("../README.md")]
+use serde::{Deserialize, Serialize};
+pub use zarrs_conventions;
+use zarrs_conventions::{
+    define_convention,
+    iref::{Uri, UriBuf},
+    validate::{Issue, Lint},
+};
+
+pub mod resolve;
+
+/// What a contact is responsible for, after the ISO 19115 role codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum Role {
+    /// Maintains the data, and can be told about problems with it.
+    Maintainer,
+    /// Answers questions about the data.
+    PointOfContact,
+    /// Is accountable for the care of the data.
+    Custodian,
+    Owner,
+    Publisher,
+    Distributor,
+    Other,
+}
+
+/// Why a contact is invalid.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum ContactError {
+    #[error("a contact needs an email address or URL")]
+    Unreachable,
+    #[error("'{0}' is not an email address")]
+    Email(String),
+    #[error("contact URL '{0}' must be an http(s) URL with a host")]
+    Url(String),
+}
+
+/// Check the shape of an email address: a local part and a domain with at least one dot.
+///
+/// This does not check that the address exists.
+fn check_email(email: &str) -> Result<(), ContactError> {
+    let valid = match email.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.contains('@')
+                && domain.split('.').count() >= 2
+                && domain.split('.').all(|label| {
+                    !label.is_empty()
+                        && !label.starts_with('-')
+                        && !label.ends_with('-')
+                        && label.chars().all(|c| c.is_alphanumeric() || c == '-')
+                })
+                && !email.chars().any(|c| c.is_whitespace() || c.is_control())
+        }
+        None => false,
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(ContactError::Email(email.to_string()))
+    }
+}
+
+fn check_url(url: &Uri) -> Result<(), ContactError> {
+    let valid = url
+        .as_str()
+        .split_once("://")
+        .is_some_and(|(scheme, rest)| {
+            ["http", "https"]
+                .iter()
+                .any(|s| s.eq_ignore_ascii_case(scheme))
+                && !rest.starts_with(['/', '?', '#'])
+                && !rest.is_empty()
+        });
+    if valid {
+        Ok(())
+    } else {
+        Err(ContactError::Url(url.to_string()))
+    }
+}
+
+/// A person, team, or organisation to contact about the data.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Contact {
+    /// Name of the person, team, or organisation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    /// Email address; either this or `url` is required.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schema", schemars(extend("format" = "email")))]
+    email: Option<String>,
+    /// http(s) URL of a web page through which to make contact, e.g. an issue tracker.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<String>", extend("format" = "uri")))]
+    url: Option<UriBuf>,
+    /// What the contact is responsible for.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    role: Option<Role>,
+}
+
+impl Contact {
+    /// A contact, which needs an email address or URL.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Set the email address, which is checked when building [Contacts].
+    pub fn with_email(mut self, email: impl Into<String>) -> Self {
+        self.email = Some(email.into());
+        self
+    }
+
+    /// Set a web page through which to make contact, e.g. an issue tracker.
+    pub fn with_url(mut self, url: UriBuf) -> Self {
+        self.url = Some(url);
+        self
+    }
+
+    pub fn with_role(mut self, role: Role) -> Self {
+        self.role = Some(role);
+        self
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn email(&self) -> Option<&str> {
+        self.email.as_deref()
+    }
+
+    pub fn url(&self) -> Option<&Uri> {
+        self.url.as_deref()
+    }
+
+    pub fn role(&self) -> Option<Role> {
+        self.role
+    }
+
+    /// Check that the contact can be reached, and that its email address and URL are well-formed.
+    pub fn validate(&self) -> Result<(), ContactError> {
+        self.problems().into_iter().next().map_or(Ok(()), Err)
+    }
+
+    fn problems(&self) -> Vec<ContactError> {
+        let mut problems = Vec::default();
+        if self.email.is_none() && self.url.is_none() {
+            problems.push(ContactError::Unreachable);
+        }
+        if let Some(Err(e)) = self.email.as_deref().map(check_email) {
+            problems.push(e);
+        }
+        if let Some(Err(e)) = self.url.as_deref().map(check_url) {
+            problems.push(e);
+        }
+        problems
+    }
+}
+
+define_convention! {
+    /// People or organisations to contact about a node and, unless they declare their own, its children.
+    #[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+    #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+    #[serde(transparent)]
+    pub struct Contacts(Vec<Contact>);
+    schema_url = "https://raw.githubusercontent.com/clbarnes/zarrs_conventions/refs/tags/contact-v1/zarrs_conventions_contact/spec/schema.json",
+    spec_url = "https://github.com/clbarnes/zarrs_conventions/blob/contact-v1/zarrs_conventions_contact/spec/README.md",
+    name = "contacts",
+    description = "Who to contact about a node and its children",
+    nested = "contacts",
+    register = false,
+}
+
+impl Contacts {
+    pub fn builder() -> Builder {
+        Default::default()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Contact> {
+        self.0.iter()
+    }
+
+    /// Contacts with the given role.
+    pub fn with_role(&self, role: Role) -> impl Iterator<Item = &Contact> {
+        self.0.iter().filter(move |c| c.role == Some(role))
+    }
+}
+
+impl Lint for Contacts {
+    fn lint(&self) -> Vec<Issue> {
+        let mut issues = Vec::default();
+        if self.0.is_empty() {
+            issues.push(Issue::error(
+                "no-contacts",
+                BuildError::NoContacts.to_string(),
+            ));
+        }
+        for (index, contact) in self.0.iter().enumerate() {
+            for e in contact.problems() {
+                let code = match e {
+                    ContactError::Unreachable => "unreachable-contact",
+                    ContactError::Email(_) => "invalid-email",
+                    ContactError::Url(_) => "invalid-url",
+                };
+                issues.push(Issue::error(code, format!("contact {index}: {e}")));
+            }
+        }
+        issues
+    }
+}
+
+/// Why contacts could not be built.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum BuildError {
+    #[error("at least one contact must be given")]
+    NoContacts,
+    #[error("contact {index}: {source}")]
+    Contact { index: usize, source: ContactError },
+}
+
+/// Builder for [Contacts], created by [Contacts::builder].
+#[derive(Debug, Clone, Default)]
+pub struct Builder {
+    inner: Contacts,
+}
+
+impl Builder {
+    pub fn contact(mut self, contact: Contact) -> Self {
+        self.inner.0.push(contact);
+        self
+    }
+
+    /// Build the contacts, checking that there is at least one and that each is valid.
+    pub fn build(self) -> Result<Contacts, BuildError> {
+        if self.inner.0.is_empty() {
+            return Err(BuildError::NoContacts);
+        }
+        for (index, contact) in self.inner.0.iter().enumerate() {
+            contact
+                .validate()
+                .map_err(|source| BuildError::Contact { index, source })?;
+        }
+        Ok(self.inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use zarrs_conventions::validate::Lint;
+
+    use crate::{BuildError, Contact, ContactError, Contacts, Role, check_email};
+
+    #[test]
+    fn build() {
+        let contacts = Contacts::builder()
+            .contact(
+                Contact::new()
+                    .with_email("data@example.org")
+                    .with_name("Data team")
+                    .with_role(Role::Maintainer),
+            )
+            .contact(
+                Contact::new()
+                    .with_url("https://github.com/example/data/issues".parse().unwrap())
+                    .with_role(Role::PointOfContact),
+            )
+            .build()
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(&contacts).unwrap(),
+            json!([
+                {"name": "Data team", "email": "data@example.org", "role": "maintainer"},
+                {"url": "https://github.com/example/data/issues", "role": "point_of_contact"},
+            ])
+        );
+        assert_eq!(contacts.with_role(Role::Maintainer).count(), 1);
+        assert!(contacts.lint().is_empty());
+
+        assert_eq!(Contacts::builder().build(), Err(BuildError::NoContacts));
+        assert_eq!(
+            Contacts::builder()
+                .contact(Contact::new().with_name("Nobody"))
+                .build(),
+            Err(BuildError::Contact {
+                index: 0,
+                source: ContactError::Unreachable
+            })
+        );
+        assert_eq!(
+            Contacts::builder()
+                .contact(Contact::new().with_url("ftp://example.org".parse().unwrap()))
+                .build(),
+            Err(BuildError::Contact {
+                index: 0,
+                source: ContactError::Url("ftp://example.org".into())
+            })
+        );
+    }
+
+    #[test]
+    fn emails() {
+        for valid in [
+            "a@example.org",
+            "first.last+tag@sub.example.co.uk",
+            "ü@bücher.de",
+        ] {
+            assert!(check_email(valid).is_ok(), "{valid}");
+        }
+        for invalid in [
+            "example.org",
+            "@example.org",
+            "a@localhost",
+            "a@b@example.org",
+            "a b@example.org",
+            "a@example..org",
+            "a@-example.org",
+        ] {
+            assert!(check_email(invalid).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn lint() {
+        let contacts: Contacts = serde_json::from_value(json!([
+            {"name": "Nobody"},
+            {"email": "not an email", "url": "https:///path"},
+        ]))
+        .unwrap();
+        let codes: Vec<_> = contacts.lint().into_iter().map(|i| i.code).collect();
+        assert_eq!(
+            codes,
+            ["unreachable-contact", "invalid-email", "invalid-url"]
+        );
+        let empty = Contacts::default();
+        assert_eq!(empty.lint()[0].code, "no-contacts");
+    }
+}
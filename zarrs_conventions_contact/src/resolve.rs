@@ -0,0 +1,83 @@
+//! Contacts inherited through a hierarchy:
+//! the contacts declared by a node apply to all of its descendants which do not declare their own.
+use zarrs_conventions::{Attributes, NestedRepr, NodePath};
+
+use crate::Contacts;
+
+/// Contacts which apply to a node, and where they were declared.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Inherited {
+    /// Absolute path of the node declaring the contacts; may be the node itself.
+    pub declared_by: String,
+    pub contacts: Contacts,
+}
+
+/// The contacts which apply to the node at the absolute path `node`:
+/// its own if it declares any, otherwise those of its nearest ancestor which does.
+///
+/// `lookup` returns the attributes of the node at an absolute path.
+/// Returns `None` if neither the node nor any ancestor declares contacts.
+///
+/// ```
+/// use serde_json::json;
+/// use zarrs_conventions_contact::resolve::effective_contacts;
+///
+/// let inherited = effective_contacts("/group/array", |node| match node {
+///     "/" => json!({"contacts": [{"email": "data@example.org"}]}).as_object().cloned(),
+///     _ => Some(Default::default()),
+/// })
+/// .unwrap()
+/// .unwrap();
+/// assert_eq!(inherited.declared_by, "/");
+/// ```
+pub fn effective_contacts(
+    node: &str,
+    lookup: impl Fn(&str) -> Option<Attributes>,
+) -> Result<Option<Inherited>, String> {
+    let node = NodePath::new(node).map_err(|e| e.to_string())?;
+    for path in node.ancestors() {
+        if let Some(attributes) = lookup(path.as_str())
+            && attributes.contains_key(Contacts::KEY)
+        {
+            let contacts = Contacts::from_attributes_nested(&attributes)
+                .map_err(|e| format!("invalid contacts at {path}: {e}"))?;
+            return Ok(Some(Inherited {
+                declared_by: path.to_string(),
+                contacts,
+            }));
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use zarrs_conventions::Attributes;
+
+    use super::effective_contacts;
+
+    #[test]
+    fn inheritance() {
+        let lookup = |node: &str| -> Option<Attributes> {
+            let attributes = match node {
+                "/" => json!({"contacts": [{"email": "root@example.org"}]}),
+                "/a" => json!({"contacts": [{"email": "a@example.org"}]}),
+                "/bad" => json!({"contacts": {"email": "bad@example.org"}}),
+                _ => json!({}),
+            };
+            attributes.as_object().cloned()
+        };
+        let email = |node| {
+            let inherited = effective_contacts(node, lookup).unwrap().unwrap();
+            let email = inherited.contacts.iter().next().unwrap().email();
+            (inherited.declared_by, email.unwrap().to_string())
+        };
+        assert_eq!(email("/"), ("/".into(), "root@example.org".into()));
+        assert_eq!(email("/b/c"), ("/".into(), "root@example.org".into()));
+        assert_eq!(email("/a/c/"), ("/a".into(), "a@example.org".into()));
+        assert!(effective_contacts("/bad/c", lookup).is_err());
+        assert!(effective_contacts("a", lookup).is_err());
+        assert_eq!(effective_contacts("/a", |_| None), Ok(None));
+    }
+}
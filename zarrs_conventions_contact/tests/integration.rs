@@ -0,0 +1,33 @@
+use serde_json::json;
+use zarrs_conventions::test_util::{parse_example, parse_modified_example, parse_nested};
+use zarrs_conventions_contact::Contacts;
+
+const EXAMPLE: &str = include_str!("examples/maintainers.json");
+
+zarrs_conventions::convention_test_suite! {
+    Contacts,
+    repr = nested,
+    example = parse_example(EXAMPLE, parse_nested),
+    examples = "tests/examples",
+    schema = "spec/schema.json",
+    registered = false,
+}
+
+#[test]
+fn reject_unknown_role() {
+    let parsed = parse_modified_example(
+        EXAMPLE,
+        |attrs| attrs["contacts"][0]["role"] = json!("boss"),
+        parse_nested::<Contacts>,
+    );
+    assert!(parsed.is_err());
+}
+
+#[cfg(feature = "schema")]
+#[test]
+fn spec_up_to_date() {
+    zarrs_conventions::test_util::assert_docs_up_to_date(
+        &zarrs_conventions::docgen::ConventionDocs::nested::<Contacts>(),
+        concat!(env!("CARGO_MANIFEST_DIR"), "/spec"),
+    );
+}
@@ -0,0 +1,110 @@
+//! Colors and colormaps.
+use serde::{Deserialize, Serialize};
+
+/// Why a string is not a color.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("'{0}' is not a hex color, e.g. #ff8000")]
+pub struct ColorError(pub String);
+
+/// An sRGB color as a hex string, e.g. `#ff8000`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(transparent)]
+pub struct Color(String);
+
+impl Color {
+    pub fn new(color: impl Into<String>) -> Result<Self, ColorError> {
+        let color = Self(color.into());
+        color.rgb()?;
+        Ok(color)
+    }
+
+    /// A color which is checked when building [DisplaySettings](crate::DisplaySettings),
+    /// or by [Color::rgb].
+    pub fn new_unchecked(color: impl Into<String>) -> Self {
+        Self(color.into())
+    }
+
+    pub fn from_rgb([r, g, b]: [u8; 3]) -> Self {
+        Self(format!("#{r:02x}{g:02x}{b:02x}"))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Red, green, and blue components.
+    ///
+    /// ```
+    /// use zarrs_conventions_display::Color;
+    ///
+    /// assert_eq!(Color::new("#FF8000").unwrap().rgb(), Ok([255, 128, 0]));
+    /// assert!(Color::new("orange").is_err());
+    /// ```
+    pub fn rgb(&self) -> Result<[u8; 3], ColorError> {
+        let err = || ColorError(self.0.clone());
+        let hex = self.0.strip_prefix('#').ok_or_else(err)?;
+        if hex.len() != 6 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(err());
+        }
+        let component = |i| u8::from_str_radix(&hex[i..i + 2], 16).expect("checked hex digits");
+        Ok([component(0), component(2), component(4)])
+    }
+
+    /// Components scaled to the range 0 to 1.
+    pub(crate) fn rgb_unit(&self) -> Result<[f64; 3], ColorError> {
+        Ok(self.rgb()?.map(|c| c as f64 / 255.0))
+    }
+}
+
+/// Colormap names understood by common viewers.
+pub const KNOWN_COLORMAPS: &[&str] = &[
+    "gray",
+    "viridis",
+    "magma",
+    "inferno",
+    "plasma",
+    "cividis",
+    "turbo",
+    "jet",
+    "cubehelix",
+    "red",
+    "green",
+    "blue",
+    "cyan",
+    "magenta",
+    "yellow",
+];
+
+/// Single-hue colormaps, which map intensity to the given color.
+pub(crate) const HUES: &[(&str, [u8; 3])] = &[
+    ("gray", [255, 255, 255]),
+    ("red", [255, 0, 0]),
+    ("green", [0, 255, 0]),
+    ("blue", [0, 0, 255]),
+    ("cyan", [0, 255, 255]),
+    ("magenta", [255, 0, 255]),
+    ("yellow", [255, 255, 0]),
+];
+
+/// How intensities map to colors.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum Colormap {
+    /// A colormap known by name, preferably one of [KNOWN_COLORMAPS].
+    Named(String),
+    /// A lookup table of at least two colors, evenly spaced from the lowest to the highest intensity
+    /// and interpolated between.
+    Lut(Vec<Color>),
+}
+
+impl Colormap {
+    /// Whether a named colormap is one of [KNOWN_COLORMAPS].
+    pub fn is_known(&self) -> bool {
+        match self {
+            Self::Named(name) => KNOWN_COLORMAPS.contains(&name.as_str()),
+            Self::Lut(_) => true,
+        }
+    }
+}
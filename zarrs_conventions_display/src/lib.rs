@@ -0,0 +1,388 @@
+#![doc = include_str!("../README.md")]
+use serde::{Deserialize, Serialize};
+pub use zarrs_conventions;
+use zarrs_conventions::{
+    define_convention,
+    validate::{Issue, Lint},
+};
+
+mod color;
+pub use color::{Color, ColorError, Colormap, KNOWN_COLORMAPS};
+pub mod napari;
+pub mod neuroglancer;
+
+/// Range of intensities mapped from the lowest to the highest color, for contrast.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Window {
+    /// Intensity shown with the lowest color.
+    pub min: f64,
+    /// Intensity shown with the highest color.
+    pub max: f64,
+}
+
+/// How to display a single channel.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Channel {
+    /// Name of the channel, e.g. `DAPI`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    /// Color in whose shades intensities are shown.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    color: Option<Color>,
+    /// Colormap with which intensities are shown; takes precedence over `color`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    colormap: Option<Colormap>,
+    /// Contrast window.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    window: Option<Window>,
+    /// Opacity, from 0 (transparent) to 1 (opaque).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    opacity: Option<f64>,
+}
+
+impl Channel {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set the name of the channel, e.g. `DAPI`.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Show intensities in shades of a color, which is checked when building [DisplaySettings].
+    pub fn with_color(mut self, color: impl Into<String>) -> Self {
+        self.color = Some(Color::new_unchecked(color));
+        self
+    }
+
+    /// Show intensities with a colormap, which takes precedence over a color.
+    pub fn with_colormap(mut self, colormap: Colormap) -> Self {
+        self.colormap = Some(colormap);
+        self
+    }
+
+    /// Set the contrast window.
+    pub fn with_window(mut self, min: f64, max: f64) -> Self {
+        self.window = Some(Window { min, max });
+        self
+    }
+
+    /// Set the opacity, from 0 (transparent) to 1 (opaque).
+    pub fn with_opacity(mut self, opacity: f64) -> Self {
+        self.opacity = Some(opacity);
+        self
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn color(&self) -> Option<&Color> {
+        self.color.as_ref()
+    }
+
+    pub fn colormap(&self) -> Option<&Colormap> {
+        self.colormap.as_ref()
+    }
+
+    pub fn window(&self) -> Option<Window> {
+        self.window
+    }
+
+    pub fn opacity(&self) -> Option<f64> {
+        self.opacity
+    }
+
+    /// Problems with the channel's settings.
+    fn validate(&self) -> Vec<ChannelError> {
+        let mut errors = Vec::default();
+        if let Some(Err(e)) = self.color.as_ref().map(Color::rgb) {
+            errors.push(ChannelError::Color(e));
+        }
+        match &self.colormap {
+            Some(Colormap::Lut(colors)) if colors.len() < 2 => errors.push(ChannelError::ShortLut),
+            Some(Colormap::Lut(colors)) => errors.extend(
+                colors
+                    .iter()
+                    .filter_map(|c| c.rgb().err())
+                    .map(ChannelError::Color),
+            ),
+            _ => (),
+        }
+        if let Some(Window { min, max }) = self.window
+            && !(min.is_finite() && max.is_finite() && min < max)
+        {
+            errors.push(ChannelError::Window { min, max });
+        }
+        if let Some(opacity) = self.opacity
+            && !(0.0..=1.0).contains(&opacity)
+        {
+            errors.push(ChannelError::Opacity(opacity));
+        }
+        errors
+    }
+}
+
+/// Why a channel's settings are invalid.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[non_exhaustive]
+pub enum ChannelError {
+    #[error(transparent)]
+    Color(#[from] ColorError),
+    #[error("a colormap lookup table needs at least two colors")]
+    ShortLut,
+    #[error("contrast window from {min} to {max} is not a finite, increasing range")]
+    Window { min: f64, max: f64 },
+    #[error("opacity {0} is not between 0 and 1")]
+    Opacity(f64),
+}
+
+impl ChannelError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::Color(_) => "invalid-color",
+            Self::ShortLut => "short-lut",
+            Self::Window { .. } => "invalid-window",
+            Self::Opacity(_) => "invalid-opacity",
+        }
+    }
+}
+
+define_convention! {
+    /// Hints for displaying an array, for each of its channels.
+    #[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+    #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+    pub struct DisplaySettings {
+        /// Index of the array dimension over which channels vary, if there is more than one.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        channel_axis: Option<usize>,
+        /// Settings for each channel, in order.
+        channels: Vec<Channel>,
+    }
+    schema_url = "https://raw.githubusercontent.com/clbarnes/zarrs_conventions/refs/tags/display-v1/zarrs_conventions_display/spec/schema.json",
+    spec_url = "https://github.com/clbarnes/zarrs_conventions/blob/display-v1/zarrs_conventions_display/spec/README.md",
+    name = "display",
+    description = "Hints for displaying an array's channels",
+    nested = "display",
+    register = false,
+}
+
+impl DisplaySettings {
+    pub fn builder() -> Builder {
+        Default::default()
+    }
+
+    pub fn channel_axis(&self) -> Option<usize> {
+        self.channel_axis
+    }
+
+    /// Settings for each channel, in order.
+    pub fn channels(&self) -> &[Channel] {
+        &self.channels
+    }
+
+    /// Settings for the first channel with the given name.
+    pub fn get_by_name(&self, name: &str) -> Option<&Channel> {
+        self.channels.iter().find(|c| c.name() == Some(name))
+    }
+
+    fn validate(&self) -> Result<(), BuildError> {
+        match self.channels.len() {
+            0 => return Err(BuildError::NoChannels),
+            1 => (),
+            _ if self.channel_axis.is_none() => return Err(BuildError::NoChannelAxis),
+            _ => (),
+        }
+        for (index, channel) in self.channels.iter().enumerate() {
+            if let Some(source) = channel.validate().into_iter().next() {
+                return Err(BuildError::Channel { index, source });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Lint for DisplaySettings {
+    fn lint(&self) -> Vec<Issue> {
+        let mut issues = Vec::default();
+        if let Err(e @ (BuildError::NoChannels | BuildError::NoChannelAxis)) = self.validate() {
+            let code = match e {
+                BuildError::NoChannels => "no-channels",
+                _ => "no-channel-axis",
+            };
+            issues.push(Issue::error(code, e.to_string()));
+        }
+        for (index, channel) in self.channels.iter().enumerate() {
+            for e in channel.validate() {
+                issues.push(Issue::error(e.code(), format!("channel {index}: {e}")));
+            }
+            if channel.color.is_some() && channel.colormap.is_some() {
+                issues.push(Issue::warning(
+                    "color-and-colormap",
+                    format!("channel {index}: both a color and a colormap are given; the colormap is used"),
+                ));
+            }
+            if let Some(Colormap::Named(name)) = &channel.colormap
+                && !KNOWN_COLORMAPS.contains(&name.as_str())
+            {
+                issues.push(Issue::warning(
+                    "unknown-colormap",
+                    format!("channel {index}: colormap '{name}' may not be understood by viewers"),
+                ));
+            }
+        }
+        issues
+    }
+}
+
+/// Why display settings could not be built.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[non_exhaustive]
+pub enum BuildError {
+    #[error("at least one channel must be given")]
+    NoChannels,
+    #[error("a channel axis must be given for more than one channel")]
+    NoChannelAxis,
+    #[error("{found} channels are given for an array with {expected} channels")]
+    ChannelCount { expected: usize, found: usize },
+    #[error("channel {index}: {source}")]
+    Channel { index: usize, source: ChannelError },
+}
+
+/// Builder for [DisplaySettings], created by [DisplaySettings::builder].
+#[derive(Debug, Clone, Default)]
+pub struct Builder {
+    inner: DisplaySettings,
+}
+
+impl Builder {
+    /// Add the settings for the next channel.
+    pub fn channel(mut self, channel: Channel) -> Self {
+        self.inner.channels.push(channel);
+        self
+    }
+
+    /// Set the index of the array dimension over which channels vary.
+    pub fn channel_axis(mut self, axis: usize) -> Self {
+        self.inner.channel_axis = Some(axis);
+        self
+    }
+
+    /// Build the settings, checking those of each channel.
+    pub fn build(self) -> Result<DisplaySettings, BuildError> {
+        self.inner.validate()?;
+        Ok(self.inner)
+    }
+
+    /// Build the settings, checking that there are settings for each channel of an array
+    /// with the given shape.
+    pub fn build_for_shape(self, shape: &[u64]) -> Result<DisplaySettings, BuildError> {
+        let expected = match self.inner.channel_axis {
+            Some(axis) => shape.get(axis).copied().unwrap_or(0) as usize,
+            None => 1,
+        };
+        let found = self.inner.channels.len();
+        if found != expected {
+            return Err(BuildError::ChannelCount { expected, found });
+        }
+        self.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use zarrs_conventions::validate::Lint;
+
+    use crate::{BuildError, Channel, ChannelError, ColorError, Colormap, DisplaySettings};
+
+    #[test]
+    fn build() {
+        let display = DisplaySettings::builder()
+            .channel_axis(0)
+            .channel(Channel::new().with_name("DAPI").with_color("#0000ff"))
+            .channel(
+                Channel::new()
+                    .with_colormap(Colormap::Named("viridis".into()))
+                    .with_window(0.0, 4095.0)
+                    .with_opacity(0.5),
+            )
+            .build_for_shape(&[2, 512, 512])
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(&display).unwrap(),
+            json!({
+                "channel_axis": 0,
+                "channels": [
+                    {"name": "DAPI", "color": "#0000ff"},
+                    {"colormap": "viridis", "window": {"min": 0.0, "max": 4095.0}, "opacity": 0.5},
+                ],
+            })
+        );
+        assert!(display.lint().is_empty());
+        assert!(display.get_by_name("DAPI").is_some());
+
+        assert_eq!(
+            DisplaySettings::builder()
+                .channel(Channel::new())
+                .build_for_shape(&[512, 512]),
+            Ok(DisplaySettings {
+                channel_axis: None,
+                channels: vec![Channel::new()]
+            })
+        );
+        assert_eq!(
+            DisplaySettings::builder()
+                .channel_axis(2)
+                .channel(Channel::new())
+                .build_for_shape(&[512, 512, 3]),
+            Err(BuildError::ChannelCount {
+                expected: 3,
+                found: 1
+            })
+        );
+        assert_eq!(
+            DisplaySettings::builder()
+                .channel(Channel::new())
+                .channel(Channel::new())
+                .build(),
+            Err(BuildError::NoChannelAxis)
+        );
+        assert_eq!(
+            DisplaySettings::builder()
+                .channel(Channel::new().with_color("blue"))
+                .build(),
+            Err(BuildError::Channel {
+                index: 0,
+                source: ChannelError::Color(ColorError("blue".into()))
+            })
+        );
+    }
+
+    #[test]
+    fn lint() {
+        let display: DisplaySettings = serde_json::from_value(json!({
+            "channels": [
+                {"color": "#00ff00", "colormap": "fancy", "opacity": 2.0},
+                {"colormap": ["#000000"], "window": {"min": 1.0, "max": 1.0}},
+            ],
+        }))
+        .unwrap();
+        let codes: Vec<_> = display.lint().into_iter().map(|i| i.code).collect();
+        assert_eq!(
+            codes,
+            [
+                "no-channel-axis",
+                "invalid-opacity",
+                "color-and-colormap",
+                "unknown-colormap",
+                "short-lut",
+                "invalid-window",
+            ]
+        );
+    }
+}
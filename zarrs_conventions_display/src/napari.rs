@@ -0,0 +1,118 @@
+//! Keyword arguments for [napari](https://napari.org)'s `Viewer.add_image`.
+use serde_json::{Map, Value, json};
+
+use crate::{Channel, Color, ColorError, Colormap, DisplaySettings};
+
+/// A napari colormap of evenly spaced RGBA colors.
+fn custom(name: String, colors: &[[f64; 3]]) -> Value {
+    let colors: Vec<_> = colors
+        .iter()
+        .map(|[r, g, b]| json!([r, g, b, 1.0]))
+        .collect();
+    json!({"name": name, "colors": colors})
+}
+
+fn colormap(index: usize, channel: &Channel) -> Result<Value, ColorError> {
+    Ok(match (&channel.colormap, &channel.color) {
+        (Some(Colormap::Named(name)), _) => name.as_str().into(),
+        (Some(Colormap::Lut(colors)), _) => {
+            let colors = colors
+                .iter()
+                .map(Color::rgb_unit)
+                .collect::<Result<Vec<_>, _>>()?;
+            let name = channel
+                .name
+                .clone()
+                .unwrap_or_else(|| format!("channel {index}"));
+            custom(name, &colors)
+        }
+        (None, Some(color)) => custom(color.as_str().into(), &[[0.0; 3], color.rgb_unit()?]),
+        (None, None) => "gray".into(),
+    })
+}
+
+impl DisplaySettings {
+    /// Keyword arguments for napari's `Viewer.add_image`, to be given with the array.
+    ///
+    /// Settings for each channel are given as lists if there is a channel axis,
+    /// and the channels are blended additively.
+    ///
+    /// ```
+    /// use zarrs_conventions_display::{Channel, DisplaySettings};
+    ///
+    /// let display = DisplaySettings::builder()
+    ///     .channel(Channel::new().with_name("DAPI").with_window(0.0, 255.0))
+    ///     .build()
+    ///     .unwrap();
+    /// let kwargs = display.to_napari().unwrap();
+    /// assert_eq!(kwargs["name"], "DAPI");
+    /// assert_eq!(kwargs["contrast_limits"], serde_json::json!([0.0, 255.0]));
+    /// ```
+    pub fn to_napari(&self) -> Result<Map<String, Value>, ColorError> {
+        let mut columns: [(&str, Vec<Value>); 4] = [
+            ("name", Vec::default()),
+            ("colormap", Vec::default()),
+            ("contrast_limits", Vec::default()),
+            ("opacity", Vec::default()),
+        ];
+        for (index, channel) in self.channels.iter().enumerate() {
+            columns[0].1.push(channel.name.clone().into());
+            columns[1].1.push(colormap(index, channel)?);
+            columns[2]
+                .1
+                .push(channel.window.map(|w| json!([w.min, w.max])).into());
+            columns[3].1.push(channel.opacity.unwrap_or(1.0).into());
+        }
+        let mut kwargs = Map::default();
+        for (key, values) in columns {
+            if values.iter().all(Value::is_null) {
+                continue;
+            }
+            let value = match self.channel_axis {
+                Some(_) => values.into(),
+                None => values.into_iter().next().unwrap_or_default(),
+            };
+            kwargs.insert(key.into(), value);
+        }
+        if let Some(axis) = self.channel_axis {
+            kwargs.insert("channel_axis".into(), axis.into());
+            kwargs.insert("blending".into(), "additive".into());
+        }
+        Ok(kwargs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{Channel, Color, Colormap, DisplaySettings};
+
+    #[test]
+    fn napari() {
+        let display = DisplaySettings::builder()
+            .channel_axis(0)
+            .channel(Channel::new().with_color("#ff0000").with_window(0.0, 100.0))
+            .channel(Channel::new().with_colormap(Colormap::Lut(vec![
+                Color::from_rgb([0, 0, 0]),
+                Color::from_rgb([0, 255, 0]),
+            ])))
+            .channel(Channel::new().with_opacity(0.5))
+            .build()
+            .unwrap();
+        assert_eq!(
+            serde_json::Value::from(display.to_napari().unwrap()),
+            json!({
+                "channel_axis": 0,
+                "blending": "additive",
+                "colormap": [
+                    {"name": "#ff0000", "colors": [[0.0, 0.0, 0.0, 1.0], [1.0, 0.0, 0.0, 1.0]]},
+                    {"name": "channel 1", "colors": [[0.0, 0.0, 0.0, 1.0], [0.0, 1.0, 0.0, 1.0]]},
+                    "gray",
+                ],
+                "contrast_limits": [[0.0, 100.0], null, null],
+                "opacity": [1.0, 1.0, 0.5],
+            })
+        );
+    }
+}
@@ -0,0 +1,167 @@
+//! Image layers for [Neuroglancer](https://github.com/google/neuroglancer).
+//!
+//! Each channel is shown as an image layer whose shader applies its colors and contrast window.
+//! Layers have no `source`, which must be added along with any selection of the layer's channel.
+use std::fmt::Write;
+
+use serde_json::{Map, Value, json};
+
+use crate::{Channel, ColorError, Colormap, DisplaySettings, color::HUES};
+
+/// Why display settings could not be converted for Neuroglancer.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[non_exhaustive]
+pub enum NeuroglancerError {
+    #[error(transparent)]
+    Color(#[from] ColorError),
+    #[error("colormap '{0}' has no Neuroglancer equivalent")]
+    UnsupportedColormap(String),
+}
+
+fn vec3([r, g, b]: [f64; 3]) -> String {
+    format!("vec3({r:.4}, {g:.4}, {b:.4})")
+}
+
+/// A shader showing the channel's contrast-adjusted intensity, from `#uicontrol invlerp window`.
+fn shader(channel: &Channel) -> Result<String, NeuroglancerError> {
+    let mut shader = String::from("#uicontrol invlerp window\n");
+    let rgb = match (&channel.colormap, &channel.color) {
+        (Some(Colormap::Named(name)), _) => match name.as_str() {
+            "jet" => "colormapJet(window())".into(),
+            "cubehelix" => "colormapCubehelix(window())".into(),
+            name => {
+                let (_, hue) = HUES
+                    .iter()
+                    .find(|(n, _)| *n == name)
+                    .ok_or_else(|| NeuroglancerError::UnsupportedColormap(name.into()))?;
+                format!("{} * window()", vec3(hue.map(|c| c as f64 / 255.0)))
+            }
+        },
+        (Some(Colormap::Lut(colors)), _) => {
+            let colors = colors
+                .iter()
+                .map(|c| Ok(vec3(c.rgb_unit()?)))
+                .collect::<Result<Vec<_>, ColorError>>()?;
+            let n = colors.len();
+            writeln!(
+                shader,
+                "const vec3 LUT[{n}] = vec3[{n}]({});",
+                colors.join(", ")
+            )
+            .expect("writing to a string cannot fail");
+            shader.push_str(&format!(
+                "vec3 lut(float x) {{\n  float i = clamp(x, 0.0, 1.0) * {}.0;\n  int j = int(min(floor(i), {}.0));\n  return mix(LUT[j], LUT[j + 1], i - float(j));\n}}\n",
+                n - 1,
+                n.saturating_sub(2)
+            ));
+            "lut(window())".into()
+        }
+        (None, Some(color)) => format!("{} * window()", vec3(color.rgb_unit()?)),
+        (None, None) => "vec3(window())".into(),
+    };
+    shader.push_str(&format!("void main() {{\n  emitRGB({rgb});\n}}\n"));
+    Ok(shader)
+}
+
+impl DisplaySettings {
+    /// A Neuroglancer image layer for each channel, blended additively if there is more than one.
+    ///
+    /// Named colormaps other than `jet`, `cubehelix`, and single hues such as `gray` are not supported.
+    ///
+    /// ```
+    /// use zarrs_conventions_display::{Channel, DisplaySettings};
+    ///
+    /// let display = DisplaySettings::builder()
+    ///     .channel(Channel::new().with_color("#00ff00").with_window(0.0, 255.0))
+    ///     .build()
+    ///     .unwrap();
+    /// let layers = display.to_neuroglancer().unwrap();
+    /// assert_eq!(layers[0]["shaderControls"]["window"]["range"], serde_json::json!([0.0, 255.0]));
+    /// ```
+    pub fn to_neuroglancer(&self) -> Result<Vec<Value>, NeuroglancerError> {
+        self.channels
+            .iter()
+            .enumerate()
+            .map(|(index, channel)| {
+                let mut layer = Map::default();
+                layer.insert("type".into(), "image".into());
+                let name = channel
+                    .name
+                    .clone()
+                    .unwrap_or_else(|| format!("channel {index}"));
+                layer.insert("name".into(), name.into());
+                layer.insert("shader".into(), shader(channel)?.into());
+                if let Some(window) = channel.window {
+                    layer.insert(
+                        "shaderControls".into(),
+                        json!({"window": {"range": [window.min, window.max]}}),
+                    );
+                }
+                if let Some(opacity) = channel.opacity {
+                    layer.insert("opacity".into(), opacity.into());
+                }
+                if self.channels.len() > 1 {
+                    layer.insert("blend".into(), "additive".into());
+                }
+                Ok(layer.into())
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NeuroglancerError;
+    use crate::{Channel, Color, Colormap, DisplaySettings};
+
+    fn shader(channel: Channel) -> Result<String, NeuroglancerError> {
+        let display = DisplaySettings::builder().channel(channel).build().unwrap();
+        let layers = display.to_neuroglancer()?;
+        Ok(layers[0]["shader"].as_str().unwrap().to_string())
+    }
+
+    #[test]
+    fn shaders() {
+        assert_eq!(
+            shader(Channel::new()).unwrap(),
+            "#uicontrol invlerp window\nvoid main() {\n  emitRGB(vec3(window()));\n}\n"
+        );
+        assert!(
+            shader(Channel::new().with_color("#ff8000"))
+                .unwrap()
+                .contains("emitRGB(vec3(1.0000, 0.5020, 0.0000) * window())")
+        );
+        assert!(
+            shader(Channel::new().with_colormap(Colormap::Named("jet".into())))
+                .unwrap()
+                .contains("colormapJet(window())")
+        );
+        let lut = shader(Channel::new().with_colormap(Colormap::Lut(vec![
+            Color::from_rgb([0, 0, 0]),
+            Color::from_rgb([255, 0, 0]),
+            Color::from_rgb([255, 255, 0]),
+        ])))
+        .unwrap();
+        assert!(lut.contains("const vec3 LUT[3] = vec3[3](vec3(0.0000, 0.0000, 0.0000), "));
+        assert!(lut.contains("* 2.0;") && lut.contains("min(floor(i), 1.0)"));
+        assert_eq!(
+            shader(Channel::new().with_colormap(Colormap::Named("viridis".into()))),
+            Err(NeuroglancerError::UnsupportedColormap("viridis".into()))
+        );
+    }
+
+    #[test]
+    fn layers() {
+        let display = DisplaySettings::builder()
+            .channel_axis(0)
+            .channel(Channel::new().with_name("DAPI").with_opacity(0.5))
+            .channel(Channel::new())
+            .build()
+            .unwrap();
+        let layers = display.to_neuroglancer().unwrap();
+        assert_eq!(layers[0]["name"], "DAPI");
+        assert_eq!(layers[0]["opacity"], 0.5);
+        assert_eq!(layers[1]["name"], "channel 1");
+        assert_eq!(layers[1]["blend"], "additive");
+    }
+}
@@ -0,0 +1,131 @@
+//! Conversion to the `dct:spatial` and `dct:temporal` properties of a
+//! [DCAT](https://www.w3.org/TR/vocab-dcat-3/) dataset, in JSON-LD.
+//!
+//! Bounding boxes are written as GeoSPARQL WKT literals in their own CRS,
+//! without any height.
+use serde_json::{Map, Value, json};
+
+use crate::{Extent, SpatialExtent};
+
+const WKT_LITERAL: &str = "http://www.opengis.net/ont/geosparql#wktLiteral";
+
+/// URI of a CRS in the OGC definitions register, e.g. `http://www.opengis.net/def/crs/EPSG/0/32633`.
+fn crs_uri(code: &str) -> String {
+    match code.split_once(':') {
+        Some((authority, "CRS84")) if authority.eq_ignore_ascii_case("OGC") => {
+            "http://www.opengis.net/def/crs/OGC/1.3/CRS84".to_string()
+        }
+        Some((authority, code)) => {
+            format!("http://www.opengis.net/def/crs/{authority}/0/{code}")
+        }
+        None => code.to_string(),
+    }
+}
+
+/// The bounding box as a WKT polygon, prefixed by its CRS unless it is the GeoSPARQL default, CRS84.
+fn wkt(spatial: &SpatialExtent) -> String {
+    let b = spatial.bbox();
+    let polygon = format!(
+        "POLYGON(({xmin} {ymin}, {xmax} {ymin}, {xmax} {ymax}, {xmin} {ymax}, {xmin} {ymin}))",
+        xmin = b.xmin,
+        ymin = b.ymin,
+        xmax = b.xmax,
+        ymax = b.ymax,
+    );
+    if spatial.crs().eq_ignore_ascii_case(crate::DEFAULT_CRS) {
+        polygon
+    } else {
+        format!("<{}> {polygon}", crs_uri(spatial.crs()))
+    }
+}
+
+impl Extent {
+    /// `dct:spatial` and `dct:temporal` properties of a DCAT dataset.
+    ///
+    /// ```
+    /// use zarrs_conventions_extent::{Bbox, Extent};
+    ///
+    /// let extent = Extent::builder()
+    ///     .bbox_in(Bbox::new(300000.0, 5000000.0, 400000.0, 5100000.0), "EPSG:32633")
+    ///     .build()
+    ///     .unwrap();
+    /// let dcat = extent.to_dcat();
+    /// assert_eq!(
+    ///     dcat["dct:spatial"]["dcat:bbox"]["@value"],
+    ///     "<http://www.opengis.net/def/crs/EPSG/0/32633> POLYGON((300000 5000000, 400000 5000000, 400000 5100000, 300000 5100000, 300000 5000000))"
+    /// );
+    /// ```
+    pub fn to_dcat(&self) -> Map<String, Value> {
+        let mut properties = Map::default();
+        if let Some(spatial) = self.spatial() {
+            properties.insert(
+                "dct:spatial".into(),
+                json!({
+                    "@type": "dct:Location",
+                    "dcat:bbox": {"@value": wkt(spatial), "@type": WKT_LITERAL},
+                }),
+            );
+        }
+        if let Some(temporal) = self.temporal() {
+            let mut period = Map::default();
+            period.insert("@type".into(), "dct:PeriodOfTime".into());
+            for (key, timestamp) in [
+                ("dcat:startDate", temporal.start()),
+                ("dcat:endDate", temporal.end()),
+            ] {
+                if let Some(timestamp) = timestamp {
+                    period.insert(
+                        key.into(),
+                        json!({"@value": timestamp.as_str(), "@type": "xsd:dateTime"}),
+                    );
+                }
+            }
+            properties.insert("dct:temporal".into(), period.into());
+        }
+        properties
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::crs_uri;
+    use crate::{Bbox, Extent};
+
+    #[test]
+    fn dcat() {
+        assert_eq!(
+            crs_uri("OGC:CRS84"),
+            "http://www.opengis.net/def/crs/OGC/1.3/CRS84"
+        );
+        assert_eq!(
+            crs_uri("EPSG:4326"),
+            "http://www.opengis.net/def/crs/EPSG/0/4326"
+        );
+
+        let extent = Extent::builder()
+            .bbox(Bbox::new(-10.0, 40.5, 5.0, 60.0))
+            .start("2020-01-01T00:00:00Z")
+            .end("2020-12-31T00:00:00Z")
+            .build()
+            .unwrap();
+        assert_eq!(
+            serde_json::Value::Object(extent.to_dcat()),
+            json!({
+                "dct:spatial": {
+                    "@type": "dct:Location",
+                    "dcat:bbox": {
+                        "@value": "POLYGON((-10 40.5, 5 40.5, 5 60, -10 60, -10 40.5))",
+                        "@type": "http://www.opengis.net/ont/geosparql#wktLiteral",
+                    },
+                },
+                "dct:temporal": {
+                    "@type": "dct:PeriodOfTime",
+                    "dcat:startDate": {"@value": "2020-01-01T00:00:00Z", "@type": "xsd:dateTime"},
+                    "dcat:endDate": {"@value": "2020-12-31T00:00:00Z", "@type": "xsd:dateTime"},
+                },
+            })
+        );
+    }
+}
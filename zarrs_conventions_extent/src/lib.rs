@@ -0,0 +1,435 @@
+#![doc = include_str!("../README.md")]
+use serde::{Deserialize, Serialize};
+pub use zarrs_conventions;
+use zarrs_conventions::{
+    define_convention,
+    validate::{Issue, Lint},
+};
+pub use zarrs_conventions_timestamps::{Timestamp, TimestampError};
+
+pub mod dcat;
+#[cfg(feature = "proj")]
+pub mod proj;
+pub mod stac;
+
+/// CRS of bounding boxes which do not give one: longitude and latitude on WGS 84.
+pub const DEFAULT_CRS: &str = "OGC:CRS84";
+
+/// Codes of geographic CRSs whose bounding boxes are given as longitude and latitude (and height),
+/// and may cross the antimeridian.
+static GEOGRAPHIC_CRS: &[&str] = &["OGC:CRS84", "OGC:CRS84h", "EPSG:4326", "EPSG:4979"];
+
+/// Bounding box in 2 or 3 dimensions, written as `[xmin, ymin, xmax, ymax]`
+/// or `[xmin, ymin, zmin, xmax, ymax, zmax]`, as in GeoJSON and STAC.
+///
+/// In a geographic CRS, `xmin` is greater than `xmax` if the box crosses the antimeridian.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "schema", schemars(extend("minItems" = 4, "maxItems" = 6)))]
+#[serde(try_from = "Vec<f64>", into = "Vec<f64>")]
+pub struct Bbox {
+    pub xmin: f64,
+    pub ymin: f64,
+    pub xmax: f64,
+    pub ymax: f64,
+    /// Minimum and maximum height, for a 3D box.
+    pub z: Option<(f64, f64)>,
+}
+
+impl Bbox {
+    pub fn new(xmin: f64, ymin: f64, xmax: f64, ymax: f64) -> Self {
+        Self {
+            xmin,
+            ymin,
+            xmax,
+            ymax,
+            z: None,
+        }
+    }
+
+    pub fn with_z(mut self, zmin: f64, zmax: f64) -> Self {
+        self.z = Some((zmin, zmax));
+        self
+    }
+
+    pub fn is_3d(&self) -> bool {
+        self.z.is_some()
+    }
+
+    /// Whether the bounds are finite and the minima are no greater than the maxima,
+    /// except that `xmin` may exceed `xmax` in a geographic CRS.
+    fn is_valid(&self, geographic: bool) -> bool {
+        let (zmin, zmax) = self.z.unwrap_or_default();
+        [self.xmin, self.ymin, self.xmax, self.ymax, zmin, zmax]
+            .iter()
+            .all(|v| v.is_finite())
+            && (geographic || self.xmin <= self.xmax)
+            && self.ymin <= self.ymax
+            && zmin <= zmax
+    }
+
+    /// Whether the longitudes and latitudes are within their ranges.
+    fn is_geographic(&self) -> bool {
+        [self.xmin, self.xmax]
+            .iter()
+            .all(|x| (-180.0..=180.0).contains(x))
+            && [self.ymin, self.ymax]
+                .iter()
+                .all(|y| (-90.0..=90.0).contains(y))
+    }
+
+    /// Whether the box contains another, for boxes in the same projected CRS.
+    pub fn contains(&self, other: &Bbox) -> bool {
+        self.xmin <= other.xmin
+            && self.ymin <= other.ymin
+            && self.xmax >= other.xmax
+            && self.ymax >= other.ymax
+            && match (self.z, other.z) {
+                (Some((min, max)), Some((omin, omax))) => min <= omin && max >= omax,
+                _ => true,
+            }
+    }
+}
+
+impl TryFrom<Vec<f64>> for Bbox {
+    type Error = String;
+
+    fn try_from(value: Vec<f64>) -> Result<Self, Self::Error> {
+        match value[..] {
+            [xmin, ymin, xmax, ymax] => Ok(Self::new(xmin, ymin, xmax, ymax)),
+            [xmin, ymin, zmin, xmax, ymax, zmax] => {
+                Ok(Self::new(xmin, ymin, xmax, ymax).with_z(zmin, zmax))
+            }
+            _ => Err(format!(
+                "bounding box must have 4 or 6 values, not {}",
+                value.len()
+            )),
+        }
+    }
+}
+
+impl From<Bbox> for Vec<f64> {
+    fn from(value: Bbox) -> Self {
+        match value.z {
+            Some((zmin, zmax)) => vec![value.xmin, value.ymin, zmin, value.xmax, value.ymax, zmax],
+            None => vec![value.xmin, value.ymin, value.xmax, value.ymax],
+        }
+    }
+}
+
+/// Whether a CRS code is of the form `AUTHORITY:CODE`.
+fn is_crs_code(code: &str) -> bool {
+    matches!(code.split_once(':'), Some((authority, code)) if !authority.is_empty() && !code.is_empty() && !code.contains(':'))
+}
+
+/// The area covered by data, as a bounding box in a CRS.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SpatialExtent {
+    /// Bounding box of the data.
+    bbox: Bbox,
+    /// Authority code of the bounding box's CRS; [DEFAULT_CRS] if absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    crs: Option<String>,
+}
+
+impl SpatialExtent {
+    pub fn bbox(&self) -> &Bbox {
+        &self.bbox
+    }
+
+    /// Authority code of the bounding box's CRS, e.g. `EPSG:32633`.
+    pub fn crs(&self) -> &str {
+        self.crs.as_deref().unwrap_or(DEFAULT_CRS)
+    }
+
+    /// Whether the bounding box is given in longitude and latitude.
+    pub fn is_geographic(&self) -> bool {
+        GEOGRAPHIC_CRS
+            .iter()
+            .any(|c| c.eq_ignore_ascii_case(self.crs()))
+    }
+
+    fn problems(&self) -> Vec<BuildError> {
+        let mut problems = Vec::default();
+        if !is_crs_code(self.crs()) {
+            problems.push(BuildError::InvalidCrs(self.crs().to_string()));
+        }
+        let geographic = self.is_geographic();
+        if !self.bbox.is_valid(geographic) {
+            problems.push(BuildError::InvalidBbox(self.bbox));
+        } else if geographic && !self.bbox.is_geographic() {
+            problems.push(BuildError::BboxOutOfRange(self.bbox));
+        }
+        problems
+    }
+}
+
+/// The period covered by data; either end may be open.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TemporalExtent {
+    /// Start of the period; open if absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    start: Option<Timestamp>,
+    /// End of the period; open if absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    end: Option<Timestamp>,
+}
+
+impl TemporalExtent {
+    pub fn start(&self) -> Option<&Timestamp> {
+        self.start.as_ref()
+    }
+
+    pub fn end(&self) -> Option<&Timestamp> {
+        self.end.as_ref()
+    }
+
+    /// Whether the instant, as seconds and nanoseconds since the Unix epoch, is within the period.
+    pub fn contains(&self, instant: (i64, u32)) -> bool {
+        let after_start = self
+            .start
+            .as_ref()
+            .is_none_or(|s| s.instant().is_ok_and(|s| s <= instant));
+        let before_end = self
+            .end
+            .as_ref()
+            .is_none_or(|e| e.instant().is_ok_and(|e| instant <= e));
+        after_start && before_end
+    }
+
+    fn problems(&self) -> Vec<BuildError> {
+        let mut problems = Vec::default();
+        if self.start.is_none() && self.end.is_none() {
+            problems.push(BuildError::EmptyInterval);
+        }
+        let mut instant =
+            |field, timestamp: &Option<Timestamp>| match timestamp.as_ref().map(Timestamp::instant)
+            {
+                Some(Ok(instant)) => Some(instant),
+                Some(Err(source)) => {
+                    problems.push(BuildError::Timestamp { field, source });
+                    None
+                }
+                None => None,
+            };
+        let start = instant("start", &self.start);
+        let end = instant("end", &self.end);
+        if let (Some(start), Some(end)) = (start, end)
+            && end < start
+        {
+            problems.push(BuildError::EndBeforeStart);
+        }
+        problems
+    }
+}
+
+define_convention! {
+    /// Where and when data are, for discovery in catalogs.
+    #[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+    #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+    pub struct Extent {
+        /// The area covered by the data.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        spatial: Option<SpatialExtent>,
+        /// The period covered by the data.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        temporal: Option<TemporalExtent>,
+    }
+    schema_url = "https://raw.githubusercontent.com/clbarnes/zarrs_conventions/refs/tags/extent-v1/zarrs_conventions_extent/spec/schema.json",
+    spec_url = "https://github.com/clbarnes/zarrs_conventions/blob/extent-v1/zarrs_conventions_extent/spec/README.md",
+    name = "extent",
+    description = "Spatial bounding box and temporal extent of a node's data",
+    nested = "extent",
+    register = false,
+}
+
+impl Extent {
+    pub fn builder() -> Builder {
+        Default::default()
+    }
+
+    pub fn spatial(&self) -> Option<&SpatialExtent> {
+        self.spatial.as_ref()
+    }
+
+    pub fn temporal(&self) -> Option<&TemporalExtent> {
+        self.temporal.as_ref()
+    }
+
+    fn problems(&self) -> Vec<BuildError> {
+        let mut problems = Vec::default();
+        if self.spatial.is_none() && self.temporal.is_none() {
+            problems.push(BuildError::Empty);
+        }
+        if let Some(spatial) = &self.spatial {
+            problems.extend(spatial.problems());
+        }
+        if let Some(temporal) = &self.temporal {
+            problems.extend(temporal.problems());
+        }
+        problems
+    }
+}
+
+impl Lint for Extent {
+    fn lint(&self) -> Vec<Issue> {
+        self.problems()
+            .into_iter()
+            .map(|e| {
+                let code = match e {
+                    BuildError::Empty => "no-extent",
+                    BuildError::InvalidCrs(_) => "invalid-crs",
+                    BuildError::InvalidBbox(_) => "invalid-bbox",
+                    BuildError::BboxOutOfRange(_) => "bbox-out-of-range",
+                    BuildError::EmptyInterval => "empty-interval",
+                    BuildError::Timestamp { .. } => "invalid-timestamp",
+                    BuildError::EndBeforeStart => "end-before-start",
+                };
+                Issue::error(code, e.to_string())
+            })
+            .collect()
+    }
+}
+
+/// Why an extent could not be built.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[non_exhaustive]
+pub enum BuildError {
+    #[error("a spatial or temporal extent must be given")]
+    Empty,
+    #[error("CRS code '{0}' is not of the form AUTHORITY:CODE")]
+    InvalidCrs(String),
+    #[error("bounding box {0:?} is not finite, or its minima exceed its maxima")]
+    InvalidBbox(Bbox),
+    #[error("bounding box {0:?} is outside the range of longitude and latitude")]
+    BboxOutOfRange(Bbox),
+    #[error("a temporal extent must have a start or an end")]
+    EmptyInterval,
+    #[error("invalid {field} time: {source}")]
+    Timestamp {
+        field: &'static str,
+        source: TimestampError,
+    },
+    #[error("temporal extent ends before it starts")]
+    EndBeforeStart,
+}
+
+/// Builder for [Extent], created by [Extent::builder].
+#[derive(Debug, Clone, Default)]
+pub struct Builder {
+    inner: Extent,
+}
+
+impl Builder {
+    /// Set the bounding box, in [DEFAULT_CRS].
+    pub fn bbox(mut self, bbox: Bbox) -> Self {
+        self.inner.spatial = Some(SpatialExtent { bbox, crs: None });
+        self
+    }
+
+    /// Set the bounding box, in the CRS with the given authority code, e.g. `EPSG:32633`.
+    pub fn bbox_in(mut self, bbox: Bbox, crs: impl Into<String>) -> Self {
+        self.inner.spatial = Some(SpatialExtent {
+            bbox,
+            crs: Some(crs.into()),
+        });
+        self
+    }
+
+    /// Set the start of the period, as an RFC 3339 timestamp; checked when building.
+    pub fn start(mut self, timestamp: impl Into<String>) -> Self {
+        self.inner.temporal.get_or_insert_default().start =
+            Some(Timestamp::new_unchecked(timestamp));
+        self
+    }
+
+    /// Set the end of the period, as an RFC 3339 timestamp; checked when building.
+    pub fn end(mut self, timestamp: impl Into<String>) -> Self {
+        self.inner.temporal.get_or_insert_default().end = Some(Timestamp::new_unchecked(timestamp));
+        self
+    }
+
+    /// Build the extent, checking the bounding box against its CRS,
+    /// and that the period's timestamps are valid and in order.
+    pub fn build(self) -> Result<Extent, BuildError> {
+        match self.inner.problems().into_iter().next() {
+            Some(e) => Err(e),
+            None => Ok(self.inner),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use zarrs_conventions::validate::Lint;
+
+    use crate::{Bbox, BuildError, Extent};
+
+    #[test]
+    fn build() {
+        let extent = Extent::builder()
+            .bbox(Bbox::new(170.0, -50.0, -170.0, -30.0).with_z(0.0, 100.0))
+            .start("2020-01-01T00:00:00Z")
+            .build()
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(&extent).unwrap(),
+            json!({
+                "spatial": {"bbox": [170.0, -50.0, 0.0, -170.0, -30.0, 100.0]},
+                "temporal": {"start": "2020-01-01T00:00:00Z"},
+            })
+        );
+        assert!(extent.spatial().unwrap().is_geographic());
+        assert!(extent.lint().is_empty());
+
+        let projected = Bbox::new(500000.0, 0.0, 400000.0, 100.0);
+        assert_eq!(
+            Extent::builder().bbox_in(projected, "EPSG:32633").build(),
+            Err(BuildError::InvalidBbox(projected))
+        );
+        assert_eq!(
+            Extent::builder()
+                .bbox(Bbox::new(0.0, 0.0, 200.0, 10.0))
+                .build(),
+            Err(BuildError::BboxOutOfRange(Bbox::new(0.0, 0.0, 200.0, 10.0)))
+        );
+        assert_eq!(Extent::builder().build(), Err(BuildError::Empty));
+        assert_eq!(
+            Extent::builder()
+                .start("2020-01-02T00:00:00Z")
+                .end("2020-01-01T00:00:00Z")
+                .build(),
+            Err(BuildError::EndBeforeStart)
+        );
+        assert!(serde_json::from_value::<Extent>(json!({"spatial": {"bbox": [0, 1, 2]}})).is_err());
+    }
+
+    #[test]
+    fn temporal() {
+        let extent = Extent::builder()
+            .start("2020-01-01T00:00:00Z")
+            .end("2020-12-31T23:59:59Z")
+            .build()
+            .unwrap();
+        let temporal = extent.temporal().unwrap();
+        assert!(temporal.contains((1600000000, 0)));
+        assert!(!temporal.contains((1500000000, 0)));
+    }
+
+    #[test]
+    fn lint() {
+        let extent: Extent = serde_json::from_value(json!({
+            "spatial": {"bbox": [0, 0, 1, 1], "crs": "4326"},
+            "temporal": {"start": "yesterday"},
+        }))
+        .unwrap();
+        let codes: Vec<_> = extent.lint().into_iter().map(|i| i.code).collect();
+        assert_eq!(codes, ["invalid-crs", "invalid-timestamp"]);
+        let empty: Extent = serde_json::from_value(json!({"temporal": {}})).unwrap();
+        assert_eq!(empty.lint()[0].code, "empty-interval");
+        assert_eq!(Extent::default().lint()[0].code, "no-extent");
+    }
+}
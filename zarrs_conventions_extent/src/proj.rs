@@ -0,0 +1,137 @@
+//! Checking extents against the [proj](zarrs_conventions_proj) convention,
+//! which gives the CRS of the data and their bounding box in it.
+//!
+//! An extent may be given in the data's CRS or in longitude and latitude;
+//! as bounding boxes are not reprojected, only the former can be compared.
+use zarrs_conventions::{Attributes, NestedOrPrefixedRepr, NestedRepr, validate::Issue};
+use zarrs_conventions_proj::Proj;
+
+use crate::{Bbox, Extent, SpatialExtent};
+
+impl Extent {
+    /// A spatial extent with the bounding box of the proj metadata, in its CRS,
+    /// if it gives both a bounding box and an authority code.
+    pub fn from_proj(proj: &Proj) -> Option<Self> {
+        let b = proj.bbox()?;
+        Some(Self {
+            spatial: Some(SpatialExtent {
+                bbox: Bbox::new(b.xmin, b.ymin, b.xmax, b.ymax),
+                crs: Some(proj.code()?.to_string()),
+            }),
+            temporal: None,
+        })
+    }
+
+    /// Check the spatial extent against proj metadata of the same data.
+    ///
+    /// ```
+    /// use zarrs_conventions_extent::{Bbox, Extent};
+    /// use zarrs_conventions_proj::{BoundingBox, Proj};
+    ///
+    /// let proj = Proj::builder()
+    ///     .code("EPSG:32633")
+    ///     .bbox(BoundingBox::new(0.0, 0.0, 10.0, 10.0))
+    ///     .build()
+    ///     .unwrap();
+    /// let extent = Extent::builder()
+    ///     .bbox_in(Bbox::new(0.0, 0.0, 5.0, 5.0), "EPSG:32633")
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(extent.lint_with_proj(&proj)[0].code, "extent-excludes-data");
+    /// ```
+    pub fn lint_with_proj(&self, proj: &Proj) -> Vec<Issue> {
+        let (Some(spatial), Some(code)) = (self.spatial(), proj.code()) else {
+            return Vec::default();
+        };
+        if !spatial.crs().eq_ignore_ascii_case(code) {
+            if spatial.is_geographic() {
+                return Vec::default();
+            }
+            return vec![Issue::warning(
+                "unrelated-crs",
+                format!(
+                    "extent is in {}, which is neither the data's CRS ({code}) nor longitude and latitude",
+                    spatial.crs()
+                ),
+            )];
+        }
+        match proj.bbox() {
+            Some(b)
+                if !spatial
+                    .bbox()
+                    .contains(&Bbox::new(b.xmin, b.ymin, b.xmax, b.ymax)) =>
+            {
+                vec![Issue::warning(
+                    "extent-excludes-data",
+                    format!(
+                        "extent {:?} does not contain the data's bounding box {b:?}",
+                        spatial.bbox()
+                    ),
+                )]
+            }
+            _ => Vec::default(),
+        }
+    }
+}
+
+/// Check the extent against proj metadata in the same attributes, if both are present and valid.
+pub fn lint_attributes(attributes: &Attributes) -> Vec<Issue> {
+    if !attributes.contains_key(Extent::KEY) {
+        return Vec::default();
+    }
+    let (Ok(extent), Ok(proj)) = (
+        Extent::from_attributes_nested(attributes),
+        Proj::from_attributes(attributes),
+    ) else {
+        // reported when validating the payloads themselves
+        return Vec::default();
+    };
+    extent.lint_with_proj(&proj)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use zarrs_conventions_proj::{BoundingBox, Proj};
+
+    use super::lint_attributes;
+    use crate::Extent;
+
+    fn codes(attributes: serde_json::Value) -> Vec<&'static str> {
+        let issues = lint_attributes(attributes.as_object().unwrap());
+        issues.into_iter().map(|i| i.code).collect()
+    }
+
+    #[test]
+    fn proj() {
+        let proj = Proj::builder()
+            .code("EPSG:32633")
+            .bbox(BoundingBox::new(0.0, 0.0, 10.0, 10.0))
+            .build()
+            .unwrap();
+        let extent = Extent::from_proj(&proj).unwrap();
+        assert_eq!(extent.spatial().unwrap().crs(), "EPSG:32633");
+        assert!(extent.lint_with_proj(&proj).is_empty());
+        assert!(Extent::from_proj(&Proj::from_epsg(4326)).is_none());
+
+        let proj = json!({"code": "EPSG:32633", "bbox": [0, 0, 10, 10]});
+        assert!(codes(json!({"extent": {"spatial": {"bbox": [-1, -1, 11, 11], "crs": "EPSG:32633"}}, "proj": proj})).is_empty());
+        assert!(
+            codes(json!({"extent": {"spatial": {"bbox": [14, 0, 15, 1]}}, "proj": proj}))
+                .is_empty()
+        );
+        assert_eq!(
+            codes(
+                json!({"extent": {"spatial": {"bbox": [0, 0, 1, 1], "crs": "EPSG:3857"}}, "proj": proj})
+            ),
+            ["unrelated-crs"]
+        );
+        assert_eq!(
+            codes(
+                json!({"extent": {"spatial": {"bbox": [0, 0, 1, 1], "crs": "EPSG:32633"}}, "proj:code": "EPSG:32633", "proj:bbox": [0, 0, 10, 10]})
+            ),
+            ["extent-excludes-data"]
+        );
+        assert!(codes(json!({"proj": proj})).is_empty());
+    }
+}
@@ -0,0 +1,111 @@
+//! Conversion to the `extent` of a STAC Collection.
+//!
+//! STAC bounding boxes are in longitude and latitude on WGS 84,
+//! so only extents in a geographic CRS can be converted; they are not reprojected.
+use serde::{Deserialize, Serialize};
+
+use crate::Extent;
+
+/// Spatial extent of a STAC Collection.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpatialExtent {
+    pub bbox: Vec<Vec<f64>>,
+}
+
+/// Temporal extent of a STAC Collection; `None` is an open end.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemporalExtent {
+    pub interval: Vec<[Option<String>; 2]>,
+}
+
+/// `extent` of a STAC Collection.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StacExtent {
+    pub spatial: SpatialExtent,
+    pub temporal: TemporalExtent,
+}
+
+/// Why an extent could not be converted to STAC.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum StacError {
+    #[error("STAC collections need a spatial extent")]
+    NoSpatialExtent,
+    #[error("bounding box is in {0}, but STAC needs longitude and latitude on WGS 84")]
+    NotGeographic(String),
+}
+
+impl Extent {
+    /// The `extent` of a STAC Collection.
+    ///
+    /// A missing temporal extent is written as open at both ends.
+    ///
+    /// ```
+    /// use zarrs_conventions_extent::{Bbox, Extent};
+    ///
+    /// let extent = Extent::builder()
+    ///     .bbox(Bbox::new(-10.0, 40.0, 5.0, 60.0))
+    ///     .start("2020-01-01T00:00:00Z")
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     serde_json::to_value(extent.to_stac().unwrap()).unwrap(),
+    ///     serde_json::json!({
+    ///         "spatial": {"bbox": [[-10.0, 40.0, 5.0, 60.0]]},
+    ///         "temporal": {"interval": [["2020-01-01T00:00:00Z", null]]},
+    ///     })
+    /// );
+    /// ```
+    pub fn to_stac(&self) -> Result<StacExtent, StacError> {
+        let spatial = self.spatial().ok_or(StacError::NoSpatialExtent)?;
+        if !spatial.is_geographic() {
+            return Err(StacError::NotGeographic(spatial.crs().to_string()));
+        }
+        let interval = match self.temporal() {
+            Some(temporal) => [
+                temporal.start().map(ToString::to_string),
+                temporal.end().map(ToString::to_string),
+            ],
+            None => [None, None],
+        };
+        Ok(StacExtent {
+            spatial: SpatialExtent {
+                bbox: vec![(*spatial.bbox()).into()],
+            },
+            temporal: TemporalExtent {
+                interval: vec![interval],
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StacError;
+    use crate::{Bbox, Extent};
+
+    #[test]
+    fn stac() {
+        let extent = Extent::builder()
+            .bbox(Bbox::new(-10.0, 40.0, 5.0, 60.0).with_z(0.0, 10.0))
+            .build()
+            .unwrap();
+        let stac = extent.to_stac().unwrap();
+        assert_eq!(stac.spatial.bbox, [[-10.0, 40.0, 0.0, 5.0, 60.0, 10.0]]);
+        assert_eq!(stac.temporal.interval, [[None, None]]);
+
+        let projected = Extent::builder()
+            .bbox_in(Bbox::new(0.0, 0.0, 1.0, 1.0), "EPSG:32633")
+            .build()
+            .unwrap();
+        assert_eq!(
+            projected.to_stac(),
+            Err(StacError::NotGeographic("EPSG:32633".into()))
+        );
+        let temporal = Extent::builder()
+            .end("2020-01-01T00:00:00Z")
+            .build()
+            .unwrap();
+        assert_eq!(temporal.to_stac(), Err(StacError::NoSpatialExtent));
+    }
+}
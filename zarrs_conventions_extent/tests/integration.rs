@@ -0,0 +1,33 @@
+use serde_json::json;
+use zarrs_conventions::test_util::{parse_example, parse_modified_example, parse_nested};
+use zarrs_conventions_extent::Extent;
+
+const EXAMPLE: &str = include_str!("examples/utm.json");
+
+zarrs_conventions::convention_test_suite! {
+    Extent,
+    repr = nested,
+    example = parse_example(EXAMPLE, parse_nested),
+    examples = "tests/examples",
+    schema = "spec/schema.json",
+    registered = false,
+}
+
+#[test]
+fn reject_invalid_bbox() {
+    let parsed = parse_modified_example(
+        EXAMPLE,
+        |attrs| attrs["extent"]["spatial"]["bbox"] = json!([300000.0, 5000000.0, 409800.0]),
+        parse_nested::<Extent>,
+    );
+    assert!(parsed.is_err());
+}
+
+#[cfg(feature = "schema")]
+#[test]
+fn spec_up_to_date() {
+    zarrs_conventions::test_util::assert_docs_up_to_date(
+        &zarrs_conventions::docgen::ConventionDocs::nested::<Extent>(),
+        concat!(env!("CARGO_MANIFEST_DIR"), "/spec"),
+    );
+}
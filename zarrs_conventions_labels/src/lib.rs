@@ -0,0 +1,408 @@
+#![doc = include_str!("../README.md")]
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+pub use zarrs_conventions;
+use zarrs_conventions::{
+    define_convention,
+    iref::{Uri, UriBuf},
+    validate::{Issue, Lint},
+};
+pub use zarrs_conventions_display::{Color, ColorError};
+
+pub mod lut;
+pub mod napari;
+pub mod neuroglancer;
+
+/// Integer data types of zarr v3, and the ranges of their values.
+static DATA_TYPES: &[(&str, i128, i128)] = &[
+    ("int8", i8::MIN as i128, i8::MAX as i128),
+    ("int16", i16::MIN as i128, i16::MAX as i128),
+    ("int32", i32::MIN as i128, i32::MAX as i128),
+    ("int64", i64::MIN as i128, i64::MAX as i128),
+    ("uint8", 0, u8::MAX as i128),
+    ("uint16", 0, u16::MAX as i128),
+    ("uint32", 0, u32::MAX as i128),
+    ("uint64", 0, u64::MAX as i128),
+];
+
+/// What a single stored value means.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Label {
+    /// The stored value, e.g. `1`.
+    value: i64,
+    /// Name of the label, e.g. `nucleus`, unique within the map.
+    name: String,
+    /// Color in which to show the label.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    color: Option<Color>,
+    /// URI of the ontology term for the label, e.g. from UBERON.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<String>", extend("format" = "uri")))]
+    term: Option<UriBuf>,
+}
+
+impl Label {
+    /// The label of a stored value, e.g. `1` for `nucleus`.
+    pub fn new(value: i64, name: impl Into<String>) -> Self {
+        Self {
+            value,
+            name: name.into(),
+            color: None,
+            term: None,
+        }
+    }
+
+    /// Show the label in a color, which is checked when building [LabelMap].
+    pub fn with_color(mut self, color: impl Into<String>) -> Self {
+        self.color = Some(Color::new_unchecked(color));
+        self
+    }
+
+    /// Set the ontology term for the label,
+    /// e.g. `http://purl.obolibrary.org/obo/GO_0005634` for nucleus.
+    pub fn with_term(mut self, term: UriBuf) -> Self {
+        self.term = Some(term);
+        self
+    }
+
+    pub fn value(&self) -> i64 {
+        self.value
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn color(&self) -> Option<&Color> {
+        self.color.as_ref()
+    }
+
+    pub fn term(&self) -> Option<&Uri> {
+        self.term.as_deref()
+    }
+}
+
+define_convention! {
+    /// Names, colors, and ontology terms of the values stored in a segmentation or classification array.
+    #[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+    #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+    pub struct LabelMap {
+        /// Value of unlabelled elements, if any.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        background: Option<i64>,
+        /// Each label, with unique values.
+        labels: Vec<Label>,
+    }
+    schema_url = "https://raw.githubusercontent.com/clbarnes/zarrs_conventions/refs/tags/labels-v1/zarrs_conventions_labels/spec/schema.json",
+    spec_url = "https://github.com/clbarnes/zarrs_conventions/blob/labels-v1/zarrs_conventions_labels/spec/README.md",
+    name = "labels",
+    description = "Names, colors, and ontology terms of the values stored in a label array",
+    nested = "labels",
+    register = false,
+}
+
+impl LabelMap {
+    pub fn builder() -> Builder {
+        Default::default()
+    }
+
+    pub fn background(&self) -> Option<i64> {
+        self.background
+    }
+
+    /// Each label, in the order given.
+    pub fn labels(&self) -> &[Label] {
+        &self.labels
+    }
+
+    /// The label of a stored value.
+    ///
+    /// ```
+    /// use zarrs_conventions_labels::{Label, LabelMap};
+    ///
+    /// let labels = LabelMap::builder()
+    ///     .background(0)
+    ///     .label(Label::new(1, "nucleus").with_color("#0000ff"))
+    ///     .label(Label::new(2, "cytoplasm"))
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(labels.get(1).unwrap().name(), "nucleus");
+    /// assert_eq!(labels.value_of("cytoplasm"), Some(2));
+    /// assert!(labels.get(0).is_none());
+    /// ```
+    pub fn get(&self, value: i64) -> Option<&Label> {
+        self.labels.iter().find(|l| l.value == value)
+    }
+
+    /// Name of the label of a stored value.
+    pub fn name_of(&self, value: i64) -> Option<&str> {
+        self.get(value).map(Label::name)
+    }
+
+    /// Color of the label of a stored value.
+    pub fn color_of(&self, value: i64) -> Option<&Color> {
+        self.get(value).and_then(Label::color)
+    }
+
+    /// Stored value of the label with the given name.
+    pub fn value_of(&self, name: &str) -> Option<i64> {
+        self.labels
+            .iter()
+            .find(|l| l.name == name)
+            .map(Label::value)
+    }
+
+    /// Labels for the given ontology term.
+    pub fn with_term<'a>(&'a self, term: &'a Uri) -> impl Iterator<Item = &'a Label> {
+        self.labels.iter().filter(move |l| l.term() == Some(term))
+    }
+
+    /// Check that every labelled value can be stored in the given zarr v3 integer data type, e.g. `uint8`.
+    pub fn validate_data_type(&self, data_type: &str) -> Result<(), BuildError> {
+        let (_, min, max) = DATA_TYPES
+            .iter()
+            .find(|(name, _, _)| *name == data_type)
+            .ok_or_else(|| BuildError::NotInteger(data_type.to_string()))?;
+        let out_of_range = self
+            .labels
+            .iter()
+            .map(Label::value)
+            .chain(self.background)
+            .find(|v| !(*min..=*max).contains(&(*v as i128)));
+        match out_of_range {
+            Some(value) => Err(BuildError::OutOfRange {
+                value,
+                data_type: data_type.to_string(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    fn problems(&self) -> Vec<BuildError> {
+        let mut problems = Vec::default();
+        if self.labels.is_empty() {
+            problems.push(BuildError::NoLabels);
+        }
+        let mut values = HashSet::new();
+        let mut names = HashSet::new();
+        for label in &self.labels {
+            if label.name.trim().is_empty() {
+                problems.push(BuildError::EmptyName(label.value));
+            } else if !names.insert(label.name.as_str()) {
+                problems.push(BuildError::DuplicateName(label.name.clone()));
+            }
+            if !values.insert(label.value) {
+                problems.push(BuildError::DuplicateValue(label.value));
+            }
+            if let Some(Err(source)) = label.color.as_ref().map(Color::rgb) {
+                problems.push(BuildError::Color {
+                    value: label.value,
+                    source,
+                });
+            }
+        }
+        problems
+    }
+}
+
+impl Lint for LabelMap {
+    fn lint(&self) -> Vec<Issue> {
+        let mut issues: Vec<_> = self
+            .problems()
+            .into_iter()
+            .map(|e| {
+                let code = match e {
+                    BuildError::NoLabels => "no-labels",
+                    BuildError::EmptyName(_) => "empty-name",
+                    BuildError::DuplicateName(_) => "duplicate-name",
+                    BuildError::DuplicateValue(_) => "duplicate-value",
+                    BuildError::Color { .. } => "invalid-color",
+                    BuildError::NotInteger(_) => "not-integer",
+                    BuildError::OutOfRange { .. } => "value-out-of-range",
+                };
+                Issue::error(code, e.to_string())
+            })
+            .collect();
+        let mut colors = HashSet::new();
+        for label in &self.labels {
+            if let Some(Ok(rgb)) = label.color.as_ref().map(Color::rgb)
+                && !colors.insert(rgb)
+            {
+                issues.push(Issue::warning(
+                    "duplicate-color",
+                    format!(
+                        "label {}: color {} is used by another label",
+                        label.value,
+                        label.color.as_ref().expect("checked above").as_str()
+                    ),
+                ));
+            }
+        }
+        if let Some(background) = self.background
+            && let Some(label) = self.get(background)
+            && label.color.is_some()
+        {
+            issues.push(Issue::warning(
+                "colored-background",
+                format!("background value {background} is given a color, which viewers may show"),
+            ));
+        }
+        issues
+    }
+}
+
+/// Why a label map could not be built.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum BuildError {
+    #[error("at least one label must be given")]
+    NoLabels,
+    #[error("label {0}: name is empty")]
+    EmptyName(i64),
+    #[error("label name '{0}' is used more than once")]
+    DuplicateName(String),
+    #[error("value {0} is labelled more than once")]
+    DuplicateValue(i64),
+    #[error("label {value}: {source}")]
+    Color { value: i64, source: ColorError },
+    #[error("'{0}' is not an integer data type")]
+    NotInteger(String),
+    #[error("value {value} cannot be stored as {data_type}")]
+    OutOfRange { value: i64, data_type: String },
+}
+
+/// Builder for [LabelMap], created by [LabelMap::builder].
+#[derive(Debug, Clone, Default)]
+pub struct Builder {
+    inner: LabelMap,
+}
+
+impl Builder {
+    /// Add a label.
+    pub fn label(mut self, label: Label) -> Self {
+        self.inner.labels.push(label);
+        self
+    }
+
+    /// Set the value of unlabelled elements, usually `0`.
+    pub fn background(mut self, value: i64) -> Self {
+        self.inner.background = Some(value);
+        self
+    }
+
+    /// Build the label map, checking that values and names are unique and colors are valid.
+    pub fn build(self) -> Result<LabelMap, BuildError> {
+        match self.inner.problems().into_iter().next() {
+            Some(e) => Err(e),
+            None => Ok(self.inner),
+        }
+    }
+
+    /// Build the label map, checking that every value can be stored in an array of the given
+    /// zarr v3 data type, e.g. `uint8`.
+    pub fn build_for_data_type(self, data_type: &str) -> Result<LabelMap, BuildError> {
+        self.inner.validate_data_type(data_type)?;
+        self.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use zarrs_conventions::{iref::uri, validate::Lint};
+
+    use crate::{BuildError, Label, LabelMap};
+
+    #[test]
+    fn build() {
+        let term = uri!("http://purl.obolibrary.org/obo/GO_0005634").to_owned();
+        let labels = LabelMap::builder()
+            .background(0)
+            .label(
+                Label::new(1, "nucleus")
+                    .with_color("#0000ff")
+                    .with_term(term.clone()),
+            )
+            .label(Label::new(300, "cytoplasm"))
+            .build_for_data_type("uint16")
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(&labels).unwrap(),
+            json!({
+                "background": 0,
+                "labels": [
+                    {"value": 1, "name": "nucleus", "color": "#0000ff", "term": term.as_str()},
+                    {"value": 300, "name": "cytoplasm"},
+                ],
+            })
+        );
+        assert!(labels.lint().is_empty());
+        assert_eq!(labels.name_of(300), Some("cytoplasm"));
+        assert_eq!(labels.color_of(1).unwrap().as_str(), "#0000ff");
+        assert_eq!(
+            labels
+                .with_term(&term)
+                .map(Label::value)
+                .collect::<Vec<_>>(),
+            [1]
+        );
+
+        assert_eq!(
+            labels.validate_data_type("uint8"),
+            Err(BuildError::OutOfRange {
+                value: 300,
+                data_type: "uint8".into()
+            })
+        );
+        assert_eq!(
+            labels.validate_data_type("float32"),
+            Err(BuildError::NotInteger("float32".into()))
+        );
+        assert_eq!(
+            LabelMap::builder()
+                .background(-1)
+                .label(Label::new(1, "cell"))
+                .build_for_data_type("uint8"),
+            Err(BuildError::OutOfRange {
+                value: -1,
+                data_type: "uint8".into()
+            })
+        );
+        assert_eq!(
+            LabelMap::builder()
+                .label(Label::new(1, "cell"))
+                .label(Label::new(1, "nucleus"))
+                .build(),
+            Err(BuildError::DuplicateValue(1))
+        );
+        assert_eq!(LabelMap::builder().build(), Err(BuildError::NoLabels));
+    }
+
+    #[test]
+    fn lint() {
+        let labels: LabelMap = serde_json::from_value(json!({
+            "background": 0,
+            "labels": [
+                {"value": 0, "name": "background", "color": "#000000"},
+                {"value": 1, "name": "cell", "color": "red"},
+                {"value": 1, "name": " "},
+                {"value": 2, "name": "cell", "color": "#000000"},
+            ],
+        }))
+        .unwrap();
+        let codes: Vec<_> = labels.lint().into_iter().map(|i| i.code).collect();
+        assert_eq!(
+            codes,
+            [
+                "invalid-color",
+                "empty-name",
+                "duplicate-value",
+                "duplicate-name",
+                "duplicate-color",
+                "colored-background",
+            ]
+        );
+    }
+}
@@ -0,0 +1,128 @@
+//! Label description files of [ITK-SNAP](http://www.itksnap.org) and color tables of [3D Slicer](https://www.slicer.org).
+//!
+//! Both need a color for every label, so labels without one are written in gray.
+use std::fmt::Write;
+
+use crate::{ColorError, Label, LabelMap};
+
+const GRAY: [u8; 3] = [128, 128, 128];
+
+impl Label {
+    fn rgb_or_gray(&self) -> Result<[u8; 3], ColorError> {
+        self.color.as_ref().map_or(Ok(GRAY), |c| c.rgb())
+    }
+}
+
+impl LabelMap {
+    /// An ITK-SNAP label description file, with the background as its clear label.
+    ///
+    /// ```
+    /// use zarrs_conventions_labels::{Label, LabelMap};
+    ///
+    /// let labels = LabelMap::builder()
+    ///     .background(0)
+    ///     .label(Label::new(1, "liver").with_color("#dd8265"))
+    ///     .build()
+    ///     .unwrap();
+    /// let file = labels.to_itksnap().unwrap();
+    /// assert!(file.ends_with("    1   221  130  101        1  1  1    \"liver\"\n"));
+    /// ```
+    pub fn to_itksnap(&self) -> Result<String, ColorError> {
+        let mut out = String::from(
+            "################################################\n\
+             # ITK-SnAP Label Description File\n\
+             # File format:\n\
+             # IDX   -R-  -G-  -B-  -A--  VIS MSH  LABEL\n\
+             ################################################\n",
+        );
+        if let Some(background) = self.background
+            && self.get(background).is_none()
+        {
+            writeln!(
+                out,
+                "{background:>5} {:>5} {:>4} {:>4} {:>8} {:>2} {:>2}    \"Clear Label\"",
+                0, 0, 0, 0, 0, 0
+            )
+            .expect("writing to a string");
+        }
+        for label in &self.labels {
+            let [r, g, b] = label.rgb_or_gray()?;
+            let visible = u8::from(Some(label.value) != self.background);
+            writeln!(
+                out,
+                "{:>5} {r:>5} {g:>4} {b:>4} {visible:>8} {visible:>2} {visible:>2}    \"{}\"",
+                label.value,
+                label.name.replace('"', "'"),
+            )
+            .expect("writing to a string");
+        }
+        Ok(out)
+    }
+
+    /// A 3D Slicer color table file, in which whitespace in names is replaced with underscores.
+    ///
+    /// ```
+    /// use zarrs_conventions_labels::{Label, LabelMap};
+    ///
+    /// let labels = LabelMap::builder()
+    ///     .label(Label::new(1, "left kidney").with_color("#b96956"))
+    ///     .build()
+    ///     .unwrap();
+    /// assert!(labels.to_slicer().unwrap().ends_with("1 left_kidney 185 105 86 255\n"));
+    /// ```
+    pub fn to_slicer(&self) -> Result<String, ColorError> {
+        let mut out = String::from("# Color table file\n");
+        for label in &self.labels {
+            let [r, g, b] = label.rgb_or_gray()?;
+            let name = label.name.split_whitespace().collect::<Vec<_>>().join("_");
+            let alpha = if Some(label.value) == self.background {
+                0
+            } else {
+                255
+            };
+            writeln!(out, "{} {name} {r} {g} {b} {alpha}", label.value)
+                .expect("writing to a string");
+        }
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Label, LabelMap};
+
+    #[test]
+    fn lut() {
+        let labels = LabelMap::builder()
+            .background(0)
+            .label(Label::new(0, "background"))
+            .label(Label::new(2, "the \"other\" organ").with_color("#00ff00"))
+            .build()
+            .unwrap();
+        let itksnap = labels.to_itksnap().unwrap();
+        let rows: Vec<_> = itksnap.lines().filter(|l| !l.starts_with('#')).collect();
+        assert_eq!(
+            rows,
+            [
+                "    0   128  128  128        0  0  0    \"background\"",
+                "    2     0  255    0        1  1  1    \"the 'other' organ\"",
+            ]
+        );
+        assert_eq!(
+            labels.to_slicer().unwrap(),
+            "# Color table file\n0 background 128 128 128 0\n2 the_\"other\"_organ 0 255 0 255\n"
+        );
+
+        let clear = LabelMap::builder()
+            .background(0)
+            .label(Label::new(1, "cell"))
+            .build()
+            .unwrap();
+        assert!(
+            clear
+                .to_itksnap()
+                .unwrap()
+                .contains("    0     0    0    0        0  0  0    \"Clear Label\"\n")
+        );
+    }
+}
@@ -0,0 +1,83 @@
+//! Keyword arguments for [napari](https://napari.org)'s `Viewer.add_labels`.
+use serde_json::{Map, Value, json};
+
+use crate::{ColorError, LabelMap};
+
+impl LabelMap {
+    /// Keyword arguments for napari's `Viewer.add_labels`, to be given with the array.
+    ///
+    /// The `colormap` maps each colored value to its color, and the background to `transparent`;
+    /// its keys are the values as strings, which must be converted to integers in Python.
+    /// Names are given as a `features` table, with the value of each label in its `index` column.
+    ///
+    /// ```
+    /// use zarrs_conventions_labels::{Label, LabelMap};
+    ///
+    /// let labels = LabelMap::builder()
+    ///     .background(0)
+    ///     .label(Label::new(1, "nucleus").with_color("#0000ff"))
+    ///     .build()
+    ///     .unwrap();
+    /// let kwargs = labels.to_napari().unwrap();
+    /// assert_eq!(kwargs["colormap"]["1"], "#0000ff");
+    /// assert_eq!(kwargs["features"]["name"][0], "nucleus");
+    /// ```
+    pub fn to_napari(&self) -> Result<Map<String, Value>, ColorError> {
+        let mut colormap = Map::default();
+        if let Some(background) = self.background {
+            colormap.insert(background.to_string(), "transparent".into());
+        }
+        for label in &self.labels {
+            if let Some(color) = &label.color {
+                color.rgb()?;
+                colormap.insert(label.value.to_string(), color.as_str().into());
+            }
+        }
+        let (index, name): (Vec<_>, Vec<_>) = self
+            .labels
+            .iter()
+            .map(|l| (Value::from(l.value), Value::from(l.name.clone())))
+            .unzip();
+        let mut kwargs = Map::default();
+        if !colormap.is_empty() {
+            kwargs.insert("colormap".into(), colormap.into());
+        }
+        kwargs.insert("features".into(), json!({"index": index, "name": name}));
+        Ok(kwargs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{ColorError, Label, LabelMap};
+
+    #[test]
+    fn napari() {
+        let labels = LabelMap::builder()
+            .label(Label::new(1, "cell"))
+            .label(Label::new(-1, "edge").with_color("#ff0000"))
+            .build()
+            .unwrap();
+        assert_eq!(
+            serde_json::Value::from(labels.to_napari().unwrap()),
+            json!({
+                "colormap": {"-1": "#ff0000"},
+                "features": {"index": [1, -1], "name": ["cell", "edge"]},
+            })
+        );
+
+        let uncolored = LabelMap::builder()
+            .label(Label::new(1, "cell"))
+            .build()
+            .unwrap();
+        assert!(!uncolored.to_napari().unwrap().contains_key("colormap"));
+
+        let invalid: LabelMap = serde_json::from_value(json!({
+            "labels": [{"value": 1, "name": "cell", "color": "red"}],
+        }))
+        .unwrap();
+        assert_eq!(invalid.to_napari(), Err(ColorError("red".into())));
+    }
+}
@@ -0,0 +1,79 @@
+//! Segment properties and colors for [Neuroglancer](https://github.com/google/neuroglancer)'s segmentation layers.
+//!
+//! Neuroglancer identifies segments by unsigned integers, so labels of negative values are left out.
+use serde_json::{Map, Value, json};
+
+use crate::{ColorError, Label, LabelMap};
+
+impl LabelMap {
+    fn segments(&self) -> impl Iterator<Item = &Label> {
+        self.labels.iter().filter(|l| l.value >= 0)
+    }
+
+    /// The `info` of an inline [segment properties](https://github.com/google/neuroglancer/blob/master/src/datasource/precomputed/segment_properties.md) source,
+    /// naming each segment.
+    ///
+    /// ```
+    /// use zarrs_conventions_labels::{Label, LabelMap};
+    ///
+    /// let labels = LabelMap::builder()
+    ///     .label(Label::new(1, "nucleus"))
+    ///     .build()
+    ///     .unwrap();
+    /// let info = labels.to_segment_properties();
+    /// assert_eq!(info["inline"]["ids"][0], "1");
+    /// assert_eq!(info["inline"]["properties"][0]["values"][0], "nucleus");
+    /// ```
+    pub fn to_segment_properties(&self) -> Value {
+        let (ids, names): (Vec<_>, Vec<_>) = self
+            .segments()
+            .map(|l| (l.value.to_string(), l.name.clone()))
+            .unzip();
+        json!({
+            "@type": "neuroglancer_segment_properties",
+            "inline": {
+                "ids": ids,
+                "properties": [{"id": "label", "type": "label", "values": names}],
+            },
+        })
+    }
+
+    /// The `segmentColors` of a segmentation layer, for each colored label.
+    pub fn to_segment_colors(&self) -> Result<Map<String, Value>, ColorError> {
+        self.segments()
+            .filter_map(|l| l.color.as_ref().map(|c| (l.value, c)))
+            .map(|(value, color)| {
+                color.rgb()?;
+                Ok((value.to_string(), color.as_str().into()))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{Label, LabelMap};
+
+    #[test]
+    fn neuroglancer() {
+        let labels = LabelMap::builder()
+            .label(Label::new(-1, "ignored").with_color("#ffffff"))
+            .label(Label::new(7, "axon").with_color("#ff8000"))
+            .label(Label::new(3, "dendrite"))
+            .build()
+            .unwrap();
+        assert_eq!(
+            labels.to_segment_properties()["inline"],
+            json!({
+                "ids": ["7", "3"],
+                "properties": [{"id": "label", "type": "label", "values": ["axon", "dendrite"]}],
+            })
+        );
+        assert_eq!(
+            serde_json::Value::from(labels.to_segment_colors().unwrap()),
+            json!({"7": "#ff8000"})
+        );
+    }
+}
@@ -0,0 +1,34 @@
+use zarrs_conventions::test_util::{parse_example, parse_modified_example, parse_nested};
+use zarrs_conventions_labels::LabelMap;
+
+const EXAMPLE: &str = include_str!("examples/landcover.json");
+
+zarrs_conventions::convention_test_suite! {
+    LabelMap,
+    repr = nested,
+    example = parse_example(EXAMPLE, parse_nested),
+    examples = "tests/examples",
+    schema = "spec/schema.json",
+    registered = false,
+}
+
+#[test]
+fn reject_missing_labels() {
+    let parsed = parse_modified_example(
+        EXAMPLE,
+        |attrs| {
+            attrs["labels"].as_object_mut().unwrap().remove("labels");
+        },
+        parse_nested::<LabelMap>,
+    );
+    assert!(parsed.is_err());
+}
+
+#[cfg(feature = "schema")]
+#[test]
+fn spec_up_to_date() {
+    zarrs_conventions::test_util::assert_docs_up_to_date(
+        &zarrs_conventions::docgen::ConventionDocs::nested::<LabelMap>(),
+        concat!(env!("CARGO_MANIFEST_DIR"), "/spec"),
+    );
+}
@@ -0,0 +1,94 @@
+use crate::{License, terms::terms};
+
+/// Broad class of a license, ordered from least to most restrictive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Category {
+    /// Rights are waived, e.g. `CC0-1.0`.
+    PublicDomain,
+    /// Reuse is allowed under any terms, subject to attribution, e.g. `MIT`, `CC-BY-4.0`.
+    Permissive,
+    /// Modifications to the licensed data must keep the license, e.g. `MPL-2.0`.
+    WeakCopyleft,
+    /// Derived data must keep the license, e.g. `GPL-3.0-only`, `CC-BY-SA-4.0`.
+    Copyleft,
+    /// Not a free or open license, e.g. `CC-BY-NC-4.0`.
+    Proprietary,
+    /// The license's terms are not known.
+    Unknown,
+}
+
+impl License {
+    /// Broad class of the license, from SPDX metadata.
+    ///
+    /// Licenses given only by the URL of a [KnownLicense](crate::KnownLicense) are classified;
+    /// others without a listed SPDX identifier are [Category::Unknown].
+    ///
+    /// ```
+    /// use zarrs_conventions_license::{License, category::Category};
+    ///
+    /// assert_eq!(License::new_spdx("CC-BY-SA-4.0").category(), Category::Copyleft);
+    /// assert_eq!(License::new_spdx("LicenseRef-mine").category(), Category::Unknown);
+    /// ```
+    pub fn category(&self) -> Category {
+        let Some(terms) = self.identity().and_then(|id| terms(&id)) else {
+            return Category::Unknown;
+        };
+        if terms.public_domain {
+            Category::PublicDomain
+        } else if !terms.free {
+            Category::Proprietary
+        } else if terms.weak_copyleft {
+            Category::WeakCopyleft
+        } else if terms.copyleft {
+            Category::Copyleft
+        } else {
+            Category::Permissive
+        }
+    }
+
+    /// The license with the most restrictive [Category], if any;
+    /// the first of equally restrictive licenses.
+    pub fn most_restrictive<'a>(
+        licenses: impl IntoIterator<Item = &'a License>,
+    ) -> Option<(&'a License, Category)> {
+        licenses
+            .into_iter()
+            .map(|l| (l, l.category()))
+            .reduce(|a, b| if b.1 > a.1 { b } else { a })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Category;
+    use crate::{KnownLicense, License};
+
+    #[test]
+    fn category_classes() {
+        let category = |id: &str| License::new_spdx(id).category();
+        assert_eq!(category("CC0-1.0"), Category::PublicDomain);
+        assert_eq!(category("PDDL-1.0"), Category::PublicDomain);
+        assert_eq!(category("Apache-2.0"), Category::Permissive);
+        assert_eq!(category("ODC-By-1.0"), Category::Permissive);
+        assert_eq!(category("LGPL-2.1-or-later"), Category::WeakCopyleft);
+        assert_eq!(category("ODbL-1.0"), Category::Copyleft);
+        assert_eq!(category("CC-BY-NC-ND-4.0"), Category::Proprietary);
+        assert_eq!(category("not-a-license"), Category::Unknown);
+        assert_eq!(
+            License::new_url(KnownLicense::Mpl2.url().to_owned()).category(),
+            Category::WeakCopyleft
+        );
+
+        let licenses = [
+            License::new_spdx("MIT"),
+            License::new_spdx("GPL-3.0-only"),
+            License::new_spdx("CC0-1.0"),
+        ];
+        let (license, category) = License::most_restrictive(&licenses).unwrap();
+        assert_eq!(
+            (license.spdx(), category),
+            (Some("GPL-3.0-only"), Category::Copyleft)
+        );
+        assert!(License::most_restrictive(&[]).is_none());
+    }
+}
@@ -0,0 +1,292 @@
+//! Whether licensed data may be combined or redistributed.
+//!
+//! These are heuristics based on SPDX metadata, not legal advice.
+use std::fmt;
+
+use crate::{
+    License,
+    terms::{Terms, terms, version},
+};
+
+/// Overall answer to a compatibility question.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Compatible,
+    Incompatible,
+    /// The licenses' terms are not known well enough to answer.
+    Unknown,
+}
+
+/// Why a [Verdict] was reached.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reason {
+    /// A license has no SPDX identifier, so its terms are not known.
+    NoSpdx,
+    /// A license's identifier is not on the SPDX license list.
+    UnknownSpdx(String),
+    /// A license is not known to be a free or open license.
+    NotFree(String),
+    /// Both licenses are the same.
+    Identical,
+    /// The license's terms allow relicensing, subject to attribution.
+    Permissive(String),
+    /// Combined or derived data must be distributed under this license.
+    Copyleft(String),
+    /// The first license allows use of the second, a later version.
+    LaterVersion { from: String, to: String },
+    /// Both licenses require derived data to use them.
+    ConflictingCopyleft(String, String),
+    /// The license does not allow derived data.
+    NoDerivatives(String),
+    /// The license does not allow commercial use.
+    NonCommercial(String),
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoSpdx => write!(f, "license has no SPDX identifier"),
+            Self::UnknownSpdx(id) => write!(f, "'{id}' is not on the SPDX license list"),
+            Self::NotFree(id) => write!(f, "{id} is not a known free or open license"),
+            Self::Identical => write!(f, "licenses are identical"),
+            Self::Permissive(id) => write!(f, "{id} is permissive"),
+            Self::Copyleft(id) => write!(f, "derived data must be distributed under {id}"),
+            Self::LaterVersion { from, to } => write!(f, "{from} allows use of {to}"),
+            Self::ConflictingCopyleft(a, b) => {
+                write!(f, "{a} and {b} both require derived data to use them")
+            }
+            Self::NoDerivatives(id) => write!(f, "{id} does not allow derived data"),
+            Self::NonCommercial(id) => write!(f, "{id} does not allow commercial use"),
+        }
+    }
+}
+
+/// Answer to a compatibility question, with the reasons for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Compatibility {
+    pub verdict: Verdict,
+    pub reasons: Vec<Reason>,
+}
+
+impl Compatibility {
+    fn new(verdict: Verdict, reasons: Vec<Reason>) -> Self {
+        Self { verdict, reasons }
+    }
+
+    pub fn is_compatible(&self) -> bool {
+        self.verdict == Verdict::Compatible
+    }
+}
+
+/// Terms of a license, or why they are not known.
+fn known_terms(license: &License) -> Result<Terms, Reason> {
+    let id = license.identity().ok_or(Reason::NoSpdx)?;
+    terms(&id).ok_or(Reason::UnknownSpdx(id))
+}
+
+/// Whether `from` allows use of the later version `to` of the same license.
+fn allows_later(from: &Terms, to: &Terms) -> bool {
+    match (version(from.id), version(to.id)) {
+        (Some((fam_from, ver_from)), Some((fam_to, ver_to))) => {
+            from.or_later && fam_from == fam_to && ver_to >= ver_from
+        }
+        _ => false,
+    }
+}
+
+impl License {
+    /// Whether data under this license and `other` may be combined into one dataset.
+    ///
+    /// Licenses are identified by their SPDX identifier or [known](crate::KnownLicense) URL;
+    /// [the same](License::same_license) license is always compatible with itself.
+    ///
+    /// ```
+    /// use zarrs_conventions_license::{License, compat::{Reason, Verdict}};
+    ///
+    /// let compat = License::new_spdx("MIT").compatible_with(&License::new_spdx("CC-BY-SA-4.0"));
+    /// assert_eq!(compat.verdict, Verdict::Compatible);
+    /// assert!(compat.reasons.contains(&Reason::Copyleft("CC-BY-SA-4.0".into())));
+    /// ```
+    pub fn compatible_with(&self, other: &License) -> Compatibility {
+        if self.same_license(other) {
+            return Compatibility::new(Verdict::Compatible, vec![Reason::Identical]);
+        }
+        let (a, b) = match (known_terms(self), known_terms(other)) {
+            (Ok(a), Ok(b)) => (a, b),
+            (a, b) => {
+                let reasons = [a.err(), b.err()].into_iter().flatten().collect();
+                return Compatibility::new(Verdict::Unknown, reasons);
+            }
+        };
+        if a.id == b.id {
+            return Compatibility::new(Verdict::Compatible, vec![Reason::Identical]);
+        }
+        let nd: Vec<_> = [a, b]
+            .iter()
+            .filter(|t| t.no_derivatives)
+            .map(|t| Reason::NoDerivatives(t.id.to_string()))
+            .collect();
+        if !nd.is_empty() {
+            return Compatibility::new(Verdict::Incompatible, nd);
+        }
+        let mut reasons = Vec::default();
+        let mut unknown = false;
+        for t in [a, b] {
+            if t.non_commercial {
+                reasons.push(Reason::NonCommercial(t.id.to_string()));
+            } else if !t.free {
+                unknown = true;
+                reasons.push(Reason::NotFree(t.id.to_string()));
+            }
+        }
+        match (a.copyleft, b.copyleft) {
+            (true, true) if allows_later(&a, &b) => reasons.push(Reason::LaterVersion {
+                from: a.id.to_string(),
+                to: b.id.to_string(),
+            }),
+            (true, true) if allows_later(&b, &a) => reasons.push(Reason::LaterVersion {
+                from: b.id.to_string(),
+                to: a.id.to_string(),
+            }),
+            (true, true) => {
+                reasons.push(Reason::ConflictingCopyleft(
+                    a.id.to_string(),
+                    b.id.to_string(),
+                ));
+                return Compatibility::new(Verdict::Incompatible, reasons);
+            }
+            (true, false) => reasons.push(Reason::Copyleft(a.id.to_string())),
+            (false, true) => reasons.push(Reason::Copyleft(b.id.to_string())),
+            (false, false) => {
+                reasons.push(Reason::Permissive(a.id.to_string()));
+                reasons.push(Reason::Permissive(b.id.to_string()));
+            }
+        }
+        let verdict = if unknown {
+            Verdict::Unknown
+        } else {
+            Verdict::Compatible
+        };
+        Compatibility::new(verdict, reasons)
+    }
+
+    /// Whether data under this license may be redistributed under the given SPDX license.
+    ///
+    /// ```
+    /// use zarrs_conventions_license::License;
+    ///
+    /// assert!(License::new_spdx("CC0-1.0").is_redistributable_under("CC-BY-4.0").is_compatible());
+    /// assert!(!License::new_spdx("CC-BY-SA-4.0").is_redistributable_under("MIT").is_compatible());
+    /// ```
+    pub fn is_redistributable_under(&self, spdx_id: &str) -> Compatibility {
+        let target = License::new_spdx(spdx_id);
+        let (from, to) = match (known_terms(self), known_terms(&target)) {
+            (Ok(a), Ok(b)) => (a, b),
+            (a, b) => {
+                let reasons = [a.err(), b.err()].into_iter().flatten().collect();
+                return Compatibility::new(Verdict::Unknown, reasons);
+            }
+        };
+        if from.id == to.id {
+            return Compatibility::new(Verdict::Compatible, vec![Reason::Identical]);
+        }
+        if from.no_derivatives {
+            return Compatibility::new(
+                Verdict::Incompatible,
+                vec![Reason::NoDerivatives(from.id.to_string())],
+            );
+        }
+        if from.non_commercial && !to.non_commercial {
+            return Compatibility::new(
+                Verdict::Incompatible,
+                vec![Reason::NonCommercial(from.id.to_string())],
+            );
+        }
+        if from.copyleft {
+            return if allows_later(&from, &to) {
+                Compatibility::new(
+                    Verdict::Compatible,
+                    vec![Reason::LaterVersion {
+                        from: from.id.to_string(),
+                        to: to.id.to_string(),
+                    }],
+                )
+            } else {
+                Compatibility::new(
+                    Verdict::Incompatible,
+                    vec![Reason::Copyleft(from.id.to_string())],
+                )
+            };
+        }
+        if !from.free {
+            return Compatibility::new(
+                Verdict::Unknown,
+                vec![Reason::NotFree(from.id.to_string())],
+            );
+        }
+        Compatibility::new(
+            Verdict::Compatible,
+            vec![Reason::Permissive(from.id.to_string())],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Reason, Verdict};
+    use crate::License;
+
+    fn verdict(a: &str, b: &str) -> Verdict {
+        License::new_spdx(a)
+            .compatible_with(&License::new_spdx(b))
+            .verdict
+    }
+
+    #[test]
+    fn compat_combine() {
+        assert_eq!(verdict("MIT", "MIT"), Verdict::Compatible);
+        assert_eq!(verdict("CC0-1.0", "CC-BY-4.0"), Verdict::Compatible);
+        assert_eq!(verdict("CC-BY-SA-4.0", "ODbL-1.0"), Verdict::Incompatible);
+        assert_eq!(
+            verdict("GPL-2.0-or-later", "GPL-3.0-only"),
+            Verdict::Compatible
+        );
+        assert_eq!(
+            verdict("GPL-2.0-only", "GPL-3.0-only"),
+            Verdict::Incompatible
+        );
+        assert_eq!(verdict("CC-BY-ND-4.0", "MIT"), Verdict::Incompatible);
+        assert_eq!(verdict("MIT", "NotALicense"), Verdict::Unknown);
+
+        let compat = License::new_spdx("CC-BY-NC-4.0").compatible_with(&License::new_spdx("MIT"));
+        assert_eq!(compat.verdict, Verdict::Compatible);
+        assert_eq!(
+            compat.reasons[0],
+            Reason::NonCommercial("CC-BY-NC-4.0".into())
+        );
+
+        let url = License::new_url("https://example.com/license".parse().unwrap());
+        assert_eq!(
+            url.compatible_with(&License::new_spdx("MIT")).reasons,
+            [Reason::NoSpdx]
+        );
+        let mit_url = License::new_url("https://opensource.org/license/mit".parse().unwrap());
+        let compat = mit_url.compatible_with(&License::new_spdx("MIT"));
+        assert_eq!(compat.reasons, [Reason::Identical]);
+        assert_eq!(
+            mit_url
+                .compatible_with(&License::new_spdx("CC-BY-SA-4.0"))
+                .verdict,
+            Verdict::Compatible
+        );
+    }
+
+    #[test]
+    fn compat_redistribute() {
+        let check = |from: &str, to: &str| License::new_spdx(from).is_redistributable_under(to);
+        assert!(check("MIT", "CC-BY-SA-4.0").is_compatible());
+        assert!(check("GPL-2.0+", "GPL-3.0-or-later").is_compatible());
+        assert!(!check("CC-BY-NC-4.0", "CC-BY-4.0").is_compatible());
+        assert_eq!(check("PDDL-1.0", "CC0-1.0").verdict, Verdict::Compatible);
+    }
+}
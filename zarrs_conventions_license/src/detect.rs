@@ -0,0 +1,106 @@
+//! Identify a license from its text by similarity to the SPDX license texts.
+use std::{collections::HashSet, sync::OnceLock};
+
+use crate::{
+    License,
+    similarity::{bigrams, similarity},
+};
+
+/// A match between license text and an SPDX-listed license.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Detection {
+    /// SPDX identifier of the best-matching license.
+    pub spdx: &'static str,
+    /// Similarity between the texts, from 0 (nothing in common) to 1 (identical).
+    pub score: f32,
+}
+
+/// Bigrams of every non-deprecated SPDX license text, computed on first use.
+fn corpus() -> &'static [(&'static str, HashSet<u64>)] {
+    static CORPUS: OnceLock<Vec<(&'static str, HashSet<u64>)>> = OnceLock::new();
+    CORPUS.get_or_init(|| {
+        spdx::identifiers::LICENSES
+            .iter()
+            .filter_map(|(name, ..)| spdx::license_id(name))
+            .filter(|lic| !lic.is_deprecated())
+            .map(|lic| (lic.name, bigrams(lic.text())))
+            .collect()
+    })
+}
+
+impl License {
+    /// The SPDX-listed license whose text is most similar to the given text.
+    ///
+    /// Licenses with identical texts (e.g. `-only` and `-or-later` variants)
+    /// cannot be distinguished; the first alphabetically is returned.
+    /// The first call indexes the full SPDX corpus, which takes some time.
+    ///
+    /// ```
+    /// use zarrs_conventions_license::License;
+    ///
+    /// let text = License::new_spdx("MIT").resolve_text().unwrap().replace("<year>", "2025");
+    /// let detection = License::detect_from_text(&text).unwrap();
+    /// assert_eq!(detection.spdx, "MIT");
+    /// assert!(detection.score > 0.95);
+    /// ```
+    pub fn detect_from_text(text: &str) -> Option<Detection> {
+        let query = bigrams(text);
+        if query.is_empty() {
+            return None;
+        }
+        corpus()
+            .iter()
+            .map(|(spdx, grams)| Detection {
+                spdx,
+                score: similarity(&query, grams),
+            })
+            .reduce(|best, d| if d.score > best.score { d } else { best })
+    }
+
+    /// Add an SPDX identifier detected from the license's own text,
+    /// if it has none and the match scores at least `threshold`.
+    ///
+    /// Text in a `file` must be loaded first, e.g. with [License::resolve_file].
+    pub fn with_detected_spdx(mut self, threshold: f32) -> Self {
+        if self.spdx().is_none()
+            && let Some(detection) = self.text().and_then(License::detect_from_text)
+            && detection.score >= threshold
+        {
+            self.0.spdx = Some(detection.spdx.to_string());
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::License;
+
+    fn text(spdx: &str) -> String {
+        License::new_spdx(spdx).resolve_text().unwrap().to_string()
+    }
+
+    #[test]
+    fn detect_licenses() {
+        let apache = text("Apache-2.0");
+        let reflowed = apache.split_whitespace().collect::<Vec<_>>().join(" ");
+        let detected = License::detect_from_text(&reflowed.to_uppercase()).unwrap();
+        assert_eq!(detected.spdx, "Apache-2.0");
+        assert!(detected.score > 0.99);
+
+        let headed = format!("Copyright 2025 Example Ltd.\n\n{apache}");
+        assert_eq!(
+            License::detect_from_text(&headed).unwrap().spdx,
+            "Apache-2.0"
+        );
+
+        let unrelated = License::detect_from_text("the quick brown fox jumps over the lazy dog");
+        assert!(unrelated.unwrap().score < 0.1);
+        assert!(License::detect_from_text("!!").is_none());
+
+        let upgraded = License::new_text(text("BSD-3-Clause")).with_detected_spdx(0.9);
+        assert_eq!(upgraded.spdx(), Some("BSD-3-Clause"));
+        let kept = License::new_text("custom terms").with_detected_spdx(0.9);
+        assert_eq!(kept.spdx(), None);
+    }
+}
@@ -0,0 +1,124 @@
+//! Differences between the licenses of two nodes or dataset versions.
+use crate::License;
+
+/// A license present in both versions with differing fields.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Changed {
+    pub old: License,
+    pub new: License,
+    /// Names of the fields which differ, e.g. `url`.
+    pub fields: Vec<&'static str>,
+}
+
+/// Differences between two sets of licenses.
+///
+/// Licenses are matched by [License::same_license],
+/// so adding a URL to an SPDX-identified license is a change, not a relicensing.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LicenseDiff {
+    pub added: Vec<License>,
+    pub removed: Vec<License>,
+    pub changed: Vec<Changed>,
+}
+
+impl LicenseDiff {
+    /// Whether there are no differences.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// Whether licenses were added or removed, rather than only re-expressed.
+    pub fn is_relicensing(&self) -> bool {
+        !self.added.is_empty() || !self.removed.is_empty()
+    }
+}
+
+/// Names of the fields which differ between two licenses.
+fn changed_fields(old: &License, new: &License) -> Vec<&'static str> {
+    let (a, b) = (&old.0, &new.0);
+    let mut fields = vec![
+        ("spdx", a.spdx != b.spdx),
+        ("url", a.url != b.url),
+        ("text", a.text != b.text),
+        ("file", a.file != b.file),
+        ("path", a.path != b.path),
+    ];
+    #[cfg(feature = "attribution")]
+    fields.extend([
+        ("copyright_holder", a.copyright_holder != b.copyright_holder),
+        ("year", a.year != b.year),
+    ]);
+    fields.push(("extras", a.extras != b.extras));
+    fields
+        .into_iter()
+        .filter_map(|(name, differs)| differs.then_some(name))
+        .collect()
+}
+
+impl License {
+    /// Differences from this license to `new`.
+    ///
+    /// ```
+    /// use zarrs_conventions_license::{KnownLicense, License};
+    ///
+    /// let diff = License::new_spdx("MIT").diff(&License::from(KnownLicense::Mit));
+    /// assert!(!diff.is_relicensing());
+    /// assert_eq!(diff.changed[0].fields, ["url"]);
+    ///
+    /// let diff = License::new_spdx("MIT").diff(&License::new_spdx("Apache-2.0"));
+    /// assert!(diff.is_relicensing());
+    /// ```
+    pub fn diff(&self, new: &License) -> LicenseDiff {
+        License::diff_all(std::slice::from_ref(self), std::slice::from_ref(new))
+    }
+
+    /// Differences from the `old` licenses to the `new` ones.
+    pub fn diff_all(old: &[License], new: &[License]) -> LicenseDiff {
+        let mut diff = LicenseDiff::default();
+        let mut unmatched: Vec<&License> = new.iter().collect();
+        for o in old {
+            match unmatched.iter().position(|n| o.same_license(n)) {
+                Some(idx) => {
+                    let n = unmatched.remove(idx);
+                    let fields = changed_fields(o, n);
+                    if !fields.is_empty() {
+                        diff.changed.push(Changed {
+                            old: o.clone(),
+                            new: n.clone(),
+                            fields,
+                        });
+                    }
+                }
+                None => diff.removed.push(o.clone()),
+            }
+        }
+        diff.added = unmatched.into_iter().cloned().collect();
+        diff
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{KnownLicense, License};
+
+    #[test]
+    fn diff_versions() {
+        let old = [License::new_spdx("MIT"), License::new_file("NOTICE")];
+        assert!(License::diff_all(&old, &old).is_empty());
+
+        let new = [
+            License::new_file("NOTICE"),
+            License::from(KnownLicense::Mit),
+            License::new_spdx("CC-BY-4.0"),
+        ];
+        let diff = License::diff_all(&old, &new);
+        assert_eq!(diff.added, [License::new_spdx("CC-BY-4.0")]);
+        assert!(diff.removed.is_empty());
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].new, License::from(KnownLicense::Mit));
+
+        let diff = License::diff_all(&new, &old[1..]);
+        assert_eq!(diff.removed.len(), 2);
+        assert!(diff.is_relicensing());
+    }
+}
@@ -0,0 +1,114 @@
+use serde_json::{Map, Value, json};
+
+use crate::License;
+
+/// URL of the SPDX license list page for an identifier.
+fn spdx_url(id: &str) -> String {
+    format!("https://spdx.org/licenses/{id}")
+}
+
+/// Full name of the license, if it can be determined.
+fn full_name(license: &License) -> Option<String> {
+    let id = license.spdx()?;
+    #[cfg(feature = "spdx")]
+    if let Some(lic) = spdx::license_id(id) {
+        return Some(lic.full_name.to_string());
+    }
+    Some(id.to_string())
+}
+
+impl License {
+    /// Entry for the `rightsList` of DataCite metadata (REST API JSON form).
+    ///
+    /// SPDX identifiers are given as the `rightsIdentifier` with the SPDX scheme;
+    /// `rightsUri` is the license's URL, or otherwise its SPDX license list page.
+    ///
+    /// ```
+    /// use serde_json::json;
+    /// use zarrs_conventions_license::{KnownLicense, License};
+    ///
+    /// let rights = License::from(KnownLicense::CcBy4).to_datacite_rights();
+    /// assert_eq!(rights["rightsIdentifier"], "cc-by-4.0");
+    /// assert_eq!(rights["rightsUri"], "https://creativecommons.org/licenses/by/4.0/");
+    /// ```
+    pub fn to_datacite_rights(&self) -> Map<String, Value> {
+        let mut rights = Map::default();
+        rights.insert(
+            "rights".into(),
+            full_name(self)
+                .unwrap_or_else(|| self.summary(false))
+                .into(),
+        );
+        let uri = self
+            .url()
+            .map(|u| u.to_string())
+            .or_else(|| self.spdx().map(spdx_url));
+        if let Some(uri) = uri {
+            rights.insert("rightsUri".into(), uri.into());
+        }
+        if let Some(id) = self.spdx() {
+            rights.insert("rightsIdentifier".into(), id.to_lowercase().into());
+            rights.insert("rightsIdentifierScheme".into(), "SPDX".into());
+            rights.insert("schemeUri".into(), "https://spdx.org/licenses/".into());
+        }
+        rights
+    }
+
+    /// Value for the schema.org `license` property:
+    /// a URL if the license has an SPDX identifier or URL, otherwise a `CreativeWork`.
+    ///
+    /// ```
+    /// use serde_json::json;
+    /// use zarrs_conventions_license::License;
+    ///
+    /// assert_eq!(
+    ///     License::new_spdx("MIT").to_schema_org(),
+    ///     json!("https://spdx.org/licenses/MIT")
+    /// );
+    /// ```
+    pub fn to_schema_org(&self) -> Value {
+        if let Some(id) = self.spdx() {
+            return spdx_url(id).into();
+        }
+        if let Some(url) = self.url() {
+            return url.to_string().into();
+        }
+        let mut work = json!({"@type": "CreativeWork"});
+        if let Some(text) = self.text() {
+            work["text"] = text.into();
+        } else if let Some(path) = self.file().or(self.path()) {
+            work["name"] = path.into();
+        }
+        work
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::License;
+
+    #[test]
+    fn export_rights() {
+        let rights = License::new_spdx("MIT").to_datacite_rights();
+        #[cfg(feature = "spdx")]
+        assert_eq!(rights["rights"], "MIT License");
+        assert_eq!(rights["rightsUri"], "https://spdx.org/licenses/MIT");
+        assert_eq!(rights["rightsIdentifierScheme"], "SPDX");
+
+        let custom = License::new_text("All rights reserved");
+        assert_eq!(
+            serde_json::Value::Object(custom.to_datacite_rights()),
+            json!({"rights": "custom license"})
+        );
+        assert_eq!(
+            custom.to_schema_org(),
+            json!({"@type": "CreativeWork", "text": "All rights reserved"})
+        );
+        assert_eq!(
+            License::new_file("LICENSE").to_schema_org(),
+            json!({"@type": "CreativeWork", "name": "LICENSE"})
+        );
+    }
+}
@@ -0,0 +1,321 @@
+//! SPDX license expressions, e.g. `MIT OR Apache-2.0`.
+//!
+//! The convention's `spdx` field holds a single license,
+//! so compound expressions are expanded into one [License] per license they mention.
+//! That list does not record whether all (`AND`) or any (`OR`) of the licenses apply,
+//! and license items have no field for a `WITH` exception.
+use std::{fmt, str::FromStr};
+
+use crate::License;
+
+/// A single license in an SPDX expression, optionally with an exception.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SpdxLicense {
+    /// License identifier, e.g. `GPL-2.0-only` or `LicenseRef-custom`.
+    pub id: String,
+    /// Whether the license is followed by `+`, i.e. "or any later version".
+    pub or_later: bool,
+    /// Exception identifier following `WITH`.
+    pub exception: Option<String>,
+}
+
+impl SpdxLicense {
+    /// The license without any exception, e.g. `GPL-2.0+`.
+    pub fn identifier(&self) -> String {
+        if self.or_later {
+            format!("{}+", self.id)
+        } else {
+            self.id.clone()
+        }
+    }
+}
+
+impl fmt::Display for SpdxLicense {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.identifier())?;
+        if let Some(exception) = &self.exception {
+            write!(f, " WITH {exception}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Parsed SPDX license expression.
+///
+/// ```
+/// use zarrs_conventions_license::SpdxExpression;
+///
+/// let expr: SpdxExpression = "MIT OR (Apache-2.0 AND GPL-2.0-or-later WITH Classpath-exception-2.0)"
+///     .parse()
+///     .unwrap();
+/// assert_eq!(expr.licenses().len(), 3);
+/// assert_eq!(
+///     expr.to_string(),
+///     "MIT OR Apache-2.0 AND GPL-2.0-or-later WITH Classpath-exception-2.0"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SpdxExpression {
+    License(SpdxLicense),
+    /// All of the terms apply.
+    And(Vec<SpdxExpression>),
+    /// Any one of the terms may be chosen.
+    Or(Vec<SpdxExpression>),
+}
+
+impl SpdxExpression {
+    /// Parse an expression, accepting upper- or lower-case operators.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let tokens = tokenize(s)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let expr = parser.or()?;
+        match parser.tokens.get(parser.pos) {
+            None => Ok(expr),
+            Some(t) => Err(format!("unexpected '{t}' in SPDX expression '{s}'")),
+        }
+    }
+
+    /// All licenses in the expression, in order of appearance.
+    pub fn licenses(&self) -> Vec<&SpdxLicense> {
+        match self {
+            Self::License(l) => vec![l],
+            Self::And(terms) | Self::Or(terms) => terms.iter().flat_map(|t| t.licenses()).collect(),
+        }
+    }
+
+    /// One license item per license in the expression, in order of appearance.
+    ///
+    /// The list is flat: it does not say whether all (`AND`) or any (`OR`) of the licenses apply,
+    /// so keep the expression itself where that matters.
+    ///
+    /// Fails if a license has a `WITH` exception, which a license item cannot hold.
+    pub fn to_licenses(&self) -> Result<Vec<License>, String> {
+        self.licenses()
+            .into_iter()
+            .map(|l| match &l.exception {
+                Some(_) => Err(format!(
+                    "license item cannot hold the exception in '{l}'; use the SPDX expression"
+                )),
+                None => Ok(License::new_spdx(l.identifier())),
+            })
+            .collect()
+    }
+
+    fn fmt_term(&self, f: &mut fmt::Formatter<'_>, parent_and: bool) -> fmt::Result {
+        let (terms, op) = match self {
+            Self::License(l) => return write!(f, "{l}"),
+            Self::And(terms) => (terms, " AND "),
+            Self::Or(terms) => (terms, " OR "),
+        };
+        let parens = parent_and && matches!(self, Self::Or(_));
+        if parens {
+            write!(f, "(")?;
+        }
+        for (idx, term) in terms.iter().enumerate() {
+            if idx > 0 {
+                write!(f, "{op}")?;
+            }
+            term.fmt_term(f, matches!(self, Self::And(_)))?;
+        }
+        if parens {
+            write!(f, ")")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for SpdxExpression {
+    /// Render the expression, with parentheses only where precedence requires them.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_term(f, false)
+    }
+}
+
+impl FromStr for SpdxExpression {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl From<SpdxLicense> for SpdxExpression {
+    fn from(value: SpdxLicense) -> Self {
+        Self::License(value)
+    }
+}
+
+fn tokenize(s: &str) -> Result<Vec<String>, String> {
+    let mut tokens = Vec::default();
+    for word in s.split_whitespace() {
+        let mut rest = word;
+        while !rest.is_empty() {
+            let end = rest.find(['(', ')']).unwrap_or(rest.len());
+            if end == 0 {
+                tokens.push(rest[..1].to_string());
+                rest = &rest[1..];
+                continue;
+            }
+            let token = &rest[..end];
+            if !token
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '+' | ':'))
+            {
+                return Err(format!("invalid characters in '{token}'"));
+            }
+            tokens.push(token.to_string());
+            rest = &rest[end..];
+        }
+    }
+    Ok(tokens)
+}
+
+fn is_op(token: &str, op: &str) -> bool {
+    token == op || token == op.to_ascii_lowercase()
+}
+
+struct Parser {
+    tokens: Vec<String>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek_op(&self, op: &str) -> bool {
+        self.tokens.get(self.pos).is_some_and(|t| is_op(t, op))
+    }
+
+    fn next(&mut self, expected: &str) -> Result<&str, String> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .ok_or_else(|| format!("expected {expected}, found end of expression"))?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn or(&mut self) -> Result<SpdxExpression, String> {
+        let mut terms = vec![self.and()?];
+        while self.peek_op("OR") {
+            self.pos += 1;
+            terms.push(self.and()?);
+        }
+        Ok(flatten(terms, false))
+    }
+
+    fn and(&mut self) -> Result<SpdxExpression, String> {
+        let mut terms = vec![self.atom()?];
+        while self.peek_op("AND") {
+            self.pos += 1;
+            terms.push(self.atom()?);
+        }
+        Ok(flatten(terms, true))
+    }
+
+    fn atom(&mut self) -> Result<SpdxExpression, String> {
+        let token = self.next("a license or '('")?.to_string();
+        if token == "(" {
+            let inner = self.or()?;
+            return match self.next("')'")? {
+                ")" => Ok(inner),
+                t => Err(format!("expected ')', found '{t}'")),
+            };
+        }
+        if token == ")" || ["AND", "OR", "WITH"].iter().any(|op| is_op(&token, op)) {
+            return Err(format!("expected a license, found '{token}'"));
+        }
+        let (id, or_later) = match token.strip_suffix('+') {
+            Some(id) => (id.to_string(), true),
+            None => (token, false),
+        };
+        if id.is_empty() || id.contains('+') {
+            return Err(format!("invalid license identifier '{id}'"));
+        }
+        let exception = if self.peek_op("WITH") {
+            self.pos += 1;
+            let exc = self.next("an exception")?;
+            if exc == "(" || exc == ")" {
+                return Err(format!("expected an exception, found '{exc}'"));
+            }
+            Some(exc.to_string())
+        } else {
+            None
+        };
+        Ok(SpdxExpression::License(SpdxLicense {
+            id,
+            or_later,
+            exception,
+        }))
+    }
+}
+
+/// Combine terms with `AND` or `OR`, merging parenthesised operations of the same kind.
+fn flatten(terms: Vec<SpdxExpression>, and: bool) -> SpdxExpression {
+    if terms.len() == 1 {
+        return terms.into_iter().next().expect("one term");
+    }
+    let mut out = Vec::with_capacity(terms.len());
+    for term in terms {
+        match term {
+            SpdxExpression::And(inner) if and => out.extend(inner),
+            SpdxExpression::Or(inner) if !and => out.extend(inner),
+            term => out.push(term),
+        }
+    }
+    if and {
+        SpdxExpression::And(out)
+    } else {
+        SpdxExpression::Or(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SpdxExpression, SpdxLicense};
+    use crate::License;
+
+    #[test]
+    fn expression_parse_render() {
+        let expr =
+            SpdxExpression::parse("(MIT or Apache-2.0) AND GPL-2.0+ WITH Bison-exception-2.2")
+                .unwrap();
+        let SpdxExpression::And(terms) = &expr else {
+            panic!("expected conjunction, got {expr:?}");
+        };
+        assert!(matches!(terms[0], SpdxExpression::Or(_)));
+        assert_eq!(
+            terms[1],
+            SpdxExpression::License(SpdxLicense {
+                id: "GPL-2.0".into(),
+                or_later: true,
+                exception: Some("Bison-exception-2.2".into()),
+            })
+        );
+        assert_eq!(
+            expr.to_string(),
+            "(MIT OR Apache-2.0) AND GPL-2.0+ WITH Bison-exception-2.2"
+        );
+        assert_eq!(
+            SpdxExpression::parse("A OR (B OR C)").unwrap(),
+            SpdxExpression::parse("A OR B OR C").unwrap()
+        );
+
+        for bad in ["", "MIT OR", "(MIT", "MIT)", "AND MIT", "MIT WITH", "M$T"] {
+            assert!(SpdxExpression::parse(bad).is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn expression_to_licenses() {
+        let items = License::from_spdx_expression("MIT OR Apache-2.0").unwrap();
+        let ids: Vec<_> = items.iter().map(|l| l.spdx().unwrap()).collect();
+        assert_eq!(ids, ["MIT", "Apache-2.0"]);
+
+        let items = License::from_spdx_expression("MIT AND GPL-2.0+").unwrap();
+        let ids: Vec<_> = items.iter().map(|l| l.spdx().unwrap()).collect();
+        assert_eq!(ids, ["MIT", "GPL-2.0+"]);
+
+        let err =
+            License::from_spdx_expression("MIT OR GPL-2.0+ WITH Bison-exception-2.2").unwrap_err();
+        assert!(err.contains("GPL-2.0+ WITH Bison-exception-2.2"), "{err}");
+    }
+}
@@ -0,0 +1,188 @@
+//! Download license text from a license's URL.
+use std::io::Read;
+
+use reqwest::{
+    blocking::Client,
+    header::{CONTENT_LENGTH, CONTENT_TYPE},
+};
+
+use crate::License;
+
+/// Default limit on the size of downloaded license text, in bytes.
+pub const DEFAULT_MAX_BYTES: u64 = 1 << 20;
+
+/// Why license text could not be fetched.
+#[derive(Debug, thiserror::Error)]
+pub enum FetchError {
+    #[error("license has no URL")]
+    NoUrl,
+    #[error("request failed: {0}")]
+    Request(#[from] reqwest::Error),
+    #[error("could not read response: {0}")]
+    Read(#[from] std::io::Error),
+    #[error("server responded with status {0}")]
+    Status(u16),
+    #[error("content type '{0}' is not text")]
+    ContentType(String),
+    #[error("content is larger than {0} bytes")]
+    TooLarge(u64),
+    #[error("content is not UTF-8 text")]
+    NotText,
+}
+
+/// License text downloaded from a URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fetched {
+    /// Content type reported by the server, without parameters.
+    pub content_type: Option<String>,
+    pub text: String,
+}
+
+impl Fetched {
+    /// Whether the content is an HTML page rather than plain text.
+    pub fn is_html(&self) -> bool {
+        self.content_type
+            .as_deref()
+            .is_some_and(|t| t == "text/html" || t == "application/xhtml+xml")
+    }
+}
+
+/// Whether a content type is textual; unspecified or generic binary types are sniffed instead.
+fn is_textual(content_type: &str) -> Option<bool> {
+    let (kind, sub) = content_type.split_once('/')?;
+    if content_type == "application/octet-stream" {
+        return None;
+    }
+    Some(
+        kind == "text"
+            || sub == "xml"
+            || sub.ends_with("+xml")
+            || sub == "json"
+            || sub.ends_with("+json"),
+    )
+}
+
+impl License {
+    /// Download the content of the license's URL,
+    /// up to [DEFAULT_MAX_BYTES].
+    pub fn fetch_text(&self, client: &Client) -> Result<Fetched, FetchError> {
+        self.fetch_text_limited(client, DEFAULT_MAX_BYTES)
+    }
+
+    /// Download the content of the license's URL, failing if it is larger than `max_bytes`,
+    /// is not a textual content type, or (if the content type is not given) does not look like text.
+    pub fn fetch_text_limited(
+        &self,
+        client: &Client,
+        max_bytes: u64,
+    ) -> Result<Fetched, FetchError> {
+        let url = self.url().ok_or(FetchError::NoUrl)?;
+        let response = client.get(url.as_str()).send()?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(FetchError::Status(status.as_u16()));
+        }
+        let content_type = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| {
+                v.split(';')
+                    .next()
+                    .unwrap_or_default()
+                    .trim()
+                    .to_lowercase()
+            })
+            .filter(|v| !v.is_empty());
+        if let Some(t) = &content_type
+            && is_textual(t) == Some(false)
+        {
+            return Err(FetchError::ContentType(t.clone()));
+        }
+        let length = response
+            .headers()
+            .get(CONTENT_LENGTH)
+            .and_then(|v| v.to_str().ok()?.parse::<u64>().ok());
+        if length.is_some_and(|len| len > max_bytes) {
+            return Err(FetchError::TooLarge(max_bytes));
+        }
+
+        let mut body = Vec::default();
+        response.take(max_bytes + 1).read_to_end(&mut body)?;
+        if body.len() as u64 > max_bytes {
+            return Err(FetchError::TooLarge(max_bytes));
+        }
+        if content_type.as_deref().and_then(is_textual).is_none() && body.contains(&0) {
+            return Err(FetchError::NotText);
+        }
+        let text = String::from_utf8(body).map_err(|_| FetchError::NotText)?;
+        Ok(Fetched { content_type, text })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use reqwest::blocking::Client;
+    use tiny_http::{Header, Response, Server};
+
+    use super::FetchError;
+    use crate::License;
+
+    /// Serve each (path, content type, body) once, in any order.
+    fn serve(routes: Vec<(&'static str, Option<&'static str>, Vec<u8>)>) -> String {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", server.server_addr());
+        thread::spawn(move || {
+            for _ in 0..routes.len() {
+                let request = server.recv().unwrap();
+                let (_, content_type, body) = routes
+                    .iter()
+                    .find(|(path, ..)| *path == request.url())
+                    .cloned()
+                    .unwrap_or(("", None, Vec::default()));
+                let mut response = Response::from_data(body);
+                if let Some(t) = content_type {
+                    response.add_header(Header::from_bytes("Content-Type", t).unwrap());
+                }
+                request.respond(response).unwrap();
+            }
+        });
+        base
+    }
+
+    #[test]
+    fn fetch_text() {
+        let base = serve(vec![
+            (
+                "/plain",
+                Some("text/plain; charset=utf-8"),
+                b"MIT License".to_vec(),
+            ),
+            ("/html", Some("text/html"), b"<p>MIT</p>".to_vec()),
+            ("/sniffed", None, b"MIT License".to_vec()),
+            ("/binary", None, vec![0, 1, 2]),
+            ("/pdf", Some("application/pdf"), b"%PDF".to_vec()),
+            ("/big", Some("text/plain"), vec![b'a'; 100]),
+        ]);
+        let client = Client::new();
+        let fetch = |path: &str| {
+            License::new_url(format!("{base}{path}").parse().unwrap())
+                .fetch_text_limited(&client, 50)
+        };
+
+        let plain = fetch("/plain").unwrap();
+        assert_eq!(plain.text, "MIT License");
+        assert_eq!(plain.content_type.as_deref(), Some("text/plain"));
+        assert!(fetch("/html").unwrap().is_html());
+        assert_eq!(fetch("/sniffed").unwrap().text, "MIT License");
+        assert!(matches!(fetch("/binary"), Err(FetchError::NotText)));
+        assert!(matches!(fetch("/pdf"), Err(FetchError::ContentType(_))));
+        assert!(matches!(fetch("/big"), Err(FetchError::TooLarge(50))));
+        assert!(matches!(
+            License::new_spdx("MIT").fetch_text(&client),
+            Err(FetchError::NoUrl)
+        ));
+    }
+}
@@ -0,0 +1,186 @@
+use std::{fmt, str::FromStr};
+
+use zarrs_conventions::iref::{Uri, uri};
+
+use crate::License;
+
+/// Commonly-used licenses, with canonical SPDX identifiers and URLs.
+///
+/// ```
+/// use zarrs_conventions_license::{KnownLicense, License};
+///
+/// let license: License = KnownLicense::CcBy4.into();
+/// assert_eq!(license.spdx(), Some("CC-BY-4.0"));
+/// assert_eq!(
+///     license.url().unwrap().as_str(),
+///     "https://creativecommons.org/licenses/by/4.0/"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum KnownLicense {
+    Mit,
+    Apache2,
+    Bsd2Clause,
+    Bsd3Clause,
+    Mpl2,
+    Gpl3Only,
+    Gpl3OrLater,
+    Lgpl3Only,
+    Unlicense,
+    Cc0,
+    CcBy4,
+    CcBySa4,
+    CcByNc4,
+    CcByNd4,
+    Pddl1,
+    OdcBy1,
+    Odbl1,
+}
+
+impl KnownLicense {
+    pub const ALL: [Self; 17] = [
+        Self::Mit,
+        Self::Apache2,
+        Self::Bsd2Clause,
+        Self::Bsd3Clause,
+        Self::Mpl2,
+        Self::Gpl3Only,
+        Self::Gpl3OrLater,
+        Self::Lgpl3Only,
+        Self::Unlicense,
+        Self::Cc0,
+        Self::CcBy4,
+        Self::CcBySa4,
+        Self::CcByNc4,
+        Self::CcByNd4,
+        Self::Pddl1,
+        Self::OdcBy1,
+        Self::Odbl1,
+    ];
+
+    /// Canonical SPDX identifier.
+    pub const fn spdx(self) -> &'static str {
+        match self {
+            Self::Mit => "MIT",
+            Self::Apache2 => "Apache-2.0",
+            Self::Bsd2Clause => "BSD-2-Clause",
+            Self::Bsd3Clause => "BSD-3-Clause",
+            Self::Mpl2 => "MPL-2.0",
+            Self::Gpl3Only => "GPL-3.0-only",
+            Self::Gpl3OrLater => "GPL-3.0-or-later",
+            Self::Lgpl3Only => "LGPL-3.0-only",
+            Self::Unlicense => "Unlicense",
+            Self::Cc0 => "CC0-1.0",
+            Self::CcBy4 => "CC-BY-4.0",
+            Self::CcBySa4 => "CC-BY-SA-4.0",
+            Self::CcByNc4 => "CC-BY-NC-4.0",
+            Self::CcByNd4 => "CC-BY-ND-4.0",
+            Self::Pddl1 => "PDDL-1.0",
+            Self::OdcBy1 => "ODC-By-1.0",
+            Self::Odbl1 => "ODbL-1.0",
+        }
+    }
+
+    /// Canonical URL of the license text, as published by its steward.
+    pub fn url(self) -> &'static Uri {
+        match self {
+            Self::Mit => uri!("https://opensource.org/license/mit"),
+            Self::Apache2 => uri!("https://www.apache.org/licenses/LICENSE-2.0"),
+            Self::Bsd2Clause => uri!("https://opensource.org/license/bsd-2-clause"),
+            Self::Bsd3Clause => uri!("https://opensource.org/license/bsd-3-clause"),
+            Self::Mpl2 => uri!("https://mozilla.org/MPL/2.0/"),
+            Self::Gpl3Only | Self::Gpl3OrLater => uri!("https://www.gnu.org/licenses/gpl-3.0.html"),
+            Self::Lgpl3Only => uri!("https://www.gnu.org/licenses/lgpl-3.0.html"),
+            Self::Unlicense => uri!("https://unlicense.org/"),
+            Self::Cc0 => uri!("https://creativecommons.org/publicdomain/zero/1.0/"),
+            Self::CcBy4 => uri!("https://creativecommons.org/licenses/by/4.0/"),
+            Self::CcBySa4 => uri!("https://creativecommons.org/licenses/by-sa/4.0/"),
+            Self::CcByNc4 => uri!("https://creativecommons.org/licenses/by-nc/4.0/"),
+            Self::CcByNd4 => uri!("https://creativecommons.org/licenses/by-nd/4.0/"),
+            Self::Pddl1 => uri!("https://opendatacommons.org/licenses/pddl/1-0/"),
+            Self::OdcBy1 => uri!("https://opendatacommons.org/licenses/by/1-0/"),
+            Self::Odbl1 => uri!("https://opendatacommons.org/licenses/odbl/1-0/"),
+        }
+    }
+
+    /// The known license with the given SPDX identifier, ignoring case.
+    pub fn from_spdx(identifier: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|k| k.spdx().eq_ignore_ascii_case(identifier))
+    }
+
+    /// The known license with the given canonical URL,
+    /// ignoring scheme, a leading `www.`, and a trailing slash.
+    pub fn from_url(url: &Uri) -> Option<Self> {
+        fn key(url: &str) -> &str {
+            let url = url.split_once("://").map_or(url, |(_, rest)| rest);
+            let url = url.strip_prefix("www.").unwrap_or(url);
+            url.strip_suffix('/').unwrap_or(url)
+        }
+        let url = key(url.as_str());
+        Self::ALL
+            .into_iter()
+            .find(|k| key(k.url().as_str()).eq_ignore_ascii_case(url))
+    }
+}
+
+impl fmt::Display for KnownLicense {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.spdx())
+    }
+}
+
+impl FromStr for KnownLicense {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_spdx(s).ok_or_else(|| format!("'{s}' is not a well-known license"))
+    }
+}
+
+impl From<KnownLicense> for License {
+    fn from(value: KnownLicense) -> Self {
+        License::builder()
+            .spdx(value.spdx())
+            .url(value.url().to_owned())
+            .build()
+            .expect("known licenses are valid")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zarrs_conventions::iref::uri;
+
+    use super::KnownLicense;
+    use crate::{License, canonical_url};
+
+    #[test]
+    fn known_roundtrip() {
+        for known in KnownLicense::ALL {
+            assert_eq!(known.to_string().parse(), Ok(known));
+            let license = License::from(known);
+            assert_eq!(license.spdx(), Some(known.spdx()));
+            assert_eq!(canonical_url(known.url()), known.url());
+        }
+        assert_eq!(
+            KnownLicense::from_spdx("apache-2.0"),
+            Some(KnownLicense::Apache2)
+        );
+        assert!("GPL-2.0-only".parse::<KnownLicense>().is_err());
+        assert_eq!(
+            KnownLicense::from_url(uri!("http://creativecommons.org/licenses/by/4.0")),
+            Some(KnownLicense::CcBy4)
+        );
+    }
+
+    #[cfg(feature = "spdx")]
+    #[test]
+    fn known_are_spdx() {
+        for known in KnownLicense::ALL {
+            assert!(License::from(known).is_valid_spdx(), "{known}");
+        }
+    }
+}
@@ -1,5 +1,6 @@
 #![doc = include_str!("../README.md")]
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 pub use zarrs_conventions;
 use zarrs_conventions::{
     ConventionDefinition, NestedRepr, ZarrConventionImpl,
@@ -7,8 +8,43 @@ use zarrs_conventions::{
     register_zarr_conventions, uuid,
 };
 
+#[cfg(feature = "spdx")]
+pub mod category;
+#[cfg(feature = "spdx")]
+pub mod compat;
+#[cfg(feature = "detect")]
+pub mod detect;
+pub mod diff;
+mod export;
+mod expression;
+#[cfg(feature = "fetch")]
+pub mod fetch;
+pub use expression::{SpdxExpression, SpdxLicense};
+mod known;
+pub use known::KnownLicense;
+mod localized;
+use localized::Text;
+mod loose;
+pub use loose::LooseParse;
+mod normalize;
+pub use normalize::normalize_all;
+pub mod notice;
+pub mod resolve;
+#[cfg(feature = "text")]
+mod similarity;
+pub mod stac;
+mod summary;
+pub use summary::summarize;
+mod url;
+pub use url::canonical_url;
+#[cfg(feature = "spdx")]
+mod terms;
+mod validation;
+
 /// Single license applicable to the data.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Equality compares fields exactly; see [License::same_license].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(try_from = "Inner", into = "Inner")]
 pub struct License(Inner);
 
@@ -24,7 +60,7 @@ impl TryFrom<Inner> for License {
     fn try_from(value: Inner) -> Result<Self, Self::Error> {
         if value.spdx.is_none()
             && value.url.is_none()
-            && value.text.is_none()
+            && value.text.as_ref().is_none_or(Text::is_empty)
             && value.file.is_none()
             && value.path.is_none()
         {
@@ -36,18 +72,55 @@ impl TryFrom<Inner> for License {
 
 /// Inner type used by the [License] and [Builder] types.
 /// May contain incomplete or invalid data (i.e. no identifier).
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
 struct Inner {
     #[serde(skip_serializing_if = "Option::is_none")]
     spdx: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     url: Option<UriBuf>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    text: Option<String>,
+    text: Option<Text>,
     #[serde(skip_serializing_if = "Option::is_none")]
     file: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     path: Option<String>,
+    #[cfg(feature = "attribution")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    copyright_holder: Option<String>,
+    #[cfg(feature = "attribution")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    year: Option<String>,
+    /// Fields not defined by this version of the convention, retained for round-tripping.
+    #[serde(flatten)]
+    extras: Map<String, Value>,
+}
+
+impl Inner {
+    /// Only keep the most preferred form.
+    fn shorten(&mut self) {
+        let mut none = false;
+        if self.spdx.is_some() {
+            none = true;
+        }
+        if none {
+            self.url = None;
+        } else if self.url.is_some() {
+            none = true;
+        }
+        if none {
+            self.text = None;
+        } else if self.text.is_some() {
+            none = true;
+        }
+        if none {
+            self.file = None;
+        } else if self.file.is_some() {
+            none = true;
+        }
+        if none {
+            self.path = None;
+        }
+    }
 }
 
 impl License {
@@ -56,7 +129,14 @@ impl License {
         Builder::default()
     }
 
+    /// Builder for constructing several [License]s at once.
+    pub fn list_builder() -> ListBuilder {
+        ListBuilder::default()
+    }
+
     /// Create a new license item from an SPDX identifier.
+    ///
+    /// The identifier is not checked; see [Self::try_new_spdx].
     pub fn new_spdx<S: Into<String>>(identifier: S) -> Self {
         Self(Inner {
             spdx: Some(identifier.into()),
@@ -64,6 +144,32 @@ impl License {
         })
     }
 
+    /// Create a new license item from an SPDX identifier,
+    /// failing if it is not on the SPDX license list
+    /// (or a user-defined `LicenseRef-` identifier).
+    #[cfg(feature = "spdx")]
+    pub fn try_new_spdx<S: Into<String>>(identifier: S) -> Result<Self, String> {
+        let identifier = identifier.into();
+        validation::check_spdx(&identifier)?;
+        Ok(Self::new_spdx(identifier))
+    }
+
+    /// Whether the license has an SPDX identifier which is on the SPDX license list
+    /// (or is a user-defined `LicenseRef-` identifier).
+    #[cfg(feature = "spdx")]
+    pub fn is_valid_spdx(&self) -> bool {
+        self.spdx().is_some_and(validation::is_known_spdx)
+    }
+
+    /// One license item per license in an SPDX expression, e.g. `MIT OR Apache-2.0`.
+    ///
+    /// The operators are not retained, so the list does not say whether all or any of the licenses apply;
+    /// parse a [SpdxExpression] to keep them.
+    /// Fails on `WITH` exceptions, which a license item cannot hold.
+    pub fn from_spdx_expression(expression: &str) -> Result<Vec<Self>, String> {
+        SpdxExpression::parse(expression)?.to_licenses()
+    }
+
     /// License as an SPDX identifier.
     /// Should not be a multi-license expression.
     pub fn spdx(&self) -> Option<&str> {
@@ -86,14 +192,66 @@ impl License {
     /// Create a new license item from a URL to the license text.
     pub fn new_text<S: Into<String>>(text: S) -> Self {
         Self(Inner {
-            text: Some(text.into()),
+            text: Some(Text::Plain(text.into())),
             ..Default::default()
         })
     }
 
-    /// Full license text.
+    /// Full license text; see [Self::text_in] for text in several languages.
     pub fn text(&self) -> Option<&str> {
-        self.0.text.as_deref()
+        self.0.text.as_ref().map(Text::default_text)
+    }
+
+    /// Create a new license item from license text in several languages,
+    /// by BCP 47 language tag.
+    ///
+    /// This is not part of v1 of the convention.
+    #[cfg(feature = "multilingual")]
+    pub fn new_localized_text<K: Into<String>, V: Into<String>>(
+        texts: impl IntoIterator<Item = (K, V)>,
+    ) -> Self {
+        Self(Inner {
+            text: Some(Text::Localized(
+                texts
+                    .into_iter()
+                    .map(|(k, v)| (k.into(), v.into()))
+                    .collect(),
+            )),
+            ..Default::default()
+        })
+    }
+
+    /// Language tag and full license text best matching the preferred languages,
+    /// falling back to less specific tags, then any variant of `en`, then any language.
+    /// Text given as a plain string has no language tag.
+    #[cfg(feature = "multilingual")]
+    pub fn text_in(&self, preferred: &[&str]) -> Option<(Option<&str>, &str)> {
+        self.0.text.as_ref()?.select(preferred)
+    }
+
+    /// Language tags of the license text, if it is given in several languages.
+    #[cfg(feature = "multilingual")]
+    pub fn languages(&self) -> impl Iterator<Item = &str> {
+        let map = match &self.0.text {
+            Some(Text::Localized(map)) => Some(map),
+            _ => None,
+        };
+        map.into_iter().flat_map(|m| m.keys().map(String::as_str))
+    }
+
+    /// Full license text: the item's own text if present,
+    /// otherwise the canonical text for its SPDX identifier, without network access.
+    ///
+    /// ```
+    /// use zarrs_conventions_license::License;
+    ///
+    /// let license = License::new_spdx("MIT");
+    /// assert!(license.resolve_text().unwrap().starts_with("MIT License"));
+    /// ```
+    #[cfg(feature = "text")]
+    pub fn resolve_text(&self) -> Option<&str> {
+        self.text()
+            .or_else(|| Some(spdx::license_id(self.spdx()?)?.text()))
     }
 
     /// Create a new license item from a relative path to an object containing the license text.
@@ -121,6 +279,80 @@ impl License {
     pub fn path(&self) -> Option<&str> {
         self.0.path.as_deref()
     }
+
+    /// Whom to attribute the data to.
+    ///
+    /// This is not part of v1 of the convention.
+    #[cfg(feature = "attribution")]
+    pub fn copyright_holder(&self) -> Option<&str> {
+        self.0.copyright_holder.as_deref()
+    }
+
+    /// Year or range of years of copyright, e.g. `2024` or `2019-2024`.
+    ///
+    /// This is not part of v1 of the convention.
+    #[cfg(feature = "attribution")]
+    pub fn year(&self) -> Option<&str> {
+        self.0.year.as_deref()
+    }
+
+    /// Copyright notice, e.g. `© 2024 Jane Doe`, if there is a copyright holder.
+    #[cfg(feature = "attribution")]
+    pub fn copyright(&self) -> Option<String> {
+        let holder = self.copyright_holder()?;
+        Some(match self.year() {
+            Some(year) => format!("© {year} {holder}"),
+            None => format!("© {holder}"),
+        })
+    }
+}
+
+/// How to treat fields not defined by the convention when parsing a [License].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ParseMode {
+    /// Fail on unknown fields, and on a URL which cannot locate license text on the web.
+    Strict,
+    /// Discard unknown fields.
+    Lenient,
+    /// Keep unknown fields and write them back out when serializing;
+    /// this is how [License] deserializes.
+    #[default]
+    Passthrough,
+}
+
+impl License {
+    /// Parse license metadata, treating unknown fields according to `mode`.
+    ///
+    /// ```
+    /// use serde_json::json;
+    /// use zarrs_conventions_license::{License, ParseMode};
+    ///
+    /// let value = json!({"spdx": "MIT", "attribution": "Me"});
+    /// assert!(License::parse(&value, ParseMode::Strict).is_err());
+    /// let license = License::parse(&value, ParseMode::Passthrough).unwrap();
+    /// assert_eq!(serde_json::to_value(&license).unwrap(), value);
+    /// ```
+    pub fn parse(value: &Value, mode: ParseMode) -> Result<Self, String> {
+        let mut license = License::deserialize(value).map_err(|e| e.to_string())?;
+        match mode {
+            ParseMode::Strict => {
+                if let Some(key) = license.0.extras.keys().next() {
+                    return Err(format!("unknown license field '{key}'"));
+                }
+                if let Some(url) = &license.0.url {
+                    url::check_url(url)?;
+                }
+            }
+            ParseMode::Lenient => license.0.extras.clear(),
+            ParseMode::Passthrough => (),
+        }
+        Ok(license)
+    }
+
+    /// Fields not defined by this version of the convention.
+    pub fn extras(&self) -> &Map<String, Value> {
+        &self.0.extras
+    }
 }
 
 impl ZarrConventionImpl for License {
@@ -156,27 +388,12 @@ register_zarr_conventions!(License);
 ///     .build()
 ///     .unwrap();
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct Builder {
     inner: Inner,
     short: bool,
 }
 
-impl Default for Builder {
-    fn default() -> Self {
-        Self {
-            inner: Inner {
-                spdx: None,
-                url: None,
-                text: None,
-                file: None,
-                path: None,
-            },
-            short: false,
-        }
-    }
-}
-
 impl Builder {
     /// Shorten the license metadata by only keeping the most preferred form.
     pub fn short(mut self, short: bool) -> Self {
@@ -202,7 +419,22 @@ impl Builder {
     /// Full license text;
     /// preferred over [Self::file] but below [Self::url].
     pub fn text(mut self, text: impl Into<String>) -> Self {
-        self.inner.text = Some(text.into());
+        self.inner.text = Some(Text::Plain(text.into()));
+        self
+    }
+
+    /// Full license text in one language, by BCP 47 language tag;
+    /// replaces text without a language.
+    ///
+    /// This is not part of v1 of the convention.
+    #[cfg(feature = "multilingual")]
+    pub fn localized_text(mut self, language: impl Into<String>, text: impl Into<String>) -> Self {
+        let mut map = match self.inner.text.take() {
+            Some(Text::Localized(map)) => map,
+            _ => Default::default(),
+        };
+        map.insert(language.into(), text.into());
+        self.inner.text = Some(Text::Localized(map));
         self
     }
 
@@ -220,37 +452,110 @@ impl Builder {
         self
     }
 
+    /// Whom to attribute the data to; does not count as a license identifier.
+    ///
+    /// This is not part of v1 of the convention.
+    #[cfg(feature = "attribution")]
+    pub fn copyright_holder(mut self, holder: impl Into<String>) -> Self {
+        self.inner.copyright_holder = Some(holder.into());
+        self
+    }
+
+    /// Year or range of years of copyright; does not count as a license identifier.
+    ///
+    /// This is not part of v1 of the convention.
+    #[cfg(feature = "attribution")]
+    pub fn year(mut self, year: impl Into<String>) -> Self {
+        self.inner.year = Some(year.into());
+        self
+    }
+
     /// Build the license item.
-    /// Fails if no specifiers are set.
+    /// Fails if no specifiers are set, if the URL is not an http(s) URL with a host,
+    /// or with the `spdx` feature, if the SPDX identifier is not known.
     pub fn build(mut self) -> Result<License, String> {
+        #[cfg(feature = "spdx")]
+        if let Some(id) = &self.inner.spdx {
+            validation::check_spdx(id)?;
+        }
+        if let Some(url) = &self.inner.url {
+            url::check_url(url)?;
+        }
         if self.short {
-            let mut none = false;
-            if self.inner.spdx.is_some() {
-                none = true;
-            }
-            if none {
-                self.inner.url = None;
-            } else if self.inner.url.is_some() {
-                none = true;
-            }
-            if none {
-                self.inner.text = None;
-            } else if self.inner.text.is_some() {
-                none = true;
-            }
-            if none {
-                self.inner.file = None;
-            } else if self.inner.file.is_some() {
-                none = true;
-            }
-            if none {
-                self.inner.path = None;
-            }
+            self.inner.shorten();
         }
         self.inner.try_into()
     }
 }
 
+/// Builder for several [License]s, created by [License::list_builder].
+///
+/// ```
+/// use zarrs_conventions_license::{KnownLicense, License};
+///
+/// let licenses = License::list_builder()
+///     .add_spdx("MIT")
+///     .add_known(KnownLicense::CcBy4)
+///     .add_url("https://opensource.org/license/mit".parse().unwrap())
+///     .build()
+///     .unwrap();
+/// assert_eq!(licenses.len(), 2);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ListBuilder {
+    licenses: Vec<License>,
+    error: Option<String>,
+    short: bool,
+}
+
+impl ListBuilder {
+    /// Only keep the most preferred form of each license; see [Builder::short].
+    pub fn short(mut self, short: bool) -> Self {
+        self.short = short;
+        self
+    }
+
+    /// Add a license.
+    pub fn add_license(mut self, license: impl Into<License>) -> Self {
+        self.licenses.push(license.into());
+        self
+    }
+
+    /// Add a license by SPDX identifier;
+    /// with the `spdx` feature, [Self::build] fails if it is not known.
+    pub fn add_spdx(mut self, spdx: impl Into<String>) -> Self {
+        let spdx = spdx.into();
+        #[cfg(feature = "spdx")]
+        let error = validation::check_spdx(&spdx).err();
+        #[cfg(not(feature = "spdx"))]
+        let error = None;
+        self.error = self.error.or(error);
+        self.add_license(License::new_spdx(spdx))
+    }
+
+    /// Add a license by URL to its full text.
+    pub fn add_url(self, url: UriBuf) -> Self {
+        self.add_license(License::new_url(url))
+    }
+
+    /// Add a well-known license, with both its SPDX identifier and URL.
+    pub fn add_known(self, known: KnownLicense) -> Self {
+        self.add_license(known)
+    }
+
+    /// Build the licenses, combined and ordered by [normalize_all].
+    /// Fails if none were added, or any identifier was invalid.
+    pub fn build(self) -> Result<Vec<License>, String> {
+        if let Some(e) = self.error {
+            return Err(e);
+        }
+        if self.licenses.is_empty() {
+            return Err("At least one license must be added".to_string());
+        }
+        Ok(normalize_all(self.licenses, self.short))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use serde_json::json;
@@ -259,7 +564,7 @@ mod tests {
         ZarrConventionImpl,
     };
 
-    use crate::License;
+    use crate::{KnownLicense, License, ParseMode};
 
     #[test]
     fn is_registered() {
@@ -307,4 +612,64 @@ mod tests {
         let _attrs = builder.build().unwrap();
         println!("{_attrs:#}");
     }
+
+    #[test]
+    fn parse_modes() {
+        let value = json!({"spdx": "MIT", "x-custom": [1]});
+        let passthrough: License = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(passthrough.extras()["x-custom"], json!([1]));
+        assert_eq!(serde_json::to_value(&passthrough).unwrap(), value);
+
+        let lenient = License::parse(&value, ParseMode::Lenient).unwrap();
+        assert_eq!(
+            serde_json::to_value(&lenient).unwrap(),
+            json!({"spdx": "MIT"})
+        );
+        let err = License::parse(&value, ParseMode::Strict).unwrap_err();
+        assert!(err.contains("x-custom"));
+
+        assert!(License::parse(&json!({"x-custom": 1}), ParseMode::Passthrough).is_err());
+    }
+
+    #[test]
+    fn parse_non_http_url() {
+        for url in ["ftp://example.com/LICENSE", "urn:isbn:0451450523"] {
+            let value = json!({"url": url});
+            let license = License::parse(&value, ParseMode::Lenient).unwrap();
+            assert_eq!(license.url().unwrap().as_str(), url);
+            assert!(serde_json::from_value::<License>(value.clone()).is_ok());
+            assert!(License::parse(&value, ParseMode::Strict).is_err());
+        }
+    }
+
+    #[test]
+    fn list_builder() {
+        assert!(License::list_builder().build().is_err());
+        let licenses = License::list_builder()
+            .short(true)
+            .add_known(KnownLicense::Apache2)
+            .add_license(License::new_spdx("apache-2.0"))
+            .add_license(License::new_file("NOTICE"))
+            .build()
+            .unwrap();
+        assert_eq!(
+            licenses,
+            [License::new_spdx("Apache-2.0"), License::new_file("NOTICE")]
+        );
+        #[cfg(feature = "spdx")]
+        assert!(License::list_builder().add_spdx("apache").build().is_err());
+    }
+
+    #[cfg(feature = "text")]
+    #[test]
+    fn resolve_text() {
+        assert!(
+            License::new_spdx("Apache-2.0")
+                .resolve_text()
+                .unwrap()
+                .contains("Apache License")
+        );
+        assert_eq!(License::new_text("mine").resolve_text(), Some("mine"));
+        assert_eq!(License::new_spdx("LicenseRef-mine").resolve_text(), None);
+    }
 }
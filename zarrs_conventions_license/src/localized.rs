@@ -0,0 +1,123 @@
+//! License text, optionally in several languages.
+#[cfg(feature = "multilingual")]
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Value of the `text` field.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum Text {
+    Plain(String),
+    /// Text by BCP 47 language tag.
+    #[cfg(feature = "multilingual")]
+    Localized(BTreeMap<String, String>),
+}
+
+impl Text {
+    /// Text in the default language; see [Text::select].
+    pub fn default_text(&self) -> &str {
+        match self {
+            Self::Plain(text) => text,
+            #[cfg(feature = "multilingual")]
+            Self::Localized(_) => self.select(&[]).map_or("", |(_, text)| text),
+        }
+    }
+
+    /// Whether the text has content to show.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Self::Plain(_) => false,
+            #[cfg(feature = "multilingual")]
+            Self::Localized(map) => map.is_empty(),
+        }
+    }
+
+    /// Language tag and text best matching the preferred languages, in order:
+    /// each preferred tag and its less specific forms (`de-CH` then `de`),
+    /// any variant of each preferred tag's primary language,
+    /// then `en`, then any variant of `en` (`en-GB`), then `und`,
+    /// then the first tag alphabetically.
+    /// Plain text has no language tag.
+    #[cfg(feature = "multilingual")]
+    pub fn select(&self, preferred: &[&str]) -> Option<(Option<&str>, &str)> {
+        let map = match self {
+            Self::Plain(text) => return Some((None, text)),
+            Self::Localized(map) => map,
+        };
+        let find = |want: &str| {
+            map.iter()
+                .find(|(tag, _)| tag.eq_ignore_ascii_case(want))
+                .map(|(tag, text)| (Some(tag.as_str()), text.as_str()))
+        };
+        let primary = |tag: &str| {
+            tag.split('-')
+                .next()
+                .unwrap_or_default()
+                .to_ascii_lowercase()
+        };
+        let find_primary = |want: &str| {
+            map.iter()
+                .find(|(tag, _)| primary(tag) == primary(want))
+                .map(|(tag, text)| (Some(tag.as_str()), text.as_str()))
+        };
+        preferred
+            .iter()
+            .find_map(|want| {
+                let mut want = *want;
+                loop {
+                    if let Some(found) = find(want) {
+                        return Some(found);
+                    }
+                    want = want.rsplit_once('-')?.0;
+                }
+            })
+            .or_else(|| preferred.iter().find_map(|want| find_primary(want)))
+            .or_else(|| find("en"))
+            .or_else(|| find_primary("en"))
+            .or_else(|| find("und"))
+            .or_else(|| {
+                map.iter()
+                    .next()
+                    .map(|(tag, text)| (Some(tag.as_str()), text.as_str()))
+            })
+    }
+}
+
+#[cfg(all(test, feature = "multilingual"))]
+mod tests {
+    use serde_json::json;
+
+    use crate::License;
+
+    #[test]
+    fn localized_select() {
+        let license: License = serde_json::from_value(json!({
+            "text": {"de": "Lizenz", "en-GB": "Licence", "fr-CA": "Licence (CA)"}
+        }))
+        .unwrap();
+        assert_eq!(license.text(), Some("Licence"));
+        assert_eq!(license.text_in(&["ja"]), Some((Some("en-GB"), "Licence")));
+        assert_eq!(license.text_in(&["de-AT"]), Some((Some("de"), "Lizenz")));
+        assert_eq!(
+            license.text_in(&["fr"]),
+            Some((Some("fr-CA"), "Licence (CA)"))
+        );
+        assert_eq!(
+            license.text_in(&["ja", "en"]),
+            Some((Some("en-GB"), "Licence"))
+        );
+        assert_eq!(license.languages().count(), 3);
+
+        let plain = License::new_text("License");
+        assert_eq!(plain.text_in(&["de"]), Some((None, "License")));
+
+        let built = License::builder()
+            .localized_text("en", "License")
+            .localized_text("de", "Lizenz")
+            .build()
+            .unwrap();
+        assert_eq!(built.text_in(&[]), Some((Some("en"), "License")));
+        assert!(serde_json::from_value::<License>(json!({"text": {}})).is_err());
+    }
+}
@@ -0,0 +1,228 @@
+//! Best-effort parsing of license strings found in the wild.
+#[cfg(not(feature = "spdx"))]
+use crate::KnownLicense;
+use crate::{License, SpdxExpression};
+
+/// Licenses identified in a loosely-formatted string, and how.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LooseParse {
+    pub licenses: Vec<License>,
+    /// Interpretations which may not match the author's intent.
+    pub assumptions: Vec<String>,
+    /// Parts of the string which could not be identified as a license.
+    pub unrecognized: Vec<String>,
+}
+
+/// Common names of licenses, after [simplify], and their SPDX identifiers.
+const ALIASES: &[(&str, &str)] = &[
+    ("mit", "MIT"),
+    ("expat", "MIT"),
+    ("isc", "ISC"),
+    ("bsd", "BSD-3-Clause"),
+    ("new bsd", "BSD-3-Clause"),
+    ("modified bsd", "BSD-3-Clause"),
+    ("bsd 3 clause", "BSD-3-Clause"),
+    ("3 clause bsd", "BSD-3-Clause"),
+    ("simplified bsd", "BSD-2-Clause"),
+    ("freebsd", "BSD-2-Clause"),
+    ("bsd 2 clause", "BSD-2-Clause"),
+    ("2 clause bsd", "BSD-2-Clause"),
+    ("apache", "Apache-2.0"),
+    ("apache 2", "Apache-2.0"),
+    ("apache 2.0", "Apache-2.0"),
+    ("apache v2", "Apache-2.0"),
+    ("asl 2.0", "Apache-2.0"),
+    ("mpl 2.0", "MPL-2.0"),
+    ("mozilla public 2.0", "MPL-2.0"),
+    ("gplv2", "GPL-2.0-only"),
+    ("gpl 2", "GPL-2.0-only"),
+    ("gpl v2", "GPL-2.0-only"),
+    ("gplv2+", "GPL-2.0-or-later"),
+    ("gplv3", "GPL-3.0-only"),
+    ("gpl 3", "GPL-3.0-only"),
+    ("gpl v3", "GPL-3.0-only"),
+    ("gplv3+", "GPL-3.0-or-later"),
+    ("lgplv2.1", "LGPL-2.1-only"),
+    ("lgplv3", "LGPL-3.0-only"),
+    ("unlicense", "Unlicense"),
+    ("cc0", "CC0-1.0"),
+    ("cc zero", "CC0-1.0"),
+    ("cc by", "CC-BY-4.0"),
+    ("cc by sa", "CC-BY-SA-4.0"),
+    ("odbl", "ODbL-1.0"),
+];
+
+/// Lower-case words of a license name, without filler such as "license" and "version".
+fn simplify(name: &str) -> String {
+    name.to_lowercase()
+        .split(|c: char| c.is_whitespace() || c == '-' || c == '_' || c == '(' || c == ')')
+        .filter(|w| {
+            !w.is_empty()
+                && ![
+                    "license", "licence", "the", "version", "software", "gnu", "general", "public",
+                ]
+                .contains(w)
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace("gpl v", "gplv")
+}
+
+/// SPDX identifier on the license list with this exact spelling.
+fn exact_spdx(id: &str) -> Option<&'static str> {
+    #[cfg(feature = "spdx")]
+    return spdx::license_id(id)
+        .filter(|l| l.name == id.trim_end_matches('+'))
+        .map(|l| l.name);
+    #[cfg(not(feature = "spdx"))]
+    return KnownLicense::from_spdx(id)
+        .map(KnownLicense::spdx)
+        .filter(|name| *name == id);
+}
+
+/// SPDX identifier for a license name, and what was assumed to find it.
+fn identify(name: &str) -> Option<(String, Option<String>)> {
+    if name.starts_with("LicenseRef-") || exact_spdx(name).is_some() {
+        return Some((name.to_string(), None));
+    }
+    let hyphenated = name.split_whitespace().collect::<Vec<_>>().join("-");
+    for candidate in [name, hyphenated.as_str()] {
+        #[cfg(feature = "spdx")]
+        let found = spdx::identifiers::LICENSES
+            .iter()
+            .find(|(id, ..)| id.eq_ignore_ascii_case(candidate))
+            .map(|(id, ..)| *id);
+        #[cfg(not(feature = "spdx"))]
+        let found = KnownLicense::from_spdx(candidate).map(KnownLicense::spdx);
+        if let Some(id) = found {
+            return Some((id.to_string(), Some(format!("read '{name}' as '{id}'"))));
+        }
+    }
+    let simple = simplify(name);
+    if let Some((_, id)) = ALIASES.iter().find(|(alias, _)| *alias == simple) {
+        return Some((
+            id.to_string(),
+            Some(format!("assumed '{name}' means '{id}'")),
+        ));
+    }
+    #[cfg(feature = "spdx")]
+    if let Some((lic, _)) = spdx::imprecise_license_id(name) {
+        return Some((
+            lic.name.to_string(),
+            Some(format!("assumed '{name}' means '{}'", lic.name)),
+        ));
+    }
+    None
+}
+
+impl License {
+    /// Identify licenses in a loosely-formatted string, such as
+    /// `MIT/Apache-2.0`, `BSD License`, or `CC BY 4.0`.
+    ///
+    /// Valid SPDX expressions are read exactly.
+    /// Otherwise, the string is split on separators such as `/`, `,` and `or`,
+    /// and each part is matched against SPDX identifiers ignoring case and spacing,
+    /// then against common names.
+    /// Anything beyond exact SPDX identifiers is recorded as an assumption.
+    ///
+    /// ```
+    /// use zarrs_conventions_license::License;
+    ///
+    /// let parsed = License::parse_loose("MIT/Apache 2.0");
+    /// let ids: Vec<_> = parsed.licenses.iter().map(|l| l.spdx().unwrap()).collect();
+    /// assert_eq!(ids, ["MIT", "Apache-2.0"]);
+    /// assert!(!parsed.assumptions.is_empty());
+    /// ```
+    pub fn parse_loose(s: &str) -> LooseParse {
+        let s = s.trim();
+        let mut out = LooseParse::default();
+        if s.is_empty() {
+            return out;
+        }
+        if s.contains("://") {
+            if let Ok(url) = s.parse() {
+                out.licenses.push(License::new_url(url));
+            } else {
+                out.unrecognized.push(s.to_string());
+            }
+            return out;
+        }
+        if let Ok(expr) = SpdxExpression::parse(s)
+            && expr
+                .licenses()
+                .iter()
+                .all(|l| l.id.starts_with("LicenseRef-") || exact_spdx(&l.id).is_some())
+        {
+            for l in expr.licenses() {
+                if let Some(exception) = &l.exception {
+                    out.assumptions.push(format!(
+                        "dropped exception '{exception}' from '{l}', which a license item cannot hold"
+                    ));
+                }
+                out.licenses.push(License::new_spdx(l.identifier()));
+            }
+            return out;
+        }
+
+        let mut parts = vec![s];
+        for sep in ["/", ",", ";", "|", "&", " or ", " OR ", " and ", " AND "] {
+            parts = parts.into_iter().flat_map(|p| p.split(sep)).collect();
+        }
+        let parts: Vec<_> = parts
+            .into_iter()
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .collect();
+        if parts.len() > 1 {
+            out.assumptions
+                .push(format!("read '{s}' as {} separate licenses", parts.len()));
+        }
+        for part in parts {
+            match identify(part) {
+                Some((id, assumption)) => {
+                    out.assumptions.extend(assumption);
+                    out.licenses.push(License::new_spdx(id));
+                }
+                None => out.unrecognized.push(part.to_string()),
+            }
+        }
+        License::dedup(&mut out.licenses);
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::License;
+
+    fn ids(s: &str) -> Vec<String> {
+        License::parse_loose(s)
+            .licenses
+            .iter()
+            .map(|l| l.spdx().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn loose_formats() {
+        let exact = License::parse_loose("MIT OR Apache-2.0");
+        assert_eq!(exact.licenses.len(), 2);
+        assert!(exact.assumptions.is_empty());
+
+        let exception = License::parse_loose("Apache-2.0 WITH LLVM-exception");
+        assert_eq!(exception.licenses, [License::new_spdx("Apache-2.0")]);
+        assert_eq!(exception.assumptions.len(), 1);
+
+        assert_eq!(ids("BSD License"), ["BSD-3-Clause"]);
+        assert_eq!(ids("CC BY 4.0"), ["CC-BY-4.0"]);
+        assert_eq!(ids("mit, MIT License"), ["MIT"]);
+        assert_eq!(ids("GNU GPL v3"), ["GPL-3.0-only"]);
+
+        let url = License::parse_loose("https://example.com/license");
+        assert!(url.licenses[0].url().is_some());
+
+        let unknown = License::parse_loose("MIT / Our Special Terms");
+        assert_eq!(unknown.licenses, [License::new_spdx("MIT")]);
+        assert_eq!(unknown.unrecognized, ["Our Special Terms"]);
+    }
+}
@@ -0,0 +1,361 @@
+use crate::{Inner, KnownLicense, License, Text, canonical_url};
+
+/// SPDX identifier with canonical capitalisation, if it is known.
+pub(crate) fn canonical_spdx(id: &str) -> Option<&'static str> {
+    #[cfg(feature = "spdx")]
+    if let Some(lic) = spdx::license_id(id) {
+        return Some(lic.name).filter(|name| name.eq_ignore_ascii_case(id));
+    }
+    KnownLicense::from_spdx(id).map(KnownLicense::spdx)
+}
+
+impl Inner {
+    /// Most preferred form present, as (rank, value), for ordering.
+    fn primary(&self) -> (u8, &str) {
+        [
+            self.spdx.as_deref(),
+            self.url.as_ref().map(|u| u.as_str()),
+            self.text.as_ref().map(Text::default_text),
+            self.file.as_deref(),
+            self.path.as_deref(),
+        ]
+        .into_iter()
+        .enumerate()
+        .find_map(|(rank, value)| Some((rank as u8, value?)))
+        .unwrap_or((u8::MAX, ""))
+    }
+
+    /// Fill in fields from an equivalent item.
+    fn absorb(&mut self, other: Inner) {
+        self.spdx = self.spdx.take().or(other.spdx);
+        self.url = self.url.take().or(other.url);
+        self.text = self.text.take().or(other.text);
+        self.file = self.file.take().or(other.file);
+        self.path = self.path.take().or(other.path);
+        #[cfg(feature = "attribution")]
+        {
+            self.copyright_holder = self.copyright_holder.take().or(other.copyright_holder);
+            self.year = self.year.take().or(other.year);
+        }
+        for (key, value) in other.extras {
+            self.extras.entry(key).or_insert(value);
+        }
+    }
+}
+
+impl License {
+    /// Canonical SPDX identifier of the license, from its `spdx` field,
+    /// or its `url` if that is the canonical URL of a [KnownLicense].
+    pub(crate) fn identity(&self) -> Option<String> {
+        match self.spdx() {
+            Some(id) => {
+                let id = id.trim();
+                Some(canonical_spdx(id).unwrap_or(id).to_string())
+            }
+            None => KnownLicense::from_url(self.url()?).map(|k| k.spdx().to_string()),
+        }
+    }
+
+    /// Whether both denote the same license, even if expressed in different forms.
+    ///
+    /// If both can be identified with an SPDX identifier (see [KnownLicense] for URLs),
+    /// those are compared.
+    /// With the `text` feature, a license identified only by its text
+    /// is the same as an identified license if the text is that license's SPDX text,
+    /// ignoring case, punctuation, layout, and filled-in copyright lines.
+    /// Otherwise they are the same if they share any form.
+    ///
+    /// This is a similarity rather than an equivalence relation, as it is not transitive:
+    /// `{"file": "LICENSE"}` is the same as both `{"spdx": "MIT", "file": "LICENSE"}`
+    /// and `{"spdx": "Apache-2.0", "file": "LICENSE"}`, which are different licenses.
+    ///
+    /// ```
+    /// use zarrs_conventions_license::License;
+    ///
+    /// let spdx = License::new_spdx("MIT");
+    /// let url = License::new_url("https://opensource.org/license/mit/".parse().unwrap());
+    /// assert!(spdx.same_license(&url));
+    /// assert!(!spdx.same_license(&License::new_spdx("Apache-2.0")));
+    /// ```
+    pub fn same_license(&self, other: &License) -> bool {
+        let (a, b) = (self.identity(), other.identity());
+        if let (Some(a), Some(b)) = (&a, &b) {
+            return a == b;
+        }
+        #[cfg(feature = "text")]
+        for (id, license) in [(&a, other), (&b, self)] {
+            if let (Some(id), Some(text)) = (id, license.text()) {
+                return crate::similarity::is_text_of(text, id);
+            }
+        }
+        fn same<T: PartialEq>(a: &Option<T>, b: &Option<T>) -> bool {
+            a.is_some() && a == b
+        }
+        let (a, b) = (&self.0, &other.0);
+        same(&a.url, &b.url)
+            || same(&a.text, &b.text)
+            || same(&a.file, &b.file)
+            || same(&a.path, &b.path)
+    }
+
+    /// Whether both denote the same license: an alias of [License::same_license].
+    ///
+    /// ```
+    /// use zarrs_conventions_license::{KnownLicense, License};
+    ///
+    /// let url = License::new_url(KnownLicense::Mit.url().to_owned());
+    /// assert!(License::new_spdx("MIT").semantically_equal(&url));
+    /// ```
+    pub fn semantically_equal(&self, other: &License) -> bool {
+        self.same_license(other)
+    }
+
+    /// Combine licenses which are [the same](Self::same_license),
+    /// filling in the first one's missing forms from the others.
+    ///
+    /// Licenses are grouped regardless of their order:
+    /// those with the same SPDX identifier (see [KnownLicense] for URLs) are combined,
+    /// then each unidentified license joins the identified license it is the same as,
+    /// unless it is the same as several different ones (in which case it is kept separate),
+    /// and the remaining unidentified licenses are combined with each other.
+    pub fn dedup(licenses: &mut Vec<License>) {
+        /// Index of the first member of the license's group.
+        fn root(groups: &[usize], mut idx: usize) -> usize {
+            while groups[idx] != idx {
+                idx = groups[idx];
+            }
+            idx
+        }
+        fn join(groups: &mut [usize], a: usize, b: usize) {
+            let (a, b) = (root(groups, a), root(groups, b));
+            groups[a.max(b)] = a.min(b);
+        }
+
+        let ids: Vec<_> = licenses.iter().map(License::identity).collect();
+        let mut groups: Vec<usize> = (0..licenses.len()).collect();
+        for (a, id_a) in ids.iter().enumerate() {
+            for (b, id_b) in ids.iter().enumerate().skip(a + 1) {
+                if id_a.is_some() && id_a == id_b {
+                    join(&mut groups, a, b);
+                }
+            }
+        }
+        let mut unresolved = Vec::default();
+        for (u, license) in licenses.iter().enumerate() {
+            if ids[u].is_some() {
+                continue;
+            }
+            let mut matches = (0..licenses.len())
+                .filter(|&k| ids[k].is_some() && license.same_license(&licenses[k]));
+            match matches.next() {
+                None => unresolved.push(u),
+                Some(k) => {
+                    if matches.all(|other| ids[other] == ids[k]) {
+                        join(&mut groups, u, k);
+                    }
+                }
+            }
+        }
+        for (idx, &a) in unresolved.iter().enumerate() {
+            for &b in &unresolved[idx + 1..] {
+                if licenses[a].same_license(&licenses[b]) {
+                    join(&mut groups, a, b);
+                }
+            }
+        }
+
+        let mut out: Vec<(usize, License)> = Vec::with_capacity(licenses.len());
+        for (idx, license) in licenses.drain(..).enumerate() {
+            let first = root(&groups, idx);
+            match out.iter_mut().find(|(i, _)| *i == first) {
+                Some((_, existing)) => existing.0.absorb(license.0),
+                None => out.push((idx, license)),
+            }
+        }
+        *licenses = out.into_iter().map(|(_, license)| license).collect();
+    }
+
+    /// Canonical form of this license:
+    /// whitespace is trimmed, known SPDX identifiers are capitalised canonically,
+    /// URLs on well-known license hosts are [canonicalized](canonical_url),
+    /// and if `short`, only the most preferred form (`spdx > url > text > file > path`) is kept.
+    ///
+    /// ```
+    /// use serde_json::json;
+    /// use zarrs_conventions_license::License;
+    ///
+    /// let license: License = serde_json::from_value(json!({
+    ///     "spdx": " mit ",
+    ///     "url": "https://opensource.org/license/mit"
+    /// }))
+    /// .unwrap();
+    /// let license = license.normalize(true);
+    /// assert_eq!(license.spdx(), Some("MIT"));
+    /// assert_eq!(license.url(), None);
+    /// ```
+    pub fn normalize(mut self, short: bool) -> Self {
+        let inner = &mut self.0;
+        if let Some(spdx) = inner.spdx.as_mut() {
+            let trimmed = spdx.trim();
+            *spdx = canonical_spdx(trimmed).unwrap_or(trimmed).to_string();
+        }
+        if let Some(url) = inner.url.as_mut() {
+            *url = canonical_url(url);
+        }
+        for field in [&mut inner.file, &mut inner.path] {
+            if let Some(value) = field.as_mut() {
+                *value = value.trim().to_string();
+            }
+        }
+        if short {
+            inner.shorten();
+        }
+        self
+    }
+}
+
+/// Normalize each license (see [License::normalize]),
+/// combine licenses which are [the same](License::same_license),
+/// and sort them by their most preferred form.
+///
+/// ```
+/// use zarrs_conventions_license::{License, normalize_all};
+///
+/// let licenses = normalize_all(
+///     vec![
+///         License::new_url("https://opensource.org/license/mit".parse().unwrap()),
+///         License::new_spdx("Apache-2.0"),
+///         License::new_spdx("MIT"),
+///     ],
+///     true,
+/// );
+/// let ids: Vec<_> = licenses.iter().map(|l| l.spdx().unwrap()).collect();
+/// assert_eq!(ids, ["Apache-2.0", "MIT"]);
+/// ```
+pub fn normalize_all(licenses: impl IntoIterator<Item = License>, short: bool) -> Vec<License> {
+    let mut out: Vec<License> = licenses.into_iter().map(|l| l.normalize(false)).collect();
+    License::dedup(&mut out);
+    if short {
+        out.iter_mut().for_each(|l| l.0.shorten());
+    }
+    out.sort_by(|a, b| a.0.primary().cmp(&b.0.primary()));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{KnownLicense, License, normalize_all};
+
+    #[test]
+    fn normalize_licenses() {
+        let full = License::from(KnownLicense::CcBy4);
+        assert_eq!(full.clone().normalize(false).url(), full.url());
+        assert_eq!(
+            License::new_spdx("cc-by-4.0").normalize(false).spdx(),
+            Some("CC-BY-4.0")
+        );
+        assert_eq!(
+            License::new_spdx("LicenseRef-x ").normalize(false).spdx(),
+            Some("LicenseRef-x")
+        );
+
+        let licenses = normalize_all(
+            vec![
+                License::new_path("../other"),
+                License::new_file("LICENSE"),
+                License::new_url(KnownLicense::CcBy4.url().to_owned()),
+                License::new_spdx("cc-by-4.0"),
+                License::new_file("LICENSE"),
+            ],
+            false,
+        );
+        assert_eq!(licenses.len(), 3);
+        assert_eq!(licenses[0].spdx(), Some("CC-BY-4.0"));
+        assert_eq!(licenses[0].url(), full.url());
+        assert_eq!(licenses[1].file(), Some("LICENSE"));
+        assert_eq!(licenses[2].path(), Some("../other"));
+    }
+
+    #[test]
+    fn normalize_dedup() {
+        let mit_url = License::new_url("http://opensource.org/license/mit".parse().unwrap());
+        assert_ne!(License::new_spdx("MIT"), mit_url);
+        assert!(License::new_spdx("mit").same_license(&mit_url));
+        assert!(License::new_spdx("mit").semantically_equal(&mit_url));
+        assert!(!License::new_spdx("MIT").same_license(&License::new_file("LICENSE")));
+        let conflicting = License::builder()
+            .spdx("Apache-2.0")
+            .file("LICENSE")
+            .build()
+            .unwrap();
+        let mit_file = License::builder()
+            .spdx("MIT")
+            .file("LICENSE")
+            .build()
+            .unwrap();
+        assert!(!conflicting.same_license(&mit_file));
+
+        let mut licenses = vec![
+            License::new_spdx("MIT"),
+            mit_url,
+            License::from(KnownLicense::Mit),
+            License::new_file("LICENSE"),
+        ];
+        License::dedup(&mut licenses);
+        assert_eq!(licenses.len(), 2);
+        assert_eq!(licenses[0].spdx(), Some("MIT"));
+        assert!(licenses[0].url().is_some());
+    }
+
+    #[test]
+    fn dedup_order_independent() {
+        let mit = License::builder()
+            .spdx("MIT")
+            .file("LICENSE")
+            .build()
+            .unwrap();
+        let file = License::new_file("LICENSE");
+        let apache = License::builder()
+            .spdx("Apache-2.0")
+            .file("LICENSE")
+            .build()
+            .unwrap();
+        assert!(mit.same_license(&file));
+        assert!(file.same_license(&apache));
+        assert!(!mit.same_license(&apache));
+
+        // the unidentified license is the same as two different licenses, so joins neither
+        let forward = normalize_all(vec![mit.clone(), file.clone(), apache.clone()], false);
+        let backward = normalize_all(vec![apache.clone(), file.clone(), mit.clone()], false);
+        assert_eq!(forward, backward);
+        assert_eq!(forward, [apache, mit.clone(), file.clone()]);
+
+        // without the conflict, it joins the identified license wherever it is
+        for licenses in [
+            vec![mit.clone(), file.clone()],
+            vec![file.clone(), mit.clone()],
+        ] {
+            assert_eq!(normalize_all(licenses, false), vec![mit.clone()]);
+        }
+    }
+
+    #[cfg(feature = "text")]
+    #[test]
+    fn same_license_text() {
+        let mit_text = License::new_spdx("MIT")
+            .resolve_text()
+            .unwrap()
+            .replace("<year> <copyright holders>", "2025 Example Ltd.");
+        let mit_text = License::new_text(mit_text);
+        let mit_url = License::new_url(KnownLicense::Mit.url().to_owned());
+        assert!(mit_text.same_license(&License::new_spdx("MIT")));
+        assert!(mit_url.same_license(&mit_text));
+        assert!(!mit_text.same_license(&License::new_spdx("Apache-2.0")));
+        assert!(!License::new_text("custom terms").same_license(&mit_url));
+
+        let mut licenses = vec![License::new_spdx("MIT"), mit_text];
+        License::dedup(&mut licenses);
+        assert_eq!(licenses.len(), 1);
+        assert!(licenses[0].text().is_some());
+    }
+}
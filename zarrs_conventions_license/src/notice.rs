@@ -0,0 +1,140 @@
+//! Consolidated license notices for a hierarchy.
+use std::fmt::Write;
+
+use zarrs_conventions::{Attributes, NodePath};
+
+use crate::{License, resolve::effective_license};
+
+/// Full text of a node's effective license: its own text,
+/// the object its `file` refers to, or (with the `text` feature) the SPDX text.
+fn full_text(
+    node: &str,
+    license: &License,
+    load: &impl Fn(&str) -> Option<String>,
+) -> Result<Option<String>, String> {
+    if let Some(text) = license.text() {
+        return Ok(Some(text.to_string()));
+    }
+    if let Some(file) = license.file()
+        && let Some(text) = load(
+            NodePath::new(node)
+                .and_then(|node| node.join(file))
+                .map_err(|e| e.to_string())?
+                .as_str(),
+        )
+    {
+        return Ok(Some(text));
+    }
+    #[cfg(feature = "text")]
+    return Ok(license.resolve_text().map(str::to_string));
+    #[cfg(not(feature = "text"))]
+    Ok(None)
+}
+
+/// Markdown section listing each license applying to the given nodes,
+/// the nodes it covers, and its full text where available.
+///
+/// Each node's license is its [effective license](effective_license);
+/// nodes whose licenses are [the same](License::same_license) are grouped together.
+/// `lookup` returns the attributes of the node at an absolute path,
+/// and `load` the text of the object at an absolute path (for `file` licenses).
+///
+/// ```
+/// use serde_json::json;
+/// use zarrs_conventions_license::notice::notice;
+///
+/// let markdown = notice(
+///     ["/", "/data"],
+///     |node| match node {
+///         "/" => json!({"license": {"spdx": "CC-BY-4.0"}}).as_object().cloned(),
+///         _ => Some(Default::default()),
+///     },
+///     |_| None,
+/// )
+/// .unwrap();
+/// assert!(markdown.contains("## CC-BY-4.0\n\n- `/`\n- `/data`\n"));
+/// ```
+pub fn notice<'a>(
+    nodes: impl IntoIterator<Item = &'a str>,
+    lookup: impl Fn(&str) -> Option<Attributes>,
+    load: impl Fn(&str) -> Option<String>,
+) -> Result<String, String> {
+    // (license, covered nodes, text)
+    let mut groups: Vec<(License, Vec<String>, Option<String>)> = Vec::default();
+    let mut unlicensed = Vec::default();
+    for node in nodes {
+        let Some(inherited) = effective_license(node, &lookup)? else {
+            unlicensed.push(node.to_string());
+            continue;
+        };
+        let license = inherited.license;
+        match groups.iter_mut().find(|(l, ..)| l.same_license(&license)) {
+            Some((_, covered, text)) => {
+                covered.push(node.to_string());
+                if text.is_none() {
+                    *text = full_text(node, &license, &load)?;
+                }
+            }
+            None => {
+                let text = full_text(node, &license, &load)?;
+                groups.push((license, vec![node.to_string()], text));
+            }
+        }
+    }
+
+    let mut out = String::from("# Licenses\n");
+    for (license, covered, text) in groups {
+        write!(out, "\n## {license}\n\n").expect("writing to a String");
+        for node in covered {
+            writeln!(out, "- `{node}`").expect("writing to a String");
+        }
+        if let Some(url) = license.url() {
+            write!(out, "\nSee <{url}>.\n").expect("writing to a String");
+        }
+        if let Some(text) = text {
+            write!(out, "\n```text\n{}\n```\n", text.trim_end()).expect("writing to a String");
+        }
+    }
+    if !unlicensed.is_empty() {
+        out.push_str("\n## No license declared\n\n");
+        for node in unlicensed {
+            writeln!(out, "- `{node}`").expect("writing to a String");
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::notice;
+
+    #[test]
+    fn notice_hierarchy() {
+        let nodes = json!({
+            "/": {"license": {"spdx": "LicenseRef-terms", "file": "LICENSE"}},
+            "/a": {},
+            "/b": {"license": {"url": "https://opensource.org/license/mit"}},
+            "/c": {"license": {"spdx": "MIT"}},
+        });
+        let markdown = notice(
+            ["/", "/a", "/b", "/c"],
+            |node| match node {
+                "/x" => Some(Default::default()),
+                _ => nodes.get(node).and_then(Value::as_object).cloned(),
+            },
+            |key| (key == "/LICENSE").then(|| "Our terms.\n".to_string()),
+        )
+        .unwrap();
+        assert!(
+            markdown
+                .starts_with("# Licenses\n\n## LicenseRef-terms (see LICENSE)\n\n- `/`\n- `/a`\n")
+        );
+        assert!(markdown.contains("```text\nOur terms.\n```\n"));
+        assert!(markdown.contains("- `/b`\n- `/c`\n\nSee <https://opensource.org/license/mit>.\n"));
+
+        let unlicensed = notice(["/x"], |_| Some(Default::default()), |_| None).unwrap();
+        assert!(unlicensed.contains("## No license declared\n\n- `/x`\n"));
+    }
+}
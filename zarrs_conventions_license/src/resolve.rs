@@ -0,0 +1,385 @@
+//! Resolve license text and metadata referenced by the `file` and `path` forms.
+use std::fmt::Display;
+
+use zarrs_conventions::{Attributes, AttributesBuilder, NestedRepr, NodePath};
+
+use crate::License;
+
+/// Maximum number of `path` references followed by [License::resolve].
+pub const MAX_DEPTH: usize = 16;
+
+/// License which applies to a node, and where it was declared.
+#[derive(Debug, Clone)]
+pub struct Inherited {
+    /// Absolute path of the node declaring the license; may be the node itself.
+    pub declared_by: String,
+    /// The license, with `path` references resolved and any `file` relative to the node asked about.
+    pub license: License,
+}
+
+/// The license which applies to the node at the absolute path `node`:
+/// its own license metadata if it has any, otherwise that of its nearest ancestor.
+///
+/// `lookup` returns the attributes of the node at an absolute path.
+/// Returns `None` if neither the node nor any ancestor has license metadata.
+///
+/// ```
+/// use serde_json::json;
+/// use zarrs_conventions_license::resolve::effective_license;
+///
+/// let inherited = effective_license("/group/array", |node| match node {
+///     "/" => json!({"license": {"spdx": "CC-BY-4.0"}}).as_object().cloned(),
+///     _ => Some(Default::default()),
+/// })
+/// .unwrap()
+/// .unwrap();
+/// assert_eq!(inherited.declared_by, "/");
+/// assert_eq!(inherited.license.spdx(), Some("CC-BY-4.0"));
+/// ```
+pub fn effective_license(
+    node: &str,
+    lookup: impl Fn(&str) -> Option<Attributes>,
+) -> Result<Option<Inherited>, String> {
+    let node = NodePath::new(node).map_err(|e| e.to_string())?;
+    for current in node.ancestors() {
+        if let Some(attributes) = lookup(current.as_str())
+            && attributes.contains_key(License::KEY)
+        {
+            let license = License::from_attributes_nested(&attributes)
+                .map_err(|e| format!("invalid license metadata at {current}: {e}"))?;
+            let mut license = license.resolve(current.as_str(), &lookup)?;
+            if let Some(file) = &license.0.file {
+                let file = current.join(file).map_err(|e| e.to_string())?;
+                license.0.file = Some(file.relative_to(&node));
+            }
+            return Ok(Some(Inherited {
+                declared_by: current.to_string(),
+                license,
+            }));
+        }
+    }
+    Ok(None)
+}
+
+fn decode(file: &str, bytes: Vec<u8>) -> Result<String, String> {
+    String::from_utf8(bytes).map_err(|_| format!("license file '{file}' is not UTF-8 text"))
+}
+
+impl License {
+    /// Load the text of the object referenced by the `file` field.
+    ///
+    /// `store` is given the `file` field as written, i.e. relative to this node
+    /// (see [NodePath::join]), and should fail if the object does not exist.
+    /// Returns `None` if there is no `file` field.
+    ///
+    /// ```
+    /// use zarrs_conventions_license::License;
+    ///
+    /// let license = License::new_file("LICENSE.txt");
+    /// let text = license
+    ///     .resolve_file(|key| match key {
+    ///         "LICENSE.txt" => Ok(b"MIT License".to_vec()),
+    ///         _ => Err("not found"),
+    ///     })
+    ///     .unwrap();
+    /// assert_eq!(text.as_deref(), Some("MIT License"));
+    /// ```
+    pub fn resolve_file<E: Display>(
+        &self,
+        store: impl FnOnce(&str) -> Result<Vec<u8>, E>,
+    ) -> Result<Option<String>, String> {
+        let Some(file) = self.file() else {
+            return Ok(None);
+        };
+        let bytes =
+            store(file).map_err(|e| format!("could not load license file '{file}': {e}"))?;
+        decode(file, bytes).map(Some)
+    }
+
+    /// As [Self::resolve_file], for asynchronous stores such as `object_store`.
+    pub async fn resolve_file_async<E: Display, F: Future<Output = Result<Vec<u8>, E>>>(
+        &self,
+        store: impl FnOnce(&str) -> F,
+    ) -> Result<Option<String>, String> {
+        let Some(file) = self.file() else {
+            return Ok(None);
+        };
+        let bytes = store(file)
+            .await
+            .map_err(|e| format!("could not load license file '{file}': {e}"))?;
+        decode(file, bytes).map(Some)
+    }
+
+    /// Write license text to an object relative to this node with `store`,
+    /// and set the `file` field to refer to it.
+    pub fn write_file<E: Display>(
+        &mut self,
+        file: impl Into<String>,
+        text: &str,
+        store: impl FnOnce(&str, &[u8]) -> Result<(), E>,
+    ) -> Result<(), String> {
+        let file = file.into();
+        store(&file, text.as_bytes())
+            .map_err(|e| format!("could not write license file '{file}': {e}"))?;
+        self.0.file = Some(file);
+        Ok(())
+    }
+
+    /// Write license text as a sidecar object and record it in a node's attributes.
+    ///
+    /// The text is written to `key` (relative to the node) with `writer`.
+    /// Only if that succeeds are the attributes updated:
+    /// the node's existing license gets its `file` field set,
+    /// or a new license with only the `file` form is added along with the convention's declaration.
+    /// This is the reverse of [Self::resolve_file].
+    ///
+    /// ```
+    /// use serde_json::json;
+    /// use zarrs_conventions::Attributes;
+    /// use zarrs_conventions_license::License;
+    ///
+    /// let mut attributes = Attributes::default();
+    /// let license = License::materialize_file(&mut attributes, "terms", "LICENSE", |key, bytes| {
+    ///     assert_eq!((key, bytes), ("LICENSE", b"terms".as_slice()));
+    ///     Ok::<_, std::io::Error>(())
+    /// })
+    /// .unwrap();
+    /// assert_eq!(attributes["license"], json!({"file": "LICENSE"}));
+    /// ```
+    pub fn materialize_file<E: Display>(
+        attributes: &mut Attributes,
+        text: &str,
+        key: &str,
+        writer: impl FnOnce(&str, &[u8]) -> Result<(), E>,
+    ) -> Result<License, String> {
+        let existing = if attributes.contains_key(License::KEY) {
+            let license = License::from_attributes_nested(attributes)
+                .map_err(|e| format!("invalid existing license metadata: {e}"))?;
+            Some(license)
+        } else {
+            None
+        };
+        let mut license = existing.unwrap_or_else(|| License::new_file(key));
+        license.write_file(key, text, writer)?;
+
+        let mut builder = AttributesBuilder::from_attributes(attributes.clone());
+        builder.add_nested(&license).map_err(|e| e.to_string())?;
+        match builder.build().map_err(|e| e.to_string())? {
+            serde_json::Value::Object(updated) => *attributes = updated,
+            _ => unreachable!("attributes should build to an object"),
+        }
+        Ok(license)
+    }
+
+    /// Follow `path` references to other nodes' license metadata,
+    /// filling in fields this license does not have.
+    ///
+    /// `node` is the absolute path of the node this license belongs to;
+    /// `lookup` returns the attributes of the node at an absolute path.
+    /// A `file` taken from another node is rewritten to be relative to `node`.
+    /// The result has no `path`.
+    ///
+    /// Fails if a referenced node or its license metadata does not exist,
+    /// if the references form a cycle, or if more than [MAX_DEPTH] references are followed.
+    ///
+    /// ```
+    /// use serde_json::json;
+    /// use zarrs_conventions_license::License;
+    ///
+    /// let license = License::new_path("..");
+    /// let resolved = license
+    ///     .resolve("/data", |node| match node {
+    ///         "/" => json!({"license": {"spdx": "MIT"}}).as_object().cloned(),
+    ///         _ => None,
+    ///     })
+    ///     .unwrap();
+    /// assert_eq!(resolved.spdx(), Some("MIT"));
+    /// assert_eq!(resolved.path(), None);
+    /// ```
+    pub fn resolve(
+        &self,
+        node: &str,
+        lookup: impl Fn(&str) -> Option<Attributes>,
+    ) -> Result<License, String> {
+        let mut resolved = self.0.clone();
+        let mut visited = vec![NodePath::new(node).map_err(|e| e.to_string())?];
+        while let Some(path) = resolved.path.take() {
+            let current = visited.last().expect("starts non-empty");
+            let target = current.join(&path).map_err(|e| e.to_string())?;
+            if visited.contains(&target) {
+                visited.push(target);
+                let cycle: Vec<_> = visited.iter().map(NodePath::as_str).collect();
+                return Err(format!(
+                    "license path references form a cycle: {}",
+                    cycle.join(" -> ")
+                ));
+            }
+            if visited.len() > MAX_DEPTH {
+                return Err(format!(
+                    "more than {MAX_DEPTH} license path references followed from {}",
+                    visited[0]
+                ));
+            }
+            let attributes = lookup(target.as_str())
+                .ok_or_else(|| format!("license path node {target} not found"))?;
+            let other = License::from_attributes_nested(&attributes)
+                .map_err(|e| format!("no valid license metadata at {target}: {e}"))?
+                .0;
+            resolved.spdx = resolved.spdx.or(other.spdx);
+            resolved.url = resolved.url.or(other.url);
+            resolved.text = resolved.text.or(other.text);
+            if resolved.file.is_none()
+                && let Some(file) = other.file
+            {
+                let file = target.join(&file).map_err(|e| e.to_string())?;
+                resolved.file = Some(file.relative_to(&visited[0]));
+            }
+            resolved.path = other.path;
+            visited.push(target);
+        }
+        resolved.try_into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::RefCell,
+        collections::HashMap,
+        pin::pin,
+        task::{Context, Poll, Waker},
+    };
+
+    use serde_json::{Value, json};
+
+    use super::{MAX_DEPTH, effective_license};
+    use crate::License;
+
+    #[test]
+    fn resolve_path() {
+        let nodes = json!({
+            "/": {"license": {"spdx": "CC-BY-4.0", "file": "LICENSE"}},
+            "/a": {"license": {"url": "https://example.com/license", "path": ".."}},
+            "/a/b": {"license": {"path": ".."}},
+            "/cycle/x": {"license": {"path": "../y"}},
+            "/cycle/y": {"license": {"path": "../x"}},
+            "/empty": {}
+        });
+        let lookup = |node: &str| nodes.get(node).and_then(Value::as_object).cloned();
+
+        let resolved = License::new_path("..").resolve("/a/b", lookup).unwrap();
+        assert_eq!(resolved.spdx(), Some("CC-BY-4.0"));
+        assert_eq!(
+            resolved.url().unwrap().as_str(),
+            "https://example.com/license"
+        );
+        assert_eq!(resolved.file(), Some("../../LICENSE"));
+        assert_eq!(resolved.path(), None);
+
+        let own = License::new_spdx("MIT").resolve("/a", lookup).unwrap();
+        assert_eq!(own.spdx(), Some("MIT"));
+
+        let cycle = License::new_path("../y").resolve("/cycle/x", lookup);
+        assert!(
+            cycle
+                .unwrap_err()
+                .contains("/cycle/x -> /cycle/y -> /cycle/x")
+        );
+        assert!(License::new_path("../empty").resolve("/a", lookup).is_err());
+        assert!(License::new_path("missing").resolve("/", lookup).is_err());
+
+        let deep = |_: &str| json!({"license": {"path": "next"}}).as_object().cloned();
+        let err = License::new_path("next").resolve("/", deep).unwrap_err();
+        assert!(err.contains(&MAX_DEPTH.to_string()));
+    }
+
+    #[test]
+    fn resolve_effective() {
+        let nodes = json!({
+            "/": {"license": {"spdx": "CC-BY-4.0", "file": "LICENSE"}},
+            "/a": {},
+            "/a/b": {"license": {"spdx": "MIT"}},
+            "/a/c": {"license": {"path": "../b"}}
+        });
+        let lookup = |node: &str| nodes.get(node).and_then(Value::as_object).cloned();
+
+        let own = effective_license("/a/b", lookup).unwrap().unwrap();
+        assert_eq!(
+            (own.declared_by.as_str(), own.license.spdx()),
+            ("/a/b", Some("MIT"))
+        );
+        let inherited = effective_license("/a/d", lookup).unwrap().unwrap();
+        assert_eq!(inherited.declared_by, "/");
+        assert_eq!(inherited.license.file(), Some("../../LICENSE"));
+        let referenced = effective_license("/a/c", lookup).unwrap().unwrap();
+        assert_eq!(referenced.license.spdx(), Some("MIT"));
+
+        assert!(effective_license("/x", |_| None).unwrap().is_none());
+        assert!(effective_license("x", lookup).is_err());
+    }
+
+    #[test]
+    fn resolve_file_roundtrip() {
+        let objects = RefCell::new(HashMap::new());
+        let mut license = License::new_spdx("LicenseRef-custom");
+        assert_eq!(license.resolve_file(|_| Err("unreachable")), Ok(None));
+
+        license
+            .write_file("LICENSE", "custom terms", |key, bytes| {
+                objects.borrow_mut().insert(key.to_string(), bytes.to_vec());
+                Ok::<_, String>(())
+            })
+            .unwrap();
+        assert_eq!(license.file(), Some("LICENSE"));
+        let load = |key: &str| objects.borrow().get(key).cloned().ok_or("not found");
+        assert_eq!(
+            license.resolve_file(load),
+            Ok(Some("custom terms".to_string()))
+        );
+
+        let future = pin!(license.resolve_file_async(|key| std::future::ready(load(key))));
+        let Poll::Ready(text) = future.poll(&mut Context::from_waker(Waker::noop())) else {
+            panic!("ready future should complete");
+        };
+        assert_eq!(text, Ok(Some("custom terms".to_string())));
+
+        let missing = License::new_file("MISSING");
+        assert!(
+            missing
+                .resolve_file(load)
+                .unwrap_err()
+                .contains("not found")
+        );
+        objects.borrow_mut().insert("BIN".into(), vec![0xff]);
+        assert!(License::new_file("BIN").resolve_file(load).is_err());
+    }
+
+    #[test]
+    fn resolve_materialize() {
+        let mut attributes = json!({"license": {"spdx": "MIT"}, "other": 1})
+            .as_object()
+            .cloned()
+            .unwrap();
+        let failed = License::materialize_file(&mut attributes, "x", "LICENSE", |_, _| {
+            Err("read-only store")
+        });
+        assert!(failed.unwrap_err().contains("read-only store"));
+        assert_eq!(attributes["license"], json!({"spdx": "MIT"}));
+
+        let mut written = None;
+        let license =
+            License::materialize_file(&mut attributes, "MIT License", "LICENSE", |k, b| {
+                written = Some((k.to_string(), b.to_vec()));
+                Ok::<_, String>(())
+            })
+            .unwrap();
+        assert_eq!(written, Some(("LICENSE".into(), b"MIT License".to_vec())));
+        assert_eq!(license.spdx(), Some("MIT"));
+        assert_eq!(
+            attributes["license"],
+            json!({"spdx": "MIT", "file": "LICENSE"})
+        );
+        assert_eq!(attributes["other"], 1);
+        assert!(attributes.contains_key("zarr_conventions"));
+    }
+}
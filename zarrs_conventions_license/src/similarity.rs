@@ -0,0 +1,41 @@
+//! Similarity of license texts, ignoring case, punctuation, and layout.
+use std::{
+    collections::HashSet,
+    hash::{DefaultHasher, Hash, Hasher},
+};
+
+/// Texts at least this [similar](similarity) to an SPDX license text are considered to be that license;
+/// this allows for filled-in copyright lines and minor rewording.
+pub(crate) const SAME_TEXT_THRESHOLD: f32 = 0.9;
+
+/// Hashes of the pairs of consecutive words in the text,
+/// ignoring case, punctuation, and layout.
+pub(crate) fn bigrams(text: &str) -> HashSet<u64> {
+    let lower = text.to_lowercase();
+    let words: Vec<_> = lower
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect();
+    words
+        .windows(2)
+        .map(|pair| {
+            let mut hasher = DefaultHasher::new();
+            pair.hash(&mut hasher);
+            hasher.finish()
+        })
+        .collect()
+}
+
+/// Dice coefficient of two sets.
+pub(crate) fn similarity(a: &HashSet<u64>, b: &HashSet<u64>) -> f32 {
+    if a.is_empty() && b.is_empty() {
+        return 0.0;
+    }
+    2.0 * a.intersection(b).count() as f32 / (a.len() + b.len()) as f32
+}
+
+/// Whether the text is that of the given SPDX license.
+pub(crate) fn is_text_of(text: &str, spdx_id: &str) -> bool {
+    spdx::license_id(spdx_id)
+        .is_some_and(|lic| similarity(&bigrams(text), &bigrams(lic.text())) >= SAME_TEXT_THRESHOLD)
+}
@@ -0,0 +1,141 @@
+//! Conversion to and from the STAC `license` field and `license` links.
+//!
+//! Follows STAC 1.0 common metadata: a single SPDX identifier is used as is,
+//! several licenses are `various`, and a license without an SPDX identifier is `proprietary`;
+//! in the latter cases, `license` links locate the licenses.
+use serde::{Deserialize, Serialize};
+
+use crate::License;
+
+/// Value of `license` for several licenses.
+pub const VARIOUS: &str = "various";
+/// Value of `license` for a license without an SPDX identifier.
+pub const PROPRIETARY: &str = "proprietary";
+/// Value of `license` for a license without an SPDX identifier in STAC 1.1.
+pub const OTHER: &str = "other";
+
+/// STAC link object with `rel: license`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Link {
+    pub rel: String,
+    pub href: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+/// License metadata for a STAC Item or Collection.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Stac {
+    /// Value of the `license` field.
+    pub license: String,
+    /// Links to add to the `links` array.
+    pub links: Vec<Link>,
+}
+
+/// Whether the identifier can be used as the STAC `license` value.
+fn is_spdx(id: &str) -> bool {
+    #[cfg(feature = "spdx")]
+    return crate::validation::is_known_spdx(id) && !id.starts_with("LicenseRef-");
+    #[cfg(not(feature = "spdx"))]
+    return !id.starts_with("LicenseRef-");
+}
+
+/// Link to the license text, if it can be located.
+fn link(license: &License) -> Option<Link> {
+    let href = match (license.url(), license.spdx(), license.file()) {
+        (Some(url), ..) => url.to_string(),
+        (None, _, Some(file)) => file.to_string(),
+        (None, Some(id), None) if is_spdx(id) => format!("https://spdx.org/licenses/{id}"),
+        _ => return None,
+    };
+    Some(Link {
+        rel: "license".to_string(),
+        href,
+        title: Some(license.summary(false)),
+    })
+}
+
+/// STAC `license` value and `license` links for the given licenses.
+///
+/// Licenses which cannot be linked to (e.g. text-only licenses) are omitted from the links;
+/// the text should be published separately.
+///
+/// ```
+/// use zarrs_conventions_license::{KnownLicense, License, stac::to_stac};
+///
+/// let stac = to_stac(&[License::from(KnownLicense::CcBy4)]);
+/// assert_eq!(stac.license, "CC-BY-4.0");
+/// assert_eq!(stac.links[0].href, "https://creativecommons.org/licenses/by/4.0/");
+///
+/// let stac = to_stac(&[License::new_spdx("MIT"), License::new_spdx("Apache-2.0")]);
+/// assert_eq!(stac.license, "various");
+/// assert_eq!(stac.links.len(), 2);
+/// ```
+pub fn to_stac(licenses: &[License]) -> Stac {
+    let license = match licenses {
+        [single] => match single.spdx() {
+            Some(id) if is_spdx(id) => id.to_string(),
+            _ => PROPRIETARY.to_string(),
+        },
+        _ => VARIOUS.to_string(),
+    };
+    Stac {
+        license,
+        links: licenses.iter().filter_map(link).collect(),
+    }
+}
+
+/// Licenses from a STAC `license` value and `links`.
+///
+/// An SPDX identifier or expression gives one license per identifier;
+/// otherwise, each `license` link gives a license by URL.
+pub fn from_stac(license: &str, links: &[Link]) -> Result<Vec<License>, String> {
+    if ![VARIOUS, PROPRIETARY, OTHER].contains(&license) {
+        return License::from_spdx_expression(license);
+    }
+    links
+        .iter()
+        .filter(|l| l.rel == "license")
+        .map(|l| {
+            l.href
+                .parse()
+                .map(License::new_url)
+                .map_err(|_| format!("license link '{}' is not an absolute URL", l.href))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Link, from_stac, to_stac};
+    use crate::License;
+
+    #[test]
+    fn stac_roundtrip() {
+        let custom = License::builder()
+            .spdx("LicenseRef-custom")
+            .url("https://example.com/license".parse().unwrap())
+            .build()
+            .unwrap();
+        let stac = to_stac(std::slice::from_ref(&custom));
+        assert_eq!(stac.license, "proprietary");
+        assert_eq!(stac.links[0].href, "https://example.com/license");
+        let parsed = from_stac(&stac.license, &stac.links).unwrap();
+        assert_eq!(parsed[0].url(), custom.url());
+
+        let text = to_stac(&[License::new_text("terms")]);
+        assert_eq!(
+            (text.license.as_str(), text.links.len()),
+            ("proprietary", 0)
+        );
+
+        let spdx = from_stac("MIT OR Apache-2.0", &[]).unwrap();
+        assert_eq!(spdx.len(), 2);
+        let links = [Link {
+            rel: "license".into(),
+            href: "LICENSE".into(),
+            title: None,
+        }];
+        assert!(from_stac("various", &links).is_err());
+    }
+}
@@ -0,0 +1,116 @@
+use std::fmt;
+
+use crate::License;
+
+impl License {
+    /// Short human-readable description, e.g. `CC-BY-4.0 (see LICENSE.txt)`.
+    ///
+    /// If `verbose`, URLs are included alongside SPDX identifiers.
+    ///
+    /// ```
+    /// use zarrs_conventions_license::{KnownLicense, License};
+    ///
+    /// let license = License::builder().spdx("MIT").file("LICENSE.txt").build().unwrap();
+    /// assert_eq!(license.summary(false), "MIT (see LICENSE.txt)");
+    /// assert_eq!(
+    ///     License::from(KnownLicense::Mit).summary(true),
+    ///     "MIT <https://opensource.org/license/mit>"
+    /// );
+    /// ```
+    pub fn summary(&self, verbose: bool) -> String {
+        let mut head = match (self.spdx(), self.url()) {
+            (Some(spdx), Some(url)) if verbose => format!("{spdx} <{url}>"),
+            (Some(spdx), _) => spdx.to_string(),
+            (None, Some(url)) => url.to_string(),
+            (None, None) if self.text().is_some() => "custom license".to_string(),
+            (None, None) => String::default(),
+        };
+        let mut notes = Vec::default();
+        #[cfg(feature = "attribution")]
+        notes.extend(self.copyright());
+        if let Some(file) = self.file() {
+            notes.push(format!("see {file}"));
+        }
+        if let Some(path) = self.path() {
+            notes.push(format!("as for {path}"));
+        }
+        if notes.is_empty() {
+            return head;
+        }
+        if head.is_empty() {
+            return notes.join(", ");
+        }
+        head.push_str(&format!(" ({})", notes.join(", ")));
+        head
+    }
+}
+
+impl fmt::Display for License {
+    /// As [License::summary], or verbose with the alternate flag (`{:#}`).
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.summary(f.alternate()))
+    }
+}
+
+/// Summaries of several licenses, separated by semicolons, e.g. `MIT; CC-BY-4.0 (see LICENSE.txt)`.
+pub fn summarize<'a>(licenses: impl IntoIterator<Item = &'a License>, verbose: bool) -> String {
+    licenses
+        .into_iter()
+        .map(|l| l.summary(verbose))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::summarize;
+    use crate::{KnownLicense, License};
+
+    #[test]
+    fn summary_forms() {
+        let cc = License::builder()
+            .spdx("CC-BY-4.0")
+            .file("LICENSE.txt")
+            .build()
+            .unwrap();
+        assert_eq!(
+            summarize(&[License::new_spdx("MIT"), cc], false),
+            "MIT; CC-BY-4.0 (see LICENSE.txt)"
+        );
+        assert_eq!(License::new_text("terms").to_string(), "custom license");
+        assert_eq!(License::new_path("..").to_string(), "as for ..");
+        let known = License::from(KnownLicense::CcBy4);
+        assert_eq!(known.to_string(), "CC-BY-4.0");
+        assert_eq!(
+            format!("{known:#}"),
+            "CC-BY-4.0 <https://creativecommons.org/licenses/by/4.0/>"
+        );
+        let url = License::new_url(KnownLicense::Mit.url().to_owned());
+        assert_eq!(url.to_string(), "https://opensource.org/license/mit");
+    }
+
+    #[cfg(feature = "attribution")]
+    #[test]
+    fn summary_attribution() {
+        let license = License::builder()
+            .spdx("CC-BY-4.0")
+            .file("LICENSE")
+            .copyright_holder("Jane Doe")
+            .year("2024")
+            .build()
+            .unwrap();
+        assert_eq!(
+            license.to_string(),
+            "CC-BY-4.0 (© 2024 Jane Doe, see LICENSE)"
+        );
+        let value = serde_json::to_value(&license).unwrap();
+        assert_eq!(value["copyright_holder"], "Jane Doe");
+        assert_eq!(value["year"], "2024");
+        assert!(
+            License::builder()
+                .copyright_holder("Jane Doe")
+                .build()
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,71 @@
+//! Usage terms of SPDX-listed licenses, from SPDX metadata
+//! supplemented with terms it does not record for common data licenses.
+
+/// Terms of a license relevant to combining and redistributing data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Terms {
+    pub id: &'static str,
+    /// OSI approved, FSF free/libre, or a known open data license.
+    pub free: bool,
+    /// Derived works must use the same license (including share-alike).
+    pub copyleft: bool,
+    pub no_derivatives: bool,
+    pub non_commercial: bool,
+    /// Later versions of the license may be used instead (`-or-later` or `+`).
+    pub or_later: bool,
+    /// Waives rights rather than licensing them.
+    pub public_domain: bool,
+    /// Copyleft which applies only to the licensed work, not works combined with it.
+    pub weak_copyleft: bool,
+}
+
+/// Open data licenses which SPDX does not mark as free.
+const FREE_DATA: &[&str] = &[
+    "PDDL-1.0",
+    "ODC-By-1.0",
+    "CDLA-Permissive-1.0",
+    "CDLA-Permissive-2.0",
+];
+
+/// Public domain dedications and equivalents.
+const PUBLIC_DOMAIN: &[&str] = &["CC0-1.0", "PDDL-1.0", "Unlicense", "CC-PDDC"];
+
+/// Families of licenses whose copyleft applies per file or library.
+const WEAK_COPYLEFT: &[&str] = &["LGPL-", "MPL-", "EPL-", "CDDL-", "CECILL-C"];
+
+/// Share-alike data licenses which SPDX does not mark as copyleft.
+const SHARE_ALIKE_DATA: &[&str] = &["ODbL-1.0", "CDLA-Sharing-1.0"];
+
+pub(crate) fn terms(id: &str) -> Option<Terms> {
+    let lic = spdx::license_id(id)?;
+    let name = lic.name;
+    let no_derivatives = name.contains("-ND");
+    let non_commercial = name.contains("-NC");
+    Some(Terms {
+        id: name,
+        free: (lic.is_osi_approved() || lic.is_fsf_free_libre() || FREE_DATA.contains(&name))
+            && !no_derivatives
+            && !non_commercial,
+        copyleft: lic.is_copyleft() || name.contains("-SA") || SHARE_ALIKE_DATA.contains(&name),
+        no_derivatives,
+        non_commercial,
+        or_later: id.ends_with('+') || name.ends_with("-or-later"),
+        public_domain: PUBLIC_DOMAIN.contains(&name),
+        weak_copyleft: WEAK_COPYLEFT.iter().any(|f| name.starts_with(f)),
+    })
+}
+
+/// Family and version of a versioned identifier, e.g. `GPL-2.0-or-later` is `("GPL", [2, 0])`.
+pub(crate) fn version(id: &str) -> Option<(&str, Vec<u32>)> {
+    let id = ["-or-later", "-only", "+"]
+        .iter()
+        .find_map(|suffix| id.strip_suffix(suffix))
+        .unwrap_or(id);
+    let (family, version) = id.rsplit_once('-')?;
+    let version = version
+        .split('.')
+        .map(str::parse)
+        .collect::<Result<Vec<u32>, _>>()
+        .ok()?;
+    Some((family, version))
+}
@@ -0,0 +1,131 @@
+//! Checks and canonical forms for license URLs.
+use zarrs_conventions::iref::{Uri, UriBuf};
+
+/// URL schemes allowed for license text.
+const SCHEMES: &[&str] = &["http", "https"];
+
+/// Hosts of URL shortening services, whose links may stop resolving.
+pub(crate) const SHORTENERS: &[&str] = &[
+    "bit.ly",
+    "tinyurl.com",
+    "t.co",
+    "goo.gl",
+    "ow.ly",
+    "is.gd",
+    "buff.ly",
+    "rebrand.ly",
+    "tiny.cc",
+];
+
+/// Scheme, host, and the remainder (path, query, and fragment) of a URL.
+pub(crate) fn split(url: &Uri) -> Option<(&str, &str, &str)> {
+    let (scheme, rest) = url.as_str().split_once("://")?;
+    let end = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+    Some((scheme, &rest[..end], &rest[end..]))
+}
+
+/// Fails if the URL cannot locate license text on the web:
+/// it must be `http` or `https` and have a host.
+pub(crate) fn check_url(url: &Uri) -> Result<(), String> {
+    match split(url) {
+        Some((scheme, host, _))
+            if SCHEMES.iter().any(|s| s.eq_ignore_ascii_case(scheme)) && !host.is_empty() =>
+        {
+            Ok(())
+        }
+        _ => Err(format!(
+            "license URL '{url}' must be an http(s) URL with a host"
+        )),
+    }
+}
+
+/// Stable form of a URL on a well-known license host:
+/// `https`, no `www.`, and the path form currently published by
+/// opensource.org, creativecommons.org, and spdx.org.
+/// Other URLs are returned unchanged.
+///
+/// ```
+/// use zarrs_conventions_license::{canonical_url, zarrs_conventions::iref::UriBuf};
+///
+/// let url: UriBuf = "http://www.opensource.org/licenses/MIT".parse().unwrap();
+/// assert_eq!(canonical_url(&url).as_str(), "https://opensource.org/license/mit");
+/// let url: UriBuf = "http://creativecommons.org/licenses/by/4.0/legalcode".parse().unwrap();
+/// assert_eq!(canonical_url(&url).as_str(), "https://creativecommons.org/licenses/by/4.0/");
+/// ```
+pub fn canonical_url(url: &Uri) -> UriBuf {
+    let Some((_, host, rest)) = split(url) else {
+        return url.to_owned();
+    };
+    let host = host.to_ascii_lowercase();
+    let host = host.strip_prefix("www.").unwrap_or(&host);
+    let path = rest
+        .split(['?', '#'])
+        .next()
+        .unwrap_or_default()
+        .trim_end_matches('/');
+    let path = match host {
+        "opensource.org" => match path
+            .strip_prefix("/licenses/")
+            .or(path.strip_prefix("/license/"))
+        {
+            Some(name) => {
+                let name = name.trim_end_matches(".php").trim_end_matches(".html");
+                format!("/license/{}", name.to_ascii_lowercase())
+            }
+            None => return url.to_owned(),
+        },
+        "creativecommons.org" => {
+            let mut parts: Vec<_> = path.split('/').collect();
+            if parts
+                .last()
+                .is_some_and(|p| p.starts_with("legalcode") || p.starts_with("deed"))
+            {
+                parts.pop();
+            }
+            format!("{}/", parts.join("/"))
+        }
+        "spdx.org" => match path.strip_prefix("/licenses/") {
+            Some(id) if !id.is_empty() && !id.contains('/') => {
+                format!("/licenses/{}.html", id.trim_end_matches(".html"))
+            }
+            _ => return url.to_owned(),
+        },
+        _ => return url.to_owned(),
+    };
+    format!("https://{host}{path}")
+        .parse()
+        .expect("canonical license URL should be valid")
+}
+
+#[cfg(test)]
+mod tests {
+    use zarrs_conventions::iref::UriBuf;
+
+    use super::{canonical_url, check_url};
+
+    fn url(s: &str) -> UriBuf {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn url_canonical() {
+        let canon = |s: &str| canonical_url(&url(s)).as_str().to_string();
+        assert_eq!(
+            canon("https://opensource.org/licenses/Apache-2.0/"),
+            "https://opensource.org/license/apache-2.0"
+        );
+        assert_eq!(
+            canon("https://creativecommons.org/publicdomain/zero/1.0/deed.en"),
+            "https://creativecommons.org/publicdomain/zero/1.0/"
+        );
+        assert_eq!(
+            canon("http://spdx.org/licenses/MIT"),
+            "https://spdx.org/licenses/MIT.html"
+        );
+        assert_eq!(canon("https://example.com/L"), "https://example.com/L");
+
+        assert!(check_url(&url("HTTPS://example.com")).is_ok());
+        assert!(check_url(&url("ftp://example.com/LICENSE")).is_err());
+        assert!(check_url(&url("urn:isbn:0451450523")).is_err());
+    }
+}
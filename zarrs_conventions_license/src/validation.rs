@@ -0,0 +1,239 @@
+//! Checks on license metadata beyond what parsing enforces.
+use zarrs_conventions::validate::{Issue, Lint};
+
+use crate::{
+    KnownLicense, License, canonical_url,
+    normalize::canonical_spdx,
+    url::{SHORTENERS, check_url, split},
+};
+
+/// Whether the identifier is on the SPDX license list,
+/// or is a user-defined `LicenseRef-` identifier.
+#[cfg(feature = "spdx")]
+pub(crate) fn is_known_spdx(id: &str) -> bool {
+    id.starts_with("LicenseRef-") || spdx::license_id(id).is_some()
+}
+
+/// A listed identifier which the given one may be a typo of.
+#[cfg(feature = "spdx")]
+fn suggest_spdx(id: &str) -> Option<&'static str> {
+    spdx::identifiers::LICENSES
+        .iter()
+        .find(|(name, _, _)| name.eq_ignore_ascii_case(id))
+        .map(|(name, _, _)| *name)
+        .or_else(|| {
+            spdx::imprecise_license_id(id)
+                .filter(|(_, len)| *len == id.len())
+                .map(|(lic, _)| lic.name)
+        })
+}
+
+/// Fails with an actionable message if the identifier is not known.
+#[cfg(feature = "spdx")]
+pub(crate) fn check_spdx(id: &str) -> Result<(), String> {
+    if is_known_spdx(id) {
+        return Ok(());
+    }
+    let mut msg = format!("'{id}' is not an SPDX license identifier");
+    if let Some(suggestion) = suggest_spdx(id) {
+        msg.push_str(&format!("; did you mean '{suggestion}'?"));
+    }
+    Err(msg)
+}
+
+/// Issues with a license URL which cannot locate license text, or may not stay usable.
+fn lint_url(license: &License, issues: &mut Vec<Issue>) {
+    let Some(url) = license.url() else {
+        return;
+    };
+    if let Err(msg) = check_url(url) {
+        issues.push(Issue::error("unusable-url", msg));
+        return;
+    }
+    let Some((scheme, host, _)) = split(url) else {
+        return;
+    };
+    if scheme.eq_ignore_ascii_case("http") {
+        issues.push(Issue::warning(
+            "insecure-url",
+            format!("license URL '{url}' should use https"),
+        ));
+    }
+    if SHORTENERS.iter().any(|s| s.eq_ignore_ascii_case(host)) {
+        issues.push(Issue::warning(
+            "shortened-url",
+            format!("license URL '{url}' uses a link shortener; link to the license directly"),
+        ));
+    }
+    let canonical = canonical_url(url);
+    if canonical != url {
+        issues.push(Issue::warning(
+            "non-canonical-url",
+            format!("license URL '{url}' has the stable form '{canonical}'"),
+        ));
+    }
+}
+
+/// SPDX identifier without an `-only`/`-or-later`/`+` suffix,
+/// which license URLs do not distinguish.
+fn family(id: &str) -> &str {
+    ["-only", "-or-later", "+"]
+        .into_iter()
+        .find_map(|suffix| id.strip_suffix(suffix))
+        .unwrap_or(id)
+}
+
+/// Issues where forms set on the same license contradict each other.
+fn lint_consistency(license: &License, issues: &mut Vec<Issue>) {
+    let spdx = license
+        .spdx()
+        .map(str::trim)
+        .map(|id| canonical_spdx(id).unwrap_or(id));
+    let known_url = license.url().and_then(KnownLicense::from_url);
+    if let (Some(id), Some(known)) = (spdx, known_url)
+        && family(id) != family(known.spdx())
+    {
+        issues.push(Issue::warning(
+            "spdx-url-mismatch",
+            format!(
+                "license URL '{}' is for '{known}', not SPDX identifier '{id}'",
+                known.url()
+            ),
+        ));
+    }
+    #[cfg(feature = "text")]
+    if let Some(text) = license.text()
+        && let Some(id) = spdx.or(known_url.map(KnownLicense::spdx))
+        && spdx::license_id(id).is_some()
+        && !crate::similarity::is_text_of(text, id)
+    {
+        issues.push(Issue::warning(
+            "text-mismatch",
+            format!("license text does not match the text of '{id}'"),
+        ));
+    }
+}
+
+impl Lint for License {
+    fn lint(&self) -> Vec<Issue> {
+        let mut issues = Vec::default();
+        lint_url(self, &mut issues);
+        lint_consistency(self, &mut issues);
+        #[cfg(feature = "spdx")]
+        if let Some(id) = self.spdx() {
+            match check_spdx(id) {
+                Err(msg) => issues.push(Issue::warning("unknown-spdx", msg)),
+                Ok(()) => {
+                    if spdx::license_id(id).is_some_and(|l| l.is_deprecated()) {
+                        issues.push(Issue::warning(
+                            "deprecated-spdx",
+                            format!("SPDX identifier '{id}' is deprecated"),
+                        ));
+                    }
+                }
+            }
+        }
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use zarrs_conventions::validate::Lint;
+
+    use crate::License;
+
+    #[test]
+    fn url_lints() {
+        let codes = |url: &str| -> Vec<_> {
+            License::new_url(url.parse().unwrap())
+                .lint()
+                .into_iter()
+                .map(|i| i.code)
+                .collect()
+        };
+        assert_eq!(
+            codes("http://creativecommons.org/licenses/by/4.0/"),
+            ["insecure-url", "non-canonical-url"]
+        );
+        assert_eq!(codes("https://bit.ly/abc"), ["shortened-url"]);
+        assert!(codes("https://example.com/LICENSE").is_empty());
+        assert_eq!(codes("ftp://example.com/L"), ["unusable-url"]);
+    }
+
+    #[cfg(feature = "spdx")]
+    #[test]
+    fn spdx_validity() {
+        assert!(License::new_spdx("MIT").is_valid_spdx());
+        assert!(License::new_spdx("LicenseRef-internal").is_valid_spdx());
+        assert!(!License::new_spdx("BSD").is_valid_spdx());
+        assert!(!License::new_url("https://example.com/l".parse().unwrap()).is_valid_spdx());
+
+        assert!(License::try_new_spdx("Apache-2.0").is_ok());
+        let err = License::builder().spdx("mit").build().unwrap_err();
+        assert!(err.contains("did you mean 'MIT'"), "{err}");
+
+        let codes = |id: &str| -> Vec<_> {
+            License::new_spdx(id)
+                .lint()
+                .into_iter()
+                .map(|i| i.code)
+                .collect()
+        };
+        assert_eq!(codes("GPL"), ["unknown-spdx"]);
+        assert_eq!(codes("GPL-2.0"), ["deprecated-spdx"]);
+        assert!(codes("GPL-2.0-only").is_empty());
+    }
+
+    #[test]
+    fn consistency_lints() {
+        let codes = |license: serde_json::Value| -> Vec<_> {
+            serde_json::from_value::<License>(license)
+                .unwrap()
+                .lint()
+                .into_iter()
+                .map(|i| i.code)
+                .collect()
+        };
+        assert!(
+            codes(json!({"spdx": "MIT", "url": "https://opensource.org/license/mit"})).is_empty()
+        );
+        assert!(
+            codes(json!({"spdx": "GPL-3.0-or-later", "url": "https://www.gnu.org/licenses/gpl-3.0.html"}))
+                .is_empty()
+        );
+        assert!(codes(json!({"spdx": "LicenseRef-x", "url": "https://example.com/x"})).is_empty());
+        assert_eq!(
+            codes(json!({"spdx": "Apache-2.0", "url": "https://opensource.org/license/mit"})),
+            ["spdx-url-mismatch"]
+        );
+    }
+
+    #[cfg(feature = "text")]
+    #[test]
+    fn text_consistency_lints() {
+        let mit_text = License::new_spdx("MIT").resolve_text().unwrap().to_string();
+        let lint = |license: serde_json::Value| -> Vec<_> {
+            serde_json::from_value::<License>(license)
+                .unwrap()
+                .lint()
+                .into_iter()
+                .map(|i| i.code)
+                .collect()
+        };
+        assert!(lint(json!({"spdx": "MIT", "text": mit_text})).is_empty());
+        assert!(
+            lint(json!({"url": "https://opensource.org/license/mit", "text": mit_text})).is_empty()
+        );
+        assert!(lint(json!({"spdx": "LicenseRef-x", "text": mit_text})).is_empty());
+        assert_eq!(
+            lint(json!({"spdx": "Apache-2.0", "text": mit_text})),
+            ["text-mismatch"]
+        );
+        assert_eq!(
+            lint(json!({"url": "https://opensource.org/license/mit", "text": "our own terms"})),
+            ["text-mismatch"]
+        );
+    }
+}
@@ -1,27 +1,9 @@
-use rstest::rstest;
-use zarrs_conventions::{
-    DEFAULT_ZARR_CONVENTION_REGISTRY, NestedRepr, ZarrConventionImpl, ZarrConventions, ZarrMetadata,
-};
 use zarrs_conventions_license::License;
 
-#[test]
-fn is_registered() {
-    assert!(DEFAULT_ZARR_CONVENTION_REGISTRY.contains(&License::DEFINITION.id_uuid()));
-}
-
-#[rstest]
-fn test_examples(
-    #[files("spec/examples/*.json")]
-    #[mode = bytes]
-    contents: &[u8],
-) {
-    let attrs = serde_json::from_slice::<ZarrMetadata>(contents)
-        .expect("should be valid metadata")
-        .attributes;
-
-    let conventions =
-        ZarrConventions::from_attributes(&attrs).expect("should all be valid conventions");
-    assert!(conventions.contains(&License::DEFINITION.id_uuid()));
-
-    let _ = License::from_attributes_nested(&attrs).expect("should be present");
+zarrs_conventions::convention_test_suite! {
+    License,
+    repr = nested,
+    example = License::new_spdx("MIT"),
+    schema = "spec/schema.json",
+    optional_spec = true,
 }
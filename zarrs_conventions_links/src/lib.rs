@@ -0,0 +1,349 @@
+#![doc = include_str!("../README.md")]
+use serde::{Deserialize, Serialize};
+pub use zarrs_conventions;
+use zarrs_conventions::{
+    NodePath, NodePathError, define_convention,
+    iref::{Uri, UriBuf},
+    validate::{Issue, Lint},
+};
+
+pub mod stac;
+
+/// Relation types registered with IANA, or used by STAC, which are common for datasets.
+///
+/// Other relation types should be URIs, as extension relation types in RFC 8288.
+pub const KNOWN_RELS: &[&str] = &[
+    "alternate",
+    "child",
+    "cite-as",
+    "collection",
+    "derived_from",
+    "describedby",
+    "item",
+    "license",
+    "parent",
+    "preview",
+    "related",
+    "root",
+    "self",
+    "via",
+];
+
+/// What a link points to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum Target {
+    /// A resource outside the hierarchy, by absolute URI.
+    Href(
+        #[cfg_attr(feature = "schema", schemars(with = "String", extend("format" = "uri")))] UriBuf,
+    ),
+    /// Another node in the same hierarchy, by a path relative to the linking node,
+    /// or absolute from the root of the hierarchy.
+    Path(String),
+}
+
+/// Where a link resolves to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Resolved {
+    Href(UriBuf),
+    Node(NodePath),
+}
+
+/// A typed relation to another resource.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Link {
+    /// Relation type, e.g. `describedby`, or an extension relation type URI.
+    rel: String,
+    #[serde(flatten)]
+    target: Target,
+    /// Media type of the linked resource, e.g. `application/pdf`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    media_type: Option<String>,
+    /// Human-readable title of the linked resource.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+}
+
+impl Link {
+    /// A link to a resource outside the hierarchy.
+    pub fn href(rel: impl Into<String>, href: UriBuf) -> Self {
+        Self::new(rel, Target::Href(href))
+    }
+
+    /// A link to another node, which is checked when building [Links].
+    pub fn path(rel: impl Into<String>, path: impl Into<String>) -> Self {
+        Self::new(rel, Target::Path(path.into()))
+    }
+
+    fn new(rel: impl Into<String>, target: Target) -> Self {
+        Self {
+            rel: rel.into(),
+            target,
+            media_type: None,
+            title: None,
+        }
+    }
+
+    /// Set the media type of the linked resource, e.g. `application/pdf`.
+    pub fn with_media_type(mut self, media_type: impl Into<String>) -> Self {
+        self.media_type = Some(media_type.into());
+        self
+    }
+
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Relation type, e.g. `describedby`.
+    pub fn rel(&self) -> &str {
+        &self.rel
+    }
+
+    pub fn target(&self) -> &Target {
+        &self.target
+    }
+
+    pub fn media_type(&self) -> Option<&str> {
+        self.media_type.as_deref()
+    }
+
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// Whether the relation type is one of [KNOWN_RELS] or an extension relation type URI.
+    pub fn is_known_rel(&self) -> bool {
+        KNOWN_RELS.contains(&self.rel.as_str()) || Uri::new(self.rel.as_str()).is_ok()
+    }
+
+    /// Where the link points, from the node with the absolute path `node`.
+    ///
+    /// ```
+    /// use zarrs_conventions_links::{Link, Resolved};
+    ///
+    /// let link = Link::path("derived_from", "../raw");
+    /// let resolved = link.resolve(&"/processed/v1".parse().unwrap()).unwrap();
+    /// assert_eq!(resolved, Resolved::Node("/processed/raw".parse().unwrap()));
+    /// ```
+    pub fn resolve(&self, node: &NodePath) -> Result<Resolved, NodePathError> {
+        match &self.target {
+            Target::Href(href) => Ok(Resolved::Href(href.clone())),
+            Target::Path(path) => node.resolve(path).map(Resolved::Node),
+        }
+    }
+
+    fn problems(&self) -> Vec<BuildError> {
+        let mut problems = Vec::default();
+        if self.rel.trim().is_empty() {
+            problems.push(BuildError::EmptyRel);
+        }
+        // relative paths can only be checked against the linking node
+        if let Target::Path(path) = &self.target
+            && path.starts_with('/')
+            && let Err(e) = NodePath::new(path)
+        {
+            problems.push(BuildError::Path(e));
+        }
+        if let Some(media_type) = &self.media_type
+            && !is_media_type(media_type)
+        {
+            problems.push(BuildError::MediaType(media_type.clone()));
+        }
+        problems
+    }
+}
+
+/// Whether a string has the shape `type/subtype`, optionally with parameters.
+fn is_media_type(media_type: &str) -> bool {
+    let essence = media_type.split(';').next().unwrap_or_default().trim();
+    let token = |s: &str| {
+        !s.is_empty()
+            && s.chars()
+                .all(|c| c.is_ascii_alphanumeric() || "!#$&-^_.+".contains(c))
+    };
+    essence
+        .split_once('/')
+        .is_some_and(|(t, s)| token(t) && token(s))
+}
+
+define_convention! {
+    /// Typed relations from a node to other resources, inside or outside its hierarchy.
+    ///
+    /// Each link gives exactly one target: an absolute URI as `href`,
+    /// or the path of another node in the hierarchy as `path`.
+    #[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+    #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+    #[serde(transparent)]
+    pub struct Links(Vec<Link>);
+    schema_url = "https://raw.githubusercontent.com/clbarnes/zarrs_conventions/refs/tags/links-v1/zarrs_conventions_links/spec/schema.json",
+    spec_url = "https://github.com/clbarnes/zarrs_conventions/blob/links-v1/zarrs_conventions_links/spec/README.md",
+    name = "links",
+    description = "Typed relations to other resources",
+    nested = "links",
+    register = false,
+}
+
+impl Links {
+    pub fn builder() -> Builder {
+        Default::default()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Link> {
+        self.0.iter()
+    }
+
+    /// Links with the given relation type.
+    pub fn with_rel<'a>(&'a self, rel: &'a str) -> impl Iterator<Item = &'a Link> {
+        self.0.iter().filter(move |l| l.rel == rel)
+    }
+
+    /// Where each link points, from the node with the absolute path `node`.
+    pub fn resolve(&self, node: &NodePath) -> Result<Vec<Resolved>, NodePathError> {
+        self.0.iter().map(|l| l.resolve(node)).collect()
+    }
+}
+
+impl Lint for Links {
+    fn lint(&self) -> Vec<Issue> {
+        let mut issues = Vec::default();
+        for (index, link) in self.0.iter().enumerate() {
+            for e in link.problems() {
+                let (code, message) = (e.code(), format!("link {index}: {e}"));
+                issues.push(match e {
+                    BuildError::MediaType(_) => Issue::warning(code, message),
+                    _ => Issue::error(code, message),
+                });
+            }
+            if !link.rel.trim().is_empty() && !link.is_known_rel() {
+                issues.push(Issue::warning(
+                    "unknown-rel",
+                    format!(
+                        "link {index}: relation type '{}' is neither registered nor a URI",
+                        link.rel
+                    ),
+                ));
+            }
+        }
+        issues
+    }
+}
+
+/// Why links could not be built.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum BuildError {
+    #[error("relation type is empty")]
+    EmptyRel,
+    #[error(transparent)]
+    Path(#[from] NodePathError),
+    #[error("'{0}' is not a media type, e.g. application/json")]
+    MediaType(String),
+}
+
+impl BuildError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::EmptyRel => "empty-rel",
+            Self::Path(_) => "invalid-path",
+            Self::MediaType(_) => "invalid-media-type",
+        }
+    }
+}
+
+/// Builder for [Links], created by [Links::builder].
+#[derive(Debug, Clone, Default)]
+pub struct Builder {
+    inner: Links,
+}
+
+impl Builder {
+    pub fn link(mut self, link: Link) -> Self {
+        self.inner.0.push(link);
+        self
+    }
+
+    /// Build the links, checking relation types, paths, and media types.
+    pub fn build(self) -> Result<Links, BuildError> {
+        match self.inner.0.iter().flat_map(Link::problems).next() {
+            Some(e) => Err(e),
+            None => Ok(self.inner),
+        }
+    }
+
+    /// Build the links, also checking that every path resolves from the node with the absolute path `node`.
+    pub fn build_for_node(self, node: &NodePath) -> Result<Links, BuildError> {
+        let links = self.build()?;
+        links.resolve(node)?;
+        Ok(links)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use zarrs_conventions::{NodePath, NodePathError, validate::Lint};
+
+    use crate::{BuildError, Link, Links, Resolved};
+
+    #[test]
+    fn build() {
+        let links = Links::builder()
+            .link(
+                Link::href(
+                    "describedby",
+                    "https://example.org/paper.pdf".parse().unwrap(),
+                )
+                .with_media_type("application/pdf")
+                .with_title("Paper"),
+            )
+            .link(Link::path("derived_from", "../raw"))
+            .build_for_node(&"/processed".parse().unwrap())
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(&links).unwrap(),
+            json!([
+                {
+                    "rel": "describedby",
+                    "href": "https://example.org/paper.pdf",
+                    "media_type": "application/pdf",
+                    "title": "Paper",
+                },
+                {"rel": "derived_from", "path": "../raw"},
+            ])
+        );
+        assert_eq!(
+            links.resolve(&"/processed".parse().unwrap()).unwrap()[1],
+            Resolved::Node("/raw".parse().unwrap())
+        );
+        assert_eq!(links.with_rel("derived_from").count(), 1);
+        assert!(links.lint().is_empty());
+
+        assert_eq!(
+            Links::builder()
+                .link(Link::path("parent", ".."))
+                .build_for_node(&NodePath::root()),
+            Err(BuildError::Path(NodePathError::EscapesRoot("..".into())))
+        );
+        assert_eq!(
+            Links::builder()
+                .link(Link::path("child", "/a").with_media_type("json"))
+                .build(),
+            Err(BuildError::MediaType("json".into()))
+        );
+    }
+
+    #[test]
+    fn lint() {
+        let links: Links = serde_json::from_value(json!([
+            {"rel": "", "path": "/.."},
+            {"rel": "cousin", "href": "https://example.org", "media_type": "text/html; charset=utf-8"},
+            {"rel": "https://example.org/rels/calibration", "path": "../../calibration"},
+        ]))
+        .unwrap();
+        let codes: Vec<_> = links.lint().into_iter().map(|i| i.code).collect();
+        assert_eq!(codes, ["empty-rel", "invalid-path", "unknown-rel"]);
+    }
+}
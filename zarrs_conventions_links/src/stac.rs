@@ -0,0 +1,92 @@
+//! Export of links as [STAC](https://stacspec.org) link objects.
+use serde::{Deserialize, Serialize};
+use zarrs_conventions::{NodePath, NodePathError};
+
+use crate::{Links, Resolved};
+
+/// STAC link object.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Link {
+    pub rel: String,
+    pub href: String,
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub media_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+impl Links {
+    /// STAC links from the node with the absolute path `node`,
+    /// for a STAC object describing that node.
+    ///
+    /// Links to other nodes have hrefs relative to the node's location, ending in `/`,
+    /// so that they resolve against a STAC object's URL in the node, e.g. `/raw` from `/processed` is `../raw/`.
+    ///
+    /// ```
+    /// use zarrs_conventions_links::{Link, Links};
+    ///
+    /// let links = Links::builder()
+    ///     .link(Link::path("derived_from", "/raw").with_title("Raw data"))
+    ///     .build()
+    ///     .unwrap();
+    /// let stac = links.to_stac(&"/processed".parse().unwrap()).unwrap();
+    /// assert_eq!(stac[0].href, "../raw/");
+    /// ```
+    pub fn to_stac(&self, node: &NodePath) -> Result<Vec<Link>, NodePathError> {
+        self.0
+            .iter()
+            .map(|link| {
+                let href = match link.resolve(node)? {
+                    Resolved::Href(href) => href.to_string(),
+                    Resolved::Node(target) => {
+                        // hrefs are relative to a document within the node
+                        let relative = target.relative_to(node);
+                        if relative.is_empty() {
+                            "./".to_string()
+                        } else {
+                            format!("{relative}/")
+                        }
+                    }
+                };
+                Ok(Link {
+                    rel: link.rel.clone(),
+                    href,
+                    media_type: link.media_type.clone(),
+                    title: link.title.clone(),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{Link, Links};
+
+    #[test]
+    fn stac() {
+        let links = Links::builder()
+            .link(
+                Link::href("license", "https://example.org/LICENSE".parse().unwrap())
+                    .with_media_type("text/plain"),
+            )
+            .link(Link::path("self", ""))
+            .link(Link::path("child", "b"))
+            .link(Link::path("parent", ".."))
+            .build()
+            .unwrap();
+        let stac = links.to_stac(&"/a".parse().unwrap()).unwrap();
+        assert_eq!(
+            serde_json::to_value(&stac).unwrap(),
+            json!([
+                {"rel": "license", "href": "https://example.org/LICENSE", "type": "text/plain"},
+                {"rel": "self", "href": "./"},
+                {"rel": "child", "href": "b/"},
+                {"rel": "parent", "href": "../"},
+            ])
+        );
+        assert!(links.to_stac(&"/".parse().unwrap()).is_err());
+    }
+}
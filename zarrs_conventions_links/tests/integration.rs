@@ -0,0 +1,34 @@
+use zarrs_conventions::test_util::{parse_example, parse_modified_example, parse_nested};
+use zarrs_conventions_links::Links;
+
+const EXAMPLE: &str = include_str!("examples/provenance.json");
+
+zarrs_conventions::convention_test_suite! {
+    Links,
+    repr = nested,
+    example = parse_example(EXAMPLE, parse_nested),
+    examples = "tests/examples",
+    schema = "spec/schema.json",
+    registered = false,
+}
+
+#[test]
+fn reject_missing_rel() {
+    let parsed = parse_modified_example(
+        EXAMPLE,
+        |attrs| {
+            attrs["links"][0].as_object_mut().unwrap().remove("rel");
+        },
+        parse_nested::<Links>,
+    );
+    assert!(parsed.is_err());
+}
+
+#[cfg(feature = "schema")]
+#[test]
+fn spec_up_to_date() {
+    zarrs_conventions::test_util::assert_docs_up_to_date(
+        &zarrs_conventions::docgen::ConventionDocs::nested::<Links>(),
+        concat!(env!("CARGO_MANIFEST_DIR"), "/spec"),
+    );
+}
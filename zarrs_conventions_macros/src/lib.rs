@@ -0,0 +1,112 @@
+//! Procedural macros for [zarrs_conventions](https://docs.rs/zarrs_conventions).
+//!
+//! Use them through the re-exports in that crate, which supply the crate path.
+use std::path::Path;
+
+use proc_macro2::{TokenStream, TokenTree};
+use quote::quote;
+use syn::LitStr;
+
+/// Embed a directory's JSON files, checking that they parse.
+///
+/// Input is the path to the `zarrs_conventions` crate, a comma, and the directory
+/// relative to the invoking crate's manifest.
+/// See `zarrs_conventions::include_examples!`.
+///
+/// If followed by `, optional`, expands to an `Option` which is `None` if the directory cannot be read,
+/// rather than failing to compile.
+///
+/// Each file is embedded with `include_str!`, so edits to it are tracked,
+/// but stable proc macros cannot depend on a directory:
+/// files added or removed after the invoking crate was compiled are not seen until it is rebuilt.
+#[proc_macro]
+pub fn include_examples(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    match include_examples_impl(input.into()) {
+        Ok(ts) => ts.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn include_examples_impl(input: TokenStream) -> syn::Result<TokenStream> {
+    let mut tokens = input.into_iter();
+    let krate: TokenStream = tokens
+        .by_ref()
+        .take_while(|tt| !matches!(tt, TokenTree::Punct(p) if p.as_char() == ','))
+        .collect();
+    let dir: LitStr = syn::parse2(
+        tokens
+            .by_ref()
+            .take_while(|tt| !matches!(tt, TokenTree::Punct(p) if p.as_char() == ','))
+            .collect(),
+    )?;
+    let optional = match tokens.next() {
+        None => false,
+        Some(TokenTree::Ident(ident)) if ident == "optional" && tokens.next().is_none() => true,
+        Some(tt) => return Err(syn::Error::new(tt.span(), "expected `optional`")),
+    };
+
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
+        .map_err(|e| syn::Error::new(dir.span(), format!("CARGO_MANIFEST_DIR not set: {e}")))?;
+    let full_dir = Path::new(&manifest_dir).join(dir.value());
+    let entries = match std::fs::read_dir(&full_dir) {
+        Ok(entries) => entries,
+        Err(_) if optional => return Ok(quote! { ::core::option::Option::None }),
+        Err(e) => {
+            return Err(syn::Error::new(
+                dir.span(),
+                format!("could not read {}: {e}", full_dir.display()),
+            ));
+        }
+    };
+
+    let mut paths = Vec::default();
+    for entry in entries {
+        let path = entry
+            .map_err(|e| syn::Error::new(dir.span(), e.to_string()))?
+            .path();
+        if path.extension().is_some_and(|ext| ext == "json") {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    let mut examples = Vec::with_capacity(paths.len());
+    for path in paths {
+        let contents = std::fs::read_to_string(&path).map_err(|e| {
+            syn::Error::new(
+                dir.span(),
+                format!("could not read {}: {e}", path.display()),
+            )
+        })?;
+        if let Err(e) = serde_json::from_str::<serde_json::Value>(&contents) {
+            return Err(syn::Error::new(
+                dir.span(),
+                format!("{} is not valid JSON: {e}", path.display()),
+            ));
+        }
+        let name = path
+            .file_name()
+            .expect("listed file should have a name")
+            .to_string_lossy()
+            .into_owned();
+        let abs = path.to_string_lossy().into_owned();
+        examples.push(quote! {
+            #krate::test_util::Example {
+                name: #name,
+                json: ::core::include_str!(#abs),
+            }
+        });
+    }
+
+    let examples = quote! {
+        {
+            const EXAMPLES: &[#krate::test_util::Example] = &[#(#examples),*];
+            EXAMPLES
+        }
+    };
+    Ok(if optional {
+        quote! { ::core::option::Option::Some(#examples) }
+    } else {
+        examples
+    })
+}
@@ -0,0 +1,107 @@
+//! Titles and descriptions for dataset catalogs, e.g. STAC and DataCite.
+use serde_json::{Map, Value, json};
+
+use crate::Names;
+
+impl Names {
+    /// The most suitable title for a catalog entry:
+    /// the title, the long name, or the standard name written as words.
+    ///
+    /// ```
+    /// use zarrs_conventions_names::{Names, StandardName};
+    ///
+    /// let names = Names::builder()
+    ///     .standard_name(StandardName::new("sea_water_temperature"))
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(names.catalog_title().as_deref(), Some("Sea water temperature"));
+    /// ```
+    pub fn catalog_title(&self) -> Option<String> {
+        if let Some(title) = self.title.as_ref().or(self.long_name.as_ref()) {
+            return Some(title.clone());
+        }
+        let words = self.standard_name.as_ref()?.name().replace('_', " ");
+        let mut chars = words.chars();
+        let first = chars.next()?;
+        Some(first.to_uppercase().chain(chars).collect())
+    }
+
+    /// STAC `title` and `description` fields, for an Item's properties or a Collection.
+    pub fn to_stac(&self) -> Map<String, Value> {
+        let mut fields = Map::default();
+        if let Some(title) = self.catalog_title() {
+            fields.insert("title".into(), title.into());
+        }
+        if let Some(description) = &self.description {
+            fields.insert("description".into(), description.clone().into());
+        }
+        fields
+    }
+
+    /// DataCite `titles` and `descriptions`, with the long name as an alternative title
+    /// if there is also a title.
+    ///
+    /// ```
+    /// use zarrs_conventions_names::Names;
+    ///
+    /// let names = Names::builder().title("SST").description("Daily means").build().unwrap();
+    /// let datacite = names.to_datacite();
+    /// assert_eq!(datacite["titles"][0]["title"], "SST");
+    /// assert_eq!(datacite["descriptions"][0]["descriptionType"], "Abstract");
+    /// ```
+    pub fn to_datacite(&self) -> Value {
+        let mut titles = Vec::default();
+        if let Some(title) = self.catalog_title() {
+            titles.push(json!({"title": title}));
+        }
+        if let (Some(_), Some(long_name)) = (&self.title, &self.long_name) {
+            titles.push(json!({"title": long_name, "titleType": "AlternativeTitle"}));
+        }
+        let descriptions: Vec<_> = self
+            .description
+            .iter()
+            .map(|d| json!({"description": d, "descriptionType": "Abstract"}))
+            .collect();
+        json!({"titles": titles, "descriptions": descriptions})
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{Names, StandardName};
+
+    #[test]
+    fn catalog() {
+        let names = Names::builder()
+            .title("SST")
+            .long_name("Sea surface temperature")
+            .standard_name(StandardName::new("sea_surface_temperature"))
+            .build()
+            .unwrap();
+        assert_eq!(
+            serde_json::Value::from(names.to_stac()),
+            json!({"title": "SST"})
+        );
+        assert_eq!(
+            names.to_datacite(),
+            json!({
+                "titles": [
+                    {"title": "SST"},
+                    {"title": "Sea surface temperature", "titleType": "AlternativeTitle"},
+                ],
+                "descriptions": [],
+            })
+        );
+        let names = Names::builder()
+            .long_name("Sea surface temperature")
+            .build()
+            .unwrap();
+        assert_eq!(
+            names.catalog_title().as_deref(),
+            Some("Sea surface temperature")
+        );
+        assert_eq!(Names::default().catalog_title(), None);
+    }
+}
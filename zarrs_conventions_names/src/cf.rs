@@ -0,0 +1,191 @@
+//! Plain attributes of the [CF](https://cfconventions.org) and
+//! [ACDD](https://wiki.esipfed.org/Attribute_Convention_for_Data_Discovery_1-3) conventions,
+//! kept alongside the formal names payload for tools which do not know the convention.
+//!
+//! The description is written as the ACDD `summary`, and the standard name's vocabulary
+//! as the ACDD `standard_name_vocabulary`.
+use zarrs_conventions::{Attributes, NestedRepr, validate::Issue};
+
+use crate::{BuildError, Names, StandardName};
+
+pub const TITLE_KEY: &str = "title";
+pub const LONG_NAME_KEY: &str = "long_name";
+pub const SUMMARY_KEY: &str = "summary";
+pub const STANDARD_NAME_KEY: &str = "standard_name";
+pub const STANDARD_NAME_VOCABULARY_KEY: &str = "standard_name_vocabulary";
+
+/// Why CF attributes could not be read.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum CfError {
+    #[error("'{0}' attribute is not a string")]
+    NotAString(&'static str),
+    #[error(transparent)]
+    StandardName(#[from] BuildError),
+}
+
+fn get<'a>(attributes: &'a Attributes, key: &'static str) -> Result<Option<&'a str>, CfError> {
+    attributes
+        .get(key)
+        .map(|v| v.as_str().ok_or(CfError::NotAString(key)))
+        .transpose()
+}
+
+/// Each plain attribute which would be written for the names.
+fn cf_attributes(names: &Names) -> Vec<(&'static str, String)> {
+    let mut out = Vec::default();
+    let texts = [
+        (TITLE_KEY, &names.title),
+        (LONG_NAME_KEY, &names.long_name),
+        (SUMMARY_KEY, &names.description),
+    ];
+    for (key, text) in texts {
+        if let Some(text) = text {
+            out.push((key, text.clone()));
+        }
+    }
+    if let Some(standard_name) = &names.standard_name {
+        out.push((STANDARD_NAME_KEY, standard_name.to_string()));
+        if let Some(vocabulary) = standard_name.vocabulary() {
+            out.push((STANDARD_NAME_VOCABULARY_KEY, vocabulary.to_string()));
+        }
+    }
+    out
+}
+
+impl Names {
+    /// Write the names as plain CF and ACDD attributes.
+    ///
+    /// ```
+    /// use zarrs_conventions_names::{Names, StandardName};
+    ///
+    /// let names = Names::builder()
+    ///     .long_name("Air temperature at 2m")
+    ///     .standard_name(StandardName::new("air_temperature"))
+    ///     .build()
+    ///     .unwrap();
+    /// let mut attributes = serde_json::Map::default();
+    /// names.write_cf_attributes(&mut attributes);
+    /// assert_eq!(attributes["standard_name"], "air_temperature");
+    /// assert_eq!(Names::from_cf_attributes(&attributes), Ok(Some(names)));
+    /// ```
+    pub fn write_cf_attributes(&self, attributes: &mut Attributes) {
+        for (key, value) in cf_attributes(self) {
+            attributes.insert(key.to_string(), value.into());
+        }
+    }
+
+    /// Names from plain CF and ACDD attributes, if any are present.
+    pub fn from_cf_attributes(attributes: &Attributes) -> Result<Option<Self>, CfError> {
+        let mut standard_name = get(attributes, STANDARD_NAME_KEY)?
+            .map(StandardName::parse)
+            .transpose()?;
+        if let Some(vocabulary) = get(attributes, STANDARD_NAME_VOCABULARY_KEY)? {
+            standard_name = standard_name.map(|s| s.with_vocabulary(vocabulary));
+        }
+        let names = Self {
+            title: get(attributes, TITLE_KEY)?.map(str::to_string),
+            long_name: get(attributes, LONG_NAME_KEY)?.map(str::to_string),
+            description: get(attributes, SUMMARY_KEY)?.map(str::to_string),
+            standard_name,
+        };
+        Ok((names != Self::default()).then_some(names))
+    }
+}
+
+/// Check that plain CF and ACDD attributes agree with the names payload, if both are present.
+///
+/// ```
+/// use zarrs_conventions_names::cf::lint_cf_attributes;
+///
+/// let attributes = serde_json::json!({
+///     "long_name": "Temperature",
+///     "names": {"long_name": "Air temperature"},
+/// });
+/// let issues = lint_cf_attributes(attributes.as_object().unwrap());
+/// assert_eq!(issues[0].code, "inconsistent-cf-attribute");
+/// ```
+pub fn lint_cf_attributes(attributes: &Attributes) -> Vec<Issue> {
+    let Ok(names) = Names::from_attributes_nested(attributes) else {
+        // reported when validating the payload itself
+        return Vec::default();
+    };
+    cf_attributes(&names)
+        .into_iter()
+        .filter_map(|(key, expected)| {
+            let found = attributes.get(key)?;
+            let message = match found.as_str() {
+                Some(found) if found == expected => return None,
+                Some(found) => {
+                    format!("'{key}' attribute is '{found}', but the payload gives '{expected}'")
+                }
+                None => CfError::NotAString(key).to_string(),
+            };
+            Some(Issue::warning("inconsistent-cf-attribute", message))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{CfError, lint_cf_attributes};
+    use crate::{BuildError, Modifier, Names};
+
+    #[test]
+    fn cf_attributes() {
+        let attributes = json!({
+            "title": "SST",
+            "summary": "Daily sea surface temperature",
+            "standard_name": "sea_surface_temperature status_flag",
+            "standard_name_vocabulary": "CF Standard Name Table v84",
+        });
+        let names = Names::from_cf_attributes(attributes.as_object().unwrap())
+            .unwrap()
+            .unwrap();
+        assert_eq!(names.title(), Some("SST"));
+        assert_eq!(names.description(), Some("Daily sea surface temperature"));
+        let standard_name = names.standard_name().unwrap();
+        assert_eq!(standard_name.modifier(), Some(Modifier::StatusFlag));
+        assert_eq!(
+            standard_name.vocabulary(),
+            Some("CF Standard Name Table v84")
+        );
+
+        let mut written = serde_json::Map::default();
+        names.write_cf_attributes(&mut written);
+        assert_eq!(serde_json::Value::from(written), attributes);
+
+        assert_eq!(
+            Names::from_cf_attributes(json!({"units": "K"}).as_object().unwrap()),
+            Ok(None)
+        );
+        assert_eq!(
+            Names::from_cf_attributes(json!({"long_name": 1}).as_object().unwrap()),
+            Err(CfError::NotAString("long_name"))
+        );
+        assert_eq!(
+            Names::from_cf_attributes(json!({"standard_name": "Temp"}).as_object().unwrap()),
+            Err(CfError::StandardName(BuildError::InvalidStandardName(
+                "Temp".into()
+            )))
+        );
+    }
+
+    #[test]
+    fn consistency() {
+        let lint = |value: serde_json::Value| {
+            let issues = lint_cf_attributes(value.as_object().unwrap());
+            issues.into_iter().map(|i| i.code).collect::<Vec<_>>()
+        };
+        let names = json!({"title": "SST", "standard_name": {"name": "sea_surface_temperature"}});
+        assert!(lint(json!({"names": names})).is_empty());
+        assert!(lint(json!({"title": "SST", "names": names})).is_empty());
+        assert!(lint(json!({"title": "Other"})).is_empty());
+        assert_eq!(
+            lint(json!({"title": "Other", "standard_name": 1, "names": names})),
+            ["inconsistent-cf-attribute", "inconsistent-cf-attribute"]
+        );
+    }
+}
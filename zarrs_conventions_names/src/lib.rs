@@ -0,0 +1,347 @@
+#![doc = include_str!("../README.md")]
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+pub use zarrs_conventions;
+use zarrs_conventions::{
+    define_convention,
+    validate::{Issue, Lint},
+};
+
+pub mod catalog;
+pub mod cf;
+#[cfg(feature = "cf-table")]
+pub mod table;
+
+/// A qualifier of a CF standard name, for quantities derived from the named quantity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum Modifier {
+    DetectionMinimum,
+    NumberOfObservations,
+    StandardError,
+    StatusFlag,
+}
+
+impl Modifier {
+    const ALL: [Self; 4] = [
+        Self::DetectionMinimum,
+        Self::NumberOfObservations,
+        Self::StandardError,
+        Self::StatusFlag,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::DetectionMinimum => "detection_minimum",
+            Self::NumberOfObservations => "number_of_observations",
+            Self::StandardError => "standard_error",
+            Self::StatusFlag => "status_flag",
+        }
+    }
+}
+
+/// A name from a controlled vocabulary, by default the
+/// [CF standard name table](https://cfconventions.org/Data/cf-standard-names/current/build/cf-standard-name-table.html).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct StandardName {
+    /// The name, e.g. `sea_water_temperature`.
+    name: String,
+    /// Qualifier for quantities derived from the named quantity.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    modifier: Option<Modifier>,
+    /// The vocabulary and its version, if not the CF standard name table.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    vocabulary: Option<String>,
+}
+
+impl StandardName {
+    /// A standard name, e.g. `sea_water_temperature`, which is checked when building [Names].
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            modifier: None,
+            vocabulary: None,
+        }
+    }
+
+    pub fn with_modifier(mut self, modifier: Modifier) -> Self {
+        self.modifier = Some(modifier);
+        self
+    }
+
+    /// Name the vocabulary and its version, e.g. `CF Standard Name Table v84`.
+    pub fn with_vocabulary(mut self, vocabulary: impl Into<String>) -> Self {
+        self.vocabulary = Some(vocabulary.into());
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn modifier(&self) -> Option<Modifier> {
+        self.modifier
+    }
+
+    pub fn vocabulary(&self) -> Option<&str> {
+        self.vocabulary.as_deref()
+    }
+
+    /// Parse a CF `standard_name` attribute, which may be followed by a modifier.
+    ///
+    /// ```
+    /// use zarrs_conventions_names::{Modifier, StandardName};
+    ///
+    /// let name = StandardName::parse("air_temperature standard_error").unwrap();
+    /// assert_eq!(name.name(), "air_temperature");
+    /// assert_eq!(name.modifier(), Some(Modifier::StandardError));
+    /// assert_eq!(name.to_string(), "air_temperature standard_error");
+    /// ```
+    pub fn parse(attribute: &str) -> Result<Self, BuildError> {
+        let mut parts = attribute.split_whitespace();
+        let invalid = || BuildError::InvalidStandardName(attribute.to_string());
+        let mut standard_name = Self::new(parts.next().ok_or_else(invalid)?);
+        if let Some(modifier) = parts.next() {
+            let modifier = Modifier::ALL
+                .into_iter()
+                .find(|m| m.as_str() == modifier)
+                .ok_or_else(invalid)?;
+            standard_name.modifier = Some(modifier);
+        }
+        if parts.next().is_some() {
+            return Err(invalid());
+        }
+        standard_name.validate()?;
+        Ok(standard_name)
+    }
+
+    /// Check that the name is made of lowercase letters, digits, and underscores,
+    /// starting with a letter.
+    pub fn validate(&self) -> Result<(), BuildError> {
+        let valid = self.name.starts_with(|c: char| c.is_ascii_lowercase())
+            && self
+                .name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+        if valid {
+            Ok(())
+        } else {
+            Err(BuildError::InvalidStandardName(self.name.clone()))
+        }
+    }
+}
+
+impl fmt::Display for StandardName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)?;
+        if let Some(modifier) = self.modifier {
+            write!(f, " {}", modifier.as_str())?;
+        }
+        Ok(())
+    }
+}
+
+define_convention! {
+    /// Human-readable names of a dataset or array, and the standard name of the quantity it holds.
+    #[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+    #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+    pub struct Names {
+        /// Short title, e.g. of a dataset in a catalog.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        title: Option<String>,
+        /// Descriptive name of the quantity, e.g. `Sea surface temperature from MODIS`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        long_name: Option<String>,
+        /// Free-text description.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+        /// Name of the quantity from a controlled vocabulary.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        standard_name: Option<StandardName>,
+    }
+    schema_url = "https://raw.githubusercontent.com/clbarnes/zarrs_conventions/refs/tags/names-v1/zarrs_conventions_names/spec/schema.json",
+    spec_url = "https://github.com/clbarnes/zarrs_conventions/blob/names-v1/zarrs_conventions_names/spec/README.md",
+    name = "names",
+    description = "Human-readable and standard names of a node",
+    nested = "names",
+    register = false,
+}
+
+impl Names {
+    pub fn builder() -> Builder {
+        Default::default()
+    }
+
+    /// Short title, e.g. of a dataset in a catalog.
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// Descriptive name of the quantity, e.g. `Sea surface temperature from MODIS`.
+    pub fn long_name(&self) -> Option<&str> {
+        self.long_name.as_deref()
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    pub fn standard_name(&self) -> Option<&StandardName> {
+        self.standard_name.as_ref()
+    }
+
+    /// Each free-text field with its name.
+    fn texts(&self) -> impl Iterator<Item = (&'static str, &str)> {
+        [
+            ("title", &self.title),
+            ("long_name", &self.long_name),
+            ("description", &self.description),
+        ]
+        .into_iter()
+        .filter_map(|(field, text)| Some((field, text.as_deref()?)))
+    }
+
+    fn validate(&self) -> Result<(), BuildError> {
+        if let Some((field, _)) = self.texts().find(|(_, text)| text.trim().is_empty()) {
+            return Err(BuildError::Empty(field));
+        }
+        if let Some(standard_name) = &self.standard_name {
+            standard_name.validate()?;
+        }
+        Ok(())
+    }
+}
+
+impl Lint for Names {
+    fn lint(&self) -> Vec<Issue> {
+        let mut issues = Vec::default();
+        if self.texts().next().is_none() && self.standard_name.is_none() {
+            issues.push(Issue::warning("no-names", "no names are given"));
+        }
+        for (field, _) in self.texts().filter(|(_, text)| text.trim().is_empty()) {
+            issues.push(Issue::error(
+                "empty-name",
+                BuildError::Empty(field).to_string(),
+            ));
+        }
+        if let Some(Err(e)) = self.standard_name.as_ref().map(StandardName::validate) {
+            issues.push(Issue::error("invalid-standard-name", e.to_string()));
+        }
+        issues
+    }
+}
+
+/// Why names could not be built.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum BuildError {
+    #[error("{0} is empty")]
+    Empty(&'static str),
+    #[error("'{0}' is not a standard name, which is lowercase words joined by underscores")]
+    InvalidStandardName(String),
+}
+
+/// Builder for [Names], created by [Names::builder].
+#[derive(Debug, Clone, Default)]
+pub struct Builder {
+    inner: Names,
+}
+
+impl Builder {
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.inner.title = Some(title.into());
+        self
+    }
+
+    pub fn long_name(mut self, long_name: impl Into<String>) -> Self {
+        self.inner.long_name = Some(long_name.into());
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.inner.description = Some(description.into());
+        self
+    }
+
+    pub fn standard_name(mut self, standard_name: StandardName) -> Self {
+        self.inner.standard_name = Some(standard_name);
+        self
+    }
+
+    /// Build the names, checking that none is empty and that the standard name is well-formed.
+    pub fn build(self) -> Result<Names, BuildError> {
+        self.inner.validate()?;
+        Ok(self.inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use zarrs_conventions::validate::Lint;
+
+    use crate::{BuildError, Modifier, Names, StandardName};
+
+    #[test]
+    fn build() {
+        let names = Names::builder()
+            .long_name("Sea surface temperature")
+            .standard_name(
+                StandardName::new("sea_surface_temperature")
+                    .with_modifier(Modifier::StandardError)
+                    .with_vocabulary("CF Standard Name Table v84"),
+            )
+            .build()
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(&names).unwrap(),
+            json!({
+                "long_name": "Sea surface temperature",
+                "standard_name": {
+                    "name": "sea_surface_temperature",
+                    "modifier": "standard_error",
+                    "vocabulary": "CF Standard Name Table v84",
+                },
+            })
+        );
+        assert!(names.lint().is_empty());
+
+        assert_eq!(
+            Names::builder().title(" ").build(),
+            Err(BuildError::Empty("title"))
+        );
+        assert_eq!(
+            Names::builder()
+                .standard_name(StandardName::new("Sea Water Temperature"))
+                .build(),
+            Err(BuildError::InvalidStandardName(
+                "Sea Water Temperature".into()
+            ))
+        );
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(StandardName::parse("time"), Ok(StandardName::new("time")));
+        for invalid in ["", "air_temperature error", "a b c", "2m_temperature"] {
+            assert!(StandardName::parse(invalid).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn lint() {
+        let codes = |value| {
+            let names: Names = serde_json::from_value(value).unwrap();
+            names.lint().into_iter().map(|i| i.code).collect::<Vec<_>>()
+        };
+        assert_eq!(codes(json!({})), ["no-names"]);
+        assert_eq!(
+            codes(json!({"title": "", "standard_name": {"name": "Air temperature"}})),
+            ["empty-name", "invalid-standard-name"]
+        );
+    }
+}
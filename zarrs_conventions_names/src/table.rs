@@ -0,0 +1,332 @@
+//! Lookup of standard names in the CF standard name table,
+//! as published in XML at <https://cfconventions.org/Data/cf-standard-names/current/src/cf-standard-name-table.xml>.
+//!
+//! An excerpt of commonly used names is built in;
+//! load the full table with [StandardNameTable::from_xml] to check names thoroughly.
+use std::{collections::HashMap, sync::LazyLock};
+
+use zarrs_conventions::validate::Issue;
+
+use crate::Names;
+
+static BUILTIN: LazyLock<StandardNameTable> = LazyLock::new(|| StandardNameTable {
+    excerpt: true,
+    ..StandardNameTable::from_xml(include_str!("../data/cf-standard-name-table.xml"))
+        .expect("built-in table is valid")
+});
+
+/// Why a standard name table could not be read.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("malformed standard name table: {0}")]
+pub struct TableError(String);
+
+/// A standard name defined by the table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// Units of the quantity, in UDUNITS syntax, e.g. `kg m-2 s-1`.
+    pub canonical_units: String,
+    pub description: String,
+}
+
+/// Result of looking up a standard name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lookup<'a> {
+    /// The name is defined.
+    Entry(&'a Entry),
+    /// The name is a deprecated alias of another name, which is defined.
+    Alias { name: &'a str, entry: &'a Entry },
+    /// The name is not in the table.
+    Unknown,
+}
+
+/// The CF standard name table, or part of it.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct StandardNameTable {
+    version: Option<u32>,
+    entries: HashMap<String, Entry>,
+    aliases: HashMap<String, String>,
+    excerpt: bool,
+}
+
+/// The text of the first element with the given tag in the XML, and the rest after it.
+fn element<'a>(xml: &'a str, tag: &str) -> Option<(&'a str, &'a str, &'a str)> {
+    let open = format!("<{tag}");
+    let start = xml.find(&open)?;
+    let after_open = &xml[start + open.len()..];
+    let attrs_end = after_open.find('>')?;
+    let attrs = &after_open[..attrs_end];
+    if let Some(attrs) = attrs.strip_suffix('/') {
+        return Some((attrs, "", &after_open[attrs_end + 1..]));
+    }
+    let body = &after_open[attrs_end + 1..];
+    let close = format!("</{tag}>");
+    let end = body.find(&close)?;
+    Some((attrs, &body[..end], &body[end + close.len()..]))
+}
+
+fn id(attrs: &str) -> Option<&str> {
+    let start = attrs.find("id=\"")? + 4;
+    let len = attrs[start..].find('"')?;
+    Some(&attrs[start..start + len])
+}
+
+fn unescape(text: &str) -> String {
+    text.trim()
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Each element with the given tag, and its attributes.
+fn elements<'a>(mut xml: &'a str, tag: &'a str) -> impl Iterator<Item = (&'a str, &'a str)> {
+    std::iter::from_fn(move || {
+        let (attrs, body, rest) = element(xml, tag)?;
+        xml = rest;
+        Some((attrs, body))
+    })
+}
+
+impl StandardNameTable {
+    /// An excerpt of the table with a few dozen commonly used names, built into the crate.
+    ///
+    /// Names missing from the excerpt may still be in the full table;
+    /// see [Names::lint_standard_name].
+    pub fn builtin() -> &'static Self {
+        &BUILTIN
+    }
+
+    /// Read the table from its XML publication.
+    pub fn from_xml(xml: &str) -> Result<Self, TableError> {
+        let err = |msg: &str| TableError(msg.to_string());
+        let (_, table, _) = element(xml, "standard_name_table")
+            .ok_or_else(|| err("no standard_name_table element"))?;
+        let version = match element(table, "version_number") {
+            Some((_, v, _)) => Some(
+                v.trim()
+                    .parse()
+                    .map_err(|_| err("version_number is not an integer"))?,
+            ),
+            None => None,
+        };
+        let mut entries = HashMap::default();
+        for (attrs, body) in elements(table, "entry") {
+            let id = id(attrs).ok_or_else(|| err("entry has no id"))?;
+            let text = |tag| {
+                element(body, tag)
+                    .map(|(_, t, _)| unescape(t))
+                    .unwrap_or_default()
+            };
+            entries.insert(
+                id.to_string(),
+                Entry {
+                    canonical_units: text("canonical_units"),
+                    description: text("description"),
+                },
+            );
+        }
+        let mut aliases = HashMap::default();
+        for (attrs, body) in elements(table, "alias") {
+            let id = id(attrs).ok_or_else(|| err("alias has no id"))?;
+            let (_, target, _) =
+                element(body, "entry_id").ok_or_else(|| err("alias has no entry_id"))?;
+            aliases.insert(id.to_string(), unescape(target));
+        }
+        Ok(Self {
+            version,
+            entries,
+            aliases,
+            excerpt: false,
+        })
+    }
+
+    /// Version number of the table, e.g. 84.
+    pub fn version(&self) -> Option<u32> {
+        self.version
+    }
+
+    /// The vocabulary of names from this table, as in the ACDD `standard_name_vocabulary`.
+    pub fn vocabulary(&self) -> String {
+        match self.version {
+            Some(v) => format!("CF Standard Name Table v{v}"),
+            None => "CF Standard Name Table".into(),
+        }
+    }
+
+    /// Whether this is only part of the table, like [Self::builtin].
+    pub fn is_excerpt(&self) -> bool {
+        self.excerpt
+    }
+
+    /// Number of defined names, not counting aliases.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Look up a standard name, following an alias to the name which replaced it.
+    ///
+    /// ```
+    /// use zarrs_conventions_names::table::{Lookup, StandardNameTable};
+    ///
+    /// let table = StandardNameTable::builtin();
+    /// let Lookup::Entry(entry) = table.lookup("air_temperature") else { panic!() };
+    /// assert_eq!(entry.canonical_units, "K");
+    /// assert_eq!(table.lookup("air_temprature"), Lookup::Unknown);
+    /// ```
+    pub fn lookup(&self, name: &str) -> Lookup<'_> {
+        if let Some(entry) = self.entries.get(name) {
+            return Lookup::Entry(entry);
+        }
+        match self
+            .aliases
+            .get(name)
+            .and_then(|target| Some((target, self.entries.get(target)?)))
+        {
+            Some((name, entry)) => Lookup::Alias { name, entry },
+            None => Lookup::Unknown,
+        }
+    }
+}
+
+impl Names {
+    /// Check the standard name against a table,
+    /// e.g. [StandardNameTable::builtin] or the full table read with [StandardNameTable::from_xml].
+    ///
+    /// Names which are unknown are only reported if they are from this table's vocabulary,
+    /// or no vocabulary is given;
+    /// if the table [is an excerpt](StandardNameTable::is_excerpt), only as information.
+    pub fn lint_standard_name(&self, table: &StandardNameTable) -> Vec<Issue> {
+        let Some(standard_name) = &self.standard_name else {
+            return Vec::default();
+        };
+        if standard_name
+            .vocabulary()
+            .is_some_and(|v| !v.starts_with("CF Standard Name Table"))
+        {
+            return Vec::default();
+        }
+        let name = standard_name.name();
+        match table.lookup(name) {
+            Lookup::Entry(_) => Vec::default(),
+            Lookup::Alias { name: target, .. } => vec![Issue::warning(
+                "aliased-standard-name",
+                format!("standard name '{name}' is an alias of '{target}'"),
+            )],
+            Lookup::Unknown if table.is_excerpt() => vec![Issue::info(
+                "unknown-standard-name",
+                format!(
+                    "'{name}' is not in the excerpt of {} built in; check it against the full table",
+                    table.vocabulary()
+                ),
+            )],
+            Lookup::Unknown => vec![Issue::warning(
+                "unknown-standard-name",
+                format!("'{name}' is not in {}", table.vocabulary()),
+            )],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zarrs_conventions::validate::Severity;
+
+    use super::{Lookup, StandardNameTable};
+    use crate::{Names, StandardName};
+
+    #[test]
+    fn builtin() {
+        let table = StandardNameTable::builtin();
+        assert_eq!(table.version(), Some(84));
+        assert_eq!(table.vocabulary(), "CF Standard Name Table v84");
+        assert!(table.len() > 30);
+        let Lookup::Entry(entry) = table.lookup("precipitation_flux") else {
+            panic!("precipitation_flux is defined")
+        };
+        assert_eq!(entry.canonical_units, "kg m-2 s-1");
+        assert!(entry.description.contains("\"flux\""));
+        assert!(matches!(
+            table.lookup("atmosphere_cloud_liquid_water_content"),
+            Lookup::Alias {
+                name: "atmosphere_mass_content_of_cloud_liquid_water",
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn from_xml() {
+        let table = StandardNameTable::from_xml(
+            r#"<standard_name_table>
+                <entry id="a_b"><canonical_units>m &amp; s</canonical_units></entry>
+                <entry id="c"><description/></entry>
+            </standard_name_table>"#,
+        )
+        .unwrap();
+        assert_eq!(table.version(), None);
+        assert_eq!(table.len(), 2);
+        let Lookup::Entry(entry) = table.lookup("a_b") else {
+            panic!("a_b is defined")
+        };
+        assert_eq!(entry.canonical_units, "m & s");
+        assert!(StandardNameTable::from_xml("<other/>").is_err());
+        assert!(
+            StandardNameTable::from_xml(
+                "<standard_name_table><entry></entry></standard_name_table>"
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn lint_standard_name() {
+        let table = StandardNameTable::builtin();
+        assert!(table.is_excerpt());
+        let lint = |table: &StandardNameTable, standard_name| {
+            let names = Names::builder()
+                .standard_name(standard_name)
+                .build()
+                .unwrap();
+            let issues = names.lint_standard_name(table);
+            issues
+                .into_iter()
+                .map(|i| (i.severity, i.code))
+                .collect::<Vec<_>>()
+        };
+        assert!(lint(table, StandardName::new("time")).is_empty());
+        assert_eq!(
+            lint(
+                table,
+                StandardName::new("atmosphere_cloud_liquid_water_content")
+            ),
+            [(Severity::Warning, "aliased-standard-name")]
+        );
+        let misspelt =
+            StandardName::new("air_temprature").with_vocabulary("CF Standard Name Table v84");
+        assert_eq!(
+            lint(table, misspelt.clone()),
+            [(Severity::Info, "unknown-standard-name")]
+        );
+        let full = StandardNameTable::from_xml(
+            "<standard_name_table><entry id=\"air_temperature\"/></standard_name_table>",
+        )
+        .unwrap();
+        assert!(!full.is_excerpt());
+        assert_eq!(
+            lint(&full, misspelt),
+            [(Severity::Warning, "unknown-standard-name")]
+        );
+        assert!(
+            lint(
+                table,
+                StandardName::new("air_temprature").with_vocabulary("GCMD")
+            )
+            .is_empty()
+        );
+    }
+}
@@ -0,0 +1,37 @@
+use zarrs_conventions::test_util::{parse_example, parse_modified_example, parse_nested};
+use zarrs_conventions_names::Names;
+
+const EXAMPLE: &str = include_str!("examples/sst.json");
+
+zarrs_conventions::convention_test_suite! {
+    Names,
+    repr = nested,
+    example = parse_example(EXAMPLE, parse_nested),
+    examples = "tests/examples",
+    schema = "spec/schema.json",
+    registered = false,
+}
+
+#[test]
+fn reject_unnamed_standard_name() {
+    let parsed = parse_modified_example(
+        EXAMPLE,
+        |attrs| {
+            attrs["names"]["standard_name"]
+                .as_object_mut()
+                .unwrap()
+                .remove("name");
+        },
+        parse_nested::<Names>,
+    );
+    assert!(parsed.is_err());
+}
+
+#[cfg(feature = "schema")]
+#[test]
+fn spec_up_to_date() {
+    zarrs_conventions::test_util::assert_docs_up_to_date(
+        &zarrs_conventions::docgen::ConventionDocs::nested::<Names>(),
+        concat!(env!("CARGO_MANIFEST_DIR"), "/spec"),
+    );
+}
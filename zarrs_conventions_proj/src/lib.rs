@@ -0,0 +1,451 @@
+#![doc = include_str!("../README.md")]
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+pub use zarrs_conventions;
+use zarrs_conventions::{
+    ConventionDefinition, NestedRepr, PrefixedRepr, ZarrConventionImpl,
+    iref::uri,
+    register_zarr_conventions,
+    uuid::uuid,
+    validate::{Issue, Lint},
+};
+
+#[cfg(feature = "proj4rs")]
+mod proj4;
+#[cfg(feature = "proj4rs")]
+pub use proj4::{Proj4Error, epsg_proj_string};
+
+/// WKT2 keywords which may begin a CRS definition.
+static WKT2_KEYWORDS: &[&str] = &[
+    "BOUNDCRS",
+    "COMPOUNDCRS",
+    "ENGCRS",
+    "ENGINEERINGCRS",
+    "GEODCRS",
+    "GEODETICCRS",
+    "GEOGCRS",
+    "GEOGRAPHICCRS",
+    "PROJCRS",
+    "PROJECTEDCRS",
+    "VERTCRS",
+    "VERTICALCRS",
+    "TIMECRS",
+    "DERIVEDPROJCRS",
+];
+
+/// Coordinate reference system of geospatial data,
+/// given as an authority code, WKT2, and/or PROJJSON,
+/// with the bounding box of the data in that system.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Proj {
+    /// Authority code of the CRS, e.g. `EPSG:4326`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code: Option<String>,
+    /// Definition of the CRS in WKT2.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    wkt2: Option<String>,
+    /// Definition of the CRS in PROJJSON.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    projjson: Option<Map<String, Value>>,
+    /// Bounding box of the data, in the CRS's coordinates.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bbox: Option<BoundingBox>,
+}
+
+impl Proj {
+    pub fn builder() -> Builder {
+        Default::default()
+    }
+
+    /// CRS identified by an authority code, e.g. `EPSG:4326`.
+    ///
+    /// The code is not checked; see [Builder::build].
+    pub fn from_code(code: impl Into<String>) -> Self {
+        Self {
+            code: Some(code.into()),
+            ..Default::default()
+        }
+    }
+
+    /// CRS identified by its EPSG code.
+    pub fn from_epsg(code: u32) -> Self {
+        Self::from_code(format!("EPSG:{code}"))
+    }
+
+    /// Authority code of the CRS, e.g. `EPSG:4326`.
+    pub fn code(&self) -> Option<&str> {
+        self.code.as_deref()
+    }
+
+    /// Authority and code of the CRS, e.g. `("EPSG", "4326")`,
+    /// if the code is of the form `AUTHORITY:CODE`.
+    pub fn authority_code(&self) -> Option<(&str, &str)> {
+        split_code(self.code.as_deref()?)
+    }
+
+    /// Numeric EPSG code of the CRS, if it is identified by one.
+    pub fn epsg(&self) -> Option<u32> {
+        match self.authority_code()? {
+            (authority, code) if authority.eq_ignore_ascii_case("EPSG") => code.parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// Definition of the CRS in [WKT2](https://docs.ogc.org/is/18-010r11/18-010r11.pdf).
+    pub fn wkt2(&self) -> Option<&str> {
+        self.wkt2.as_deref()
+    }
+
+    /// Definition of the CRS in [PROJJSON](https://proj.org/specifications/projjson.html).
+    pub fn projjson(&self) -> Option<&Map<String, Value>> {
+        self.projjson.as_ref()
+    }
+
+    /// Bounding box of the data, in the CRS's coordinates.
+    pub fn bbox(&self) -> Option<&BoundingBox> {
+        self.bbox.as_ref()
+    }
+
+    /// Whether any definition of the CRS is given.
+    pub fn has_crs(&self) -> bool {
+        self.code.is_some() || self.wkt2.is_some() || self.projjson.is_some()
+    }
+}
+
+/// Split an `AUTHORITY:CODE` string, neither part of which may be empty.
+fn split_code(code: &str) -> Option<(&str, &str)> {
+    match code.split_once(':')? {
+        ("", _) | (_, "") => None,
+        (authority, code) if !code.contains(':') => Some((authority, code)),
+        _ => None,
+    }
+}
+
+impl ZarrConventionImpl for Proj {
+    const DEFINITION: ConventionDefinition = ConventionDefinition {
+        uuid: uuid!("f17cb550-5864-4468-aeb7-f3180cfb622f"),
+        schema_url: uri!(
+            "https://raw.githubusercontent.com/zarr-experimental/geo-proj/refs/tags/v1/schema.json"
+        ),
+        spec_url: uri!("https://github.com/zarr-experimental/geo-proj/blob/v1/README.md"),
+        name: "proj:",
+        description: "Coordinate reference system information for geospatial data",
+    };
+}
+
+impl NestedRepr for Proj {
+    const KEY: &'static str = "proj";
+}
+
+impl PrefixedRepr for Proj {
+    const PREFIX: &'static str = "proj:";
+}
+
+register_zarr_conventions!(Proj);
+
+/// Extent of data in a CRS's coordinates,
+/// written as `[xmin, ymin, xmax, ymax]` or, in 3D, `[xmin, ymin, zmin, xmax, ymax, zmax]`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(try_from = "Vec<f64>", into = "Vec<f64>")]
+pub struct BoundingBox {
+    pub xmin: f64,
+    pub ymin: f64,
+    pub xmax: f64,
+    pub ymax: f64,
+    /// Minimum and maximum of the third (usually vertical) coordinate, for 3D boxes.
+    pub z: Option<(f64, f64)>,
+}
+
+impl BoundingBox {
+    pub fn new(xmin: f64, ymin: f64, xmax: f64, ymax: f64) -> Self {
+        Self {
+            xmin,
+            ymin,
+            xmax,
+            ymax,
+            z: None,
+        }
+    }
+
+    /// Extend the box into 3D.
+    pub fn with_z(mut self, zmin: f64, zmax: f64) -> Self {
+        self.z = Some((zmin, zmax));
+        self
+    }
+
+    pub fn width(&self) -> f64 {
+        self.xmax - self.xmin
+    }
+
+    pub fn height(&self) -> f64 {
+        self.ymax - self.ymin
+    }
+
+    /// Whether the point is inside the box, or on its edge, ignoring any third coordinate.
+    pub fn contains(&self, x: f64, y: f64) -> bool {
+        (self.xmin..=self.xmax).contains(&x) && (self.ymin..=self.ymax).contains(&y)
+    }
+
+    /// Whether the bounds are finite and the minima are no greater than the maxima.
+    pub fn is_valid(&self) -> bool {
+        let (zmin, zmax) = self.z.unwrap_or_default();
+        [self.xmin, self.ymin, self.xmax, self.ymax, zmin, zmax]
+            .iter()
+            .all(|v| v.is_finite())
+            && self.xmin <= self.xmax
+            && self.ymin <= self.ymax
+            && zmin <= zmax
+    }
+}
+
+impl From<[f64; 4]> for BoundingBox {
+    fn from([xmin, ymin, xmax, ymax]: [f64; 4]) -> Self {
+        Self::new(xmin, ymin, xmax, ymax)
+    }
+}
+
+impl From<[f64; 6]> for BoundingBox {
+    fn from([xmin, ymin, zmin, xmax, ymax, zmax]: [f64; 6]) -> Self {
+        Self::new(xmin, ymin, xmax, ymax).with_z(zmin, zmax)
+    }
+}
+
+impl TryFrom<Vec<f64>> for BoundingBox {
+    type Error = BuildError;
+
+    fn try_from(value: Vec<f64>) -> Result<Self, Self::Error> {
+        match value[..] {
+            [xmin, ymin, xmax, ymax] => Ok(Self::new(xmin, ymin, xmax, ymax)),
+            [xmin, ymin, zmin, xmax, ymax, zmax] => {
+                Ok(Self::new(xmin, ymin, xmax, ymax).with_z(zmin, zmax))
+            }
+            _ => Err(BuildError::BoundingBoxLength(value.len())),
+        }
+    }
+}
+
+impl From<BoundingBox> for Vec<f64> {
+    fn from(value: BoundingBox) -> Self {
+        match value.z {
+            None => vec![value.xmin, value.ymin, value.xmax, value.ymax],
+            Some((zmin, zmax)) => vec![value.xmin, value.ymin, zmin, value.xmax, value.ymax, zmax],
+        }
+    }
+}
+
+impl Lint for Proj {
+    fn lint(&self) -> Vec<Issue> {
+        let mut issues = Vec::default();
+        if !self.has_crs() {
+            issues.push(Issue::error("missing-crs", BuildError::NoCrs.to_string()));
+        }
+        if let Some(code) = self.code()
+            && split_code(code).is_none()
+        {
+            issues.push(Issue::error(
+                "invalid-crs-code",
+                BuildError::InvalidCode(code.into()).to_string(),
+            ));
+        }
+        if let Some(wkt2) = self.wkt2()
+            && !is_wkt2_crs(wkt2)
+        {
+            issues.push(Issue::warning(
+                "invalid-wkt2",
+                "'wkt2' does not begin with a WKT2 CRS keyword, e.g. PROJCRS",
+            ));
+        }
+        if let Some(projjson) = self.projjson()
+            && !projjson.get("type").is_some_and(Value::is_string)
+        {
+            issues.push(Issue::warning(
+                "invalid-projjson",
+                "'projjson' has no 'type', e.g. ProjectedCRS",
+            ));
+        }
+        if let Some(bbox) = self.bbox()
+            && !bbox.is_valid()
+        {
+            issues.push(Issue::error(
+                "invalid-bbox",
+                BuildError::InvalidBoundingBox(*bbox).to_string(),
+            ));
+        }
+        issues
+    }
+}
+
+fn is_wkt2_crs(wkt2: &str) -> bool {
+    let keyword = wkt2.trim_start().split(['[', '(']).next().unwrap_or("");
+    WKT2_KEYWORDS.contains(&keyword.trim_end().to_ascii_uppercase().as_str())
+}
+
+/// Why CRS metadata could not be built.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[non_exhaustive]
+pub enum BuildError {
+    #[error("none of 'code', 'wkt2', or 'projjson' is given")]
+    NoCrs,
+    #[error("CRS code '{0}' is not of the form AUTHORITY:CODE")]
+    InvalidCode(String),
+    #[error("bounding box {0:?} is not finite, or its minima exceed its maxima")]
+    InvalidBoundingBox(BoundingBox),
+    #[error("bounding box has {0} values, not 4 or 6")]
+    BoundingBoxLength(usize),
+}
+
+/// Builder for [Proj], created by [Proj::builder].
+///
+/// At least one of the code, WKT2, and PROJJSON must be set.
+#[derive(Debug, Clone, Default)]
+pub struct Builder {
+    inner: Proj,
+}
+
+impl Builder {
+    /// Authority code of the CRS, e.g. `EPSG:4326`.
+    pub fn code(mut self, code: impl Into<String>) -> Self {
+        self.inner.code = Some(code.into());
+        self
+    }
+
+    /// Definition of the CRS in WKT2.
+    pub fn wkt2(mut self, wkt2: impl Into<String>) -> Self {
+        self.inner.wkt2 = Some(wkt2.into());
+        self
+    }
+
+    /// Definition of the CRS in PROJJSON.
+    pub fn projjson(mut self, projjson: Map<String, Value>) -> Self {
+        self.inner.projjson = Some(projjson);
+        self
+    }
+
+    /// Bounding box of the data, in the CRS's coordinates.
+    pub fn bbox(mut self, bbox: BoundingBox) -> Self {
+        self.inner.bbox = Some(bbox);
+        self
+    }
+
+    /// Build the metadata, checking that the CRS is given,
+    /// any code is of the form `AUTHORITY:CODE`, and any bounding box is valid.
+    pub fn build(self) -> Result<Proj, BuildError> {
+        let proj = self.inner;
+        if !proj.has_crs() {
+            return Err(BuildError::NoCrs);
+        }
+        if let Some(code) = proj.code()
+            && split_code(code).is_none()
+        {
+            return Err(BuildError::InvalidCode(code.into()));
+        }
+        if let Some(bbox) = proj.bbox()
+            && !bbox.is_valid()
+        {
+            return Err(BuildError::InvalidBoundingBox(*bbox));
+        }
+        Ok(proj)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use zarrs_conventions::{NestedRepr, PrefixedRepr, validate::Lint};
+
+    use crate::{BoundingBox, BuildError, Proj};
+
+    #[test]
+    fn representations() {
+        let proj = Proj::builder()
+            .code("EPSG:4326")
+            .bbox(BoundingBox::new(-10.0, 40.0, 5.0, 60.0))
+            .build()
+            .unwrap();
+        let mut attributes = serde_json::Map::default();
+        proj.to_attributes_prefixed(&mut attributes).unwrap();
+        assert_eq!(
+            serde_json::Value::Object(attributes.clone()),
+            json!({"proj:code": "EPSG:4326", "proj:bbox": [-10.0, 40.0, 5.0, 60.0]})
+        );
+        assert_eq!(Proj::from_attributes_prefixed(&attributes).unwrap(), proj);
+
+        let mut attributes = serde_json::Map::default();
+        proj.to_attributes_nested(&mut attributes).unwrap();
+        assert_eq!(attributes["proj"]["code"], "EPSG:4326");
+        assert_eq!(Proj::from_attributes_nested(&attributes).unwrap(), proj);
+    }
+
+    #[test]
+    fn bbox_3d() {
+        let proj: Proj = serde_json::from_value(json!({
+            "code": "EPSG:4979",
+            "bbox": [-10.0, 40.0, -100.0, 5.0, 60.0, 3000.0],
+        }))
+        .unwrap();
+        let bbox = proj.bbox().unwrap();
+        assert_eq!(
+            *bbox,
+            BoundingBox::new(-10.0, 40.0, 5.0, 60.0).with_z(-100.0, 3000.0)
+        );
+        assert!(bbox.is_valid());
+        assert_eq!(
+            serde_json::to_value(bbox).unwrap(),
+            json!([-10.0, 40.0, -100.0, 5.0, 60.0, 3000.0])
+        );
+        assert!(
+            !BoundingBox::new(0.0, 0.0, 1.0, 1.0)
+                .with_z(1.0, 0.0)
+                .is_valid()
+        );
+
+        let err = serde_json::from_value::<BoundingBox>(json!([0.0, 0.0, 1.0])).unwrap_err();
+        assert!(err.to_string().contains("not 4 or 6"));
+    }
+
+    #[test]
+    fn codes() {
+        assert_eq!(Proj::from_epsg(4326).epsg(), Some(4326));
+        assert_eq!(
+            Proj::from_code("ESRI:54030").authority_code(),
+            Some(("ESRI", "54030"))
+        );
+        assert_eq!(Proj::from_code("ESRI:54030").epsg(), None);
+        assert_eq!(Proj::from_code("4326").authority_code(), None);
+        assert_eq!(
+            Proj::builder().code("EPSG:").build(),
+            Err(BuildError::InvalidCode("EPSG:".into()))
+        );
+        assert_eq!(Proj::builder().build(), Err(BuildError::NoCrs));
+    }
+
+    #[test]
+    fn lint() {
+        let proj: Proj = serde_json::from_value(json!({
+            "code": "4326",
+            "wkt2": "PROJCS[\"WGS 84 / UTM zone 33N\"]",
+            "projjson": {"name": "WGS 84"},
+            "bbox": [10.0, 0.0, 0.0, 10.0],
+        }))
+        .unwrap();
+        let codes: Vec<_> = proj.lint().into_iter().map(|i| i.code).collect();
+        assert_eq!(
+            codes,
+            [
+                "invalid-crs-code",
+                "invalid-wkt2",
+                "invalid-projjson",
+                "invalid-bbox"
+            ]
+        );
+        assert_eq!(Proj::default().lint()[0].code, "missing-crs");
+
+        let proj = Proj::builder()
+            .wkt2("PROJCRS[\"WGS 84 / UTM zone 33N\"]")
+            .build()
+            .unwrap();
+        assert!(proj.lint().is_empty());
+    }
+}
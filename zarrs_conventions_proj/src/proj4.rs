@@ -0,0 +1,140 @@
+//! Conversion to [proj4rs] projections.
+//!
+//! proj4rs parses neither WKT2 nor PROJJSON, and does not expose the definition of a projection,
+//! so conversion is from EPSG codes only, and only one way.
+use crate::{BoundingBox, Proj};
+
+/// Why a CRS could not be converted to a [proj4rs::Proj].
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Proj4Error {
+    #[error("the CRS is not identified by an EPSG code")]
+    NoEpsgCode,
+    #[error("no proj string is known for EPSG:{0}")]
+    UnknownEpsgCode(u32),
+    #[error(transparent)]
+    Proj4rs(#[from] proj4rs::errors::Error),
+}
+
+/// Proj string for EPSG codes which can be defined without a CRS database:
+/// WGS 84 (4326), Web Mercator (3857), and the WGS 84 UTM zones (32601-32660 and 32701-32760).
+///
+/// ```
+/// use zarrs_conventions_proj::epsg_proj_string;
+///
+/// assert_eq!(
+///     epsg_proj_string(32633).unwrap(),
+///     "+proj=utm +zone=33 +datum=WGS84 +units=m +no_defs"
+/// );
+/// assert!(epsg_proj_string(27700).is_none());
+/// ```
+pub fn epsg_proj_string(code: u32) -> Option<String> {
+    match code {
+        4326 => Some("+proj=longlat +datum=WGS84 +no_defs".into()),
+        3857 => Some(
+            "+proj=merc +a=6378137 +b=6378137 +lat_ts=0 +lon_0=0 +x_0=0 +y_0=0 +k=1 +units=m +no_defs"
+                .into(),
+        ),
+        32601..=32660 => Some(format!(
+            "+proj=utm +zone={} +datum=WGS84 +units=m +no_defs",
+            code - 32600
+        )),
+        32701..=32760 => Some(format!(
+            "+proj=utm +zone={} +south +datum=WGS84 +units=m +no_defs",
+            code - 32700
+        )),
+        _ => None,
+    }
+}
+
+impl Proj {
+    /// The CRS as a [proj4rs::Proj], from its EPSG code; see [epsg_proj_string] for supported codes.
+    pub fn to_proj4rs(&self) -> Result<proj4rs::Proj, Proj4Error> {
+        let code = self.epsg().ok_or(Proj4Error::NoEpsgCode)?;
+        let definition = epsg_proj_string(code).ok_or(Proj4Error::UnknownEpsgCode(code))?;
+        Ok(proj4rs::Proj::from_proj_string(&definition)?)
+    }
+}
+
+impl BoundingBox {
+    /// The box enclosing this box's corners transformed from one CRS to another,
+    /// keeping any third coordinate as it is.
+    ///
+    /// Geographic coordinates are in degrees.
+    /// Edges may curve under the transformation, so the result can be smaller than the true extent.
+    pub fn transform(
+        &self,
+        from: &proj4rs::Proj,
+        to: &proj4rs::Proj,
+    ) -> Result<BoundingBox, Proj4Error> {
+        let mut corners = [
+            (self.xmin, self.ymin),
+            (self.xmin, self.ymax),
+            (self.xmax, self.ymin),
+            (self.xmax, self.ymax),
+        ];
+        if from.is_latlong() {
+            for (x, y) in corners.iter_mut() {
+                *x = x.to_radians();
+                *y = y.to_radians();
+            }
+        }
+        proj4rs::transform::transform(from, to, &mut corners[..])?;
+        if to.is_latlong() {
+            for (x, y) in corners.iter_mut() {
+                *x = x.to_degrees();
+                *y = y.to_degrees();
+            }
+        }
+        let (mut xmin, mut ymin) = (f64::INFINITY, f64::INFINITY);
+        let (mut xmax, mut ymax) = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+        for (x, y) in corners {
+            xmin = xmin.min(x);
+            ymin = ymin.min(y);
+            xmax = xmax.max(x);
+            ymax = ymax.max(y);
+        }
+        Ok(BoundingBox {
+            xmin,
+            ymin,
+            xmax,
+            ymax,
+            z: self.z,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BoundingBox, Proj, Proj4Error};
+
+    #[test]
+    fn to_proj4rs() {
+        let wgs84 = Proj::from_epsg(4326).to_proj4rs().unwrap();
+        assert!(wgs84.is_latlong());
+        let utm = Proj::from_epsg(32633).to_proj4rs().unwrap();
+        assert_eq!(utm.projname(), "utm");
+        assert!(matches!(
+            Proj::from_epsg(27700).to_proj4rs(),
+            Err(Proj4Error::UnknownEpsgCode(27700))
+        ));
+        assert!(matches!(
+            Proj::from_code("ESRI:54030").to_proj4rs(),
+            Err(Proj4Error::NoEpsgCode)
+        ));
+    }
+
+    #[test]
+    fn transform_bbox() {
+        let wgs84 = Proj::from_epsg(4326).to_proj4rs().unwrap();
+        let utm = Proj::from_epsg(32633).to_proj4rs().unwrap();
+        let bbox = BoundingBox::new(14.0, 50.0, 16.0, 51.0);
+        let projected = bbox.transform(&wgs84, &utm).unwrap();
+        assert!(projected.contains(500000.0, 5600000.0));
+        assert!((projected.xmin - 428_300.0).abs() < 1000.0, "{projected:?}");
+
+        let back = projected.transform(&utm, &wgs84).unwrap();
+        assert!(back.contains(15.0, 50.5));
+        assert!((back.xmin - 14.0).abs() < 0.1, "{back:?}");
+    }
+}
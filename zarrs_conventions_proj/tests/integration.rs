@@ -0,0 +1,32 @@
+use serde_json::json;
+use zarrs_conventions::test_util::{parse_either, parse_example, parse_modified_example};
+use zarrs_conventions_proj::Proj;
+
+const UTM: &str = include_str!("examples/utm.json");
+
+zarrs_conventions::convention_test_suite! {
+    Proj,
+    repr = both,
+    example = parse_example(UTM, parse_either),
+    examples = "tests/examples",
+    schema = "spec/schema.json",
+}
+
+#[test]
+fn reject_invalid_bbox() {
+    let parsed = parse_modified_example(
+        UTM,
+        |attrs| attrs["proj:bbox"] = json!([500000.0, 4000000.0, 600000.0]),
+        parse_either::<Proj>,
+    );
+    assert!(parsed.is_err());
+}
+
+#[cfg(feature = "schema")]
+#[test]
+fn spec_up_to_date() {
+    zarrs_conventions::test_util::assert_docs_up_to_date(
+        &zarrs_conventions::docgen::ConventionDocs::either::<Proj>(),
+        concat!(env!("CARGO_MANIFEST_DIR"), "/spec"),
+    );
+}
@@ -0,0 +1,174 @@
+//! Plain `flag_values`, `flag_masks` and `flag_meanings` attributes of the [CF](https://cfconventions.org) conventions,
+//! kept alongside the formal QC payload for tools which do not know the convention.
+//!
+//! These attributes describe the array they are written on,
+//! so should be written on the array holding the flags, which may be a companion.
+use serde_json::Value;
+use zarrs_conventions::Attributes;
+
+use crate::{BuildError, Flag, QcFlags};
+
+pub const FLAG_VALUES_KEY: &str = "flag_values";
+pub const FLAG_MASKS_KEY: &str = "flag_masks";
+pub const FLAG_MEANINGS_KEY: &str = "flag_meanings";
+
+/// Why CF flag attributes could not be read.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum CfError {
+    #[error("'{0}' attribute must be a non-negative integer or a list of them")]
+    NotIntegers(&'static str),
+    #[error("'{FLAG_MEANINGS_KEY}' attribute is not a string")]
+    NotAString,
+    #[error("'{FLAG_MEANINGS_KEY}' attribute is missing")]
+    NoMeanings,
+    #[error("'{FLAG_MEANINGS_KEY}' has {meanings} words, but '{key}' has {values} values")]
+    Misaligned {
+        key: &'static str,
+        meanings: usize,
+        values: usize,
+    },
+    #[error(transparent)]
+    Flags(#[from] BuildError),
+}
+
+/// An integer attribute, which may be a scalar or a list, if present.
+fn integers(attributes: &Attributes, key: &'static str) -> Result<Option<Vec<u64>>, CfError> {
+    let not_integers = || CfError::NotIntegers(key);
+    match attributes.get(key) {
+        None => Ok(None),
+        Some(Value::Array(values)) => values
+            .iter()
+            .map(|v| v.as_u64().ok_or_else(not_integers))
+            .collect::<Result<_, _>>()
+            .map(Some),
+        Some(value) => Ok(Some(vec![value.as_u64().ok_or_else(not_integers)?])),
+    }
+}
+
+impl QcFlags {
+    /// Write the flags as plain CF attributes.
+    ///
+    /// ```
+    /// use zarrs_conventions_qc::{Flag, QcFlags};
+    ///
+    /// let qc = QcFlags::builder()
+    ///     .flag(Flag::exclusive("good", 1))
+    ///     .flag(Flag::exclusive("bad", 4))
+    ///     .build()
+    ///     .unwrap();
+    /// let mut attributes = serde_json::Map::default();
+    /// qc.write_cf_attributes(&mut attributes);
+    /// assert_eq!(attributes["flag_values"], serde_json::json!([1, 4]));
+    /// assert_eq!(attributes["flag_meanings"], "good bad");
+    /// assert_eq!(QcFlags::from_cf_attributes(&attributes), Ok(Some(qc)));
+    /// ```
+    pub fn write_cf_attributes(&self, attributes: &mut Attributes) {
+        let meanings: Vec<_> = self.flags.iter().map(Flag::meaning).collect();
+        attributes.insert(FLAG_MEANINGS_KEY.to_string(), meanings.join(" ").into());
+        for (key, get) in [
+            (FLAG_VALUES_KEY, Flag::value as fn(&Flag) -> Option<u64>),
+            (FLAG_MASKS_KEY, Flag::mask),
+        ] {
+            let values: Option<Vec<_>> = self.flags.iter().map(get).collect();
+            if let Some(values) = values {
+                attributes.insert(key.to_string(), values.into());
+            }
+        }
+    }
+
+    /// Flags from plain CF attributes, if any are present.
+    ///
+    /// The flags are checked as when building them; the companion is not set.
+    pub fn from_cf_attributes(attributes: &Attributes) -> Result<Option<Self>, CfError> {
+        let values = integers(attributes, FLAG_VALUES_KEY)?;
+        let masks = integers(attributes, FLAG_MASKS_KEY)?;
+        let meanings = match attributes.get(FLAG_MEANINGS_KEY) {
+            Some(meanings) => meanings.as_str().ok_or(CfError::NotAString)?,
+            None if values.is_none() && masks.is_none() => return Ok(None),
+            None => return Err(CfError::NoMeanings),
+        };
+        let meanings: Vec<_> = meanings.split_whitespace().collect();
+        for (key, list) in [(FLAG_VALUES_KEY, &values), (FLAG_MASKS_KEY, &masks)] {
+            if let Some(list) = list
+                && list.len() != meanings.len()
+            {
+                return Err(CfError::Misaligned {
+                    key,
+                    meanings: meanings.len(),
+                    values: list.len(),
+                });
+            }
+        }
+        let mut builder = Self::builder();
+        for (index, meaning) in meanings.into_iter().enumerate() {
+            builder = builder.flag(Flag {
+                meaning: meaning.to_string(),
+                value: values.as_ref().map(|v| v[index]),
+                mask: masks.as_ref().map(|m| m[index]),
+            });
+        }
+        Ok(Some(builder.build()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::CfError;
+    use crate::{BuildError, Flag, QcFlags};
+
+    fn read(attributes: serde_json::Value) -> Result<Option<QcFlags>, CfError> {
+        QcFlags::from_cf_attributes(attributes.as_object().unwrap())
+    }
+
+    #[test]
+    fn cf() {
+        let qc = QcFlags::builder()
+            .flag(Flag::masked("good", 3, 1))
+            .flag(Flag::masked("suspect", 3, 2))
+            .flag(Flag::masked("interpolated", 4, 4))
+            .build()
+            .unwrap();
+        let mut attributes = serde_json::Map::default();
+        qc.write_cf_attributes(&mut attributes);
+        assert_eq!(
+            serde_json::Value::Object(attributes.clone()),
+            json!({
+                "flag_meanings": "good suspect interpolated",
+                "flag_values": [1, 2, 4],
+                "flag_masks": [3, 3, 4],
+            })
+        );
+        assert_eq!(QcFlags::from_cf_attributes(&attributes), Ok(Some(qc)));
+
+        assert_eq!(
+            read(json!({"flag_masks": 1, "flag_meanings": "bad"})),
+            Ok(Some(
+                QcFlags::builder()
+                    .flag(Flag::bit("bad", 1))
+                    .build()
+                    .unwrap()
+            ))
+        );
+        assert_eq!(read(json!({"units": "1"})), Ok(None));
+        assert_eq!(
+            read(json!({"flag_values": [0, 1], "flag_meanings": "good"})),
+            Err(CfError::Misaligned {
+                key: "flag_values",
+                meanings: 1,
+                values: 2
+            })
+        );
+        assert_eq!(read(json!({"flag_values": [0]})), Err(CfError::NoMeanings));
+        assert_eq!(
+            read(json!({"flag_values": [-1], "flag_meanings": "bad"})),
+            Err(CfError::NotIntegers("flag_values"))
+        );
+        assert_eq!(
+            read(json!({"flag_values": [1, 1], "flag_meanings": "good bad"})),
+            Err(CfError::Flags(BuildError::DuplicateEncoding("bad".into())))
+        );
+    }
+}
@@ -0,0 +1,496 @@
+#![doc = include_str!("../README.md")]
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+pub use zarrs_conventions;
+use zarrs_conventions::{
+    NodePath, NodePathError, define_convention,
+    validate::{Issue, Lint},
+};
+
+pub mod cf;
+
+/// A quality-control condition, and how it is encoded in the flag values.
+///
+/// As in the CF conventions, a flag may be a value which the whole flag value must equal,
+/// a bit mask which the flag value must have any bits set in,
+/// or a mask and a value which the masked flag value must equal.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Flag {
+    /// The condition, as a single word, e.g. `sensor_failure`.
+    meaning: String,
+    /// Value which the flag value, masked if `mask` is given, must equal.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    value: Option<u64>,
+    /// Bit mask of the flag; without `value`, the flag is set when any of its bits are.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    mask: Option<u64>,
+}
+
+impl Flag {
+    /// A flag set when the flag value is exactly `value`; flags of this kind are mutually exclusive.
+    pub fn exclusive(meaning: impl Into<String>, value: u64) -> Self {
+        Self {
+            meaning: meaning.into(),
+            value: Some(value),
+            mask: None,
+        }
+    }
+
+    /// A flag set when any bit of `mask` is set; flags of this kind can be combined.
+    pub fn bit(meaning: impl Into<String>, mask: u64) -> Self {
+        Self {
+            meaning: meaning.into(),
+            value: None,
+            mask: Some(mask),
+        }
+    }
+
+    /// A flag set when the bits of `mask` in the flag value equal `value`.
+    pub fn masked(meaning: impl Into<String>, mask: u64, value: u64) -> Self {
+        Self {
+            meaning: meaning.into(),
+            value: Some(value),
+            mask: Some(mask),
+        }
+    }
+
+    /// The condition, as a single word, e.g. `sensor_failure`.
+    pub fn meaning(&self) -> &str {
+        &self.meaning
+    }
+
+    pub fn value(&self) -> Option<u64> {
+        self.value
+    }
+
+    pub fn mask(&self) -> Option<u64> {
+        self.mask
+    }
+
+    /// Whether the flag is set in a flag value.
+    pub fn is_set(&self, flags: u64) -> bool {
+        match (self.mask, self.value) {
+            (Some(mask), Some(value)) => flags & mask == value,
+            (Some(mask), None) => flags & mask != 0,
+            (None, Some(value)) => flags == value,
+            (None, None) => false,
+        }
+    }
+
+    fn style(&self) -> Option<Style> {
+        match (self.mask, self.value) {
+            (Some(_), Some(_)) => Some(Style::MaskedValues),
+            (Some(_), None) => Some(Style::Masks),
+            (None, Some(_)) => Some(Style::Values),
+            (None, None) => None,
+        }
+    }
+}
+
+/// How the flags of an array are encoded; all of its flags must use the same style.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Style {
+    /// CF `flag_values`: each flag value means exactly one condition.
+    Values,
+    /// CF `flag_masks`: each bit of a flag value is an independent condition.
+    Masks,
+    /// CF `flag_masks` and `flag_values`: groups of bits each hold one of several conditions.
+    MaskedValues,
+}
+
+/// Whether a meaning is a single word which can be written in CF `flag_meanings`.
+fn is_valid_meaning(meaning: &str) -> bool {
+    !meaning.is_empty()
+        && meaning
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "_-.+@".contains(c))
+}
+
+define_convention! {
+    /// Declares that an array holds quality-control flags, or that a companion array holds them for it,
+    /// and what each flag means.
+    #[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+    #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+    pub struct QcFlags {
+        /// Each condition and how it is encoded, with unique meanings.
+        flags: Vec<Flag>,
+        /// Path of the array holding the flags, relative to this node or absolute;
+        /// if absent, this array holds them.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        companion: Option<String>,
+    }
+    schema_url = "https://raw.githubusercontent.com/clbarnes/zarrs_conventions/refs/tags/qc-v1/zarrs_conventions_qc/spec/schema.json",
+    spec_url = "https://github.com/clbarnes/zarrs_conventions/blob/qc-v1/zarrs_conventions_qc/spec/README.md",
+    name = "qc",
+    description = "Quality-control flags held by an array or its companion, and what they mean",
+    nested = "qc",
+    register = false,
+}
+
+impl QcFlags {
+    pub fn builder() -> Builder {
+        Default::default()
+    }
+
+    pub fn flags(&self) -> &[Flag] {
+        &self.flags
+    }
+
+    pub fn companion(&self) -> Option<&str> {
+        self.companion.as_deref()
+    }
+
+    /// Path of the array holding the flags described by the node at `node`.
+    pub fn flags_array(&self, node: &NodePath) -> Result<NodePath, NodePathError> {
+        match &self.companion {
+            Some(companion) => node.resolve(companion),
+            None => Ok(node.clone()),
+        }
+    }
+
+    /// How the flags are encoded, or `None` if there are none or they are inconsistent.
+    pub fn style(&self) -> Option<Style> {
+        let mut styles = self.flags.iter().map(Flag::style);
+        let first = styles.next()??;
+        styles.all(|s| s == Some(first)).then_some(first)
+    }
+
+    pub fn get(&self, meaning: &str) -> Option<&Flag> {
+        self.flags.iter().find(|f| f.meaning == meaning)
+    }
+
+    /// Meanings of the flags set in a flag value.
+    ///
+    /// ```
+    /// use zarrs_conventions_qc::{Flag, QcFlags};
+    ///
+    /// let qc = QcFlags::builder()
+    ///     .flag(Flag::bit("low_battery", 0b01))
+    ///     .flag(Flag::bit("sensor_failure", 0b10))
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(qc.decode(0b11), ["low_battery", "sensor_failure"]);
+    /// assert!(qc.decode(0).is_empty());
+    /// ```
+    pub fn decode(&self, flags: u64) -> Vec<&str> {
+        self.flags
+            .iter()
+            .filter(|f| f.is_set(flags))
+            .map(Flag::meaning)
+            .collect()
+    }
+
+    /// Whether the flag with the given meaning is set in a flag value, or `None` if there is no such flag.
+    pub fn is_set(&self, flags: u64, meaning: &str) -> Option<bool> {
+        self.get(meaning).map(|f| f.is_set(flags))
+    }
+
+    /// The flag value with the flags of the given meanings set.
+    ///
+    /// ```
+    /// use zarrs_conventions_qc::{Flag, QcFlags};
+    ///
+    /// let qc = QcFlags::builder()
+    ///     .flag(Flag::masked("good", 0b011, 0b001))
+    ///     .flag(Flag::masked("suspect", 0b011, 0b010))
+    ///     .flag(Flag::masked("interpolated", 0b100, 0b100))
+    ///     .build()
+    ///     .unwrap();
+    /// let flags = qc.encode(["suspect", "interpolated"]).unwrap();
+    /// assert_eq!(flags, 0b110);
+    /// assert!(qc.encode(["good", "suspect"]).is_err());
+    /// ```
+    pub fn encode<'a>(
+        &self,
+        meanings: impl IntoIterator<Item = &'a str>,
+    ) -> Result<u64, EncodeError> {
+        let mut out = 0;
+        // bits fixed by the flags set so far, and their values
+        let mut fixed: Vec<(&str, u64, u64)> = Vec::default();
+        for meaning in meanings {
+            let flag = self
+                .get(meaning)
+                .ok_or_else(|| EncodeError::Unknown(meaning.to_string()))?;
+            let (mask, value) = match (flag.mask, flag.value) {
+                (Some(mask), None) => {
+                    out |= mask;
+                    continue;
+                }
+                (Some(mask), Some(value)) => (mask, value),
+                (None, Some(value)) => (u64::MAX, value),
+                (None, None) => return Err(EncodeError::NoEncoding(meaning.to_string())),
+            };
+            if let Some((other, ..)) = fixed.iter().find(|(_, m, v)| (v ^ value) & m & mask != 0) {
+                return Err(EncodeError::Conflict(
+                    other.to_string(),
+                    meaning.to_string(),
+                ));
+            }
+            fixed.push((flag.meaning(), mask, value));
+            out |= value;
+        }
+        Ok(out)
+    }
+
+    fn problems(&self) -> Vec<BuildError> {
+        let mut problems = Vec::default();
+        if self.flags.is_empty() {
+            problems.push(BuildError::NoFlags);
+        }
+        let mut meanings = BTreeSet::default();
+        let mut encodings = BTreeSet::default();
+        for flag in &self.flags {
+            let meaning = || flag.meaning.clone();
+            if !is_valid_meaning(&flag.meaning) {
+                problems.push(BuildError::InvalidMeaning(meaning()));
+            } else if !meanings.insert(flag.meaning.as_str()) {
+                problems.push(BuildError::DuplicateMeaning(meaning()));
+            }
+            match (flag.mask, flag.value) {
+                (None, None) => problems.push(BuildError::NoEncoding(meaning())),
+                (Some(0), _) => problems.push(BuildError::ZeroMask(meaning())),
+                (Some(mask), Some(value)) if value & !mask != 0 => {
+                    problems.push(BuildError::ValueOutsideMask(meaning()))
+                }
+                encoding => {
+                    if !encodings.insert(encoding) {
+                        problems.push(BuildError::DuplicateEncoding(meaning()));
+                    }
+                }
+            }
+        }
+        if !self.flags.is_empty()
+            && self.flags.iter().all(|f| f.style().is_some())
+            && self.style().is_none()
+        {
+            problems.push(BuildError::MixedStyles);
+        }
+        if let Some(companion) = &self.companion
+            && companion.starts_with('/')
+            && let Err(e) = NodePath::new(companion)
+        {
+            problems.push(BuildError::Companion(e));
+        }
+        problems
+    }
+}
+
+impl Lint for QcFlags {
+    fn lint(&self) -> Vec<Issue> {
+        self.problems()
+            .into_iter()
+            .map(|e| {
+                let code = match e {
+                    BuildError::NoFlags => "no-flags",
+                    BuildError::InvalidMeaning(_) => "invalid-meaning",
+                    BuildError::DuplicateMeaning(_) => "duplicate-meaning",
+                    BuildError::NoEncoding(_) => "no-encoding",
+                    BuildError::ZeroMask(_) => "zero-mask",
+                    BuildError::ValueOutsideMask(_) => "value-outside-mask",
+                    BuildError::DuplicateEncoding(_) => "duplicate-encoding",
+                    BuildError::MixedStyles => "mixed-styles",
+                    BuildError::Companion(_) => "invalid-companion",
+                };
+                Issue::error(code, e.to_string())
+            })
+            .collect()
+    }
+}
+
+/// Why a flag value could not be encoded.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum EncodeError {
+    #[error("no flag means '{0}'")]
+    Unknown(String),
+    #[error("flag '{0}' has neither a value nor a mask")]
+    NoEncoding(String),
+    #[error("flags '{0}' and '{1}' cannot both be set")]
+    Conflict(String, String),
+}
+
+/// Why QC flags could not be built.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum BuildError {
+    #[error("at least one flag must be given")]
+    NoFlags,
+    #[error("flag meaning '{0}' must be a single word of letters, digits, and _-.+@")]
+    InvalidMeaning(String),
+    #[error("more than one flag means '{0}'")]
+    DuplicateMeaning(String),
+    #[error("flag '{0}' has neither a value nor a mask")]
+    NoEncoding(String),
+    #[error("flag '{0}' has a mask with no bits set")]
+    ZeroMask(String),
+    #[error("flag '{0}' has a value with bits outside its mask")]
+    ValueOutsideMask(String),
+    #[error("flag '{0}' is encoded the same as an earlier flag")]
+    DuplicateEncoding(String),
+    #[error("flags must all have values, all have masks, or all have both")]
+    MixedStyles,
+    #[error("invalid companion path: {0}")]
+    Companion(#[from] NodePathError),
+}
+
+/// Builder for [QcFlags], created by [QcFlags::builder].
+#[derive(Debug, Clone, Default)]
+pub struct Builder {
+    inner: QcFlags,
+}
+
+impl Builder {
+    pub fn flag(mut self, flag: Flag) -> Self {
+        self.inner.flags.push(flag);
+        self
+    }
+
+    /// Declare that the flags are held by another array, at a path relative to this node or absolute.
+    pub fn companion(mut self, path: impl Into<String>) -> Self {
+        self.inner.companion = Some(path.into());
+        self
+    }
+
+    /// Build the flags, checking that meanings and encodings are unique and consistent.
+    pub fn build(self) -> Result<QcFlags, BuildError> {
+        match self.inner.problems().into_iter().next() {
+            Some(e) => Err(e),
+            None => Ok(self.inner),
+        }
+    }
+
+    /// Build the flags for the node at `node`, also checking that a relative companion path stays within the hierarchy.
+    pub fn build_for_node(self, node: &NodePath) -> Result<QcFlags, BuildError> {
+        self.inner.flags_array(node)?;
+        self.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use zarrs_conventions::{NodePathError, validate::Lint};
+
+    use crate::{BuildError, EncodeError, Flag, QcFlags, Style};
+
+    #[test]
+    fn build() {
+        let qc = QcFlags::builder()
+            .flag(Flag::exclusive("good", 1))
+            .flag(Flag::exclusive("suspect", 3))
+            .flag(Flag::exclusive("bad", 4))
+            .companion("../qc")
+            .build_for_node(&"/data/temperature".parse().unwrap())
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(&qc).unwrap(),
+            json!({
+                "flags": [
+                    {"meaning": "good", "value": 1},
+                    {"meaning": "suspect", "value": 3},
+                    {"meaning": "bad", "value": 4},
+                ],
+                "companion": "../qc",
+            })
+        );
+        assert_eq!(qc.style(), Some(Style::Values));
+        assert_eq!(
+            qc.flags_array(&"/data/temperature".parse().unwrap())
+                .unwrap()
+                .as_str(),
+            "/data/qc"
+        );
+        assert!(qc.lint().is_empty());
+
+        assert_eq!(QcFlags::builder().build(), Err(BuildError::NoFlags));
+        assert_eq!(
+            QcFlags::builder()
+                .flag(Flag::exclusive("good", 1))
+                .flag(Flag::bit("bad", 2))
+                .build(),
+            Err(BuildError::MixedStyles)
+        );
+        assert_eq!(
+            QcFlags::builder()
+                .flag(Flag::masked("good", 0b01, 0b10))
+                .build(),
+            Err(BuildError::ValueOutsideMask("good".into()))
+        );
+        assert_eq!(
+            QcFlags::builder()
+                .flag(Flag::exclusive("good", 1))
+                .companion("../../qc")
+                .build_for_node(&"/a".parse().unwrap()),
+            Err(BuildError::Companion(NodePathError::EscapesRoot(
+                "../../qc".into()
+            )))
+        );
+    }
+
+    #[test]
+    fn codec() {
+        let exclusive = QcFlags::builder()
+            .flag(Flag::exclusive("good", 1))
+            .flag(Flag::exclusive("bad", 4))
+            .build()
+            .unwrap();
+        assert_eq!(exclusive.decode(4), ["bad"]);
+        assert!(exclusive.decode(5).is_empty());
+        assert_eq!(exclusive.encode(["bad"]), Ok(4));
+        assert_eq!(
+            exclusive.encode(["good", "bad"]),
+            Err(EncodeError::Conflict("good".into(), "bad".into()))
+        );
+        assert_eq!(
+            exclusive.encode(["ugly"]),
+            Err(EncodeError::Unknown("ugly".into()))
+        );
+
+        let bits = QcFlags::builder()
+            .flag(Flag::bit("low_battery", 0b01))
+            .flag(Flag::bit("sensor_failure", 0b10))
+            .build()
+            .unwrap();
+        assert_eq!(bits.encode(["low_battery", "sensor_failure"]), Ok(0b11));
+        assert_eq!(bits.is_set(0b10, "low_battery"), Some(false));
+        assert_eq!(bits.is_set(0b10, "ugly"), None);
+
+        let masked = QcFlags::builder()
+            .flag(Flag::masked("good", 0b011, 0b001))
+            .flag(Flag::masked("suspect", 0b011, 0b010))
+            .flag(Flag::masked("interpolated", 0b100, 0b100))
+            .build()
+            .unwrap();
+        assert_eq!(masked.decode(0b101), ["good", "interpolated"]);
+        assert_eq!(masked.decode(0b011), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn lint() {
+        let qc: QcFlags = serde_json::from_value(json!({
+            "flags": [
+                {"meaning": "good", "value": 1},
+                {"meaning": "good", "value": 2},
+                {"meaning": "not good", "value": 3},
+                {"meaning": "other", "value": 1},
+                {"meaning": "unknown"},
+            ],
+            "companion": "/../qc",
+        }))
+        .unwrap();
+        let codes: Vec<_> = qc.lint().into_iter().map(|i| i.code).collect();
+        assert_eq!(
+            codes,
+            [
+                "duplicate-meaning",
+                "invalid-meaning",
+                "duplicate-encoding",
+                "no-encoding",
+                "invalid-companion"
+            ]
+        );
+        assert_eq!(QcFlags::default().lint()[0].code, "no-flags");
+    }
+}
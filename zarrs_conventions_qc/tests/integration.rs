@@ -0,0 +1,34 @@
+use zarrs_conventions::test_util::{parse_example, parse_modified_example, parse_nested};
+use zarrs_conventions_qc::QcFlags;
+
+const EXAMPLE: &str = include_str!("examples/bits.json");
+
+zarrs_conventions::convention_test_suite! {
+    QcFlags,
+    repr = nested,
+    example = parse_example(EXAMPLE, parse_nested),
+    examples = "tests/examples",
+    schema = "spec/schema.json",
+    registered = false,
+}
+
+#[test]
+fn reject_missing_flags() {
+    let parsed = parse_modified_example(
+        EXAMPLE,
+        |attrs| {
+            attrs["qc"].as_object_mut().unwrap().remove("flags");
+        },
+        parse_nested::<QcFlags>,
+    );
+    assert!(parsed.is_err());
+}
+
+#[cfg(feature = "schema")]
+#[test]
+fn spec_up_to_date() {
+    zarrs_conventions::test_util::assert_docs_up_to_date(
+        &zarrs_conventions::docgen::ConventionDocs::nested::<QcFlags>(),
+        concat!(env!("CARGO_MANIFEST_DIR"), "/spec"),
+    );
+}
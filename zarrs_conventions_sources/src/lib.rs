@@ -0,0 +1,438 @@
+#![doc = include_str!("../README.md")]
+use serde::{Deserialize, Serialize};
+pub use zarrs_conventions;
+use zarrs_conventions::{
+    define_convention,
+    iref::{Uri, UriBuf},
+    validate::{Issue, Lint},
+};
+pub use zarrs_conventions_checksum::Algorithm;
+use zarrs_conventions_timestamps::{Timestamp, TimestampError};
+
+pub mod report;
+mod verify;
+pub use verify::{Report, VerifyError};
+
+/// How a dataset used a source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum Role {
+    /// Data which the dataset was processed from.
+    #[default]
+    Input,
+    /// Data used alongside the inputs, e.g. a land mask or elevation model.
+    Ancillary,
+    /// Data used to calibrate the inputs, e.g. dark frames or a flat field.
+    Calibration,
+    /// Data which the dataset was registered, aligned, or compared to.
+    Reference,
+    /// A trained model applied to the inputs, e.g. the weights of a segmentation network.
+    Model,
+}
+
+impl Role {
+    /// Name of the role, as serialized.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Input => "input",
+            Self::Ancillary => "ancillary",
+            Self::Calibration => "calibration",
+            Self::Reference => "reference",
+            Self::Model => "model",
+        }
+    }
+}
+
+/// A digest of a source's bytes, as hexadecimal.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Digest {
+    /// Hash algorithm of the digest.
+    algorithm: Algorithm,
+    /// The digest, as hexadecimal.
+    value: String,
+}
+
+impl Digest {
+    /// A digest which is checked when building [Sources].
+    pub fn new(algorithm: Algorithm, value: impl Into<String>) -> Self {
+        Self {
+            algorithm,
+            value: value.into(),
+        }
+    }
+
+    /// The digest of some bytes.
+    pub fn compute(algorithm: Algorithm, bytes: &[u8]) -> Self {
+        Self::new(algorithm, algorithm.digest(bytes))
+    }
+
+    pub fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Whether the digests are of the same bytes, ignoring the case of their hexadecimal.
+    pub fn matches(&self, other: &Digest) -> bool {
+        self.algorithm == other.algorithm && self.value.eq_ignore_ascii_case(&other.value)
+    }
+}
+
+/// A file, URL, or accession which a dataset was derived from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Source {
+    /// Where the source can be found, e.g. `https://example.org/scene.tif`,
+    /// or its persistent identifier, e.g. `doi:10.5281/zenodo.1234` or `https://identifiers.org/ena.embl:PRJEB1234`.
+    #[cfg_attr(feature = "schema", schemars(with = "String", extend("format" = "uri")))]
+    uri: UriBuf,
+    /// How the dataset used the source.
+    #[serde(default)]
+    role: Role,
+    /// Digest of the source's bytes as retrieved, for checking that it is unchanged.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    checksum: Option<Digest>,
+    /// When the source was retrieved.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    retrieved: Option<Timestamp>,
+    /// Free-text description of the source.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+}
+
+impl Source {
+    /// An input to the dataset.
+    pub fn new(uri: UriBuf) -> Self {
+        Self {
+            uri,
+            role: Role::default(),
+            checksum: None,
+            retrieved: None,
+            description: None,
+        }
+    }
+
+    pub fn with_role(mut self, role: Role) -> Self {
+        self.role = role;
+        self
+    }
+
+    /// Set the digest of the source's bytes as retrieved, for checking that it is unchanged.
+    pub fn with_checksum(mut self, checksum: Digest) -> Self {
+        self.checksum = Some(checksum);
+        self
+    }
+
+    /// Set when the source was retrieved, as an RFC 3339 timestamp which is checked when building [Sources].
+    pub fn with_retrieved(mut self, timestamp: impl Into<String>) -> Self {
+        self.retrieved = Some(Timestamp::new_unchecked(timestamp));
+        self
+    }
+
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn uri(&self) -> &Uri {
+        &self.uri
+    }
+
+    pub fn role(&self) -> Role {
+        self.role
+    }
+
+    pub fn checksum(&self) -> Option<&Digest> {
+        self.checksum.as_ref()
+    }
+
+    pub fn retrieved(&self) -> Option<&Timestamp> {
+        self.retrieved.as_ref()
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Whether two entries describe the same source:
+    /// they have the same URI, and do not record different checksums of it.
+    pub fn same_source(&self, other: &Source) -> bool {
+        self.uri == other.uri
+            && match (&self.checksum, &other.checksum) {
+                (Some(a), Some(b)) => a.matches(b),
+                _ => true,
+            }
+    }
+
+    /// Fill in details which are missing here from another entry for the same source.
+    fn absorb(&mut self, other: Source) {
+        self.checksum = self.checksum.take().or(other.checksum);
+        self.retrieved = self.retrieved.take().or(other.retrieved);
+        self.description = self.description.take().or(other.description);
+    }
+
+    fn problems(&self) -> Vec<BuildError> {
+        let mut problems = Vec::default();
+        if let Some(Digest { algorithm, value }) = &self.checksum
+            && !algorithm.is_digest(value)
+        {
+            problems.push(BuildError::Digest {
+                algorithm: *algorithm,
+                value: value.clone(),
+            });
+        }
+        if let Some(Err(e)) = self.retrieved.as_ref().map(Timestamp::instant) {
+            problems.push(BuildError::Retrieved(e));
+        }
+        problems
+    }
+}
+
+define_convention! {
+    /// The upstream files, URLs, and accessions which a dataset was derived from.
+    #[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+    #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+    #[serde(transparent)]
+    pub struct Sources(Vec<Source>);
+    schema_url = "https://raw.githubusercontent.com/clbarnes/zarrs_conventions/refs/tags/sources-v1/zarrs_conventions_sources/spec/schema.json",
+    spec_url = "https://github.com/clbarnes/zarrs_conventions/blob/sources-v1/zarrs_conventions_sources/spec/README.md",
+    name = "sources",
+    description = "Upstream files, URLs, and accessions which a dataset was derived from",
+    nested = "sources",
+    register = false,
+}
+
+impl Sources {
+    pub fn builder() -> Builder {
+        Default::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Source> {
+        self.0.iter()
+    }
+
+    /// Sources with the given role.
+    pub fn with_role(&self, role: Role) -> impl Iterator<Item = &Source> {
+        self.0.iter().filter(move |s| s.role == role)
+    }
+
+    /// The first entry for the given URI.
+    pub fn get(&self, uri: &Uri) -> Option<&Source> {
+        self.0.iter().find(|s| s.uri == *uri)
+    }
+
+    /// Remove entries for [the same source](Source::same_source) as an earlier one,
+    /// filling in the earlier one's missing details from them.
+    ///
+    /// ```
+    /// use zarrs_conventions_sources::{Source, Sources};
+    ///
+    /// let uri: zarrs_conventions::iref::UriBuf = "https://example.org/scene.tif".parse().unwrap();
+    /// let mut sources: Sources = vec![
+    ///     Source::new(uri.clone()),
+    ///     Source::new(uri.clone()).with_retrieved("2024-05-01T09:00:00Z"),
+    /// ]
+    /// .into();
+    /// sources.dedup();
+    /// assert_eq!(sources.len(), 1);
+    /// assert!(sources.get(&uri).unwrap().retrieved().is_some());
+    /// ```
+    pub fn dedup(&mut self) {
+        let mut out: Vec<Source> = Vec::with_capacity(self.0.len());
+        for source in self.0.drain(..) {
+            match out.iter_mut().find(|s| s.same_source(&source)) {
+                Some(existing) => existing.absorb(source),
+                None => out.push(source),
+            }
+        }
+        self.0 = out;
+    }
+
+    /// Add the sources of another dataset, e.g. when combining the lineage of several inputs,
+    /// removing duplicates.
+    pub fn merge(&mut self, other: Sources) {
+        self.0.extend(other.0);
+        self.dedup();
+    }
+}
+
+impl From<Vec<Source>> for Sources {
+    fn from(value: Vec<Source>) -> Self {
+        Self(value)
+    }
+}
+
+impl Lint for Sources {
+    fn lint(&self) -> Vec<Issue> {
+        let mut issues = Vec::default();
+        for (index, source) in self.0.iter().enumerate() {
+            for e in source.problems() {
+                issues.push(Issue::error(e.code(), format!("source {index}: {e}")));
+            }
+            if self.0[..index].iter().any(|s| s.same_source(source)) {
+                issues.push(Issue::warning(
+                    "duplicate-source",
+                    format!("source {index}: '{}' is listed more than once", source.uri),
+                ));
+            }
+            if source.checksum.is_none() && source.uri.scheme().as_str().starts_with("http") {
+                issues.push(Issue::warning(
+                    "no-checksum",
+                    format!(
+                        "source {index}: '{}' has no checksum to detect whether it has changed",
+                        source.uri
+                    ),
+                ));
+            }
+        }
+        issues
+    }
+}
+
+/// Why sources could not be built.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum BuildError {
+    #[error("'{value}' is not a {} digest", algorithm.name())]
+    Digest { algorithm: Algorithm, value: String },
+    #[error("retrieval time: {0}")]
+    Retrieved(#[from] TimestampError),
+}
+
+impl BuildError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::Digest { .. } => "invalid-checksum",
+            Self::Retrieved(_) => "invalid-retrieved",
+        }
+    }
+}
+
+/// Builder for [Sources], created by [Sources::builder].
+#[derive(Debug, Clone, Default)]
+pub struct Builder {
+    inner: Sources,
+}
+
+impl Builder {
+    pub fn source(mut self, source: Source) -> Self {
+        self.inner.0.push(source);
+        self
+    }
+
+    /// Build the sources, removing duplicates and checking checksums and retrieval times.
+    pub fn build(mut self) -> Result<Sources, BuildError> {
+        self.inner.dedup();
+        match self.inner.0.iter().flat_map(Source::problems).next() {
+            Some(e) => Err(e),
+            None => Ok(self.inner),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use zarrs_conventions::{iref::UriBuf, validate::Lint};
+    use zarrs_conventions_timestamps::TimestampError;
+
+    use crate::{Algorithm, BuildError, Digest, Role, Source, Sources};
+
+    fn uri(s: &str) -> UriBuf {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn build() {
+        let sources = Sources::builder()
+            .source(
+                Source::new(uri("https://example.org/scene.tif"))
+                    .with_checksum(Digest::compute(Algorithm::Crc32, b"123456789"))
+                    .with_retrieved("2024-05-01T09:00:00Z"),
+            )
+            .source(
+                Source::new(uri("doi:10.5281/zenodo.1234"))
+                    .with_role(Role::Calibration)
+                    .with_description("flat field"),
+            )
+            .source(
+                Source::new(uri("https://example.org/scene.tif")).with_description("Landsat scene"),
+            )
+            .build()
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(&sources).unwrap(),
+            json!([
+                {
+                    "uri": "https://example.org/scene.tif",
+                    "role": "input",
+                    "checksum": {"algorithm": "crc32", "value": "cbf43926"},
+                    "retrieved": "2024-05-01T09:00:00Z",
+                    "description": "Landsat scene",
+                },
+                {
+                    "uri": "doi:10.5281/zenodo.1234",
+                    "role": "calibration",
+                    "description": "flat field",
+                },
+            ])
+        );
+        assert!(sources.lint().is_empty());
+        assert_eq!(sources.with_role(Role::Calibration).count(), 1);
+
+        let mut merged = sources.clone();
+        merged.merge(
+            vec![
+                Source::new(uri("https://example.org/scene.tif"))
+                    .with_checksum(Digest::new(Algorithm::Crc32, "CBF43926")),
+                Source::new(uri("https://example.org/scene.tif"))
+                    .with_checksum(Digest::new(Algorithm::Crc32, "00000000")),
+            ]
+            .into(),
+        );
+        assert_eq!(merged.len(), 3);
+
+        assert_eq!(
+            Sources::builder()
+                .source(Source::new(uri("file:///data/a.nc")).with_retrieved("yesterday"))
+                .build(),
+            Err(BuildError::Retrieved(TimestampError::Syntax(
+                "yesterday".into()
+            )))
+        );
+    }
+
+    #[test]
+    fn lint() {
+        let sources: Sources = serde_json::from_value(json!([
+            {"uri": "https://example.org/a.nc", "checksum": {"algorithm": "sha256", "value": "abc"}},
+            {"uri": "https://example.org/b.nc", "retrieved": "2024-05-01"},
+            {"uri": "https://example.org/a.nc"},
+        ]))
+        .unwrap();
+        let codes: Vec<_> = sources.lint().into_iter().map(|i| i.code).collect();
+        assert_eq!(
+            codes,
+            [
+                "invalid-checksum",
+                "invalid-retrieved",
+                "no-checksum",
+                "duplicate-source",
+                "no-checksum",
+            ]
+        );
+    }
+}
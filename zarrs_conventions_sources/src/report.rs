@@ -0,0 +1,143 @@
+//! Rendering sources into provenance reports.
+use std::fmt::Write;
+
+use crate::{Report, Role, Sources};
+
+/// Roles in the order they are reported, with their section headings.
+static SECTIONS: &[(Role, &str)] = &[
+    (Role::Input, "Inputs"),
+    (Role::Ancillary, "Ancillary data"),
+    (Role::Calibration, "Calibration data"),
+    (Role::Reference, "References"),
+    (Role::Model, "Models"),
+];
+
+/// Text safe to put in a Markdown table cell.
+fn cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+impl Sources {
+    /// A Markdown report of the sources, with a section for each role, e.g. `## Inputs`,
+    /// holding a table of the sources with that role.
+    ///
+    /// If a verification report is given, the status of each source's checksum is included.
+    ///
+    /// ```
+    /// use zarrs_conventions_sources::{Source, Sources};
+    ///
+    /// let sources = Sources::builder()
+    ///     .source(Source::new("doi:10.5281/zenodo.1234".parse().unwrap()).with_description("raw scans"))
+    ///     .build()
+    ///     .unwrap();
+    /// let report = sources.to_markdown(None);
+    /// assert!(report.contains("| <doi:10.5281/zenodo.1234> |  |  | raw scans |"));
+    /// ```
+    pub fn to_markdown(&self, verification: Option<&Report>) -> String {
+        let mut out = String::default();
+        for (role, heading) in SECTIONS {
+            let sources: Vec<_> = self.with_role(*role).collect();
+            if sources.is_empty() {
+                continue;
+            }
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            let status = if verification.is_some() {
+                " Status |"
+            } else {
+                ""
+            };
+            writeln!(out, "## {heading}\n").expect("writing to a string");
+            writeln!(
+                out,
+                "| Source | Retrieved | Checksum | Description |{status}"
+            )
+            .expect("writing to a string");
+            writeln!(
+                out,
+                "|---|---|---|---|{}",
+                if status.is_empty() { "" } else { "---|" }
+            )
+            .expect("writing to a string");
+            for source in sources {
+                let checksum = source
+                    .checksum()
+                    .map(|d| format!("{}:{}", d.algorithm().name(), d.value()))
+                    .unwrap_or_default();
+                write!(
+                    out,
+                    "| <{}> | {} | {checksum} | {} |",
+                    source.uri(),
+                    source.retrieved().map(|t| t.as_str()).unwrap_or_default(),
+                    cell(source.description().unwrap_or_default()),
+                )
+                .expect("writing to a string");
+                if let Some(report) = verification {
+                    let uri = source.uri();
+                    let status = if report.mismatched.iter().any(|u| u == uri) {
+                        "changed"
+                    } else if report.missing.iter().any(|u| u == uri) {
+                        "unavailable"
+                    } else if source.checksum().is_none() {
+                        "unchecked"
+                    } else {
+                        "verified"
+                    };
+                    write!(out, " {status} |").expect("writing to a string");
+                }
+                out.push('\n');
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zarrs_conventions::iref::UriBuf;
+
+    use crate::{Algorithm, Digest, Report, Role, Source, Sources};
+
+    fn uri(s: &str) -> UriBuf {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn markdown() {
+        let sources = Sources::builder()
+            .source(
+                Source::new(uri("https://example.org/dem.tif"))
+                    .with_role(Role::Ancillary)
+                    .with_description("elevation | 30 m"),
+            )
+            .source(
+                Source::new(uri("https://example.org/scene.tif"))
+                    .with_checksum(Digest::compute(Algorithm::Crc32, b"123456789"))
+                    .with_retrieved("2024-05-01T09:00:00Z"),
+            )
+            .build()
+            .unwrap();
+        assert_eq!(
+            sources.to_markdown(None),
+            "## Inputs\n\n\
+             | Source | Retrieved | Checksum | Description |\n\
+             |---|---|---|---|\n\
+             | <https://example.org/scene.tif> | 2024-05-01T09:00:00Z | crc32:cbf43926 |  |\n\
+             \n\
+             ## Ancillary data\n\n\
+             | Source | Retrieved | Checksum | Description |\n\
+             |---|---|---|---|\n\
+             | <https://example.org/dem.tif> |  |  | elevation \\| 30 m |\n"
+        );
+
+        let verification = Report {
+            mismatched: vec![uri("https://example.org/scene.tif")],
+            ..Default::default()
+        };
+        let report = sources.to_markdown(Some(&verification));
+        assert!(report.contains("| Description | Status |\n|---|---|---|---|---|\n"));
+        assert!(report.contains("crc32:cbf43926 |  | changed |\n"));
+        assert!(report.contains("30 m | unchecked |\n"));
+    }
+}
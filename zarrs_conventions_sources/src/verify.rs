@@ -0,0 +1,165 @@
+//! Computing and verifying the checksums of sources.
+//!
+//! Sources are read through a callback taking a source's URI and returning its bytes,
+//! or `None` if it can no longer be retrieved.
+use std::io;
+
+use zarrs_conventions::iref::{Uri, UriBuf};
+
+use crate::{Algorithm, Digest, Source, Sources};
+
+/// Why sources could not be verified.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum VerifyError {
+    #[error("could not read '{uri}': {source}")]
+    Read { uri: UriBuf, source: io::Error },
+}
+
+fn read_source(
+    read: &mut impl FnMut(&Uri) -> io::Result<Option<Vec<u8>>>,
+    source: &Source,
+) -> Result<Option<Vec<u8>>, VerifyError> {
+    read(&source.uri).map_err(|e| VerifyError::Read {
+        uri: source.uri.clone(),
+        source: e,
+    })
+}
+
+/// The outcome of verifying sources against their recorded checksums.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Report {
+    /// Sources whose digest differs from the one recorded.
+    pub mismatched: Vec<UriBuf>,
+    /// Sources with a recorded checksum which could not be retrieved.
+    pub missing: Vec<UriBuf>,
+    /// Sources with no recorded checksum, which were not read.
+    pub unchecked: Vec<UriBuf>,
+}
+
+impl Report {
+    /// Whether every source with a checksum matches it.
+    pub fn is_ok(&self) -> bool {
+        self.mismatched.is_empty() && self.missing.is_empty()
+    }
+}
+
+impl Sources {
+    /// Verify each source which has a checksum against it.
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use zarrs_conventions_sources::{Algorithm, Digest, Source, Sources};
+    ///
+    /// let uri: zarrs_conventions::iref::UriBuf = "https://example.org/a.csv".parse().unwrap();
+    /// let store = HashMap::from([(uri.clone(), b"x,y\n1,2\n".to_vec())]);
+    /// let sources = Sources::builder()
+    ///     .source(Source::new(uri).with_checksum(Digest::compute(Algorithm::Sha256, b"x,y\n1,2\n")))
+    ///     .build()
+    ///     .unwrap();
+    /// let report = sources.verify(|uri| Ok(store.get(uri).cloned())).unwrap();
+    /// assert!(report.is_ok());
+    /// ```
+    pub fn verify(
+        &self,
+        mut read: impl FnMut(&Uri) -> io::Result<Option<Vec<u8>>>,
+    ) -> Result<Report, VerifyError> {
+        let mut report = Report::default();
+        for source in &self.0 {
+            let Some(expected) = &source.checksum else {
+                report.unchecked.push(source.uri.clone());
+                continue;
+            };
+            match read_source(&mut read, source)? {
+                None => report.missing.push(source.uri.clone()),
+                Some(bytes) => {
+                    if !Digest::compute(expected.algorithm, &bytes).matches(expected) {
+                        report.mismatched.push(source.uri.clone());
+                    }
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    /// Compute and record a checksum for each source which has none and can be retrieved,
+    /// returning the URIs of those which could not.
+    pub fn record_checksums(
+        &mut self,
+        algorithm: Algorithm,
+        mut read: impl FnMut(&Uri) -> io::Result<Option<Vec<u8>>>,
+    ) -> Result<Vec<UriBuf>, VerifyError> {
+        let mut missing = Vec::default();
+        for source in self.0.iter_mut().filter(|s| s.checksum.is_none()) {
+            match read_source(&mut read, source)? {
+                Some(bytes) => source.checksum = Some(Digest::compute(algorithm, &bytes)),
+                None => missing.push(source.uri.clone()),
+            }
+        }
+        Ok(missing)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, io};
+
+    use zarrs_conventions::iref::UriBuf;
+
+    use super::VerifyError;
+    use crate::{Algorithm, Digest, Report, Source, Sources};
+
+    fn uri(s: &str) -> UriBuf {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn verify() {
+        let store = HashMap::from([
+            (uri("https://example.org/a"), b"a".to_vec()),
+            (uri("https://example.org/b"), b"changed".to_vec()),
+            (uri("https://example.org/d"), b"d".to_vec()),
+        ]);
+        let read = |uri: &zarrs_conventions::iref::Uri| Ok(store.get(uri).cloned());
+        let mut sources = Sources::builder()
+            .source(
+                Source::new(uri("https://example.org/a"))
+                    .with_checksum(Digest::compute(Algorithm::Crc32, b"a")),
+            )
+            .source(
+                Source::new(uri("https://example.org/b"))
+                    .with_checksum(Digest::compute(Algorithm::Crc32, b"b")),
+            )
+            .source(
+                Source::new(uri("https://example.org/c"))
+                    .with_checksum(Digest::compute(Algorithm::Crc32, b"c")),
+            )
+            .source(Source::new(uri("https://example.org/d")))
+            .source(Source::new(uri("https://example.org/e")))
+            .build()
+            .unwrap();
+        assert_eq!(
+            sources.verify(read).unwrap(),
+            Report {
+                mismatched: vec![uri("https://example.org/b")],
+                missing: vec![uri("https://example.org/c")],
+                unchecked: vec![uri("https://example.org/d"), uri("https://example.org/e")],
+            }
+        );
+
+        assert_eq!(
+            sources.record_checksums(Algorithm::Sha256, read).unwrap(),
+            [uri("https://example.org/e")]
+        );
+        let d = sources.get(&uri("https://example.org/d")).unwrap();
+        assert_eq!(d.checksum().unwrap().algorithm(), Algorithm::Sha256);
+        assert!(sources.verify(read).unwrap().unchecked.len() == 1);
+
+        let err = sources
+            .verify(|_| Err(io::Error::other("offline")))
+            .unwrap_err();
+        assert!(
+            matches!(err, VerifyError::Read { uri, .. } if uri.as_str() == "https://example.org/a")
+        );
+    }
+}
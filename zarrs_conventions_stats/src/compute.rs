@@ -0,0 +1,262 @@
+//! Computing statistics from an array's decoded values, and attaching them to its attributes.
+//!
+//! Values are read a region at a time, e.g. a chunk, through a callback
+//! returning the decoded elements of the region, including any fill values.
+use std::io;
+
+use zarrs_conventions::{Attributes, AttributesBuilder};
+
+use crate::{Region, RegionSummary, Stats, Summary, Timestamp};
+
+/// Why statistics could not be computed and attached.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum ComputeError {
+    #[error("could not read region {region}: {source}")]
+    Read { region: Region, source: io::Error },
+    #[error("could not write stats metadata: {0}")]
+    Attributes(#[from] serde_json::Error),
+}
+
+/// Accumulates statistics of values in a single pass,
+/// with a numerically stable mean and variance which can be combined across regions.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Accumulator {
+    count: u64,
+    nan_count: u64,
+    inf_count: u64,
+    min: f64,
+    max: f64,
+    mean: f64,
+    /// Sum of squared differences from the mean.
+    m2: f64,
+}
+
+impl Accumulator {
+    pub fn push(&mut self, value: f64) {
+        if value.is_nan() {
+            self.nan_count += 1;
+        } else if value.is_infinite() {
+            self.inf_count += 1;
+        } else if self.count == 0 {
+            *self = Self {
+                count: 1,
+                min: value,
+                max: value,
+                mean: value,
+                m2: 0.0,
+                ..*self
+            };
+        } else {
+            self.count += 1;
+            self.min = self.min.min(value);
+            self.max = self.max.max(value);
+            let delta = value - self.mean;
+            self.mean += delta / self.count as f64;
+            self.m2 += delta * (value - self.mean);
+        }
+    }
+
+    /// Push decoded values, e.g. of a retrieved chunk.
+    pub fn extend<T: Copy + Into<f64>>(&mut self, values: &[T]) {
+        for value in values {
+            self.push((*value).into());
+        }
+    }
+
+    /// Combine with the statistics of other values.
+    pub fn merge(&mut self, other: &Accumulator) {
+        if other.count == 0 {
+            self.nan_count += other.nan_count;
+            self.inf_count += other.inf_count;
+            return;
+        }
+        if self.count == 0 {
+            *self = Self {
+                nan_count: self.nan_count + other.nan_count,
+                inf_count: self.inf_count + other.inf_count,
+                ..*other
+            };
+            return;
+        }
+        let count = self.count + other.count;
+        let delta = other.mean - self.mean;
+        self.mean += delta * other.count as f64 / count as f64;
+        self.m2 +=
+            other.m2 + delta * delta * (self.count as f64 * other.count as f64) / count as f64;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.count = count;
+        self.nan_count += other.nan_count;
+        self.inf_count += other.inf_count;
+    }
+
+    pub fn summary(&self) -> Summary {
+        let finite = |v: f64| (self.count > 0).then_some(v);
+        Summary {
+            count: self.count,
+            nan_count: self.nan_count,
+            inf_count: self.inf_count,
+            min: finite(self.min),
+            max: finite(self.max),
+            mean: finite(self.mean),
+            std: finite((self.m2 / self.count as f64).sqrt()),
+        }
+    }
+}
+
+impl Stats {
+    /// Statistics of all of an array's decoded values, computed now.
+    ///
+    /// ```
+    /// use zarrs_conventions_stats::Stats;
+    ///
+    /// let stats = Stats::compute(&[1u8, 2, 3, 4]);
+    /// assert_eq!(stats.summary().mean, Some(2.5));
+    /// assert!(stats.computed_at().is_some());
+    /// ```
+    pub fn compute<T: Copy + Into<f64>>(values: &[T]) -> Self {
+        let mut accumulator = Accumulator::default();
+        accumulator.extend(values);
+        Self {
+            summary: accumulator.summary(),
+            regions: Vec::default(),
+            computed_at: Some(Timestamp::now()),
+        }
+    }
+
+    /// Statistics of an array read a region at a time, e.g. each chunk, and of each region, computed now.
+    ///
+    /// The regions should cover the array without overlapping;
+    /// use [Stats::without_regions] to keep only the statistics of the whole array.
+    ///
+    /// ```
+    /// use zarrs_conventions_stats::{Region, Stats};
+    ///
+    /// let chunks = [vec![1.0f32, 2.0], vec![3.0, f32::NAN]];
+    /// let regions = [Region::chunk(&[0], &[2]), Region::chunk(&[1], &[2])];
+    /// let stats = Stats::compute_regions(regions, |region| {
+    ///     Ok(chunks[region.origin[0] as usize / 2].clone())
+    /// })
+    /// .unwrap();
+    /// assert_eq!(stats.summary().max, Some(3.0));
+    /// assert_eq!(stats.summary().nan_count, 1);
+    /// assert_eq!(stats.regions()[1].summary.count, 1);
+    /// ```
+    pub fn compute_regions<T: Copy + Into<f64>>(
+        regions: impl IntoIterator<Item = Region>,
+        mut read: impl FnMut(&Region) -> io::Result<Vec<T>>,
+    ) -> Result<Self, ComputeError> {
+        let mut total = Accumulator::default();
+        let mut summaries = Vec::default();
+        for region in regions {
+            let values = match read(&region) {
+                Ok(values) => values,
+                Err(source) => return Err(ComputeError::Read { region, source }),
+            };
+            let mut accumulator = Accumulator::default();
+            accumulator.extend(&values);
+            total.merge(&accumulator);
+            summaries.push(RegionSummary {
+                region,
+                summary: accumulator.summary(),
+            });
+        }
+        Ok(Self {
+            summary: total.summary(),
+            regions: summaries,
+            computed_at: Some(Timestamp::now()),
+        })
+    }
+
+    /// Write the statistics into an array's attributes, declaring the convention if it is not already.
+    pub fn attach(&self, attributes: &mut Attributes) -> serde_json::Result<()> {
+        let mut builder = AttributesBuilder::from_attributes(std::mem::take(attributes));
+        builder.add_nested(self)?;
+        if let serde_json::Value::Object(built) = builder.build()? {
+            *attributes = built;
+        }
+        Ok(())
+    }
+
+    /// Compute statistics as with [Stats::compute_regions], and [attach](Stats::attach) them to the attributes.
+    pub fn compute_and_attach<T: Copy + Into<f64>>(
+        attributes: &mut Attributes,
+        regions: impl IntoIterator<Item = Region>,
+        read: impl FnMut(&Region) -> io::Result<Vec<T>>,
+    ) -> Result<Self, ComputeError> {
+        let stats = Self::compute_regions(regions, read)?;
+        stats.attach(attributes)?;
+        Ok(stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use zarrs_conventions::{NestedRepr, ZarrConventionImpl, ZarrConventions};
+
+    use super::{Accumulator, ComputeError};
+    use crate::{Region, Stats};
+
+    #[test]
+    fn accumulate() {
+        let values = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        let mut whole = Accumulator::default();
+        whole.extend(&values);
+        let summary = whole.summary();
+        assert_eq!(summary.count, 8);
+        assert_eq!(summary.mean, Some(5.0));
+        assert_eq!(summary.std, Some(2.0));
+        assert_eq!((summary.min, summary.max), (Some(2.0), Some(9.0)));
+
+        let mut merged = Accumulator::default();
+        for part in [&values[..3], &[][..], &values[3..]] {
+            let mut a = Accumulator::default();
+            a.extend(part);
+            merged.merge(&a);
+        }
+        let merged = merged.summary();
+        assert_eq!(merged.count, 8);
+        assert!((merged.mean.unwrap() - 5.0).abs() < 1e-12);
+        assert!((merged.std.unwrap() - 2.0).abs() < 1e-12);
+
+        let mut special = Accumulator::default();
+        special.extend(&[f64::NAN, f64::INFINITY, f64::NEG_INFINITY]);
+        let special = special.summary();
+        assert_eq!(
+            (special.count, special.nan_count, special.inf_count),
+            (0, 1, 2)
+        );
+        assert_eq!(special.min, None);
+    }
+
+    #[test]
+    fn compute_and_attach() {
+        let mut attributes = serde_json::json!({"zarr_conventions": [], "other": 1})
+            .as_object()
+            .cloned()
+            .unwrap();
+        let regions = [
+            Region::chunk(&[0, 0], &[1, 2]),
+            Region::chunk(&[1, 0], &[1, 2]),
+        ];
+        let stats = Stats::compute_and_attach(&mut attributes, regions, |r| {
+            Ok(vec![r.origin[0] as u16; 2])
+        })
+        .unwrap();
+        assert_eq!(stats.summary().mean, Some(0.5));
+        assert_eq!(Stats::from_attributes_nested(&attributes).unwrap(), stats);
+        assert!(
+            ZarrConventions::from_attributes(&attributes)
+                .is_ok_and(|c| c.contains(Stats::DEFINITION.uuid))
+        );
+        assert_eq!(attributes["other"], 1);
+
+        let failed = Stats::compute_regions([Region::chunk(&[0], &[1])], |_| {
+            Err::<Vec<u8>, _>(io::Error::other("gone"))
+        });
+        assert!(matches!(failed, Err(ComputeError::Read { .. })));
+    }
+}
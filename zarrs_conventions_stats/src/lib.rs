@@ -0,0 +1,414 @@
+#![doc = include_str!("../README.md")]
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+pub use zarrs_conventions;
+use zarrs_conventions::{
+    Attributes, NestedRepr, define_convention,
+    validate::{Issue, Lint},
+};
+use zarrs_conventions_timestamps::Timestamps;
+pub use zarrs_conventions_timestamps::{Timestamp, TimestampError};
+
+mod compute;
+pub use compute::{Accumulator, ComputeError};
+
+fn is_zero(n: &u64) -> bool {
+    *n == 0
+}
+
+/// Statistics of a set of values.
+///
+/// The minimum, maximum, mean, and (population) standard deviation are of the finite values,
+/// and are absent if there are none.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Summary {
+    /// Number of finite values.
+    pub count: u64,
+    /// Number of NaN values.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub nan_count: u64,
+    /// Number of infinite values, of either sign.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub inf_count: u64,
+    /// Smallest finite value.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+    /// Largest finite value.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+    /// Mean of the finite values.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mean: Option<f64>,
+    /// Population standard deviation of the finite values.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub std: Option<f64>,
+}
+
+impl Summary {
+    /// Why the statistics cannot describe any set of values, if they cannot.
+    fn problem(&self) -> Option<&'static str> {
+        let stats = [self.min, self.max, self.mean, self.std];
+        if self.count == 0 {
+            return stats
+                .iter()
+                .any(Option::is_some)
+                .then_some("statistics are given for no finite values");
+        }
+        if stats.iter().flatten().any(|v| !v.is_finite()) {
+            return Some("statistics of finite values must be finite");
+        }
+        if let (Some(min), Some(max)) = (self.min, self.max) {
+            if min > max {
+                return Some("minimum is greater than maximum");
+            }
+            if self.mean.is_some_and(|mean| mean < min || mean > max) {
+                return Some("mean is outside the range");
+            }
+        }
+        if self.std.is_some_and(|std| std < 0.0) {
+            return Some("standard deviation is negative");
+        }
+        None
+    }
+}
+
+/// A hyperrectangular region of an array, e.g. a chunk, in elements.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Region {
+    /// Index of the region's first element along each dimension.
+    pub origin: Vec<u64>,
+    /// Number of elements in the region along each dimension.
+    pub shape: Vec<u64>,
+}
+
+impl Region {
+    pub fn new(origin: Vec<u64>, shape: Vec<u64>) -> Self {
+        Self { origin, shape }
+    }
+
+    /// The region covered by the chunk at the given grid indices of a regular chunk grid.
+    pub fn chunk(indices: &[u64], chunk_shape: &[u64]) -> Self {
+        Self {
+            origin: indices
+                .iter()
+                .zip(chunk_shape)
+                .map(|(i, s)| i * s)
+                .collect(),
+            shape: chunk_shape.to_vec(),
+        }
+    }
+
+    pub fn rank(&self) -> usize {
+        self.origin.len()
+    }
+}
+
+impl fmt::Display for Region {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} of shape {:?}", self.origin, self.shape)
+    }
+}
+
+/// Statistics of the values in a region of an array.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RegionSummary {
+    /// The region which the statistics describe.
+    pub region: Region,
+    #[serde(flatten)]
+    pub summary: Summary,
+}
+
+define_convention! {
+    /// Precomputed statistics of an array's values, e.g. for a viewer to set contrast limits without scanning the data,
+    /// and optionally of regions of it.
+    #[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+    #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+    pub struct Stats {
+        #[serde(flatten)]
+        summary: Summary,
+        /// Statistics of regions of the array, e.g. of chunks.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        regions: Vec<RegionSummary>,
+        /// When the statistics were computed, to compare with when the data were last modified.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        computed_at: Option<Timestamp>,
+    }
+    schema_url = "https://raw.githubusercontent.com/clbarnes/zarrs_conventions/refs/tags/stats-v1/zarrs_conventions_stats/spec/schema.json",
+    spec_url = "https://github.com/clbarnes/zarrs_conventions/blob/stats-v1/zarrs_conventions_stats/spec/README.md",
+    name = "stats",
+    description = "Precomputed summary statistics of an array's values",
+    nested = "stats",
+    register = false,
+}
+
+impl Stats {
+    pub fn builder(summary: Summary) -> Builder {
+        Builder {
+            inner: Self {
+                summary,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Statistics of the whole array.
+    pub fn summary(&self) -> &Summary {
+        &self.summary
+    }
+
+    pub fn regions(&self) -> &[RegionSummary] {
+        &self.regions
+    }
+
+    pub fn computed_at(&self) -> Option<&Timestamp> {
+        self.computed_at.as_ref()
+    }
+
+    /// The statistics without those of regions.
+    pub fn without_regions(mut self) -> Self {
+        self.regions.clear();
+        self
+    }
+
+    /// Whether the data were modified after the statistics were computed,
+    /// or `None` if either time is unknown or invalid.
+    ///
+    /// ```
+    /// use zarrs_conventions_stats::{Stats, Summary};
+    ///
+    /// let stats = Stats::builder(Summary::default())
+    ///     .computed_at("2024-03-01T12:00:00Z")
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(stats.is_stale(&"2024-03-02T00:00:00Z".parse().unwrap()), Some(true));
+    /// assert_eq!(stats.is_stale(&"2024-03-01T11:00:00Z".parse().unwrap()), Some(false));
+    /// ```
+    pub fn is_stale(&self, modified: &Timestamp) -> Option<bool> {
+        let computed = self.computed_at.as_ref()?.instant().ok()?;
+        Some(modified.instant().ok()? > computed)
+    }
+
+    /// Whether the statistics are older than the data, according to the modification time
+    /// (or, failing that, the creation time) of the timestamps convention in the same attributes.
+    ///
+    /// `None` if no time is known for either.
+    pub fn is_stale_for(&self, attributes: &Attributes) -> Option<bool> {
+        if !attributes.contains_key(Timestamps::KEY) {
+            return None;
+        }
+        let timestamps = Timestamps::from_attributes_nested(attributes).ok()?;
+        self.is_stale(timestamps.modified().or(timestamps.created())?)
+    }
+
+    fn problems(&self) -> Vec<BuildError> {
+        let mut problems = Vec::default();
+        if let Some(problem) = self.summary.problem() {
+            problems.push(BuildError::InconsistentSummary {
+                region: None,
+                problem,
+            });
+        }
+        let rank = self.regions.first().map(|r| r.region.rank());
+        for RegionSummary { region, summary } in &self.regions {
+            if region.shape.len() != region.rank() || Some(region.rank()) != rank {
+                problems.push(BuildError::MismatchedRegionRank(region.clone()));
+            } else if region.shape.contains(&0) {
+                problems.push(BuildError::EmptyRegion(region.clone()));
+            }
+            if let Some(problem) = summary.problem() {
+                problems.push(BuildError::InconsistentSummary {
+                    region: Some(region.clone()),
+                    problem,
+                });
+            }
+        }
+        if let Some(Err(e)) = self.computed_at.as_ref().map(Timestamp::instant) {
+            problems.push(BuildError::ComputedAt(e));
+        }
+        problems
+    }
+}
+
+impl Lint for Stats {
+    fn lint(&self) -> Vec<Issue> {
+        let mut issues: Vec<_> = self
+            .problems()
+            .into_iter()
+            .map(|e| {
+                let code = match e {
+                    BuildError::InconsistentSummary { .. } => "inconsistent-summary",
+                    BuildError::MismatchedRegionRank(_) => "mismatched-region-rank",
+                    BuildError::EmptyRegion(_) => "empty-region",
+                    BuildError::ComputedAt(_) => "invalid-computed-at",
+                };
+                Issue::error(code, e.to_string())
+            })
+            .collect();
+        if self.computed_at.is_none() {
+            issues.push(Issue::warning(
+                "no-computed-at",
+                "without a computation time, stale statistics cannot be detected",
+            ));
+        }
+        issues
+    }
+}
+
+/// Why statistics could not be built.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum BuildError {
+    #[error("{}{problem}", region.as_ref().map(|r| format!("region {r}: ")).unwrap_or_default())]
+    InconsistentSummary {
+        region: Option<Region>,
+        problem: &'static str,
+    },
+    #[error("region {0} does not have the same rank as the others")]
+    MismatchedRegionRank(Region),
+    #[error("region {0} is empty")]
+    EmptyRegion(Region),
+    #[error("invalid computation time: {0}")]
+    ComputedAt(#[from] TimestampError),
+}
+
+/// Builder for [Stats], created by [Stats::builder].
+#[derive(Debug, Clone)]
+pub struct Builder {
+    inner: Stats,
+}
+
+impl Builder {
+    pub fn region(mut self, region: Region, summary: Summary) -> Self {
+        self.inner.regions.push(RegionSummary { region, summary });
+        self
+    }
+
+    /// Set when the statistics were computed, as an RFC 3339 timestamp; checked when building.
+    pub fn computed_at(mut self, timestamp: impl Into<String>) -> Self {
+        self.inner.computed_at = Some(Timestamp::new_unchecked(timestamp));
+        self
+    }
+
+    /// Record that the statistics were computed now.
+    pub fn computed_now(mut self) -> Self {
+        self.inner.computed_at = Some(Timestamp::now());
+        self
+    }
+
+    /// Build the statistics, checking that they are consistent, that regions share a rank,
+    /// and that any computation time is valid.
+    pub fn build(self) -> Result<Stats, BuildError> {
+        match self.inner.problems().into_iter().next() {
+            Some(e) => Err(e),
+            None => Ok(self.inner),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use zarrs_conventions::validate::Lint;
+
+    use crate::{BuildError, Region, Stats, Summary};
+
+    fn summary(min: f64, max: f64) -> Summary {
+        Summary {
+            count: 4,
+            min: Some(min),
+            max: Some(max),
+            mean: Some((min + max) / 2.0),
+            std: Some(1.0),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn build() {
+        let stats = Stats::builder(summary(0.0, 10.0))
+            .region(Region::chunk(&[0, 1], &[2, 2]), summary(0.0, 4.0))
+            .computed_at("2024-03-01T12:00:00Z")
+            .build()
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(&stats).unwrap(),
+            json!({
+                "count": 4, "min": 0.0, "max": 10.0, "mean": 5.0, "std": 1.0,
+                "regions": [{
+                    "region": {"origin": [0, 2], "shape": [2, 2]},
+                    "count": 4, "min": 0.0, "max": 4.0, "mean": 2.0, "std": 1.0,
+                }],
+                "computed_at": "2024-03-01T12:00:00Z",
+            })
+        );
+        assert!(stats.lint().is_empty());
+
+        assert_eq!(
+            Stats::builder(summary(10.0, 0.0)).build(),
+            Err(BuildError::InconsistentSummary {
+                region: None,
+                problem: "minimum is greater than maximum"
+            })
+        );
+        assert!(matches!(
+            Stats::builder(Summary::default())
+                .computed_at("yesterday")
+                .build(),
+            Err(BuildError::ComputedAt(_))
+        ));
+    }
+
+    #[test]
+    fn staleness() {
+        let stats = Stats::builder(Summary::default())
+            .computed_at("2024-03-01T12:00:00+01:00")
+            .build()
+            .unwrap();
+        let attributes = |value: serde_json::Value| value.as_object().cloned().unwrap();
+        assert_eq!(
+            stats.is_stale_for(&attributes(
+                json!({"timestamps": {"modified": "2024-03-01T11:30:00Z"}})
+            )),
+            Some(true)
+        );
+        assert_eq!(
+            stats.is_stale_for(&attributes(
+                json!({"timestamps": {"created": "2024-03-01T10:30:00Z"}})
+            )),
+            Some(false)
+        );
+        assert_eq!(stats.is_stale_for(&attributes(json!({}))), None);
+        assert_eq!(
+            Stats::default().is_stale(&"2024-03-01T12:00:00Z".parse().unwrap()),
+            None
+        );
+    }
+
+    #[test]
+    fn lint() {
+        let stats: Stats = serde_json::from_value(json!({
+            "count": 0,
+            "min": 1.0,
+            "regions": [
+                {"region": {"origin": [0, 0], "shape": [2, 2]}, "count": 0},
+                {"region": {"origin": [0], "shape": [2]}, "count": 0},
+                {"region": {"origin": [0, 2], "shape": [0, 2]}, "count": 0},
+            ],
+        }))
+        .unwrap();
+        let codes: Vec<_> = stats.lint().into_iter().map(|i| i.code).collect();
+        assert_eq!(
+            codes,
+            [
+                "inconsistent-summary",
+                "mismatched-region-rank",
+                "empty-region",
+                "no-computed-at"
+            ]
+        );
+    }
+}
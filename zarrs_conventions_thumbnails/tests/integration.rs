@@ -1,27 +1,19 @@
-use rstest::rstest;
-use zarrs_conventions::{
-    DEFAULT_ZARR_CONVENTION_REGISTRY, NestedRepr, ZarrConventionImpl, ZarrConventions, ZarrMetadata,
-};
-use zarrs_conventions_thumbnails::Thumbnails;
+use zarrs_conventions_thumbnails::{Thumbnail, ThumbnailLocation, Thumbnails};
 
-#[test]
-fn is_registered() {
-    assert!(DEFAULT_ZARR_CONVENTION_REGISTRY.contains(&Thumbnails::DEFINITION.id_uuid()));
+fn example() -> Thumbnails {
+    let thumb = Thumbnail::try_new(
+        96,
+        96,
+        "image/jpeg",
+        ThumbnailLocation::new_path("thumbnails/thumb96.jpeg"),
+    )
+    .unwrap();
+    vec![thumb].into()
 }
 
-#[rstest]
-fn test_examples(
-    #[files("spec/examples/*.json")]
-    #[mode = bytes]
-    contents: &[u8],
-) {
-    let attrs = serde_json::from_slice::<ZarrMetadata>(contents)
-        .expect("should be valid metadata")
-        .attributes;
-
-    let conventions =
-        ZarrConventions::from_attributes(&attrs).expect("should all be valid conventions");
-    assert!(conventions.contains(&Thumbnails::DEFINITION.id_uuid()));
-
-    let _ = Thumbnails::from_attributes_nested(&attrs).expect("should be present");
+zarrs_conventions::convention_test_suite! {
+    Thumbnails,
+    repr = nested,
+    example = example(),
+    optional_spec = true,
 }
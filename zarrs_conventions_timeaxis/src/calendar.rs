@@ -0,0 +1,407 @@
+//! Calendars of time coordinates, as used by climate models, and dates within them.
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+use zarrs_conventions_timestamps::{civil_from_days, days_from_civil};
+
+use crate::TimeError;
+
+const NANOS_PER_DAY: i128 = 86_400_000_000_000;
+
+/// Days before each month of a 365-day year.
+const CUMULATIVE_DAYS: [i64; 12] = [0, 31, 59, 90, 120, 151, 181, 212, 243, 273, 304, 334];
+/// Days before each month of a 366-day year.
+const CUMULATIVE_LEAP_DAYS: [i64; 12] = [0, 31, 60, 91, 121, 152, 182, 213, 244, 274, 305, 335];
+
+/// A calendar of the [CF conventions](https://cfconventions.org/cf-conventions/cf-conventions.html#calendar).
+///
+/// Julian dates are not supported,
+/// so neither is the `julian` calendar nor dates before 1582-10-15 in the `standard` calendar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum Calendar {
+    /// The Gregorian calendar, from 1582-10-15; also called `gregorian`.
+    #[default]
+    #[serde(alias = "gregorian")]
+    Standard,
+    /// The Gregorian calendar, extended to dates before its introduction.
+    ProlepticGregorian,
+    /// Every year has 365 days; also called `365_day`.
+    #[serde(alias = "365_day")]
+    Noleap,
+    /// Every year has 366 days; also called `366_day`.
+    #[serde(alias = "366_day")]
+    AllLeap,
+    /// Every year has 12 months of 30 days.
+    #[serde(rename = "360_day")]
+    Day360,
+}
+
+fn is_gregorian_leap_year(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+/// Date of a day of a year of fixed length, given the days before each month.
+fn from_day_of_year(year: i64, day_of_year: i64, cumulative: &[i64; 12]) -> (i64, u32, u32) {
+    let month = cumulative
+        .iter()
+        .rposition(|&c| c <= day_of_year)
+        .unwrap_or(0);
+    (
+        year,
+        month as u32 + 1,
+        (day_of_year - cumulative[month]) as u32 + 1,
+    )
+}
+
+impl Calendar {
+    /// Name of the calendar in the CF conventions.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Standard => "standard",
+            Self::ProlepticGregorian => "proleptic_gregorian",
+            Self::Noleap => "noleap",
+            Self::AllLeap => "all_leap",
+            Self::Day360 => "360_day",
+        }
+    }
+
+    /// Whether dates in this calendar are dates in the (proleptic) Gregorian calendar.
+    pub fn is_gregorian(&self) -> bool {
+        matches!(self, Self::Standard | Self::ProlepticGregorian)
+    }
+
+    pub fn days_in_month(&self, year: i64, month: u32) -> u32 {
+        match (self, month) {
+            (Self::Day360, _) => 30,
+            (Self::AllLeap, 2) => 29,
+            (Self::Noleap, 2) => 28,
+            (Self::Standard | Self::ProlepticGregorian, 2) if is_gregorian_leap_year(year) => 29,
+            (_, 2) => 28,
+            (_, 4 | 6 | 9 | 11) => 30,
+            _ => 31,
+        }
+    }
+
+    /// Check that the date exists in this calendar.
+    pub fn check(&self, date: &CalendarDate) -> Result<(), TimeError> {
+        if !(1..=12).contains(&date.month)
+            || date.day < 1
+            || date.day > self.days_in_month(date.year, date.month)
+        {
+            return Err(TimeError::InvalidDate {
+                date: date.to_string(),
+                calendar: *self,
+            });
+        }
+        if *self == Self::Standard && (date.year, date.month, date.day) < (1582, 10, 15) {
+            return Err(TimeError::BeforeGregorian(date.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Days since the calendar's origin, which is 1970-01-01 in the Gregorian calendars.
+    fn days(&self, year: i64, month: u32, day: u32) -> i64 {
+        let m = month as usize - 1;
+        let day = day as i64 - 1;
+        match self {
+            Self::Standard | Self::ProlepticGregorian => {
+                days_from_civil(year, month, day as u32 + 1)
+            }
+            Self::Noleap => year * 365 + CUMULATIVE_DAYS[m] + day,
+            Self::AllLeap => year * 366 + CUMULATIVE_LEAP_DAYS[m] + day,
+            Self::Day360 => year * 360 + m as i64 * 30 + day,
+        }
+    }
+
+    fn date(&self, days: i64) -> (i64, u32, u32) {
+        match self {
+            Self::Standard | Self::ProlepticGregorian => civil_from_days(days),
+            Self::Noleap => {
+                from_day_of_year(days.div_euclid(365), days.rem_euclid(365), &CUMULATIVE_DAYS)
+            }
+            Self::AllLeap => from_day_of_year(
+                days.div_euclid(366),
+                days.rem_euclid(366),
+                &CUMULATIVE_LEAP_DAYS,
+            ),
+            Self::Day360 => {
+                let day_of_year = days.rem_euclid(360);
+                (
+                    days.div_euclid(360),
+                    (day_of_year / 30) as u32 + 1,
+                    (day_of_year % 30) as u32 + 1,
+                )
+            }
+        }
+    }
+
+    /// Nanoseconds since the calendar's origin of a date, which must exist in the calendar.
+    pub(crate) fn to_nanos(self, date: &CalendarDate) -> i128 {
+        let seconds = date.hour as i128 * 3600 + date.minute as i128 * 60 + date.second as i128;
+        self.days(date.year, date.month, date.day) as i128 * NANOS_PER_DAY
+            + seconds * 1_000_000_000
+            + date.nanosecond as i128
+    }
+
+    /// The date at a number of nanoseconds since the calendar's origin.
+    pub(crate) fn date_at(self, nanos: i128) -> CalendarDate {
+        let days = nanos.div_euclid(NANOS_PER_DAY);
+        let of_day = nanos.rem_euclid(NANOS_PER_DAY);
+        let (year, month, day) = self.date(days as i64);
+        let seconds = (of_day / 1_000_000_000) as u32;
+        CalendarDate {
+            year,
+            month,
+            day,
+            hour: seconds / 3600,
+            minute: seconds / 60 % 60,
+            second: seconds % 60,
+            nanosecond: (of_day % 1_000_000_000) as u32,
+        }
+    }
+}
+
+impl fmt::Display for Calendar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Calendar {
+    type Err = TimeError;
+
+    /// Parse a CF calendar name, ignoring case, including its aliases.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.trim().to_ascii_lowercase().as_str() {
+            "standard" | "gregorian" => Self::Standard,
+            "proleptic_gregorian" => Self::ProlepticGregorian,
+            "noleap" | "365_day" => Self::Noleap,
+            "all_leap" | "366_day" => Self::AllLeap,
+            "360_day" => Self::Day360,
+            _ => return Err(TimeError::UnknownCalendar(s.to_string())),
+        })
+    }
+}
+
+/// A date and time of day in UTC, which may only exist in some calendars, e.g. `2000-02-30T00:00:00`.
+///
+/// Written as ISO 8601, and read as in CF `units` attributes,
+/// where the time is optional, may be separated from the date by a space,
+/// and fields need not be padded, e.g. `1-1-1 0:0:0`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(try_from = "String", into = "String")]
+pub struct CalendarDate {
+    pub year: i64,
+    pub month: u32,
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+    pub nanosecond: u32,
+}
+
+impl CalendarDate {
+    /// Midnight at the start of a day.
+    pub fn new(year: i64, month: u32, day: u32) -> Self {
+        Self {
+            year,
+            month,
+            day,
+            hour: 0,
+            minute: 0,
+            second: 0,
+            nanosecond: 0,
+        }
+    }
+
+    pub fn with_time(mut self, hour: u32, minute: u32, second: u32) -> Self {
+        self.hour = hour;
+        self.minute = minute;
+        self.second = second;
+        self
+    }
+
+    /// Parse a date, checking field ranges but not that the day exists in any calendar.
+    ///
+    /// ```
+    /// use zarrs_conventions_timeaxis::CalendarDate;
+    ///
+    /// let date = CalendarDate::parse("1850-1-1 12:30").unwrap();
+    /// assert_eq!(date.to_string(), "1850-01-01T12:30:00");
+    /// assert!(CalendarDate::parse("2000-01-01T00:00:00+01:00").is_err());
+    /// ```
+    pub fn parse(date: &str) -> Result<Self, TimeError> {
+        let syntax = || TimeError::Syntax(date.to_string());
+        let number = |s: &str| -> Result<u32, TimeError> {
+            if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(syntax());
+            }
+            s.parse().map_err(|_| syntax())
+        };
+        let trimmed = date.trim();
+        let trimmed = ["Z", "+00:00", "+0000", " UTC"]
+            .iter()
+            .find_map(|suffix| trimmed.strip_suffix(suffix))
+            .unwrap_or(trimmed);
+        let (negative, trimmed) = match trimmed.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, trimmed),
+        };
+        let (date_part, time_part) = match trimmed.split_once(['T', ' ']) {
+            Some((d, t)) => (d, Some(t.trim_start())),
+            None => (trimmed, None),
+        };
+        let fields: Vec<_> = date_part.split('-').collect();
+        let [year, month, day] = fields[..] else {
+            return Err(syntax());
+        };
+        let year = year
+            .parse::<u32>()
+            .ok()
+            .filter(|_| year.bytes().all(|b| b.is_ascii_digit()))
+            .ok_or_else(syntax)? as i64;
+        let mut out = Self::new(
+            if negative { -year } else { year },
+            number(month)?,
+            number(day)?,
+        );
+        if let Some(time) = time_part {
+            let fields: Vec<_> = time.split(':').collect();
+            let (hour, minute, second) = match fields[..] {
+                [h, m] => (h, m, "0"),
+                [h, m, s] => (h, m, s),
+                _ => return Err(syntax()),
+            };
+            let (second, fraction) = match second.split_once('.') {
+                Some((s, f)) if !f.is_empty() && f.len() <= 9 => (s, f),
+                Some(_) => return Err(syntax()),
+                None => (second, ""),
+            };
+            out.hour = number(hour)?;
+            out.minute = number(minute)?;
+            out.second = number(second)?;
+            if !fraction.is_empty() {
+                out.nanosecond = number(fraction)? * 10u32.pow(9 - fraction.len() as u32);
+            }
+        }
+        if !(1..=12).contains(&out.month)
+            || !(1..=31).contains(&out.day)
+            || out.hour > 23
+            || out.minute > 59
+            || out.second > 59
+        {
+            return Err(TimeError::Syntax(date.to_string()));
+        }
+        Ok(out)
+    }
+}
+
+impl fmt::Display for CalendarDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.year < 0 {
+            write!(f, "-{:04}", -self.year)?;
+        } else {
+            write!(f, "{:04}", self.year)?;
+        }
+        write!(
+            f,
+            "-{:02}-{:02}T{:02}:{:02}:{:02}",
+            self.month, self.day, self.hour, self.minute, self.second
+        )?;
+        if self.nanosecond > 0 {
+            let fraction = format!("{:09}", self.nanosecond);
+            write!(f, ".{}", fraction.trim_end_matches('0'))?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for CalendarDate {
+    type Err = TimeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl TryFrom<String> for CalendarDate {
+    type Error = TimeError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(&value)
+    }
+}
+
+impl From<CalendarDate> for String {
+    fn from(value: CalendarDate) -> Self {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Calendar, CalendarDate};
+
+    fn date(s: &str) -> CalendarDate {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(date("1970-01-01"), CalendarDate::new(1970, 1, 1));
+        assert_eq!(date("1-1-1 0:0:0 UTC"), CalendarDate::new(1, 1, 1));
+        assert_eq!(
+            date("2000-02-30T06:00:00.25Z").to_string(),
+            "2000-02-30T06:00:00.25"
+        );
+        assert_eq!(date("-100-03-01").to_string(), "-0100-03-01T00:00:00");
+        for invalid in [
+            "1970-01",
+            "1970-13-01",
+            "1970-01-01T24:00",
+            "1970-01-01T00:00:00.",
+            "x-01-01",
+        ] {
+            assert!(CalendarDate::parse(invalid).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn calendars() {
+        for calendar in [
+            Calendar::Standard,
+            Calendar::ProlepticGregorian,
+            Calendar::Noleap,
+            Calendar::AllLeap,
+            Calendar::Day360,
+        ] {
+            assert_eq!(calendar.name().parse::<Calendar>().unwrap(), calendar);
+            for d in [
+                "1999-12-30T23:59:59.5",
+                "2000-01-01",
+                "2000-02-28T12:00:00",
+                "2001-03-01",
+            ] {
+                let d = date(d);
+                assert_eq!(calendar.date_at(calendar.to_nanos(&d)), d, "{calendar} {d}");
+            }
+        }
+        assert_eq!(
+            Calendar::Standard.to_nanos(&date("1970-01-02")),
+            86_400_000_000_000
+        );
+        let check = |calendar: Calendar, d| calendar.check(&date(d)).is_ok();
+        assert!(check(Calendar::Day360, "2001-02-30"));
+        assert!(!check(Calendar::Noleap, "2000-02-29"));
+        assert!(check(Calendar::AllLeap, "2001-02-29"));
+        assert!(check(Calendar::Standard, "2000-02-29"));
+        assert!(!check(Calendar::Standard, "1900-02-29"));
+        assert!(!check(Calendar::Standard, "1582-10-14"));
+        assert!(check(Calendar::ProlepticGregorian, "1582-10-14"));
+        assert_eq!("GREGORIAN".parse::<Calendar>().unwrap(), Calendar::Standard);
+        assert!("julian".parse::<Calendar>().is_err());
+    }
+}
@@ -0,0 +1,144 @@
+//! Plain `units` and `calendar` attributes of [CF](https://cfconventions.org) time coordinates,
+//! e.g. `days since 1850-01-01 00:00:00`, kept alongside the formal payload for tools which do not know the convention,
+//! such as xarray.
+use zarrs_conventions::Attributes;
+
+use crate::{Calendar, CalendarDate, TimeAxis, TimeError};
+
+pub const UNITS_KEY: &str = "units";
+pub const CALENDAR_KEY: &str = "calendar";
+
+/// UDUNITS spellings of units of time, and their UCUM codes; the first of each is written.
+static CF_UNITS: &[(&[&str], &str)] = &[
+    (&["days", "day", "d"], "d"),
+    (&["hours", "hour", "hrs", "hr", "h"], "h"),
+    (&["minutes", "minute", "mins", "min"], "min"),
+    (&["seconds", "second", "secs", "sec", "s"], "s"),
+    (
+        &["milliseconds", "millisecond", "msecs", "msec", "ms"],
+        "ms",
+    ),
+    (
+        &["microseconds", "microsecond", "usecs", "usec", "us"],
+        "us",
+    ),
+    (&["nanoseconds", "nanosecond", "ns"], "ns"),
+];
+
+/// Why CF time attributes could not be read.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum CfError {
+    #[error("'{0}' attribute is not a string")]
+    NotAString(&'static str),
+    #[error(transparent)]
+    Time(#[from] TimeError),
+}
+
+fn get<'a>(attributes: &'a Attributes, key: &'static str) -> Result<Option<&'a str>, CfError> {
+    attributes
+        .get(key)
+        .map(|v| v.as_str().ok_or(CfError::NotAString(key)))
+        .transpose()
+}
+
+impl TimeAxis {
+    /// The CF `units` attribute, e.g. `days since 1850-01-01 00:00:00`.
+    ///
+    /// Fails if the unit has no UDUNITS name here, e.g. UCUM's months and years,
+    /// which the CF conventions advise against.
+    pub fn cf_units(&self) -> Result<String, TimeError> {
+        let (names, _) = CF_UNITS
+            .iter()
+            .find(|(_, ucum)| *ucum == self.unit)
+            .ok_or_else(|| TimeError::UnknownUnit(self.unit.clone()))?;
+        let epoch = self.epoch.to_string().replacen('T', " ", 1);
+        Ok(format!("{} since {epoch}", names[0]))
+    }
+
+    /// Write the CF `units` and `calendar` attributes.
+    ///
+    /// ```
+    /// use zarrs_conventions_timeaxis::{Calendar, TimeAxis};
+    ///
+    /// let axis = TimeAxis::builder("1850-01-01".parse().unwrap(), "d")
+    ///     .calendar(Calendar::Noleap)
+    ///     .build()
+    ///     .unwrap();
+    /// let mut attributes = serde_json::Map::default();
+    /// axis.write_cf_attributes(&mut attributes).unwrap();
+    /// assert_eq!(attributes["units"], "days since 1850-01-01 00:00:00");
+    /// assert_eq!(attributes["calendar"], "noleap");
+    /// assert_eq!(TimeAxis::from_cf_attributes(&attributes), Ok(Some(axis)));
+    /// ```
+    pub fn write_cf_attributes(&self, attributes: &mut Attributes) -> Result<(), TimeError> {
+        attributes.insert(UNITS_KEY.to_string(), self.cf_units()?.into());
+        attributes.insert(CALENDAR_KEY.to_string(), self.calendar.name().into());
+        Ok(())
+    }
+
+    /// A time axis from CF `units` and `calendar` attributes,
+    /// or `None` if there is no `units` attribute of the form `UNIT since DATE`.
+    ///
+    /// The calendar is `standard` if not given.
+    pub fn from_cf_attributes(attributes: &Attributes) -> Result<Option<Self>, CfError> {
+        let Some(units) = get(attributes, UNITS_KEY)? else {
+            return Ok(None);
+        };
+        let Some((unit, epoch)) = units.trim().split_once(" since ") else {
+            return Ok(None);
+        };
+        let unit = unit.trim();
+        let (_, ucum) = CF_UNITS
+            .iter()
+            .find(|(names, _)| names.iter().any(|n| n.eq_ignore_ascii_case(unit)))
+            .ok_or_else(|| TimeError::UnknownUnit(unit.to_string()))?;
+        let calendar = match get(attributes, CALENDAR_KEY)? {
+            Some(calendar) => calendar.parse()?,
+            None => Calendar::default(),
+        };
+        let epoch = CalendarDate::parse(epoch)?;
+        Ok(Some(
+            Self::builder(epoch, *ucum).calendar(calendar).build()?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::CfError;
+    use crate::{Calendar, CalendarDate, TimeAxis, TimeError};
+
+    fn read(attributes: serde_json::Value) -> Result<Option<TimeAxis>, CfError> {
+        TimeAxis::from_cf_attributes(attributes.as_object().unwrap())
+    }
+
+    #[test]
+    fn cf() {
+        let axis = read(json!({"units": "hours since 2000-1-1", "calendar": "360_day"}))
+            .unwrap()
+            .unwrap();
+        assert_eq!(axis.unit(), "h");
+        assert_eq!(axis.calendar(), Calendar::Day360);
+        assert_eq!(axis.epoch(), &CalendarDate::new(2000, 1, 1));
+        assert_eq!(axis.cf_units().unwrap(), "hours since 2000-01-01 00:00:00");
+
+        let default = read(json!({"units": "Seconds since 1970-01-01T00:00:00Z"}))
+            .unwrap()
+            .unwrap();
+        assert_eq!(default.calendar(), Calendar::Standard);
+        assert_eq!(read(json!({"units": "K"})), Ok(None));
+        assert_eq!(read(json!({})), Ok(None));
+        assert_eq!(
+            read(json!({"units": "months since 2000-01-01"})),
+            Err(CfError::Time(TimeError::UnknownUnit("months".into())))
+        );
+        assert_eq!(
+            read(json!({"units": "days since 2000-01-01", "calendar": "julian"})),
+            Err(CfError::Time(TimeError::UnknownCalendar("julian".into())))
+        );
+        assert_eq!(read(json!({"units": 1})), Err(CfError::NotAString("units")));
+    }
+}
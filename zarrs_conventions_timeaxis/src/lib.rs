@@ -0,0 +1,300 @@
+#![doc = include_str!("../README.md")]
+use serde::{Deserialize, Serialize};
+pub use zarrs_conventions;
+use zarrs_conventions::{
+    define_convention,
+    validate::{Issue, Lint},
+};
+
+mod calendar;
+pub mod cf;
+#[cfg(feature = "chrono")]
+mod typed;
+
+pub use calendar::{Calendar, CalendarDate};
+
+/// Why a time coordinate or date could not be read or converted.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum TimeError {
+    #[error("'{0}' is not a date, e.g. 1970-01-01 00:00:00")]
+    Syntax(String),
+    #[error("{date} is not a date in the {calendar} calendar")]
+    InvalidDate { date: String, calendar: Calendar },
+    #[error(
+        "{0} is before 1582-10-15, from when the standard calendar is Gregorian; Julian dates are not supported"
+    )]
+    BeforeGregorian(String),
+    #[error("unknown or unsupported calendar '{0}'")]
+    UnknownCalendar(String),
+    #[error("'{0}' is not a supported unit of time")]
+    UnknownUnit(String),
+    #[error("time coordinate {0} is out of range")]
+    OutOfRange(String),
+    #[error("dates in the {0} calendar are not Gregorian dates")]
+    NotGregorian(Calendar),
+}
+
+/// UCUM units of time which are always supported, and their lengths in seconds.
+static UNITS: &[(&str, f64)] = &[
+    ("ns", 1e-9),
+    ("us", 1e-6),
+    ("ms", 1e-3),
+    ("s", 1.0),
+    ("min", 60.0),
+    ("h", 3600.0),
+    ("d", 86400.0),
+];
+
+/// Length in seconds of a UCUM unit of time.
+fn seconds_per(unit: &str) -> Option<f64> {
+    if let Some((_, seconds)) = UNITS.iter().find(|(u, _)| *u == unit) {
+        return Some(*seconds);
+    }
+    #[cfg(feature = "uom")]
+    {
+        use zarrs_conventions_uom::Ucum;
+        let conversion = Ucum::parse(unit)
+            .ok()?
+            .conversion_to(&Ucum::parse("s").expect("second is a UCUM unit"))
+            .ok()?;
+        if conversion.offset == 0.0 && conversion.scale > 0.0 {
+            return Some(conversion.scale);
+        }
+    }
+    None
+}
+
+define_convention! {
+    /// Describes the values of a time coordinate array: each is a number of units since an epoch,
+    /// counted in a calendar.
+    #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+    pub struct TimeAxis {
+        /// The date from which time is counted, at value 0.
+        epoch: CalendarDate,
+        /// UCUM code of the unit of time, e.g. `d`.
+        unit: String,
+        /// Calendar in which time is counted.
+        #[serde(default)]
+        calendar: Calendar,
+    }
+    schema_url = "https://raw.githubusercontent.com/clbarnes/zarrs_conventions/refs/tags/timeaxis-v1/zarrs_conventions_timeaxis/spec/schema.json",
+    spec_url = "https://github.com/clbarnes/zarrs_conventions/blob/timeaxis-v1/zarrs_conventions_timeaxis/spec/README.md",
+    name = "timeaxis",
+    description = "Epoch, unit, and calendar of the values of a time coordinate array",
+    nested = "timeaxis",
+    register = false,
+}
+
+impl TimeAxis {
+    pub fn builder(epoch: CalendarDate, unit: impl Into<String>) -> Builder {
+        Builder {
+            inner: Self {
+                epoch,
+                unit: unit.into(),
+                calendar: Calendar::default(),
+            },
+        }
+    }
+
+    /// The date from which time is counted, at value 0.
+    pub fn epoch(&self) -> &CalendarDate {
+        &self.epoch
+    }
+
+    /// UCUM code of the unit of time, e.g. `d`.
+    pub fn unit(&self) -> &str {
+        &self.unit
+    }
+
+    pub fn calendar(&self) -> Calendar {
+        self.calendar
+    }
+
+    /// Length of the unit in seconds.
+    ///
+    /// Without the `uom` feature, only `ns`, `us`, `ms`, `s`, `min`, `h`, and `d` are supported.
+    pub fn seconds_per_unit(&self) -> Result<f64, TimeError> {
+        seconds_per(&self.unit).ok_or_else(|| TimeError::UnknownUnit(self.unit.clone()))
+    }
+
+    /// The date of a time coordinate value, to the nearest nanosecond.
+    ///
+    /// ```
+    /// use zarrs_conventions_timeaxis::{Calendar, TimeAxis};
+    ///
+    /// let axis = TimeAxis::builder("2000-01-01".parse().unwrap(), "d")
+    ///     .calendar(Calendar::Day360)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(axis.to_date(59.5).unwrap().to_string(), "2000-02-30T12:00:00");
+    /// ```
+    pub fn to_date(&self, value: f64) -> Result<CalendarDate, TimeError> {
+        let out_of_range = || TimeError::OutOfRange(value.to_string());
+        let offset = value * self.seconds_per_unit()? * 1e9;
+        if !offset.is_finite() || offset.abs() > 1e30 {
+            return Err(out_of_range());
+        }
+        let nanos = self.calendar.to_nanos(&self.epoch) + offset.round() as i128;
+        let date = self.calendar.date_at(nanos);
+        self.calendar.check(&date)?;
+        Ok(date)
+    }
+
+    /// Dates of time coordinate values, e.g. of a retrieved chunk.
+    pub fn to_dates<T: Copy + Into<f64>>(
+        &self,
+        values: &[T],
+    ) -> Result<Vec<CalendarDate>, TimeError> {
+        values.iter().map(|v| self.to_date((*v).into())).collect()
+    }
+
+    /// The time coordinate value of a date, which must exist in the calendar.
+    ///
+    /// ```
+    /// use zarrs_conventions_timeaxis::TimeAxis;
+    ///
+    /// let axis = TimeAxis::builder("1970-01-01".parse().unwrap(), "h").build().unwrap();
+    /// assert_eq!(axis.to_value(&"1970-01-02 06:00".parse().unwrap()).unwrap(), 30.0);
+    /// ```
+    pub fn to_value(&self, date: &CalendarDate) -> Result<f64, TimeError> {
+        self.calendar.check(date)?;
+        let nanos = self.calendar.to_nanos(date) - self.calendar.to_nanos(&self.epoch);
+        Ok(nanos as f64 / 1e9 / self.seconds_per_unit()?)
+    }
+
+    fn problems(&self) -> Vec<(&'static str, TimeError)> {
+        let mut problems = Vec::default();
+        if let Err(e) = self.calendar.check(&self.epoch) {
+            problems.push(("invalid-epoch", e));
+        }
+        if let Err(e) = self.seconds_per_unit() {
+            problems.push(("unknown-unit", e));
+        }
+        problems
+    }
+
+    /// The unit of the time coordinates, for the uom convention.
+    #[cfg(feature = "uom")]
+    pub fn uom(&self) -> zarrs_conventions_uom::UnitOfMeasurement {
+        zarrs_conventions_uom::UnitOfMeasurement::builder()
+            .unit(self.unit.clone())
+            .build()
+    }
+}
+
+impl Lint for TimeAxis {
+    fn lint(&self) -> Vec<Issue> {
+        self.problems()
+            .into_iter()
+            .map(|(code, e)| Issue::error(code, e.to_string()))
+            .collect()
+    }
+}
+
+/// Builder for [TimeAxis], created by [TimeAxis::builder].
+#[derive(Debug, Clone)]
+pub struct Builder {
+    inner: TimeAxis,
+}
+
+impl Builder {
+    /// Set the calendar; `standard` by default.
+    pub fn calendar(mut self, calendar: Calendar) -> Self {
+        self.inner.calendar = calendar;
+        self
+    }
+
+    /// Build the axis, checking that the epoch exists in the calendar and that the unit is supported.
+    pub fn build(self) -> Result<TimeAxis, TimeError> {
+        match self.inner.problems().into_iter().next() {
+            Some((_, e)) => Err(e),
+            None => Ok(self.inner),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use zarrs_conventions::validate::Lint;
+
+    use crate::{Calendar, CalendarDate, TimeAxis, TimeError};
+
+    fn date(s: &str) -> CalendarDate {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn build() {
+        let axis = TimeAxis::builder(date("1850-01-01"), "d")
+            .calendar(Calendar::Noleap)
+            .build()
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(&axis).unwrap(),
+            json!({"epoch": "1850-01-01T00:00:00", "unit": "d", "calendar": "noleap"})
+        );
+        assert!(axis.lint().is_empty());
+        assert_eq!(
+            serde_json::from_value::<TimeAxis>(json!({"epoch": "1970-01-01", "unit": "s"}))
+                .unwrap()
+                .calendar(),
+            Calendar::Standard
+        );
+
+        assert_eq!(
+            TimeAxis::builder(date("2001-02-29"), "d").build(),
+            Err(TimeError::InvalidDate {
+                date: "2001-02-29T00:00:00".into(),
+                calendar: Calendar::Standard
+            })
+        );
+        assert_eq!(
+            TimeAxis::builder(date("2001-01-01"), "fortnight").build(),
+            Err(TimeError::UnknownUnit("fortnight".into()))
+        );
+    }
+
+    #[test]
+    fn convert() {
+        let noleap = TimeAxis::builder(date("2000-01-01"), "d")
+            .calendar(Calendar::Noleap)
+            .build()
+            .unwrap();
+        assert_eq!(noleap.to_date(59.0).unwrap(), date("2000-03-01"));
+        assert_eq!(noleap.to_date(365.0).unwrap(), date("2001-01-01"));
+        assert_eq!(noleap.to_date(-0.25).unwrap(), date("1999-12-31T18:00:00"));
+        assert_eq!(noleap.to_value(&date("2001-01-01")).unwrap(), 365.0);
+        assert!(noleap.to_value(&date("2000-02-29")).is_err());
+
+        let standard = TimeAxis::builder(date("1970-01-01"), "s").build().unwrap();
+        assert_eq!(
+            standard.to_dates(&[0u32, 951782400]).unwrap(),
+            [date("1970-01-01"), date("2000-02-29")]
+        );
+        assert_eq!(standard.to_date(1.5e-9).unwrap().nanosecond, 2);
+        assert!(matches!(
+            standard.to_date(-2e10),
+            Err(TimeError::BeforeGregorian(_))
+        ));
+        assert!(matches!(
+            standard.to_date(f64::NAN),
+            Err(TimeError::OutOfRange(_))
+        ));
+    }
+
+    #[test]
+    fn lint() {
+        let axis: TimeAxis = serde_json::from_value(
+            json!({"epoch": "2000-02-30", "unit": "mo", "calendar": "gregorian"}),
+        )
+        .unwrap();
+        let codes: Vec<_> = axis.lint().into_iter().map(|i| i.code).collect();
+        #[cfg(not(feature = "uom"))]
+        assert_eq!(codes, ["invalid-epoch", "unknown-unit"]);
+        #[cfg(feature = "uom")]
+        assert_eq!(codes, ["invalid-epoch"]);
+    }
+}
@@ -0,0 +1,80 @@
+//! Conversions between time coordinates and chrono's date-times, for Gregorian calendars.
+use chrono::{DateTime, Datelike, NaiveDate, TimeZone, Timelike, Utc};
+
+use crate::{CalendarDate, TimeAxis, TimeError};
+
+impl TimeAxis {
+    fn check_gregorian(&self) -> Result<(), TimeError> {
+        if self.calendar.is_gregorian() {
+            Ok(())
+        } else {
+            Err(TimeError::NotGregorian(self.calendar))
+        }
+    }
+
+    /// The date-time of a time coordinate value, if the calendar is Gregorian.
+    ///
+    /// ```
+    /// use zarrs_conventions_timeaxis::TimeAxis;
+    ///
+    /// let axis = TimeAxis::builder("1970-01-01".parse().unwrap(), "d").build().unwrap();
+    /// assert_eq!(axis.to_chrono(1.5).unwrap().timestamp(), 129600);
+    /// ```
+    pub fn to_chrono(&self, value: f64) -> Result<DateTime<Utc>, TimeError> {
+        self.check_gregorian()?;
+        let date = self.to_date(value)?;
+        let out_of_range = || TimeError::OutOfRange(value.to_string());
+        let naive = i32::try_from(date.year)
+            .ok()
+            .and_then(|year| NaiveDate::from_ymd_opt(year, date.month, date.day))
+            .and_then(|d| d.and_hms_nano_opt(date.hour, date.minute, date.second, date.nanosecond))
+            .ok_or_else(out_of_range)?;
+        Ok(Utc.from_utc_datetime(&naive))
+    }
+
+    /// The time coordinate value of a date-time, if the calendar is Gregorian.
+    pub fn value_from_chrono<Tz: TimeZone>(
+        &self,
+        datetime: &DateTime<Tz>,
+    ) -> Result<f64, TimeError> {
+        self.check_gregorian()?;
+        let utc = datetime.naive_utc();
+        let date = CalendarDate {
+            year: utc.year() as i64,
+            month: utc.month(),
+            day: utc.day(),
+            hour: utc.hour(),
+            minute: utc.minute(),
+            second: utc.second(),
+            nanosecond: utc.nanosecond().min(999_999_999),
+        };
+        self.to_value(&date)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use crate::{Calendar, TimeAxis, TimeError};
+
+    #[test]
+    fn chrono() {
+        let axis = TimeAxis::builder("2000-01-01 12:00".parse().unwrap(), "h")
+            .build()
+            .unwrap();
+        let datetime = Utc.with_ymd_and_hms(2000, 3, 1, 0, 0, 0).unwrap();
+        let value = axis.value_from_chrono(&datetime).unwrap();
+        assert_eq!(value, 60.0 * 24.0 - 12.0);
+        assert_eq!(axis.to_chrono(value).unwrap(), datetime);
+
+        let noleap = TimeAxis::builder("2000-01-01".parse().unwrap(), "d")
+            .calendar(Calendar::Noleap)
+            .build()
+            .unwrap();
+        assert_eq!(
+            noleap.to_chrono(0.0),
+            Err(TimeError::NotGregorian(Calendar::Noleap))
+        );
+    }
+}
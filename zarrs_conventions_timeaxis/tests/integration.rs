@@ -0,0 +1,34 @@
+use zarrs_conventions::test_util::{parse_example, parse_modified_example, parse_nested};
+use zarrs_conventions_timeaxis::TimeAxis;
+
+const EXAMPLE: &str = include_str!("examples/cmip.json");
+
+zarrs_conventions::convention_test_suite! {
+    TimeAxis,
+    repr = nested,
+    example = parse_example(EXAMPLE, parse_nested),
+    examples = "tests/examples",
+    schema = "spec/schema.json",
+    registered = false,
+}
+
+#[test]
+fn reject_missing_epoch() {
+    let parsed = parse_modified_example(
+        EXAMPLE,
+        |attrs| {
+            attrs["timeaxis"].as_object_mut().unwrap().remove("epoch");
+        },
+        parse_nested::<TimeAxis>,
+    );
+    assert!(parsed.is_err());
+}
+
+#[cfg(feature = "schema")]
+#[test]
+fn spec_up_to_date() {
+    zarrs_conventions::test_util::assert_docs_up_to_date(
+        &zarrs_conventions::docgen::ConventionDocs::nested::<TimeAxis>(),
+        concat!(env!("CARGO_MANIFEST_DIR"), "/spec"),
+    );
+}
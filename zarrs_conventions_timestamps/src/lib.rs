@@ -0,0 +1,344 @@
+#![doc = include_str!("../README.md")]
+use std::{
+    fmt,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+pub use zarrs_conventions;
+use zarrs_conventions::{
+    define_convention,
+    validate::{Issue, Lint},
+};
+
+mod rfc3339;
+pub use rfc3339::{civil_from_days, days_from_civil};
+#[cfg(any(feature = "chrono", feature = "time"))]
+mod typed;
+
+/// Why a string is not a valid timestamp.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum TimestampError {
+    #[error("'{0}' is not an RFC 3339 timestamp, e.g. 2024-01-31T12:00:00Z")]
+    Syntax(String),
+    #[error("'{0}' has no UTC offset, e.g. Z or +01:00")]
+    NoOffset(String),
+    #[error("'{0}' is not a valid date and time")]
+    OutOfRange(String),
+}
+
+/// An instant as an [RFC 3339](https://www.rfc-editor.org/rfc/rfc3339) timestamp,
+/// with a UTC offset, e.g. `2024-01-31T12:00:00+01:00`.
+///
+/// The string is kept as given, so timestamps of the same instant with different offsets
+/// are not equal; compare them with [Timestamp::instant].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "schema", schemars(extend("format" = "date-time")))]
+#[serde(transparent)]
+pub struct Timestamp(String);
+
+impl Timestamp {
+    pub fn new(timestamp: impl Into<String>) -> Result<Self, TimestampError> {
+        let timestamp = Self(timestamp.into());
+        timestamp.instant()?;
+        Ok(timestamp)
+    }
+
+    /// A timestamp which is checked when building [Timestamps], or by [Timestamp::instant].
+    pub fn new_unchecked(timestamp: impl Into<String>) -> Self {
+        Self(timestamp.into())
+    }
+
+    /// The current time in UTC, to the second.
+    pub fn now() -> Self {
+        let seconds = match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(d) => d.as_secs() as i64,
+            Err(e) => -(e.duration().as_secs_f64().ceil() as i64),
+        };
+        Self(rfc3339::format_utc(seconds))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Seconds and nanoseconds since the Unix epoch, which order timestamps regardless of their offsets.
+    ///
+    /// ```
+    /// use zarrs_conventions_timestamps::Timestamp;
+    ///
+    /// let utc = Timestamp::new("2024-01-31T12:00:00Z").unwrap();
+    /// let cet = Timestamp::new("2024-01-31T13:00:00+01:00").unwrap();
+    /// assert_ne!(utc, cet);
+    /// assert_eq!(utc.instant(), cet.instant());
+    /// ```
+    pub fn instant(&self) -> Result<(i64, u32), TimestampError> {
+        rfc3339::parse(&self.0)
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::str::FromStr for Timestamp {
+    type Err = TimestampError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+define_convention! {
+    /// When a dataset was created, last modified, and published.
+    #[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+    #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+    pub struct Timestamps {
+        /// When the dataset was created.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        created: Option<Timestamp>,
+        /// When the dataset was last modified.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        modified: Option<Timestamp>,
+        /// When the dataset was published.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        published: Option<Timestamp>,
+    }
+    schema_url = "https://raw.githubusercontent.com/clbarnes/zarrs_conventions/refs/tags/timestamps-v1/zarrs_conventions_timestamps/spec/schema.json",
+    spec_url = "https://github.com/clbarnes/zarrs_conventions/blob/timestamps-v1/zarrs_conventions_timestamps/spec/README.md",
+    name = "timestamps",
+    description = "When a dataset was created, modified, and published",
+    nested = "timestamps",
+    register = false,
+}
+
+impl Timestamps {
+    pub fn builder() -> Builder {
+        Default::default()
+    }
+
+    pub fn created(&self) -> Option<&Timestamp> {
+        self.created.as_ref()
+    }
+
+    pub fn modified(&self) -> Option<&Timestamp> {
+        self.modified.as_ref()
+    }
+
+    pub fn published(&self) -> Option<&Timestamp> {
+        self.published.as_ref()
+    }
+
+    /// Record a modification at the given time, which is also the creation time if none was recorded.
+    pub fn touch_at(&mut self, timestamp: Timestamp) {
+        if self.created.is_none() {
+            self.created = Some(timestamp.clone());
+        }
+        self.modified = Some(timestamp);
+    }
+
+    /// Record a modification now, e.g. before writing changes to the dataset.
+    pub fn touch(&mut self) {
+        self.touch_at(Timestamp::now());
+    }
+
+    /// Each timestamp with the name of its field.
+    fn fields(&self) -> impl Iterator<Item = (&'static str, &Timestamp)> {
+        [
+            ("created", &self.created),
+            ("modified", &self.modified),
+            ("published", &self.published),
+        ]
+        .into_iter()
+        .filter_map(|(field, ts)| Some((field, ts.as_ref()?)))
+    }
+
+    /// Check that every timestamp is valid, and that none precedes the creation time.
+    fn validate(&self) -> Result<(), Vec<BuildError>> {
+        let mut errors = Vec::default();
+        for (field, ts) in self.fields() {
+            if let Err(source) = ts.instant() {
+                errors.push(BuildError::Invalid { field, source });
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        if let Some(created) = &self.created {
+            let created_at = created.instant().expect("checked above");
+            for (field, ts) in self.fields().filter(|(f, _)| *f != "created") {
+                if ts.instant().expect("checked above") < created_at {
+                    errors.push(BuildError::BeforeCreated {
+                        field,
+                        timestamp: ts.clone(),
+                        created: created.clone(),
+                    });
+                }
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl Lint for Timestamps {
+    fn lint(&self) -> Vec<Issue> {
+        let mut issues = Vec::default();
+        if self.fields().next().is_none() {
+            issues.push(Issue::warning("no-timestamps", "no timestamps are given"));
+        }
+        for e in self.validate().err().unwrap_or_default() {
+            let code = match e {
+                BuildError::Invalid { .. } => "invalid-timestamp",
+                BuildError::BeforeCreated { .. } => "before-created",
+            };
+            issues.push(Issue::error(code, e.to_string()));
+        }
+        issues
+    }
+}
+
+/// Why timestamps could not be built.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum BuildError {
+    #[error("invalid {field} timestamp: {source}")]
+    Invalid {
+        field: &'static str,
+        source: TimestampError,
+    },
+    #[error("{field} timestamp {timestamp} is before the creation time {created}")]
+    BeforeCreated {
+        field: &'static str,
+        timestamp: Timestamp,
+        created: Timestamp,
+    },
+}
+
+/// Builder for [Timestamps], created by [Timestamps::builder].
+#[derive(Debug, Clone, Default)]
+pub struct Builder {
+    inner: Timestamps,
+}
+
+impl Builder {
+    /// Set the creation time, which is checked when building.
+    pub fn created(mut self, timestamp: impl Into<String>) -> Self {
+        self.inner.created = Some(Timestamp::new_unchecked(timestamp));
+        self
+    }
+
+    /// Set the time of the last modification, which is checked when building.
+    pub fn modified(mut self, timestamp: impl Into<String>) -> Self {
+        self.inner.modified = Some(Timestamp::new_unchecked(timestamp));
+        self
+    }
+
+    /// Set the publication time, which is checked when building.
+    pub fn published(mut self, timestamp: impl Into<String>) -> Self {
+        self.inner.published = Some(Timestamp::new_unchecked(timestamp));
+        self
+    }
+
+    /// Record a modification now, which is also the creation time if none was set;
+    /// see [Timestamps::touch].
+    pub fn touch(mut self) -> Self {
+        self.inner.touch();
+        self
+    }
+
+    /// Build the timestamps, checking that each is valid and none precedes the creation time.
+    ///
+    /// Only the first problem is returned; use [Lint] to find all of them.
+    pub fn build(self) -> Result<Timestamps, BuildError> {
+        match self.inner.validate() {
+            Ok(()) => Ok(self.inner),
+            Err(mut errors) => Err(errors.swap_remove(0)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use zarrs_conventions::validate::Lint;
+
+    use crate::{BuildError, Timestamp, TimestampError, Timestamps};
+
+    #[test]
+    fn build() {
+        let timestamps = Timestamps::builder()
+            .created("2024-01-31T12:00:00+01:00")
+            .modified("2024-01-31T11:00:00Z")
+            .build()
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(&timestamps).unwrap(),
+            json!({"created": "2024-01-31T12:00:00+01:00", "modified": "2024-01-31T11:00:00Z"})
+        );
+        assert!(timestamps.lint().is_empty());
+
+        assert_eq!(
+            Timestamps::builder()
+                .created("2024-01-31T12:00:00Z")
+                .published("2024-01-31T12:00:00+01:00")
+                .build(),
+            Err(BuildError::BeforeCreated {
+                field: "published",
+                timestamp: Timestamp::new_unchecked("2024-01-31T12:00:00+01:00"),
+                created: Timestamp::new_unchecked("2024-01-31T12:00:00Z"),
+            })
+        );
+        assert_eq!(
+            Timestamps::builder().modified("2024-01-31").build(),
+            Err(BuildError::Invalid {
+                field: "modified",
+                source: TimestampError::Syntax("2024-01-31".into())
+            })
+        );
+    }
+
+    #[test]
+    fn touch() {
+        let mut timestamps = Timestamps::builder().touch().build().unwrap();
+        assert_eq!(timestamps.created(), timestamps.modified());
+
+        let later = Timestamp::new("9999-12-31T23:59:59Z").unwrap();
+        timestamps.touch_at(later.clone());
+        assert_ne!(timestamps.created(), Some(&later));
+        assert_eq!(timestamps.modified(), Some(&later));
+        assert!(timestamps.lint().is_empty());
+    }
+
+    #[test]
+    fn lint() {
+        let codes = |value| {
+            let timestamps: Timestamps = serde_json::from_value(value).unwrap();
+            timestamps
+                .lint()
+                .into_iter()
+                .map(|i| i.code)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(codes(json!({})), ["no-timestamps"]);
+        assert_eq!(
+            codes(json!({"created": "2024-01-31T12:00:00", "modified": "yesterday"})),
+            ["invalid-timestamp", "invalid-timestamp"]
+        );
+        assert_eq!(
+            codes(json!({
+                "created": "2024-01-31T12:00:00Z",
+                "modified": "2024-01-30T12:00:00Z",
+                "published": "2024-01-29T12:00:00Z",
+            })),
+            ["before-created", "before-created"]
+        );
+    }
+}
@@ -0,0 +1,216 @@
+//! Parsing and formatting RFC 3339 timestamps, as instants since the Unix epoch.
+use crate::TimestampError;
+
+/// Days since 1970-01-01 of a date in the proleptic Gregorian calendar.
+///
+/// ```
+/// use zarrs_conventions_timestamps::{civil_from_days, days_from_civil};
+///
+/// assert_eq!(days_from_civil(2000, 3, 1), 11017);
+/// assert_eq!(civil_from_days(11017), (2000, 3, 1));
+/// ```
+pub fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month = month as i64;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// Date of a number of days since 1970-01-01 in the proleptic Gregorian calendar.
+pub fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Reads fixed-width fields from a timestamp.
+struct Cursor<'a> {
+    s: &'a [u8],
+    pos: usize,
+}
+
+impl Cursor<'_> {
+    fn digits(&mut self, n: usize) -> Option<u32> {
+        let field = self.s.get(self.pos..self.pos + n)?;
+        if !field.iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+        self.pos += n;
+        Some(field.iter().fold(0, |acc, d| acc * 10 + (d - b'0') as u32))
+    }
+
+    fn expect(&mut self, options: &[u8]) -> Option<u8> {
+        let c = *self.s.get(self.pos).filter(|c| options.contains(c))?;
+        self.pos += 1;
+        Some(c)
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.s.get(self.pos).copied()
+    }
+}
+
+/// Seconds and nanoseconds since the Unix epoch of an RFC 3339 timestamp.
+///
+/// A leap second is counted as the following second.
+pub(crate) fn parse(timestamp: &str) -> Result<(i64, u32), TimestampError> {
+    let syntax = || TimestampError::Syntax(timestamp.to_string());
+    let range = || TimestampError::OutOfRange(timestamp.to_string());
+    let mut c = Cursor {
+        s: timestamp.as_bytes(),
+        pos: 0,
+    };
+    let field = |c: &mut Cursor, n, sep: &[u8]| {
+        let value = c.digits(n)?;
+        if !sep.is_empty() {
+            c.expect(sep)?;
+        }
+        Some(value)
+    };
+    let year = field(&mut c, 4, b"-").ok_or_else(syntax)?;
+    let month = field(&mut c, 2, b"-").ok_or_else(syntax)?;
+    let day = field(&mut c, 2, b"Tt").ok_or_else(syntax)?;
+    let hour = field(&mut c, 2, b":").ok_or_else(syntax)?;
+    let minute = field(&mut c, 2, b":").ok_or_else(syntax)?;
+    let second = field(&mut c, 2, b"").ok_or_else(syntax)?;
+    let mut nanos = 0;
+    if c.expect(b".").is_some() {
+        let start = c.pos;
+        while c.peek().is_some_and(|d| d.is_ascii_digit()) {
+            if c.pos - start < 9 {
+                nanos = nanos * 10 + (c.s[c.pos] - b'0') as u32;
+            }
+            c.pos += 1;
+        }
+        match c.pos - start {
+            0 => return Err(syntax()),
+            n if n < 9 => nanos *= 10u32.pow(9 - n as u32),
+            _ => (),
+        }
+    }
+    let offset_minutes = match c.peek() {
+        None => return Err(TimestampError::NoOffset(timestamp.to_string())),
+        Some(b'Z' | b'z') => {
+            c.pos += 1;
+            0
+        }
+        Some(_) => {
+            let sign = c.expect(b"+-").ok_or_else(syntax)?;
+            let hours = field(&mut c, 2, b":").ok_or_else(syntax)?;
+            let minutes = field(&mut c, 2, b"").ok_or_else(syntax)?;
+            if hours > 23 || minutes > 59 {
+                return Err(range());
+            }
+            let offset = (hours * 60 + minutes) as i64;
+            if sign == b'-' { -offset } else { offset }
+        }
+    };
+    if c.pos != c.s.len() {
+        return Err(syntax());
+    }
+    let year = year as i64;
+    if !(1..=12).contains(&month)
+        || day == 0
+        || day > days_in_month(year, month)
+        || hour > 23
+        || minute > 59
+        || second > 60
+    {
+        return Err(range());
+    }
+    let seconds = days_from_civil(year, month, day) * 86400
+        + (hour * 3600 + minute * 60 + second) as i64
+        - offset_minutes * 60;
+    Ok((seconds, nanos))
+}
+
+/// An RFC 3339 timestamp in UTC of seconds since the Unix epoch.
+pub(crate) fn format_utc(seconds: i64) -> String {
+    let (year, month, day) = civil_from_days(seconds.div_euclid(86400));
+    let time = seconds.rem_euclid(86400);
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        time / 3600,
+        time % 3600 / 60,
+        time % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_utc, parse};
+    use crate::TimestampError;
+
+    #[test]
+    fn parse_timestamps() {
+        assert_eq!(parse("1970-01-01T00:00:00Z"), Ok((0, 0)));
+        assert_eq!(parse("1970-01-01t01:00:00+01:00"), Ok((0, 0)));
+        assert_eq!(parse("1969-12-31T23:59:59.5z"), Ok((-1, 500_000_000)));
+        assert_eq!(
+            parse("2024-02-29T12:30:00.123456789123-05:30"),
+            Ok((1709229600, 123_456_789))
+        );
+        assert_eq!(parse("2016-12-31T23:59:60Z"), Ok((1483228800, 0)));
+
+        for syntax in [
+            "2024-01-01",
+            "2024-01-01 00:00:00Z",
+            "24-01-01T00:00:00Z",
+            "2024-01-01T00:00:00.Z",
+            "2024-01-01T00:00:00+0100",
+            "2024-01-01T00:00:00Zjunk",
+        ] {
+            assert!(
+                matches!(parse(syntax), Err(TimestampError::Syntax(_))),
+                "{syntax}"
+            );
+        }
+        for range in [
+            "2023-02-29T00:00:00Z",
+            "2024-13-01T00:00:00Z",
+            "2024-01-01T24:00:00Z",
+            "2024-01-01T00:00:00+24:00",
+        ] {
+            assert!(
+                matches!(parse(range), Err(TimestampError::OutOfRange(_))),
+                "{range}"
+            );
+        }
+        assert!(matches!(
+            parse("2024-01-01T00:00:00"),
+            Err(TimestampError::NoOffset(_))
+        ));
+    }
+
+    #[test]
+    fn format() {
+        for s in [
+            "1970-01-01T00:00:00Z",
+            "2000-02-29T23:59:59Z",
+            "1969-07-20T20:17:40Z",
+            "2262-04-11T23:47:16Z",
+        ] {
+            assert_eq!(format_utc(parse(s).unwrap().0), s);
+        }
+    }
+}
@@ -0,0 +1,90 @@
+//! Conversions between timestamps and the date-time types of the chrono and time crates.
+use crate::Timestamp;
+
+#[cfg(feature = "chrono")]
+impl Timestamp {
+    /// The timestamp as a chrono date-time, keeping its offset.
+    ///
+    /// ```
+    /// use zarrs_conventions_timestamps::Timestamp;
+    ///
+    /// let ts = Timestamp::new("2024-01-31T12:00:00+01:00").unwrap();
+    /// assert_eq!(ts.to_chrono().unwrap().timestamp(), 1706698800);
+    /// ```
+    pub fn to_chrono(&self) -> Result<chrono::DateTime<chrono::FixedOffset>, chrono::ParseError> {
+        chrono::DateTime::parse_from_rfc3339(&self.0)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<Tz: chrono::TimeZone> From<chrono::DateTime<Tz>> for Timestamp
+where
+    Tz::Offset: std::fmt::Display,
+{
+    fn from(value: chrono::DateTime<Tz>) -> Self {
+        Self(value.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true))
+    }
+}
+
+#[cfg(feature = "time")]
+impl Timestamp {
+    /// The timestamp as a time date-time, keeping its offset.
+    ///
+    /// ```
+    /// use zarrs_conventions_timestamps::Timestamp;
+    ///
+    /// let ts = Timestamp::new("2024-01-31T12:00:00+01:00").unwrap();
+    /// assert_eq!(ts.to_time().unwrap().unix_timestamp(), 1706698800);
+    /// ```
+    pub fn to_time(&self) -> Result<time::OffsetDateTime, time::error::Parse> {
+        time::OffsetDateTime::parse(&self.0, &time::format_description::well_known::Rfc3339)
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<time::OffsetDateTime> for Timestamp {
+    type Error = time::error::Format;
+
+    /// Fails for years which cannot be written in four digits.
+    fn try_from(value: time::OffsetDateTime) -> Result<Self, Self::Error> {
+        value
+            .format(&time::format_description::well_known::Rfc3339)
+            .map(Self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Timestamp;
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono() {
+        use chrono::{FixedOffset, TimeZone, Utc};
+
+        let dt = FixedOffset::east_opt(-5 * 3600)
+            .unwrap()
+            .with_ymd_and_hms(2024, 2, 29, 12, 30, 0)
+            .unwrap();
+        let ts = Timestamp::from(dt);
+        assert_eq!(ts.as_str(), "2024-02-29T12:30:00-05:00");
+        assert_eq!(ts.to_chrono().unwrap(), dt);
+        assert_eq!(ts.instant().unwrap().0, dt.timestamp());
+
+        let utc = Timestamp::from(Utc.timestamp_opt(0, 5_000_000).unwrap());
+        assert_eq!(utc.as_str(), "1970-01-01T00:00:00.005Z");
+        assert_eq!(utc.instant(), Ok((0, 5_000_000)));
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn time() {
+        let dt = time::OffsetDateTime::from_unix_timestamp(1709229600)
+            .unwrap()
+            .to_offset(time::UtcOffset::from_hms(-5, -30, 0).unwrap());
+        let ts = Timestamp::try_from(dt).unwrap();
+        assert_eq!(ts.as_str(), "2024-02-29T12:30:00-05:30");
+        assert_eq!(ts.to_time().unwrap(), dt);
+        assert_eq!(ts.instant(), Ok((1709229600, 0)));
+    }
+}
@@ -0,0 +1,33 @@
+use serde_json::json;
+use zarrs_conventions::test_util::{parse_example, parse_modified_example, parse_nested};
+use zarrs_conventions_timestamps::Timestamps;
+
+const EXAMPLE: &str = include_str!("examples/published.json");
+
+zarrs_conventions::convention_test_suite! {
+    Timestamps,
+    repr = nested,
+    example = parse_example(EXAMPLE, parse_nested),
+    examples = "tests/examples",
+    schema = "spec/schema.json",
+    registered = false,
+}
+
+#[test]
+fn reject_non_string_timestamp() {
+    let parsed = parse_modified_example(
+        EXAMPLE,
+        |attrs| attrs["timestamps"]["created"] = json!(20240131),
+        parse_nested::<Timestamps>,
+    );
+    assert!(parsed.is_err());
+}
+
+#[cfg(feature = "schema")]
+#[test]
+fn spec_up_to_date() {
+    zarrs_conventions::test_util::assert_docs_up_to_date(
+        &zarrs_conventions::docgen::ConventionDocs::nested::<Timestamps>(),
+        concat!(env!("CARGO_MANIFEST_DIR"), "/spec"),
+    );
+}
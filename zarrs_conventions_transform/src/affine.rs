@@ -0,0 +1,198 @@
+//! Affine matrices in homogeneous coordinates, to which all transforms can be reduced.
+use serde::{Deserialize, Serialize};
+
+/// Why a matrix is not an affine transform.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("affine matrix with {rows} rows must have {} columns in every row", rows + 1)]
+pub struct ShapeError {
+    pub rows: usize,
+}
+
+/// Affine transform of rank `n`, as an `n` by `n + 1` matrix:
+/// the linear part followed by a column of translations.
+///
+/// ```
+/// use zarrs_conventions_transform::Affine;
+///
+/// let affine = Affine::new(vec![vec![2.0, 0.0, 1.0], vec![0.0, 2.0, 0.0]]).unwrap();
+/// assert_eq!(affine.apply(&[1.0, 1.0]), [3.0, 2.0]);
+/// assert_eq!(affine.inverse().unwrap().apply(&[3.0, 2.0]), [1.0, 1.0]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(try_from = "Vec<Vec<f64>>", into = "Vec<Vec<f64>>")]
+pub struct Affine {
+    rows: Vec<Vec<f64>>,
+}
+
+impl Affine {
+    pub fn new(rows: Vec<Vec<f64>>) -> Result<Self, ShapeError> {
+        let n = rows.len();
+        if rows.iter().any(|r| r.len() != n + 1) {
+            return Err(ShapeError { rows: n });
+        }
+        Ok(Self { rows })
+    }
+
+    pub fn identity(rank: usize) -> Self {
+        Self::from_scale(&vec![1.0; rank])
+    }
+
+    /// Scaling along each axis.
+    pub fn from_scale(scale: &[f64]) -> Self {
+        let rows = (0..scale.len())
+            .map(|i| {
+                let mut row = vec![0.0; scale.len() + 1];
+                row[i] = scale[i];
+                row
+            })
+            .collect();
+        Self { rows }
+    }
+
+    /// Translation along each axis.
+    pub fn from_translation(translation: &[f64]) -> Self {
+        let mut affine = Self::identity(translation.len());
+        for (row, t) in affine.rows.iter_mut().zip(translation) {
+            row[translation.len()] = *t;
+        }
+        affine
+    }
+
+    /// Number of dimensions transformed.
+    pub fn rank(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn rows(&self) -> &[Vec<f64>] {
+        &self.rows
+    }
+
+    /// Transform a point.
+    ///
+    /// # Panics
+    ///
+    /// If the point's length is not the rank of the transform.
+    pub fn apply(&self, point: &[f64]) -> Vec<f64> {
+        assert_eq!(point.len(), self.rank(), "point has the wrong rank");
+        self.rows
+            .iter()
+            .map(|row| row.iter().zip(point).map(|(a, x)| a * x).sum::<f64>() + row[point.len()])
+            .collect()
+    }
+
+    /// The transform applying this one and then `next`.
+    ///
+    /// # Panics
+    ///
+    /// If the transforms have different ranks.
+    pub fn then(&self, next: &Affine) -> Affine {
+        let n = self.rank();
+        assert_eq!(n, next.rank(), "transforms have different ranks");
+        let rows = next
+            .rows
+            .iter()
+            .map(|next_row| {
+                (0..=n)
+                    .map(|j| {
+                        let linear: f64 = (0..n).map(|k| next_row[k] * self.rows[k][j]).sum();
+                        if j == n { linear + next_row[n] } else { linear }
+                    })
+                    .collect()
+            })
+            .collect();
+        Affine { rows }
+    }
+
+    /// The transform undoing this one, if it is invertible.
+    pub fn inverse(&self) -> Option<Affine> {
+        let n = self.rank();
+        // Gauss-Jordan elimination on the linear part, augmented with the identity
+        let mut m: Vec<Vec<f64>> = self
+            .rows
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let mut augmented = row[..n].to_vec();
+                augmented.extend((0..n).map(|j| if i == j { 1.0 } else { 0.0 }));
+                augmented
+            })
+            .collect();
+        for col in 0..n {
+            let pivot = (col..n).max_by(|a, b| m[*a][col].abs().total_cmp(&m[*b][col].abs()))?;
+            if m[pivot][col].abs() < f64::EPSILON {
+                return None;
+            }
+            m.swap(col, pivot);
+            let scale = m[col][col];
+            m[col].iter_mut().for_each(|v| *v /= scale);
+            let pivot_row = m[col].clone();
+            for (i, row) in m.iter_mut().enumerate() {
+                if i != col {
+                    let factor = row[col];
+                    for (v, p) in row.iter_mut().zip(&pivot_row) {
+                        *v -= factor * p;
+                    }
+                }
+            }
+        }
+        let rows = m
+            .into_iter()
+            .map(|row| {
+                let mut inverse = row[n..].to_vec();
+                let translation = inverse.iter().zip(&self.rows).map(|(a, r)| -a * r[n]).sum();
+                inverse.push(translation);
+                inverse
+            })
+            .collect();
+        Some(Affine { rows })
+    }
+
+    /// Whether the transform can be undone.
+    pub fn is_invertible(&self) -> bool {
+        self.inverse().is_some()
+    }
+}
+
+impl TryFrom<Vec<Vec<f64>>> for Affine {
+    type Error = ShapeError;
+
+    fn try_from(value: Vec<Vec<f64>>) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<Affine> for Vec<Vec<f64>> {
+    fn from(value: Affine) -> Self {
+        value.rows
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Affine, ShapeError};
+
+    fn close(a: &[f64], b: &[f64]) -> bool {
+        a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-9)
+    }
+
+    #[test]
+    fn algebra() {
+        let scale = Affine::from_scale(&[2.0, 3.0]);
+        let shift = Affine::from_translation(&[1.0, -1.0]);
+        assert_eq!(scale.then(&shift).apply(&[1.0, 1.0]), [3.0, 2.0]);
+        assert_eq!(shift.then(&scale).apply(&[1.0, 1.0]), [4.0, 0.0]);
+
+        let rotation = Affine::new(vec![vec![0.0, -1.0, 5.0], vec![1.0, 0.0, 0.0]]).unwrap();
+        let composed = rotation.then(&scale).then(&shift);
+        let round_trip = composed.then(&composed.inverse().unwrap());
+        assert!(close(&round_trip.apply(&[7.0, -2.0]), &[7.0, -2.0]));
+
+        assert!(!Affine::from_scale(&[1.0, 0.0]).is_invertible());
+        assert_eq!(
+            Affine::new(vec![vec![1.0]]).unwrap_err(),
+            ShapeError { rows: 1 }
+        );
+        assert!(serde_json::from_str::<Affine>("[[1.0, 2.0, 3.0]]").is_err());
+    }
+}
@@ -0,0 +1,385 @@
+#![doc = include_str!("../README.md")]
+use serde::{Deserialize, Serialize};
+pub use zarrs_conventions;
+use zarrs_conventions::{
+    define_convention,
+    validate::{Issue, Lint},
+};
+
+mod affine;
+pub mod ome;
+pub use affine::{Affine, ShapeError};
+
+/// A single step of a [CoordinateTransform].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum Transform {
+    /// Leaves coordinates unchanged, for any rank.
+    Identity,
+    /// Multiplies each coordinate by a factor.
+    Scale { scale: Vec<f64> },
+    /// Adds an offset to each coordinate.
+    Translation { translation: Vec<f64> },
+    /// Applies an affine matrix.
+    Affine { affine: Affine },
+}
+
+impl Transform {
+    pub fn scale(scale: impl IntoIterator<Item = f64>) -> Self {
+        Self::Scale {
+            scale: scale.into_iter().collect(),
+        }
+    }
+
+    pub fn translation(translation: impl IntoIterator<Item = f64>) -> Self {
+        Self::Translation {
+            translation: translation.into_iter().collect(),
+        }
+    }
+
+    pub fn affine(affine: Affine) -> Self {
+        Self::Affine { affine }
+    }
+
+    /// Number of dimensions transformed, or None for the identity, which fits any rank.
+    pub fn rank(&self) -> Option<usize> {
+        match self {
+            Self::Identity => None,
+            Self::Scale { scale } => Some(scale.len()),
+            Self::Translation { translation } => Some(translation.len()),
+            Self::Affine { affine } => Some(affine.rank()),
+        }
+    }
+
+    /// The transform as an affine matrix of the given rank,
+    /// if that is the transform's rank.
+    pub fn to_affine(&self, rank: usize) -> Option<Affine> {
+        if self.rank().is_some_and(|r| r != rank) {
+            return None;
+        }
+        Some(match self {
+            Self::Identity => Affine::identity(rank),
+            Self::Scale { scale } => Affine::from_scale(scale),
+            Self::Translation { translation } => Affine::from_translation(translation),
+            Self::Affine { affine } => affine.clone(),
+        })
+    }
+
+    /// Transform a point.
+    ///
+    /// # Panics
+    ///
+    /// If the point's length is not the rank of the transform.
+    pub fn apply(&self, point: &[f64]) -> Vec<f64> {
+        match self {
+            Self::Identity => point.to_vec(),
+            Self::Scale { scale } => {
+                assert_eq!(point.len(), scale.len(), "point has the wrong rank");
+                point.iter().zip(scale).map(|(x, s)| x * s).collect()
+            }
+            Self::Translation { translation } => {
+                assert_eq!(point.len(), translation.len(), "point has the wrong rank");
+                point.iter().zip(translation).map(|(x, t)| x + t).collect()
+            }
+            Self::Affine { affine } => affine.apply(point),
+        }
+    }
+
+    /// The transform undoing this one, if it is invertible.
+    pub fn inverse(&self) -> Option<Self> {
+        Some(match self {
+            Self::Identity => Self::Identity,
+            Self::Scale { scale } => {
+                if scale.contains(&0.0) {
+                    return None;
+                }
+                Self::scale(scale.iter().map(|s| 1.0 / s))
+            }
+            Self::Translation { translation } => Self::translation(translation.iter().map(|t| -t)),
+            Self::Affine { affine } => Self::affine(affine.inverse()?),
+        })
+    }
+}
+
+/// Why a transform does not fit an array.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum RankError {
+    #[error("transform {index} has rank {found}, but rank {expected} is expected")]
+    Transform {
+        index: usize,
+        expected: usize,
+        found: usize,
+    },
+    #[error("{found} axes are named, but rank {expected} is expected")]
+    Axes { expected: usize, found: usize },
+}
+
+define_convention! {
+    /// Mapping of an array's indices to coordinates in physical space,
+    /// as a sequence of transforms applied in order.
+    #[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+    #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+    pub struct CoordinateTransform {
+        /// Names of the output axes, in order.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        axes: Option<Vec<String>>,
+        /// Transforms, in the order they are applied.
+        transforms: Vec<Transform>,
+    }
+    schema_url = "https://raw.githubusercontent.com/clbarnes/zarrs_conventions/refs/tags/transform-v1/zarrs_conventions_transform/spec/schema.json",
+    spec_url = "https://github.com/clbarnes/zarrs_conventions/blob/transform-v1/zarrs_conventions_transform/spec/README.md",
+    name = "transform",
+    description = "Mapping of array indices to physical coordinates",
+    nested = "transform",
+    register = false,
+}
+
+impl CoordinateTransform {
+    pub fn builder() -> Builder {
+        Default::default()
+    }
+
+    /// Names of the output axes, in order, if given.
+    pub fn axes(&self) -> Option<&[String]> {
+        self.axes.as_deref()
+    }
+
+    /// Transforms, in the order they are applied.
+    pub fn transforms(&self) -> &[Transform] {
+        &self.transforms
+    }
+
+    /// Rank of the transforms and axes, if any have one,
+    /// or which transform does not match the others.
+    pub fn rank(&self) -> Result<Option<usize>, RankError> {
+        let mut rank = self.axes.as_ref().map(Vec::len);
+        for (index, transform) in self.transforms.iter().enumerate() {
+            match (rank, transform.rank()) {
+                (None, found) => rank = found,
+                (Some(expected), Some(found)) if expected != found => {
+                    return Err(RankError::Transform {
+                        index,
+                        expected,
+                        found,
+                    });
+                }
+                _ => (),
+            }
+        }
+        Ok(rank)
+    }
+
+    /// Check that the axes and every transform fit an array of the given rank.
+    pub fn validate_rank(&self, rank: usize) -> Result<(), RankError> {
+        if let Some(axes) = &self.axes
+            && axes.len() != rank
+        {
+            return Err(RankError::Axes {
+                expected: rank,
+                found: axes.len(),
+            });
+        }
+        for (index, transform) in self.transforms.iter().enumerate() {
+            if let Some(found) = transform.rank()
+                && found != rank
+            {
+                return Err(RankError::Transform {
+                    index,
+                    expected: rank,
+                    found,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Transform a point, e.g. an array index.
+    ///
+    /// # Panics
+    ///
+    /// If the point's length is not the rank of every transform.
+    pub fn apply(&self, point: &[f64]) -> Vec<f64> {
+        self.transforms
+            .iter()
+            .fold(point.to_vec(), |p, t| t.apply(&p))
+    }
+
+    /// All of the transforms composed into a single affine matrix of the given rank.
+    pub fn to_affine(&self, rank: usize) -> Result<Affine, RankError> {
+        self.validate_rank(rank)?;
+        Ok(self
+            .transforms
+            .iter()
+            .filter_map(|t| t.to_affine(rank))
+            .fold(Affine::identity(rank), |acc, t| acc.then(&t)))
+    }
+
+    /// The transform applying this one and then `next`,
+    /// whose axes are those of the output.
+    pub fn then(&self, next: &CoordinateTransform) -> CoordinateTransform {
+        CoordinateTransform {
+            axes: next.axes.clone().or_else(|| self.axes.clone()),
+            transforms: self
+                .transforms
+                .iter()
+                .chain(&next.transforms)
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// The transform from physical coordinates back to array indices, if every step is invertible.
+    ///
+    /// The array's axes are not named, so the inverse has no axes.
+    pub fn inverse(&self) -> Option<CoordinateTransform> {
+        Some(CoordinateTransform {
+            axes: None,
+            transforms: self
+                .transforms
+                .iter()
+                .rev()
+                .map(Transform::inverse)
+                .collect::<Option<_>>()?,
+        })
+    }
+}
+
+impl Lint for CoordinateTransform {
+    fn lint(&self) -> Vec<Issue> {
+        let mut issues = Vec::default();
+        if let Err(e) = self.rank() {
+            issues.push(Issue::error("inconsistent-rank", e.to_string()));
+        }
+        for (index, transform) in self.transforms.iter().enumerate() {
+            if transform.inverse().is_none() {
+                issues.push(Issue::warning(
+                    "singular-transform",
+                    format!("transform {index} cannot be inverted"),
+                ));
+            }
+        }
+        issues
+    }
+}
+
+/// Builder for [CoordinateTransform], created by [CoordinateTransform::builder].
+#[derive(Debug, Clone, Default)]
+pub struct Builder {
+    inner: CoordinateTransform,
+}
+
+impl Builder {
+    /// Names of the output axes, in order.
+    pub fn axes(mut self, axes: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.inner.axes = Some(axes.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Add a transform, applied after those already added.
+    pub fn then(mut self, transform: Transform) -> Self {
+        self.inner.transforms.push(transform);
+        self
+    }
+
+    /// Build the transform, checking that the axes and transforms have the same rank.
+    pub fn build(self) -> Result<CoordinateTransform, RankError> {
+        self.inner.rank()?;
+        Ok(self.inner)
+    }
+
+    /// Build the transform, checking that it fits an array of the given rank.
+    pub fn build_for_rank(self, rank: usize) -> Result<CoordinateTransform, RankError> {
+        self.inner.validate_rank(rank)?;
+        Ok(self.inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use zarrs_conventions::validate::Lint;
+
+    use crate::{Affine, CoordinateTransform, RankError, Transform};
+
+    #[test]
+    fn serde() {
+        let transform = CoordinateTransform::builder()
+            .axes(["y", "x"])
+            .then(Transform::scale([2.0, 2.0]))
+            .then(Transform::Identity)
+            .then(Transform::affine(Affine::identity(2)))
+            .build()
+            .unwrap();
+        let value = serde_json::to_value(&transform).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "axes": ["y", "x"],
+                "transforms": [
+                    {"type": "scale", "scale": [2.0, 2.0]},
+                    {"type": "identity"},
+                    {"type": "affine", "affine": [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0]]},
+                ]
+            })
+        );
+        assert_eq!(
+            serde_json::from_value::<CoordinateTransform>(value).unwrap(),
+            transform
+        );
+    }
+
+    #[test]
+    fn ranks() {
+        let builder = CoordinateTransform::builder()
+            .then(Transform::scale([1.0, 2.0, 3.0]))
+            .then(Transform::translation([1.0, 2.0]));
+        assert_eq!(
+            builder.clone().build().unwrap_err(),
+            RankError::Transform {
+                index: 1,
+                expected: 3,
+                found: 2
+            }
+        );
+        assert_eq!(
+            CoordinateTransform::builder()
+                .axes(["x"])
+                .build_for_rank(2)
+                .unwrap_err(),
+            RankError::Axes {
+                expected: 2,
+                found: 1
+            }
+        );
+        let transform: CoordinateTransform = serde_json::from_value(json!({
+            "transforms": [{"type": "scale", "scale": [0.0, 1.0]}, {"type": "translation", "translation": [1.0]}]
+        }))
+        .unwrap();
+        let codes: Vec<_> = transform.lint().into_iter().map(|i| i.code).collect();
+        assert_eq!(codes, ["inconsistent-rank", "singular-transform"]);
+    }
+
+    #[test]
+    fn composition() {
+        let a = CoordinateTransform::builder()
+            .then(Transform::scale([2.0, 4.0]))
+            .build()
+            .unwrap();
+        let b = CoordinateTransform::builder()
+            .axes(["y", "x"])
+            .then(Transform::translation([-1.0, 1.0]))
+            .build()
+            .unwrap();
+        let composed = a.then(&b);
+        assert_eq!(composed.axes().unwrap(), ["y", "x"]);
+        assert_eq!(composed.apply(&[1.0, 1.0]), [1.0, 5.0]);
+        assert_eq!(
+            composed.to_affine(2).unwrap().apply(&[1.0, 1.0]),
+            [1.0, 5.0]
+        );
+        assert_eq!(composed.inverse().unwrap().apply(&[1.0, 5.0]), [1.0, 1.0]);
+        assert!(composed.to_affine(3).is_err());
+    }
+}
@@ -0,0 +1,95 @@
+//! Conversion to and from the `coordinateTransformations` of
+//! [OME-NGFF](https://ngff.openmicroscopy.org/latest/#trafo-md) multiscale datasets.
+//!
+//! OME-NGFF 0.4 only allows a scale, optionally followed by a translation;
+//! identity and affine transforms are from later versions.
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::{CoordinateTransform, Transform};
+
+/// Why OME-NGFF coordinate transformations could not be read.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum OmeError {
+    #[error("'coordinateTransformations' is not an array")]
+    NotAnArray,
+    #[error("OME-NGFF transform type '{0}' is not supported")]
+    Unsupported(String),
+    #[error("OME-NGFF transform parameters stored at a path are not supported")]
+    Path,
+    #[error(transparent)]
+    Deserialize(#[from] serde_json::Error),
+}
+
+impl CoordinateTransform {
+    /// Read the value of an OME-NGFF `coordinateTransformations` field.
+    pub fn from_ome(transformations: &Value) -> Result<Self, OmeError> {
+        let items = transformations.as_array().ok_or(OmeError::NotAnArray)?;
+        let transforms = items
+            .iter()
+            .map(|item| {
+                if item.get("path").is_some() {
+                    return Err(OmeError::Path);
+                }
+                match item.get("type").and_then(Value::as_str) {
+                    Some("identity" | "scale" | "translation" | "affine") => {
+                        Ok(Transform::deserialize(item)?)
+                    }
+                    other => Err(OmeError::Unsupported(other.unwrap_or_default().into())),
+                }
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            axes: None,
+            transforms,
+        })
+    }
+
+    /// The transforms as the value of an OME-NGFF `coordinateTransformations` field;
+    /// axes are described separately in OME-NGFF.
+    pub fn to_ome(&self) -> Value {
+        serde_json::to_value(&self.transforms).expect("transforms should serialize")
+    }
+
+    /// Whether the transforms can be written for OME-NGFF 0.4,
+    /// i.e. they are a single scale, optionally followed by a single translation.
+    pub fn is_ome_v04_compatible(&self) -> bool {
+        matches!(
+            self.transforms.as_slice(),
+            [Transform::Scale { .. }] | [Transform::Scale { .. }, Transform::Translation { .. }]
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::OmeError;
+    use crate::CoordinateTransform;
+
+    #[test]
+    fn ome() {
+        let ome = json!([
+            {"type": "scale", "scale": [1.0, 0.5, 0.5]},
+            {"type": "translation", "translation": [0.0, 10.0, 10.0]},
+        ]);
+        let transform = CoordinateTransform::from_ome(&ome).unwrap();
+        assert!(transform.is_ome_v04_compatible());
+        assert_eq!(transform.to_ome(), ome);
+
+        let affine = json!([{"type": "affine", "affine": [[1.0, 0.0, 2.0], [0.0, 1.0, 0.0]]}]);
+        let transform = CoordinateTransform::from_ome(&affine).unwrap();
+        assert!(!transform.is_ome_v04_compatible());
+
+        assert!(matches!(
+            CoordinateTransform::from_ome(&json!([{"type": "rotation", "rotation": [[1.0]]}])),
+            Err(OmeError::Unsupported(t)) if t == "rotation"
+        ));
+        assert!(matches!(
+            CoordinateTransform::from_ome(&json!([{"type": "scale", "path": "scale.bin"}])),
+            Err(OmeError::Path)
+        ));
+    }
+}
@@ -0,0 +1,37 @@
+use zarrs_conventions::test_util::{parse_example, parse_modified_example, parse_nested};
+use zarrs_conventions_transform::CoordinateTransform;
+
+const EXAMPLE: &str = include_str!("examples/scale_translation.json");
+
+zarrs_conventions::convention_test_suite! {
+    CoordinateTransform,
+    repr = nested,
+    example = parse_example(EXAMPLE, parse_nested),
+    examples = "tests/examples",
+    schema = "spec/schema.json",
+    registered = false,
+}
+
+#[test]
+fn reject_missing_transforms() {
+    let parsed = parse_modified_example(
+        EXAMPLE,
+        |attrs| {
+            attrs["transform"]
+                .as_object_mut()
+                .unwrap()
+                .remove("transforms");
+        },
+        parse_nested::<CoordinateTransform>,
+    );
+    assert!(parsed.is_err());
+}
+
+#[cfg(feature = "schema")]
+#[test]
+fn spec_up_to_date() {
+    zarrs_conventions::test_util::assert_docs_up_to_date(
+        &zarrs_conventions::docgen::ConventionDocs::nested::<CoordinateTransform>(),
+        concat!(env!("CARGO_MANIFEST_DIR"), "/spec"),
+    );
+}
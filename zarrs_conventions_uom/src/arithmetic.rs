@@ -0,0 +1,169 @@
+//! Units of values computed from other values, e.g. `flux = counts / time / area`.
+use crate::{
+    UnitOfMeasurement,
+    ucum::{Component, Op, ParseError, Term, UnitExpr},
+};
+
+/// Why units could not be combined.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[non_exhaustive]
+pub enum ArithmeticError {
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+    #[error("no unit is declared")]
+    Undeclared,
+}
+
+/// The canonical product of the expressions, each multiplied or divided in turn.
+fn combine<'a>(terms: impl IntoIterator<Item = (Op, &'a UnitExpr)>) -> UnitExpr {
+    let terms = terms
+        .into_iter()
+        .map(|(op, expr)| Term {
+            op,
+            component: Component::Group(expr.clone()),
+        })
+        .collect();
+    UnitExpr { terms }.canonicalize()
+}
+
+impl UnitExpr {
+    /// The unit of the product of values in this unit and the other.
+    ///
+    /// ```
+    /// use zarrs_conventions_uom::ucum::UnitExpr;
+    ///
+    /// let n: UnitExpr = "N".parse().unwrap();
+    /// assert_eq!(n.multiply(&"m".parse().unwrap()).to_string(), "N.m");
+    /// ```
+    pub fn multiply(&self, other: &UnitExpr) -> UnitExpr {
+        combine([(Op::Multiply, self), (Op::Multiply, other)])
+    }
+
+    /// The unit of the quotient of values in this unit by values in the other.
+    ///
+    /// ```
+    /// use zarrs_conventions_uom::ucum::UnitExpr;
+    ///
+    /// let m: UnitExpr = "m".parse().unwrap();
+    /// assert_eq!(m.divide(&"s2".parse().unwrap()).to_string(), "m/s2");
+    /// ```
+    pub fn divide(&self, other: &UnitExpr) -> UnitExpr {
+        combine([(Op::Multiply, self), (Op::Divide, other)])
+    }
+
+    /// The unit of values in this unit raised to a power.
+    ///
+    /// ```
+    /// use zarrs_conventions_uom::ucum::UnitExpr;
+    ///
+    /// let m: UnitExpr = "m/s".parse().unwrap();
+    /// assert_eq!(m.pow(2).to_string(), "m2/s2");
+    /// assert_eq!(m.pow(-1).to_string(), "s/m");
+    /// ```
+    pub fn pow(&self, exponent: i32) -> UnitExpr {
+        let op = if exponent < 0 {
+            Op::Divide
+        } else {
+            Op::Multiply
+        };
+        combine((0..exponent.unsigned_abs()).map(|_| (op, self)))
+    }
+}
+
+/// Combine the declared units, keeping the UCUM version if they share one.
+fn combine_declared(
+    units: &[&UnitOfMeasurement],
+    f: impl FnOnce(&[UnitExpr]) -> UnitExpr,
+) -> Result<UnitOfMeasurement, ArithmeticError> {
+    let exprs = units
+        .iter()
+        .map(|uom| Ok(uom.ucum().parsed().ok_or(ArithmeticError::Undeclared)??))
+        .collect::<Result<Vec<_>, ArithmeticError>>()?;
+    let mut builder = UnitOfMeasurement::builder().unit(f(&exprs).to_string());
+    let version = units[0].ucum().version();
+    if let Some(version) = version
+        && units
+            .iter()
+            .all(|uom| uom.ucum().version() == Some(version))
+    {
+        builder = builder.version(version);
+    }
+    Ok(builder.build())
+}
+
+impl UnitOfMeasurement {
+    /// The [canonical](UnitExpr::canonicalize) unit of the product of values in the declared units.
+    ///
+    /// Descriptions and other metadata of the units are not kept.
+    pub fn multiply(&self, other: &UnitOfMeasurement) -> Result<Self, ArithmeticError> {
+        combine_declared(&[self, other], |e| e[0].multiply(&e[1]))
+    }
+
+    /// The [canonical](UnitExpr::canonicalize) unit of the quotient of values in the declared units.
+    ///
+    /// Descriptions and other metadata of the units are not kept.
+    ///
+    /// ```
+    /// use zarrs_conventions_uom::UnitOfMeasurement;
+    ///
+    /// let counts = UnitOfMeasurement::builder().unit("{counts}").build();
+    /// let flux = counts
+    ///     .divide(&UnitOfMeasurement::SECOND)
+    ///     .and_then(|u| u.divide(&UnitOfMeasurement::builder().unit("cm2").build()))
+    ///     .unwrap();
+    /// assert_eq!(flux.ucum().unit(), Some("{counts}/cm2/s"));
+    /// ```
+    pub fn divide(&self, other: &UnitOfMeasurement) -> Result<Self, ArithmeticError> {
+        combine_declared(&[self, other], |e| e[0].divide(&e[1]))
+    }
+
+    /// The [canonical](UnitExpr::canonicalize) unit of values in the declared unit raised to a power.
+    ///
+    /// Descriptions and other metadata of the unit are not kept.
+    pub fn pow(&self, exponent: i32) -> Result<Self, ArithmeticError> {
+        combine_declared(&[self], |e| e[0].pow(exponent))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ArithmeticError;
+    use crate::{UnitOfMeasurement, ucum::UnitExpr};
+
+    fn expr(unit: &str) -> UnitExpr {
+        unit.parse().unwrap()
+    }
+
+    #[test]
+    fn unit_arithmetic() {
+        assert_eq!(expr("m").multiply(&expr("m")).to_string(), "m2");
+        assert_eq!(expr("kg.m").divide(&expr("s2")).to_string(), "kg.m/s2");
+        assert_eq!(expr("m/s").divide(&expr("m/s")).to_string(), "1");
+        assert_eq!(expr("km").multiply(&expr("m")).to_string(), "m.km");
+        assert_eq!(expr("10*3.m").pow(2).to_string(), "10*6.m2");
+        assert_eq!(expr("1000/s").pow(2).to_string(), "1000000/s2");
+        assert_eq!(expr("m").pow(0).to_string(), "1");
+        assert_eq!(expr("/s").pow(-2).to_string(), "s2");
+
+        let versioned = UnitOfMeasurement::builder()
+            .unit("m")
+            .version("2.1")
+            .description("distance")
+            .build();
+        let area = versioned.multiply(&versioned).unwrap();
+        assert_eq!(area.ucum().unit(), Some("m2"));
+        assert_eq!(area.ucum().version(), Some("2.1"));
+        assert_eq!(area.description(), "");
+        let speed = versioned.divide(&UnitOfMeasurement::SECOND).unwrap();
+        assert_eq!(speed.ucum().version(), None);
+
+        assert_eq!(
+            versioned.divide(&UnitOfMeasurement::default()),
+            Err(ArithmeticError::Undeclared)
+        );
+        assert!(matches!(
+            UnitOfMeasurement::builder().unit("UM").build().pow(2),
+            Err(ArithmeticError::Parse(_))
+        ));
+    }
+}
@@ -0,0 +1,165 @@
+//! Reading the values of [zarrs](https://docs.rs/zarrs) arrays converted to another unit.
+use zarrs::{
+    array::{Array, ArraySubset, ElementOwned},
+    storage::{ReadableStorageTraits, ReadableWritableStorageTraits},
+};
+use zarrs_conventions::Attributes;
+
+use crate::{
+    UnitOfMeasurement,
+    values::{ValueConversion, ValuesError},
+};
+
+/// An array whose values are converted to a target unit as they are retrieved,
+/// applying any calibration along with the unit conversion.
+///
+/// ```
+/// # use std::sync::Arc;
+/// use zarrs::{array::{ArrayBuilder, data_type}, storage::store::MemoryStore};
+/// use zarrs_conventions_uom::{UnitOfMeasurement, array::ConvertedArray};
+///
+/// let attributes = serde_json::json!({
+///     "uom": {"ucum": {"unit": "mm"}, "calibration": {"scale": 0.5}}
+/// });
+/// let array = ArrayBuilder::new(vec![4], vec![2], data_type::uint16(), 0u16)
+///     .attributes(attributes.as_object().unwrap().clone())
+///     .build(Arc::new(MemoryStore::new()), "/array")
+///     .unwrap();
+/// array.store_chunk(&[1], vec![2000u16, 4000]).unwrap();
+///
+/// let converted = ConvertedArray::new(&array, UnitOfMeasurement::builder().unit("m").build()).unwrap();
+/// assert_eq!(converted.retrieve_chunk::<u16>(&[1]).unwrap(), [1.0, 2.0]);
+/// ```
+#[derive(Debug)]
+pub struct ConvertedArray<'a, TStorage: ?Sized> {
+    array: &'a Array<TStorage>,
+    conversion: ValueConversion,
+}
+
+impl<'a, TStorage: ?Sized> ConvertedArray<'a, TStorage> {
+    /// Convert the values of an array, which must have uom metadata, to the target unit.
+    pub fn new(array: &'a Array<TStorage>, target: UnitOfMeasurement) -> Result<Self, ValuesError> {
+        let conversion = ValueConversion::from_attributes(array.attributes(), target)?;
+        Ok(Self { array, conversion })
+    }
+
+    /// The array whose values are converted.
+    pub fn array(&self) -> &'a Array<TStorage> {
+        self.array
+    }
+
+    /// Conversion applied to each retrieved value.
+    pub fn conversion(&self) -> &ValueConversion {
+        &self.conversion
+    }
+
+    /// Attributes for an array of the converted values:
+    /// those of the source array, with uom metadata for the target unit.
+    pub fn converted_attributes(&self) -> Result<Attributes, ValuesError> {
+        let mut attributes = self.array.attributes().clone();
+        self.conversion.write_attributes(&mut attributes)?;
+        Ok(attributes)
+    }
+}
+
+impl<TStorage: ?Sized + ReadableStorageTraits + 'static> ConvertedArray<'_, TStorage> {
+    /// Retrieve a chunk, with elements of the array's type `T`, as converted values.
+    pub fn retrieve_chunk<T: ElementOwned + Copy + Into<f64>>(
+        &self,
+        chunk_indices: &[u64],
+    ) -> Result<Vec<f64>, ValuesError> {
+        let raw: Vec<T> = self.array.retrieve_chunk(chunk_indices)?;
+        Ok(self.conversion.convert(&raw))
+    }
+
+    /// Retrieve a subset of the array, with elements of the array's type `T`, as converted values.
+    pub fn retrieve_array_subset<T: ElementOwned + Copy + Into<f64>>(
+        &self,
+        array_subset: &ArraySubset,
+    ) -> Result<Vec<f64>, ValuesError> {
+        let raw: Vec<T> = self.array.retrieve_array_subset(array_subset)?;
+        Ok(self.conversion.convert(&raw))
+    }
+
+    /// Write the converted values into a `float64` array of the same shape, chunk by chunk.
+    ///
+    /// The destination's attributes are left to the caller, e.g. from [Self::converted_attributes].
+    pub fn store_converted<T, TDest>(&self, destination: &Array<TDest>) -> Result<(), ValuesError>
+    where
+        T: ElementOwned + Copy + Into<f64>,
+        TDest: ?Sized + ReadableWritableStorageTraits + 'static,
+    {
+        let chunks = ArraySubset::new_with_shape(self.array.chunk_grid_shape().to_vec());
+        for chunk_indices in &chunks.indices() {
+            let subset = self.array.chunk_subset_bounded(&chunk_indices)?;
+            let values = self.retrieve_array_subset::<T>(&subset)?;
+            destination.store_array_subset(&subset, values)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use serde_json::json;
+    use zarrs::{
+        array::{ArrayBuilder, ArraySubset, data_type},
+        storage::store::MemoryStore,
+    };
+    use zarrs_conventions::NestedRepr;
+
+    use super::ConvertedArray;
+    use crate::{UnitOfMeasurement, values::ValuesError};
+
+    #[test]
+    fn convert_array() {
+        let store = Arc::new(MemoryStore::new());
+        let source = json!({"uom": {"ucum": {"unit": "Cel"}, "calibration": {"scale": 0.5}}});
+        let array = ArrayBuilder::new(vec![3, 3], vec![2, 2], data_type::uint8(), 0u8)
+            .attributes(source.as_object().unwrap().clone())
+            .build(store.clone(), "/source")
+            .unwrap();
+        array
+            .store_array_subset(&array.subset_all(), (0..9u8).collect::<Vec<_>>())
+            .unwrap();
+
+        let kelvin = UnitOfMeasurement::builder().unit("K").build();
+        let converted = ConvertedArray::new(&array, kelvin.clone()).unwrap();
+        let expected = |raw: u8| f64::from(raw) * 0.5 + 273.15;
+        assert_eq!(
+            converted.retrieve_chunk::<u8>(&[0, 0]).unwrap(),
+            [0, 1, 3, 4].map(expected)
+        );
+        assert_eq!(
+            converted
+                .retrieve_array_subset::<u8>(&ArraySubset::new_with_ranges(&[2..3, 0..3]))
+                .unwrap(),
+            [6, 7, 8].map(expected)
+        );
+
+        let copy = ArrayBuilder::new(vec![3, 3], vec![3, 3], data_type::float64(), f64::NAN)
+            .attributes(converted.converted_attributes().unwrap())
+            .build(store.clone(), "/kelvin")
+            .unwrap();
+        converted.store_converted::<u8, _>(&copy).unwrap();
+        assert_eq!(
+            copy.retrieve_array_subset::<Vec<f64>>(&copy.subset_all())
+                .unwrap(),
+            (0..9u8).map(expected).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            UnitOfMeasurement::from_attributes_nested(copy.attributes()).unwrap(),
+            kelvin
+        );
+
+        let unlabelled = ArrayBuilder::new(vec![1], vec![1], data_type::uint8(), 0u8)
+            .build(store, "/unlabelled")
+            .unwrap();
+        assert!(matches!(
+            ConvertedArray::new(&unlabelled, UnitOfMeasurement::MILLIMETER),
+            Err(ValuesError::Attributes(_))
+        ));
+    }
+}
@@ -0,0 +1,164 @@
+//! Units for each axis of an array, rather than for its values.
+use serde::{Deserialize, Serialize};
+use zarrs_conventions::validate::{Issue, Lint};
+
+use crate::UnitOfMeasurement;
+
+/// Why per-axis units do not fit an array.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("{found} axis units are given for an array with {expected} dimensions")]
+pub struct RankError {
+    pub expected: usize,
+    pub found: usize,
+}
+
+/// One unit per axis of an array, in axis order;
+/// axes without a unit (e.g. channels, or a categorical axis) are `null`.
+///
+/// ```
+/// use zarrs_conventions_uom::{UnitOfMeasurement, axes::DimensionUnits};
+///
+/// let units = DimensionUnits::builder()
+///     .unitless_axis()
+///     .axis(UnitOfMeasurement::builder().unit("um"))
+///     .axis(UnitOfMeasurement::builder().unit("um"))
+///     .build();
+/// assert_eq!(units.get(1).and_then(|u| u.ucum().unit()), Some("um"));
+/// assert!(units.get(0).is_none());
+/// assert!(units.validate_rank(3).is_ok());
+/// assert!(units.validate_rank(2).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(transparent)]
+pub struct DimensionUnits(Vec<Option<UnitOfMeasurement>>);
+
+impl DimensionUnits {
+    pub fn builder() -> DimensionUnitsBuilder {
+        Default::default()
+    }
+
+    /// Number of axes described.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Unit of the given axis, if it has one.
+    pub fn get(&self, axis: usize) -> Option<&UnitOfMeasurement> {
+        self.0.get(axis)?.as_ref()
+    }
+
+    /// Unit of each axis, in order.
+    pub fn iter(&self) -> impl Iterator<Item = Option<&UnitOfMeasurement>> {
+        self.0.iter().map(Option::as_ref)
+    }
+
+    /// Check that there is one entry for each dimension of an array with the given rank.
+    pub fn validate_rank(&self, rank: usize) -> Result<(), RankError> {
+        if self.0.len() == rank {
+            Ok(())
+        } else {
+            Err(RankError {
+                expected: rank,
+                found: self.0.len(),
+            })
+        }
+    }
+}
+
+impl From<Vec<Option<UnitOfMeasurement>>> for DimensionUnits {
+    fn from(value: Vec<Option<UnitOfMeasurement>>) -> Self {
+        Self(value)
+    }
+}
+
+impl Lint for DimensionUnits {
+    fn lint(&self) -> Vec<Issue> {
+        self.0
+            .iter()
+            .enumerate()
+            .filter_map(|(axis, unit)| Some((axis, unit.as_ref()?)))
+            .flat_map(|(axis, unit)| {
+                unit.lint().into_iter().map(move |issue| Issue {
+                    message: format!("axis {axis}: {}", issue.message),
+                    ..issue
+                })
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct DimensionUnitsBuilder {
+    units: Vec<Option<UnitOfMeasurement>>,
+}
+
+impl DimensionUnitsBuilder {
+    /// Add an axis with the given unit.
+    pub fn axis(mut self, unit: impl Into<UnitOfMeasurement>) -> Self {
+        self.units.push(Some(unit.into()));
+        self
+    }
+
+    /// Add an axis with no unit.
+    pub fn unitless_axis(mut self) -> Self {
+        self.units.push(None);
+        self
+    }
+
+    /// Build the units, checking that there is one for each dimension of an array with the given rank.
+    pub fn build_for_rank(self, rank: usize) -> Result<DimensionUnits, RankError> {
+        let units = self.build();
+        units.validate_rank(rank)?;
+        Ok(units)
+    }
+
+    pub fn build(self) -> DimensionUnits {
+        DimensionUnits(self.units)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use zarrs_conventions::validate::Lint;
+
+    use super::{DimensionUnits, RankError};
+    use crate::UnitOfMeasurement;
+
+    #[test]
+    fn dimension_units() {
+        let units = DimensionUnits::builder()
+            .axis(UnitOfMeasurement::builder().unit("s"))
+            .unitless_axis()
+            .axis(UnitOfMeasurement::builder().unit("UM"))
+            .build();
+        assert_eq!(
+            serde_json::to_value(&units).unwrap(),
+            json!([{"ucum": {"unit": "s"}}, null, {"ucum": {"unit": "UM"}}])
+        );
+        let round_trip: DimensionUnits =
+            serde_json::from_value(serde_json::to_value(&units).unwrap()).unwrap();
+        assert_eq!(round_trip.get(2).unwrap().ucum().unit(), Some("UM"));
+        assert_eq!(units.iter().filter(Option::is_some).count(), 2);
+
+        let issues = units.lint();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].code, "invalid-ucum");
+        assert!(issues[0].message.starts_with("axis 2: "));
+
+        assert_eq!(
+            DimensionUnits::builder()
+                .unitless_axis()
+                .build_for_rank(2)
+                .unwrap_err(),
+            RankError {
+                expected: 2,
+                found: 1
+            }
+        );
+    }
+}
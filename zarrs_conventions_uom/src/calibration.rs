@@ -0,0 +1,132 @@
+//! Mapping of raw stored values, e.g. detector counts, to physical quantities.
+use std::hash::{Hash, Hasher};
+
+use serde::{Deserialize, Serialize};
+
+use crate::UnitOfMeasurement;
+
+fn one() -> f64 {
+    1.0
+}
+
+fn is_one(n: &f64) -> bool {
+    *n == 1.0
+}
+
+fn is_zero(n: &f64) -> bool {
+    *n == 0.0
+}
+
+/// Linear calibration of stored values: `raw * scale + offset`
+/// gives the physical quantity in the declared unit.
+///
+/// Values compare and hash by their bit patterns, so `NaN` equals itself.
+///
+/// ```
+/// use zarrs_conventions_uom::{UnitOfMeasurement, calibration::Calibration};
+///
+/// let uom = UnitOfMeasurement::builder()
+///     .unit("Cel")
+///     .calibration(Calibration::new(0.01, -40.0))
+///     .build();
+/// assert_eq!(uom.apply(6500.0), 25.0);
+/// ```
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Calibration {
+    #[serde(default = "one", skip_serializing_if = "is_one")]
+    pub scale: f64,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub offset: f64,
+}
+
+impl Calibration {
+    pub fn new(scale: f64, offset: f64) -> Self {
+        Self { scale, offset }
+    }
+
+    /// Physical quantity for a raw stored value.
+    pub fn apply(&self, raw: f64) -> f64 {
+        raw * self.scale + self.offset
+    }
+
+    /// Whether both terms are finite and the scale is non-zero,
+    /// so that raw values can be recovered from physical quantities.
+    pub fn is_valid(&self) -> bool {
+        self.scale.is_finite() && self.scale != 0.0 && self.offset.is_finite()
+    }
+}
+
+impl Default for Calibration {
+    fn default() -> Self {
+        Self::new(1.0, 0.0)
+    }
+}
+
+impl PartialEq for Calibration {
+    fn eq(&self, other: &Self) -> bool {
+        self.scale.to_bits() == other.scale.to_bits()
+            && self.offset.to_bits() == other.offset.to_bits()
+    }
+}
+
+impl Eq for Calibration {}
+
+impl Hash for Calibration {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.scale.to_bits().hash(state);
+        self.offset.to_bits().hash(state);
+    }
+}
+
+impl UnitOfMeasurement {
+    /// Physical quantity for a raw stored value,
+    /// which is unchanged if no calibration is given.
+    pub fn apply(&self, raw: f64) -> f64 {
+        self.calibration().map_or(raw, |c| c.apply(raw))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zarrs_conventions::validate::Lint;
+
+    use super::Calibration;
+    use crate::{BuildError, UnitOfMeasurement};
+
+    #[test]
+    fn serde() {
+        let uom = UnitOfMeasurement::builder()
+            .unit("mV")
+            .calibration(Calibration::new(0.5, 0.0))
+            .build();
+        let json = serde_json::to_value(&uom).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"ucum": {"unit": "mV"}, "calibration": {"scale": 0.5}})
+        );
+        let read: UnitOfMeasurement = serde_json::from_value(
+            serde_json::json!({"ucum": {"unit": "K"}, "calibration": {"offset": 3.0}}),
+        )
+        .unwrap();
+        assert_eq!(read.calibration(), Some(&Calibration::new(1.0, 3.0)));
+        assert_eq!(read.apply(2.0), 5.0);
+    }
+
+    #[test]
+    fn requires_unit() {
+        let builder = UnitOfMeasurement::builder().calibration(Calibration::new(2.0, 0.0));
+        assert_eq!(builder.build().lint()[0].code, "calibration-without-unit");
+        assert_eq!(
+            UnitOfMeasurement::builder()
+                .calibration(Calibration::new(2.0, 0.0))
+                .build_validated()
+                .unwrap_err(),
+            BuildError::CalibrationWithoutUnit
+        );
+        let invalid = UnitOfMeasurement::builder()
+            .unit("mV")
+            .calibration(Calibration::new(0.0, 1.0))
+            .build();
+        assert_eq!(invalid.lint()[0].code, "invalid-calibration");
+    }
+}
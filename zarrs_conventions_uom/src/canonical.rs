@@ -0,0 +1,299 @@
+//! Normalized UCUM forms, and separating magnitudes from units.
+use crate::{
+    Ucum,
+    table::Atom,
+    ucum::{Component, Op, ParseError, Term, Unit, UnitExpr},
+};
+
+/// A component of a flattened expression, with its exponent.
+enum Item {
+    Factor(u64, i32),
+    Unit(Unit),
+    Annotation(String, i32),
+}
+
+fn is_ten(atom: &Atom) -> bool {
+    matches!(atom.code, "10*" | "10^")
+}
+
+/// Components of the expression with their signed exponents, without groups.
+fn flatten(expr: &UnitExpr, sign: i32, items: &mut Vec<Item>) {
+    for term in &expr.terms {
+        let sign = match term.op {
+            Op::Multiply => sign,
+            Op::Divide => -sign,
+        };
+        match &term.component {
+            Component::Factor(n) => items.push(Item::Factor(*n, sign)),
+            Component::Annotation(a) => items.push(Item::Annotation(a.clone(), sign)),
+            Component::Group(inner) => flatten(inner, sign, items),
+            Component::Unit(unit) => {
+                let exponent = unit.exponent * sign;
+                let existing = items.iter_mut().find_map(|item| match item {
+                    Item::Unit(u)
+                        if u.prefix == unit.prefix
+                            && std::ptr::eq(u.atom, unit.atom)
+                            && u.annotation == unit.annotation =>
+                    {
+                        Some(u)
+                    }
+                    _ => None,
+                });
+                match existing {
+                    Some(u) => u.exponent += exponent,
+                    None => items.push(Item::Unit(Unit {
+                        exponent,
+                        ..unit.clone()
+                    })),
+                }
+            }
+        }
+    }
+}
+
+/// Product of the factors with the given sign, if it does not overflow.
+fn product(items: &[Item], positive: bool) -> Option<Option<u64>> {
+    let mut factors = items.iter().filter_map(|item| match item {
+        Item::Factor(n, sign) if (*sign > 0) == positive => Some(*n),
+        _ => None,
+    });
+    let Some(first) = factors.next() else {
+        return Some(None);
+    };
+    factors
+        .try_fold(first, |acc, n| acc.checked_mul(n))
+        .map(Some)
+}
+
+impl UnitExpr {
+    /// Equivalent expression in a normalized form, so that equal units are written the same way:
+    /// parentheses are removed, repeated units are combined,
+    /// factors are multiplied together, and units are sorted,
+    /// with powers of ten first and divisors last.
+    ///
+    /// ```
+    /// use zarrs_conventions_uom::ucum::UnitExpr;
+    ///
+    /// let expr: UnitExpr = "s-1.(m/s).kg".parse().unwrap();
+    /// assert_eq!(expr.canonicalize().to_string(), "kg.m/s2");
+    /// ```
+    pub fn canonicalize(&self) -> UnitExpr {
+        let mut items = Vec::default();
+        flatten(self, 1, &mut items);
+        let mut units: Vec<Unit> = Vec::default();
+        let mut annotations = Vec::default();
+        for item in &items {
+            match item {
+                Item::Unit(unit) if unit.exponent != 0 => units.push(unit.clone()),
+                Item::Annotation(a, sign) => annotations.push((a.clone(), *sign)),
+                _ => {}
+            }
+        }
+        // powers of ten first, then by atom and prefix
+        units.sort_by_key(|u| {
+            (
+                !is_ten(u.atom),
+                u.atom.code,
+                u.prefix.map(|p| p.code),
+                u.annotation.clone(),
+            )
+        });
+
+        let mut numerator = Vec::default();
+        let mut denominator = Vec::default();
+        // factors which would overflow when multiplied are kept separately
+        for positive in [true, false] {
+            let out = if positive {
+                &mut numerator
+            } else {
+                &mut denominator
+            };
+            match product(&items, positive) {
+                Some(Some(n)) => out.push(Component::Factor(n)),
+                Some(None) => {}
+                None => out.extend(items.iter().filter_map(|item| match item {
+                    Item::Factor(n, sign) if (*sign > 0) == positive => Some(Component::Factor(*n)),
+                    _ => None,
+                })),
+            }
+        }
+        for unit in units {
+            // powers of ten keep negative exponents, e.g. `10*-6.m`
+            if unit.exponent > 0 || is_ten(unit.atom) {
+                numerator.push(Component::Unit(unit));
+            } else {
+                denominator.push(Component::Unit(Unit {
+                    exponent: -unit.exponent,
+                    ..unit
+                }));
+            }
+        }
+        for (annotation, sign) in annotations {
+            let out = if sign > 0 {
+                &mut numerator
+            } else {
+                &mut denominator
+            };
+            out.push(Component::Annotation(annotation));
+        }
+        if numerator.is_empty() && denominator.is_empty() {
+            numerator.push(Component::Factor(1));
+        }
+        let terms = numerator
+            .into_iter()
+            .map(|component| Term {
+                op: Op::Multiply,
+                component,
+            })
+            .chain(denominator.into_iter().map(|component| Term {
+                op: Op::Divide,
+                component,
+            }))
+            .collect();
+        UnitExpr { terms }
+    }
+
+    /// The numerical magnitude of the expression (its factors and powers of ten),
+    /// and the [canonical](Self::canonicalize) unit without them.
+    ///
+    /// ```
+    /// use zarrs_conventions_uom::ucum::UnitExpr;
+    ///
+    /// let expr: UnitExpr = "10*-6.m".parse().unwrap();
+    /// let (magnitude, unit) = expr.split_magnitude();
+    /// assert_eq!(magnitude, 1e-6);
+    /// assert_eq!(unit.to_string(), "m");
+    /// ```
+    pub fn split_magnitude(&self) -> (f64, UnitExpr) {
+        let canonical = self.canonicalize();
+        let mut magnitude = 1.0;
+        let mut exp10 = 0;
+        let mut terms = Vec::default();
+        for term in canonical.terms {
+            let sign = match term.op {
+                Op::Multiply => 1,
+                Op::Divide => -1,
+            };
+            match &term.component {
+                Component::Factor(n) => magnitude *= (*n as f64).powi(sign),
+                Component::Unit(unit) if is_ten(unit.atom) && unit.annotation.is_none() => {
+                    exp10 += sign * unit.exponent;
+                }
+                _ => terms.push(term),
+            }
+        }
+        magnitude = match exp10 {
+            n if n >= 0 => magnitude * 10f64.powi(n),
+            n => magnitude / 10f64.powi(-n),
+        };
+        if terms.is_empty() {
+            terms.push(Term {
+                op: Op::Multiply,
+                component: Component::Factor(1),
+            });
+        }
+        (magnitude, UnitExpr { terms })
+    }
+}
+
+impl Ucum {
+    /// The unit in a [normalized form](UnitExpr::canonicalize), if one is declared.
+    ///
+    /// ```
+    /// use zarrs_conventions_uom::UnitOfMeasurement;
+    ///
+    /// let uom = UnitOfMeasurement::builder().unit("m/(s.s)").build();
+    /// assert_eq!(uom.ucum().canonicalize(), Some(Ok("m/s2".to_string())));
+    /// ```
+    pub fn canonicalize(&self) -> Option<Result<String, ParseError>> {
+        Some(self.parsed()?.map(|expr| expr.canonicalize().to_string()))
+    }
+
+    /// The numerical magnitude of the declared quantity, and its [canonical](Self::canonicalize) unit,
+    /// if one is declared.
+    ///
+    /// As well as UCUM magnitude terms such as `10*-6.m`,
+    /// a leading decimal number separated from the unit by whitespace is accepted, e.g. `4.2 um`.
+    ///
+    /// ```
+    /// use zarrs_conventions_uom::UnitOfMeasurement;
+    ///
+    /// let uom = UnitOfMeasurement::builder().unit("4.2 um").build();
+    /// assert_eq!(uom.ucum().split_magnitude(), Some(Ok((4.2, "um".to_string()))));
+    /// ```
+    pub fn split_magnitude(&self) -> Option<Result<(f64, String), ParseError>> {
+        let unit = self.unit()?.trim();
+        let number = |s: &str| s.parse::<f64>().ok().filter(|n| n.is_finite());
+        // `4.2` is valid UCUM (meaning 4 × 2), but is much more likely to be a decimal
+        let (number, unit) = match unit.split_once(char::is_whitespace) {
+            Some((head, rest)) => match number(head) {
+                Some(n) => (n, rest.trim_start()),
+                None => (1.0, unit),
+            },
+            None => match number(unit) {
+                Some(n) => (n, "1"),
+                None => (1.0, unit),
+            },
+        };
+        Some(Self::parse(unit).map(|expr| {
+            let (magnitude, expr) = expr.split_magnitude();
+            (number * magnitude, expr.to_string())
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{UnitOfMeasurement, ucum::UnitExpr};
+
+    fn canonical(unit: &str) -> String {
+        unit.parse::<UnitExpr>().unwrap().canonicalize().to_string()
+    }
+
+    fn split(unit: &str) -> (f64, String) {
+        UnitOfMeasurement::builder()
+            .unit(unit)
+            .build()
+            .ucum()
+            .split_magnitude()
+            .unwrap()
+            .unwrap()
+    }
+
+    #[test]
+    fn canonical_forms() {
+        assert_eq!(canonical("m.m"), "m2");
+        assert_eq!(canonical("m/m"), "1");
+        assert_eq!(canonical("/s"), "/s");
+        assert_eq!(canonical("kcal/(mol.K)"), "kcal/K/mol");
+        assert_eq!(canonical("m/(s/kg)"), "kg.m/s");
+        assert_eq!(canonical("2.m.3/5"), "6.m/5");
+        assert_eq!(canonical("m.10*3.10*-6"), "10*-3.m");
+        assert_eq!(canonical("{cells}/uL"), "{cells}/uL");
+        assert_eq!(canonical("um{a}.um"), "um.um{a}");
+        assert_eq!(canonical("s.m"), canonical("m.s"));
+    }
+
+    #[test]
+    fn split_magnitudes() {
+        assert_eq!(split("um"), (1.0, "um".into()));
+        assert_eq!(split("10*-6.m"), (1e-6, "m".into()));
+        assert_eq!(split("1000.m/s"), (1000.0, "m/s".into()));
+        assert_eq!(split("4.2 um"), (4.2, "um".into()));
+        assert_eq!(split("-2e3  m.m"), (-2000.0, "m2".into()));
+        assert_eq!(split("4.2"), (4.2, "1".into()));
+        assert_eq!(split("10*3"), (1000.0, "1".into()));
+        assert_eq!(split("/10*3.s"), (1e-3, "s".into()));
+        assert_eq!(split("m/1000"), (1e-3, "m".into()));
+        assert!(
+            UnitOfMeasurement::builder()
+                .unit("4.2 furlong")
+                .build()
+                .ucum()
+                .split_magnitude()
+                .unwrap()
+                .is_err()
+        );
+        assert_eq!(UnitOfMeasurement::default().ucum().split_magnitude(), None);
+    }
+}
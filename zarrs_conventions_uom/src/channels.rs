@@ -0,0 +1,221 @@
+//! Units for each channel of an array whose channels measure different quantities,
+//! e.g. a multichannel sensor recording both temperature and voltage.
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use zarrs_conventions::validate::{Issue, Lint};
+
+use crate::UnitOfMeasurement;
+
+/// Why per-channel units do not fit an array.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("{found} channel units are given for an array with {expected} channels")]
+pub struct ChannelCountError {
+    pub expected: usize,
+    pub found: usize,
+}
+
+/// Unit of a single channel, optionally identified by name.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Channel {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    uom: UnitOfMeasurement,
+}
+
+impl Channel {
+    /// Name of the channel, if given, e.g. `temperature`.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn uom(&self) -> &UnitOfMeasurement {
+        &self.uom
+    }
+}
+
+/// One unit per channel of an array, in channel order.
+///
+/// ```
+/// use zarrs_conventions_uom::{UnitOfMeasurement, channels::ChannelUnits};
+///
+/// let units = ChannelUnits::builder()
+///     .named_channel("temperature", UnitOfMeasurement::DEGREE_CELSIUS)
+///     .named_channel("voltage", UnitOfMeasurement::MILLIVOLT)
+///     .build_for_channels(2)
+///     .unwrap();
+/// assert_eq!(units.get(1).unwrap().ucum().unit(), Some("mV"));
+/// assert_eq!(units.get_by_name("temperature").unwrap().ucum().unit(), Some("Cel"));
+/// assert!(units.validate_channels(3).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(transparent)]
+pub struct ChannelUnits(Vec<Channel>);
+
+impl ChannelUnits {
+    pub fn builder() -> ChannelUnitsBuilder {
+        Default::default()
+    }
+
+    /// Number of channels described.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Unit of the channel at the given index.
+    pub fn get(&self, index: usize) -> Option<&UnitOfMeasurement> {
+        self.0.get(index).map(Channel::uom)
+    }
+
+    /// Unit of the first channel with the given name.
+    pub fn get_by_name(&self, name: &str) -> Option<&UnitOfMeasurement> {
+        self.0
+            .iter()
+            .find(|c| c.name() == Some(name))
+            .map(Channel::uom)
+    }
+
+    /// Each channel, in order.
+    pub fn iter(&self) -> impl Iterator<Item = &Channel> {
+        self.0.iter()
+    }
+
+    /// Check that there is one entry for each channel of an array with the given channel count.
+    pub fn validate_channels(&self, count: usize) -> Result<(), ChannelCountError> {
+        if self.0.len() == count {
+            Ok(())
+        } else {
+            Err(ChannelCountError {
+                expected: count,
+                found: self.0.len(),
+            })
+        }
+    }
+}
+
+impl From<Vec<UnitOfMeasurement>> for ChannelUnits {
+    fn from(value: Vec<UnitOfMeasurement>) -> Self {
+        Self(
+            value
+                .into_iter()
+                .map(|uom| Channel { name: None, uom })
+                .collect(),
+        )
+    }
+}
+
+impl Lint for ChannelUnits {
+    fn lint(&self) -> Vec<Issue> {
+        let mut issues: Vec<_> = self
+            .0
+            .iter()
+            .enumerate()
+            .flat_map(|(index, channel)| {
+                channel.uom.lint().into_iter().map(move |issue| Issue {
+                    message: format!("channel {index}: {}", issue.message),
+                    ..issue
+                })
+            })
+            .collect();
+        let mut names = HashSet::new();
+        for name in self.0.iter().filter_map(Channel::name) {
+            if !names.insert(name) {
+                issues.push(Issue::warning(
+                    "duplicate-channel-name",
+                    format!("channel name '{name}' is used more than once"),
+                ));
+            }
+        }
+        issues
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ChannelUnitsBuilder {
+    channels: Vec<Channel>,
+}
+
+impl ChannelUnitsBuilder {
+    /// Add an unnamed channel with the given unit.
+    pub fn channel(mut self, unit: impl Into<UnitOfMeasurement>) -> Self {
+        self.channels.push(Channel {
+            name: None,
+            uom: unit.into(),
+        });
+        self
+    }
+
+    /// Add a named channel with the given unit.
+    pub fn named_channel(
+        mut self,
+        name: impl Into<String>,
+        unit: impl Into<UnitOfMeasurement>,
+    ) -> Self {
+        self.channels.push(Channel {
+            name: Some(name.into()),
+            uom: unit.into(),
+        });
+        self
+    }
+
+    /// Build the units, checking that there is one for each channel of an array with the given channel count.
+    pub fn build_for_channels(self, count: usize) -> Result<ChannelUnits, ChannelCountError> {
+        let units = self.build();
+        units.validate_channels(count)?;
+        Ok(units)
+    }
+
+    pub fn build(self) -> ChannelUnits {
+        ChannelUnits(self.channels)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use zarrs_conventions::validate::Lint;
+
+    use super::{ChannelCountError, ChannelUnits};
+    use crate::UnitOfMeasurement;
+
+    #[test]
+    fn channel_units() {
+        let units = ChannelUnits::builder()
+            .named_channel("temperature", UnitOfMeasurement::builder().unit("Cel"))
+            .channel(UnitOfMeasurement::builder().unit("UM"))
+            .named_channel("temperature", UnitOfMeasurement::builder().unit("K"))
+            .build();
+        assert_eq!(
+            serde_json::to_value(&units).unwrap(),
+            json!([
+                {"name": "temperature", "uom": {"ucum": {"unit": "Cel"}}},
+                {"uom": {"ucum": {"unit": "UM"}}},
+                {"name": "temperature", "uom": {"ucum": {"unit": "K"}}},
+            ])
+        );
+        let round_trip: ChannelUnits =
+            serde_json::from_value(serde_json::to_value(&units).unwrap()).unwrap();
+        assert_eq!(round_trip, units);
+        assert_eq!(
+            units.get_by_name("temperature").unwrap().ucum().unit(),
+            Some("Cel")
+        );
+
+        let issues = units.lint();
+        let codes: Vec<_> = issues.iter().map(|i| i.code).collect();
+        assert_eq!(codes, ["invalid-ucum", "duplicate-channel-name"]);
+        assert!(issues[0].message.starts_with("channel 1: "));
+
+        assert_eq!(
+            units.validate_channels(2).unwrap_err(),
+            ChannelCountError {
+                expected: 2,
+                found: 3
+            }
+        );
+    }
+}
@@ -0,0 +1,136 @@
+//! Prebuilt values for common units.
+use std::{borrow::Cow, collections::BTreeMap};
+
+use crate::{Ucum, UnitOfMeasurement};
+
+const fn unit(code: &'static str, description: &'static str) -> UnitOfMeasurement {
+    UnitOfMeasurement {
+        ucum: Ucum {
+            unit: Some(Cow::Borrowed(code)),
+            version: None,
+            extras: BTreeMap::new(),
+        },
+        description: Some(Cow::Borrowed(description)),
+        calibration: None,
+        #[cfg(feature = "quantity-kind")]
+        quantity_kind: None,
+        extras: BTreeMap::new(),
+    }
+}
+
+/// Common units, described by name.
+///
+/// ```
+/// use zarrs_conventions_uom::UnitOfMeasurement;
+///
+/// let uom = UnitOfMeasurement::MICROMETER;
+/// assert_eq!(uom.ucum().unit(), Some("um"));
+/// assert_eq!(uom.description(), "micrometre");
+/// ```
+impl UnitOfMeasurement {
+    pub const DIMENSIONLESS: Self = unit("1", "dimensionless");
+    pub const PERCENT: Self = unit("%", "percent");
+
+    pub const METER: Self = unit("m", "metre");
+    pub const KILOMETER: Self = unit("km", "kilometre");
+    pub const CENTIMETER: Self = unit("cm", "centimetre");
+    pub const MILLIMETER: Self = unit("mm", "millimetre");
+    pub const MICROMETER: Self = unit("um", "micrometre");
+    pub const NANOMETER: Self = unit("nm", "nanometre");
+    pub const PICOMETER: Self = unit("pm", "picometre");
+    pub const ANGSTROM: Self = unit("Ao", "ångström");
+
+    pub const SECOND: Self = unit("s", "second");
+    pub const MILLISECOND: Self = unit("ms", "millisecond");
+    pub const MICROSECOND: Self = unit("us", "microsecond");
+    pub const NANOSECOND: Self = unit("ns", "nanosecond");
+    pub const MINUTE: Self = unit("min", "minute");
+    pub const HOUR: Self = unit("h", "hour");
+    pub const DAY: Self = unit("d", "day");
+
+    pub const GRAM: Self = unit("g", "gram");
+    pub const KILOGRAM: Self = unit("kg", "kilogram");
+    pub const MILLIGRAM: Self = unit("mg", "milligram");
+
+    pub const KELVIN: Self = unit("K", "kelvin");
+    pub const DEGREE_CELSIUS: Self = unit("Cel", "degree Celsius");
+
+    pub const MOLE: Self = unit("mol", "mole");
+    pub const MILLIMOLAR: Self = unit("mmol/L", "millimole per litre");
+
+    pub const RADIAN: Self = unit("rad", "radian");
+    pub const DEGREE: Self = unit("deg", "degree");
+
+    pub const LITER: Self = unit("L", "litre");
+    pub const MILLILITER: Self = unit("mL", "millilitre");
+    pub const MICROLITER: Self = unit("uL", "microlitre");
+
+    pub const HERTZ: Self = unit("Hz", "hertz");
+    pub const PASCAL: Self = unit("Pa", "pascal");
+    pub const JOULE: Self = unit("J", "joule");
+    pub const WATT: Self = unit("W", "watt");
+    pub const AMPERE: Self = unit("A", "ampere");
+    pub const VOLT: Self = unit("V", "volt");
+    pub const MILLIVOLT: Self = unit("mV", "millivolt");
+
+    pub const METER_PER_SECOND: Self = unit("m/s", "metre per second");
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::UnitOfMeasurement;
+
+    #[test]
+    fn constants_are_valid() {
+        let constants = [
+            UnitOfMeasurement::PERCENT,
+            UnitOfMeasurement::METER,
+            UnitOfMeasurement::KILOMETER,
+            UnitOfMeasurement::CENTIMETER,
+            UnitOfMeasurement::MILLIMETER,
+            UnitOfMeasurement::MICROMETER,
+            UnitOfMeasurement::NANOMETER,
+            UnitOfMeasurement::PICOMETER,
+            UnitOfMeasurement::ANGSTROM,
+            UnitOfMeasurement::SECOND,
+            UnitOfMeasurement::MILLISECOND,
+            UnitOfMeasurement::MICROSECOND,
+            UnitOfMeasurement::NANOSECOND,
+            UnitOfMeasurement::MINUTE,
+            UnitOfMeasurement::HOUR,
+            UnitOfMeasurement::DAY,
+            UnitOfMeasurement::GRAM,
+            UnitOfMeasurement::KILOGRAM,
+            UnitOfMeasurement::MILLIGRAM,
+            UnitOfMeasurement::KELVIN,
+            UnitOfMeasurement::DEGREE_CELSIUS,
+            UnitOfMeasurement::MOLE,
+            UnitOfMeasurement::MILLIMOLAR,
+            UnitOfMeasurement::RADIAN,
+            UnitOfMeasurement::DEGREE,
+            UnitOfMeasurement::LITER,
+            UnitOfMeasurement::MILLILITER,
+            UnitOfMeasurement::MICROLITER,
+            UnitOfMeasurement::HERTZ,
+            UnitOfMeasurement::PASCAL,
+            UnitOfMeasurement::JOULE,
+            UnitOfMeasurement::WATT,
+            UnitOfMeasurement::AMPERE,
+            UnitOfMeasurement::VOLT,
+            UnitOfMeasurement::MILLIVOLT,
+            UnitOfMeasurement::METER_PER_SECOND,
+        ];
+        for uom in constants {
+            // descriptions are the units' names
+            let name = uom.ucum().to_name().unwrap().unwrap();
+            assert_eq!(uom.description(), name);
+        }
+        assert!(UnitOfMeasurement::DIMENSIONLESS.ucum().is_valid());
+
+        let json = serde_json::to_value(UnitOfMeasurement::MICROMETER).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"ucum": {"unit": "um"}, "description": "micrometre"})
+        );
+    }
+}
@@ -0,0 +1,568 @@
+//! Conversion between commensurable units.
+use crate::{
+    Ucum, UnitOfMeasurement,
+    table::{Atom, Definition},
+    ucum::{Component, Op, ParseError, Unit, UnitExpr},
+};
+
+/// Number of UCUM base units: `m`, `s`, `g`, `rad`, `K`, `C`, and `cd`, in that order.
+pub(crate) const N_BASE: usize = 7;
+
+/// Why one unit cannot be converted to another.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[non_exhaustive]
+pub enum ConversionError {
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+    #[error("no unit is declared")]
+    Undeclared,
+    #[error("'{0}' is an arbitrary unit, which cannot be converted")]
+    Arbitrary(&'static str),
+    #[error("'{0}' is not a multiple of other units, so cannot be converted by scaling")]
+    NonLinear(&'static str),
+    #[error("'{0}' has an offset from zero, so cannot be converted when combined with other units")]
+    OffsetInProduct(&'static str),
+    #[error("'{0}' is not a logarithmic unit")]
+    NotLogarithmic(String),
+    #[error("'{from}' and '{to}' measure different kinds of quantity")]
+    Incommensurable { from: String, to: String },
+}
+
+/// Conversion of values from one unit to another: `value * scale + offset`.
+///
+/// The offset is non-zero only between units with different zero points,
+/// e.g. `Cel` and `K`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LinearConversion {
+    pub scale: f64,
+    pub offset: f64,
+}
+
+impl LinearConversion {
+    /// Convert a value.
+    pub fn apply(&self, value: f64) -> f64 {
+        value * self.scale + self.offset
+    }
+
+    /// Convert a difference between two values, e.g. a temperature interval,
+    /// to which the offset does not apply.
+    pub fn apply_difference(&self, difference: f64) -> f64 {
+        difference * self.scale
+    }
+}
+
+/// Conversion of levels in a logarithmic unit, e.g. `dB[SPL]`,
+/// to quantities in a linear unit: `reference * base ^ (level / factor)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LevelConversion {
+    pub base: f64,
+    pub factor: f64,
+    /// Quantity at level zero, in the linear unit.
+    pub reference: f64,
+}
+
+impl LevelConversion {
+    /// Quantity for a level.
+    pub fn apply(&self, level: f64) -> f64 {
+        self.reference * self.base.powf(level / self.factor)
+    }
+
+    /// Level for a quantity.
+    pub fn invert(&self, quantity: f64) -> f64 {
+        self.factor * (quantity / self.reference).log(self.base)
+    }
+}
+
+/// Logarithmic special units: a level `v` of a quantity `q` is
+/// `v = factor * log_base(q / (magnitude * reference))`,
+/// where `reference` is the unit's reference unit.
+static LOGARITHMIC: &[(&str, f64, f64, f64)] = &[
+    ("Np", std::f64::consts::E, 1.0, 1.0),
+    ("B", 10.0, 1.0, 1.0),
+    ("B[SPL]", 10.0, 2.0, 2.0),
+    ("B[V]", 10.0, 2.0, 1.0),
+    ("B[mV]", 10.0, 2.0, 1.0),
+    ("B[uV]", 10.0, 2.0, 1.0),
+    ("B[W]", 10.0, 1.0, 1.0),
+    ("B[kW]", 10.0, 1.0, 1.0),
+    ("[pH]", 10.0, -1.0, 1.0),
+    ("bit_s", 2.0, 1.0, 1.0),
+];
+
+/// Special units which are multiples of their reference unit with a different zero point:
+/// a value `v` is `v * scale + offset` in the reference unit.
+static AFFINE: &[(&str, f64, f64)] = &[
+    ("Cel", 1.0, 273.15),
+    ("[degF]", 5.0 / 9.0, 459.67 * 5.0 / 9.0),
+    ("[degRe]", 5.0 / 4.0, 273.15),
+];
+
+/// A unit expressed as a multiple of a product of powers of the base units.
+///
+/// Powers of ten are kept separate from the rest of the magnitude,
+/// so that converting between prefixes is exact.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Reduced {
+    pub magnitude: f64,
+    pub exp10: i32,
+    pub exponents: [i32; N_BASE],
+}
+
+impl Reduced {
+    const ONE: Self = Self {
+        magnitude: 1.0,
+        exp10: 0,
+        exponents: [0; N_BASE],
+    };
+
+    fn mul(self, other: Self, op: Op) -> Self {
+        let sign = match op {
+            Op::Multiply => 1,
+            Op::Divide => -1,
+        };
+        let mut exponents = self.exponents;
+        for (e, o) in exponents.iter_mut().zip(other.exponents) {
+            *e += sign * o;
+        }
+        Self {
+            magnitude: self.magnitude * other.magnitude.powi(sign),
+            exp10: self.exp10 + sign * other.exp10,
+            exponents,
+        }
+    }
+
+    fn pow(self, exponent: i32) -> Self {
+        Self {
+            magnitude: self.magnitude.powi(exponent),
+            exp10: self.exp10 * exponent,
+            exponents: self.exponents.map(|e| e * exponent),
+        }
+    }
+
+    /// Magnitude of this unit relative to another with the same exponents.
+    fn ratio(&self, other: &Self) -> f64 {
+        let ratio = self.magnitude / other.magnitude;
+        match self.exp10 - other.exp10 {
+            n if n >= 0 => ratio * 10f64.powi(n),
+            n => ratio / 10f64.powi(-n),
+        }
+    }
+}
+
+fn reduce_atom(atom: &'static Atom) -> Result<Reduced, ConversionError> {
+    match atom.definition {
+        Definition::Base => {
+            let index = crate::table::ATOMS[..N_BASE]
+                .iter()
+                .position(|base| base.code == atom.code)
+                .expect("base units come first in the table");
+            let mut exponents = [0; N_BASE];
+            exponents[index] = 1;
+            Ok(Reduced {
+                exponents,
+                ..Reduced::ONE
+            })
+        }
+        Definition::Derived { .. } if matches!(atom.code, "10*" | "10^") => Ok(Reduced {
+            exp10: 1,
+            ..Reduced::ONE
+        }),
+        Definition::Derived { magnitude, unit } => {
+            let expr: UnitExpr = unit.parse().expect("table definitions are valid");
+            let reduced = reduce(&expr)?;
+            Ok(Reduced {
+                magnitude: magnitude * reduced.magnitude,
+                ..reduced
+            })
+        }
+        Definition::Special { .. } if AFFINE.iter().any(|(code, ..)| *code == atom.code) => {
+            Err(ConversionError::OffsetInProduct(atom.code))
+        }
+        Definition::Special { .. } => Err(ConversionError::NonLinear(atom.code)),
+        Definition::Arbitrary => Err(ConversionError::Arbitrary(atom.code)),
+    }
+}
+
+/// Express a unit in terms of the base units.
+pub(crate) fn reduce(expr: &UnitExpr) -> Result<Reduced, ConversionError> {
+    expr.terms.iter().try_fold(Reduced::ONE, |acc, term| {
+        let reduced = match &term.component {
+            Component::Unit(unit) => {
+                let mut reduced = reduce_atom(unit.atom)?;
+                match unit.prefix {
+                    Some(p) if p.base == 10 => reduced.exp10 += p.exponent,
+                    Some(p) => reduced.magnitude *= p.value(),
+                    None => {}
+                }
+                reduced.pow(unit.exponent)
+            }
+            Component::Factor(n) => Reduced {
+                magnitude: *n as f64,
+                ..Reduced::ONE
+            },
+            Component::Annotation(_) => Reduced::ONE,
+            Component::Group(expr) => reduce(expr)?,
+        };
+        Ok(acc.mul(reduced, term.op))
+    })
+}
+
+/// Express a unit in terms of the base units, with an offset,
+/// which is non-zero only for a sole unit with a different zero point, e.g. `Cel`.
+///
+/// Values `v` in the unit are `v * magnitude + offset` in the base units.
+fn reduce_affine(expr: &UnitExpr) -> Result<(Reduced, f64), ConversionError> {
+    let affine = sole_unit(expr).and_then(|unit| {
+        let (_, scale, offset) = AFFINE.iter().find(|(code, ..)| *code == unit.atom.code)?;
+        Some((unit, scale, offset))
+    });
+    let Some((unit, scale, offset)) = affine else {
+        return Ok((reduce(expr)?, 0.0));
+    };
+    let mut reduced = reduce_reference(unit.atom)?;
+    reduced.magnitude *= scale * unit.prefix.map_or(1.0, |p| p.value());
+    Ok((reduced, *offset))
+}
+
+/// The only unit in an expression, if it has no exponent, ignoring annotations.
+fn sole_unit(expr: &UnitExpr) -> Option<&Unit> {
+    let mut terms = expr
+        .terms
+        .iter()
+        .filter(|t| !matches!(t.component, Component::Annotation(_)));
+    match (terms.next(), terms.next()) {
+        (Some(term), None) if term.op == Op::Multiply => match &term.component {
+            Component::Unit(unit) if unit.exponent == 1 => Some(unit),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// The reference unit of a special unit, in terms of the base units.
+fn reduce_reference(atom: &Atom) -> Result<Reduced, ConversionError> {
+    let Definition::Special { unit } = atom.definition else {
+        unreachable!("only special units have reference units");
+    };
+    reduce(&unit.parse().expect("table definitions are valid"))
+}
+
+/// A sole logarithmic unit: a level `v` of a quantity `q` is `v = factor * log_base(q / reference)`.
+struct Level {
+    base: f64,
+    factor: f64,
+    reference: Reduced,
+}
+
+impl Level {
+    fn of(expr: &UnitExpr) -> Result<Option<Self>, ConversionError> {
+        let Some(unit) = sole_unit(expr) else {
+            return Ok(None);
+        };
+        let Some((_, base, factor, magnitude)) = LOGARITHMIC
+            .iter()
+            .find(|(code, ..)| *code == unit.atom.code)
+        else {
+            return Ok(None);
+        };
+        let mut reference = reduce_reference(unit.atom)?;
+        reference.magnitude *= magnitude;
+        Ok(Some(Self {
+            base: *base,
+            // e.g. a level of 1 dB is 0.1 B
+            factor: factor / unit.prefix.map_or(1.0, |p| p.value()),
+            reference,
+        }))
+    }
+}
+
+impl UnitExpr {
+    /// Whether this is a logarithmic unit, such as `dB[SPL]`, `Np`, or `[pH]`,
+    /// whose values are levels rather than multiples of a quantity.
+    pub fn is_logarithmic(&self) -> bool {
+        sole_unit(self)
+            .is_some_and(|unit| LOGARITHMIC.iter().any(|(code, ..)| *code == unit.atom.code))
+    }
+
+    /// Conversion of values in this unit to the target unit.
+    ///
+    /// Units with a different zero point to their base units, such as `Cel` and `[degF]`,
+    /// can be converted only on their own, not as part of a product such as `Cel/s`.
+    /// Levels in logarithmic units, such as `dB[mV]`, can be converted to levels in other
+    /// logarithmic units with commensurable reference units, e.g. `B[V]`;
+    /// see [Self::level_conversion_to] for converting levels to linear quantities.
+    ///
+    /// ```
+    /// use zarrs_conventions_uom::ucum::UnitExpr;
+    ///
+    /// let um: UnitExpr = "um".parse().unwrap();
+    /// let conversion = um.conversion_to(&"nm".parse().unwrap()).unwrap();
+    /// assert_eq!(conversion.apply(2.0), 2000.0);
+    /// assert!(um.conversion_to(&"s".parse().unwrap()).is_err());
+    /// ```
+    pub fn conversion_to(&self, target: &UnitExpr) -> Result<LinearConversion, ConversionError> {
+        if let (Some(from), Some(to)) = (Level::of(self)?, Level::of(target)?) {
+            if from.reference.exponents != to.reference.exponents {
+                return Err(ConversionError::Incommensurable {
+                    from: self.to_string(),
+                    to: target.to_string(),
+                });
+            }
+            // q = r1 * b1 ^ (v1 / k1), so v2 = k2 * (v1 * ln(b1) / k1 + ln(r1 / r2)) / ln(b2)
+            let ln_base = to.base.ln();
+            return Ok(LinearConversion {
+                scale: to.factor * from.base.ln() / (from.factor * ln_base),
+                offset: to.factor * from.reference.ratio(&to.reference).ln() / ln_base,
+            });
+        }
+        let (from, from_offset) = reduce_affine(self)?;
+        let (to, to_offset) = reduce_affine(target)?;
+        if from.exponents != to.exponents {
+            return Err(ConversionError::Incommensurable {
+                from: self.to_string(),
+                to: target.to_string(),
+            });
+        }
+        // offsets are in the base units
+        Ok(LinearConversion {
+            scale: from.ratio(&to),
+            offset: (from_offset - to_offset) / to.ratio(&Reduced::ONE),
+        })
+    }
+}
+
+impl UnitExpr {
+    /// Conversion of levels in this logarithmic unit to quantities in the target linear unit.
+    ///
+    /// Levels are relative to the unit's reference quantity, e.g. 20 µPa for `B[SPL]`,
+    /// multiplied by any given `reference`,
+    /// which is needed for units whose reference is a pure number, such as `dB` and `Np`.
+    ///
+    /// ```
+    /// use zarrs_conventions_uom::ucum::UnitExpr;
+    ///
+    /// let db_spl: UnitExpr = "dB[SPL]".parse().unwrap();
+    /// let pa: UnitExpr = "Pa".parse().unwrap();
+    /// let conversion = db_spl.level_conversion_to(&pa, None).unwrap();
+    /// assert!((conversion.apply(94.0) - 1.0).abs() < 1e-2);
+    ///
+    /// // dBm, i.e. decibels relative to a milliwatt
+    /// let db: UnitExpr = "dB".parse().unwrap();
+    /// let reference: UnitExpr = "mW".parse().unwrap();
+    /// let conversion = db.level_conversion_to(&"W".parse().unwrap(), Some(&reference)).unwrap();
+    /// assert!((conversion.apply(30.0) - 1.0).abs() < 1e-9);
+    /// ```
+    pub fn level_conversion_to(
+        &self,
+        target: &UnitExpr,
+        reference: Option<&UnitExpr>,
+    ) -> Result<LevelConversion, ConversionError> {
+        let level =
+            Level::of(self)?.ok_or_else(|| ConversionError::NotLogarithmic(self.to_string()))?;
+        let mut from = level.reference;
+        if let Some(reference) = reference {
+            from = from.mul(reduce(reference)?, Op::Multiply);
+        }
+        let to = reduce(target)?;
+        if from.exponents != to.exponents {
+            return Err(ConversionError::Incommensurable {
+                from: match reference {
+                    Some(reference) => format!("{self} relative to {reference}"),
+                    None => self.to_string(),
+                },
+                to: target.to_string(),
+            });
+        }
+        Ok(LevelConversion {
+            base: level.base,
+            factor: level.factor,
+            reference: from.ratio(&to),
+        })
+    }
+}
+
+impl Ucum {
+    /// The [parsed](Self::parsed) unit, which must be declared to be converted.
+    pub(crate) fn expr(&self) -> Result<UnitExpr, ConversionError> {
+        Ok(self.parsed().ok_or(ConversionError::Undeclared)??)
+    }
+}
+
+impl UnitOfMeasurement {
+    /// Conversion of values in this unit to the target unit,
+    /// if both are declared and measure the same kind of quantity.
+    ///
+    /// ```
+    /// use zarrs_conventions_uom::UnitOfMeasurement;
+    ///
+    /// let mm = UnitOfMeasurement::builder().unit("mm").build();
+    /// let inch = UnitOfMeasurement::builder().unit("[in_i]").build();
+    /// let in_mm = inch.conversion_to(&mm).unwrap().apply(1.0);
+    /// assert!((in_mm - 25.4).abs() < 1e-12);
+    /// ```
+    pub fn conversion_to(
+        &self,
+        target: &UnitOfMeasurement,
+    ) -> Result<LinearConversion, ConversionError> {
+        self.ucum().expr()?.conversion_to(&target.ucum().expr()?)
+    }
+
+    /// Whether the declared unit is [logarithmic](UnitExpr::is_logarithmic).
+    pub fn is_logarithmic(&self) -> bool {
+        self.ucum().expr().is_ok_and(|expr| expr.is_logarithmic())
+    }
+
+    /// Conversion of levels in the declared logarithmic unit to quantities in the target unit;
+    /// see [UnitExpr::level_conversion_to].
+    ///
+    /// ```
+    /// use zarrs_conventions_uom::UnitOfMeasurement;
+    ///
+    /// let ph = UnitOfMeasurement::builder().unit("[pH]").build();
+    /// let molar = UnitOfMeasurement::builder().unit("mmol/L").build();
+    /// let conversion = ph.level_conversion_to(&molar, None).unwrap();
+    /// assert!((conversion.apply(7.0) - 1e-4).abs() < 1e-12);
+    /// ```
+    pub fn level_conversion_to(
+        &self,
+        target: &UnitOfMeasurement,
+        reference: Option<&UnitExpr>,
+    ) -> Result<LevelConversion, ConversionError> {
+        self.ucum()
+            .expr()?
+            .level_conversion_to(&target.ucum().expr()?, reference)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ConversionError;
+    use crate::{UnitOfMeasurement, ucum::UnitExpr};
+
+    fn scale(from: &str, to: &str) -> Result<f64, ConversionError> {
+        let from: UnitExpr = from.parse().unwrap();
+        Ok(from.conversion_to(&to.parse().unwrap())?.scale)
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() <= 1e-9 * a.abs().max(b.abs())
+    }
+
+    fn convert(from: &str, to: &str, value: f64) -> f64 {
+        let from: UnitExpr = from.parse().unwrap();
+        from.conversion_to(&to.parse().unwrap())
+            .unwrap()
+            .apply(value)
+    }
+
+    #[test]
+    fn convert_temperatures() {
+        assert!(close(convert("Cel", "K", 25.0), 298.15));
+        assert!(close(convert("K", "Cel", 0.0), -273.15));
+        assert!(close(convert("[degF]", "Cel", 212.0), 100.0));
+        assert!(close(convert("Cel", "[degF]", -40.0), -40.0));
+        assert!(close(convert("[degRe]", "Cel", 80.0), 100.0));
+        assert!(close(convert("[degR]", "[degF]", 0.0), -459.67));
+        assert!(close(convert("mK", "Cel", 274_150.0), 1.0));
+        assert!(close(convert("Cel{air}", "K", 1.0), 274.15));
+
+        let from: UnitExpr = "[degF]".parse().unwrap();
+        let conversion = from.conversion_to(&"K".parse().unwrap()).unwrap();
+        assert!(close(conversion.apply_difference(9.0), 5.0));
+    }
+
+    #[test]
+    fn convert_levels() {
+        assert!(close(convert("B", "dB", 1.0), 10.0));
+        assert!(close(convert("Np", "B", 1.0), std::f64::consts::LOG10_E));
+        assert!(close(convert("dB[mV]", "dB[V]", 0.0), -60.0));
+        assert!(close(convert("B[kW]", "B[W]", 0.0), 3.0));
+        assert!(close(convert("[pH]", "[pH]", 7.0), 7.0));
+        assert_eq!(
+            scale("dB[SPL]", "dB[V]").unwrap_err(),
+            ConversionError::Incommensurable {
+                from: "dB[SPL]".into(),
+                to: "dB[V]".into()
+            }
+        );
+        assert_eq!(scale("dB", "1"), Err(ConversionError::NonLinear("B")));
+        assert_eq!(scale("dB/s", "B/s"), Err(ConversionError::NonLinear("B")));
+
+        let level = |unit: &str, target: &str, reference: Option<&str>| {
+            let unit: UnitExpr = unit.parse().unwrap();
+            let reference: Option<UnitExpr> = reference.map(|r| r.parse().unwrap());
+            unit.level_conversion_to(&target.parse().unwrap(), reference.as_ref())
+        };
+        let spl = level("dB[SPL]", "uPa", None).unwrap();
+        assert!(close(spl.apply(0.0), 20.0));
+        assert!(close(spl.apply(20.0), 200.0));
+        assert!(close(spl.invert(200.0), 20.0));
+        let power = level("dB[W]", "mW", None).unwrap();
+        assert!(close(power.apply(10.0), 10_000.0));
+        let bits = level("bit_s", "1", None).unwrap();
+        assert!(close(bits.apply(8.0), 256.0));
+        assert!(close(
+            level("dB", "V", Some("V")).unwrap().apply(20.0),
+            100.0
+        ));
+        assert!(matches!(
+            level("dB", "V", None),
+            Err(ConversionError::Incommensurable { .. })
+        ));
+        assert_eq!(
+            level("V", "V", None).unwrap_err(),
+            ConversionError::NotLogarithmic("V".into())
+        );
+    }
+
+    #[test]
+    fn convert_units() {
+        assert!(close(scale("um", "nm").unwrap(), 1e3));
+        assert!(close(scale("10*-6.m", "um").unwrap(), 1.0));
+        assert!(close(scale("km/h", "m/s").unwrap(), 1.0 / 3.6));
+        assert!(close(scale("N", "kg.m/s2").unwrap(), 1.0));
+        assert!(close(scale("L", "cm3").unwrap(), 1e3));
+        assert!(close(scale("d", "min").unwrap(), 1440.0));
+        assert!(close(scale("[lb_av]", "kg").unwrap(), 0.453_592_37));
+        assert!(close(
+            scale("deg", "rad").unwrap(),
+            std::f64::consts::PI / 180.0
+        ));
+        assert!(close(scale("%", "1").unwrap(), 0.01));
+        assert!(close(scale("{cells}/uL", "/mL").unwrap(), 1e3));
+        assert!(close(scale("kcal/(mol.K)", "J/(mol.K)").unwrap(), 4184.0));
+        assert!(close(scale("KiBy", "bit").unwrap(), 8192.0));
+
+        assert!(matches!(
+            scale("m", "s"),
+            Err(ConversionError::Incommensurable { .. })
+        ));
+        assert_eq!(
+            scale("[pH]", "mol/l"),
+            Err(ConversionError::NonLinear("[pH]"))
+        );
+        assert_eq!(
+            scale("Cel/s", "K/s"),
+            Err(ConversionError::OffsetInProduct("Cel"))
+        );
+        assert_eq!(
+            scale("Cel2", "K2"),
+            Err(ConversionError::OffsetInProduct("Cel"))
+        );
+        assert_eq!(
+            scale("[arb'U]", "1"),
+            Err(ConversionError::Arbitrary("[arb'U]"))
+        );
+
+        let declared = UnitOfMeasurement::builder().unit("um").build();
+        assert_eq!(
+            declared.conversion_to(&UnitOfMeasurement::default()),
+            Err(ConversionError::Undeclared)
+        );
+        let invalid = UnitOfMeasurement::builder().unit("UM").build();
+        assert!(matches!(
+            declared.conversion_to(&invalid),
+            Err(ConversionError::Parse(_))
+        ));
+    }
+}
@@ -0,0 +1,190 @@
+//! Dimensions of units, for checking commensurability without converting.
+use std::fmt;
+
+use crate::{
+    UnitOfMeasurement,
+    convert::{ConversionError, N_BASE},
+    table::{ATOMS, Atom, Definition},
+    ucum::{Component, Op, UnitExpr},
+};
+
+/// Dimension of a unit, as the exponents of the UCUM base units
+/// `m`, `s`, `g`, `rad`, `K`, `C`, and `cd`, in that order.
+///
+/// Displayed as a product of the base units, e.g. `m.s-2`, or `1` if dimensionless.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Dimension(pub [i32; N_BASE]);
+
+impl Dimension {
+    /// Codes of the UCUM base units, in the order of the exponents.
+    pub const BASE_UNITS: [&'static str; N_BASE] = ["m", "s", "g", "rad", "K", "C", "cd"];
+
+    pub fn is_dimensionless(&self) -> bool {
+        self.0 == [0; N_BASE]
+    }
+
+    fn combine(self, other: Self, sign: i32) -> Self {
+        let mut out = self.0;
+        for (o, e) in out.iter_mut().zip(other.0) {
+            *o += sign * e;
+        }
+        Self(out)
+    }
+}
+
+impl fmt::Display for Dimension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_dimensionless() {
+            return f.write_str("1");
+        }
+        let mut first = true;
+        for (code, exponent) in Self::BASE_UNITS.iter().zip(self.0) {
+            if exponent == 0 {
+                continue;
+            }
+            if !first {
+                f.write_str(".")?;
+            }
+            first = false;
+            f.write_str(code)?;
+            if exponent != 1 {
+                write!(f, "{exponent}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn atom_dimension(atom: &'static Atom) -> Result<Dimension, ConversionError> {
+    let unit = match atom.definition {
+        Definition::Base => {
+            let mut exponents = [0; N_BASE];
+            exponents[ATOMS
+                .iter()
+                .position(|a| a.code == atom.code)
+                .expect("atom is in the table")] = 1;
+            return Ok(Dimension(exponents));
+        }
+        Definition::Derived { unit, .. } | Definition::Special { unit } => unit,
+        Definition::Arbitrary => return Err(ConversionError::Arbitrary(atom.code)),
+    };
+    unit.parse::<UnitExpr>()
+        .expect("table definitions are valid")
+        .dimension()
+}
+
+impl UnitExpr {
+    /// Dimension of the unit.
+    ///
+    /// Unlike [conversion](Self::conversion_to), this is defined for special units such as `[pH]`,
+    /// but not for arbitrary units.
+    ///
+    /// ```
+    /// use zarrs_conventions_uom::ucum::UnitExpr;
+    ///
+    /// let expr: UnitExpr = "N".parse().unwrap();
+    /// assert_eq!(expr.dimension().unwrap().to_string(), "m.s-2.g");
+    /// ```
+    pub fn dimension(&self) -> Result<Dimension, ConversionError> {
+        self.terms
+            .iter()
+            .try_fold(Dimension::default(), |acc, term| {
+                let dimension = match &term.component {
+                    Component::Unit(unit) => {
+                        let base = atom_dimension(unit.atom)?;
+                        Dimension(base.0.map(|e| e * unit.exponent))
+                    }
+                    Component::Factor(_) | Component::Annotation(_) => Dimension::default(),
+                    Component::Group(expr) => expr.dimension()?,
+                };
+                let sign = match term.op {
+                    Op::Multiply => 1,
+                    Op::Divide => -1,
+                };
+                Ok(acc.combine(dimension, sign))
+            })
+    }
+}
+
+impl UnitOfMeasurement {
+    /// Dimension of the declared unit.
+    pub fn dimension(&self) -> Result<Dimension, ConversionError> {
+        self.ucum().expr()?.dimension()
+    }
+
+    /// Whether a unit is declared which is a pure number, e.g. `1`, `%`, or `{cells}`.
+    ///
+    /// Undeclared units are [arbitrary](Self::is_arbitrary), not dimensionless.
+    pub fn is_dimensionless(&self) -> bool {
+        self.dimension().is_ok_and(|d| d.is_dimensionless())
+    }
+
+    /// Whether values are in an arbitrary unit:
+    /// either no unit is declared, or it includes an arbitrary unit such as `[arb'U]`.
+    pub fn is_arbitrary(&self) -> bool {
+        matches!(
+            self.dimension(),
+            Err(ConversionError::Undeclared | ConversionError::Arbitrary(_))
+        )
+    }
+
+    /// Whether both units are declared and measure the same kind of quantity.
+    ///
+    /// [Logarithmic](UnitExpr::is_logarithmic) units are commensurable only with each other,
+    /// as levels cannot be scaled to quantities.
+    ///
+    /// ```
+    /// use zarrs_conventions_uom::UnitOfMeasurement;
+    ///
+    /// let unit = |u| UnitOfMeasurement::builder().unit(u).build();
+    /// assert!(unit("um").commensurable_with(&unit("[in_i]")));
+    /// assert!(unit("Cel").commensurable_with(&unit("K")));
+    /// assert!(!unit("um").commensurable_with(&unit("s")));
+    /// ```
+    pub fn commensurable_with(&self, other: &UnitOfMeasurement) -> bool {
+        match (self.dimension(), other.dimension()) {
+            (Ok(a), Ok(b)) => a == b && self.is_logarithmic() == other.is_logarithmic(),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Dimension;
+    use crate::{UnitOfMeasurement, convert::ConversionError, ucum::UnitExpr};
+
+    fn dimension(unit: &str) -> Result<Dimension, ConversionError> {
+        unit.parse::<UnitExpr>().unwrap().dimension()
+    }
+
+    #[test]
+    fn dimensions() {
+        assert_eq!(dimension("kg.m/s2").unwrap(), dimension("N").unwrap());
+        assert_eq!(dimension("J").unwrap().0, [2, -2, 1, 0, 0, 0, 0]);
+        assert!(dimension("%").unwrap().is_dimensionless());
+        assert_eq!(dimension("{cells}/uL").unwrap().to_string(), "m-3");
+        assert_eq!(dimension("[degF]").unwrap().to_string(), "K");
+        assert_eq!(dimension("dB[SPL]").unwrap(), dimension("Pa").unwrap());
+        assert_eq!(dimension("[IU]/L"), Err(ConversionError::Arbitrary("[IU]")));
+
+        let undeclared = UnitOfMeasurement::default();
+        assert_eq!(undeclared.dimension(), Err(ConversionError::Undeclared));
+        assert!(!undeclared.commensurable_with(&undeclared));
+
+        let uom = |u| UnitOfMeasurement::builder().unit(u).build();
+        assert!(uom("dB[SPL]").commensurable_with(&uom("B[SPL]")));
+        assert!(!uom("dB[mV]").commensurable_with(&uom("Np")));
+        assert!(!uom("dB[SPL]").commensurable_with(&uom("Pa")));
+        assert!(uom("[pH]").commensurable_with(&uom("[pH]")));
+        assert!(undeclared.is_arbitrary());
+        assert!(!undeclared.is_dimensionless());
+
+        let unit = |u| UnitOfMeasurement::builder().unit(u).build();
+        assert!(unit("1").is_dimensionless());
+        assert!(unit("{cells}").is_dimensionless());
+        assert!(!unit("1").is_arbitrary());
+        assert!(unit("[IU]/L").is_arbitrary());
+        assert!(!unit("UM").is_arbitrary());
+    }
+}
@@ -1,18 +1,59 @@
 #[doc = include_str!("../README.md")]
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 pub use zarrs_conventions;
 use zarrs_conventions::{
-    ConventionDefinition, NestedRepr, ZarrConventionImpl, iref::uri, register_zarr_conventions,
+    ConventionDefinition, NestedRepr, ZarrConventionImpl,
+    iref::uri,
+    register_zarr_conventions,
     uuid::uuid,
+    validate::{Issue, Lint},
 };
 
+use std::{borrow::Cow, collections::BTreeMap};
+
+pub mod arithmetic;
+#[cfg(feature = "zarrs")]
+pub mod array;
+pub mod axes;
+pub mod calibration;
+mod canonical;
+pub mod channels;
+mod constants;
+pub mod convert;
+pub mod dimension;
+pub mod ome;
+pub mod pint;
+mod pretty;
+#[cfg(feature = "quantity-kind")]
+pub mod quantity_kind;
+mod syntax;
+mod table;
+#[cfg(feature = "uom")]
+pub mod typed;
+pub mod ucum;
+pub mod udunits;
+pub mod values;
+mod version;
+pub mod xarray;
+use calibration::Calibration;
+use ucum::{ParseError, UnitExpr};
+
 /// Conventional metadata for units of measurement,
 /// applied to numerical Zarr arrays.
-#[derive(Debug, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
 pub struct UnitOfMeasurement {
     ucum: Ucum,
     #[serde(skip_serializing_if = "Option::is_none")]
-    description: Option<String>,
+    description: Option<Cow<'static, str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    calibration: Option<Calibration>,
+    #[cfg(feature = "quantity-kind")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    quantity_kind: Option<quantity_kind::QuantityKind>,
+    /// Fields not defined by this version of the convention, retained for round-tripping.
+    #[serde(flatten)]
+    extras: BTreeMap<String, Value>,
 }
 
 impl UnitOfMeasurement {
@@ -20,6 +61,24 @@ impl UnitOfMeasurement {
         Default::default()
     }
 
+    /// Values in an arbitrary unit, e.g. uncalibrated intensities,
+    /// declared explicitly as UCUM's `[arb'U]`.
+    ///
+    /// This is preferred to declaring no unit at all,
+    /// which is read the same way but may be an oversight.
+    ///
+    /// ```
+    /// use zarrs_conventions_uom::UnitOfMeasurement;
+    ///
+    /// let uom = UnitOfMeasurement::arbitrary();
+    /// assert!(uom.is_arbitrary());
+    /// assert!(!uom.is_dimensionless());
+    /// assert!(UnitOfMeasurement::DIMENSIONLESS.is_dimensionless());
+    /// ```
+    pub fn arbitrary() -> Self {
+        Self::builder().unit("[arb'U]").build()
+    }
+
     pub fn description(&self) -> &str {
         self.description.as_deref().unwrap_or("")
     }
@@ -27,15 +86,134 @@ impl UnitOfMeasurement {
     pub fn ucum(&self) -> &Ucum {
         &self.ucum
     }
+
+    /// Mapping of raw stored values to quantities in the declared unit, if given.
+    pub fn calibration(&self) -> Option<&Calibration> {
+        self.calibration.as_ref()
+    }
+
+    /// Set the **case-sensitive** UCUM unit string, or clear it with `None`,
+    /// keeping any UCUM version.
+    ///
+    /// ```
+    /// use zarrs_conventions_uom::UnitOfMeasurement;
+    ///
+    /// let mut uom = UnitOfMeasurement::MILLIMETER;
+    /// uom.set_unit(Some("um")).set_description(Some("micrometre"));
+    /// assert_eq!(uom, UnitOfMeasurement::MICROMETER);
+    /// ```
+    pub fn set_unit(&mut self, unit: Option<impl Into<String>>) -> &mut Self {
+        self.ucum.unit = unit.map(|u| Cow::Owned(u.into()));
+        self
+    }
+
+    /// Set the free-text description, or clear it with `None`.
+    pub fn set_description(&mut self, description: Option<impl Into<String>>) -> &mut Self {
+        self.description = description.map(|d| Cow::Owned(d.into()));
+        self
+    }
+
+    /// Set the calibration of raw stored values, or clear it with `None`.
+    pub fn set_calibration(&mut self, calibration: Option<Calibration>) -> &mut Self {
+        self.calibration = calibration;
+        self
+    }
+
+    /// Kind of quantity measured, if given.
+    ///
+    /// This is not part of v1 of the convention.
+    #[cfg(feature = "quantity-kind")]
+    pub fn quantity_kind(&self) -> Option<&quantity_kind::QuantityKind> {
+        self.quantity_kind.as_ref()
+    }
+
+    /// Set the kind of quantity measured, or clear it with `None`.
+    ///
+    /// This is not part of v1 of the convention.
+    #[cfg(feature = "quantity-kind")]
+    pub fn set_quantity_kind(
+        &mut self,
+        quantity_kind: Option<quantity_kind::QuantityKind>,
+    ) -> &mut Self {
+        self.quantity_kind = quantity_kind;
+        self
+    }
+}
+
+/// How to treat fields not defined by the convention when parsing a [UnitOfMeasurement].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ParseMode {
+    /// Fail on unknown fields.
+    Strict,
+    /// Discard unknown fields.
+    Lenient,
+    /// Keep unknown fields and write them back out when serializing;
+    /// this is how [UnitOfMeasurement] deserializes.
+    #[default]
+    Passthrough,
+}
+
+/// Why a uom payload could not be parsed.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum PayloadError {
+    #[error(transparent)]
+    Deserialize(#[from] serde_json::Error),
+    #[error("unknown uom field '{0}'")]
+    UnknownField(String),
+}
+
+impl UnitOfMeasurement {
+    /// Parse a uom payload, treating unknown fields according to `mode`.
+    ///
+    /// ```
+    /// use serde_json::json;
+    /// use zarrs_conventions_uom::{ParseMode, UnitOfMeasurement};
+    ///
+    /// let value = json!({"ucum": {"unit": "um", "system": "x"}, "precision": 2});
+    /// assert!(UnitOfMeasurement::parse(&value, ParseMode::Strict).is_err());
+    /// let uom = UnitOfMeasurement::parse(&value, ParseMode::Passthrough).unwrap();
+    /// assert_eq!(serde_json::to_value(&uom).unwrap(), value);
+    /// ```
+    pub fn parse(value: &Value, mode: ParseMode) -> Result<Self, PayloadError> {
+        let mut uom = Self::deserialize(value)?;
+        match mode {
+            ParseMode::Strict => {
+                let unknown = uom
+                    .extras
+                    .keys()
+                    .cloned()
+                    .chain(uom.ucum.extras.keys().map(|k| format!("ucum.{k}")))
+                    .next();
+                if let Some(key) = unknown {
+                    return Err(PayloadError::UnknownField(key));
+                }
+            }
+            ParseMode::Lenient => {
+                uom.extras.clear();
+                uom.ucum.extras.clear();
+            }
+            ParseMode::Passthrough => (),
+        }
+        Ok(uom)
+    }
+
+    /// Fields not defined by this version of the convention.
+    pub fn extras(&self) -> &BTreeMap<String, Value> {
+        &self.extras
+    }
 }
 
 /// Metadata using the [Unified Code for Units and Measures specification](https://ucum.org/ucum).
-#[derive(Debug, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
 pub struct Ucum {
     #[serde(skip_serializing_if = "Option::is_none")]
-    unit: Option<String>,
+    unit: Option<Cow<'static, str>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    version: Option<String>,
+    version: Option<Cow<'static, str>>,
+    /// Fields not defined by this version of the convention, retained for round-tripping.
+    #[serde(flatten)]
+    extras: BTreeMap<String, Value>,
 }
 
 impl Ucum {
@@ -47,10 +225,135 @@ impl Ucum {
         self.unit.as_deref()
     }
 
+    /// The parsed unit, if one is declared,
+    /// for structural analysis of its prefixes, atoms, exponents, factors, and annotations.
+    ///
+    /// ```
+    /// use zarrs_conventions_uom::{UnitOfMeasurement, ucum::Component};
+    ///
+    /// let uom = UnitOfMeasurement::builder().unit("um2").build();
+    /// let expr = uom.ucum().parsed().unwrap().unwrap();
+    /// let Component::Unit(unit) = &expr.terms[0].component else {
+    ///     panic!("expected a unit");
+    /// };
+    /// assert_eq!(unit.prefix.unwrap().name, "micro");
+    /// assert_eq!(unit.atom.code, "m");
+    /// assert_eq!(unit.exponent, 2);
+    /// ```
+    pub fn parsed(&self) -> Option<Result<UnitExpr, ParseError>> {
+        self.unit().map(Self::parse)
+    }
+
     /// Version of the UCUM specification, if defined.
     pub fn version(&self) -> Option<&str> {
         self.version.as_deref()
     }
+
+    /// Fields of the `ucum` object not defined by this version of the convention.
+    pub fn extras(&self) -> &BTreeMap<String, Value> {
+        &self.extras
+    }
+
+    /// Parse a **case-sensitive** UCUM unit string.
+    ///
+    /// ```
+    /// use zarrs_conventions_uom::Ucum;
+    ///
+    /// assert!(Ucum::parse("kg.m/s2").is_ok());
+    /// let err = Ucum::parse("UM").unwrap_err();
+    /// assert!(err.to_string().contains("did you mean 'um'?"));
+    /// ```
+    pub fn parse(unit: &str) -> Result<UnitExpr, ParseError> {
+        unit.parse()
+    }
+
+    /// Whether the unit string, if any, is valid UCUM.
+    pub fn is_valid(&self) -> bool {
+        self.unit.as_deref().is_none_or(|u| Self::parse(u).is_ok())
+    }
+
+    /// Released versions of the UCUM specification.
+    pub const KNOWN_VERSIONS: &[&str] = &[
+        "1.6", "1.7", "1.8", "1.8.1", "1.8.2", "1.9", "2.0", "2.0.1", "2.1", "2.2",
+    ];
+
+    /// Whether the version, if any, is a released version of the UCUM specification.
+    pub fn is_known_version(&self) -> bool {
+        self.version
+            .as_deref()
+            .is_none_or(|v| Self::KNOWN_VERSIONS.contains(&v))
+    }
+}
+
+impl Lint for UnitOfMeasurement {
+    fn lint(&self) -> Vec<Issue> {
+        let mut issues = Vec::default();
+        if let Some(Err(e)) = self.ucum.parsed() {
+            issues.push(Issue::error("invalid-ucum", e.to_string()));
+        }
+        if !self.ucum.is_known_version() {
+            issues.push(Issue::warning(
+                "unknown-ucum-version",
+                BuildError::UnknownVersion(self.ucum.version().unwrap_or_default().into())
+                    .to_string(),
+            ));
+        }
+        if let Some(version) = self.ucum.version()
+            && self.ucum.is_known_version()
+            && !Ucum::supported_versions().contains(&version)
+        {
+            issues.push(Issue::warning(
+                "unsupported-ucum-version",
+                format!(
+                    "unit tables for UCUM version '{version}' are not embedded; supported versions are {}",
+                    Ucum::supported_versions().join(", ")
+                ),
+            ));
+        }
+        if self.ucum.is_defined_in_version() == Some(false) {
+            let unit = self.ucum.unit().unwrap_or_default();
+            issues.push(Issue::error(
+                "ucum-version-mismatch",
+                BuildError::NotInVersion {
+                    unit: unit.into(),
+                    version: self.ucum.version().unwrap_or_default().into(),
+                    earliest: Ucum::parse(unit)
+                        .map(|e| e.earliest_version())
+                        .unwrap_or_default(),
+                }
+                .to_string(),
+            ));
+        }
+        if let Some(calibration) = &self.calibration {
+            if self.ucum.unit().is_none() {
+                issues.push(Issue::error(
+                    "calibration-without-unit",
+                    BuildError::CalibrationWithoutUnit.to_string(),
+                ));
+            }
+            if !calibration.is_valid() {
+                issues.push(Issue::error(
+                    "invalid-calibration",
+                    BuildError::InvalidCalibration(*calibration).to_string(),
+                ));
+            }
+        }
+        if self.ucum.unit().is_none() && self.calibration.is_none() {
+            issues.push(Issue::warning(
+                "undeclared-unit",
+                "no UCUM unit is declared, so values are in an arbitrary unit; \
+                 declare `1` for dimensionless values or `[arb'U]` for an arbitrary unit",
+            ));
+        }
+        #[cfg(feature = "quantity-kind")]
+        if self.quantity_kind.as_ref().is_some_and(|k| k.is_empty()) {
+            issues.push(Issue::warning(
+                "empty-quantity-kind",
+                "quantity kind has neither a label nor a URI",
+            ));
+        }
+        issues
+    }
 }
 
 impl ZarrConventionImpl for UnitOfMeasurement {
@@ -71,11 +374,34 @@ impl NestedRepr for UnitOfMeasurement {
 
 register_zarr_conventions!(UnitOfMeasurement);
 
+/// Why a [Builder] could not build a valid unit.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[non_exhaustive]
+pub enum BuildError {
+    #[error("invalid UCUM unit: {0}")]
+    InvalidUnit(#[from] ParseError),
+    #[error("unknown UCUM version '{0}'; expected one of {known}", known = Ucum::KNOWN_VERSIONS.join(", "))]
+    UnknownVersion(String),
+    #[error("'{unit}' is not defined in UCUM version {version}, only from version {earliest}")]
+    NotInVersion {
+        unit: String,
+        version: String,
+        earliest: &'static str,
+    },
+    #[error("calibrated values must declare a unit")]
+    CalibrationWithoutUnit,
+    #[error("calibration must have a finite, non-zero scale and a finite offset; got {0:?}")]
+    InvalidCalibration(Calibration),
+}
+
 #[derive(Debug, Default)]
 pub struct Builder {
     unit: Option<String>,
     version: Option<String>,
     description: Option<String>,
+    calibration: Option<Calibration>,
+    #[cfg(feature = "quantity-kind")]
+    quantity_kind: Option<quantity_kind::QuantityKind>,
 }
 
 impl Builder {
@@ -98,15 +424,86 @@ impl Builder {
         self
     }
 
+    /// Set the mapping of raw stored values, e.g. detector counts,
+    /// to quantities in the declared unit.
+    pub fn calibration(mut self, calibration: Calibration) -> Self {
+        self.calibration = Some(calibration);
+        self
+    }
+
+    /// Set the kind of quantity measured, e.g. temperature.
+    ///
+    /// This is not part of v1 of the convention.
+    ///
+    /// ```
+    /// use zarrs_conventions_uom::{UnitOfMeasurement, quantity_kind::QuantityKind};
+    ///
+    /// let uom = UnitOfMeasurement::builder()
+    ///     .unit("Cel")
+    ///     .quantity_kind(QuantityKind::from_label("temperature"))
+    ///     .build();
+    /// assert_eq!(uom.quantity_kind().unwrap().label(), Some("temperature"));
+    /// ```
+    #[cfg(feature = "quantity-kind")]
+    pub fn quantity_kind(mut self, quantity_kind: quantity_kind::QuantityKind) -> Self {
+        self.quantity_kind = Some(quantity_kind);
+        self
+    }
+
     /// Build the unit.
     pub fn build(self) -> UnitOfMeasurement {
         UnitOfMeasurement {
             ucum: Ucum {
-                unit: self.unit,
-                version: self.version,
+                unit: self.unit.map(Cow::Owned),
+                version: self.version.map(Cow::Owned),
+                extras: BTreeMap::new(),
             },
-            description: self.description,
+            description: self.description.map(Cow::Owned),
+            calibration: self.calibration,
+            #[cfg(feature = "quantity-kind")]
+            quantity_kind: self.quantity_kind,
+            extras: BTreeMap::new(),
+        }
+    }
+
+    /// Build the unit, checking that the unit string is valid UCUM,
+    /// the version is a released version of UCUM which defines the unit, if its tables are embedded,
+    /// and any calibration is invertible and applies to a declared unit.
+    ///
+    /// ```
+    /// use zarrs_conventions_uom::{BuildError, UnitOfMeasurement};
+    ///
+    /// assert!(UnitOfMeasurement::builder().unit("um").build_validated().is_ok());
+    /// let err = UnitOfMeasurement::builder().unit("UM").build_validated().unwrap_err();
+    /// assert!(err.to_string().contains("did you mean 'um'?"));
+    /// let err = UnitOfMeasurement::builder().version("3").build_validated().unwrap_err();
+    /// assert_eq!(err, BuildError::UnknownVersion("3".into()));
+    /// ```
+    pub fn build_validated(self) -> Result<UnitOfMeasurement, BuildError> {
+        let expr = self.unit.as_deref().map(Ucum::parse).transpose()?;
+        if let Some(version) = self.version.as_deref() {
+            if !Ucum::KNOWN_VERSIONS.contains(&version) {
+                return Err(BuildError::UnknownVersion(version.to_string()));
+            }
+            if let Some(expr) = &expr
+                && expr.is_defined_in(version) == Some(false)
+            {
+                return Err(BuildError::NotInVersion {
+                    unit: expr.to_string(),
+                    version: version.to_string(),
+                    earliest: expr.earliest_version(),
+                });
+            }
+        }
+        if let Some(calibration) = self.calibration {
+            if self.unit.is_none() {
+                return Err(BuildError::CalibrationWithoutUnit);
+            }
+            if !calibration.is_valid() {
+                return Err(BuildError::InvalidCalibration(calibration));
+            }
         }
+        Ok(self.build())
     }
 }
 
@@ -115,3 +512,96 @@ impl From<Builder> for UnitOfMeasurement {
         value.build()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use zarrs_conventions::validate::Lint;
+
+    use crate::{ParseMode, PayloadError, UnitOfMeasurement};
+
+    #[test]
+    fn parse_modes() {
+        let value = serde_json::json!({"ucum": {"unit": "um", "x-system": 1}, "x-custom": [1]});
+        let passthrough: UnitOfMeasurement = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(passthrough.extras()["x-custom"], serde_json::json!([1]));
+        assert_eq!(passthrough.ucum().extras()["x-system"], 1);
+        assert_eq!(serde_json::to_value(&passthrough).unwrap(), value);
+
+        let lenient = UnitOfMeasurement::parse(&value, ParseMode::Lenient).unwrap();
+        assert_eq!(lenient, UnitOfMeasurement::builder().unit("um").build());
+        let strict = UnitOfMeasurement::parse(&value, ParseMode::Strict).unwrap_err();
+        assert!(matches!(strict, PayloadError::UnknownField(k) if k == "x-custom"));
+        let nested = serde_json::json!({"ucum": {"unit": "um", "x-system": 1}});
+        let strict = UnitOfMeasurement::parse(&nested, ParseMode::Strict).unwrap_err();
+        assert!(matches!(strict, PayloadError::UnknownField(k) if k == "ucum.x-system"));
+
+        assert!(matches!(
+            UnitOfMeasurement::parse(&serde_json::json!({"x-custom": 1}), ParseMode::Passthrough),
+            Err(PayloadError::Deserialize(_))
+        ));
+    }
+
+    #[test]
+    fn ucum_parsed() {
+        use crate::ucum::{Component, ErrorKind};
+
+        assert!(UnitOfMeasurement::default().ucum().parsed().is_none());
+        let uom = UnitOfMeasurement::builder().unit("mm/s").build();
+        let expr = uom.ucum().parsed().unwrap().unwrap();
+        let Component::Unit(unit) = &expr.terms[0].component else {
+            panic!("expected a unit");
+        };
+        assert_eq!((unit.prefix.unwrap().code, unit.atom.code), ("m", "m"));
+        let uom = UnitOfMeasurement::builder().unit("m/(s").build();
+        let err = uom.ucum().parsed().unwrap().unwrap_err();
+        assert_eq!((err.position, err.kind), (2, ErrorKind::Unclosed('(')));
+    }
+
+    #[test]
+    fn lint_versions() {
+        let known = UnitOfMeasurement::builder()
+            .unit("um")
+            .version("2.1")
+            .build();
+        assert!(known.lint().is_empty());
+        let unknown = UnitOfMeasurement::builder()
+            .unit("um")
+            .version("v2")
+            .build();
+        let issues = unknown.lint();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].code, "unknown-ucum-version");
+    }
+
+    #[test]
+    fn lint_undeclared() {
+        let issues = UnitOfMeasurement::default().lint();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].code, "undeclared-unit");
+        assert!(UnitOfMeasurement::arbitrary().lint().is_empty());
+        assert!(UnitOfMeasurement::DIMENSIONLESS.lint().is_empty());
+    }
+
+    #[test]
+    fn modify() {
+        let json = serde_json::json!({"ucum": {"unit": "mm", "version": "2.1"}});
+        let read: UnitOfMeasurement = serde_json::from_value(json).unwrap();
+        let mut modified = read.clone();
+        assert_eq!(modified, read);
+
+        modified
+            .set_unit(Some("um"))
+            .set_description(None::<String>);
+        assert_ne!(modified, read);
+        assert_eq!(modified.ucum().version(), Some("2.1"));
+        assert_eq!(
+            serde_json::to_value(&modified).unwrap(),
+            serde_json::json!({"ucum": {"unit": "um", "version": "2.1"}})
+        );
+
+        let units: HashSet<_> = [read, modified.clone(), modified].into_iter().collect();
+        assert_eq!(units.len(), 2);
+    }
+}
@@ -0,0 +1,126 @@
+//! Mapping to and from the unit names of [OME-NGFF](https://ngff.openmicroscopy.org/latest/#axes-md) axes.
+//!
+//! OME-NGFF spells out UDUNITS-2 names for space and time units, e.g. `micrometer`,
+//! where this convention uses UCUM codes, e.g. `um`.
+use crate::{Ucum, UnitOfMeasurement};
+
+/// Units allowed on OME-NGFF axes, and their UCUM codes.
+static UNITS: &[(&str, &str)] = &[
+    // space
+    ("angstrom", "Ao"),
+    ("attometer", "am"),
+    ("centimeter", "cm"),
+    ("decimeter", "dm"),
+    ("exameter", "Em"),
+    ("femtometer", "fm"),
+    ("foot", "[ft_i]"),
+    ("gigameter", "Gm"),
+    ("hectometer", "hm"),
+    ("inch", "[in_i]"),
+    ("kilometer", "km"),
+    ("megameter", "Mm"),
+    ("meter", "m"),
+    ("micrometer", "um"),
+    ("mile", "[mi_i]"),
+    ("millimeter", "mm"),
+    ("nanometer", "nm"),
+    ("parsec", "pc"),
+    ("petameter", "Pm"),
+    ("picometer", "pm"),
+    ("terameter", "Tm"),
+    ("yard", "[yd_i]"),
+    ("yoctometer", "ym"),
+    ("yottameter", "Ym"),
+    ("zeptometer", "zm"),
+    ("zettameter", "Zm"),
+    // time
+    ("attosecond", "as"),
+    ("centisecond", "cs"),
+    ("day", "d"),
+    ("decisecond", "ds"),
+    ("exasecond", "Es"),
+    ("femtosecond", "fs"),
+    ("gigasecond", "Gs"),
+    ("hectosecond", "hs"),
+    ("hour", "h"),
+    ("kilosecond", "ks"),
+    ("megasecond", "Ms"),
+    ("microsecond", "us"),
+    ("millisecond", "ms"),
+    ("minute", "min"),
+    ("nanosecond", "ns"),
+    ("petasecond", "Ps"),
+    ("picosecond", "ps"),
+    ("second", "s"),
+    ("terasecond", "Ts"),
+    ("yoctosecond", "ys"),
+    ("yottasecond", "Ys"),
+    ("zeptosecond", "zs"),
+    ("zettasecond", "Zs"),
+];
+
+/// UCUM code for an OME-NGFF axis unit name.
+///
+/// ```
+/// use zarrs_conventions_uom::ome::ucum_from_ome;
+///
+/// assert_eq!(ucum_from_ome("micrometer"), Some("um"));
+/// assert_eq!(ucum_from_ome("micrometre"), None);
+/// ```
+pub fn ucum_from_ome(name: &str) -> Option<&'static str> {
+    UNITS.iter().find(|(n, _)| *n == name).map(|(_, u)| *u)
+}
+
+/// OME-NGFF axis unit name for a UCUM unit, if it has one.
+///
+/// Equivalent spellings of the unit are accepted, e.g. `s` as well as `(s)`.
+///
+/// ```
+/// use zarrs_conventions_uom::ome::ome_from_ucum;
+///
+/// assert_eq!(ome_from_ucum("us"), Some("microsecond"));
+/// assert_eq!(ome_from_ucum("m/s"), None);
+/// ```
+pub fn ome_from_ucum(unit: &str) -> Option<&'static str> {
+    let canonical = Ucum::parse(unit).ok()?.canonicalize().to_string();
+    UNITS.iter().find(|(_, u)| *u == canonical).map(|(n, _)| *n)
+}
+
+impl UnitOfMeasurement {
+    /// The unit for an OME-NGFF axis unit name.
+    ///
+    /// ```
+    /// use zarrs_conventions_uom::UnitOfMeasurement;
+    ///
+    /// let uom = UnitOfMeasurement::from_ome_unit("nanometer").unwrap();
+    /// assert_eq!(uom.ucum().unit(), Some("nm"));
+    /// assert_eq!(uom.to_ome_unit(), Some("nanometer"));
+    /// ```
+    pub fn from_ome_unit(name: &str) -> Option<Self> {
+        Some(Self::builder().unit(ucum_from_ome(name)?).build())
+    }
+
+    /// OME-NGFF axis unit name of the declared unit, if it has one.
+    pub fn to_ome_unit(&self) -> Option<&'static str> {
+        ome_from_ucum(self.ucum().unit()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{UNITS, ome_from_ucum, ucum_from_ome};
+    use crate::Ucum;
+
+    #[test]
+    fn ome_units() {
+        for (name, ucum) in UNITS {
+            let expr = Ucum::parse(ucum).unwrap();
+            assert_eq!(expr.canonicalize().to_string(), *ucum);
+            assert_eq!(ome_from_ucum(ucum), Some(*name));
+            assert_eq!(ucum_from_ome(name), Some(*ucum));
+        }
+        assert_eq!(ome_from_ucum("10*-6.m"), None);
+        assert_eq!(ome_from_ucum("UM"), None);
+        assert_eq!(ucum_from_ome("um"), None);
+    }
+}
@@ -0,0 +1,284 @@
+//! Unit strings in the syntax of Python's [pint](https://pint.readthedocs.io/) library.
+//!
+//! Written strings use pint's default unit registry, e.g. `kg / m ** 2 / s`,
+//! and can be passed directly to `pint.UnitRegistry()`.
+use crate::{
+    Ucum, UnitOfMeasurement,
+    syntax::{self, Mapping, SyntaxError, name, symbol},
+    ucum::{Component, Op, ParseError, Unit, UnitExpr},
+};
+
+/// Why a unit could not be translated to or from pint.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[non_exhaustive]
+pub enum PintError {
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+    #[error("no unit is declared")]
+    Undeclared,
+    #[error("'{0}' has no pint equivalent")]
+    NoEquivalent(String),
+    #[error("'{0}' is not a known pint unit")]
+    UnknownUnit(String),
+    #[error("invalid pint unit string '{unit}' at position {position}")]
+    Syntax { unit: String, position: usize },
+}
+
+static UNITS: &[Mapping] = &[
+    symbol("m", "m", &["meter", "metre"]),
+    symbol("s", "s", &["second", "sec"]),
+    symbol("g", "g", &["gram"]),
+    symbol("K", "K", &["kelvin"]),
+    symbol("mol", "mol", &["mole"]),
+    symbol("cd", "cd", &["candela"]),
+    symbol("A", "A", &["ampere", "amp"]),
+    symbol("rad", "rad", &["radian"]),
+    symbol("sr", "sr", &["steradian"]),
+    symbol("Hz", "Hz", &["hertz"]),
+    symbol("N", "N", &["newton"]),
+    symbol("Pa", "Pa", &["pascal"]),
+    symbol("J", "J", &["joule"]),
+    symbol("W", "W", &["watt"]),
+    symbol("C", "C", &["coulomb"]),
+    symbol("V", "V", &["volt"]),
+    symbol("F", "F", &["farad"]),
+    name("Ohm", "ohm", &["Ω"]),
+    symbol("S", "S", &["siemens"]),
+    symbol("Wb", "Wb", &["weber"]),
+    symbol("T", "T", &["tesla"]),
+    symbol("H", "H", &["henry"]),
+    symbol("lm", "lm", &["lumen"]),
+    symbol("lx", "lx", &["lux"]),
+    symbol("Bq", "Bq", &["becquerel"]),
+    symbol("Gy", "Gy", &["gray"]),
+    symbol("Sv", "Sv", &["sievert"]),
+    symbol("kat", "kat", &["katal"]),
+    symbol("L", "L", &["l", "liter", "litre"]),
+    symbol("min", "min", &["minute"]),
+    symbol("h", "h", &["hour", "hr"]),
+    symbol("d", "d", &["day"]),
+    name("wk", "week", &[]),
+    name("mo", "month", &[]),
+    name("a", "year", &["yr", "a"]),
+    name("a_j", "julian_year", &[]),
+    name("a_g", "gregorian_year", &[]),
+    name("a_t", "tropical_year", &[]),
+    symbol("t", "t", &["tonne", "metric_ton"]),
+    symbol("bar", "bar", &[]),
+    symbol("atm", "atm", &["atmosphere"]),
+    symbol("eV", "eV", &["electron_volt"]),
+    symbol("cal", "cal", &["calorie"]),
+    symbol("M", "M", &["molar"]),
+    symbol("b", "b", &["barn"]),
+    name("deg", "degree", &["deg"]),
+    name("'", "arcminute", &["arcmin"]),
+    name("''", "arcsecond", &["arcsec"]),
+    symbol("Cel", "degC", &["degree_Celsius", "celsius"]),
+    symbol("[degF]", "degF", &["degree_Fahrenheit", "fahrenheit"]),
+    symbol("[degR]", "degR", &["degree_Rankine", "rankine"]),
+    name("%", "percent", &["%"]),
+    name("[ppm]", "ppm", &[]),
+    name("[pi]", "pi", &[]),
+    name("Ao", "angstrom", &["Å"]),
+    name("AU", "astronomical_unit", &["au"]),
+    symbol("pc", "pc", &["parsec"]),
+    name("[ly]", "light_year", &["ly"]),
+    name("[in_i]", "inch", &["in"]),
+    name("[ft_i]", "foot", &["ft", "feet"]),
+    name("[yd_i]", "yard", &["yd"]),
+    name("[mi_i]", "mile", &["mi"]),
+    name("[nmi_i]", "nautical_mile", &["nmi"]),
+    name("[kn_i]", "knot", &["kt"]),
+    name("[lb_av]", "pound", &["lb"]),
+    name("[oz_av]", "ounce", &["oz"]),
+    name("[gr]", "grain", &["gr"]),
+    name("[psi]", "psi", &[]),
+    name("[gal_us]", "gallon", &["gal"]),
+    name("[HP]", "horsepower", &["hp"]),
+    name("[Btu]", "Btu", &[]),
+    symbol("bit", "bit", &[]),
+    name("By", "byte", &["B"]),
+    name("Bd", "baud", &[]),
+];
+
+/// pint spelling of a UCUM unit, without its exponent.
+fn pint_spelling(unit: &Unit) -> Result<String, PintError> {
+    let mapping = UNITS
+        .iter()
+        .find(|m| m.ucum == unit.atom.code)
+        .ok_or_else(|| PintError::NoEquivalent(unit.to_string()))?;
+    let prefix = match unit.prefix {
+        None => "",
+        Some(p) if mapping.symbol => p.code,
+        Some(p) => p.name,
+    };
+    Ok(format!("{prefix}{}", mapping.spelling))
+}
+
+/// UCUM code for a pint spelling, without any prefix.
+fn lookup(spelling: &str) -> Option<(&'static str, bool, ())> {
+    UNITS
+        .iter()
+        .find(|m| m.spelling == spelling || m.aliases.contains(&spelling))
+        .map(|m| (m.ucum, m.symbol, ()))
+}
+
+impl UnitExpr {
+    /// The unit as a pint string.
+    ///
+    /// Annotations, which do not affect the meaning of a unit, are dropped.
+    /// Any magnitude is written as a leading number, which pint reads as a quantity.
+    ///
+    /// ```
+    /// use zarrs_conventions_uom::ucum::UnitExpr;
+    ///
+    /// let expr: UnitExpr = "kg/(m2.s)".parse().unwrap();
+    /// assert_eq!(expr.to_pint_string().unwrap(), "kg / m ** 2 / s");
+    /// ```
+    pub fn to_pint_string(&self) -> Result<String, PintError> {
+        let (mut magnitude, expr) = self.split_magnitude();
+        let mut out = String::default();
+        for term in &expr.terms {
+            let sign = match term.op {
+                Op::Multiply => 1,
+                Op::Divide => -1,
+            };
+            let Component::Unit(unit) = &term.component else {
+                if let Component::Factor(n) = &term.component {
+                    magnitude *= (*n as f64).powi(sign);
+                }
+                continue;
+            };
+            let operator = match (out.is_empty(), term.op) {
+                (true, Op::Multiply) => "",
+                (true, Op::Divide) => "1 / ",
+                (false, Op::Multiply) => " * ",
+                (false, Op::Divide) => " / ",
+            };
+            out.push_str(operator);
+            out.push_str(&pint_spelling(unit)?);
+            if unit.exponent != 1 {
+                out.push_str(&format!(" ** {}", unit.exponent));
+            }
+        }
+        if magnitude != 1.0 || out.is_empty() {
+            let number = syntax::format_number(magnitude);
+            out = match out.strip_prefix("1 / ") {
+                Some(rest) => format!("{number} / {rest}"),
+                None if out.is_empty() => number,
+                None => format!("{number} * {out}"),
+            };
+        }
+        Ok(out)
+    }
+}
+
+impl UnitOfMeasurement {
+    /// The declared unit as a pint string; see [UnitExpr::to_pint_string].
+    ///
+    /// ```
+    /// use zarrs_conventions_uom::UnitOfMeasurement;
+    ///
+    /// let uom = UnitOfMeasurement::builder().unit("um/s").build();
+    /// assert_eq!(uom.to_pint_string().unwrap(), "um / s");
+    /// ```
+    pub fn to_pint_string(&self) -> Result<String, PintError> {
+        self.ucum()
+            .parsed()
+            .ok_or(PintError::Undeclared)??
+            .to_pint_string()
+    }
+
+    /// The unit for a pint unit string, written by pint as e.g. `str(quantity.units)`.
+    ///
+    /// ```
+    /// use zarrs_conventions_uom::UnitOfMeasurement;
+    ///
+    /// let uom = UnitOfMeasurement::from_pint_string("micrometer / second ** 2").unwrap();
+    /// assert_eq!(uom.ucum().unit(), Some("um/s2"));
+    /// ```
+    pub fn from_pint_string(unit: &str) -> Result<Self, PintError> {
+        let unit = unit.trim();
+        if unit.is_empty() || unit == "dimensionless" {
+            return Ok(Self::builder().unit("1").build());
+        }
+        let ucum = syntax::parse(unit, |identifier| {
+            syntax::resolve(identifier, |_| true, lookup).map(|(ucum, _)| ucum)
+        })
+        .map_err(|e| match e {
+            SyntaxError::Invalid { position } => PintError::Syntax {
+                unit: unit.to_string(),
+                position,
+            },
+            SyntaxError::UnknownUnit(u) => PintError::UnknownUnit(u),
+        })?;
+        let canonical = Ucum::parse(&ucum)?.canonicalize().to_string();
+        Ok(Self::builder().unit(canonical).build())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PintError;
+    use crate::UnitOfMeasurement;
+
+    fn to(unit: &str) -> String {
+        UnitOfMeasurement::builder()
+            .unit(unit)
+            .build()
+            .to_pint_string()
+            .unwrap()
+    }
+
+    fn from(unit: &str) -> String {
+        let uom = UnitOfMeasurement::from_pint_string(unit).unwrap();
+        uom.ucum().unit().unwrap().to_string()
+    }
+
+    #[test]
+    fn to_pint() {
+        assert_eq!(to("m"), "m");
+        assert_eq!(to("m/s2"), "m / s ** 2");
+        assert_eq!(to("/s"), "1 / s");
+        assert_eq!(to("kOhm"), "kiloohm");
+        assert_eq!(to("KiBy"), "kibibyte");
+        assert_eq!(to("Cel"), "degC");
+        assert_eq!(to("{cells}/uL"), "1 / uL");
+        assert_eq!(to("10*-3.m"), "1e-3 * m");
+        assert_eq!(to("1000/s"), "1000 / s");
+        assert_eq!(to("10*3"), "1000");
+        assert_eq!(to("1"), "1");
+        assert!(matches!(
+            UnitOfMeasurement::builder()
+                .unit("[IU]")
+                .build()
+                .to_pint_string(),
+            Err(PintError::NoEquivalent(_))
+        ));
+    }
+
+    #[test]
+    fn from_pint() {
+        assert_eq!(from("meter"), "m");
+        assert_eq!(from("kilometer / hour"), "km/h");
+        assert_eq!(from("kg * m ** 2 / s ** 2"), "kg.m2/s2");
+        assert_eq!(from("1 / second"), "/s");
+        assert_eq!(from("µm"), "um");
+        assert_eq!(from("micrometers"), "um");
+        assert_eq!(from("degree_Celsius"), "Cel");
+        assert_eq!(from("kibibyte"), "KiBy");
+        assert_eq!(from("dimensionless"), "1");
+        assert_eq!(
+            UnitOfMeasurement::from_pint_string("furlong / fortnight").unwrap_err(),
+            PintError::UnknownUnit("furlong".into())
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        for ucum in ["m/s", "kg/m2/s", "Cel", "um", "[in_i]", "h", "KiBy", "/s"] {
+            let pint = to(ucum);
+            assert_eq!(from(&pint), ucum, "via {pint}");
+        }
+    }
+}
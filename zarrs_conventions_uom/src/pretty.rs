@@ -0,0 +1,381 @@
+//! Human-readable renderings of units, for reports and plot labels.
+use crate::{
+    Ucum, UnitOfMeasurement,
+    syntax::format_number,
+    table::Atom,
+    ucum::{Component, Op, ParseError, Unit, UnitExpr},
+};
+
+/// Unicode symbols for atoms whose UCUM codes are ASCII stand-ins.
+static SYMBOLS: &[(&str, &str)] = &[
+    ("Cel", "°C"),
+    ("[degF]", "°F"),
+    ("[degR]", "°R"),
+    ("deg", "°"),
+    ("'", "′"),
+    ("''", "″"),
+    ("Ao", "Å"),
+    ("Ohm", "Ω"),
+    ("[pi]", "π"),
+    ("10*", "10"),
+    ("10^", "10"),
+];
+
+/// siunitx macros for atoms, where it defines one.
+static SIUNITX: &[(&str, &str)] = &[
+    ("m", "metre"),
+    ("s", "second"),
+    ("g", "gram"),
+    ("rad", "radian"),
+    ("K", "kelvin"),
+    ("C", "coulomb"),
+    ("cd", "candela"),
+    ("mol", "mole"),
+    ("sr", "steradian"),
+    ("Hz", "hertz"),
+    ("N", "newton"),
+    ("Pa", "pascal"),
+    ("J", "joule"),
+    ("W", "watt"),
+    ("A", "ampere"),
+    ("V", "volt"),
+    ("F", "farad"),
+    ("Ohm", "ohm"),
+    ("S", "siemens"),
+    ("Wb", "weber"),
+    ("Cel", "degreeCelsius"),
+    ("[degF]", "degree\\text{F}"),
+    ("[degR]", "degree\\text{R}"),
+    ("T", "tesla"),
+    ("H", "henry"),
+    ("lm", "lumen"),
+    ("lx", "lux"),
+    ("Bq", "becquerel"),
+    ("Gy", "gray"),
+    ("Sv", "sievert"),
+    ("kat", "katal"),
+    ("deg", "degree"),
+    ("'", "arcminute"),
+    ("''", "arcsecond"),
+    ("l", "litre"),
+    ("L", "litre"),
+    ("min", "minute"),
+    ("h", "hour"),
+    ("d", "day"),
+    ("t", "tonne"),
+    ("bar", "bar"),
+    ("u", "dalton"),
+    ("eV", "electronvolt"),
+    ("AU", "astronomicalunit"),
+    ("Ao", "angstrom"),
+    ("b", "barn"),
+    ("[nmi_i]", "nauticalmile"),
+    ("[kn_i]", "knot"),
+    ("Np", "neper"),
+    ("B", "bel"),
+    ("%", "percent"),
+    ("bit", "bit"),
+    ("By", "byte"),
+];
+
+fn superscript(n: i32) -> String {
+    n.to_string()
+        .chars()
+        .map(|c| match c {
+            '-' => '⁻',
+            '0' => '⁰',
+            '1' => '¹',
+            '2' => '²',
+            '3' => '³',
+            '4' => '⁴',
+            '5' => '⁵',
+            '6' => '⁶',
+            '7' => '⁷',
+            '8' => '⁸',
+            _ => '⁹',
+        })
+        .collect()
+}
+
+fn atom_symbol(atom: &Atom) -> &str {
+    match SYMBOLS.iter().find(|(code, _)| *code == atom.code) {
+        Some((_, symbol)) => symbol,
+        None => atom
+            .code
+            .strip_prefix('[')
+            .and_then(|c| c.strip_suffix(']'))
+            .map_or(atom.code, |c| c.split('_').next().unwrap_or(c)),
+    }
+}
+
+fn unit_symbol(unit: &Unit, exponent: i32) -> String {
+    let prefix = match unit.prefix {
+        Some(p) if p.code == "u" => "µ",
+        Some(p) => p.code,
+        None => "",
+    };
+    let mut out = format!("{prefix}{}", atom_symbol(unit.atom));
+    if exponent != 1 {
+        out.push_str(&superscript(exponent));
+    }
+    if let Some(annotation) = &unit.annotation {
+        out.push_str(&format!("{{{annotation}}}"));
+    }
+    out
+}
+
+fn unit_name(unit: &Unit) -> String {
+    let prefix = unit.prefix.map_or("", |p| p.name);
+    let name = format!("{prefix}{}", unit.atom.name);
+    match unit.exponent {
+        1 => name,
+        2 => format!("{name} squared"),
+        3 => format!("{name} cubed"),
+        n => format!("{name} to the power {n}"),
+    }
+}
+
+/// ASCII text for an atom without a siunitx macro, e.g. `mHg` for `m[Hg]` or `pi` for `[pi]`.
+fn atom_text(atom: &Atom) -> String {
+    let symbol = atom_symbol(atom);
+    let symbol = if symbol.is_ascii() { symbol } else { atom.code };
+    symbol.replace(['[', ']'], "")
+}
+
+fn unit_siunitx(unit: &Unit, exponent: i32) -> String {
+    let mut out = String::default();
+    if exponent < 0 {
+        out.push_str("\\per");
+    }
+    match SIUNITX.iter().find(|(code, _)| *code == unit.atom.code) {
+        Some((_, name)) => {
+            if let Some(prefix) = unit.prefix {
+                out.push_str(&format!("\\{}", prefix.name));
+            }
+            out.push_str(&format!("\\{name}"));
+        }
+        // siunitx prefixes only apply to its own units, so the prefix joins the text
+        None => {
+            let prefix = match unit.prefix {
+                Some(p) if p.code == "u" => "\\textmu{}",
+                Some(p) => p.code,
+                None => "",
+            };
+            out.push_str(&format!("\\text{{{prefix}{}}}", atom_text(unit.atom)));
+        }
+    }
+    match exponent.abs() {
+        1 => (),
+        2 => out.push_str("\\squared"),
+        3 => out.push_str("\\cubed"),
+        n => out.push_str(&format!("\\tothe{{{n}}}")),
+    }
+    out
+}
+
+impl UnitExpr {
+    /// The unit for typesetting with the LaTeX package siunitx,
+    /// e.g. `\si{\micro\metre}` or `\si{\metre\per\second}`.
+    ///
+    /// A magnitude is typeset as a number with `\SI`, e.g. `\SI{1e-6}{\metre}`;
+    /// annotations are dropped, and atoms without a siunitx macro are written as `\text`.
+    ///
+    /// ```
+    /// use zarrs_conventions_uom::ucum::UnitExpr;
+    ///
+    /// let expr: UnitExpr = "kg.m/s2".parse().unwrap();
+    /// assert_eq!(expr.to_siunitx(), r"\si{\kilo\gram\metre\per\second\squared}");
+    /// ```
+    pub fn to_siunitx(&self) -> String {
+        let (magnitude, expr) = self.split_magnitude();
+        let units: String = expr
+            .terms
+            .iter()
+            .filter_map(|term| match &term.component {
+                Component::Unit(unit) => {
+                    let sign = match term.op {
+                        Op::Multiply => 1,
+                        Op::Divide => -1,
+                    };
+                    Some(unit_siunitx(unit, sign * unit.exponent))
+                }
+                _ => None,
+            })
+            .collect();
+        if magnitude == 1.0 {
+            format!("\\si{{{units}}}")
+        } else {
+            format!("\\SI{{{}}}{{{units}}}", format_number(magnitude))
+        }
+    }
+
+    /// The unit in Unicode symbols, with superscript exponents and no division,
+    /// e.g. `µm` or `m·s⁻¹`.
+    ///
+    /// ```
+    /// use zarrs_conventions_uom::ucum::UnitExpr;
+    ///
+    /// let expr: UnitExpr = "kg.m/s2".parse().unwrap();
+    /// assert_eq!(expr.to_unicode(), "kg·m·s⁻²");
+    /// ```
+    pub fn to_unicode(&self) -> String {
+        let canonical = self.canonicalize();
+        let parts: Vec<_> = canonical
+            .terms
+            .iter()
+            .map(|term| {
+                let sign = match term.op {
+                    Op::Multiply => 1,
+                    Op::Divide => -1,
+                };
+                match &term.component {
+                    Component::Unit(unit) => unit_symbol(unit, sign * unit.exponent),
+                    Component::Factor(n) if sign > 0 => n.to_string(),
+                    Component::Factor(n) => format!("{n}⁻¹"),
+                    Component::Annotation(a) if sign > 0 => format!("{{{a}}}"),
+                    Component::Annotation(a) => format!("{{{a}}}⁻¹"),
+                    Component::Group(_) => unreachable!("canonical expressions have no groups"),
+                }
+            })
+            .collect();
+        parts.join("·")
+    }
+
+    /// The unit in words, e.g. `micrometre` or `metre per second`.
+    ///
+    /// ```
+    /// use zarrs_conventions_uom::ucum::UnitExpr;
+    ///
+    /// let expr: UnitExpr = "kg.m/s2".parse().unwrap();
+    /// assert_eq!(expr.to_name(), "kilogram metre per second squared");
+    /// ```
+    pub fn to_name(&self) -> String {
+        let (magnitude, expr) = self.split_magnitude();
+        let mut out = String::default();
+        if magnitude != 1.0 {
+            out.push_str(&format_number(magnitude));
+        }
+        for term in &expr.terms {
+            let word = match &term.component {
+                Component::Unit(unit) => unit_name(unit),
+                Component::Annotation(a) => a.clone(),
+                // only the `1` of a dimensionless unit remains
+                Component::Factor(n) if out.is_empty() => n.to_string(),
+                Component::Factor(_) => continue,
+                Component::Group(_) => unreachable!("canonical expressions have no groups"),
+            };
+            let separator = match (out.is_empty(), term.op) {
+                (true, Op::Multiply) => "",
+                (true, Op::Divide) => "per ",
+                (false, Op::Multiply) => " ",
+                (false, Op::Divide) => " per ",
+            };
+            out.push_str(separator);
+            out.push_str(&word);
+        }
+        out
+    }
+}
+
+impl Ucum {
+    /// The unit [in Unicode symbols](UnitExpr::to_unicode), if one is declared.
+    ///
+    /// ```
+    /// use zarrs_conventions_uom::UnitOfMeasurement;
+    ///
+    /// let uom = UnitOfMeasurement::builder().unit("um").build();
+    /// assert_eq!(uom.ucum().to_unicode(), Some(Ok("µm".to_string())));
+    /// ```
+    pub fn to_unicode(&self) -> Option<Result<String, ParseError>> {
+        Some(self.parsed()?.map(|expr| expr.to_unicode()))
+    }
+
+    /// The unit [for siunitx](UnitExpr::to_siunitx), if one is declared.
+    pub fn to_siunitx(&self) -> Option<Result<String, ParseError>> {
+        Some(self.parsed()?.map(|expr| expr.to_siunitx()))
+    }
+
+    /// The unit [in words](UnitExpr::to_name), if one is declared.
+    ///
+    /// ```
+    /// use zarrs_conventions_uom::UnitOfMeasurement;
+    ///
+    /// let uom = UnitOfMeasurement::builder().unit("um").build();
+    /// assert_eq!(uom.ucum().to_name(), Some(Ok("micrometre".to_string())));
+    /// ```
+    pub fn to_name(&self) -> Option<Result<String, ParseError>> {
+        Some(self.parsed()?.map(|expr| expr.to_name()))
+    }
+}
+
+impl UnitOfMeasurement {
+    /// The declared unit [for siunitx](UnitExpr::to_siunitx), if one is declared.
+    ///
+    /// ```
+    /// use zarrs_conventions_uom::UnitOfMeasurement;
+    ///
+    /// let siunitx = UnitOfMeasurement::MICROMETER.to_siunitx();
+    /// assert_eq!(siunitx, Some(Ok(r"\si{\micro\metre}".to_string())));
+    /// ```
+    pub fn to_siunitx(&self) -> Option<Result<String, ParseError>> {
+        self.ucum().to_siunitx()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ucum::UnitExpr;
+
+    #[test]
+    fn siunitx() {
+        let cases = [
+            ("um", r"\si{\micro\metre}"),
+            ("m/s", r"\si{\metre\per\second}"),
+            ("Cel", r"\si{\degreeCelsius}"),
+            ("kOhm", r"\si{\kilo\ohm}"),
+            ("m4/s", r"\si{\metre\tothe{4}\per\second}"),
+            ("[in_i]", r"\si{\text{in}}"),
+            ("mm[Hg]", r"\si{\text{mmHg}}"),
+            ("um[Hg]", r"\si{\text{\textmu{}mHg}}"),
+            ("[degF]", r"\si{\degree\text{F}}"),
+            ("[pi].rad", r"\si{\text{pi}\radian}"),
+            ("10*-6.m3", r"\SI{1e-6}{\metre\cubed}"),
+            ("{cells}/uL", r"\si{\per\micro\litre}"),
+            ("1", r"\si{}"),
+        ];
+        for (unit, siunitx) in cases {
+            let expr: UnitExpr = unit.parse().unwrap();
+            assert_eq!(expr.to_siunitx(), siunitx, "{unit}");
+        }
+    }
+
+    fn render(unit: &str) -> (String, String) {
+        let expr: UnitExpr = unit.parse().unwrap();
+        (expr.to_unicode(), expr.to_name())
+    }
+
+    #[test]
+    fn renderings() {
+        let cases = [
+            ("um", "µm", "micrometre"),
+            ("m/s", "m·s⁻¹", "metre per second"),
+            ("/s", "s⁻¹", "per second"),
+            ("m2", "m²", "metre squared"),
+            ("Cel", "°C", "degree Celsius"),
+            ("kOhm", "kΩ", "kiloohm"),
+            ("[in_i]", "in", "inch"),
+            ("10*-6.m", "10⁻⁶·m", "1e-6 metre"),
+            ("{cells}/uL", "{cells}·µL⁻¹", "cells per microlitre"),
+            ("mg{dry}", "mg{dry}", "milligram"),
+            (
+                "m4/s10",
+                "m⁴·s⁻¹⁰",
+                "metre to the power 4 per second to the power 10",
+            ),
+            ("1", "1", "1"),
+        ];
+        for (unit, unicode, name) in cases {
+            assert_eq!(render(unit), (unicode.into(), name.into()), "{unit}");
+        }
+    }
+}
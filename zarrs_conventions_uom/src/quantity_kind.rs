@@ -0,0 +1,117 @@
+//! What kind of quantity was measured, which the unit alone often does not say.
+//!
+//! This is not part of v1 of the convention.
+use serde::{Deserialize, Serialize};
+use zarrs_conventions::iref::{Uri, UriBuf};
+
+/// Kind of quantity measured, e.g. temperature or fluorescence intensity,
+/// as a free-text label and/or a URI in an ontology such as
+/// [QUDT](https://qudt.org/vocab/quantitykind/) or [OM](https://github.com/HajoRijgersberg/OM).
+///
+/// ```
+/// use zarrs_conventions_uom::quantity_kind::QuantityKind;
+/// use zarrs_conventions_uom::zarrs_conventions::iref::UriBuf;
+///
+/// let uri = UriBuf::new("http://qudt.org/vocab/quantitykind/Temperature".into()).unwrap();
+/// let kind = QuantityKind::from_label("temperature").with_uri(uri);
+/// assert_eq!(kind.label(), Some("temperature"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+pub struct QuantityKind {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    label: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    uri: Option<UriBuf>,
+}
+
+impl QuantityKind {
+    /// Quantity kind described by a free-text label.
+    pub fn from_label(label: impl Into<String>) -> Self {
+        Self {
+            label: Some(label.into()),
+            uri: None,
+        }
+    }
+
+    /// Quantity kind identified by an ontology URI.
+    pub fn from_uri(uri: UriBuf) -> Self {
+        Self {
+            label: None,
+            uri: Some(uri),
+        }
+    }
+
+    /// Add a free-text label.
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Add an ontology URI.
+    pub fn with_uri(mut self, uri: UriBuf) -> Self {
+        self.uri = Some(uri);
+        self
+    }
+
+    /// Free-text label, e.g. `fluorescence intensity`.
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// URI of the quantity kind in an ontology,
+    /// e.g. `http://qudt.org/vocab/quantitykind/Temperature`.
+    pub fn uri(&self) -> Option<&Uri> {
+        self.uri.as_deref()
+    }
+
+    /// Whether neither a label nor a URI is given.
+    pub fn is_empty(&self) -> bool {
+        self.label.is_none() && self.uri.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zarrs_conventions::{iref::UriBuf, validate::Lint};
+
+    use super::QuantityKind;
+    use crate::UnitOfMeasurement;
+
+    #[test]
+    fn round_trip() {
+        let uri = UriBuf::new("http://qudt.org/vocab/quantitykind/Temperature".into()).unwrap();
+        let uom = UnitOfMeasurement::builder()
+            .unit("Cel")
+            .quantity_kind(QuantityKind::from_uri(uri).with_label("temperature"))
+            .build();
+        let json = serde_json::to_value(&uom).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "ucum": {"unit": "Cel"},
+                "quantity_kind": {
+                    "label": "temperature",
+                    "uri": "http://qudt.org/vocab/quantitykind/Temperature",
+                },
+            })
+        );
+        let read: UnitOfMeasurement = serde_json::from_value(json).unwrap();
+        let kind = read.quantity_kind().unwrap();
+        assert_eq!(kind.label(), Some("temperature"));
+        assert_eq!(
+            kind.uri().unwrap().as_str(),
+            "http://qudt.org/vocab/quantitykind/Temperature"
+        );
+    }
+
+    #[test]
+    fn lint_empty() {
+        let uom = UnitOfMeasurement::builder()
+            .unit("Cel")
+            .quantity_kind(QuantityKind::default())
+            .build();
+        let issues = uom.lint();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].code, "empty-quantity-kind");
+    }
+}
@@ -0,0 +1,360 @@
+//! The products of powers of named units used by other unit systems,
+//! e.g. `kg m-2 s-1` in UDUNITS or `kg / m ** 2 / s` in pint.
+use crate::table::{Atom, PREFIXES, Prefix};
+
+/// Why a unit string could not be read.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum SyntaxError {
+    Invalid { position: usize },
+    UnknownUnit(String),
+}
+
+/// A UCUM unit atom and its spellings in another system.
+pub(crate) struct Mapping {
+    pub ucum: &'static str,
+    /// Spelling used when writing the other system.
+    pub spelling: &'static str,
+    /// Whether the spelling is a symbol, which takes prefix symbols rather than names.
+    pub symbol: bool,
+    /// Other spellings accepted when reading the other system.
+    pub aliases: &'static [&'static str],
+}
+
+pub(crate) const fn symbol(
+    ucum: &'static str,
+    spelling: &'static str,
+    aliases: &'static [&'static str],
+) -> Mapping {
+    Mapping {
+        ucum,
+        spelling,
+        symbol: true,
+        aliases,
+    }
+}
+
+pub(crate) const fn name(
+    ucum: &'static str,
+    spelling: &'static str,
+    aliases: &'static [&'static str],
+) -> Mapping {
+    Mapping {
+        ucum,
+        spelling,
+        symbol: false,
+        aliases,
+    }
+}
+
+/// A number as written in other unit systems.
+pub(crate) fn format_number(n: f64) -> String {
+    if n.fract() == 0.0 && n.abs() < 1e15 {
+        format!("{n}")
+    } else {
+        format!("{n:e}")
+    }
+}
+
+fn metric(ucum: &str) -> bool {
+    Atom::from_code(ucum).is_some_and(|a| a.metric)
+}
+
+/// Like `lookup`, but also accepting plurals of spelled-out names, e.g. `meters`.
+fn lookup_plural<T>(
+    spelling: &str,
+    lookup: &impl Fn(&str) -> Option<(&'static str, bool, T)>,
+) -> Option<(&'static str, bool, T)> {
+    lookup(spelling).or_else(|| {
+        let singular = spelling.strip_suffix('s')?;
+        if singular.len() < 3 || !singular.chars().all(|c| c.is_lowercase() || c == '_') {
+            return None;
+        }
+        lookup(singular)
+    })
+}
+
+/// UCUM code for a unit name or symbol, possibly prefixed.
+///
+/// `lookup` gives the UCUM code for an unprefixed spelling,
+/// and whether it is a symbol, which takes prefix symbols rather than names.
+pub(crate) fn resolve<T>(
+    identifier: &str,
+    prefixes: impl Fn(&Prefix) -> bool,
+    lookup: impl Fn(&str) -> Option<(&'static str, bool, T)>,
+) -> Option<(String, T)> {
+    if let Some((ucum, _, extra)) = lookup(identifier) {
+        return Some((ucum.into(), extra));
+    }
+    for prefix in PREFIXES.iter().filter(|p| prefixes(p)) {
+        let symbol = identifier.strip_prefix(prefix.code).or_else(|| {
+            identifier
+                .strip_prefix(['µ', 'μ'])
+                .filter(|_| prefix.code == "u")
+        });
+        if let Some((ucum, true, extra)) = symbol.and_then(&lookup)
+            && metric(ucum)
+        {
+            return Some((format!("{}{ucum}", prefix.code), extra));
+        }
+    }
+    if let Some((ucum, _, extra)) = lookup_plural(identifier, &lookup) {
+        return Some((ucum.into(), extra));
+    }
+    for prefix in PREFIXES.iter().filter(|p| prefixes(p)) {
+        let name = identifier.strip_prefix(prefix.name);
+        if let Some((ucum, _, extra)) = name.and_then(|n| lookup_plural(n, &lookup))
+            && metric(ucum)
+        {
+            return Some((format!("{}{ucum}", prefix.code), extra));
+        }
+    }
+    None
+}
+
+/// Read a product of powers of units as an equivalent UCUM string,
+/// with `resolve` giving the UCUM code for each unit.
+///
+/// Units may be multiplied with `.`, `*`, `·`, or whitespace, and divided with `/`;
+/// exponents may follow `^` or `**`, or directly follow a unit, e.g. `s-1`.
+pub(crate) fn parse(
+    unit: &str,
+    resolve: impl FnMut(&str) -> Option<String>,
+) -> Result<String, SyntaxError> {
+    let mut parser = Parser {
+        unit,
+        chars: unit.char_indices().collect(),
+        index: 0,
+        resolve,
+    };
+    let value = parser.product()?;
+    if parser.peek().is_some() {
+        return Err(parser.error());
+    }
+    value.to_ucum().ok_or(SyntaxError::Invalid { position: 0 })
+}
+
+/// A product of a number and powers of UCUM units.
+#[derive(Debug, Clone)]
+struct Value {
+    factor: f64,
+    units: Vec<(String, i32)>,
+}
+
+impl Value {
+    const ONE: Self = Self {
+        factor: 1.0,
+        units: Vec::new(),
+    };
+
+    fn mul(mut self, other: Self, sign: i32) -> Self {
+        self.factor *= other.factor.powi(sign);
+        self.units
+            .extend(other.units.into_iter().map(|(u, e)| (u, sign * e)));
+        self
+    }
+
+    fn pow(mut self, exponent: i32) -> Self {
+        self.factor = self.factor.powi(exponent);
+        for (_, e) in self.units.iter_mut() {
+            *e *= exponent;
+        }
+        self
+    }
+
+    /// Equivalent UCUM string.
+    fn to_ucum(&self) -> Option<String> {
+        let mut parts = Vec::default();
+        if self.factor != 1.0 {
+            if !self.factor.is_finite() || self.factor <= 0.0 {
+                return None;
+            }
+            // as an integer and a power of ten, e.g. 1.5e-3 as 15.10*-4
+            let scientific = format!("{:e}", self.factor);
+            let (mantissa, exponent) = scientific.split_once('e')?;
+            let mut exponent: i32 = exponent.parse().ok()?;
+            let digits = match mantissa.split_once('.') {
+                Some((int, frac)) => {
+                    exponent -= frac.len() as i32;
+                    format!("{int}{frac}")
+                }
+                None => mantissa.to_string(),
+            };
+            if digits != "1" {
+                parts.push(digits);
+            }
+            if exponent != 0 {
+                parts.push(format!("10*{exponent}"));
+            }
+        }
+        for (unit, exponent) in &self.units {
+            match exponent {
+                1 => parts.push(unit.clone()),
+                e => parts.push(format!("{unit}{e}")),
+            }
+        }
+        if parts.is_empty() {
+            return Some("1".into());
+        }
+        Some(parts.join("."))
+    }
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphabetic() || matches!(c, '_' | '%' | '°')
+}
+
+/// Parser for the product grammar.
+struct Parser<'a, F> {
+    unit: &'a str,
+    chars: Vec<(usize, char)>,
+    index: usize,
+    resolve: F,
+}
+
+impl<F: FnMut(&str) -> Option<String>> Parser<'_, F> {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.index).map(|(_, c)| *c)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.index + offset).map(|(_, c)| *c)
+    }
+
+    fn position(&self) -> usize {
+        self.chars
+            .get(self.index)
+            .map_or(self.unit.len(), |(i, _)| *i)
+    }
+
+    fn error(&self) -> SyntaxError {
+        SyntaxError::Invalid {
+            position: self.position(),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.index += 1;
+        }
+    }
+
+    fn take_while(&mut self, f: impl Fn(char) -> bool) -> String {
+        let mut out = String::default();
+        while let Some(c) = self.peek().filter(|c| f(*c)) {
+            out.push(c);
+            self.index += 1;
+        }
+        out
+    }
+
+    fn product(&mut self) -> Result<Value, SyntaxError> {
+        self.skip_whitespace();
+        let mut acc = self.power()?;
+        loop {
+            self.skip_whitespace();
+            let sign = match self.peek() {
+                None | Some(')') => return Ok(acc),
+                Some('/') => {
+                    self.index += 1;
+                    -1
+                }
+                Some('.' | '·') => {
+                    self.index += 1;
+                    1
+                }
+                Some('*') if self.peek_at(1) != Some('*') => {
+                    self.index += 1;
+                    1
+                }
+                // juxtaposition
+                Some(_) => 1,
+            };
+            self.skip_whitespace();
+            acc = acc.mul(self.power()?, sign);
+        }
+    }
+
+    fn integer(&mut self) -> Result<i32, SyntaxError> {
+        let mut digits = String::default();
+        if let Some(sign @ ('-' | '+')) = self.peek() {
+            digits.push(sign);
+            self.index += 1;
+        }
+        digits.push_str(&self.take_while(|c| c.is_ascii_digit()));
+        digits.parse().map_err(|_| self.error())
+    }
+
+    fn number(&mut self) -> Result<f64, SyntaxError> {
+        let mut number = self.take_while(|c| c.is_ascii_digit());
+        if self.peek() == Some('.') && self.peek_at(1).is_some_and(|c| c.is_ascii_digit()) {
+            self.index += 1;
+            number.push('.');
+            number.push_str(&self.take_while(|c| c.is_ascii_digit()));
+        }
+        let exponent = match (self.peek_at(1), self.peek_at(2)) {
+            (Some(c), _) if c.is_ascii_digit() => true,
+            (Some('-' | '+'), Some(c)) => c.is_ascii_digit(),
+            _ => false,
+        };
+        if matches!(self.peek(), Some('e' | 'E')) && exponent {
+            self.index += 1;
+            number.push('e');
+            number.push_str(&self.integer()?.to_string());
+        }
+        number.parse().map_err(|_| self.error())
+    }
+
+    fn power(&mut self) -> Result<Value, SyntaxError> {
+        let base = match self.peek() {
+            Some('(') => {
+                self.index += 1;
+                let inner = self.product()?;
+                if self.peek() != Some(')') {
+                    return Err(self.error());
+                }
+                self.index += 1;
+                inner
+            }
+            Some(c) if c.is_ascii_digit() => {
+                return Ok(Value {
+                    factor: self.number()?,
+                    ..Value::ONE
+                });
+            }
+            Some(c) if is_identifier_char(c) => {
+                let identifier = self.take_while(is_identifier_char);
+                let ucum = (self.resolve)(&identifier)
+                    .ok_or_else(|| SyntaxError::UnknownUnit(identifier.clone()))?;
+                Value {
+                    factor: 1.0,
+                    units: vec![(ucum, 1)],
+                }
+            }
+            _ => return Err(self.error()),
+        };
+        let start = self.index;
+        self.skip_whitespace();
+        let operator = match (self.peek(), self.peek_at(1)) {
+            (Some('^'), _) => 1,
+            (Some('*'), Some('*')) => 2,
+            _ => 0,
+        };
+        let exponent = if operator > 0 {
+            self.index += operator;
+            self.skip_whitespace();
+            Some(self.integer()?)
+        } else {
+            // exponents without an operator directly follow the unit, e.g. `s-1`
+            self.index = start;
+            match (self.peek(), self.peek_at(1)) {
+                (Some(c), _) if c.is_ascii_digit() => Some(self.integer()?),
+                (Some('-' | '+'), Some(c)) if c.is_ascii_digit() => Some(self.integer()?),
+                _ => None,
+            }
+        };
+        Ok(match exponent {
+            Some(e) => base.pow(e),
+            None => base,
+        })
+    }
+}
@@ -0,0 +1,343 @@
+//! Prefixes and unit atoms from the UCUM tables.
+//!
+//! Not all of UCUM's atoms are included: those for clinical and pharmaceutical quantities,
+//! and most historical customary units, are omitted.
+
+/// A UCUM prefix, which scales a metric unit atom.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Prefix {
+    /// Case-sensitive code, e.g. `u` for micro.
+    pub code: &'static str,
+    /// Name, e.g. `micro`.
+    pub name: &'static str,
+    /// 10 for SI prefixes, 2 for binary prefixes.
+    pub base: u32,
+    /// Power of the base by which the prefix scales a unit.
+    pub exponent: i32,
+}
+
+/// How a unit atom is defined.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum Definition {
+    /// One of UCUM's base units: `m`, `s`, `g`, `rad`, `K`, `C`, and `cd`.
+    Base,
+    /// A multiple of a UCUM unit expression.
+    Derived { magnitude: f64, unit: &'static str },
+    /// Related to a UCUM unit expression by something other than a factor,
+    /// e.g. an offset or a logarithm.
+    Special { unit: &'static str },
+    /// A unit not defined in terms of others, which cannot be converted.
+    Arbitrary,
+}
+
+/// A UCUM unit atom.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Atom {
+    /// Case-sensitive code, e.g. `m` for metre.
+    pub code: &'static str,
+    /// Name, e.g. `metre`.
+    pub name: &'static str,
+    /// Whether the atom may take a prefix.
+    pub metric: bool,
+    pub definition: Definition,
+}
+
+const fn prefix(code: &'static str, name: &'static str, base: u32, exponent: i32) -> Prefix {
+    Prefix {
+        code,
+        name,
+        base,
+        exponent,
+    }
+}
+
+pub(crate) static PREFIXES: &[Prefix] = &[
+    prefix("Y", "yotta", 10, 24),
+    prefix("Z", "zetta", 10, 21),
+    prefix("E", "exa", 10, 18),
+    prefix("P", "peta", 10, 15),
+    prefix("T", "tera", 10, 12),
+    prefix("G", "giga", 10, 9),
+    prefix("M", "mega", 10, 6),
+    prefix("k", "kilo", 10, 3),
+    prefix("h", "hecto", 10, 2),
+    prefix("da", "deka", 10, 1),
+    prefix("d", "deci", 10, -1),
+    prefix("c", "centi", 10, -2),
+    prefix("m", "milli", 10, -3),
+    prefix("u", "micro", 10, -6),
+    prefix("n", "nano", 10, -9),
+    prefix("p", "pico", 10, -12),
+    prefix("f", "femto", 10, -15),
+    prefix("a", "atto", 10, -18),
+    prefix("z", "zepto", 10, -21),
+    prefix("y", "yocto", 10, -24),
+    prefix("R", "ronna", 10, 27),
+    prefix("Q", "quetta", 10, 30),
+    prefix("r", "ronto", 10, -27),
+    prefix("q", "quecto", 10, -30),
+    prefix("Ki", "kibi", 2, 10),
+    prefix("Mi", "mebi", 2, 20),
+    prefix("Gi", "gibi", 2, 30),
+    prefix("Ti", "tebi", 2, 40),
+];
+
+/// Codes of prefixes and atoms added in each UCUM version after the earliest supported version.
+pub(crate) static ADDED: &[(&str, &[&str])] = &[("2.2", &["R", "Q", "r", "q"])];
+
+const fn base(code: &'static str, name: &'static str) -> Atom {
+    Atom {
+        code,
+        name,
+        metric: true,
+        definition: Definition::Base,
+    }
+}
+
+const fn metric(
+    code: &'static str,
+    name: &'static str,
+    magnitude: f64,
+    unit: &'static str,
+) -> Atom {
+    Atom {
+        code,
+        name,
+        metric: true,
+        definition: Definition::Derived { magnitude, unit },
+    }
+}
+
+const fn other(code: &'static str, name: &'static str, magnitude: f64, unit: &'static str) -> Atom {
+    Atom {
+        code,
+        name,
+        metric: false,
+        definition: Definition::Derived { magnitude, unit },
+    }
+}
+
+const fn special(code: &'static str, name: &'static str, metric: bool, unit: &'static str) -> Atom {
+    Atom {
+        code,
+        name,
+        metric,
+        definition: Definition::Special { unit },
+    }
+}
+
+const fn arbitrary(code: &'static str, name: &'static str) -> Atom {
+    Atom {
+        code,
+        name,
+        metric: false,
+        definition: Definition::Arbitrary,
+    }
+}
+
+pub(crate) static ATOMS: &[Atom] = &[
+    // base units
+    base("m", "metre"),
+    base("s", "second"),
+    base("g", "gram"),
+    base("rad", "radian"),
+    base("K", "kelvin"),
+    base("C", "coulomb"),
+    base("cd", "candela"),
+    // dimensionless
+    other("10*", "the number ten for arbitrary powers", 10.0, "1"),
+    other("10^", "the number ten for arbitrary powers", 10.0, "1"),
+    other("[pi]", "the number pi", std::f64::consts::PI, "1"),
+    other("%", "percent", 1.0, "10*-2"),
+    other("[ppth]", "parts per thousand", 1.0, "10*-3"),
+    other("[ppm]", "parts per million", 1.0, "10*-6"),
+    other("[ppb]", "parts per billion", 1.0, "10*-9"),
+    other("[pptr]", "parts per trillion", 1.0, "10*-12"),
+    // SI units
+    metric("mol", "mole", 6.022_136_7, "10*23"),
+    metric("sr", "steradian", 1.0, "rad2"),
+    metric("Hz", "hertz", 1.0, "s-1"),
+    metric("N", "newton", 1.0, "kg.m/s2"),
+    metric("Pa", "pascal", 1.0, "N/m2"),
+    metric("J", "joule", 1.0, "N.m"),
+    metric("W", "watt", 1.0, "J/s"),
+    metric("A", "ampere", 1.0, "C/s"),
+    metric("V", "volt", 1.0, "J/C"),
+    metric("F", "farad", 1.0, "C/V"),
+    metric("Ohm", "ohm", 1.0, "V/A"),
+    metric("S", "siemens", 1.0, "Ohm-1"),
+    metric("Wb", "weber", 1.0, "V.s"),
+    special("Cel", "degree Celsius", true, "K"),
+    metric("T", "tesla", 1.0, "Wb/m2"),
+    metric("H", "henry", 1.0, "Wb/A"),
+    metric("lm", "lumen", 1.0, "cd.sr"),
+    metric("lx", "lux", 1.0, "lm/m2"),
+    metric("Bq", "becquerel", 1.0, "s-1"),
+    metric("Gy", "gray", 1.0, "J/kg"),
+    metric("Sv", "sievert", 1.0, "J/kg"),
+    // other units accepted for use with SI
+    other("gon", "gon", 0.9, "deg"),
+    other("deg", "degree", 2.0, "[pi].rad/360"),
+    other("'", "minute", 1.0, "deg/60"),
+    other("''", "second", 1.0, "'/60"),
+    metric("l", "litre", 1.0, "dm3"),
+    metric("L", "litre", 1.0, "l"),
+    metric("ar", "are", 100.0, "m2"),
+    other("min", "minute", 60.0, "s"),
+    other("h", "hour", 60.0, "min"),
+    other("d", "day", 24.0, "h"),
+    other("a_t", "tropical year", 365.242_19, "d"),
+    other("a_j", "mean Julian year", 365.25, "d"),
+    other("a_g", "mean Gregorian year", 365.2425, "d"),
+    other("a", "year", 1.0, "a_j"),
+    other("wk", "week", 7.0, "d"),
+    other("mo_s", "synodal month", 29.530_59, "d"),
+    other("mo_j", "mean Julian month", 1.0, "a_j/12"),
+    other("mo_g", "mean Gregorian month", 1.0, "a_g/12"),
+    other("mo", "month", 1.0, "mo_j"),
+    metric("t", "tonne", 1e3, "kg"),
+    metric("bar", "bar", 1e5, "Pa"),
+    metric("u", "unified atomic mass unit", 1.660_540_2e-24, "g"),
+    metric("eV", "electronvolt", 1.0, "[e].V"),
+    other("AU", "astronomic unit", 149_597.870_691, "Mm"),
+    metric("pc", "parsec", 3.085_678e16, "m"),
+    // natural units
+    metric("[c]", "velocity of light", 299_792_458.0, "m/s"),
+    metric("[h]", "Planck constant", 6.626_075_5e-34, "J.s"),
+    metric("[k]", "Boltzmann constant", 1.380_658e-23, "J/K"),
+    metric(
+        "[eps_0]",
+        "permittivity of vacuum",
+        8.854_187_817e-12,
+        "F/m",
+    ),
+    metric("[mu_0]", "permeability of vacuum", 1.0, "4.[pi].10*-7.N/A2"),
+    metric("[e]", "elementary charge", 1.602_177_33e-19, "C"),
+    metric("[m_e]", "electron mass", 9.109_389_7e-28, "g"),
+    metric("[m_p]", "proton mass", 1.672_623_1e-24, "g"),
+    metric(
+        "[G]",
+        "Newtonian constant of gravitation",
+        6.672_59e-11,
+        "m3.kg-1.s-2",
+    ),
+    metric(
+        "[g]",
+        "standard acceleration of free fall",
+        9.806_65,
+        "m/s2",
+    ),
+    other("atm", "standard atmosphere", 101_325.0, "Pa"),
+    metric("[ly]", "light-year", 1.0, "[c].a_j"),
+    metric("gf", "gram-force", 1.0, "g.[g]"),
+    // CGS units
+    other("Ao", "ångström", 0.1, "nm"),
+    metric("b", "barn", 100.0, "fm2"),
+    other("att", "technical atmosphere", 1.0, "kgf/cm2"),
+    metric("mho", "mho", 1.0, "S"),
+    metric("Ky", "kayser", 1.0, "cm-1"),
+    metric("Gal", "gal", 1.0, "cm/s2"),
+    metric("dyn", "dyne", 1.0, "g.cm/s2"),
+    metric("erg", "erg", 1.0, "dyn.cm"),
+    metric("P", "poise", 1.0, "dyn.s/cm2"),
+    metric("Bi", "biot", 10.0, "A"),
+    metric("St", "stokes", 1.0, "cm2/s"),
+    metric("Mx", "maxwell", 1e-8, "Wb"),
+    metric("G", "gauss", 1e-4, "T"),
+    metric("Oe", "oersted", 250.0, "/[pi].A/m"),
+    metric("Gb", "gilbert", 1.0, "Oe.cm"),
+    metric("sb", "stilb", 1.0, "cd/cm2"),
+    metric("Lmb", "lambert", 1.0, "cd/cm2/[pi]"),
+    metric("ph", "phot", 1e-4, "lx"),
+    metric("Ci", "curie", 3.7e10, "Bq"),
+    metric("R", "roentgen", 2.58e-4, "C/kg"),
+    metric("RAD", "radiation absorbed dose", 100.0, "erg/g"),
+    metric("REM", "radiation equivalent man", 1.0, "RAD"),
+    // international customary units
+    other("[in_i]", "inch", 2.54, "cm"),
+    other("[ft_i]", "foot", 12.0, "[in_i]"),
+    other("[yd_i]", "yard", 3.0, "[ft_i]"),
+    other("[mi_i]", "mile", 5280.0, "[ft_i]"),
+    other("[nmi_i]", "nautical mile", 1852.0, "m"),
+    other("[kn_i]", "knot", 1.0, "[nmi_i]/h"),
+    other("[sin_i]", "square inch", 1.0, "[in_i]2"),
+    other("[sft_i]", "square foot", 1.0, "[ft_i]2"),
+    other("[syd_i]", "square yard", 1.0, "[yd_i]2"),
+    other("[cin_i]", "cubic inch", 1.0, "[in_i]3"),
+    other("[cft_i]", "cubic foot", 1.0, "[ft_i]3"),
+    other("[cyd_i]", "cubic yard", 1.0, "[yd_i]3"),
+    other("[mil_i]", "mil", 1e-3, "[in_i]"),
+    other("[gr]", "grain", 64.798_91, "mg"),
+    other("[lb_av]", "pound", 7000.0, "[gr]"),
+    other("[oz_av]", "ounce", 1.0, "[lb_av]/16"),
+    other("[ston_av]", "short ton", 2000.0, "[lb_av]"),
+    other("[lton_av]", "long ton", 2240.0, "[lb_av]"),
+    other("[lbf_av]", "pound force", 1.0, "[lb_av].[g]"),
+    other("[psi]", "pound per square inch", 1.0, "[lbf_av]/[in_i]2"),
+    other("[gal_us]", "US gallon", 231.0, "[cin_i]"),
+    other("[qt_us]", "US quart", 1.0, "[gal_us]/4"),
+    other("[pt_us]", "US pint", 1.0, "[qt_us]/2"),
+    other("[foz_us]", "US fluid ounce", 1.0, "[pt_us]/16"),
+    other("[gal_br]", "imperial gallon", 4.546_09, "l"),
+    other("[pt_br]", "imperial pint", 1.0, "[gal_br]/8"),
+    other("[acr_us]", "acre", 160.0, "[rd_us]2"),
+    other("[rd_us]", "rod", 16.5, "[ft_us]"),
+    other("[ft_us]", "US survey foot", 1200.0, "m/3937"),
+    // heat
+    special("[degF]", "degree Fahrenheit", false, "K"),
+    other("[degR]", "degree Rankine", 5.0, "K/9"),
+    special("[degRe]", "degree Réaumur", false, "K"),
+    metric("cal", "calorie", 4.184, "J"),
+    other("[Cal]", "nutrition label Calorie", 1.0, "kcal"),
+    other("[Btu]", "British thermal unit", 1.055_055_852_62, "kJ"),
+    other("[HP]", "horsepower", 550.0, "[ft_i].[lbf_av]/s"),
+    // pressure
+    metric("m[Hg]", "metre of mercury column", 133.322, "kPa"),
+    metric("m[H2O]", "metre of water column", 9.806_65, "kPa"),
+    other("[in_i'Hg]", "inch of mercury column", 1.0, "m[Hg].[in_i]/m"),
+    // chemistry
+    metric("M", "molar", 1.0, "mol/l"),
+    metric("osm", "osmole", 1.0, "mol"),
+    metric("eq", "equivalent", 1.0, "mol"),
+    metric("kat", "katal", 1.0, "mol/s"),
+    metric("U", "unit", 1.0, "umol/min"),
+    special("[pH]", "pH", false, "mol/l"),
+    // levels
+    special("Np", "neper", true, "1"),
+    special("B", "bel", true, "1"),
+    special("B[SPL]", "bel sound pressure", true, "10*-5.Pa"),
+    special("B[V]", "bel volt", true, "V"),
+    special("B[mV]", "bel millivolt", true, "mV"),
+    special("B[uV]", "bel microvolt", true, "uV"),
+    special("B[W]", "bel watt", true, "W"),
+    special("B[kW]", "bel kilowatt", true, "kW"),
+    // information technology
+    special("bit_s", "bit", false, "1"),
+    metric("bit", "bit", 1.0, "1"),
+    metric("By", "byte", 8.0, "bit"),
+    metric("Bd", "baud", 1.0, "/s"),
+    // arbitrary units
+    arbitrary("[arb'U]", "arbitrary unit"),
+    arbitrary("[IU]", "international unit"),
+    arbitrary("[iU]", "international unit"),
+];
+
+impl Prefix {
+    /// Factor by which the prefix scales a unit.
+    pub fn value(&self) -> f64 {
+        f64::from(self.base).powi(self.exponent)
+    }
+
+    /// The prefix with the given case-sensitive code.
+    pub fn from_code(code: &str) -> Option<&'static Self> {
+        PREFIXES.iter().find(|p| p.code == code)
+    }
+}
+
+impl Atom {
+    /// The unit atom with the given case-sensitive code.
+    pub fn from_code(code: &str) -> Option<&'static Self> {
+        ATOMS.iter().find(|a| a.code == code)
+    }
+}
@@ -0,0 +1,254 @@
+//! Interoperability with the [uom](https://docs.rs/uom) crate's typed quantities.
+use uom::{
+    ConstantOp, Conversion,
+    si::{self, f64::*},
+};
+
+use crate::{UnitOfMeasurement, convert::ConversionError, ucum::UnitExpr};
+
+/// A quantity type from the uom crate.
+pub trait UcumQuantity: Sized {
+    /// UCUM code of the SI unit the quantity is stored in.
+    const UCUM: &'static str;
+
+    /// The quantity with the given value in that unit.
+    fn from_si(value: f64) -> Self;
+}
+
+macro_rules! ucum_quantities {
+    ($($quantity:ident, $module:ident :: $unit:ident, $ucum:literal;)*) => {
+        $(
+            impl UcumQuantity for $quantity {
+                const UCUM: &'static str = $ucum;
+
+                fn from_si(value: f64) -> Self {
+                    $quantity::new::<si::$module::$unit>(value)
+                }
+            }
+
+            /// One of the declared unit.
+            impl TryFrom<&UnitOfMeasurement> for $quantity {
+                type Error = ConversionError;
+
+                fn try_from(value: &UnitOfMeasurement) -> Result<Self, Self::Error> {
+                    value.quantity(1.0)
+                }
+            }
+        )*
+    };
+}
+
+ucum_quantities! {
+    Length, length::meter, "m";
+    Time, time::second, "s";
+    Mass, mass::kilogram, "kg";
+    ElectricCurrent, electric_current::ampere, "A";
+    TemperatureInterval, temperature_interval::kelvin, "K";
+    ThermodynamicTemperature, thermodynamic_temperature::kelvin, "K";
+    AmountOfSubstance, amount_of_substance::mole, "mol";
+    LuminousIntensity, luminous_intensity::candela, "cd";
+    Angle, angle::radian, "rad";
+    Ratio, ratio::ratio, "1";
+    Area, area::square_meter, "m2";
+    Volume, volume::cubic_meter, "m3";
+    MassDensity, mass_density::kilogram_per_cubic_meter, "kg/m3";
+    Velocity, velocity::meter_per_second, "m/s";
+    Acceleration, acceleration::meter_per_second_squared, "m/s2";
+    Frequency, frequency::hertz, "Hz";
+    Force, force::newton, "N";
+    Pressure, pressure::pascal, "Pa";
+    Energy, energy::joule, "J";
+    Power, power::watt, "W";
+    ElectricCharge, electric_charge::coulomb, "C";
+    ElectricPotential, electric_potential::volt, "V";
+}
+
+/// UCUM codes of units whose uom abbreviations are not valid UCUM.
+const ABBREVIATIONS: &[(&str, &str)] = &[
+    ("in", "[in_i]"),
+    ("ft", "[ft_i]"),
+    ("yd", "[yd_i]"),
+    ("mi", "[mi_i]"),
+    ("lb", "[lb_av]"),
+    ("oz", "[oz_av]"),
+    ("gr", "[gr]"),
+    ("kn", "[kn_i]"),
+    ("psi", "[psi]"),
+];
+
+/// Likely UCUM form of a uom unit abbreviation.
+fn ucum_from_abbreviation(abbreviation: &str) -> String {
+    let replaced = abbreviation
+        .replace("°C", "Cel")
+        .replace("°F", "[degF]")
+        .replace("°R", "[degR]")
+        .replace('°', "deg")
+        .replace('µ', "u")
+        .replace('Ω', "Ohm")
+        .replace('Å', "Ao")
+        .replace(" · ", ".")
+        .replace('·', ".")
+        .replace('⁻', "-")
+        .replace('¹', "1")
+        .replace('²', "2")
+        .replace('³', "3");
+    let mut out = String::with_capacity(replaced.len());
+    let mut word = String::default();
+    for c in replaced.chars().chain(std::iter::once('.')) {
+        if c.is_ascii_alphabetic() {
+            word.push(c);
+            continue;
+        }
+        let known = ABBREVIATIONS.iter().find(|(abbr, _)| *abbr == word);
+        out.push_str(known.map_or(word.as_str(), |(_, ucum)| ucum));
+        word.clear();
+        out.push(c);
+    }
+    out.pop();
+    match out.as_str() {
+        "" => "1".to_string(),
+        _ => out,
+    }
+}
+
+impl UnitOfMeasurement {
+    /// A value in the declared unit as a typed quantity.
+    ///
+    /// ```
+    /// use zarrs_conventions_uom::UnitOfMeasurement;
+    /// use uom::si::{f64::Length, length::nanometer};
+    ///
+    /// let uom = UnitOfMeasurement::builder().unit("um").build();
+    /// let length: Length = uom.quantity(1.5).unwrap();
+    /// assert!((length.get::<nanometer>() - 1500.0).abs() < 1e-9);
+    /// ```
+    pub fn quantity<Q: UcumQuantity>(&self, value: f64) -> Result<Q, ConversionError> {
+        let si: UnitExpr = Q::UCUM.parse().expect("quantity units are valid");
+        let conversion = self.ucum().expr()?.conversion_to(&si)?;
+        Ok(Q::from_si(conversion.apply(value)))
+    }
+
+    /// The UCUM unit equivalent to a uom unit of the quantity `Q`, if there is one.
+    ///
+    /// ```
+    /// use zarrs_conventions_uom::UnitOfMeasurement;
+    /// use uom::si::{f64::{Length, Velocity}, length::micrometer, velocity::kilometer_per_hour};
+    ///
+    /// let um = UnitOfMeasurement::from_uom_unit::<Length, micrometer>().unwrap();
+    /// assert_eq!(um.ucum().unit(), Some("um"));
+    /// let kmh = UnitOfMeasurement::from_uom_unit::<Velocity, kilometer_per_hour>().unwrap();
+    /// assert_eq!(kmh.ucum().unit(), Some("km/h"));
+    /// ```
+    pub fn from_uom_unit<Q, U>() -> Option<Self>
+    where
+        Q: UcumQuantity,
+        U: si::Unit + Conversion<f64, T = f64>,
+    {
+        let ucum = ucum_from_abbreviation(U::abbreviation());
+        let expr: UnitExpr = ucum.parse().ok()?;
+        let si: UnitExpr = Q::UCUM.parse().expect("quantity units are valid");
+        // check that the abbreviation means the same in UCUM
+        match expr.conversion_to(&si) {
+            Ok(conversion) => {
+                let coefficient = U::coefficient();
+                let constant = U::constant(ConstantOp::Add);
+                // units without a constant may be intervals, e.g. of temperature,
+                // to which the UCUM unit's offset does not apply
+                if (constant != 0.0 && (conversion.offset - constant).abs() > 1e-6 * constant.abs())
+                    || (conversion.scale - coefficient).abs() > 1e-6 * coefficient.abs()
+                {
+                    return None;
+                }
+            }
+            Err(ConversionError::NonLinear(_)) => {}
+            Err(_) => return None,
+        }
+        Some(Self::builder().unit(ucum).build())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uom::si::{
+        f64::{Length, Mass, Pressure, Ratio, ThermodynamicTemperature, Time, Velocity},
+        length::{angstrom, inch, kilometer, nautical_mile},
+        mass::{gram, pound},
+        pressure::{kilopascal, pascal},
+        ratio::percent,
+        thermodynamic_temperature::{self, degree_celsius},
+        time::{hour, second, year},
+        velocity::meter_per_second,
+    };
+
+    use super::ucum_from_abbreviation;
+    use crate::UnitOfMeasurement;
+
+    fn ucum(uom: Option<UnitOfMeasurement>) -> Option<String> {
+        Some(uom?.ucum().unit()?.to_string())
+    }
+
+    #[test]
+    fn from_uom_units() {
+        assert_eq!(ucum_from_abbreviation("m/s²"), "m/s2");
+        assert_eq!(ucum_from_abbreviation("µm · s"), "um.s");
+        assert_eq!(ucum_from_abbreviation("lb/in²"), "[lb_av]/[in_i]2");
+
+        assert_eq!(
+            ucum(UnitOfMeasurement::from_uom_unit::<Length, kilometer>()),
+            Some("km".into())
+        );
+        assert_eq!(
+            ucum(UnitOfMeasurement::from_uom_unit::<Length, inch>()),
+            Some("[in_i]".into())
+        );
+        assert_eq!(
+            ucum(UnitOfMeasurement::from_uom_unit::<Length, angstrom>()),
+            Some("Ao".into())
+        );
+        assert_eq!(
+            ucum(UnitOfMeasurement::from_uom_unit::<Mass, gram>()),
+            Some("g".into())
+        );
+        assert_eq!(
+            ucum(UnitOfMeasurement::from_uom_unit::<Ratio, percent>()),
+            Some("%".into())
+        );
+        assert_eq!(
+            ucum(UnitOfMeasurement::from_uom_unit::<
+                ThermodynamicTemperature,
+                degree_celsius,
+            >()),
+            Some("Cel".into())
+        );
+        assert_eq!(
+            ucum(UnitOfMeasurement::from_uom_unit::<Mass, pound>()),
+            Some("[lb_av]".into())
+        );
+        // uom's year has 365 days; UCUM's has 365.25
+        assert!(UnitOfMeasurement::from_uom_unit::<Time, year>().is_none());
+        // 'M' is the molar in UCUM
+        assert!(UnitOfMeasurement::from_uom_unit::<Length, nautical_mile>().is_none());
+    }
+
+    #[test]
+    fn to_uom_quantities() {
+        let mmhg = UnitOfMeasurement::builder().unit("mm[Hg]").build();
+        let pressure: Pressure = mmhg.quantity(760.0).unwrap();
+        assert!((pressure.get::<kilopascal>() - 101.325).abs() < 1e-3);
+        assert!(Time::try_from(&mmhg).is_err());
+
+        let kmh = UnitOfMeasurement::builder().unit("km/h").build();
+        let velocity = Velocity::try_from(&kmh).unwrap();
+        assert!((velocity.get::<meter_per_second>() - 1.0 / 3.6).abs() < 1e-12);
+
+        let h = UnitOfMeasurement::builder().unit("h").build();
+        assert_eq!(Time::try_from(&h).unwrap().get::<second>(), 3600.0);
+        assert_eq!(Time::try_from(&h).unwrap().get::<hour>(), 1.0);
+        let pa = Pressure::try_from(&UnitOfMeasurement::builder().unit("Pa").build());
+        assert_eq!(pa.unwrap().get::<pascal>(), 1.0);
+
+        let celsius = UnitOfMeasurement::builder().unit("Cel").build();
+        let temperature: ThermodynamicTemperature = celsius.quantity(25.0).unwrap();
+        assert!((temperature.get::<thermodynamic_temperature::kelvin>() - 298.15).abs() < 1e-9);
+    }
+}
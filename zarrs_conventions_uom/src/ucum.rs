@@ -0,0 +1,582 @@
+//! Parsing and validation of [UCUM](https://ucum.org/ucum) unit strings.
+use std::{fmt, str::FromStr};
+
+use crate::table::{ATOMS, PREFIXES};
+pub use crate::table::{Atom, Definition, Prefix};
+
+/// Parsed UCUM unit expression: a sequence of components,
+/// each multiplying or dividing everything before it.
+///
+/// ```
+/// use zarrs_conventions_uom::ucum::{Component, Op, UnitExpr};
+///
+/// let expr: UnitExpr = "um/s2".parse().unwrap();
+/// assert_eq!(expr.terms.len(), 2);
+/// assert_eq!(expr.terms[1].op, Op::Divide);
+/// let Component::Unit(unit) = &expr.terms[0].component else {
+///     panic!("expected a unit");
+/// };
+/// assert_eq!(unit.prefix.unwrap().name, "micro");
+/// assert_eq!(unit.atom.name, "metre");
+/// assert_eq!(expr.to_string(), "um/s2");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnitExpr {
+    pub terms: Vec<Term>,
+}
+
+/// How a component combines with the expression before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    /// `.`, or the first component of an expression.
+    Multiply,
+    /// `/`
+    Divide,
+}
+
+/// A component of a unit expression, with how it combines with the components before it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Term {
+    pub op: Op,
+    pub component: Component,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Component {
+    /// A (possibly prefixed) unit atom, e.g. `um2{cells}`.
+    Unit(Unit),
+    /// A positive integer factor, e.g. `1000`.
+    Factor(u64),
+    /// An annotation without a unit, which is equivalent to `1`, e.g. `{count}`.
+    Annotation(String),
+    /// A parenthesised expression.
+    Group(UnitExpr),
+}
+
+/// A (possibly prefixed) unit atom, raised to an exponent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Unit {
+    pub prefix: Option<&'static Prefix>,
+    pub atom: &'static Atom,
+    /// Exponent applying to the prefixed unit, e.g. 2 for `cm2`.
+    pub exponent: i32,
+    /// Text in curly braces, which does not affect the unit's meaning.
+    pub annotation: Option<String>,
+}
+
+/// Why a UCUM unit string could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    #[error("unit is empty")]
+    Empty,
+    #[error("unexpected end of unit")]
+    UnexpectedEnd,
+    #[error("unexpected character '{0}'")]
+    UnexpectedChar(char),
+    #[error("'{0}' is never closed")]
+    Unclosed(char),
+    #[error("unknown unit '{0}'")]
+    UnknownUnit(String),
+    #[error("unknown unit '{found}'; UCUM is case-sensitive: did you mean '{suggestion}'?")]
+    WrongCase { found: String, suggestion: String },
+    #[error("unit '{atom}' cannot take the prefix '{prefix}'")]
+    NonMetric { prefix: String, atom: String },
+    #[error("number is too large")]
+    Overflow,
+}
+
+/// A UCUM unit string which could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid UCUM unit '{unit}' at position {position}: {kind}")]
+pub struct ParseError {
+    /// The unit string.
+    pub unit: String,
+    /// Byte offset at which the problem was found.
+    pub position: usize,
+    pub kind: ErrorKind,
+}
+
+impl FromStr for UnitExpr {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser { s, pos: 0 };
+        parser.main().map_err(|(position, kind)| ParseError {
+            unit: s.to_string(),
+            position,
+            kind,
+        })
+    }
+}
+
+type PResult<T> = Result<T, (usize, ErrorKind)>;
+
+struct Parser<'a> {
+    s: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.s[self.pos..].chars().next()
+    }
+
+    fn unexpected<T>(&self) -> PResult<T> {
+        Err((
+            self.pos,
+            self.peek()
+                .map_or(ErrorKind::UnexpectedEnd, ErrorKind::UnexpectedChar),
+        ))
+    }
+
+    fn main(&mut self) -> PResult<UnitExpr> {
+        if self.s.is_empty() {
+            return Err((0, ErrorKind::Empty));
+        }
+        let expr = self.term(false)?;
+        match self.peek() {
+            None => Ok(expr),
+            Some(_) => self.unexpected(),
+        }
+    }
+
+    /// Components separated by operators, until the end of the string
+    /// or, if `nested`, a closing parenthesis.
+    fn term(&mut self, nested: bool) -> PResult<UnitExpr> {
+        let mut terms = Vec::default();
+        let mut op = Op::Multiply;
+        if !nested && self.peek() == Some('/') {
+            op = Op::Divide;
+            self.pos += 1;
+        }
+        loop {
+            let component = self.component()?;
+            terms.push(Term { op, component });
+            op = match self.peek() {
+                Some('.') => Op::Multiply,
+                Some('/') => Op::Divide,
+                Some(')') if nested => break,
+                None => break,
+                Some(_) => return self.unexpected(),
+            };
+            self.pos += 1;
+        }
+        Ok(UnitExpr { terms })
+    }
+
+    fn component(&mut self) -> PResult<Component> {
+        match self.peek() {
+            Some('(') => {
+                let start = self.pos;
+                self.pos += 1;
+                let expr = self.term(true)?;
+                if self.peek() != Some(')') {
+                    return Err((start, ErrorKind::Unclosed('(')));
+                }
+                self.pos += 1;
+                Ok(Component::Group(expr))
+            }
+            Some('{') => Ok(Component::Annotation(self.annotation()?)),
+            Some(c) if c.is_ascii_digit() => {
+                let rest = &self.s[self.pos..];
+                if let Some(atom) = rest.get(..3).and_then(Atom::from_code) {
+                    // `10*` and `10^` are the only atoms starting with a digit
+                    self.pos += 3;
+                    return self.unit(None, atom);
+                }
+                let start = self.pos;
+                let digits = self.digits();
+                digits
+                    .parse()
+                    .map(Component::Factor)
+                    .map_err(|_| (start, ErrorKind::Overflow))
+            }
+            _ => {
+                let start = self.pos;
+                let symbol = self.symbol()?;
+                let (prefix, atom) = resolve(symbol).map_err(|kind| (start, kind))?;
+                self.unit(prefix, atom)
+            }
+        }
+    }
+
+    /// The exponent and annotation following a unit symbol.
+    fn unit(&mut self, prefix: Option<&'static Prefix>, atom: &'static Atom) -> PResult<Component> {
+        let start = self.pos;
+        if matches!(self.peek(), Some('+' | '-')) {
+            self.pos += 1;
+            if self.digits().is_empty() {
+                return self.unexpected();
+            }
+        } else {
+            self.digits();
+        }
+        let exponent = match &self.s[start..self.pos] {
+            "" => 1,
+            exponent => exponent.parse().map_err(|_| (start, ErrorKind::Overflow))?,
+        };
+        let annotation = match self.peek() {
+            Some('{') => Some(self.annotation()?),
+            _ => None,
+        };
+        Ok(Component::Unit(Unit {
+            prefix,
+            atom,
+            exponent,
+            annotation,
+        }))
+    }
+
+    fn digits(&mut self) -> &str {
+        let start = self.pos;
+        let len = self.s[start..]
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(self.s.len() - start);
+        self.pos += len;
+        &self.s[start..self.pos]
+    }
+
+    /// A unit symbol, which may contain square-bracketed parts.
+    fn symbol(&mut self) -> PResult<&str> {
+        let start = self.pos;
+        let mut bracket = None;
+        for (i, c) in self.s[start..].char_indices() {
+            let at = start + i;
+            match c {
+                _ if !c.is_ascii_graphic() => return Err((at, ErrorKind::UnexpectedChar(c))),
+                '[' if bracket.is_none() => bracket = Some(at),
+                ']' if bracket.is_some() => bracket = None,
+                ']' | '}' => return Err((at, ErrorKind::UnexpectedChar(c))),
+                _ if bracket.is_some() => {}
+                '.' | '/' | '(' | ')' | '{' | '+' | '-' => break,
+                _ if c.is_ascii_digit() => break,
+                _ => {}
+            }
+            self.pos = at + c.len_utf8();
+        }
+        if let Some(open) = bracket {
+            return Err((open, ErrorKind::Unclosed('[')));
+        }
+        if self.pos == start {
+            return self.unexpected();
+        }
+        Ok(&self.s[start..self.pos])
+    }
+
+    /// Text in curly braces, returned without the braces.
+    fn annotation(&mut self) -> PResult<String> {
+        let start = self.pos;
+        let inner = start + 1;
+        for (i, c) in self.s[inner..].char_indices() {
+            match c {
+                '}' => {
+                    self.pos = inner + i + 1;
+                    return Ok(self.s[inner..inner + i].to_string());
+                }
+                '{' => return Err((inner + i, ErrorKind::UnexpectedChar(c))),
+                _ if !c.is_ascii_graphic() => {
+                    return Err((inner + i, ErrorKind::UnexpectedChar(c)));
+                }
+                _ => {}
+            }
+        }
+        Err((start, ErrorKind::Unclosed('{')))
+    }
+}
+
+/// The prefix and atom making up a unit symbol.
+fn resolve(symbol: &str) -> Result<(Option<&'static Prefix>, &'static Atom), ErrorKind> {
+    if let Some(atom) = Atom::from_code(symbol) {
+        return Ok((None, atom));
+    }
+    let mut non_metric = None;
+    for prefix in PREFIXES {
+        let Some(atom) = symbol.strip_prefix(prefix.code).and_then(Atom::from_code) else {
+            continue;
+        };
+        if atom.metric {
+            return Ok((Some(prefix), atom));
+        }
+        non_metric.get_or_insert(ErrorKind::NonMetric {
+            prefix: prefix.code.to_string(),
+            atom: atom.code.to_string(),
+        });
+    }
+    if let Some(kind) = non_metric {
+        return Err(kind);
+    }
+    let suggestion = ATOMS
+        .iter()
+        .find(|a| a.code.eq_ignore_ascii_case(symbol))
+        .map(|a| a.code.to_string())
+        .or_else(|| {
+            PREFIXES.iter().find_map(|p| {
+                let (head, tail) = symbol.split_at_checked(p.code.len())?;
+                if !head.eq_ignore_ascii_case(p.code) {
+                    return None;
+                }
+                let atom = ATOMS
+                    .iter()
+                    .find(|a| a.metric && a.code.eq_ignore_ascii_case(tail))?;
+                Some(format!("{}{}", p.code, atom.code))
+            })
+        });
+    Err(match suggestion {
+        Some(suggestion) => ErrorKind::WrongCase {
+            found: symbol.to_string(),
+            suggestion,
+        },
+        None => ErrorKind::UnknownUnit(symbol.to_string()),
+    })
+}
+
+impl fmt::Display for UnitExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, term) in self.terms.iter().enumerate() {
+            match term.op {
+                Op::Divide => f.write_str("/")?,
+                Op::Multiply if i > 0 => f.write_str(".")?,
+                Op::Multiply => {}
+            }
+            match &term.component {
+                Component::Unit(unit) => unit.fmt(f)?,
+                Component::Factor(n) => write!(f, "{n}")?,
+                Component::Annotation(a) => write!(f, "{{{a}}}")?,
+                Component::Group(expr) => write!(f, "({expr})")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(prefix) = self.prefix {
+            f.write_str(prefix.code)?;
+        }
+        f.write_str(self.atom.code)?;
+        if self.exponent != 1 {
+            write!(f, "{}", self.exponent)?;
+        }
+        if let Some(annotation) = &self.annotation {
+            write!(f, "{{{annotation}}}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Component, ErrorKind, Op, Term, UnitExpr};
+    use crate::table::{ATOMS, Definition};
+
+    fn err(unit: &str) -> (usize, ErrorKind) {
+        let e = unit.parse::<UnitExpr>().unwrap_err();
+        (e.position, e.kind)
+    }
+
+    /// The operator of each term, with its prefix code, atom code, exponent, and annotation if it is a unit.
+    fn units(terms: &[Term]) -> Vec<(Op, &str, &str, i32, Option<&str>)> {
+        terms
+            .iter()
+            .map(|term| {
+                let Component::Unit(unit) = &term.component else {
+                    panic!("expected a unit, got {:?}", term.component);
+                };
+                (
+                    term.op,
+                    unit.prefix.map_or("", |p| p.code),
+                    unit.atom.code,
+                    unit.exponent,
+                    unit.annotation.as_deref(),
+                )
+            })
+            .collect()
+    }
+
+    fn parse(unit: &str) -> UnitExpr {
+        unit.parse().unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    #[test]
+    fn parse_prefixes() {
+        let m = Op::Multiply;
+        assert_eq!(units(&parse("m").terms), [(m, "", "m", 1, None)]);
+        assert_eq!(units(&parse("um").terms), [(m, "u", "m", 1, None)]);
+        assert_eq!(units(&parse("dam").terms), [(m, "da", "m", 1, None)]);
+        assert_eq!(units(&parse("KiBy").terms), [(m, "Ki", "By", 1, None)]);
+        // atoms take precedence over prefixed atoms
+        assert_eq!(units(&parse("min").terms), [(m, "", "min", 1, None)]);
+        assert_eq!(units(&parse("cd").terms), [(m, "", "cd", 1, None)]);
+        let Component::Unit(unit) = &parse("um").terms[0].component else {
+            panic!("expected a unit");
+        };
+        assert_eq!(unit.prefix.unwrap().name, "micro");
+        assert_eq!(unit.prefix.unwrap().exponent, -6);
+    }
+
+    #[test]
+    fn parse_exponents() {
+        let (m, d) = (Op::Multiply, Op::Divide);
+        assert_eq!(units(&parse("cm2").terms), [(m, "c", "m", 2, None)]);
+        assert_eq!(units(&parse("cm+3").terms), [(m, "c", "m", 3, None)]);
+        assert_eq!(units(&parse("s-1").terms), [(m, "", "s", -1, None)]);
+        assert_eq!(
+            units(&parse("kg.m2/s-2").terms),
+            [
+                (m, "k", "g", 1, None),
+                (m, "", "m", 2, None),
+                (d, "", "s", -2, None)
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_annotations() {
+        let expr = parse("{cells}/uL");
+        assert_eq!(
+            expr.terms[0],
+            Term {
+                op: Op::Multiply,
+                component: Component::Annotation("cells".into())
+            }
+        );
+        assert_eq!(units(&expr.terms[1..]), [(Op::Divide, "u", "L", 1, None)]);
+        assert_eq!(
+            units(&parse("mL{total}").terms),
+            [(Op::Multiply, "m", "L", 1, Some("total"))]
+        );
+        assert_eq!(
+            units(&parse("m2{area}").terms),
+            [(Op::Multiply, "", "m", 2, Some("area"))]
+        );
+    }
+
+    #[test]
+    fn parse_division_and_groups() {
+        let (m, d) = (Op::Multiply, Op::Divide);
+        assert_eq!(units(&parse("/s").terms), [(d, "", "s", 1, None)]);
+        assert_eq!(
+            units(&parse("m/s/s").terms),
+            [
+                (m, "", "m", 1, None),
+                (d, "", "s", 1, None),
+                (d, "", "s", 1, None)
+            ]
+        );
+
+        let expr = parse("kcal/(mol.K)");
+        assert_eq!(expr.terms.len(), 2);
+        assert_eq!(units(&expr.terms[..1]), [(m, "k", "cal", 1, None)]);
+        assert_eq!(expr.terms[1].op, d);
+        let Component::Group(group) = &expr.terms[1].component else {
+            panic!("expected a group");
+        };
+        assert_eq!(
+            units(&group.terms),
+            [(m, "", "mol", 1, None), (m, "", "K", 1, None)]
+        );
+
+        let expr = parse("10*3/(2.m)");
+        assert_eq!(units(&expr.terms[..1]), [(m, "", "10*", 3, None)]);
+        let Component::Group(group) = &expr.terms[1].component else {
+            panic!("expected a group");
+        };
+        assert_eq!(group.terms[0].component, Component::Factor(2));
+        assert_eq!(units(&group.terms[1..]), [(m, "", "m", 1, None)]);
+    }
+
+    #[test]
+    fn parse_valid() {
+        for unit in [
+            "1",
+            "m",
+            "um",
+            "dam",
+            "kg.m/s2",
+            "/s",
+            "m-1",
+            "10*-6.m",
+            "10^3",
+            "mm[Hg]",
+            "[in_i]",
+            "kcal/(mol.K)",
+            "{cells}/uL",
+            "%{vol}",
+            "''",
+            "Cel",
+            "dB[SPL]",
+            "KiBy",
+        ] {
+            let expr: UnitExpr = unit.parse().unwrap_or_else(|e| panic!("{e}"));
+            assert_eq!(expr.to_string(), unit);
+        }
+        let expr: UnitExpr = "10*-6.m".parse().unwrap();
+        let Component::Unit(ten) = &expr.terms[0].component else {
+            panic!("expected a unit");
+        };
+        assert_eq!((ten.atom.code, ten.exponent), ("10*", -6));
+        assert_eq!("cm+3".parse::<UnitExpr>().unwrap().to_string(), "cm3");
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert_eq!(err(""), (0, ErrorKind::Empty));
+        assert_eq!(err("m."), (2, ErrorKind::UnexpectedEnd));
+        assert_eq!(err("m s"), (1, ErrorKind::UnexpectedChar(' ')));
+        assert_eq!(err("(m/s"), (0, ErrorKind::Unclosed('(')));
+        assert_eq!(err("m/s)"), (3, ErrorKind::UnexpectedChar(')')));
+        assert_eq!(err("[in_i"), (0, ErrorKind::Unclosed('[')));
+        assert_eq!(err("m{a"), (1, ErrorKind::Unclosed('{')));
+        assert_eq!(err("m{a b}"), (3, ErrorKind::UnexpectedChar(' ')));
+        assert_eq!(err("m-"), (2, ErrorKind::UnexpectedEnd));
+        assert_eq!(err("m99999999999"), (1, ErrorKind::Overflow));
+        assert_eq!(
+            err("furlong"),
+            (0, ErrorKind::UnknownUnit("furlong".into()))
+        );
+        assert_eq!(
+            err("m/UM"),
+            (
+                2,
+                ErrorKind::WrongCase {
+                    found: "UM".into(),
+                    suggestion: "um".into()
+                }
+            )
+        );
+        assert_eq!(
+            err("kh"),
+            (
+                0,
+                ErrorKind::NonMetric {
+                    prefix: "k".into(),
+                    atom: "h".into()
+                }
+            )
+        );
+        assert_eq!(err("m/(s.)"), (5, ErrorKind::UnexpectedChar(')')));
+        assert_eq!(err("m/(s"), (2, ErrorKind::Unclosed('(')));
+        let e = "UM".parse::<UnitExpr>().unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "invalid UCUM unit 'UM' at position 0: unknown unit 'UM'; UCUM is case-sensitive: did you mean 'um'?"
+        );
+    }
+
+    #[test]
+    fn table_definitions_parse() {
+        for atom in ATOMS {
+            let unit = match atom.definition {
+                Definition::Derived { unit, .. } | Definition::Special { unit } => unit,
+                _ => continue,
+            };
+            assert!(
+                unit.parse::<UnitExpr>().is_ok(),
+                "definition of '{}' is invalid",
+                atom.code
+            );
+        }
+    }
+}
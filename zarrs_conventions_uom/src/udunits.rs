@@ -0,0 +1,444 @@
+//! Translation to and from [UDUNITS-2](https://docs.unidata.ucar.edu/udunits/current/) unit strings,
+//! as used by the [CF conventions](https://cfconventions.org/).
+//!
+//! Only the common units of each system are mapped,
+//! and anything which cannot be carried across is reported as a [Loss].
+use std::fmt;
+
+use crate::{
+    Ucum, UnitOfMeasurement,
+    syntax::{self, Mapping, SyntaxError, name, symbol},
+    ucum::{Component, Op, ParseError, Unit, UnitExpr},
+};
+
+/// Something in a unit string which could not be carried over to the other system.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum Loss {
+    /// A UCUM annotation, e.g. `{cells}`, which UDUNITS cannot express.
+    Annotation(String),
+    /// The origin or direction of a UDUNITS unit,
+    /// e.g. `since 1970-01-01`, or the `north` of `degrees_north`.
+    Reference(String),
+    /// A unit written as a similar but not identical unit,
+    /// e.g. UCUM's year of 365.25 days as UDUNITS' tropical year.
+    Approximate {
+        from: &'static str,
+        to: &'static str,
+    },
+}
+
+impl fmt::Display for Loss {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Loss::Annotation(a) => write!(f, "annotation '{{{a}}}' was dropped"),
+            Loss::Reference(r) => write!(f, "reference '{r}' was dropped"),
+            Loss::Approximate { from, to } => write!(f, "'{from}' was approximated as '{to}'"),
+        }
+    }
+}
+
+/// A translated unit string, with anything which was lost in translation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Translation {
+    pub unit: String,
+    pub losses: Vec<Loss>,
+}
+
+impl Translation {
+    pub fn is_lossless(&self) -> bool {
+        self.losses.is_empty()
+    }
+}
+
+/// Why a unit string could not be translated.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[non_exhaustive]
+pub enum UdunitsError {
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+    #[error("no unit is declared")]
+    Undeclared,
+    #[error("'{0}' has no UDUNITS equivalent")]
+    NoEquivalent(String),
+    #[error("'{0}' is not a known UDUNITS unit")]
+    UnknownUnit(String),
+    #[error("invalid UDUNITS unit string '{unit}' at position {position}")]
+    Syntax { unit: String, position: usize },
+}
+
+static UNITS: &[Mapping] = &[
+    symbol("m", "m", &["meter", "metre"]),
+    symbol("s", "s", &["second", "sec"]),
+    symbol("g", "g", &["gram"]),
+    symbol("K", "K", &["kelvin"]),
+    symbol("mol", "mol", &["mole"]),
+    symbol("cd", "cd", &["candela"]),
+    symbol("A", "A", &["ampere", "amp"]),
+    symbol("rad", "rad", &["radian"]),
+    symbol("sr", "sr", &["steradian"]),
+    symbol("Hz", "Hz", &["hertz"]),
+    symbol("N", "N", &["newton"]),
+    symbol("Pa", "Pa", &["pascal"]),
+    symbol("J", "J", &["joule"]),
+    symbol("W", "W", &["watt"]),
+    symbol("C", "C", &["coulomb"]),
+    symbol("V", "V", &["volt"]),
+    symbol("F", "F", &["farad"]),
+    name("Ohm", "ohm", &["Ω"]),
+    symbol("S", "S", &["siemens"]),
+    symbol("Wb", "Wb", &["weber"]),
+    symbol("T", "T", &["tesla"]),
+    symbol("H", "H", &["henry"]),
+    symbol("lm", "lm", &["lumen"]),
+    symbol("lx", "lx", &["lux"]),
+    symbol("Bq", "Bq", &["becquerel"]),
+    symbol("Gy", "Gy", &["gray"]),
+    symbol("Sv", "Sv", &["sievert"]),
+    symbol("L", "L", &["l", "liter", "litre"]),
+    symbol("min", "min", &["minute"]),
+    symbol("h", "h", &["hour", "hr"]),
+    symbol("d", "d", &["day"]),
+    name("wk", "week", &[]),
+    name("a_t", "year", &["yr"]),
+    symbol("bar", "bar", &[]),
+    symbol("t", "t", &["tonne", "metric_ton"]),
+    symbol("eV", "eV", &["electronvolt"]),
+    symbol("atm", "atm", &["atmosphere"]),
+    name("deg", "degree", &["deg", "arc_degree", "°"]),
+    name("'", "arcminute", &["arcmin"]),
+    name("''", "arcsecond", &["arcsec"]),
+    symbol(
+        "Cel",
+        "degC",
+        &[
+            "degree_Celsius",
+            "celsius",
+            "Celsius",
+            "degree_C",
+            "deg_C",
+            "°C",
+        ],
+    ),
+    symbol(
+        "[degF]",
+        "degF",
+        &[
+            "degree_Fahrenheit",
+            "fahrenheit",
+            "Fahrenheit",
+            "degree_F",
+            "°F",
+        ],
+    ),
+    name("%", "percent", &["%"]),
+    name("[ppm]", "ppm", &[]),
+    name("[ppb]", "ppb", &[]),
+    name("[pi]", "pi", &[]),
+    name("Ao", "angstrom", &["Å"]),
+    name("[in_i]", "inch", &["in"]),
+    name("[ft_i]", "foot", &["ft", "feet"]),
+    name("[yd_i]", "yard", &["yd"]),
+    name("[mi_i]", "mile", &["mi"]),
+    name("[nmi_i]", "nautical_mile", &[]),
+    name("[kn_i]", "knot", &["kt"]),
+    name("[lb_av]", "pound", &["lb"]),
+    symbol("bit", "bit", &[]),
+    name("By", "byte", &[]),
+];
+
+/// UCUM units written as a similar UDUNITS unit.
+///
+/// When reading UDUNITS, the first UCUM unit for a spelling not in [UNITS] is used.
+static APPROXIMATIONS: &[(&str, &str)] = &[
+    ("mo", "month"),
+    ("mo_j", "month"),
+    ("mo_g", "month"),
+    ("a", "year"),
+    ("a_j", "year"),
+    ("a_g", "year"),
+];
+
+/// Spellings of longitude and latitude units, and their directions.
+static DIRECTIONS: &[(&str, &str)] = &[
+    ("north", "north"),
+    ("N", "north"),
+    ("east", "east"),
+    ("E", "east"),
+    ("south", "south"),
+    ("S", "south"),
+    ("west", "west"),
+    ("W", "west"),
+];
+
+/// Words introducing the origin of a UDUNITS unit, e.g. `days since 1970-01-01`.
+static REFERENCES: &[&str] = &["since", "after", "from", "ref", "@"];
+
+/// UDUNITS spelling of a UCUM unit, without its exponent.
+fn udunits_spelling(unit: &Unit, losses: &mut Vec<Loss>) -> Result<String, UdunitsError> {
+    let code = unit.atom.code;
+    let no_equivalent = || UdunitsError::NoEquivalent(unit.to_string());
+    let (spelling, symbol) = match UNITS.iter().find(|m| m.ucum == code) {
+        Some(m) => (m.spelling, m.symbol),
+        None => {
+            let (from, to) = APPROXIMATIONS
+                .iter()
+                .find(|(ucum, _)| *ucum == code)
+                .ok_or_else(no_equivalent)?;
+            losses.push(Loss::Approximate { from, to });
+            (*to, false)
+        }
+    };
+    let prefix = match unit.prefix {
+        None => "",
+        Some(p) if p.base != 10 => return Err(no_equivalent()),
+        Some(p) if symbol => p.code,
+        Some(p) => p.name,
+    };
+    if let Some(annotation) = &unit.annotation {
+        losses.push(Loss::Annotation(annotation.clone()));
+    }
+    Ok(format!("{prefix}{spelling}"))
+}
+
+impl UnitExpr {
+    /// The unit as a UDUNITS string, with any UCUM features which UDUNITS cannot express.
+    ///
+    /// ```
+    /// use zarrs_conventions_uom::ucum::UnitExpr;
+    ///
+    /// let expr: UnitExpr = "m/s".parse().unwrap();
+    /// assert_eq!(expr.to_udunits().unwrap().unit, "m s-1");
+    /// ```
+    pub fn to_udunits(&self) -> Result<Translation, UdunitsError> {
+        let (mut magnitude, expr) = self.split_magnitude();
+        let mut losses = Vec::default();
+        let mut parts = Vec::default();
+        for term in &expr.terms {
+            let sign = match term.op {
+                Op::Multiply => 1,
+                Op::Divide => -1,
+            };
+            match &term.component {
+                Component::Unit(unit) => {
+                    let spelling = udunits_spelling(unit, &mut losses)?;
+                    match sign * unit.exponent {
+                        1 => parts.push(spelling),
+                        exponent => parts.push(format!("{spelling}{exponent}")),
+                    }
+                }
+                Component::Factor(n) => magnitude *= (*n as f64).powi(sign),
+                Component::Annotation(a) => losses.push(Loss::Annotation(a.clone())),
+                Component::Group(_) => unreachable!("canonical expressions have no groups"),
+            }
+        }
+        if magnitude != 1.0 || parts.is_empty() {
+            parts.insert(0, syntax::format_number(magnitude));
+        }
+        Ok(Translation {
+            unit: parts.join(" "),
+            losses,
+        })
+    }
+}
+
+impl UnitOfMeasurement {
+    /// The declared unit as a UDUNITS string.
+    ///
+    /// ```
+    /// use zarrs_conventions_uom::{UnitOfMeasurement, udunits::Loss};
+    ///
+    /// let uom = UnitOfMeasurement::builder().unit("{cells}/mm2").build();
+    /// let translation = uom.to_udunits().unwrap();
+    /// assert_eq!(translation.unit, "mm-2");
+    /// assert_eq!(translation.losses, vec![Loss::Annotation("cells".into())]);
+    /// ```
+    pub fn to_udunits(&self) -> Result<Translation, UdunitsError> {
+        self.ucum()
+            .parsed()
+            .ok_or(UdunitsError::Undeclared)??
+            .to_udunits()
+    }
+}
+
+/// Mapping for a UDUNITS spelling, without any prefix.
+fn lookup(spelling: &str) -> Option<(&'static str, bool, Option<Loss>)> {
+    if let Some(m) = UNITS
+        .iter()
+        .find(|m| m.spelling == spelling || m.aliases.contains(&spelling))
+    {
+        return Some((m.ucum, m.symbol, None));
+    }
+    let (ucum, udunits) = APPROXIMATIONS.iter().find(|(_, u)| *u == spelling)?;
+    Some((
+        ucum,
+        false,
+        Some(Loss::Approximate {
+            from: udunits,
+            to: ucum,
+        }),
+    ))
+}
+
+/// UCUM code for a UDUNITS unit name or symbol, possibly prefixed.
+fn resolve(identifier: &str) -> Option<(String, Option<Loss>)> {
+    for degrees in ["degrees_", "degree_", "degrees", "degree"] {
+        let Some(direction) = identifier.strip_prefix(degrees) else {
+            continue;
+        };
+        if let Some((_, d)) = DIRECTIONS.iter().find(|(s, _)| *s == direction) {
+            return Some(("deg".into(), Some(Loss::Reference(d.to_string()))));
+        }
+    }
+    syntax::resolve(identifier, |p| p.base == 10, lookup)
+}
+
+/// Translate a UDUNITS unit string, e.g. from a CF `units` attribute, to UCUM.
+///
+/// ```
+/// use zarrs_conventions_uom::udunits::{Loss, from_udunits};
+///
+/// assert_eq!(from_udunits("m s-1").unwrap().unit, "m/s");
+/// assert_eq!(from_udunits("kilometers/hour").unwrap().unit, "km/h");
+/// let lat = from_udunits("degrees_north").unwrap();
+/// assert_eq!(lat.unit, "deg");
+/// assert_eq!(lat.losses, vec![Loss::Reference("north".into())]);
+/// ```
+pub fn from_udunits(unit: &str) -> Result<Translation, UdunitsError> {
+    let trimmed = unit.trim();
+    let mut product = trimmed;
+    let mut reference = None;
+    for (i, _) in trimmed.match_indices(char::is_whitespace) {
+        let rest = trimmed[i..].trim_start();
+        let keyword = rest.split(char::is_whitespace).next().unwrap_or_default();
+        if REFERENCES.contains(&keyword) {
+            product = trimmed[..i].trim_end();
+            reference = Some(Loss::Reference(rest.to_string()));
+            break;
+        }
+    }
+    if product.is_empty() {
+        return Ok(Translation {
+            unit: "1".into(),
+            losses: reference.into_iter().collect(),
+        });
+    }
+
+    let mut losses = Vec::default();
+    let ucum = syntax::parse(product, |identifier| {
+        let (ucum, loss) = resolve(identifier)?;
+        losses.extend(loss);
+        Some(ucum)
+    })
+    .map_err(|e| match e {
+        SyntaxError::Invalid { position } => UdunitsError::Syntax {
+            unit: product.to_string(),
+            position,
+        },
+        SyntaxError::UnknownUnit(u) => UdunitsError::UnknownUnit(u),
+    })?;
+    losses.extend(reference);
+    Ok(Translation {
+        unit: Ucum::parse(&ucum)?.canonicalize().to_string(),
+        losses,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Loss, UdunitsError, from_udunits};
+    use crate::ucum::UnitExpr;
+
+    fn to(unit: &str) -> String {
+        unit.parse::<UnitExpr>().unwrap().to_udunits().unwrap().unit
+    }
+
+    fn from(unit: &str) -> String {
+        from_udunits(unit).unwrap().unit
+    }
+
+    #[test]
+    fn ucum_to_udunits() {
+        assert_eq!(to("m/s"), "m s-1");
+        assert_eq!(to("kg.m-2.s-1"), "kg m-2 s-1");
+        assert_eq!(to("Cel"), "degC");
+        assert_eq!(to("um"), "um");
+        assert_eq!(to("kOhm"), "kiloohm");
+        assert_eq!(to("10*-3.m"), "1e-3 m");
+        assert_eq!(to("1000.Pa"), "1000 Pa");
+        assert_eq!(to("1"), "1");
+        assert_eq!(to("%"), "percent");
+        assert_eq!(to("deg"), "degree");
+
+        let year = "a".parse::<UnitExpr>().unwrap().to_udunits().unwrap();
+        assert_eq!(year.unit, "year");
+        assert_eq!(
+            year.losses,
+            vec![Loss::Approximate {
+                from: "a",
+                to: "year"
+            }]
+        );
+        assert!(matches!(
+            "[IU]".parse::<UnitExpr>().unwrap().to_udunits(),
+            Err(UdunitsError::NoEquivalent(_))
+        ));
+        assert!(matches!(
+            "KiBy".parse::<UnitExpr>().unwrap().to_udunits(),
+            Err(UdunitsError::NoEquivalent(_))
+        ));
+    }
+
+    #[test]
+    fn udunits_to_ucum() {
+        assert_eq!(from("m s-1"), "m/s");
+        assert_eq!(from("m/s"), "m/s");
+        assert_eq!(from("m.s^-1"), "m/s");
+        assert_eq!(from("m*s**-1"), "m/s");
+        assert_eq!(from("kg m-2 s-1"), "kg/m2/s");
+        assert_eq!(from("W/(m2 sr)"), "W/m2/sr");
+        assert_eq!(from("(m/s)2"), "m2/s2");
+        assert_eq!(from("degC"), "Cel");
+        assert_eq!(from("degree_Celsius"), "Cel");
+        assert_eq!(from("hPa"), "hPa");
+        assert_eq!(from("µm"), "um");
+        assert_eq!(from("ms"), "ms");
+        assert_eq!(from("micrometers"), "um");
+        assert_eq!(from("meters"), "m");
+        assert_eq!(from("feet"), "[ft_i]");
+        assert_eq!(from("1e-3 kg"), "10*-3.kg");
+        assert_eq!(from("0.5 m"), "5.10*-1.m");
+        assert_eq!(from("1"), "1");
+        assert_eq!(from(""), "1");
+        assert_eq!(from("percent"), "%");
+        assert_eq!(from("year"), "a_t");
+
+        let time = from_udunits("days since 1970-01-01 00:00:00").unwrap();
+        assert_eq!(time.unit, "d");
+        assert_eq!(
+            time.losses,
+            vec![Loss::Reference("since 1970-01-01 00:00:00".into())]
+        );
+        let lon = from_udunits("degrees_E").unwrap();
+        assert_eq!(lon.unit, "deg");
+        assert_eq!(lon.losses, vec![Loss::Reference("east".into())]);
+        assert!(from_udunits("months").unwrap().losses.len() == 1);
+
+        assert_eq!(
+            from_udunits("furlongs"),
+            Err(UdunitsError::UnknownUnit("furlongs".into()))
+        );
+        assert!(matches!(
+            from_udunits("m s)"),
+            Err(UdunitsError::Syntax { position: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn round_trip() {
+        for ucum in ["m/s", "kg/m2/s", "Cel", "um", "W/m2/sr", "[in_i]", "h"] {
+            let udunits = to(ucum);
+            assert_eq!(from(&udunits), ucum, "via {udunits}");
+        }
+    }
+}
@@ -0,0 +1,182 @@
+//! Converting the values of an array to another unit,
+//! e.g. each chunk as it is retrieved, or when writing a converted copy.
+//!
+//! Conversions work on buffers of decoded elements, independent of how they were read;
+//! with the `zarrs` feature, `array::ConvertedArray` applies them to a zarrs array's values as they are retrieved.
+use zarrs_conventions::{Attributes, NestedRepr};
+
+use crate::{
+    UnitOfMeasurement,
+    convert::{ConversionError, LinearConversion},
+};
+
+/// Why an array's values could not be converted.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum ValuesError {
+    #[error("could not read uom metadata: {0}")]
+    Attributes(#[from] serde_json::Error),
+    #[error(transparent)]
+    Conversion(#[from] ConversionError),
+    #[cfg(feature = "zarrs")]
+    #[error(transparent)]
+    Array(#[from] zarrs::array::ArrayError),
+}
+
+/// Conversion of an array's raw stored values, after any calibration, to quantities in a target unit.
+///
+/// ```
+/// use zarrs_conventions_uom::{UnitOfMeasurement, values::ValueConversion};
+///
+/// let attributes = serde_json::json!({
+///     "uom": {"ucum": {"unit": "mm"}, "calibration": {"scale": 0.5}}
+/// });
+/// let conversion = ValueConversion::from_attributes(
+///     attributes.as_object().unwrap(),
+///     UnitOfMeasurement::builder().unit("m").build(),
+/// )
+/// .unwrap();
+/// let chunk: Vec<u16> = vec![0, 2000, 4000];
+/// assert_eq!(conversion.convert(&chunk), [0.0, 1.0, 2.0]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValueConversion {
+    conversion: LinearConversion,
+    target: UnitOfMeasurement,
+}
+
+impl ValueConversion {
+    /// Conversion of values stored in the source unit to the target unit.
+    ///
+    /// Any calibration of the target is dropped, as converted values are quantities.
+    pub fn new(
+        source: &UnitOfMeasurement,
+        mut target: UnitOfMeasurement,
+    ) -> Result<Self, ConversionError> {
+        target.set_calibration(None);
+        let to_target = source.conversion_to(&target)?;
+        let calibration = source.calibration().copied().unwrap_or_default();
+        Ok(Self {
+            conversion: LinearConversion {
+                scale: calibration.scale * to_target.scale,
+                offset: calibration.offset * to_target.scale + to_target.offset,
+            },
+            target,
+        })
+    }
+
+    /// Conversion of values stored in an array with the given attributes,
+    /// which must include uom metadata, to the target unit.
+    pub fn from_attributes(
+        attributes: &Attributes,
+        target: UnitOfMeasurement,
+    ) -> Result<Self, ValuesError> {
+        let source = UnitOfMeasurement::from_attributes_nested(attributes)?;
+        Ok(Self::new(&source, target)?)
+    }
+
+    /// The combined calibration and unit conversion.
+    pub fn conversion(&self) -> LinearConversion {
+        self.conversion
+    }
+
+    /// Unit of the converted values.
+    pub fn target(&self) -> &UnitOfMeasurement {
+        &self.target
+    }
+
+    /// Whether the conversion leaves values unchanged, so that stored values can be used as they are.
+    pub fn is_identity(&self) -> bool {
+        self.conversion.scale == 1.0 && self.conversion.offset == 0.0
+    }
+
+    /// Convert a single raw value.
+    pub fn apply(&self, raw: f64) -> f64 {
+        self.conversion.apply(raw)
+    }
+
+    /// Convert raw values, e.g. of a retrieved chunk.
+    pub fn convert<T: Copy + Into<f64>>(&self, raw: &[T]) -> Vec<f64> {
+        raw.iter().map(|v| self.apply((*v).into())).collect()
+    }
+
+    /// Convert raw values in place.
+    pub fn convert_in_place(&self, values: &mut [f64]) {
+        if self.is_identity() {
+            return;
+        }
+        for value in values {
+            *value = self.apply(*value);
+        }
+    }
+
+    /// Write uom metadata for an array of converted values,
+    /// replacing that of the source array in a copy of its attributes.
+    pub fn write_attributes(&self, attributes: &mut Attributes) -> serde_json::Result<()> {
+        self.target.to_attributes_nested(attributes)
+    }
+}
+
+impl UnitOfMeasurement {
+    /// Conversion of raw stored values in this unit, after any calibration, to the target unit;
+    /// see [ValueConversion].
+    pub fn value_conversion_to(
+        &self,
+        target: UnitOfMeasurement,
+    ) -> Result<ValueConversion, ConversionError> {
+        ValueConversion::new(self, target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use zarrs_conventions::NestedRepr;
+
+    use super::{ValueConversion, ValuesError};
+    use crate::{UnitOfMeasurement, calibration::Calibration, convert::ConversionError};
+
+    #[test]
+    fn convert_values() {
+        let source = UnitOfMeasurement::builder()
+            .unit("Cel")
+            .calibration(Calibration::new(0.1, -20.0))
+            .build();
+        let conversion = source
+            .value_conversion_to(UnitOfMeasurement::builder().unit("K").build())
+            .unwrap();
+        let mut values = vec![200.0, 400.0];
+        conversion.convert_in_place(&mut values);
+        assert!((values[0] - 273.15).abs() < 1e-9);
+        assert!((values[1] - 293.15).abs() < 1e-9);
+        assert!((conversion.convert(&[0i8])[0] - 253.15).abs() < 1e-9);
+
+        let mut attributes = json!({"uom": source, "other": 1})
+            .as_object()
+            .unwrap()
+            .clone();
+        conversion.write_attributes(&mut attributes).unwrap();
+        assert_eq!(attributes["other"], 1);
+        assert_eq!(
+            UnitOfMeasurement::from_attributes_nested(&attributes).unwrap(),
+            *conversion.target()
+        );
+
+        let identity = UnitOfMeasurement::MILLIMETER
+            .value_conversion_to(UnitOfMeasurement::builder().unit("mm").build())
+            .unwrap();
+        assert!(identity.is_identity());
+
+        assert!(matches!(
+            ValueConversion::from_attributes(
+                json!({}).as_object().unwrap(),
+                UnitOfMeasurement::MILLIMETER
+            ),
+            Err(ValuesError::Attributes(_))
+        ));
+        assert!(matches!(
+            UnitOfMeasurement::MILLIMETER.value_conversion_to(UnitOfMeasurement::SECOND),
+            Err(ConversionError::Incommensurable { .. })
+        ));
+    }
+}
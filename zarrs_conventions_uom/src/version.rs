@@ -0,0 +1,134 @@
+//! Which versions of the UCUM tables define a unit.
+use crate::{
+    Ucum,
+    table::ADDED,
+    ucum::{Component, UnitExpr},
+};
+
+/// Versions of UCUM whose tables are embedded, oldest first.
+static SUPPORTED: &[&str] = &["2.1", "2.2"];
+
+/// Index of a supported version, for ordering.
+fn index(version: &str) -> Option<usize> {
+    SUPPORTED.iter().position(|v| *v == version)
+}
+
+/// The version in which a prefix or atom code was added, if later than the earliest supported version.
+fn added_in(code: &str) -> Option<&'static str> {
+    ADDED
+        .iter()
+        .find(|(_, codes)| codes.contains(&code))
+        .map(|(version, _)| *version)
+}
+
+impl UnitExpr {
+    /// The earliest [supported](Ucum::supported_versions) UCUM version
+    /// which defines every prefix and atom in the unit.
+    ///
+    /// ```
+    /// use zarrs_conventions_uom::ucum::UnitExpr;
+    ///
+    /// let expr: UnitExpr = "Qm".parse().unwrap();
+    /// assert_eq!(expr.earliest_version(), "2.2");
+    /// assert_eq!(expr.is_defined_in("2.1"), Some(false));
+    /// assert_eq!(expr.is_defined_in("1.9"), None);
+    /// ```
+    pub fn earliest_version(&self) -> &'static str {
+        self.terms
+            .iter()
+            .map(|term| match &term.component {
+                Component::Unit(unit) => {
+                    let codes = unit
+                        .prefix
+                        .map(|p| p.code)
+                        .into_iter()
+                        .chain([unit.atom.code]);
+                    codes
+                        .filter_map(added_in)
+                        .max_by_key(|v| index(v))
+                        .unwrap_or(SUPPORTED[0])
+                }
+                Component::Group(expr) => expr.earliest_version(),
+                Component::Factor(_) | Component::Annotation(_) => SUPPORTED[0],
+            })
+            .max_by_key(|v| index(v))
+            .unwrap_or(SUPPORTED[0])
+    }
+
+    /// Whether every prefix and atom in the unit is defined in the given UCUM version,
+    /// or None if the version's tables are not embedded.
+    pub fn is_defined_in(&self, version: &str) -> Option<bool> {
+        Some(index(self.earliest_version()) <= Some(index(version)?))
+    }
+}
+
+impl Ucum {
+    /// Versions of the UCUM specification whose unit tables are embedded,
+    /// so that units can be checked against them.
+    ///
+    /// Units declaring other [known versions](Self::KNOWN_VERSIONS) are checked against the latest.
+    pub fn supported_versions() -> &'static [&'static str] {
+        SUPPORTED
+    }
+
+    /// Whether the unit is defined in the declared version,
+    /// if the unit is valid and the version is [supported](Self::supported_versions).
+    pub fn is_defined_in_version(&self) -> Option<bool> {
+        let expr = Self::parse(self.unit()?).ok()?;
+        expr.is_defined_in(self.version()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zarrs_conventions::validate::Lint;
+
+    use crate::{BuildError, UnitOfMeasurement};
+
+    #[test]
+    fn versions() {
+        let unit = |unit: &str, version: &str| {
+            UnitOfMeasurement::builder()
+                .unit(unit)
+                .version(version)
+                .build()
+        };
+        assert_eq!(unit("um", "2.1").ucum().is_defined_in_version(), Some(true));
+        assert_eq!(
+            unit("qg/s", "2.1").ucum().is_defined_in_version(),
+            Some(false)
+        );
+        assert_eq!(
+            unit("(m.Rm)", "2.2").ucum().is_defined_in_version(),
+            Some(true)
+        );
+        assert_eq!(unit("um", "1.9").ucum().is_defined_in_version(), None);
+
+        let issues = unit("qg/s", "2.1").lint();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].code, "ucum-version-mismatch");
+        let issues = unit("um", "1.9").lint();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].code, "unsupported-ucum-version");
+
+        assert_eq!(
+            UnitOfMeasurement::builder()
+                .unit("qg/s")
+                .version("2.1")
+                .build_validated()
+                .unwrap_err(),
+            BuildError::NotInVersion {
+                unit: "qg/s".into(),
+                version: "2.1".into(),
+                earliest: "2.2"
+            }
+        );
+        assert!(
+            UnitOfMeasurement::builder()
+                .unit("qg/s")
+                .version("1.9")
+                .build_validated()
+                .is_ok()
+        );
+    }
+}
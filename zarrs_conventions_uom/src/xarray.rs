@@ -0,0 +1,212 @@
+//! The plain `units` attribute used by [xarray](https://docs.xarray.dev/) and the CF conventions,
+//! kept alongside the formal uom payload for tools which do not know the convention.
+//!
+//! `units` attributes are read as [UDUNITS strings](crate::udunits),
+//! plus a few other spellings common in the wild, e.g. `microns`.
+use zarrs_conventions::{Attributes, NestedRepr, validate::Issue};
+
+use crate::{
+    Ucum, UnitOfMeasurement,
+    ucum::UnitExpr,
+    udunits::{Translation, UdunitsError, from_udunits},
+};
+
+/// Key of the plain units attribute.
+pub const UNITS_KEY: &str = "units";
+
+/// Spellings which are not UDUNITS, but are common in `units` attributes.
+static SPELLINGS: &[(&str, &str)] = &[("micron", "um"), ("microns", "um"), ("dimensionless", "1")];
+
+/// Why a `units` attribute could not be read.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[non_exhaustive]
+pub enum UnitsAttributeError {
+    #[error("'{UNITS_KEY}' attribute is not a string")]
+    NotAString,
+    #[error(transparent)]
+    Udunits(#[from] UdunitsError),
+}
+
+/// Translate the value of a `units` attribute to UCUM.
+///
+/// ```
+/// use zarrs_conventions_uom::xarray::ucum_from_units;
+///
+/// assert_eq!(ucum_from_units("microns").unwrap().unit, "um");
+/// assert_eq!(ucum_from_units("m s-1").unwrap().unit, "m/s");
+/// ```
+pub fn ucum_from_units(units: &str) -> Result<Translation, UdunitsError> {
+    match SPELLINGS
+        .iter()
+        .find(|(spelling, _)| *spelling == units.trim())
+    {
+        Some((_, ucum)) => Ok(Translation {
+            unit: ucum.to_string(),
+            losses: Vec::default(),
+        }),
+        None => from_udunits(units),
+    }
+}
+
+/// The `units` attribute translated to UCUM, if present.
+pub fn read_units_attribute(
+    attributes: &Attributes,
+) -> Result<Option<Translation>, UnitsAttributeError> {
+    let Some(value) = attributes.get(UNITS_KEY) else {
+        return Ok(None);
+    };
+    let units = value.as_str().ok_or(UnitsAttributeError::NotAString)?;
+    Ok(Some(ucum_from_units(units)?))
+}
+
+/// Whether two unit strings mean the same unit, allowing for different spellings.
+fn equivalent(a: &UnitExpr, b: &UnitExpr) -> bool {
+    if a.canonicalize() == b.canonicalize() {
+        return true;
+    }
+    a.conversion_to(b)
+        .is_ok_and(|c| (c.scale - 1.0).abs() < 1e-9 && c.offset == 0.0)
+}
+
+/// Check that a `units` attribute and a uom payload, if both are present, agree.
+///
+/// ```
+/// use zarrs_conventions_uom::xarray::lint_units_attribute;
+///
+/// let attributes = serde_json::json!({"units": "mm", "uom": {"ucum": {"unit": "um"}}});
+/// let issues = lint_units_attribute(attributes.as_object().unwrap());
+/// assert_eq!(issues[0].code, "inconsistent-units-attribute");
+/// ```
+pub fn lint_units_attribute(attributes: &Attributes) -> Vec<Issue> {
+    if !attributes.contains_key(UnitOfMeasurement::KEY) {
+        return Vec::default();
+    }
+    let translation = match read_units_attribute(attributes) {
+        Ok(Some(translation)) => translation,
+        Ok(None) => return Vec::default(),
+        Err(e) => return vec![Issue::warning("invalid-units-attribute", e.to_string())],
+    };
+    let Ok(uom) = UnitOfMeasurement::from_attributes_nested(attributes) else {
+        // reported when validating the payload itself
+        return Vec::default();
+    };
+    let Some(Ok(declared)) = uom.ucum().parsed() else {
+        return Vec::default();
+    };
+    let units = Ucum::parse(&translation.unit).expect("translations are valid UCUM");
+    if equivalent(&declared, &units) {
+        return Vec::default();
+    }
+    vec![Issue::warning(
+        "inconsistent-units-attribute",
+        format!(
+            "'{UNITS_KEY}' attribute means '{}', but the declared unit is '{declared}'",
+            translation.unit
+        ),
+    )]
+}
+
+impl UnitOfMeasurement {
+    /// The unit declared by a `units` attribute, if present.
+    ///
+    /// Anything lost in translation, such as the origin of `days since 1970-01-01`, is dropped;
+    /// use [read_units_attribute] to see it.
+    ///
+    /// ```
+    /// use zarrs_conventions_uom::UnitOfMeasurement;
+    ///
+    /// let attributes = serde_json::json!({"units": "degC"});
+    /// let uom = UnitOfMeasurement::from_units_attribute(attributes.as_object().unwrap())
+    ///     .unwrap()
+    ///     .unwrap();
+    /// assert_eq!(uom.ucum().unit(), Some("Cel"));
+    /// ```
+    pub fn from_units_attribute(
+        attributes: &Attributes,
+    ) -> Result<Option<Self>, UnitsAttributeError> {
+        Ok(read_units_attribute(attributes)?.map(|t| Self::builder().unit(t.unit).build()))
+    }
+
+    /// Write the declared unit as a `units` attribute, as read by xarray,
+    /// with anything which could not be expressed in it.
+    ///
+    /// ```
+    /// use zarrs_conventions_uom::UnitOfMeasurement;
+    ///
+    /// let mut attributes = serde_json::Map::default();
+    /// UnitOfMeasurement::MICROMETER.write_units_attribute(&mut attributes).unwrap();
+    /// assert_eq!(attributes["units"], "um");
+    /// ```
+    pub fn write_units_attribute(
+        &self,
+        attributes: &mut Attributes,
+    ) -> Result<Translation, UdunitsError> {
+        let translation = self.to_udunits()?;
+        attributes.insert(UNITS_KEY.to_string(), translation.unit.clone().into());
+        Ok(translation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{UnitsAttributeError, lint_units_attribute, read_units_attribute};
+    use crate::{UnitOfMeasurement, udunits::Loss};
+
+    fn lint(attributes: serde_json::Value) -> Vec<&'static str> {
+        let issues = lint_units_attribute(attributes.as_object().unwrap());
+        issues.into_iter().map(|i| i.code).collect()
+    }
+
+    #[test]
+    fn units_attribute() {
+        let attributes = json!({"units": "days since 1970-01-01", "other": 1});
+        let translation = read_units_attribute(attributes.as_object().unwrap())
+            .unwrap()
+            .unwrap();
+        assert_eq!(translation.unit, "d");
+        assert_eq!(
+            translation.losses,
+            vec![Loss::Reference("since 1970-01-01".into())]
+        );
+        assert_eq!(
+            read_units_attribute(json!({"units": 1}).as_object().unwrap()),
+            Err(UnitsAttributeError::NotAString)
+        );
+        assert_eq!(
+            UnitOfMeasurement::from_units_attribute(json!({}).as_object().unwrap()),
+            Ok(None)
+        );
+
+        let mut attributes = serde_json::Map::default();
+        let uom = UnitOfMeasurement::builder().unit("kg/m2/s").build();
+        assert!(
+            uom.write_units_attribute(&mut attributes)
+                .unwrap()
+                .is_lossless()
+        );
+        assert_eq!(
+            UnitOfMeasurement::from_units_attribute(&attributes).unwrap(),
+            Some(uom)
+        );
+    }
+
+    #[test]
+    fn consistency() {
+        let uom = json!({"ucum": {"unit": "um"}});
+        assert!(lint(json!({"units": "microns", "uom": uom})).is_empty());
+        assert!(lint(json!({"units": "micrometer", "uom": uom})).is_empty());
+        assert!(lint(json!({"units": "1e-6 m", "uom": uom})).is_empty());
+        assert!(lint(json!({"units": "mm"})).is_empty());
+        assert!(lint(json!({"uom": uom})).is_empty());
+        assert_eq!(
+            lint(json!({"units": "mm", "uom": uom})),
+            ["inconsistent-units-attribute"]
+        );
+        assert_eq!(
+            lint(json!({"units": "furlongs", "uom": uom})),
+            ["invalid-units-attribute"]
+        );
+    }
+}
@@ -0,0 +1,185 @@
+//! Choosing and applying the next version when a dataset is republished.
+//!
+//! Changes are classified by their effect on readers of the attributes:
+//! removing anything may break them ([Bump::Major]),
+//! adding anything extends what they can use ([Bump::Minor]),
+//! and changing a value in place is a fix ([Bump::Patch]).
+//! Changes to the version payload itself are ignored.
+use zarrs_conventions::{
+    Attributes, NestedRepr, ZarrConventionImpl,
+    diff::{AttributesDiff, Change, diff_attributes},
+};
+
+use crate::{DatasetVersion, Previous, Version};
+
+/// Which part of a version to increment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Bump {
+    Patch,
+    Minor,
+    Major,
+}
+
+impl Bump {
+    /// The smallest bump which covers the changes, or `None` if nothing relevant changed.
+    ///
+    /// ```
+    /// use zarrs_conventions::diff::diff_attributes;
+    /// use zarrs_conventions_version::bump::Bump;
+    ///
+    /// let old = serde_json::json!({"title": "Survey", "version": {"version": "1.0.0"}});
+    /// let new = serde_json::json!({"title": "Survey", "units": "m", "version": {"version": "1.0.1"}});
+    /// let diff = diff_attributes(old.as_object().unwrap(), new.as_object().unwrap()).unwrap();
+    /// assert_eq!(Bump::for_diff(&diff), Some(Bump::Minor));
+    /// ```
+    pub fn for_diff(diff: &AttributesDiff) -> Option<Self> {
+        let own =
+            |c: &zarrs_conventions::Convention| c.uuid() == Some(DatasetVersion::DEFINITION.uuid);
+        let prefix = format!("/{}", DatasetVersion::KEY);
+        let changes = diff.changes.iter().filter(|c| {
+            let path = c.path();
+            !(path == prefix || path.starts_with(&format!("{prefix}/")))
+        });
+        let mut bump = None;
+        if diff.undeclared.iter().any(|c| !own(c)) {
+            return Some(Self::Major);
+        }
+        if diff.declared.iter().any(|c| !own(c)) {
+            bump = Some(Self::Minor);
+        }
+        for change in changes {
+            let level = match change {
+                Change::Removed { .. } => return Some(Self::Major),
+                Change::Added { .. } => Self::Minor,
+                Change::Changed { .. } => Self::Patch,
+            };
+            bump = bump.max(Some(level));
+        }
+        bump
+    }
+}
+
+impl Version {
+    /// The next version at the given level, without build metadata.
+    ///
+    /// A pre-release is released as the version it precedes, if that is at least the requested level.
+    ///
+    /// ```
+    /// use zarrs_conventions_version::{Version, bump::Bump};
+    ///
+    /// let v = |s: &str| s.parse::<Version>().unwrap();
+    /// assert_eq!(v("1.2.3").bump(Bump::Minor), v("1.3.0"));
+    /// assert_eq!(v("1.2.3+build.1").bump(Bump::Patch), v("1.2.4"));
+    /// assert_eq!(v("2.0.0-rc.1").bump(Bump::Major), v("2.0.0"));
+    /// assert_eq!(v("1.2.3-rc.1").bump(Bump::Major), v("2.0.0"));
+    /// ```
+    pub fn bump(&self, level: Bump) -> Version {
+        let releases = match level {
+            Bump::Major => self.minor == 0 && self.patch == 0,
+            Bump::Minor => self.patch == 0,
+            Bump::Patch => true,
+        };
+        if self.is_prerelease() && releases {
+            return Version::new(self.major, self.minor, self.patch);
+        }
+        match level {
+            Bump::Major => Version::new(self.major + 1, 0, 0),
+            Bump::Minor => Version::new(self.major, self.minor + 1, 0),
+            Bump::Patch => Version::new(self.major, self.minor, self.patch + 1),
+        }
+    }
+}
+
+impl DatasetVersion {
+    /// The next version at the given level, which records this one as its previous version.
+    ///
+    /// Release notes are not carried over; set them and the previous version's location on the builder.
+    pub fn bump(&self, level: Bump) -> crate::Builder {
+        DatasetVersion::builder(self.version.bump(level))
+            .previous(Previous::new(self.version.clone()))
+    }
+
+    /// The next version for publishing the new attributes in place of the old,
+    /// or `None` if nothing but the version payload has changed.
+    ///
+    /// Fails if either has an invalid `zarr_conventions` list.
+    ///
+    /// ```
+    /// use zarrs_conventions_version::{DatasetVersion, Version};
+    ///
+    /// let current = DatasetVersion::builder("1.4.2".parse().unwrap()).build().unwrap();
+    /// let old = serde_json::json!({"title": "Survey", "units": "m"});
+    /// let new = serde_json::json!({"title": "Survey"});
+    /// let next = current
+    ///     .bump_for_attributes(old.as_object().unwrap(), new.as_object().unwrap())
+    ///     .unwrap()
+    ///     .unwrap()
+    ///     .notes("Dropped units")
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(next.version().to_string(), "2.0.0");
+    /// assert_eq!(next.previous().unwrap().version().to_string(), "1.4.2");
+    /// ```
+    pub fn bump_for_attributes(
+        &self,
+        old: &Attributes,
+        new: &Attributes,
+    ) -> serde_json::Result<Option<crate::Builder>> {
+        let diff = diff_attributes(old, new)?;
+        Ok(Bump::for_diff(&diff).map(|level| self.bump(level)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use zarrs_conventions::{ZarrConventionImpl, diff::diff_attributes};
+
+    use super::Bump;
+    use crate::DatasetVersion;
+
+    fn bump(old: serde_json::Value, new: serde_json::Value) -> Option<Bump> {
+        let diff = diff_attributes(old.as_object().unwrap(), new.as_object().unwrap()).unwrap();
+        Bump::for_diff(&diff)
+    }
+
+    #[test]
+    fn for_diff() {
+        let base = json!({"title": "Survey", "units": "m", "version": {"version": "1.0.0"}});
+        assert_eq!(bump(base.clone(), base.clone()), None);
+        assert_eq!(
+            bump(base.clone(), json!({"title": "Survey", "units": "m"})),
+            None
+        );
+        assert_eq!(
+            bump(base.clone(), json!({"title": "Survey 2024", "units": "m"})),
+            Some(Bump::Patch)
+        );
+        assert_eq!(
+            bump(
+                base.clone(),
+                json!({"title": "Survey", "units": "m", "extra": 1})
+            ),
+            Some(Bump::Minor)
+        );
+        assert_eq!(
+            bump(base.clone(), json!({"title": "Survey 2024", "extra": 1})),
+            Some(Bump::Major)
+        );
+
+        let own = serde_json::to_value(DatasetVersion::to_convention()).unwrap();
+        let other = json!({"uuid": "4a5b39dc-d49f-5fe6-9412-82b29bb78936", "name": "contacts"});
+        assert_eq!(
+            bump(json!({}), json!({"zarr_conventions": [own.clone()]})),
+            None
+        );
+        assert_eq!(
+            bump(json!({}), json!({"zarr_conventions": [other.clone()]})),
+            Some(Bump::Minor)
+        );
+        assert_eq!(
+            bump(json!({"zarr_conventions": [other, own]}), json!({})),
+            Some(Bump::Major)
+        );
+    }
+}
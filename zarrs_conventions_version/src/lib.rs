@@ -0,0 +1,198 @@
+#![doc = include_str!("../README.md")]
+use serde::{Deserialize, Serialize};
+pub use zarrs_conventions;
+use zarrs_conventions::{
+    define_convention,
+    iref::{Uri, UriBuf},
+    validate::{Issue, Lint},
+};
+
+pub mod bump;
+mod semver;
+pub use semver::{Identifier, Version, VersionError};
+
+/// The version which a dataset version replaces.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Previous {
+    /// Semantic version of the previous dataset.
+    version: Version,
+    /// Where the previous version can be found, if it is still available.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<String>", extend("format" = "uri")))]
+    href: Option<UriBuf>,
+}
+
+impl Previous {
+    pub fn new(version: Version) -> Self {
+        Self {
+            version,
+            href: None,
+        }
+    }
+
+    pub fn with_href(mut self, href: UriBuf) -> Self {
+        self.href = Some(href);
+        self
+    }
+
+    pub fn version(&self) -> &Version {
+        &self.version
+    }
+
+    pub fn href(&self) -> Option<&Uri> {
+        self.href.as_deref()
+    }
+}
+
+define_convention! {
+    /// The semantic version of a dataset, with notes on what changed since the previous version.
+    #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+    pub struct DatasetVersion {
+        /// Semantic version of the dataset, e.g. `1.4.0`.
+        version: Version,
+        /// Release notes, describing what changed since the previous version.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        notes: Option<String>,
+        /// The version which this one replaces, which must have lower precedence.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        previous: Option<Previous>,
+    }
+    schema_url = "https://raw.githubusercontent.com/clbarnes/zarrs_conventions/refs/tags/version-v1/zarrs_conventions_version/spec/schema.json",
+    spec_url = "https://github.com/clbarnes/zarrs_conventions/blob/version-v1/zarrs_conventions_version/spec/README.md",
+    name = "version",
+    description = "Semantic version of a dataset, its release notes, and the version it replaces",
+    nested = "version",
+    register = false,
+}
+
+impl DatasetVersion {
+    pub fn builder(version: Version) -> Builder {
+        Builder {
+            inner: Self {
+                version,
+                notes: None,
+                previous: None,
+            },
+        }
+    }
+
+    pub fn version(&self) -> &Version {
+        &self.version
+    }
+
+    /// Release notes, describing what changed since the previous version.
+    pub fn notes(&self) -> Option<&str> {
+        self.notes.as_deref()
+    }
+
+    pub fn previous(&self) -> Option<&Previous> {
+        self.previous.as_ref()
+    }
+}
+
+impl Lint for DatasetVersion {
+    fn lint(&self) -> Vec<Issue> {
+        let mut issues = Vec::default();
+        if let Err(e) = self.check_previous() {
+            issues.push(Issue::error("previous-not-older", e.to_string()));
+        }
+        issues
+    }
+}
+
+impl DatasetVersion {
+    fn check_previous(&self) -> Result<(), BuildError> {
+        match &self.previous {
+            Some(previous) if previous.version.cmp_precedence(&self.version).is_ge() => {
+                Err(BuildError::PreviousNotOlder {
+                    previous: previous.version.to_string(),
+                    version: self.version.to_string(),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Why a dataset version could not be built.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum BuildError {
+    #[error("previous version {previous} does not precede version {version}")]
+    PreviousNotOlder { previous: String, version: String },
+}
+
+/// Builder for [DatasetVersion], created by [DatasetVersion::builder].
+#[derive(Debug, Clone)]
+pub struct Builder {
+    inner: DatasetVersion,
+}
+
+impl Builder {
+    pub fn notes(mut self, notes: impl Into<String>) -> Self {
+        self.inner.notes = Some(notes.into());
+        self
+    }
+
+    pub fn previous(mut self, previous: Previous) -> Self {
+        self.inner.previous = Some(previous);
+        self
+    }
+
+    /// Build the version, checking that any previous version precedes it.
+    pub fn build(self) -> Result<DatasetVersion, BuildError> {
+        self.inner.check_previous()?;
+        Ok(self.inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use zarrs_conventions::validate::Lint;
+
+    use crate::{BuildError, DatasetVersion, Previous, Version};
+
+    fn v(s: &str) -> Version {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn build() {
+        let version = DatasetVersion::builder(v("1.1.0"))
+            .notes("Added the 2024 survey")
+            .previous(
+                Previous::new(v("1.0.0"))
+                    .with_href("https://example.org/data/v1.0.0/".parse().unwrap()),
+            )
+            .build()
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(&version).unwrap(),
+            json!({
+                "version": "1.1.0",
+                "notes": "Added the 2024 survey",
+                "previous": {"version": "1.0.0", "href": "https://example.org/data/v1.0.0/"},
+            })
+        );
+        assert!(version.lint().is_empty());
+
+        assert_eq!(
+            DatasetVersion::builder(v("1.0.0"))
+                .previous(Previous::new(v("1.0.0+rebuild")))
+                .build(),
+            Err(BuildError::PreviousNotOlder {
+                previous: "1.0.0+rebuild".into(),
+                version: "1.0.0".into()
+            })
+        );
+        assert!(serde_json::from_value::<DatasetVersion>(json!({"version": "1.0"})).is_err());
+        let newer: DatasetVersion = serde_json::from_value(
+            json!({"version": "1.0.0-rc.1", "previous": {"version": "1.0.0"}}),
+        )
+        .unwrap();
+        assert_eq!(newer.lint()[0].code, "previous-not-older");
+    }
+}
@@ -0,0 +1,280 @@
+//! [Semantic versions](https://semver.org/spec/v2.0.0.html).
+use std::{cmp::Ordering, fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+/// Why a string is not a semantic version.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("'{version}' is not a semantic version: {reason}")]
+pub struct VersionError {
+    pub version: String,
+    pub reason: &'static str,
+}
+
+/// A dot-separated part of a pre-release version, e.g. `alpha` or `1` in `1.0.0-alpha.1`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Identifier {
+    Numeric(u64),
+    Alphanumeric(String),
+}
+
+impl Ord for Identifier {
+    /// Numeric identifiers are ordered numerically, and before alphanumeric identifiers,
+    /// which are ordered lexically.
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Self::Numeric(a), Self::Numeric(b)) => a.cmp(b),
+            (Self::Numeric(_), Self::Alphanumeric(_)) => Ordering::Less,
+            (Self::Alphanumeric(_), Self::Numeric(_)) => Ordering::Greater,
+            (Self::Alphanumeric(a), Self::Alphanumeric(b)) => a.cmp(b),
+        }
+    }
+}
+
+impl PartialOrd for Identifier {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Identifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Numeric(n) => write!(f, "{n}"),
+            Self::Alphanumeric(s) => f.write_str(s),
+        }
+    }
+}
+
+/// A semantic version, e.g. `1.4.0` or `2.0.0-rc.1+build.5`.
+///
+/// Versions are ordered by precedence, with build metadata (which has no precedence)
+/// only used to break ties; see [Version::cmp_precedence].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(try_from = "String", into = "String")]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    pub pre: Vec<Identifier>,
+    pub build: Vec<String>,
+}
+
+fn is_identifier(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+/// A numeric part, with no leading zeros.
+fn numeric(s: &str) -> Option<u64> {
+    if s.is_empty() || (s.len() > 1 && s.starts_with('0')) || !s.bytes().all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+    s.parse().ok()
+}
+
+impl Version {
+    pub const fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+            pre: Vec::new(),
+            build: Vec::new(),
+        }
+    }
+
+    /// Parse a version.
+    ///
+    /// ```
+    /// use zarrs_conventions_version::Version;
+    ///
+    /// let v = Version::parse("1.0.0-alpha.1+sha.5114f85").unwrap();
+    /// assert_eq!((v.major, v.minor, v.patch), (1, 0, 0));
+    /// assert!(v.is_prerelease());
+    /// assert!(Version::parse("1.0").is_err());
+    /// assert!(Version::parse("01.0.0").is_err());
+    /// ```
+    pub fn parse(version: &str) -> Result<Self, VersionError> {
+        let err = |reason| VersionError {
+            version: version.to_string(),
+            reason,
+        };
+        let (rest, build) = match version.split_once('+') {
+            Some((rest, build)) => (rest, Some(build)),
+            None => (version, None),
+        };
+        let (core, pre) = match rest.split_once('-') {
+            Some((core, pre)) => (core, Some(pre)),
+            None => (rest, None),
+        };
+        let parts: Vec<_> = core.split('.').collect();
+        let [major, minor, patch] = parts[..] else {
+            return Err(err("expected MAJOR.MINOR.PATCH"));
+        };
+        let number = |s| {
+            numeric(s).ok_or_else(|| err("version numbers must be integers without leading zeros"))
+        };
+        let mut out = Self::new(number(major)?, number(minor)?, number(patch)?);
+        if let Some(pre) = pre {
+            for part in pre.split('.') {
+                if !is_identifier(part) {
+                    return Err(err("invalid pre-release identifier"));
+                }
+                out.pre.push(if part.bytes().all(|b| b.is_ascii_digit()) {
+                    Identifier::Numeric(numeric(part).ok_or_else(|| {
+                        err("numeric pre-release identifiers must not have leading zeros")
+                    })?)
+                } else {
+                    Identifier::Alphanumeric(part.to_string())
+                });
+            }
+        }
+        if let Some(build) = build {
+            for part in build.split('.') {
+                if !is_identifier(part) {
+                    return Err(err("invalid build metadata identifier"));
+                }
+                out.build.push(part.to_string());
+            }
+        }
+        Ok(out)
+    }
+
+    pub fn is_prerelease(&self) -> bool {
+        !self.pre.is_empty()
+    }
+
+    /// Compare by semantic version precedence, ignoring build metadata.
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use zarrs_conventions_version::Version;
+    ///
+    /// let v = |s| Version::parse(s).unwrap();
+    /// assert!(v("1.0.0-alpha") < v("1.0.0-alpha.1"));
+    /// assert!(v("1.0.0-beta.11") > v("1.0.0-beta.2"));
+    /// assert!(v("1.0.0-rc.1") < v("1.0.0"));
+    /// assert_eq!(v("1.0.0+a").cmp_precedence(&v("1.0.0+b")), Ordering::Equal);
+    /// ```
+    pub fn cmp_precedence(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (self.pre.is_empty(), other.pre.is_empty()) {
+                (true, true) => Ordering::Equal,
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                (false, false) => self.pre.cmp(&other.pre),
+            })
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cmp_precedence(other)
+            .then_with(|| self.build.cmp(&other.build))
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        let join = |parts: Vec<String>| parts.join(".");
+        if !self.pre.is_empty() {
+            write!(
+                f,
+                "-{}",
+                join(self.pre.iter().map(|p| p.to_string()).collect())
+            )?;
+        }
+        if !self.build.is_empty() {
+            write!(f, "+{}", join(self.build.clone()))?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Version {
+    type Err = VersionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl TryFrom<String> for Version {
+    type Error = VersionError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(&value)
+    }
+}
+
+impl From<Version> for String {
+    fn from(value: Version) -> Self {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Version;
+
+    #[test]
+    fn round_trip() {
+        for s in [
+            "0.0.0",
+            "1.2.3",
+            "1.0.0-alpha",
+            "1.0.0-0.3.7",
+            "1.0.0-x.7.z.92",
+            "1.0.0-x-y-z.--",
+            "1.0.0+20130313144700",
+            "1.0.0-beta+exp.sha.5114f85",
+        ] {
+            assert_eq!(Version::parse(s).unwrap().to_string(), s);
+        }
+        for s in [
+            "",
+            "1",
+            "1.2.3.4",
+            "1.2.-3",
+            "1.2.3-",
+            "1.2.3-01",
+            "1.2.3+",
+            "1.2.3-a..b",
+            "v1.2.3",
+        ] {
+            assert!(Version::parse(s).is_err(), "{s}");
+        }
+    }
+
+    #[test]
+    fn precedence() {
+        let ordered = [
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-alpha.beta",
+            "1.0.0-beta",
+            "1.0.0-beta.2",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "1.0.0",
+            "2.0.0",
+            "2.1.0",
+            "2.1.1",
+        ];
+        let versions: Vec<_> = ordered.iter().map(|s| Version::parse(s).unwrap()).collect();
+        let mut sorted = versions.clone();
+        sorted.reverse();
+        sorted.sort();
+        assert_eq!(sorted, versions);
+    }
+}
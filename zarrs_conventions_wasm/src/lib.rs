@@ -0,0 +1,228 @@
+#![doc = include_str!("../README.md")]
+use std::sync::Once;
+
+use serde::Serialize;
+use serde_json::Value;
+use wasm_bindgen::prelude::*;
+use zarrs_conventions::{
+    Attributes, AttributesBuilder, AttributesParser, Convention, DEFAULT_ZARR_CONVENTION_REGISTRY,
+    ZarrConventionImpl,
+    validate::{DynConvention, Issue, check_attributes, declarations},
+};
+use zarrs_conventions_access::Access;
+use zarrs_conventions_authors::Authors;
+use zarrs_conventions_axes::Axes;
+use zarrs_conventions_channels::Channels;
+use zarrs_conventions_checksum::Checksum;
+use zarrs_conventions_contact::Contacts;
+use zarrs_conventions_display::DisplaySettings;
+use zarrs_conventions_extent::Extent;
+use zarrs_conventions_labels::LabelMap;
+use zarrs_conventions_license::License;
+use zarrs_conventions_links::Links;
+use zarrs_conventions_names::Names;
+use zarrs_conventions_proj::Proj;
+use zarrs_conventions_qc::QcFlags;
+use zarrs_conventions_sources::Sources;
+use zarrs_conventions_stats::Stats;
+use zarrs_conventions_thumbnails::Thumbnails;
+use zarrs_conventions_timeaxis::TimeAxis;
+use zarrs_conventions_timestamps::Timestamps;
+use zarrs_conventions_transform::CoordinateTransform;
+use zarrs_conventions_uom::UnitOfMeasurement;
+use zarrs_conventions_version::DatasetVersion;
+
+/// All conventions compiled into this module.
+fn known() -> [DynConvention; 22] {
+    [
+        DynConvention::nested::<License>().with_lint::<License>(),
+        DynConvention::either::<Proj>().with_lint::<Proj>(),
+        DynConvention::nested::<Thumbnails>(),
+        DynConvention::nested::<UnitOfMeasurement>().with_lint::<UnitOfMeasurement>(),
+        DynConvention::nested::<CoordinateTransform>().with_lint::<CoordinateTransform>(),
+        DynConvention::nested::<Axes>().with_lint::<Axes>(),
+        DynConvention::nested::<Authors>().with_lint::<Authors>(),
+        DynConvention::nested::<Checksum>().with_lint::<Checksum>(),
+        DynConvention::nested::<Timestamps>().with_lint::<Timestamps>(),
+        DynConvention::nested::<DisplaySettings>().with_lint::<DisplaySettings>(),
+        DynConvention::nested::<Names>().with_lint::<Names>(),
+        DynConvention::nested::<Contacts>().with_lint::<Contacts>(),
+        DynConvention::nested::<Links>().with_lint::<Links>(),
+        DynConvention::nested::<DatasetVersion>().with_lint::<DatasetVersion>(),
+        DynConvention::nested::<Access>().with_lint::<Access>(),
+        DynConvention::nested::<QcFlags>().with_lint::<QcFlags>(),
+        DynConvention::nested::<Stats>().with_lint::<Stats>(),
+        DynConvention::nested::<Extent>().with_lint::<Extent>(),
+        DynConvention::nested::<TimeAxis>().with_lint::<TimeAxis>(),
+        DynConvention::nested::<Channels>().with_lint::<Channels>(),
+        DynConvention::nested::<LabelMap>().with_lint::<LabelMap>(),
+        DynConvention::nested::<Sources>().with_lint::<Sources>(),
+    ]
+}
+
+/// Register the compiled-in conventions,
+/// which happens before `main` on other targets but must be done explicitly on wasm.
+fn ensure_registered() {
+    static REGISTER: Once = Once::new();
+
+    fn register<T: ZarrConventionImpl>() {
+        if !DEFAULT_ZARR_CONVENTION_REGISTRY.contains(T::DEFINITION.uuid) {
+            DEFAULT_ZARR_CONVENTION_REGISTRY
+                .register::<T>()
+                .expect("compiled-in conventions should not conflict");
+        }
+    }
+
+    REGISTER.call_once(|| {
+        register::<License>();
+        register::<Proj>();
+        register::<Thumbnails>();
+        register::<UnitOfMeasurement>();
+        register::<Sources>();
+        register::<LabelMap>();
+        register::<Channels>();
+        register::<TimeAxis>();
+        register::<Extent>();
+        register::<Stats>();
+        register::<QcFlags>();
+        register::<Access>();
+        register::<DatasetVersion>();
+        register::<Links>();
+        register::<Contacts>();
+        register::<Names>();
+        register::<DisplaySettings>();
+        register::<Timestamps>();
+        register::<Checksum>();
+        register::<Authors>();
+        register::<Axes>();
+        register::<CoordinateTransform>();
+    });
+}
+
+fn from_json<T: serde::de::DeserializeOwned>(what: &str, json: &str) -> Result<T, String> {
+    serde_json::from_str(json).map_err(|e| format!("invalid {what}: {e}"))
+}
+
+fn to_json(value: &impl Serialize) -> Result<String, String> {
+    serde_json::to_string(value).map_err(|e| e.to_string())
+}
+
+#[derive(Debug, Serialize)]
+struct Parsed {
+    conventions: Vec<Convention>,
+    payloads: Attributes,
+}
+
+fn parse_impl(attributes: &str) -> Result<String, String> {
+    ensure_registered();
+    let attrs: Attributes = from_json("attributes", attributes)?;
+    let conventions = declarations(&attrs).map_err(|e| e.to_string())?;
+    let parser: AttributesParser =
+        serde_json::from_value(Value::Object(attrs)).map_err(|e| e.to_string())?;
+    let mut payloads = Attributes::default();
+    for conv in known() {
+        let name = conv.definition.name;
+        if let Some(value) = conv
+            .parse(&parser)
+            .map_err(|e| format!("invalid {name} payload: {e}"))?
+        {
+            payloads.insert(name.to_string(), value);
+        }
+    }
+    to_json(&Parsed {
+        conventions,
+        payloads,
+    })
+}
+
+fn validate_impl(attributes: &str) -> Result<String, String> {
+    ensure_registered();
+    let attrs: Attributes = from_json("attributes", attributes)?;
+    let issues: Vec<Issue> = check_attributes(&attrs, &known());
+    to_json(&issues)
+}
+
+fn build_impl(payloads: &str, attributes: Option<&str>) -> Result<String, String> {
+    ensure_registered();
+    let payloads: Attributes = from_json("payloads", payloads)?;
+    let attrs = attributes
+        .map(|a| from_json("attributes", a))
+        .transpose()?
+        .unwrap_or_default();
+    let mut builder = AttributesBuilder::from_attributes(attrs);
+    let known = known();
+    for (name, payload) in &payloads {
+        let conv = known
+            .iter()
+            .find(|k| k.definition.name == name)
+            .ok_or_else(|| format!("unknown convention '{name}'"))?;
+        conv.add(&mut builder, payload)
+            .map_err(|e| format!("invalid {name} payload: {e}"))?;
+    }
+    to_json(&builder.build().map_err(|e| e.to_string())?)
+}
+
+/// Parse the declared conventions and known payloads from a JSON attributes object.
+///
+/// Returns a JSON object with `conventions` (the declarations)
+/// and `payloads` (validated payloads keyed by convention name).
+#[wasm_bindgen]
+pub fn parse(attributes: &str) -> Result<String, JsError> {
+    parse_impl(attributes).map_err(|e| JsError::new(&e))
+}
+
+/// Check a JSON attributes object for problems with its conventional metadata.
+///
+/// Returns a JSON list of issues, each with `severity`, `code`, and `message`.
+#[wasm_bindgen]
+pub fn validate(attributes: &str) -> Result<String, JsError> {
+    validate_impl(attributes).map_err(|e| JsError::new(&e))
+}
+
+/// Add payloads, given as a JSON object keyed by convention name,
+/// to a JSON attributes object (or an empty one), declaring their conventions.
+///
+/// Returns the JSON attributes object.
+#[wasm_bindgen]
+pub fn build(payloads: &str, attributes: Option<String>) -> Result<String, JsError> {
+    build_impl(payloads, attributes.as_deref()).map_err(|e| JsError::new(&e))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::{build_impl, parse_impl, validate_impl};
+
+    #[test]
+    fn roundtrip() {
+        let attrs = build_impl(
+            &json!({"license": {"spdx": "MIT"}, "uom": {"ucum": {"unit": "m"}}}).to_string(),
+            Some(&json!({"other": 1}).to_string()),
+        )
+        .unwrap();
+        let value: Value = serde_json::from_str(&attrs).unwrap();
+        assert_eq!(value["other"], 1);
+        assert_eq!(value["zarr_conventions"].as_array().unwrap().len(), 2);
+
+        let parsed: Value = serde_json::from_str(&parse_impl(&attrs).unwrap()).unwrap();
+        assert_eq!(parsed["payloads"]["license"], json!({"spdx": "MIT"}));
+        assert_eq!(parsed["payloads"]["uom"]["ucum"]["unit"], "m");
+        assert_eq!(parsed["conventions"].as_array().unwrap().len(), 2);
+
+        let issues: Value = serde_json::from_str(&validate_impl(&attrs).unwrap()).unwrap();
+        assert_eq!(issues, json!([]));
+    }
+
+    #[test]
+    fn errors() {
+        assert!(build_impl(&json!({"nothing": {}}).to_string(), None).is_err());
+        assert!(build_impl(&json!({"license": {}}).to_string(), None).is_err());
+        assert!(parse_impl("[]").is_err());
+        let issues: Value = serde_json::from_str(
+            &validate_impl(&json!({"license": {"spdx": "MIT"}}).to_string()).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(issues[0]["code"], "undeclared-payload");
+    }
+}